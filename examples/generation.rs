@@ -0,0 +1,26 @@
+//! Trains a generative network against a distinguishing network on a few simple patterns, then samples from it
+//!
+//! `cargo run --example generation`
+
+use std::fs;
+use darjeeling::{activation::ActivationFunction, config::{GanConfig, TrainingConfig}, generation::GenNetwork, input::Input};
+
+fn main() {
+    // Every pattern counts up by one, so there's something for the generator to pick up on
+    let mut data: Vec<Input> = (0..8).map(|start| {
+        Input::new((0..4).map(|i| (start + i) as f32 / 12.0).collect(), None)
+    }).collect();
+
+    let mut net = GenNetwork::new(4, 6, 4, 1, ActivationFunction::Sigmoid);
+    let config = GanConfig::default()
+        .with_cycles(3)
+        .with_discriminator(TrainingConfig::default().with_hidden_num(4).with_target_err_percent(60.0));
+    let model_name: String = net.learn_with_config(&mut data, &config, "example_generation")
+        .expect("The model can be written to the working directory");
+    // The example doesn't need to keep the model around
+    let _ = fs::remove_file(&model_name);
+
+    for sample in net.test(&data).expect("Testing doesn't fail") {
+        println!("{:?} realism {:.3}", sample.output, sample.realism.unwrap_or_default());
+    }
+}
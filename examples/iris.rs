@@ -0,0 +1,38 @@
+//! Classifies a handful of iris flowers from `examples/data/iris.csv`, holding some back to test on
+//!
+//! `cargo run --example iris`
+
+use darjeeling::{
+    activation::ActivationFunction,
+    categorize::CatNetwork,
+    csv,
+    input::Input,
+    scaler::Scaler,
+    types::Types
+};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+fn main() {
+    let mut data: Vec<Input> = csv::parse_csv(include_str!("data/iris.csv"), true, Some(4)).expect("The bundled data is valid");
+    // The same seed always holds back the same flowers
+    data.shuffle(&mut StdRng::seed_from_u64(7));
+    let mut train: Vec<Input> = data.split_off(9);
+    let test: Vec<Input> = data;
+
+    let categories: Vec<Types> = ["setosa", "versicolor", "virginica"].iter().map(|name| Types::String(name.to_string())).collect();
+    let mut net = CatNetwork::new(4, 8, 3, 1, ActivationFunction::Sigmoid);
+    net.set_scaler(Some(Scaler::fit_min_max(&train)));
+    net.start_training(categories);
+    for _ in 0..500 {
+        let record = net.step_epoch(&mut train, 0.5).expect("Nothing is written while training");
+        if record.accuracy == 1.0 {
+            break;
+        }
+    }
+
+    println!("Training: {}", net.evaluate(&train));
+    println!("Held back: {}", net.evaluate(&test));
+    for input in &test {
+        println!("{} -> {} (really {})", input, net.predict_category(&input.inputs), input.answer.as_ref().expect("Every row is labeled"));
+    }
+}
@@ -0,0 +1,28 @@
+//! Fits one period of a sine wave, using a single answer node as a regression output
+//!
+//! `cargo run --example sine`
+
+use std::f32::consts::TAU;
+use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, input::Input, types::Types};
+
+fn main() {
+    // Sigmoid outputs are between 0 and 1, so the wave is squashed to fit
+    let mut data: Vec<Input> = (0..40).map(|i| {
+        let x: f32 = i as f32 / 40.0;
+        Input::new(vec![x], Some(Types::Vector(vec![((x * TAU).sin() + 1.0) / 2.0])))
+    }).collect();
+
+    let mut net = CatNetwork::new(1, 10, 1, 1, ActivationFunction::Sigmoid);
+    net.start_training(vec![Types::String("sine".to_string())]);
+    for epoch in 1..=3000 {
+        let record = net.step_epoch(&mut data, 0.5).expect("Nothing is written while training");
+        if epoch % 500 == 0 {
+            println!("Epoch {}: loss {}", record.epoch, record.loss);
+        }
+    }
+
+    println!("{}", net.evaluate_regression(&data));
+    for x in [0.0, 0.25, 0.5, 0.75] {
+        println!("sin({:.2}) = {:.3}, predicted {:.3}", x * TAU, (x * TAU).sin(), net.inspect(&[x]).answers()[0] * 2.0 - 1.0);
+    }
+}
@@ -0,0 +1,31 @@
+//! Learns an XOR gate from the four rows in `examples/data/xor.csv`
+//!
+//! `cargo run --example xor`
+
+use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, csv, input::Input, types::Types};
+
+fn main() {
+    let mut data: Vec<Input> = csv::parse_csv(include_str!("data/xor.csv"), true, Some(2)).expect("The bundled data is valid");
+    let categories: Vec<Types> = vec![Types::String("0".to_string()), Types::String("1".to_string())];
+
+    // Weights start out random, so a net can get stuck; a few restarts are usually enough
+    for attempt in 1..=5 {
+        let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+        net.start_training(categories.clone());
+        for _ in 0..2000 {
+            let record = net.step_epoch(&mut data, 0.5).expect("Nothing is written while training");
+            if record.accuracy == 1.0 {
+                break;
+            }
+        }
+
+        let report = net.evaluate(&data);
+        println!("Attempt {}: {}", attempt, report);
+        if report.metrics.accuracy == 1.0 {
+            for input in &data {
+                println!("{} -> {}", input, net.predict_category(&input.inputs));
+            }
+            return;
+        }
+    }
+}
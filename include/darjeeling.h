@@ -0,0 +1,46 @@
+/* C interface to darjeeling categorization networks, matching src/ffi.rs */
+#ifndef DARJEELING_H
+#define DARJEELING_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The inputs or outputs pointer was null */
+#define DARJ_NULL_POINTER -1
+/* The number of inputs or outputs didn't match the network */
+#define DARJ_WRONG_SIZE -2
+/* The network panicked while predicting */
+#define DARJ_PANICKED -3
+
+#define DARJ_SIGMOID 0
+#define DARJ_TANH 1
+#define DARJ_LINEAR 2
+
+typedef struct DarjNetwork DarjNetwork;
+
+/* Creates a network with random weights, or returns null if a size isn't positive or the activation isn't known */
+DarjNetwork *darj_create(int inputs, int hidden, int answers, int hidden_layers, int activation);
+
+/* Reads a network from a .darj file, or returns null if it couldn't be read or reading it panicked */
+DarjNetwork *darj_load(const char *path);
+
+/* Writes every answer node's output into outputs, returning the index of the brightest one or a negative DARJ_ error code */
+int darj_predict(const DarjNetwork *net, const float *inputs, size_t input_len, float *outputs, size_t output_len);
+
+/* The number of inputs the network takes, or 0 if it's null */
+size_t darj_input_size(const DarjNetwork *net);
+
+/* The number of answer nodes, or 0 if the network is null */
+size_t darj_output_size(const DarjNetwork *net);
+
+/* Frees a network. Passing null does nothing. */
+void darj_free(DarjNetwork *net);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
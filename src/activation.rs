@@ -1,7 +1,9 @@
 use core::fmt;
+use std::str::FromStr;
 use serde::{Serialize, Deserialize};
+use crate::error::DarjeelingError;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum ActivationFunction {
     Sigmoid,
     Tanh,
@@ -9,6 +11,50 @@ pub enum ActivationFunction {
     // Step,
 }
 
+impl ActivationFunction {
+
+    /// Every activation function, in the order they're listed in error messages
+    pub fn all() -> [ActivationFunction; 3] {
+        [ActivationFunction::Sigmoid, ActivationFunction::Tanh, ActivationFunction::Linear]
+    }
+}
+
+impl FromStr for ActivationFunction {
+    type Err = DarjeelingError;
+
+    /// Reads the name an activation function is displayed and saved with, ignoring case and surrounding whitespace
+    ///
+    /// ## Err
+    /// - ### UnknownActivationFunction
+    /// The name isn't one of [`all`](fn@ActivationFunction::all)
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::activation::ActivationFunction;
+    ///
+    /// assert_eq!(" Tanh".parse::<ActivationFunction>().unwrap(), ActivationFunction::Tanh);
+    /// assert!("relu".parse::<ActivationFunction>().is_err());
+    /// assert!(ActivationFunction::all().iter().all(|function| function.to_string().parse::<ActivationFunction>().unwrap() == *function));
+    /// ```
+    fn from_str(name: &str) -> Result<ActivationFunction, DarjeelingError> {
+        let name: String = name.trim().to_lowercase();
+        match ActivationFunction::all().into_iter().find(|function| function.to_string() == name) {
+            Some(function) => Ok(function),
+            None => Err(DarjeelingError::UnknownActivationFunction(format!("{:?}, expected one of {}", name,
+                ActivationFunction::all().map(|function| function.to_string()).join(", ")
+            )))
+        }
+    }
+}
+
+impl TryFrom<&str> for ActivationFunction {
+    type Error = DarjeelingError;
+
+    fn try_from(name: &str) -> Result<ActivationFunction, DarjeelingError> {
+        name.parse()
+    }
+}
+
 impl fmt::Display for ActivationFunction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -0,0 +1,457 @@
+use rand::{Rng, seq::SliceRandom};
+use serde::{Serialize, Deserialize};
+use crate::{
+    augment::gaussian,
+    categorize::CatNetwork,
+    input::Input,
+    types::Types
+};
+
+/// What the generation model learns from each cycle of adversarial training
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum AdversarialLoss {
+    /// The mean squared error of the distinguishing model, which stops changing once it's sure of every sample
+    #[default]
+    Standard,
+    /// The gap between the distinguishing model's average score for real and generated samples, as in a Wasserstein GAN.
+    /// The distinguishing model is a critic: instead of learning which samples are real, it learns to widen the gap,
+    /// for [`CRITIC_EPOCHS`](constant@CRITIC_EPOCHS) passes over the samples, with its weights clipped to `-clip..=clip` after every update.
+    /// It still gives the generation model something to learn from when the distinguishing model is winning.
+    /// Sigmoid and tanh squash the scores, so a linear distinguishing model with a small learning rate gives a wider gap.
+    /// The distinguishing model's target error percent isn't used.
+    Wasserstein { clip: f32 },
+    /// How far apart the average outputs of the distinguishing model's last hidden layer are for real and generated samples, as in Salimans et al.
+    /// The generation model learns to make samples whose features look like real data's on average, instead of fooling the distinguishing model outright,
+    /// which is steadier on sparse data where the distinguishing model quickly becomes sure of every sample.
+    /// A distinguishing model without hidden layers is matched on its answer layer instead.
+    /// Each of the generation model's outputs learns from its own signal, the feature difference carried back through the distinguishing model,
+    /// while each cycle's loss is the distance itself.
+    FeatureMatching
+}
+
+/// How many times a Wasserstein critic passes over the samples each cycle
+pub const CRITIC_EPOCHS: usize = 5;
+
+/// Trains a new critic for the [Wasserstein loss](AdversarialLoss::Wasserstein) on real samples answered Boolean(true) and generated ones answered Boolean(false)
+pub(crate) fn train_critic<R: Rng>(critic: &mut CatNetwork, samples: &mut [Input], learning_rate: f32, clip: f32, rng: &mut R) {
+    critic.start_training(vec![Types::Boolean(true), Types::Boolean(false)]);
+    critic.clip_weights(clip);
+    (0..CRITIC_EPOCHS).for_each(|_| {
+        samples.shuffle(rng);
+        samples.iter().for_each(|sample| critic.critic_step(sample, learning_rate, clip));
+    });
+}
+
+/// Estimates the Wasserstein distance between the real and generated samples,
+/// as the mean critic score of real samples minus the mean of generated ones.
+/// A sample's critic score is its real answer node's output minus its generated answer node's output.
+pub(crate) fn wasserstein_estimate(critic: &CatNetwork, samples: &[Input]) -> f32 {
+    let (mut real_sum, mut real_count, mut generated_sum, mut generated_count) = (0.0, 0.0, 0.0, 0.0);
+    samples.iter().for_each(|sample| {
+        let prediction = critic.predict(&sample.inputs);
+        let score: f32 = prediction.score(&Types::Boolean(true)).unwrap_or(0.0) - prediction.score(&Types::Boolean(false)).unwrap_or(0.0);
+        match sample.answer {
+            Some(Types::Boolean(true)) => {
+                real_sum += score;
+                real_count += 1.0;
+            },
+            Some(Types::Boolean(false)) => {
+                generated_sum += score;
+                generated_count += 1.0;
+            },
+            _ => {}
+        }
+    });
+    let mean = |sum: f32, count: f32| if count == 0.0 { 0.0 } else { sum / count };
+    mean(real_sum, real_count) - mean(generated_sum, generated_count)
+}
+
+/// The mean squared difference between the average feature of real samples and of generated ones,
+/// where the features are the outputs of the critic's last hidden layer
+pub(crate) fn feature_matching_distance(critic: &CatNetwork, samples: &[Input]) -> f32 {
+    match feature_means(critic, samples) {
+        Some((real, generated, _layer)) => real.iter().zip(&generated).map(|(real, generated)| (real - generated).powi(2)).sum::<f32>() / real.len() as f32,
+        None => 0.0
+    }
+}
+
+/// How much, and which way, each of the generation model's outputs should move to close the [feature matching distance](feature_matching_distance).
+/// The difference between the average real and generated features is carried back through the critic to its inputs,
+/// which are the generation model's outputs, and averaged over the generated samples. Positive means the output should grow.
+pub(crate) fn feature_matching_signals(critic: &CatNetwork, samples: &[Input]) -> Vec<f32> {
+    let mut signals: Vec<f32> = vec![0.0; critic.input_size()];
+    let (real, generated, layer) = match feature_means(critic, samples) {
+        Some(means) => means,
+        None => return signals
+    };
+    // The distance's slope with respect to each generated feature, pointing downhill
+    let features: f32 = real.len() as f32;
+    let upstream: Vec<f32> = real.iter().zip(&generated).map(|(real, generated)| 2.0 * (real - generated) / features).collect();
+    let generated_samples: Vec<&Input> = samples.iter().filter(|sample| sample.answer == Some(Types::Boolean(false))).collect();
+    generated_samples.iter().for_each(|sample| {
+        let gradients: Vec<f32> = critic.layer_input_gradients(&sample.inputs, layer, &upstream);
+        signals.iter_mut().zip(gradients).for_each(|(signal, gradient)| *signal += gradient / generated_samples.len() as f32);
+    });
+    signals
+}
+
+/// The average features of the real and of the generated samples, and the critic's layer they come from.
+/// None if either kind of sample is missing.
+fn feature_means(critic: &CatNetwork, samples: &[Input]) -> Option<(Vec<f32>, Vec<f32>, usize)> {
+    let (mut real, mut generated): (Vec<f32>, Vec<f32>) = (vec![], vec![]);
+    let (mut real_count, mut generated_count) = (0.0, 0.0);
+    let mut layer: usize = 1;
+    samples.iter().for_each(|sample| {
+        let snapshot = critic.inspect(&sample.inputs);
+        // The last hidden layer, or the answer layer right after the sensors without one
+        layer = snapshot.hidden().len().max(1);
+        let features: &[f32] = snapshot.hidden().last().map(|layer| layer.as_slice()).unwrap_or_else(|| snapshot.answers());
+        let (sums, count) = match sample.answer {
+            Some(Types::Boolean(true)) => (&mut real, &mut real_count),
+            Some(Types::Boolean(false)) => (&mut generated, &mut generated_count),
+            _ => return
+        };
+        sums.resize(features.len(), 0.0);
+        sums.iter_mut().zip(features).for_each(|(sum, feature)| *sum += feature);
+        *count += 1.0;
+    });
+    if real_count == 0.0 || generated_count == 0.0 || real.is_empty() {
+        return None;
+    }
+    real.iter_mut().for_each(|sum| *sum /= real_count);
+    generated.iter_mut().for_each(|sum| *sum /= generated_count);
+    Some((real, generated, layer))
+}
+
+/// How the feedback of every distinguishing model in an [ensemble](DiscriminatorEnsemble) is combined into what the generation model learns from.
+/// The harshest distinguishing model is the one that tells real and generated samples apart best,
+/// with the lowest mse for the [standard loss](AdversarialLoss::Standard), the widest gap for the [Wasserstein loss](AdversarialLoss::Wasserstein),
+/// and the farthest apart features for [feature matching](AdversarialLoss::FeatureMatching).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum Aggregation {
+    /// The average feedback, which smooths out any one distinguishing model overpowering the generation model
+    #[default]
+    Mean,
+    /// The middle feedback, which ignores a distinguishing model that's much harsher or softer than the rest
+    Median,
+    /// The harshest distinguishing model's feedback, so the generation model always answers to the one it fools least
+    Harshest,
+    /// Feedback weighted by how harsh each distinguishing model is, as in GMAN.
+    /// 0 is the same as the mean, and it gets closer to the harshest as it grows.
+    Softmax { temperature: f32 }
+}
+
+impl Aggregation {
+
+    /// Combines the feedback of every distinguishing model, for the loss it was measured with
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::adversarial::{Aggregation, AdversarialLoss};
+    ///
+    /// let mse = [0.1, 0.3, 0.8];
+    /// assert_eq!(Aggregation::Median.aggregate(&mse, &AdversarialLoss::Standard), 0.3);
+    /// assert_eq!(Aggregation::Harshest.aggregate(&mse, &AdversarialLoss::Standard), 0.1);
+    /// let gaps = [0.1, 0.3, 0.8];
+    /// assert_eq!(Aggregation::Harshest.aggregate(&gaps, &AdversarialLoss::Wasserstein { clip: 0.1 }), 0.8);
+    /// ```
+    pub fn aggregate(&self, feedback: &[f32], loss: &AdversarialLoss) -> f32 {
+        feedback.iter().zip(self.weights(feedback, loss)).map(|(value, weight)| value * weight).sum()
+    }
+
+    /// How much each distinguishing model's feedback counts towards the [combined](fn@Aggregation::aggregate) feedback, adding up to 1.
+    /// [Feature matching](AdversarialLoss::FeatureMatching) combines every distinguishing model's signal for each output with the same weights.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::adversarial::{Aggregation, AdversarialLoss};
+    ///
+    /// let mse = [0.1, 0.3, 0.8];
+    /// assert_eq!(Aggregation::Harshest.weights(&mse, &AdversarialLoss::Standard), vec![1.0, 0.0, 0.0]);
+    /// assert_eq!(Aggregation::Median.weights(&[0.4, 0.1, 0.3, 0.8], &AdversarialLoss::Standard), vec![0.5, 0.0, 0.5, 0.0]);
+    /// ```
+    pub fn weights(&self, feedback: &[f32], loss: &AdversarialLoss) -> Vec<f32> {
+        let mut weights: Vec<f32> = vec![0.0; feedback.len()];
+        if feedback.is_empty() {
+            return weights;
+        }
+        // Harsher is a smaller mse, but a wider Wasserstein gap or feature distance
+        let sign: f32 = match loss {
+            AdversarialLoss::Standard => -1.0,
+            AdversarialLoss::Wasserstein { .. } | AdversarialLoss::FeatureMatching => 1.0
+        };
+        let harshest: f32 = feedback.iter().map(|value| sign * value).fold(f32::NEG_INFINITY, f32::max);
+        match *self {
+            Aggregation::Mean => weights.iter_mut().for_each(|weight| *weight = 1.0 / feedback.len() as f32),
+            Aggregation::Median => {
+                let mut sorted: Vec<usize> = (0..feedback.len()).collect();
+                sorted.sort_by(|a, b| feedback[*a].total_cmp(&feedback[*b]));
+                let middle: usize = sorted.len() / 2;
+                if sorted.len() % 2 == 0 {
+                    weights[sorted[middle - 1]] = 0.5;
+                    weights[sorted[middle]] = 0.5;
+                } else {
+                    weights[sorted[middle]] = 1.0;
+                }
+            },
+            Aggregation::Harshest => {
+                let index: usize = feedback.iter().position(|value| sign * value == harshest).unwrap_or(0);
+                weights[index] = 1.0;
+            },
+            Aggregation::Softmax { temperature } => {
+                // Shifted by the harshest so the exponents can't overflow
+                weights.iter_mut().zip(feedback).for_each(|(weight, value)| *weight = (temperature * (sign * value - harshest)).exp());
+                let total: f32 = weights.iter().sum();
+                weights.iter_mut().for_each(|weight| *weight /= total);
+            }
+        }
+        weights
+    }
+}
+
+/// Trains several distinguishing models each cycle of adversarial training instead of one, and combines their feedback,
+/// which keeps the generation model from chasing whichever way a single distinguishing model happens to lean and oscillating.
+/// Each one starts from its own random weights, and trains on its own draw of [instance noise](InstanceNoise) if there is any.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::adversarial::{Aggregation, DiscriminatorEnsemble};
+///
+/// let ensemble = DiscriminatorEnsemble::new(3, Aggregation::Softmax { temperature: 2.0 });
+/// assert_eq!(ensemble.count, 3);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct DiscriminatorEnsemble {
+    /// The number of distinguishing models trained each cycle
+    pub count: usize,
+    pub aggregation: Aggregation
+}
+
+impl DiscriminatorEnsemble {
+
+    pub fn new(count: usize, aggregation: Aggregation) -> DiscriminatorEnsemble {
+        DiscriminatorEnsemble { count, aggregation }
+    }
+}
+
+/// Noise added to what the distinguishing model trains on during adversarial training,
+/// so it can't tell real and generated data apart too easily and overpower the generation model.
+/// The noise fades each cycle, so the distinguishing model sharpens up as the generation model improves.
+///
+/// Flipped labels cap how accurate the distinguishing model can get,
+/// so keep its target error percent under `(1 - label_flip) * 100`.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::adversarial::InstanceNoise;
+///
+/// let noise = InstanceNoise::new(0.1, 0.05, 0.9);
+/// assert_eq!(noise.at_cycle(0), noise);
+/// assert!((noise.at_cycle(2).std_dev - 0.081).abs() < 0.0001);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct InstanceNoise {
+    /// The standard deviation of the normally distributed noise added to every value of every sample
+    pub std_dev: f32,
+    /// The chance a sample's real or generated label is swapped
+    pub label_flip: f32,
+    /// What both are multiplied by after every cycle, 1.0 to never fade
+    pub decay: f32
+}
+
+impl InstanceNoise {
+
+    pub fn new(std_dev: f32, label_flip: f32, decay: f32) -> InstanceNoise {
+        InstanceNoise { std_dev, label_flip, decay }
+    }
+
+    /// The noise after it's faded for some cycles
+    pub fn at_cycle(&self, cycle: usize) -> InstanceNoise {
+        let factor: f32 = self.decay.powi(cycle as i32);
+        InstanceNoise { std_dev: self.std_dev * factor, label_flip: self.label_flip * factor, decay: self.decay }
+    }
+
+    /// Adds noise to the values of every sample, and flips some Boolean answers
+    pub fn apply<R: Rng>(&self, samples: &mut [Input], rng: &mut R) {
+        samples.iter_mut().for_each(|sample| {
+            if self.std_dev > 0.0 {
+                sample.inputs.iter_mut().for_each(|value| *value += gaussian(rng) * self.std_dev);
+            }
+            if let Some(Types::Boolean(real)) = sample.answer {
+                if rng.gen::<f32>() < self.label_flip {
+                    sample.answer = Some(Types::Boolean(!real));
+                }
+            }
+        });
+    }
+}
+
+/// Generated samples kept from earlier cycles of adversarial training and mixed into what the distinguishing model trains on,
+/// as in Shrivastava et al. The distinguishing model doesn't forget what the generation model used to make,
+/// so the generation model can't fool it by going back to old outputs.
+/// At most `capacity` samples are kept, picked by reservoir sampling so every earlier cycle is equally likely to be in the buffer.
+/// Saved in [bundles](fn@crate::generation::GenNetwork::to_bundle_string), so resumed training carries on with it.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::adversarial::ReplayBuffer;
+///
+/// let mut buffer = ReplayBuffer::new(2, 0.5);
+/// buffer.add(&[vec![0.1], vec![0.2], vec![0.3]], &mut rand::thread_rng());
+/// assert_eq!(buffer.len(), 2);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplayBuffer {
+    /// The most samples the buffer holds
+    pub capacity: usize,
+    /// The share of each cycle's generated samples the distinguishing model sees from the buffer instead, from 0 to 1
+    pub ratio: f32,
+    #[serde(default)]
+    samples: Vec<Vec<f32>>,
+    /// Every sample ever added, for reservoir sampling
+    #[serde(default)]
+    seen: usize
+}
+
+impl ReplayBuffer {
+
+    pub fn new(capacity: usize, ratio: f32) -> ReplayBuffer {
+        ReplayBuffer { capacity, ratio: ratio.clamp(0.0, 1.0), samples: vec![], seen: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn samples(&self) -> &[Vec<f32>] {
+        &self.samples
+    }
+
+    /// Empties the buffer, keeping its capacity and ratio
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.seen = 0;
+    }
+
+    /// Keeps some of the generated outputs, replacing older ones once the buffer is full
+    pub fn add<R: Rng>(&mut self, generated: &[Vec<f32>], rng: &mut R) {
+        generated.iter().for_each(|sample| {
+            self.seen += 1;
+            if self.samples.len() < self.capacity {
+                self.samples.push(sample.clone());
+            } else {
+                let slot: usize = rng.gen_range(0..self.seen);
+                if slot < self.capacity {
+                    self.samples[slot] = sample.clone();
+                }
+            }
+        });
+    }
+
+    /// Swaps the values of the ratio of generated samples, the ones answered Boolean(false), for random ones from the buffer.
+    /// Does nothing while the buffer is empty.
+    pub fn mix<R: Rng>(&self, samples: &mut [Input], rng: &mut R) {
+        if self.samples.is_empty() {
+            return;
+        }
+        samples.iter_mut()
+            .filter(|sample| sample.answer == Some(Types::Boolean(false)))
+            .for_each(|sample| {
+                if rng.gen::<f32>() < self.ratio {
+                    sample.inputs = self.samples[rng.gen_range(0..self.samples.len())].clone();
+                }
+            });
+    }
+}
+
+/// How one cycle of adversarial training went
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CycleRecord {
+    /// Starts at 1
+    pub cycle: usize,
+    /// What the generation model learned from, depending on the [loss](AdversarialLoss)
+    pub loss: f32,
+    /// The average distance between two generated outputs, see [`diversity`](fn@diversity)
+    pub diversity: f32,
+    /// Whether the diversity fell under the collapse threshold, meaning the outputs are nearly all the same
+    pub collapsed: bool,
+    /// The largest difference between the feedback of two distinguishing models in the [ensemble](DiscriminatorEnsemble), 0 with only one
+    #[serde(default)]
+    pub spread: f32,
+    /// How often the distinguishing model told real and generated samples apart, averaged over the ensemble.
+    /// It's measured on a quarter of each cycle's real and generated samples, which are held out of the distinguishing model's training.
+    /// Near 0.5 it's guessing, which is the equilibrium [`EquilibriumStopping`] waits for.
+    #[serde(default)]
+    pub discriminator_accuracy: f32,
+    /// The learning rates the generation and distinguishing models trained with, after their [schedules](fn@crate::generation::GenNetwork::set_schedules)
+    #[serde(default)]
+    pub learning_rate: f32,
+    #[serde(default)]
+    pub discriminator_learning_rate: f32
+}
+
+/// Stops adversarial training before the last cycle once the distinguishing model has hovered near 50% accuracy for a while,
+/// meaning it can't tell real samples from generated ones and more cycles won't teach the generation model much
+///
+/// ## Examples
+/// ```
+/// use darjeeling::adversarial::{CycleRecord, EquilibriumStopping};
+///
+/// let stopping = EquilibriumStopping::new(0.05, 2);
+/// let cycle = |cycle: usize, discriminator_accuracy: f32| CycleRecord {
+///     cycle, loss: 0.0, diversity: 1.0, collapsed: false, spread: 0.0, discriminator_accuracy, learning_rate: 0.5, discriminator_learning_rate: 0.5
+/// };
+/// assert!(!stopping.reached(&[cycle(1, 0.52), cycle(2, 0.9)]));
+/// assert!(stopping.reached(&[cycle(1, 0.9), cycle(2, 0.52), cycle(3, 0.47)]));
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct EquilibriumStopping {
+    /// How far from 0.5 the distinguishing model's accuracy can be and still count as guessing
+    pub tolerance: f32,
+    /// How many cycles in a row it has to be guessing before training stops
+    pub patience: usize
+}
+
+impl EquilibriumStopping {
+
+    pub fn new(tolerance: f32, patience: usize) -> EquilibriumStopping {
+        EquilibriumStopping { tolerance, patience }
+    }
+
+    /// Whether the last cycles have all been at equilibrium
+    pub fn reached(&self, cycles: &[CycleRecord]) -> bool {
+        let patience: usize = self.patience.max(1);
+        cycles.len() >= patience && cycles[cycles.len() - patience..].iter().all(|cycle| (cycle.discriminator_accuracy - 0.5).abs() <= self.tolerance)
+    }
+}
+
+/// The most outputs compared when measuring diversity, since every pair is compared
+pub const DIVERSITY_SAMPLE_LIMIT: usize = 256;
+
+/// The average euclidean distance between every pair of outputs.
+/// Near 0 means the generation model has collapsed to making the same output for everything.
+/// Only the first [`DIVERSITY_SAMPLE_LIMIT`] outputs are compared.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::adversarial::diversity;
+///
+/// assert_eq!(diversity(&[vec![0.0, 0.0], vec![3.0, 4.0]]), 5.0);
+/// assert_eq!(diversity(&[vec![1.0, 1.0], vec![1.0, 1.0], vec![1.0, 1.0]]), 0.0);
+/// ```
+pub fn diversity(outputs: &[Vec<f32>]) -> f32 {
+    let outputs: &[Vec<f32>] = &outputs[..outputs.len().min(DIVERSITY_SAMPLE_LIMIT)];
+    let mut sum: f32 = 0.0;
+    let mut pairs: usize = 0;
+    for i in 0..outputs.len() {
+        for j in (i + 1)..outputs.len() {
+            sum += outputs[i].iter().zip(&outputs[j]).map(|(a, b)| (a - b).powi(2)).sum::<f32>().sqrt();
+            pairs += 1;
+        }
+    }
+    if pairs == 0 { 0.0 } else { sum / pairs as f32 }
+}
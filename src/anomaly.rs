@@ -0,0 +1,90 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    error::DarjeelingError,
+    generation::Discriminator,
+    input::Input
+};
+
+/// Flags inputs that don't look like the data a generation model was trained on,
+/// using the anomaly scores of its [distinguishing model](crate::generation::Discriminator).
+/// Good for spotting unusual readings in tabular telemetry without labelled anomalies.
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::anomaly::AnomalyDetector;
+///
+/// net.learn(&mut telemetry, 0.5, "telemetry", 100, 0.5, 10, 1, ActivationFunction::Sigmoid, 99.0).unwrap();
+/// let detector = AnomalyDetector::fit(net.discriminator().unwrap(), &clean_readings, 0.01).unwrap();
+/// let alerts: Vec<&Input> = live_readings.iter().filter(|reading| detector.is_anomaly(reading)).collect();
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnomalyDetector {
+    discriminator: Discriminator,
+    threshold: f32
+}
+
+/// How many of a clean set were flagged, after fitting a threshold
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ThresholdFit {
+    pub threshold: f32,
+    /// The fraction of the clean set scoring above the threshold
+    pub false_positive_rate: f32
+}
+
+impl AnomalyDetector {
+
+    /// Picks the threshold from a validation set with no anomalies in it
+    ///
+    /// ## Params
+    /// - Discriminator: The distinguishing model of a trained generation model
+    /// - Clean: Inputs known to be normal, which weren't trained on
+    /// - False Positive Rate: The largest fraction of clean inputs that can be flagged, between 0 and 1
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The clean set is empty, or the false positive rate isn't between 0 and 1
+    pub fn fit(discriminator: &Discriminator, clean: &[Input], false_positive_rate: f32) -> Result<AnomalyDetector, DarjeelingError> {
+        if clean.is_empty() {
+            return Err(DarjeelingError::InvalidInput("Can't fit an anomaly threshold without clean data".to_string()));
+        }
+        if !(0.0..=1.0).contains(&false_positive_rate) {
+            return Err(DarjeelingError::InvalidInput(format!("False positive rate {} isn't between 0 and 1", false_positive_rate)));
+        }
+
+        let mut scores: Vec<f32> = clean.iter().map(|input| discriminator.anomaly_score(input)).collect();
+        scores.sort_by(|a, b| a.total_cmp(b));
+        // Everything above the threshold is flagged, so the threshold is the highest score that has to pass
+        let passing: usize = ((1.0 - false_positive_rate) * scores.len() as f32).ceil() as usize;
+        let threshold: f32 = if passing == 0 { f32::NEG_INFINITY } else { scores[passing.min(scores.len()) - 1] };
+
+        Ok(AnomalyDetector { discriminator: discriminator.clone(), threshold })
+    }
+
+    /// Uses a threshold that's already known, like one from an earlier fit
+    pub fn with_threshold(discriminator: &Discriminator, threshold: f32) -> AnomalyDetector {
+        AnomalyDetector { discriminator: discriminator.clone(), threshold }
+    }
+
+    /// How unusual the input looks, from 0 for certainly normal to 1 for certainly anomalous
+    pub fn anomaly_score(&self, input: &Input) -> f32 {
+        self.discriminator.anomaly_score(input)
+    }
+
+    /// Whether the input's anomaly score is above the threshold
+    pub fn is_anomaly(&self, input: &Input) -> bool {
+        self.anomaly_score(input) > self.threshold
+    }
+
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// The threshold and how many of a set of inputs it flags, for checking a fit against another clean set
+    pub fn evaluate(&self, clean: &[Input]) -> ThresholdFit {
+        let flagged: usize = clean.iter().filter(|input| self.is_anomaly(input)).count();
+        ThresholdFit {
+            threshold: self.threshold,
+            false_positive_rate: if clean.is_empty() { 0.0 } else { flagged as f32 / clean.len() as f32 }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::fs::File;
+use arrow_array::{
+    Array, ArrayRef, RecordBatch, BooleanArray, StringArray, LargeStringArray,
+    Float32Array, Float64Array, Int8Array, Int16Array, Int32Array, Int64Array,
+    UInt8Array, UInt16Array, UInt32Array, UInt64Array
+};
+use arrow_schema::DataType;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use crate::{
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// Reads the rows of an Arrow record batch into Inputs
+///
+/// ## Params
+/// - Batch: The record batch to read
+/// - Features: The names of the columns fed to the network, in the order of its sensors. They have to be numbers or booleans.
+/// - Label: The name of the column holding each row's answer. None if the rows have no answers.
+///
+/// ## Err
+/// - ### ReadDataFailed
+/// A column is missing, has a type that can't be read, or has a null value
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::arrow;
+///
+/// let data = arrow::read_record_batch(&batch, &["sepal_length", "sepal_width"], Some("species")).unwrap();
+/// ```
+pub fn read_record_batch(batch: &RecordBatch, features: &[&str], label: Option<&str>) -> Result<Vec<Input>, DarjeelingError> {
+    let mut data: Vec<Input> = (0..batch.num_rows()).map(|_| Input::new(Vec::with_capacity(features.len()), None)).collect();
+
+    for feature in features {
+        let values: Vec<f32> = numeric_column(column(batch, feature)?, feature)?;
+        data.iter_mut().zip(values).for_each(|(input, value)| input.inputs.push(value));
+    }
+
+    if let Some(label) = label {
+        let answers: Vec<Types> = label_column(column(batch, label)?, label)?;
+        data.iter_mut().zip(answers).for_each(|(input, answer)| input.answer = Some(answer));
+    }
+    Ok(data)
+}
+
+/// Reads every row group of a Parquet file into Inputs
+///
+/// ## Params
+/// - Path: The .parquet file to read
+/// - The rest are the same as [`read_record_batch`](fn@read_record_batch)
+///
+/// ## Err
+/// - ### ReadDataFailed
+/// The file couldn't be read, or a column couldn't be, for the same reasons as [`read_record_batch`](fn@read_record_batch)
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::arrow;
+///
+/// let data = arrow::read_parquet("iris.parquet", &["sepal_length", "sepal_width"], Some("species")).unwrap();
+/// ```
+pub fn read_parquet(path: &str, features: &[&str], label: Option<&str>) -> Result<Vec<Input>, DarjeelingError> {
+    let file: File = match File::open(path) {
+        Ok(file) => file,
+        Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+    };
+    let reader = match ParquetRecordBatchReaderBuilder::try_new(file).and_then(|builder| builder.build()) {
+        Ok(reader) => reader,
+        Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+    };
+
+    let mut data: Vec<Input> = vec![];
+    for batch in reader {
+        let batch: RecordBatch = match batch {
+            Ok(batch) => batch,
+            Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+        };
+        match read_record_batch(&batch, features, label) {
+            Ok(rows) => data.extend(rows),
+            Err(DarjeelingError::ReadDataFailed(error)) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error))),
+            Err(error) => return Err(error)
+        }
+    }
+    Ok(data)
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a ArrayRef, DarjeelingError> {
+    match batch.column_by_name(name) {
+        Some(column) => Ok(column),
+        None => Err(DarjeelingError::ReadDataFailed(format!("There's no column named {}", name)))
+    }
+}
+
+/// Downcasts the column and reads every value, failing on nulls
+fn values<A: Array + 'static, T>(column: &ArrayRef, name: &str, value: impl Fn(&A, usize) -> T) -> Result<Vec<T>, DarjeelingError> {
+    let array: &A = match column.as_any().downcast_ref::<A>() {
+        Some(array) => array,
+        None => return Err(DarjeelingError::ReadDataFailed(format!("Column {} couldn't be read as {}", name, column.data_type())))
+    };
+    (0..array.len()).map(|row| {
+        if array.is_null(row) {
+            return Err(DarjeelingError::ReadDataFailed(format!("Column {} row {} is null", name, row)));
+        }
+        Ok(value(array, row))
+    }).collect()
+}
+
+fn numeric_column(column: &ArrayRef, name: &str) -> Result<Vec<f32>, DarjeelingError> {
+    match column.data_type() {
+        DataType::Float32 => values(column, name, |array: &Float32Array, row| array.value(row)),
+        DataType::Float64 => values(column, name, |array: &Float64Array, row| array.value(row) as f32),
+        DataType::Int8 => values(column, name, |array: &Int8Array, row| array.value(row) as f32),
+        DataType::Int16 => values(column, name, |array: &Int16Array, row| array.value(row) as f32),
+        DataType::Int32 => values(column, name, |array: &Int32Array, row| array.value(row) as f32),
+        DataType::Int64 => values(column, name, |array: &Int64Array, row| array.value(row) as f32),
+        DataType::UInt8 => values(column, name, |array: &UInt8Array, row| array.value(row) as f32),
+        DataType::UInt16 => values(column, name, |array: &UInt16Array, row| array.value(row) as f32),
+        DataType::UInt32 => values(column, name, |array: &UInt32Array, row| array.value(row) as f32),
+        DataType::UInt64 => values(column, name, |array: &UInt64Array, row| array.value(row) as f32),
+        DataType::Boolean => values(column, name, |array: &BooleanArray, row| if array.value(row) { 1.0 } else { 0.0 }),
+        data_type => Err(DarjeelingError::ReadDataFailed(format!("Column {} is {}, which isn't a number", name, data_type)))
+    }
+}
+
+/// Strings are read as Types::String, integers as Types::Integer, floats as Types::Float and booleans as Types::Boolean
+fn label_column(column: &ArrayRef, name: &str) -> Result<Vec<Types>, DarjeelingError> {
+    match column.data_type() {
+        DataType::Utf8 => values(column, name, |array: &StringArray, row| Types::String(array.value(row).to_string())),
+        DataType::LargeUtf8 => values(column, name, |array: &LargeStringArray, row| Types::String(array.value(row).to_string())),
+        DataType::Boolean => values(column, name, |array: &BooleanArray, row| Types::Boolean(array.value(row))),
+        DataType::Int8 => values(column, name, |array: &Int8Array, row| Types::Integer(array.value(row) as i32)),
+        DataType::Int16 => values(column, name, |array: &Int16Array, row| Types::Integer(array.value(row) as i32)),
+        DataType::Int32 => values(column, name, |array: &Int32Array, row| Types::Integer(array.value(row))),
+        DataType::Int64 => values(column, name, |array: &Int64Array, row| Types::Integer(array.value(row) as i32)),
+        DataType::Float32 => values(column, name, |array: &Float32Array, row| Types::Float(array.value(row))),
+        DataType::Float64 => values(column, name, |array: &Float64Array, row| Types::Float(array.value(row) as f32)),
+        data_type => Err(DarjeelingError::ReadDataFailed(format!("Column {} is {}, which can't be a label", name, data_type)))
+    }
+}
@@ -0,0 +1,68 @@
+use core::fmt;
+use std::{sync::Arc, time::SystemTime};
+use crate::{hash, types::Types};
+
+/// A record of one automated decision, passed to the [audit hook](fn@crate::categorize::CatNetwork::set_audit_hook) on every prediction
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredictionAudit {
+    /// When the prediction was made
+    pub timestamp: SystemTime,
+    /// The SHA-256 of the inputs, as hex, so the decision can be tied to its inputs without storing them
+    pub input_hash: String,
+    /// Every category with its answer node's raw output, in the order of the answer nodes
+    pub scores: Vec<(Types, f32)>,
+    /// The category the network answered with
+    pub category: Types
+}
+
+impl PredictionAudit {
+
+    pub(crate) fn new(inputs: &[f32], scores: Vec<(Types, f32)>, category: Types) -> PredictionAudit {
+        PredictionAudit { timestamp: SystemTime::now(), input_hash: input_hash(inputs), scores, category }
+    }
+}
+
+/// Called with every prediction a network makes, for routing a record of each decision to your own storage,
+/// like a log file, a database or a message queue.
+/// It's called on the thread that predicts, so a slow hook slows predictions down, and it should hand records off rather than block.
+///
+/// ## Examples
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use darjeeling::{audit::AuditHook, categorize::CatNetwork, activation::ActivationFunction, types::Types};
+///
+/// let decisions = Arc::new(Mutex::new(vec![]));
+/// let log = decisions.clone();
+/// let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+/// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+/// net.set_audit_hook(Some(AuditHook::new(move |audit| log.lock().unwrap().push(audit.clone()))));
+/// net.predict(&[0.5, 0.25]);
+/// assert_eq!(decisions.lock().unwrap().len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct AuditHook {
+    hook: Arc<dyn Fn(&PredictionAudit) + Send + Sync>
+}
+
+impl AuditHook {
+
+    pub fn new<F: Fn(&PredictionAudit) + Send + Sync + 'static>(hook: F) -> AuditHook {
+        AuditHook { hook: Arc::new(hook) }
+    }
+
+    pub(crate) fn record(&self, audit: &PredictionAudit) {
+        (self.hook)(audit)
+    }
+}
+
+impl fmt::Debug for AuditHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AuditHook")
+    }
+}
+
+/// The SHA-256 of the inputs' little endian bytes, as hex
+pub fn input_hash(inputs: &[f32]) -> String {
+    let bytes: Vec<u8> = inputs.iter().flat_map(|value| value.to_le_bytes()).collect();
+    hash::to_hex(&hash::sha256(&bytes))
+}
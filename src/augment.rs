@@ -0,0 +1,80 @@
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+
+/// A random change applied to an input's values during training
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Augmentation {
+    /// Adds normally distributed noise with the given standard deviation to every value
+    GaussianNoise { std_dev: f32 },
+    /// Sets each value to 0 with the given probability
+    FeatureDropout { rate: f32 },
+    /// Multiplies the whole input by a random factor between 1 - amount and 1 + amount
+    ScaleJitter { amount: f32 },
+}
+
+/// A list of augmentations applied in order to every input, each time it's seen during training.
+/// The training data itself is never changed, so every epoch sees different variations of it.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::augment::{Augmenter, Augmentation};
+///
+/// let augmenter = Augmenter::new()
+///     .with(Augmentation::GaussianNoise { std_dev: 0.05 })
+///     .with(Augmentation::FeatureDropout { rate: 0.1 });
+/// let augmented: Vec<f32> = augmenter.augmented(&[0.5, 1.0], &mut rand::thread_rng());
+/// assert_eq!(augmented.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Augmenter {
+    augmentations: Vec<Augmentation>
+}
+
+impl Augmenter {
+
+    pub fn new() -> Augmenter {
+        Augmenter { augmentations: vec![] }
+    }
+
+    /// Adds an augmentation to the end of the pipeline
+    pub fn with(mut self, augmentation: Augmentation) -> Augmenter {
+        self.augmentations.push(augmentation);
+        self
+    }
+
+    pub fn augmentations(&self) -> &[Augmentation] {
+        &self.augmentations
+    }
+
+    /// Returns an augmented copy of the values
+    pub fn augmented<R: Rng>(&self, values: &[f32], rng: &mut R) -> Vec<f32> {
+        let mut augmented: Vec<f32> = values.to_vec();
+        self.augment(&mut augmented, rng);
+        augmented
+    }
+
+    /// Augments the values in place
+    pub fn augment<R: Rng>(&self, values: &mut [f32], rng: &mut R) {
+        self.augmentations.iter().for_each(|augmentation| match *augmentation {
+            Augmentation::GaussianNoise { std_dev } => {
+                values.iter_mut().for_each(|value| *value += gaussian(rng) * std_dev);
+            },
+            Augmentation::FeatureDropout { rate } => {
+                values.iter_mut().for_each(|value| if rng.gen::<f32>() < rate { *value = 0.0 });
+            },
+            Augmentation::ScaleJitter { amount } => {
+                if amount > 0.0 {
+                    let factor: f32 = 1.0 + rng.gen_range(-amount..amount);
+                    values.iter_mut().for_each(|value| *value *= factor);
+                }
+            }
+        });
+    }
+}
+
+/// Samples a standard normal value with the Box-Muller transform
+pub(crate) fn gaussian<R: Rng>(rng: &mut R) -> f32 {
+    let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+}
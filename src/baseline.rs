@@ -0,0 +1,116 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    calibration,
+    dataset::Dataset,
+    error::DarjeelingError,
+    input::Input,
+    metrics::ClassificationMetrics,
+    prediction::Prediction,
+    types::Types
+};
+
+/// The simplest classifier worth comparing a network with. It keeps the mean of every category's inputs,
+/// and puts new inputs in the category whose mean is closest. A network that can't beat it isn't learning much from the data.
+///
+/// Distances are Euclidean, so features on large scales count for more. Scale the data the same way the network sees it before fitting.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{baseline::NearestCentroid, dataset::Dataset, input::Input, types::Types};
+///
+/// let data = Dataset::new(vec![
+///     Input::new(vec![0.0, 0.0], Some(Types::String("low".to_string()))),
+///     Input::new(vec![0.2, 0.0], Some(Types::String("low".to_string()))),
+///     Input::new(vec![1.0, 1.0], Some(Types::String("high".to_string())))
+/// ]);
+/// let baseline = NearestCentroid::fit(&data).unwrap();
+/// assert_eq!(baseline.centroids()[0], vec![0.1, 0.0]);
+/// assert_eq!(baseline.predict_category(&[0.9, 0.8]), Types::String("high".to_string()));
+/// // Compare with the network's accuracy on the same test data
+/// assert_eq!(baseline.evaluate(data.inputs()).accuracy, 1.0);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NearestCentroid {
+    /// In the order they first appear in the data
+    categories: Vec<Types>,
+    /// The mean of every category's inputs, in the same order as the categories
+    centroids: Vec<Vec<f32>>
+}
+
+impl NearestCentroid {
+
+    /// Finds the mean of every category's inputs. Inputs without an answer are skipped.
+    ///
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// No input has an answer, or the inputs aren't all the same length
+    pub fn fit(data: &Dataset) -> Result<NearestCentroid, DarjeelingError> {
+        let groups: Vec<(Types, Vec<usize>)> = data.groups();
+        let width: usize = match groups.first() {
+            Some((_, indexes)) => data.inputs()[indexes[0]].inputs.len(),
+            None => return Err(DarjeelingError::InvalidTrainingData("A nearest centroid baseline needs inputs with answers".to_string()))
+        };
+
+        let mut categories: Vec<Types> = Vec::with_capacity(groups.len());
+        let mut centroids: Vec<Vec<f32>> = Vec::with_capacity(groups.len());
+        for (category, indexes) in groups {
+            let mut centroid: Vec<f32> = vec![0.0; width];
+            for i in &indexes {
+                let inputs: &[f32] = &data.inputs()[*i].inputs;
+                if inputs.len() != width {
+                    return Err(DarjeelingError::InvalidTrainingData(format!("Input {} has {} values, but the others have {}", i, inputs.len(), width)));
+                }
+                centroid.iter_mut().zip(inputs).for_each(|(sum, value)| *sum += value);
+            }
+            centroid.iter_mut().for_each(|sum| *sum /= indexes.len() as f32);
+            categories.push(category);
+            centroids.push(centroid);
+        }
+        Ok(NearestCentroid { categories, centroids })
+    }
+
+    pub fn categories(&self) -> &[Types] {
+        &self.categories
+    }
+
+    pub fn centroids(&self) -> &[Vec<f32>] {
+        &self.centroids
+    }
+
+    /// The distance from the inputs to every category's centroid, in the same order as the categories
+    pub fn distances(&self, inputs: &[f32]) -> Vec<f32> {
+        self.centroids.iter().map(|centroid| {
+            centroid.iter().zip(inputs).map(|(mean, value)| (mean - value).powi(2)).sum::<f32>().sqrt()
+        }).collect()
+    }
+
+    /// The category with the closest centroid, the first one on a tie
+    pub fn predict_category(&self, inputs: &[f32]) -> Types {
+        let distances: Vec<f32> = self.distances(inputs);
+        let closest: usize = (1..distances.len()).fold(0, |closest, i| if distances[i] < distances[closest] { i } else { closest });
+        self.categories[closest].clone()
+    }
+
+    /// The category with the closest centroid, scored by how far away every centroid is, negated so the highest score wins.
+    /// The confidence is the softmax of the scores, so a centroid much closer than the rest is near 1.
+    pub fn predict(&self, inputs: &[f32]) -> Prediction {
+        let scores: Vec<f32> = self.distances(inputs).into_iter().map(|distance| -distance).collect();
+        let probabilities: Vec<f32> = calibration::softmax(&scores, 1.0);
+        let closest: usize = (1..scores.len()).fold(0, |closest, i| if scores[i] > scores[closest] { i } else { closest });
+        Prediction {
+            category: self.categories[closest].clone(),
+            confidence: probabilities[closest],
+            scores: self.categories.iter().cloned().zip(scores).collect(),
+            tied: vec![]
+        }
+    }
+
+    /// How well the baseline categorizes the data, to compare with a network's [evaluation](fn@crate::categorize::CatNetwork::evaluate).
+    /// Inputs without an answer are skipped.
+    pub fn evaluate(&self, data: &[Input]) -> ClassificationMetrics {
+        let (predictions, truth): (Vec<Types>, Vec<Types>) = data.iter()
+            .filter_map(|input| Some((self.predict_category(&input.inputs), input.answer.clone()?)))
+            .unzip();
+        ClassificationMetrics::compute(&predictions, &truth)
+    }
+}
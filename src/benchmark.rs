@@ -0,0 +1,74 @@
+use std::{fmt, time::Duration};
+use serde::{Serialize, Deserialize};
+
+/// How fast a network answered, measured by [`benchmark`](fn@crate::categorize::CatNetwork::benchmark).
+/// Only the forward passes are timed, not making the inputs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchmarkReport {
+    pub samples: usize,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    /// Forward passes per second, one after another on one thread
+    pub throughput: f64,
+    /// Whether the library was built with optimizations
+    pub release: bool,
+    /// The cargo features the library was built with
+    pub features: Vec<String>
+}
+
+impl BenchmarkReport {
+
+    /// Sorts the latencies of every sample into a report
+    pub(crate) fn from_latencies(mut latencies: Vec<Duration>) -> BenchmarkReport {
+        latencies.sort();
+        let total: Duration = latencies.iter().sum();
+        let percentile = |percent: usize| -> Duration {
+            // Nearest rank
+            let rank: usize = (percent * latencies.len() + 99) / 100;
+            latencies[rank.clamp(1, latencies.len()) - 1]
+        };
+        BenchmarkReport {
+            samples: latencies.len(),
+            mean: total / latencies.len() as u32,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: *latencies.last().unwrap(),
+            throughput: latencies.len() as f64 / total.as_secs_f64().max(f64::MIN_POSITIVE),
+            release: !cfg!(debug_assertions),
+            features: enabled_features()
+        }
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} samples ({} build, features: {})",
+            self.samples,
+            if self.release { "release" } else { "debug" },
+            if self.features.is_empty() { "none".to_string() } else { self.features.join(", ") }
+        )?;
+        writeln!(f, "mean {:?}, p50 {:?}, p90 {:?}, p99 {:?}, max {:?}", self.mean, self.p50, self.p90, self.p99, self.max)?;
+        write!(f, "{:.0} predictions per second", self.throughput)
+    }
+}
+
+fn enabled_features() -> Vec<String> {
+    [
+        ("image", cfg!(feature = "image")),
+        ("cli", cfg!(feature = "cli")),
+        ("serve", cfg!(feature = "serve")),
+        ("ffi", cfg!(feature = "ffi")),
+        ("parquet", cfg!(feature = "parquet")),
+        ("mixed-precision", cfg!(feature = "mixed-precision")),
+        ("signing", cfg!(feature = "signing")),
+        ("encryption", cfg!(feature = "encryption")),
+        ("plot", cfg!(feature = "plot")),
+        ("mmap", cfg!(feature = "mmap")),
+        ("parallel", cfg!(feature = "parallel")),
+        ("parallel-csv", cfg!(feature = "parallel-csv"))
+    ].iter().filter(|(_, enabled)| *enabled).map(|(feature, _)| feature.to_string()).collect()
+}
@@ -0,0 +1,222 @@
+//! The darjeeling command line, for training and using categorization models without writing Rust
+//!
+//! ```text
+//! darjeeling train --data iris.csv --header --hidden 8 --learning-rate 0.5 --target 95 --name iris
+//! darjeeling evaluate --model model_iris_123.darj --data iris_test.csv --header
+//! darjeeling predict --model model_iris_123.darj --data unlabeled.csv
+//! darjeeling score --model model_iris_123.darj --data unlabeled.csv --header --output scored.csv --threads 4
+//! ```
+
+use std::{fs, process::ExitCode};
+use clap::{Parser, Subcommand, Args, ValueEnum};
+use darjeeling::{
+    activation::ActivationFunction,
+    categorize::CatNetwork,
+    csv,
+    dataset::Dataset,
+    error::DarjeelingError,
+    input::Input,
+    parallel,
+    scaler::Scaler,
+    types::Types
+};
+
+#[derive(Parser)]
+#[command(name = "darjeeling", version, about = "Train and use darjeeling categorization models")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// How many threads parallel work runs on, instead of RAYON_NUM_THREADS or one for every core
+    #[arg(long, global = true)]
+    threads: Option<usize>
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Trains a model on a CSV file and saves it as a .darj file
+    Train(TrainArgs),
+    /// Reports a model's loss, accuracy, precision and recall on a labeled CSV file
+    Evaluate(EvaluateArgs),
+    /// Prints the predicted category of every row in a CSV file
+    Predict(PredictArgs),
+    /// Copies a CSV file with every row's predicted category and confidence added
+    Score(ScoreArgs)
+}
+
+#[derive(Args)]
+struct DataArgs {
+    /// The CSV file to read
+    #[arg(long)]
+    data: String,
+    /// Skip the first line of the file
+    #[arg(long)]
+    header: bool,
+    /// The column with each row's category, counted from 0. Defaults to the last column.
+    #[arg(long)]
+    label_column: Option<usize>
+}
+
+#[derive(Args)]
+struct TrainArgs {
+    #[command(flatten)]
+    data: DataArgs,
+    /// The number of nodes in each hidden layer
+    #[arg(long, default_value = "8")]
+    hidden: i32,
+    /// The number of hidden layers
+    #[arg(long, default_value = "1")]
+    layers: i32,
+    #[arg(long, value_enum, default_value = "sigmoid")]
+    activation: Activation,
+    #[arg(long, default_value = "0.5")]
+    learning_rate: f32,
+    /// Stop training once this percent of the training data is categorized correctly
+    #[arg(long, default_value = "95.0")]
+    target: f32,
+    /// Scale every column before it reaches the network. The scaler is saved with the model.
+    #[arg(long, value_enum)]
+    scale: Option<Scale>,
+    /// The categories, separated by commas. Defaults to every category in the data, in the order they first appear.
+    #[arg(long, value_delimiter = ',')]
+    categories: Option<Vec<String>>,
+    /// The model is saved as model_<name>_<random number>.darj
+    #[arg(long, default_value = "model")]
+    name: String
+}
+
+#[derive(Args)]
+struct EvaluateArgs {
+    /// The .darj file to read
+    #[arg(long)]
+    model: String,
+    #[command(flatten)]
+    data: DataArgs
+}
+
+#[derive(Args)]
+struct PredictArgs {
+    /// The .darj file to read
+    #[arg(long)]
+    model: String,
+    /// The CSV file to read, without a category column
+    #[arg(long)]
+    data: String,
+    /// Skip the first line of the file
+    #[arg(long)]
+    header: bool
+}
+
+#[derive(Args)]
+struct ScoreArgs {
+    /// The .darj file to read
+    #[arg(long)]
+    model: String,
+    /// The CSV file to read. With a header, a model with named features finds them by name and passes other columns through.
+    #[arg(long)]
+    data: String,
+    /// The first line of the file is column names
+    #[arg(long)]
+    header: bool,
+    /// Where to write the scored rows
+    #[arg(long)]
+    output: String
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Activation {
+    Sigmoid,
+    Tanh,
+    Linear
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Scale {
+    MinMax,
+    ZScore
+}
+
+fn main() -> ExitCode {
+    let cli: Cli = Cli::parse();
+    let result = parallel::set_threads(cli.threads).and_then(|_| match cli.command {
+        Command::Train(args) => train(args),
+        Command::Evaluate(args) => evaluate(args),
+        Command::Predict(args) => predict(args),
+        Command::Score(args) => score(args)
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn train(args: TrainArgs) -> Result<(), DarjeelingError> {
+    let mut data: Vec<Input> = read_labeled(&args.data)?;
+    let categories: Vec<Types> = match args.categories {
+        Some(categories) => categories.into_iter().map(Types::String).collect(),
+        None => Dataset::new(data.clone()).categories()
+    };
+    let activation: ActivationFunction = match args.activation {
+        Activation::Sigmoid => ActivationFunction::Sigmoid,
+        Activation::Tanh => ActivationFunction::Tanh,
+        Activation::Linear => ActivationFunction::Linear
+    };
+
+    let mut net = CatNetwork::new(data[0].inputs.len() as i32, args.hidden, categories.len() as i32, args.layers, activation);
+    net.set_scaler(args.scale.map(|scale| match scale {
+        Scale::MinMax => Scaler::fit_min_max(&data),
+        Scale::ZScore => Scaler::fit_z_score(&data)
+    }));
+    let (model_name, err_percent, _) = net.learn(&mut data, categories, args.learning_rate, &args.name, args.target, true)?;
+    println!("Saved {} with a training accuracy of {}%", model_name.unwrap_or_default(), err_percent);
+    Ok(())
+}
+
+fn evaluate(args: EvaluateArgs) -> Result<(), DarjeelingError> {
+    let net: CatNetwork = read_model(&args.model)?;
+    let data: Vec<Input> = read_labeled(&args.data)?;
+    println!("{}", net.evaluate(&data));
+    Ok(())
+}
+
+fn predict(args: PredictArgs) -> Result<(), DarjeelingError> {
+    let net: CatNetwork = read_model(&args.model)?;
+    let data: Vec<Input> = csv::read_csv(&args.data, args.header, None)?;
+    for (row, input) in data.iter().enumerate() {
+        if input.inputs.len() != net.input_size() {
+            return Err(DarjeelingError::InvalidInput(format!("Row {} has {} values, but the model takes {}", row, input.inputs.len(), net.input_size())));
+        }
+        println!("{}", net.predict(&input.inputs).category);
+    }
+    Ok(())
+}
+
+fn score(args: ScoreArgs) -> Result<(), DarjeelingError> {
+    let net: CatNetwork = read_model(&args.model)?;
+    let rows: usize = csv::score_file(&net, &args.data, &args.output, args.header)?;
+    println!("Scored {} rows into {}", rows, args.output);
+    Ok(())
+}
+
+fn read_labeled(args: &DataArgs) -> Result<Vec<Input>, DarjeelingError> {
+    let text: String = match fs::read_to_string(&args.data) {
+        Ok(text) => text,
+        Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", args.data, error)))
+    };
+    let label_column: usize = args.label_column.unwrap_or(csv::column_count(&text).saturating_sub(1));
+    let data: Vec<Input> = csv::parse_csv(&text, args.header, Some(label_column))?;
+    if data.is_empty() {
+        return Err(DarjeelingError::InvalidTrainingData(format!("{} has no rows", args.data)));
+    }
+    Ok(data)
+}
+
+fn read_model(path: &str) -> Result<CatNetwork, DarjeelingError> {
+    let net: CatNetwork = CatNetwork::read_model(path.to_string())?;
+    if net.categories().is_none() {
+        return Err(DarjeelingError::InvalidMetadataRead(format!("{} was saved without its categories, retrain it to use it here", path)));
+    }
+    Ok(net)
+}
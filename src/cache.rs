@@ -0,0 +1,183 @@
+use std::{fs, path::{Path, PathBuf}};
+use crate::{csv, error::DarjeelingError, hash, input::Input, types::Types};
+
+const MAGIC: &[u8; 8] = b"DARJCACH";
+
+/// A directory of preprocessed datasets, so repeated runs on the same data can skip parsing and encoding it again.
+/// Every dataset is stored under a key made from the raw data and a description of how it was preprocessed,
+/// so changing either one misses the cache instead of returning stale inputs.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{cache::DatasetCache, input::Input, types::Types};
+///
+/// let cache = DatasetCache::new(std::env::temp_dir().join("darjeeling_cache_example"));
+/// let raw = "0,1,1\n1,1,0\n";
+/// let key = DatasetCache::key(raw.as_bytes(), "csv, label column 2");
+/// let data = cache.get_or_insert_with(&key, || darjeeling::csv::parse_csv(raw, false, Some(2))).unwrap();
+/// // Parsed once, then read back from the cache
+/// assert_eq!(cache.get(&key).unwrap(), Some(data));
+/// # cache.clear().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetCache {
+    dir: PathBuf
+}
+
+impl DatasetCache {
+
+    /// ## Params
+    /// - Dir: The directory to keep datasets in, made the first time one is stored
+    pub fn new<P: AsRef<Path>>(dir: P) -> DatasetCache {
+        DatasetCache { dir: dir.as_ref().to_path_buf() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The SHA-256 of the raw data and the description of how it's preprocessed, as hex
+    ///
+    /// ## Params
+    /// - Content: The raw data, like the bytes of a CSV file
+    /// - Pipeline: Anything that changes the preprocessed inputs, like the column layout or the encoders as JSON
+    pub fn key(content: &[u8], pipeline: &str) -> String {
+        let mut bytes: Vec<u8> = hash::sha256(content).to_vec();
+        bytes.extend_from_slice(pipeline.as_bytes());
+        hash::to_hex(&hash::sha256(&bytes))
+    }
+
+    /// The dataset stored under a key, None if there isn't one
+    ///
+    /// ## Err
+    /// - ### ReadDataFailed
+    /// The cached file couldn't be read or isn't a cached dataset
+    pub fn get(&self, key: &str) -> Result<Option<Vec<Input>>, DarjeelingError> {
+        let path: PathBuf = self.path(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes: Vec<u8> = fs::read(&path).map_err(|error| DarjeelingError::ReadDataFailed(format!("{}: {}", path.display(), error)))?;
+        match decode(&bytes) {
+            Some(data) => Ok(Some(data)),
+            None => Err(DarjeelingError::ReadDataFailed(format!("{}: It isn't a cached dataset, clear the cache to rebuild it", path.display())))
+        }
+    }
+
+    /// Stores a dataset under a key, replacing what was there
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The directory or file couldn't be written, or an answer couldn't be serialized
+    pub fn put(&self, key: &str, data: &[Input]) -> Result<(), DarjeelingError> {
+        let path: PathBuf = self.path(key);
+        let bytes: Vec<u8> = encode(data).map_err(|error| DarjeelingError::WriteDataFailed(format!("{}: {}", path.display(), error)))?;
+        // Written beside the real file and renamed over it, so a crash never leaves half a dataset behind
+        let partial: PathBuf = path.with_extension("partial");
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&partial, bytes))
+            .and_then(|_| fs::rename(&partial, &path))
+            .map_err(|error| DarjeelingError::WriteDataFailed(format!("{}: {}", path.display(), error)))
+    }
+
+    /// The dataset stored under a key, or else the one the closure makes, which is stored for next time
+    ///
+    /// ## Err
+    /// Whatever the closure returns, and the same as [`get`](fn@DatasetCache::get) and [`put`](fn@DatasetCache::put)
+    pub fn get_or_insert_with<F: FnOnce() -> Result<Vec<Input>, DarjeelingError>>(&self, key: &str, make: F) -> Result<Vec<Input>, DarjeelingError> {
+        if let Some(data) = self.get(key)? {
+            return Ok(data);
+        }
+        let data: Vec<Input> = make()?;
+        self.put(key, &data)?;
+        Ok(data)
+    }
+
+    /// Reads a CSV file the same as [`csv::read_csv`](fn@csv::read_csv), parsing it only if it changed since it was last read
+    ///
+    /// ## Err
+    /// The same as [`csv::read_csv`](fn@csv::read_csv) and [`get_or_insert_with`](fn@DatasetCache::get_or_insert_with)
+    pub fn read_csv(&self, path: &str, has_header: bool, label_column: Option<usize>) -> Result<Vec<Input>, DarjeelingError> {
+        let text: String = fs::read_to_string(path).map_err(|error| DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))?;
+        let key: String = DatasetCache::key(text.as_bytes(), &format!("csv header {} label column {:?}", has_header, label_column));
+        self.get_or_insert_with(&key, || csv::parse_csv(&text, has_header, label_column).map_err(|error| match error {
+            DarjeelingError::ReadDataFailed(error) => DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)),
+            error => error
+        }))
+    }
+
+    /// Deletes every cached dataset
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The directory couldn't be removed
+    pub fn clear(&self) -> Result<(), DarjeelingError> {
+        match fs::remove_dir_all(&self.dir) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", self.dir.display(), error)))
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.cache", key))
+    }
+}
+
+/// The magic bytes and the number of rows, then for every row the number of inputs, the inputs as little endian f32s,
+/// and the answer's length and JSON, with a length of 0 for no answer
+fn encode(data: &[Input]) -> Result<Vec<u8>, serde_json::Error> {
+    let mut bytes: Vec<u8> = MAGIC.to_vec();
+    bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    for input in data {
+        bytes.extend_from_slice(&(input.inputs.len() as u32).to_le_bytes());
+        input.inputs.iter().for_each(|value| bytes.extend_from_slice(&value.to_le_bytes()));
+        let answer: Vec<u8> = match &input.answer {
+            Some(answer) => serde_json::to_vec(answer)?,
+            None => vec![]
+        };
+        bytes.extend_from_slice(&(answer.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&answer);
+    }
+    Ok(bytes)
+}
+
+fn decode(bytes: &[u8]) -> Option<Vec<Input>> {
+    let mut reader = Reader { bytes, at: 0 };
+    if reader.take(8)? != MAGIC {
+        return None;
+    }
+    let rows: usize = u64::from_le_bytes(reader.take(8)?.try_into().ok()?) as usize;
+    let mut data: Vec<Input> = Vec::with_capacity(rows.min(bytes.len()));
+    for _ in 0..rows {
+        let width: usize = reader.u32()? as usize;
+        let inputs: Vec<f32> = reader.take(width.checked_mul(4)?)?.chunks_exact(4).map(|value| f32::from_le_bytes(value.try_into().expect("Chunks are 4 bytes"))).collect();
+        let answer: Option<Types> = match reader.u32()? as usize {
+            0 => None,
+            length => Some(serde_json::from_slice(reader.take(length)?).ok()?)
+        };
+        data.push(Input::new(inputs, answer));
+    }
+    match reader.at == bytes.len() {
+        true => Some(data),
+        false => None
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize
+}
+
+impl<'a> Reader<'a> {
+
+    fn take(&mut self, length: usize) -> Option<&'a [u8]> {
+        let taken: &[u8] = self.bytes.get(self.at..self.at.checked_add(length)?)?;
+        self.at += length;
+        Some(taken)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+}
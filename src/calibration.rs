@@ -0,0 +1,196 @@
+use core::fmt;
+use serde::{Serialize, Deserialize};
+
+/// Predictions whose confidence fell in one range, and how often they were right
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CalibrationBucket {
+    pub lower: f32,
+    pub upper: f32,
+    /// The average confidence of the predictions in the bucket
+    pub mean_confidence: f32,
+    /// The fraction of the predictions in the bucket that were correct
+    pub accuracy: f32,
+    pub count: usize
+}
+
+/// Compares how confident a model was with how often it was right.
+/// A well calibrated model is right 70% of the time when it's 70% confident.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CalibrationReport {
+    pub buckets: Vec<CalibrationBucket>,
+    /// The gap between confidence and accuracy, averaged over the buckets and weighted by their size
+    pub expected_calibration_error: f32
+}
+
+impl CalibrationReport {
+
+    /// Groups predictions into equally wide confidence buckets
+    ///
+    /// ## Params
+    /// - Confidences: How confident the model was in each prediction, between 0 and 1
+    /// - Correct: Whether each prediction was right, in the same order
+    /// - Bucket Count: How many buckets 0 to 1 is split into
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::calibration::CalibrationReport;
+    ///
+    /// let report = CalibrationReport::compute(&[0.9, 0.9, 0.2], &[true, false, false], 2);
+    /// assert_eq!(report.buckets[1].count, 2);
+    /// assert_eq!(report.buckets[1].accuracy, 0.5);
+    /// ```
+    pub fn compute(confidences: &[f32], correct: &[bool], bucket_count: usize) -> CalibrationReport {
+        let bucket_count: usize = bucket_count.max(1);
+        let mut confidence_sums: Vec<f32> = vec![0.0; bucket_count];
+        let mut correct_counts: Vec<usize> = vec![0; bucket_count];
+        let mut counts: Vec<usize> = vec![0; bucket_count];
+
+        confidences.iter().zip(correct).for_each(|(confidence, correct)| {
+            let bucket: usize = ((confidence.clamp(0.0, 1.0) * bucket_count as f32) as usize).min(bucket_count - 1);
+            confidence_sums[bucket] += confidence;
+            counts[bucket] += 1;
+            if *correct {
+                correct_counts[bucket] += 1;
+            }
+        });
+
+        let total: usize = counts.iter().sum();
+        let mut expected_calibration_error: f32 = 0.0;
+        let buckets: Vec<CalibrationBucket> = (0..bucket_count)
+            .map(|i| {
+                let count: usize = counts[i];
+                let (mean_confidence, accuracy) = if count == 0 {
+                    (0.0, 0.0)
+                } else {
+                    (confidence_sums[i] / count as f32, correct_counts[i] as f32 / count as f32)
+                };
+                if total != 0 {
+                    expected_calibration_error += (mean_confidence - accuracy).abs() * count as f32 / total as f32;
+                }
+                CalibrationBucket {
+                    lower: i as f32 / bucket_count as f32,
+                    upper: (i + 1) as f32 / bucket_count as f32,
+                    mean_confidence,
+                    accuracy,
+                    count
+                }
+            })
+            .collect();
+
+        CalibrationReport { buckets, expected_calibration_error }
+    }
+}
+
+impl fmt::Display for CalibrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Expected Calibration Error: {:.4}", self.expected_calibration_error)?;
+        writeln!(f, "{:<13} {:>10} {:>9} {:>7}", "Confidence", "Mean", "Accuracy", "Count")?;
+        for bucket in &self.buckets {
+            writeln!(f, "{:.2} - {:.2}   {:>10.4} {:>9.4} {:>7}", bucket.lower, bucket.upper, bucket.mean_confidence, bucket.accuracy, bucket.count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Which post-processor [`fit_calibrator`](fn@crate::categorize::CatNetwork::fit_calibrator) learns
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum CalibrationMethod {
+    Temperature,
+    Platt
+}
+
+/// Turns the raw brightness of the answer nodes into a confidence that matches how often the model is right
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Calibrator {
+    /// Softmax over the answer nodes, after dividing them by the temperature.
+    /// Larger temperatures make the model less confident.
+    Temperature { temperature: f32 },
+    /// A sigmoid of the brightest node, `1 / (1 + e^-(a * brightness + b))`
+    Platt { a: f32, b: f32 }
+}
+
+impl Calibrator {
+
+    /// Fits the temperature that best predicts the correct answer nodes, by minimizing their negative log likelihood
+    ///
+    /// ## Params
+    /// - Outputs: The answer node outputs for each input
+    /// - Answers: The index of the correct answer node for each input
+    pub fn fit_temperature(outputs: &[Vec<f32>], answers: &[usize]) -> Calibrator {
+        let loss = |temperature: f32| -> f32 {
+            outputs.iter().zip(answers).map(|(outputs, answer)| {
+                -softmax(outputs, temperature)[*answer].max(f32::MIN_POSITIVE).ln()
+            }).sum()
+        };
+
+        // A coarse search over a wide range, then a finer one around the best
+        let mut best: f32 = 1.0;
+        let mut best_loss: f32 = loss(best);
+        let mut step: f32 = 0.5;
+        let mut center: f32 = 0.0;
+        for _ in 0..4 {
+            (-10..=10).for_each(|i| {
+                let temperature: f32 = (center + i as f32 * step).exp();
+                let candidate_loss: f32 = loss(temperature);
+                if candidate_loss < best_loss {
+                    best = temperature;
+                    best_loss = candidate_loss;
+                }
+            });
+            center = best.ln();
+            step /= 10.0;
+        }
+        Calibrator::Temperature { temperature: best }
+    }
+
+    /// Fits a Platt scaling sigmoid by gradient descent on the log loss
+    ///
+    /// ## Params
+    /// - Scores: The brightness of the chosen answer node for each input
+    /// - Correct: Whether each chosen answer was right
+    pub fn fit_platt(scores: &[f32], correct: &[bool]) -> Calibrator {
+        let count: f32 = scores.len().max(1) as f32;
+        let mut a: f32 = 1.0;
+        let mut b: f32 = 0.0;
+        for _ in 0..2000 {
+            let (mut gradient_a, mut gradient_b) = (0.0, 0.0);
+            scores.iter().zip(correct).for_each(|(score, correct)| {
+                let error: f32 = sigmoid(a * score + b) - if *correct { 1.0 } else { 0.0 };
+                gradient_a += error * score;
+                gradient_b += error;
+            });
+            a -= gradient_a / count;
+            b -= gradient_b / count;
+        }
+        Calibrator::Platt { a, b }
+    }
+
+    /// How confident the model is in its brightest answer node
+    pub fn confidence(&self, outputs: &[f32]) -> f32 {
+        let brightest: f32 = outputs.iter().cloned().fold(f32::MIN, f32::max);
+        match self {
+            Calibrator::Temperature { temperature } => softmax(outputs, *temperature).into_iter().fold(0.0, f32::max),
+            Calibrator::Platt { a, b } => sigmoid(a * brightest + b)
+        }
+    }
+
+    /// How likely every answer node is to be right, for calibrators that spread the confidence over all of them.
+    /// Platt scaling only calibrates the brightest node, so it has None.
+    pub fn probabilities(&self, outputs: &[f32]) -> Option<Vec<f32>> {
+        match self {
+            Calibrator::Temperature { temperature } => Some(softmax(outputs, *temperature)),
+            Calibrator::Platt { .. } => None
+        }
+    }
+}
+
+pub(crate) fn softmax(outputs: &[f32], temperature: f32) -> Vec<f32> {
+    let largest: f32 = outputs.iter().cloned().fold(f32::MIN, f32::max);
+    let exps: Vec<f32> = outputs.iter().map(|output| ((output - largest) / temperature).exp()).collect();
+    let sum: f32 = exps.iter().sum();
+    exps.into_iter().map(|exp| exp / sum).collect()
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
@@ -0,0 +1,129 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    categorize::CatNetwork,
+    error::DarjeelingError,
+    input::Input,
+    metrics::ClassificationMetrics,
+    prediction::Prediction,
+    types::Types
+};
+
+/// One network in a [`Cascade`], with the confidence it needs to answer without asking the next stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CascadeStage {
+    network: CatNetwork,
+    threshold: f32
+}
+
+impl CascadeStage {
+
+    pub fn network(&self) -> &CatNetwork {
+        &self.network
+    }
+
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+}
+
+/// Networks of growing cost that answer in turn. Every input goes to the first, cheapest network,
+/// and only inputs it isn't confident enough about are passed on to the next one, and so on.
+/// Confidences are calibrated when a network has a [calibrator](fn@crate::categorize::CatNetwork::set_calibrator),
+/// which makes the thresholds much easier to choose.
+///
+/// ## Examples
+/// ```ignore
+/// let cascade = Cascade::new(vec![(small_net, 0.9), (large_net, 0.0)]).unwrap();
+/// let answer = cascade.predict(&[5.1, 3.5, 1.4, 0.2]);
+/// println!("{} from stage {}", answer.prediction, answer.stage);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cascade {
+    stages: Vec<CascadeStage>
+}
+
+/// The answer of a [`Cascade`], and which stage gave it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CascadePrediction {
+    pub prediction: Prediction,
+    /// The index of the stage that answered
+    pub stage: usize,
+    /// Whether the answering stage met its threshold. Only the last stage answers without meeting it.
+    pub confident: bool
+}
+
+/// How a [`Cascade`] did on labeled data
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CascadeReport {
+    pub metrics: ClassificationMetrics,
+    /// How many inputs each stage answered
+    pub answered_by: Vec<usize>
+}
+
+impl Cascade {
+
+    /// ## Params
+    /// - Stages: Every network, cheapest first, with the confidence from 0 to 1 it needs to answer.
+    ///   The last stage answers everything that reaches it, so its threshold only decides whether its answers count as confident.
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There are no stages, a threshold isn't between 0 and 1, a network's categories aren't set,
+    /// or the networks don't all have the same inputs and categories
+    pub fn new(stages: Vec<(CatNetwork, f32)>) -> Result<Cascade, DarjeelingError> {
+        let first: &CatNetwork = match stages.first() {
+            Some((first, _)) => first,
+            None => return Err(DarjeelingError::InvalidInput("A cascade needs at least one stage".to_string()))
+        };
+        if first.categories().is_none() {
+            return Err(DarjeelingError::InvalidInput("Cascade stages need their categories set".to_string()));
+        }
+        if stages.iter().any(|(network, _)| network.input_size() != first.input_size() || network.categories() != first.categories()) {
+            return Err(DarjeelingError::InvalidInput("Cascade stages need the same inputs and categories".to_string()));
+        }
+        if let Some((i, (_, threshold))) = stages.iter().enumerate().find(|(_, (_, threshold))| !(0.0..=1.0).contains(threshold)) {
+            return Err(DarjeelingError::InvalidInput(format!("Stage {} has a threshold of {}, but thresholds are confidences from 0 to 1", i, threshold)));
+        }
+        Ok(Cascade { stages: stages.into_iter().map(|(network, threshold)| CascadeStage { network, threshold }).collect() })
+    }
+
+    pub fn stages(&self) -> &[CascadeStage] {
+        &self.stages
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Asks each stage in turn until one is confident enough, or the last stage answers
+    pub fn predict(&self, inputs: &[f32]) -> CascadePrediction {
+        let mut stage: usize = 0;
+        loop {
+            let prediction: Prediction = self.stages[stage].network.predict(inputs);
+            let confident: bool = prediction.confidence >= self.stages[stage].threshold;
+            if confident || stage == self.stages.len() - 1 {
+                return CascadePrediction { prediction, stage, confident };
+            }
+            stage += 1;
+        }
+    }
+
+    /// How well the cascade's answers match the answers, and how much of the data each stage had to answer,
+    /// for choosing thresholds that keep the expensive stages busy only as much as they need to be.
+    /// Inputs without an answer are skipped.
+    pub fn evaluate(&self, data: &[Input]) -> CascadeReport {
+        let mut answered_by: Vec<usize> = vec![0; self.stages.len()];
+        let answered: Vec<&Input> = data.iter().filter(|input| input.answer.is_some()).collect();
+        let predictions: Vec<Types> = answered.iter().map(|input| {
+            let answer: CascadePrediction = self.predict(&input.inputs);
+            answered_by[answer.stage] += 1;
+            answer.prediction.category
+        }).collect();
+        let truth: Vec<Types> = answered.iter().filter_map(|input| input.answer.clone()).collect();
+        CascadeReport { metrics: ClassificationMetrics::compute(&predictions, &truth), answered_by }
+    }
+}
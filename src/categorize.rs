@@ -1,26 +1,179 @@
 use crate::{
     DEBUG,
     error::DarjeelingError,
-    types::Types,
-    node::Node,
+    types::{Types, FLOAT_EPSILON},
+    node::{self, Node, Initialization},
     input::Input,
     activation::ActivationFunction,
+    scaler::Scaler,
+    encoder::{self, Encoder, Encoding},
+    augment::Augmenter,
+    dataset::{Dataset, DataSource, DataFingerprint},
+    metrics::{ClassificationMetrics, RegressionMetrics, MultiOutputMetrics, EvaluationReport, BootstrapReport, CategoryBreakdown},
+    prediction::{Prediction, TieBreak, Uncertainty},
+    ensemble::Ensemble,
+    history::{TrainingHistory, EpochRecord, LayerStats, LayerTiming, RunningStats, CompensatedSum, GradientAlarm, DeadNeuronPolicy, NeuronAction, NeuronEvent},
+    tensorboard::EventWriter,
+    prometheus::Metrics,
+    calibration::{self, Calibrator, CalibrationMethod, CalibrationReport},
+    audit::{AuditHook, PredictionAudit},
+    consolidation::Anchor,
+    cost::CostMatrix,
+    threshold::{self, DecisionThreshold, ThresholdObjective, ThresholdReport},
+    drift::{DataProfile, DriftReport},
+    schema::{self, Schema},
+    inspect::{ActivationSnapshot, DeadNeuron, DeadNeuronReport, WeightHistogram},
+    diff::{LayerDiff, ModelDiff},
+    visualize,
+    config::{TrainingConfig, Regularization, LearningRateSchedule, Reduction},
+    validation::{EarlyStopping, EarlyStoppingReport},
+    semisupervised::{PseudoLabeling, PseudoLabelingReport},
+    distillation::{Distillation, DistillationReport},
+    counterfactual::{Counterfactual, Mutability},
+    modelcard::{ModelCard, CardArchitecture, CardTraining, CardEvaluation},
+    darj,
     dbg_println,
     bench
 };
-use std::{fs, path::Path, fmt::{self, Debug}};
+use std::{fmt::{self, Debug}, path::PathBuf, sync::{mpsc::Sender, Arc}};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs, path::Path};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{config, experiment::{Experiment, ExperimentResult}, benchmark::BenchmarkReport};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(feature = "mixed-precision")]
+use crate::precision::MixedPrecision;
+#[cfg(all(feature = "signing", not(target_arch = "wasm32")))]
+use crate::signing::{self, ModelSigner, ModelVerifier};
+#[cfg(all(feature = "encryption", not(target_arch = "wasm32")))]
+use crate::encryption::ModelCipher;
 use serde::{Deserialize, Serialize};
-use rand::{Rng, seq::SliceRandom, thread_rng};
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng, seq::SliceRandom, thread_rng};
 // use rayon::prelude::*;
 
+/// The largest incoming weight of nodes added to a trained network
+const GROWN_WEIGHT: f32 = 0.05;
+
+/// The weight an [inserted layer](fn@CatNetwork::insert_layer) passes each value through sigmoid and tanh nodes with.
+/// The next layer's weights grow by its inverse over the activation function's slope, 400 times for sigmoid and 100 for tanh.
+const INSERTED_GAIN: f32 = 0.01;
+
+/// The most steps a [counterfactual](fn@CatNetwork::counterfactual) search takes before giving up
+const COUNTERFACTUAL_STEPS: usize = 1000;
+
+/// How far the features a counterfactual search changes most move each step, in scaled units
+const COUNTERFACTUAL_STEP: f32 = 0.02;
+
 /// The categorization Neural Network struct
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CatNetwork {
     node_array: Vec<Vec<Node>>,
     answer: Option<usize>,
     parameters: Option<u128>,
-    activation_function: ActivationFunction
+    activation_function: ActivationFunction,
+    scaler: Option<Scaler>,
+    encoders: Vec<Option<Encoder>>,
+    augmenter: Option<Augmenter>,
+    calibrator: Option<Calibrator>,
+    history: TrainingHistory,
+    collect_layer_stats: bool,
+    /// The error signals of every layer during the current epoch, only kept when collecting layer stats
+    #[serde(skip)]
+    err_sig_stats: Vec<RunningStats>,
+    /// The gradient norms of every input during the current epoch
+    #[serde(skip)]
+    grad_norms: RunningStats,
+    /// The squared error of every input during the current epoch
+    #[serde(skip)]
+    epoch_loss: CompensatedSum,
+    #[serde(default)]
+    gradient_alarm: Option<GradientAlarm>,
+    #[serde(skip)]
+    metrics_sender: Option<Sender<EpochRecord>>,
+    #[serde(skip)]
+    event_writer: Option<EventWriter>,
+    #[serde(skip)]
+    prometheus: Option<Arc<Metrics>>,
+    /// Where the model is saved, which isn't saved with it
+    #[serde(skip)]
+    output_dir: Option<PathBuf>,
+    /// How much each answer node's error counts, empty if they all count the same
+    #[serde(default)]
+    output_weights: Vec<f32>,
+    /// The penalties on large link weights
+    #[serde(default)]
+    regularization: Regularization,
+    /// The largest L2 norm each node's incoming link weights can have
+    #[serde(default)]
+    max_norm: Option<f32>,
+    /// How the learning rate changes from epoch to epoch
+    #[serde(default)]
+    schedule: LearningRateSchedule,
+    /// How sums are added up and epochs shuffled
+    #[serde(default)]
+    reduction: Reduction,
+    /// The data the network was last trained on, saved with the model
+    #[serde(default)]
+    data_fingerprint: Option<DataFingerprint>,
+    /// How a category is picked when answer nodes tie
+    #[serde(default)]
+    tie_break: TieBreak,
+    /// How far apart a float answer and a float category can be for the answer to still belong to the category
+    #[serde(default = "default_float_tolerance")]
+    float_tolerance: f32,
+    /// What each kind of mistake costs, which makes predictions pick the cheapest answer instead of the brightest
+    #[serde(default)]
+    cost_matrix: Option<CostMatrix>,
+    /// Answers with one category whenever it's likely enough, ahead of the cost matrix
+    #[serde(default)]
+    threshold: Option<DecisionThreshold>,
+    /// What the training data looked like, for checking later batches for drift
+    #[serde(default)]
+    data_profile: Option<DataProfile>,
+    /// The names of the inputs
+    #[serde(default)]
+    schema: Option<Schema>,
+    /// Called with every prediction
+    #[serde(skip)]
+    audit_hook: Option<AuditHook>,
+    /// The weights to pull back towards while [updating](fn@update) on new data
+    #[serde(skip)]
+    anchor: Option<Anchor>,
+    /// What to do with hidden nodes that stay dead while training
+    #[serde(default)]
+    dead_neuron_policy: Option<DeadNeuronPolicy>,
+    /// The outputs of every node during the current epoch, only kept with a dead neuron policy
+    #[serde(skip)]
+    output_stats: Vec<Vec<RunningStats>>,
+    /// How many epochs in a row each node has been dead
+    #[serde(skip)]
+    dead_epochs: Vec<Vec<usize>>,
+    /// Evaluated at the end of every epoch, in the same order every time
+    #[serde(skip)]
+    holdout: Vec<Input>,
+    /// Whether the order every epoch's inputs were shuffled into is recorded in the history
+    #[serde(default)]
+    record_order: bool,
+    /// The order of the current epoch's inputs, only kept when recording it
+    #[serde(skip)]
+    epoch_order: Vec<usize>,
+    /// Whether the time every layer takes to train is recorded in the history
+    #[serde(default)]
+    profile_layers: bool,
+    /// The time every layer has taken during the current epoch, only kept when profiling them
+    #[serde(skip)]
+    layer_timings: Vec<LayerTiming>,
+    /// Rounds activations and error signals to f16 while training, if set
+    #[cfg(feature = "mixed-precision")]
+    #[serde(skip)]
+    mixed_precision: Option<MixedPrecision>
 }
+
+fn default_float_tolerance() -> f32 {
+    FLOAT_EPSILON
+}
+
 #[warn(clippy::unwrap_in_result)]
 
 impl CatNetwork {
@@ -50,7 +203,7 @@ impl CatNetwork {
     /// let mut net: CatNetwork = CatNetwork::new(inputs, hidden, answer, hidden_layers, ActivationFunction::Sigmoid);
     /// ```
     pub fn new(input_num: i32, hidden_num: i32, answer_num: i32, hidden_layers: i32, activation_function: ActivationFunction) -> CatNetwork {
-        let mut net: CatNetwork = CatNetwork { node_array: vec![], answer: Some(hidden_layers as usize + 1), parameters: None, activation_function};
+        let mut net: CatNetwork = CatNetwork { node_array: vec![], answer: Some(hidden_layers as usize + 1), parameters: None, activation_function, scaler: None, encoders: vec![], augmenter: None, calibrator: None, history: TrainingHistory::new(), collect_layer_stats: false, err_sig_stats: vec![], grad_norms: RunningStats::default(), epoch_loss: CompensatedSum::default(), gradient_alarm: None, metrics_sender: None, event_writer: None, prometheus: None, output_dir: None, output_weights: vec![], regularization: Regularization::default(), max_norm: None, schedule: LearningRateSchedule::Constant, reduction: Reduction::Fast, data_fingerprint: None, tie_break: TieBreak::First, float_tolerance: FLOAT_EPSILON, cost_matrix: None, threshold: None, data_profile: None, schema: None, audit_hook: None, anchor: None, dead_neuron_policy: None, output_stats: vec![], dead_epochs: vec![], holdout: vec![], record_order: false, epoch_order: vec![], profile_layers: false, layer_timings: vec![], #[cfg(feature = "mixed-precision")] mixed_precision: None };
         let mut rng = rand::thread_rng();
         net.node_array.push(vec![]);    
         (0..input_num).into_iter().for_each(|_| {
@@ -95,12 +248,71 @@ impl CatNetwork {
         net
     }
 
+    /// Makes a network with the hidden layers and activation function of a config
+    /// 
+    /// ## Params
+    /// - Inputs: The number of sensors in the input layer
+    /// - Answer: The number of answer nodes, or possible categories
+    /// - Config: The rest of the architecture
+    pub fn from_config(input_num: i32, answer_num: i32, config: &TrainingConfig) -> CatNetwork {
+        let mut net: CatNetwork = CatNetwork::new(input_num, config.hidden_num, answer_num, config.hidden_layers, config.activation_function);
+        net.set_regularization(config.regularization);
+        net.set_schedule(config.schedule);
+        net.set_reduction(config.reduction);
+        net
+    }
+
+    /// Builds a network sized for the data, with one sensor for every input value and one answer node for every distinct answer.
+    /// The answer nodes are named after the answers, [sorted](fn@Dataset::sorted_categories), so [`categories`](fn@categories)
+    /// gives the categories to train with.
+    /// 
+    /// ## Params
+    /// - Data: The training data
+    /// - Hidden: The number of hidden nodes per hidden layer
+    /// - Hidden Layers: The number of different hidden layers
+    /// - Activation Function: Which activation function is used by the network
+    /// 
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// The data is empty, the inputs aren't all the same length, or none of them have an answer
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, input::Input, types::Types};
+    /// 
+    /// let data = vec![
+    ///     Input::new(vec![0.0, 1.0, 0.5], Some(Types::String("a".to_string()))),
+    ///     Input::new(vec![1.0, 0.0, 0.5], Some(Types::String("b".to_string())))
+    /// ];
+    /// let net = CatNetwork::new_from_data(&data, 4, 1, ActivationFunction::Sigmoid).unwrap();
+    /// assert_eq!(net.input_size(), 3);
+    /// assert_eq!(net.categories().unwrap().len(), 2);
+    /// ```
+    pub fn new_from_data(data: &[Input], hidden_num: i32, hidden_layers: i32, activation_function: ActivationFunction) -> Result<CatNetwork, DarjeelingError> {
+        let input_num: usize = match data.first() {
+            Some(input) => input.inputs.len(),
+            None => return Err(DarjeelingError::InvalidTrainingData("Can't size a network for empty data".to_string()))
+        };
+        if let Some((line, input)) = data.iter().enumerate().find(|(_, input)| input.inputs.len() != input_num) {
+            return Err(DarjeelingError::InvalidTrainingData(format!("Input {} has {} values, but the first input has {}", line, input.inputs.len(), input_num)));
+        }
+        let categories: Vec<Types> = Dataset::new(data.to_vec()).sorted_categories();
+        if categories.is_empty() {
+            return Err(DarjeelingError::InvalidTrainingData("None of the inputs have an answer".to_string()));
+        }
+
+        let mut net: CatNetwork = CatNetwork::new(input_num as i32, hidden_num, categories.len() as i32, hidden_layers, activation_function);
+        net.set_categories(categories);
+        Ok(net)
+    }
+
     /// Trains the neural network model to be able to categorize items in a dataset into given categories
     /// 
     /// ## Params
     /// - Data: List of inputs
     /// - Categories: List of Strings, each denoting an answer category. 
-    /// The number of answer nodes should be the same of the number of categories
+    /// The number of answer nodes should be the same of the number of categories.
+    ///   Empty uses every answer in the data, [sorted](fn@Dataset::sorted_categories) so the same data always gives the same order.
     /// - Learning Rate: The modifier that is applied to link weights as they're adjusted.
     /// Try fiddling with this one, but -1.5 - 1.5 is recommended to start.
     /// - Name: The name of the network
@@ -112,15 +324,21 @@ impl CatNetwork {
     /// - name of the model that this neural network trained(the name parameter with a random u32 appended)
     /// some if write is true, none is write is false
     /// - the error percentage of the last epoch
-    /// - the mse of the training
+    /// - the mean squared error of the last epoch
     /// 
     /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The categories were left empty, and the data doesn't have one answer for every answer node
     /// - ### WriteModelFailed
     /// There was a problem when saving the model to a file
     /// - ### ModelNameAlreadyExists
     /// The random model name chosen already exists
     /// 
     /// Change the name or retrain
+    /// - ### WriteDataFailed
+    /// The [event writer](fn@set_event_writer) couldn't write an epoch
+    /// - ### ExplodingGradient
+    /// A [gradient alarm](fn@set_gradient_alarm) that aborts went off
     /// - ### UnknownError
     /// Not sure what happened, but something failed
     /// 
@@ -129,6 +347,7 @@ impl CatNetwork {
     /// Or contact me at elocolburn@comcast.net
     /// 
     /// ## Examples
+    /// A runnable version is in `examples/xor.rs`, try it with `cargo run --example xor`
     /// ```ignore
     /// use darjeeling::{
     /// categorize::CatNetwork,
@@ -165,232 +384,2474 @@ impl CatNetwork {
         let mut sum = 0.0;
         let mut count = 0.0;
         let mut err_percent = 0.0;
-        let mut mse = 0.0;
 
+        let categories: Vec<Types> = self.discover_categories(data, categories)?;
+        self.data_fingerprint = Some(DataFingerprint::of(data));
         println!("Categrize");
         bench!(self.categorize(categories));
+        self.history = TrainingHistory::new();
         
         while err_percent < target_err_percent {
-            count = 0.0;
-            sum = 0.0;
-            data.shuffle(&mut thread_rng());
-
-            for line in 0..data.len() {
-                dbg_println!("Training Checkpoint One Passed");
-                
-                println!("Assign");
-                bench!(self.assign_answers(&mut data[line]));
+            // let _old_err_percent = err_percent;
+            let mut rng: Box<dyn RngCore> = self.epoch_rng(epochs as usize);
+            err_percent = self.train_epoch(data, self.schedule.learning_rate(learning_rate, epochs as usize), &mut epochs, &mut sum, &mut count, &mut rng)?;
+            //if err_percent - old_err_percent < 0.00000001 { break; }
+        }
+        self.data_profile = Some(self.profile(data));
 
-                println!("Push");
-                bench!(self.push_downstream(data, line));
+        self.finish_training(name, write, sum, count, err_percent, epochs)
+    }
 
-                dbg_println!("Sum: {:?} Count: {:?}", sum, count);
+    /// [`learn`](fn@learn) with its settings taken from a config instead of a long list of arguments.
+    /// The config's output directory replaces the network's if it has one.
+    /// 
+    /// ## Params
+    /// - Data: List of inputs
+    /// - Categories: The categories the inputs can be put in
+    /// - Config: The learning rate, target error percent, and whether and where the model is saved
+    /// - Name: The model name
+    /// 
+    /// ## Returns
+    /// The same as [`learn`](fn@learn)
+    /// 
+    /// ## Err
+    /// The same as [`learn`](fn@learn)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// let config = TrainingConfig::default().with_hidden_num(2).with_learning_rate(1.0);
+    /// let mut net = CatNetwork::from_config(2, 2, &config);
+    /// let (model_name, error_percentage, mse) = net.learn_with_config(&mut data, categories, &config, "xor").unwrap();
+    /// ```
+    pub fn learn_with_config(&mut self, data: &mut Vec<Input>, categories: Vec<Types>, config: &TrainingConfig, name: &str) -> Result<(Option<String>, f32, f32), DarjeelingError> {
+        if config.output_dir.is_some() {
+            self.output_dir = config.output_dir.clone();
+        }
+        self.learn(data, categories, config.learning_rate, name, config.target_err_percent, config.write)
+    }
 
-                println!("Analysis");
-                bench!(self.self_analysis(&mut Some(epochs), &mut sum, &mut count, data, &mut mse, line));
+    /// Trains on part of the data while watching the loss on the rest, and stops once that loss hasn't improved for a while.
+    /// The network is left with the weights from its best epoch, and its history keeps every epoch.
+    /// 
+    /// ## Params
+    /// - Data: Every input. Some of each category is held back for validation.
+    /// - Categories: The categories the inputs can be put in. Empty uses every answer in the data, sorted.
+    /// - Learning Rate: The modifier that is applied to link weights as they're adjusted
+    /// - Max Epochs: Stops training after this many epochs, even if the validation loss is still improving
+    /// - Early Stopping: How much data is held back, and how long to wait for the loss to improve
+    /// 
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// There's no data left to train on or to validate with after the split
+    /// - ### ArchitectureMismatch
+    /// The categories were left empty, and the data doesn't have one answer for every answer node
+    /// - The rest are the same as [`step_epoch`](fn@step_epoch)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// let report = net.learn_with_early_stopping(&dataset, vec![], 0.5, 1000, &EarlyStopping::new(0.2, 10, 42)).unwrap();
+    /// ```
+    pub fn learn_with_early_stopping(&mut self, data: &Dataset, categories: Vec<Types>, learning_rate: f32, max_epochs: usize, early_stopping: &EarlyStopping) -> Result<EarlyStoppingReport, DarjeelingError> {
+        let categories: Vec<Types> = self.discover_categories(data.inputs(), categories)?;
+        self.data_fingerprint = Some(data.fingerprint());
+        let mut rng = StdRng::seed_from_u64(early_stopping.seed);
+        let (train, validation) = data.stratified_split(early_stopping.validation_fraction, &mut rng);
+        if train.is_empty() || validation.is_empty() {
+            return Err(DarjeelingError::InvalidTrainingData(format!("Splitting {} inputs left {} to train on and {} to validate with", data.len(), train.len(), validation.len())));
+        }
+        let (train_size, validation_size) = (train.len(), validation.len());
+        let mut train: Vec<Input> = train.into_inputs();
+        self.start_training(categories);
 
-                dbg_println!("Sum: {:?} Count: {:?}", sum, count);
-                
-                println!("Backpropogate");
-                bench!(self.backpropogate(learning_rate));
+        // The weights of the best epoch, the epoch, and its validation loss
+        let mut best: (Vec<Vec<Node>>, usize, f32) = (self.node_array.clone(), 0, f32::INFINITY);
+        let mut epochs: usize = 0;
+        while epochs < max_epochs && epochs - best.1 <= early_stopping.patience {
+            self.step_epoch_with_rng(&mut train, learning_rate, &mut rng)?;
+            epochs += 1;
+            let loss: f32 = self.evaluate(validation.inputs()).loss;
+            if loss < best.2 {
+                best = (self.node_array.clone(), epochs, loss);
             }
+        }
 
-            // let _old_err_percent = err_percent;
-            err_percent = (sum/count) * 100.0;
-            epochs += 1.0;
-            println!("Epoch: {:?}", epochs);
-            println!("Training Accuracy: {:?}", err_percent);
-            //if err_percent - old_err_percent < 0.00000001 { break; }
+        self.node_array = best.0;
+        Ok(EarlyStoppingReport { best_epoch: best.1, best_loss: best.2, epochs, train_size, validation_size })
+    }
 
+    /// Trains on labeled and unlabeled data together, pseudo-labeling the unlabeled inputs the network is confident about
+    /// and mixing them into the epochs that follow
+    /// 
+    /// ## Params
+    /// - Data: Every input, with or without an answer
+    /// - Categories: The categories the inputs can be put in. Empty uses every answer in the data, sorted.
+    /// - Learning Rate: The modifier that is applied to link weights as they're adjusted
+    /// - Epochs: How many epochs to train for
+    /// - Pseudo Labeling: How confident the network has to be, and how often the unlabeled data is labeled again
+    /// 
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// None of the inputs have an answer, or the threshold isn't between 0 and 1
+    /// - ### ArchitectureMismatch
+    /// The categories were left empty, and the data doesn't have one answer for every answer node
+    /// - The rest are the same as [`step_epoch`](fn@step_epoch)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// let report = net.learn_semi_supervised(&data, vec![], 0.5, 100, &PseudoLabeling::new(0.95, 10, 5, 42)).unwrap();
+    /// ```
+    pub fn learn_semi_supervised(&mut self, data: &[Input], categories: Vec<Types>, learning_rate: f32, epochs: usize, pseudo_labeling: &PseudoLabeling) -> Result<PseudoLabelingReport, DarjeelingError> {
+        let (labeled, unlabeled): (Vec<Input>, Vec<Input>) = data.iter().cloned().partition(|input| input.answer.is_some());
+        if labeled.is_empty() {
+            return Err(DarjeelingError::InvalidTrainingData("Semi-supervised training needs some inputs with answers".to_string()));
         }
-        let mut model_name: Option<String> = None;
-        if write {
-            match self.write_model(&name) {
-                Ok(m_name) => {
-                    model_name = Some(m_name);
-                },
-                Err(err) => return Err(err),
-            }
+        if !(0.0..=1.0).contains(&pseudo_labeling.threshold) {
+            return Err(DarjeelingError::InvalidTrainingData(format!("The pseudo-labeling threshold is a confidence from 0 to 1, not {}", pseudo_labeling.threshold)));
         }
+        let categories: Vec<Types> = self.discover_categories(&labeled, categories)?;
+        self.data_fingerprint = Some(DataFingerprint::of(&labeled));
+        self.start_training(categories);
+        let mut rng = StdRng::seed_from_u64(pseudo_labeling.seed);
 
-        println!("Training: Finished with accuracy of {:?}/{:?} or {:?} percent after {:?} epochs\nmse: {}", sum, count, err_percent, epochs, mse);
-
-        Ok((model_name, err_percent, mse))
+        let mut train: Vec<Input> = labeled.clone();
+        let mut pseudo_labeled: Vec<usize> = vec![];
+        for epoch in 0..epochs {
+            if matches!(epoch.checked_sub(pseudo_labeling.warmup_epochs), Some(since) if since % pseudo_labeling.relabel_every.max(1) == 0) {
+                let confident: Vec<Input> = unlabeled.iter().filter_map(|input| {
+                    let prediction: Prediction = self.predict(&input.inputs);
+                    (prediction.confidence >= pseudo_labeling.threshold).then(|| Input::new(input.inputs.clone(), Some(prediction.category)))
+                }).collect();
+                pseudo_labeled.push(confident.len());
+                train = labeled.iter().cloned().chain(confident).collect();
+            }
+            self.step_epoch_with_rng(&mut train, learning_rate, &mut rng)?;
+        }
+        Ok(PseudoLabelingReport { epochs, labeled_size: labeled.len(), unlabeled_size: unlabeled.len(), pseudo_labeled })
     }
 
-    /// Tests a pretrained model
-    pub fn test(mut data: Vec<Input>, categories: Vec<Types>, model_name: String) -> Result<Vec<Types>, DarjeelingError> {
-        let mut sum = 0.0;
-        let mut count = 0.0;
-        // let mut category: Option<Types> = None;
-        let mut answers: Vec<Types> = vec![];
-        let mut mse = 0.0;
-
-        let mut net: CatNetwork = match CatNetwork::read_model(model_name.clone()) {
-
-            Ok(net) => net,
-            Err(error) => return Err(DarjeelingError::ReadModelFunctionFailed(model_name, Box::new(error)))
+    /// Trains this network as a student of a larger trained teacher, towards the teacher's [softened answers](Distillation),
+    /// to make a compact model that answers nearly the same. Training starts over, with the teacher's categories,
+    /// and the student takes the teacher's scaler, encoders and schema so it reads inputs the same way.
+    ///
+    /// ## Params
+    /// - Teacher: The trained network to learn from, which needs its categories
+    /// - Data: The inputs to learn from, with or without answers. The teacher answers every one.
+    /// - Learning Rate: The modifier that is applied to link weights as they're adjusted
+    /// - Distillation: The temperature, how much the teacher's answers count, and how long to train
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The teacher doesn't have categories, the temperature isn't above 0, or alpha isn't between 0 and 1
+    /// - ### ArchitectureMismatch
+    /// The student doesn't have as many sensors and answer nodes as the teacher
+    /// - ### InvalidTrainingData
+    /// There's no data
+    /// - The rest are the same as [`step_epoch`](fn@step_epoch)
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// let mut student = CatNetwork::new(4, 4, 3, 1, ActivationFunction::Sigmoid);
+    /// let report = student.distill(&teacher, &data, 0.5, &Distillation::new(2.0, 0.7, 50, 42)).unwrap();
+    /// student.write_model("iris_small").unwrap();
+    /// ```
+    pub fn distill(&mut self, teacher: &CatNetwork, data: &[Input], learning_rate: f32, distillation: &Distillation) -> Result<DistillationReport, DarjeelingError> {
+        let categories: Vec<Types> = match teacher.categories() {
+            Some(categories) => categories,
+            None => return Err(DarjeelingError::InvalidInput("The teacher needs its categories to be distilled".to_string()))
         };
-
-        for node in 0..net.node_array[net.answer.unwrap()].len() {
-            net.node_array[net.answer.unwrap()][node].category = Some(categories[node].clone());
-            dbg_println!("{:?}", net.node_array[net.answer.unwrap()][node].category);
+        if !(distillation.temperature > 0.0 && distillation.temperature.is_finite()) {
+            return Err(DarjeelingError::InvalidInput(format!("The temperature has to be above 0, not {}", distillation.temperature)));
+        }
+        if !(0.0..=1.0).contains(&distillation.alpha) {
+            return Err(DarjeelingError::InvalidInput(format!("Alpha is a weight from 0 to 1, not {}", distillation.alpha)));
+        }
+        if self.input_size() != teacher.input_size() || self.output_size() != teacher.output_size() {
+            return Err(DarjeelingError::ArchitectureMismatch(format!(
+                "The student has {} sensors and {} answer nodes, but the teacher has {} and {}", self.input_size(), self.output_size(), teacher.input_size(), teacher.output_size()
+            )));
+        }
+        if data.is_empty() {
+            return Err(DarjeelingError::InvalidTrainingData("There's no data to distill the teacher with".to_string()));
         }
 
-        for line in 0..data.len() {
-            dbg_println!("Testing Checkpoint One Passed");
-            if data[line].answer.is_some() {
-                net.assign_answers(&mut data[line]);
-            }
-            // Do we actually want to do this?
-            net.push_downstream(&mut data, line);
-            dbg_println!("Sum: {:?} Count: {:?}", sum, count);            
-            answers.push((
-                Some(net.self_analysis(&mut None, &mut sum, &mut count, &mut data, &mut mse, line).0))
-                .clone().expect("Wrapped in Some()"));
+        let mut train: Vec<Input> = data.iter().map(|input| {
+            let soft: Vec<f32> = calibration::softmax(&teacher.forward(&input.inputs), distillation.temperature);
+            let hard: Option<Vec<f32>> = match &input.answer {
+                Some(Types::Vector(_)) | None => None,
+                Some(answer) => categories.iter().position(|category| category.approx_eq(answer, self.float_tolerance))
+                    .map(|answer| (0..categories.len()).map(|i| if i == answer { 1.0 } else { 0.0 }).collect())
+            };
+            let targets: Vec<f32> = match hard {
+                Some(hard) => soft.iter().zip(hard).map(|(soft, hard)| distillation.alpha * soft + (1.0 - distillation.alpha) * hard).collect(),
+                None => soft
+            };
+            Input::new(input.inputs.clone(), Some(Types::Vector(targets)))
+        }).collect();
 
-            dbg_println!("Sum: {:?} Count: {:?}", sum, count);
+        self.scaler = teacher.scaler.clone();
+        self.encoders = teacher.encoders.clone();
+        self.schema = teacher.schema.clone();
+        self.start_training(categories);
+        let mut rng = StdRng::seed_from_u64(distillation.seed);
+        for _ in 0..distillation.epochs {
+            self.step_epoch_with_rng(&mut train, learning_rate, &mut rng)?;
+        }
 
-            // println!("Correct answer: {:?}", data[line].answer)
+        let agreeing: usize = data.iter().filter(|input| self.predict_category(&input.inputs) == teacher.predict_category(&input.inputs)).count();
+        Ok(DistillationReport {
+            epochs: distillation.epochs,
+            agreement: agreeing as f32 / data.len() as f32,
+            teacher_parameters: teacher.flat_weights().len(),
+            student_parameters: self.flat_weights().len()
+        })
+    }
+
+    /// The categories given to training, or every answer in the data if none were given
+    /// 
+    /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The data doesn't have one answer for every answer node
+    fn discover_categories(&self, data: &[Input], categories: Vec<Types>) -> Result<Vec<Types>, DarjeelingError> {
+        if !categories.is_empty() {
+            return Ok(categories);
+        }
+        let mut dataset: Dataset = Dataset::new(data.to_vec());
+        dataset.set_float_tolerance(self.float_tolerance);
+        let discovered: Vec<Types> = dataset.sorted_categories();
+        if discovered.len() != self.output_size() {
+            return Err(DarjeelingError::ArchitectureMismatch(format!("The data has {} answers for {} answer nodes", discovered.len(), self.output_size())));
         }
+        Ok(discovered)
+    }
 
-        // let _old_err_percent = err_percent;
-        let err_percent: f32 = (sum/count) * 100.0;
-        mse /= count;
-        println!("Testing: Finished with accuracy of {:?}/{:?} or {:?} percent\nMSE: {}", sum, count, err_percent, mse);
+    /// Trains a new network from an [experiment](Experiment) file, the same way every time it's run
+    /// 
+    /// ## Params
+    /// - Path: The experiment's JSON file
+    /// 
+    /// ## Err
+    /// The same as [`Experiment::read`](fn@Experiment::read) and [`Experiment::run`](fn@Experiment::run)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// let result = CatNetwork::train_from_config("experiments/xor.json").unwrap();
+    /// println!("{:?} after {} epochs", result.model_name, result.epochs);
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn train_from_config(path: &str) -> Result<ExperimentResult, DarjeelingError> {
+        Experiment::read(path)?.run()
+    }
 
-        Ok(answers)
+    /// Gets the network ready to train every answer node as its own continuous output, against vector answers.
+    /// The answer nodes are named after the outputs, and trained one epoch at a time with [`step_epoch`](fn@step_epoch).
+    /// 
+    /// ## Params
+    /// - Outputs: The name of every output, one for each answer node
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// There isn't one name for every answer node
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input, types::Types};
+    /// 
+    /// let mut net = CatNetwork::new(1, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// net.start_regression(&["x", "y"]).unwrap();
+    /// let mut data = vec![Input::new(vec![0.5], Some(Types::Vector(vec![0.2, 0.8])))];
+    /// net.step_epoch(&mut data, 0.5).unwrap();
+    /// assert_eq!(net.evaluate_regression_heads(&data).heads.len(), 2);
+    /// ```
+    pub fn start_regression(&mut self, outputs: &[&str]) -> Result<(), DarjeelingError> {
+        if outputs.len() != self.output_size() {
+            return Err(DarjeelingError::InvalidInput(format!("There are {} output names for {} answer nodes", outputs.len(), self.output_size())));
+        }
+        self.start_training(outputs.iter().map(|output| Types::String(output.to_string())).collect());
+        Ok(())
     }
 
-    /// Assigns categories to answer nodes based on a list of given categories
-    fn categorize(&mut self, categories: Vec<Types>) {
-        let mut count: usize = 0;
-        self.node_array[self.answer.unwrap()].iter_mut().for_each(|node| {
-            node.category = Some(categories[count].clone());
-            count += 1;
-        });
+    /// Pre-trains the hidden layers without answers, one at a time from the inputs up, before training with answers.
+    /// Each hidden layer learns to encode the outputs of the layer below it well enough that they can be rebuilt from it,
+    /// like the middle of an autoencoder, so it starts supervised training already picking out the structure of the data.
+    /// This helps most when there's plenty of unlabeled data but only a little labeled data.
+    /// The answer layer isn't touched.
+    ///
+    /// ## Params
+    /// - Data: Inputs to learn from. Answers are ignored, so labeled and unlabeled data can be mixed.
+    /// - Learning Rate: The modifier that is applied to link weights as they're adjusted
+    /// - Epochs: How many times each layer goes over the data
+    ///
+    /// ## Returns
+    /// How far each hidden layer's rebuilt inputs were from the real ones in its last epoch, as a mean squared error
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There's no data, or an input isn't the network's input size
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// let mut net = CatNetwork::new(20, 8, 3, 2, ActivationFunction::Sigmoid);
+    /// net.pretrain(&unlabeled, 0.5, 20)?;
+    /// net.learn(&mut labeled, categories, 0.5, "pretrained", 99.0, true)?;
+    /// ```
+    pub fn pretrain(&mut self, data: &[Input], learning_rate: f32, epochs: usize) -> Result<Vec<f32>, DarjeelingError> {
+        self.pretrain_with_rng(data, learning_rate, epochs, &mut thread_rng())
     }
-    
-    fn assign_answers(&mut self, input: &mut Input) {
-        let _ = self.node_array[self.answer.unwrap()].iter_mut().for_each(|mut node| {
-            // println!("{:?}", input);
-            if node.category.as_ref().unwrap() == input.answer.as_ref().unwrap() {
-                node.correct_answer = Some(1.0);
-            } else {
-                node.correct_answer = Some(0.0);
+
+    /// [`pretrain`](fn@pretrain), shuffling with the given generator so seeded runs repeat exactly
+    pub(crate) fn pretrain_with_rng<R: Rng>(&mut self, data: &[Input], learning_rate: f32, epochs: usize, rng: &mut R) -> Result<Vec<f32>, DarjeelingError> {
+        if data.is_empty() {
+            return Err(DarjeelingError::InvalidInput("Pre-training needs at least one input".to_string()));
+        }
+        if let Some(input) = data.iter().find(|input| input.inputs.len() != self.input_size()) {
+            return Err(DarjeelingError::InvalidInput(format!("An input has {} values, but the network has {} sensors", input.inputs.len(), self.input_size())));
+        }
+
+        let mut losses: Vec<f32> = vec![];
+        for layer in 1..self.answer.unwrap() {
+            // Each layer learns from what the layers below it, already pre-trained, make of the data
+            let below: Vec<Vec<f32>> = data.iter().map(|input| self.forward_layers(&input.inputs).swap_remove(layer - 1)).collect();
+            let mut reconstruction: Vec<Input> = below.into_iter().map(|values| Input::new(values.clone(), Some(Types::Vector(values)))).collect();
+            let (inputs, hidden) = (self.node_array[layer - 1].len(), self.node_array[layer].len());
+
+            let mut autoencoder = CatNetwork::new(inputs as i32, hidden as i32, inputs as i32, 1, self.activation_function);
+            // The decoder starts from the given generator as well, so seeded runs repeat exactly
+            autoencoder.randomize_weights(rng);
+            autoencoder.node_array[1] = self.node_array[layer].clone();
+            let mut loss: f32 = 0.0;
+            for _ in 0..epochs {
+                reconstruction.shuffle(rng);
+                loss = reconstruction.iter().map(|input| autoencoder.train_step(input, learning_rate)).sum::<f32>() / reconstruction.len() as f32;
             }
-        });
+            node::copy_weights(std::slice::from_mut(&mut self.node_array[layer]), &autoencoder.node_array[1..2])?;
+            losses.push(loss);
+        }
+        Ok(losses)
     }
 
-    /// Passes in data to the sensors, pushs data 'downstream' through the network
-    fn push_downstream(&mut self, data: &mut Vec<Input>, line: usize) {
-        // Passes in data for input layer
-        (0..self.node_array[0].len()).into_iter().for_each(|i| {
-            let input: f32 = data[line].inputs[i];
-            self.node_array[0][i].cached_output = Some(input);
-        });
-
-        // Feed-forward values for hidden and output layers
-        (1..self.node_array.len()).into_iter().for_each(|layer_i| {
-            (0..self.node_array[layer_i].len()).into_iter().for_each(|node_i| {
-                (0..self.node_array[layer_i - 1].len()).into_iter().for_each(|prev_node_i| {
-                    // self.node_array[layer][node].link_vals.push(self.node_array[layer-1][prev_node].cached_output.unwrap());
-                    self.node_array[layer_i][node_i].link_vals[prev_node_i] = Some(self.node_array[layer_i-1][prev_node_i].cached_output.unwrap());
-                    // I think this line needs to be un-commented
-                    self.node_array[layer_i][node_i].output(&self.activation_function);
-                    if layer_i == self.answer.unwrap() { dbg_println!("Ran output on answer {:?}", self.node_array[layer_i][node_i].cached_output); }
-                });
-                self.node_array[layer_i][node_i].output(&self.activation_function);
-            });
-        });
+    /// Gets the network ready to be trained one epoch at a time with [`step_epoch`](fn@step_epoch).
+    /// Clears the training history, so the next step is epoch 1.
+    pub fn start_training(&mut self, categories: Vec<Types>) {
+        self.categorize(categories);
+        self.history = TrainingHistory::new();
     }
 
-    /// Analyses the chosen answer node's result.
-    /// Also increments sum and count
-    fn self_analysis<'b>(
-        &'b self, 
-        epochs: &mut Option<f32>, 
-        sum: &'b mut f32, 
-        count: &'b mut f32, 
-        data: &mut Vec<Input>, 
-        mse: &mut f32, 
-        line: usize
-    ) -> (Types, Option<f32>) {
-        dbg_println!("answer {}", self.answer.unwrap());
-        dbg_println!("largest index {}", self.largest_node());
-        dbg_println!("{:?}", self);
-        let brightest_node: &Node = &self.node_array[self.answer.unwrap()][self.largest_node()];
-        let brightness: f32 = brightest_node.cached_output.unwrap();
+    /// Trains the network for a single epoch over the data, in a random order.
+    /// Unlike [`learn`](fn@learn), this returns after every epoch,
+    /// so the caller decides when to stop and can do other work in between,
+    /// like yielding to an async runtime or checking whether training was cancelled.
+    /// 
+    /// Call [`start_training`](fn@start_training) first, and save the model yourself when you're done.
+    /// 
+    /// ## Params
+    /// - Data: The training data
+    /// - Learning Rate: The modifier that is applied to link weights as they're adjusted
+    /// 
+    /// ## Returns
+    /// The epoch's record, which is also added to the [history](fn@history)
+    /// 
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The [event writer](fn@set_event_writer) couldn't write the epoch
+    /// - ### ExplodingGradient
+    /// A [gradient alarm](fn@set_gradient_alarm) that aborts went off
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// net.start_training(categories);
+    /// loop {
+    ///     let record = net.step_epoch(&mut data, 0.5)?;
+    ///     if record.accuracy >= 0.99 || cancelled.load(Ordering::Relaxed) {
+    ///         break;
+    ///     }
+    ///     // Lets the rest of the service run between epochs
+    ///     tokio::task::yield_now().await;
+    /// }
+    /// net.write_model("xor")?;
+    /// ```
+    pub fn step_epoch(&mut self, data: &mut [Input], learning_rate: f32) -> Result<EpochRecord, DarjeelingError> {
+        let mut rng: Box<dyn RngCore> = self.epoch_rng(self.history.len());
+        self.step_epoch_with_rng(data, learning_rate, &mut rng)
+    }
 
-        if !(epochs.is_none()) { // This won't happen during testing
-            if epochs.unwrap() % 10.0 == 0.0 && epochs.unwrap() != 0.0 {
-                println!("\n-------------------------\n");
-                println!("Epoch: {:?}", epochs);
-                println!("Category: {:?} \nBrightness: {:?}", brightest_node.category.as_ref().unwrap(), brightness);
-                if DEBUG {
-                    let dimest_node: &Node = &self.node_array[self.answer.unwrap()][self.node_array[self.answer.unwrap()].len()-1-self.largest_node()];
-                    println!("Chosen category: {:?} \nDimest Brightness: {:?}", dimest_node.category.as_ref().unwrap(), dimest_node.cached_output.unwrap());
-                }
-            }
+    /// The generator an epoch shuffles with, counting from 0.
+    /// A [deterministic reduction](Reduction::Deterministic) seeds it from the epoch, so the epoch shuffles the same way however training got to it.
+    fn epoch_rng(&self, epoch: usize) -> Box<dyn RngCore> {
+        match self.reduction {
+            Reduction::Fast => Box::new(thread_rng()),
+            Reduction::Deterministic { seed } => Box::new(StdRng::seed_from_u64(seed.wrapping_add(epoch as u64)))
         }
+    }
 
-        dbg_println!("Category: {:?} \nBrightness: {:?}", brightest_node.category.as_ref().unwrap(), brightness);
-        if data[line].answer.is_some() {
-            if brightest_node.category.as_ref().unwrap().eq(&data[line].answer.as_ref().unwrap()) {
-                dbg_println!("Sum++");
-                *sum += 1.0;
+    /// [`step_epoch`](fn@step_epoch), shuffling with the given generator so seeded runs repeat exactly
+    pub(crate) fn step_epoch_with_rng<R: Rng>(&mut self, data: &mut [Input], learning_rate: f32, rng: &mut R) -> Result<EpochRecord, DarjeelingError> {
+        let mut epochs: f32 = self.history.len() as f32;
+        let learning_rate: f32 = self.schedule.learning_rate(learning_rate, self.history.len());
+        self.train_epoch(data, learning_rate, &mut epochs, &mut 0.0, &mut 0.0, rng)?;
+        Ok(self.history.last().expect("The epoch was just recorded").clone())
+    }
+
+    /// Trains from scratch with [`step_epoch`](fn@step_epoch) until the config's target error percent or the epoch limit,
+    /// for classifiers that are made of many networks and save them together
+    pub(crate) fn learn_epochs(&mut self, data: &mut [Input], categories: Vec<Types>, config: &TrainingConfig, max_epochs: usize) -> Result<(), DarjeelingError> {
+        self.start_training(categories);
+        for _ in 0..max_epochs {
+            if self.step_epoch(data, config.learning_rate)?.accuracy * 100.0 >= config.target_err_percent {
+                break;
             }
-            *count += 1.0;
-        }
-        if epochs.is_some() {
-            (brightest_node.category.clone().unwrap(), Some(CatNetwork::calculate_err_for_generation_model(mse, brightest_node)))
-        } else {
-            (brightest_node.category.clone().unwrap(), None)
         }
-        
+        Ok(())
     }
 
-    fn calculate_err_for_generation_model(mse: &mut f32, node: &Node) -> f32 {
-        *mse += f32::powi(node.correct_answer.unwrap() - node.cached_output.unwrap(), 2);
-        *mse
+    /// Draws every weight and bias again from the given generator, the same way [`new`](fn@new) does
+    pub(crate) fn randomize_weights<R: Rng>(&mut self, rng: &mut R) {
+        self.node_array.iter_mut().for_each(|layer| {
+            layer.iter_mut().for_each(|node| node.randomize(rng, Initialization::Standard))
+        });
     }
 
-    /// Finds the index and the brightest node in an array and returns it
-    fn largest_node(&self) -> usize {
-        let mut largest_node = 0;
-        (0..self.node_array[self.answer.unwrap()].len())
-            .into_iter()
-            .for_each(|node_i| {
-                if self.node_array[self.answer.unwrap()][node_i].cached_output > self.node_array[self.answer.unwrap()][largest_node].cached_output {
-                    largest_node = node_i;
-                }
-            });
-        largest_node
-    }
-    /// Goes back through the network adjusting the weights of the all the neurons based on their error signal
-    fn backpropogate(&mut self, learning_rate: f32) {
-        let hidden_layers = (self.node_array.len() - 2) as i32;
-        for answer in 0..self.node_array[self.answer.unwrap()].len() {
-            dbg_println!("Node: {:?}", self.node_array[self.answer.unwrap()][answer]);
-            self.node_array[self.answer.unwrap()][answer].compute_answer_err_sig(&self.activation_function);
-            dbg_println!("Error: {:?}", self.node_array[self.answer.unwrap()][answer].err_sig.unwrap());
+    /// Shuffles the data and runs every input through the network once
+    /// 
+    /// ## Returns
+    /// The percent of inputs categorized correctly
+    fn train_epoch<R: Rng>(&mut self, data: &mut [Input], learning_rate: f32, epochs: &mut f32, sum: &mut f32, count: &mut f32, rng: &mut R) -> Result<f32, DarjeelingError> {
+        *count = 0.0;
+        *sum = 0.0;
+        // An epoch a gradient alarm aborted leaves its loss behind
+        self.epoch_loss = CompensatedSum::default();
+        if self.record_order {
+            // Shuffling the indices takes the same draws and makes the same swaps as shuffling the data,
+            // so recording the order doesn't change it
+            let mut order: Vec<usize> = (0..data.len()).collect();
+            order.shuffle(rng);
+            let shuffled: Vec<Input> = order.iter().map(|i| data[*i].clone()).collect();
+            data.clone_from_slice(&shuffled);
+            self.epoch_order = order;
+        } else {
+            data.shuffle(rng);
         }
-        self.adjust_hidden_weights(learning_rate, hidden_layers);
-        // Adjusts weights for answer neurons
-        for answer in 0..self.node_array[self.answer.unwrap()].len() {
-            self.node_array[self.answer.unwrap()][answer].adjust_weights(learning_rate);
+        let mut predictions: Vec<Types> = vec![];
+        let mut truth: Vec<Types> = vec![];
+
+        for line in 0..data.len() {
+            let (prediction, answer) = self.train_input(&data[line], *epochs, sum, count, learning_rate, rng);
+            self.check_gradient(*epochs + 1.0)?;
+            predictions.push(prediction);
+            truth.push(answer);
         }
+
+        *epochs += 1.0;
+        let err_percent: f32 = self.end_epoch(*epochs, *sum, *count, learning_rate, &predictions, &truth)?;
+        self.tend_dead_neurons(rng);
+        Ok(err_percent)
     }
 
-    #[allow(non_snake_case)]
-    /// Adjusts the weights of all the hidden neurons in a network
-    fn adjust_hidden_weights(&mut self, learning_rate: f32, hidden_layers: i32) {
-        // HIDDEN represents the layer, while hidden represents the node of the layer
-        for HIDDEN in 1..(hidden_layers + 1) as usize {            
-            for hidden in 0..self.node_array[HIDDEN].len() {
-                self.node_array[HIDDEN][hidden].err_sig = Some(0.0);
+    /// Trains the network with a [cosine learning rate that restarts](LearningRateSchedule::WarmRestarts) every cycle, saving a snapshot of the network at the end of each cycle,
+    /// where the learning rate is lowest. The snapshots predict together as an [ensemble](Ensemble),
+    /// so one training run gives a small committee of models.
+    /// 
+    /// ## Params
+    /// - Data: The training data
+    /// - Categories: The categories the inputs can be put in
+    /// - Max Learning Rate: The learning rate at the start of every cycle, which falls to nearly 0 by its end.
+    ///   This replaces the network's own [schedule](fn@set_schedule).
+    /// - Cycles: How many cycles to train for, and so how many snapshots are taken
+    /// - Epochs Per Cycle: How many epochs each cycle is
+    /// 
+    /// ## Returns
+    /// The snapshots. The network itself is left as the last one.
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// There are no cycles, or no epochs in them
+    /// - The rest are the same as [`step_epoch`](fn@step_epoch)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// let ensemble: Ensemble = net.learn_snapshot_ensemble(&mut data, categories, 1.0, 5, 40).unwrap();
+    /// println!("{}", ensemble.evaluate(&test_data));
+    /// ```
+    pub fn learn_snapshot_ensemble(&mut self, data: &mut [Input], categories: Vec<Types>, max_learning_rate: f32, cycles: usize, epochs_per_cycle: usize) -> Result<Ensemble, DarjeelingError> {
+        if cycles == 0 || epochs_per_cycle == 0 {
+            return Err(DarjeelingError::InvalidInput(format!("Can't take snapshots over {} cycles of {} epochs", cycles, epochs_per_cycle)));
+        }
+        self.start_training(categories);
+        let schedule: LearningRateSchedule = self.schedule;
+        self.schedule = LearningRateSchedule::warm_restarts(epochs_per_cycle, 1);
+
+        let mut snapshots: Vec<CatNetwork> = vec![];
+        let mut trained: Result<(), DarjeelingError> = Ok(());
+        'cycles: for _cycle in 0..cycles {
+            for _epoch in 0..epochs_per_cycle {
+                if let Err(error) = self.step_epoch(data, max_learning_rate) {
+                    trained = Err(error);
+                    break 'cycles;
+                }
+            }
+            snapshots.push(self.clone());
+        }
+        self.schedule = schedule;
+        snapshots.iter_mut().for_each(|snapshot| snapshot.schedule = schedule);
+        trained?;
+        Ensemble::new(snapshots)
+    }
+
+    /// Trains the network on data that's read lazily, instead of a list that's already in memory.
+    /// Every epoch starts a new pass over the source, so the data never has to be fully materialized.
+    /// Unlike [`learn`](fn@learn), the data isn't shuffled, so shuffle it in the source if the order matters.
+    /// 
+    /// ## Params
+    /// - Source: Where the data is read from. Closures returning an iterator of Inputs are data sources,
+    ///   so you can pass in something like `|| read_file_lazily("data.txt")`
+    /// - The rest are the same as [`learn`](fn@learn)
+    /// 
+    /// ## Returns
+    /// The same as [`learn`](fn@learn)
+    /// 
+    /// ## Err
+    /// The same as [`learn`](fn@learn)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input, types::Types};
+    /// 
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// // Reads the file again at the start of every epoch
+    /// let mut source = || xor_lines("training_data/xor.txt").map(|line| parse_input(line));
+    /// let categories = vec![Types::Float(0.0), Types::Float(1.0)];
+    /// let (model_name, error_percentage, mse) = net.learn_from_source(&mut source, categories, 0.5, "xor", 99.0, true).unwrap();
+    /// ```
+    pub fn learn_from_source<S: DataSource>(
+        &mut self, 
+        source: &mut S, 
+        categories: Vec<Types>, 
+        learning_rate: f32, 
+        name: &str,
+        target_err_percent: f32,
+        write: bool
+    ) -> Result<(Option<String>, f32, f32), DarjeelingError> {
+        let mut epochs = 0.0;
+        let mut sum = 0.0;
+        let mut count = 0.0;
+        let mut err_percent = 0.0;
+
+        self.categorize(categories);
+        self.history = TrainingHistory::new();
+
+        while err_percent < target_err_percent {
+            count = 0.0;
+            sum = 0.0;
+            self.epoch_loss = CompensatedSum::default();
+            let mut rng: Box<dyn RngCore> = self.epoch_rng(epochs as usize);
+
+            let mut predictions: Vec<Types> = vec![];
+            let mut truth: Vec<Types> = vec![];
+
+            let epoch_learning_rate: f32 = self.schedule.learning_rate(learning_rate, epochs as usize);
+
+            for input in source.pass() {
+                let (prediction, answer) = self.train_input(&input, epochs, &mut sum, &mut count, epoch_learning_rate, &mut rng);
+                self.check_gradient(epochs + 1.0)?;
+                predictions.push(prediction);
+                truth.push(answer);
+            }
+
+            epochs += 1.0;
+            err_percent = self.end_epoch(epochs, sum, count, epoch_learning_rate, &predictions, &truth)?;
+            self.tend_dead_neurons(&mut rng);
+        }
+
+        self.finish_training(name, write, sum, count, err_percent, epochs)
+    }
+
+    /// Runs one input through the network and backpropogates its error
+    /// 
+    /// ## Returns
+    /// The category the network chose, and the correct category
+    fn train_input<R: Rng>(&mut self, input: &Input, epochs: f32, sum: &mut f32, count: &mut f32, learning_rate: f32, rng: &mut R) -> (Types, Types) {
+        dbg_println!("Training Checkpoint One Passed");
+
+        println!("Assign");
+        bench!(self.assign_answers(input));
+
+        let augmented: Option<Vec<f32>> = self.augmenter.as_ref().map(|augmenter| augmenter.augmented(&input.inputs, rng));
+        println!("Push");
+        bench!(self.push_downstream(augmented.as_ref().unwrap_or(&input.inputs)));
+
+        dbg_println!("Sum: {:?} Count: {:?}", sum, count);
+
+        println!("Analysis");
+        bench!(let (prediction, squared_error) = self.self_analysis(&mut Some(epochs), sum, count, input));
+        // The run's total drowns out late inputs in f32, so the epoch's loss is kept apart and compensated
+        if let Some(squared_error) = squared_error {
+            self.epoch_loss.add(squared_error);
+        }
+
+        dbg_println!("Sum: {:?} Count: {:?}", sum, count);
+
+        println!("Backpropogate");
+        bench!(self.backpropogate(learning_rate, 1.0));
+
+        if self.collect_layer_stats {
+            self.record_err_sigs();
+        }
+        if self.dead_neuron_policy.is_some() {
+            self.record_outputs();
+        }
+        let grad_norm: f32 = self.gradient_norm();
+        self.grad_norms.add(grad_norm);
+
+        (prediction, self.answer_category(input))
+    }
+
+    /// Trains on a single input without recording anything, for probing how training responds
+    /// 
+    /// ## Returns
+    /// The mean squared error of the answer nodes before the weights were adjusted
+    pub(crate) fn train_step(&mut self, input: &Input, learning_rate: f32) -> f32 {
+        self.assign_answers(input);
+        self.push_downstream(&input.inputs);
+        let answers: &[Node] = &self.node_array[self.answer.unwrap()];
+        let loss: f32 = answers.iter().map(|node| (node.correct_answer.unwrap() - node.cached_output.unwrap()).powi(2)).sum::<f32>() / answers.len() as f32;
+        self.backpropogate(learning_rate, 1.0);
+        loss
+    }
+
+    /// Nudges the network towards or away from an output it chose, by how good the outcome was,
+    /// for using it as a small policy network in reinforcement learning.
+    /// The chosen answer node is trained towards 1 and the rest towards 0, like a supervised step,
+    /// but the update is scaled by the reward, so a negative reward makes the choice less likely instead.
+    /// Subtracting a baseline, like the average reward, from rewards before passing them in makes learning much steadier.
+    ///
+    /// ## Params
+    /// - Inputs: The state the choice was made in
+    /// - Chosen Output: The index of the answer node that was acted on
+    /// - Reward: The reward or advantage of the choice
+    /// - Learning Rate: The modifier that is applied to link weights as they're adjusted
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The inputs aren't the network's input size, the chosen output isn't an answer node, or the reward isn't finite
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    ///
+    /// let mut policy = CatNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    /// let state = [0.2, 0.7];
+    /// let before: f32 = policy.raw_outputs(&state)[1];
+    /// policy.reinforce(&state, 1, 1.0, 0.1).unwrap();
+    /// assert!(policy.raw_outputs(&state)[1] > before);
+    /// ```
+    pub fn reinforce(&mut self, inputs: &[f32], chosen_output: usize, reward: f32, learning_rate: f32) -> Result<(), DarjeelingError> {
+        if inputs.len() != self.input_size() {
+            return Err(DarjeelingError::InvalidInput(format!("There are {} inputs, but the network has {} sensors", inputs.len(), self.input_size())));
+        }
+        if chosen_output >= self.output_size() {
+            return Err(DarjeelingError::InvalidInput(format!("Output {} was chosen, but the network has {} answer nodes", chosen_output, self.output_size())));
+        }
+        if !reward.is_finite() {
+            return Err(DarjeelingError::InvalidInput(format!("The reward has to be finite, but it was {}", reward)));
+        }
+        self.node_array[self.answer.unwrap()].iter_mut().enumerate().for_each(|(i, node)| {
+            node.correct_answer = Some(if i == chosen_output { 1.0 } else { 0.0 });
+        });
+        self.push_downstream(inputs);
+        self.backpropogate(learning_rate, reward);
+        Ok(())
+    }
+
+    /// Fine-tunes an already trained network on new data while pulling its weights back towards an [anchor](Anchor),
+    /// so regular refreshes learn what changed without forgetting what the older data taught it.
+    /// The categories stay the same as they were, so the new data's answers have to be among them.
+    ///
+    /// ## Params
+    /// - Data: The new data
+    /// - Anchor: The weights to stay close to, usually made from the network just before updating
+    /// - Learning Rate: The modifier that is applied to link weights as they're adjusted
+    /// - Epochs: How many epochs to train on the new data
+    ///
+    /// ## Returns
+    /// A record of every epoch
+    ///
+    /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The anchor was made from a network with a different number of weights
+    /// - ### InvalidTrainingData
+    /// There's no data, an input doesn't have an answer, or the network hasn't been given categories
+    /// - The rest are the same as [`step_epoch`](fn@step_epoch)
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// let anchor = Anchor::ewc(&net, &last_month, 1.0)?;
+    /// let records = net.update(&mut this_month, anchor, 0.1, 20)?;
+    /// println!("Accuracy on the new data: {}", records.last().unwrap().accuracy);
+    /// ```
+    pub fn update(&mut self, data: &mut [Input], anchor: Anchor, learning_rate: f32, epochs: usize) -> Result<Vec<EpochRecord>, DarjeelingError> {
+        if anchor.len() != self.flat_weights().len() {
+            return Err(DarjeelingError::ArchitectureMismatch(format!("The anchor has {} weights, but the network has {}", anchor.len(), self.flat_weights().len())));
+        }
+        if data.is_empty() || data.iter().any(|input| input.answer.is_none()) {
+            return Err(DarjeelingError::InvalidTrainingData("Updating needs data where every input has an answer".to_string()));
+        }
+        if self.categories().is_none() {
+            return Err(DarjeelingError::InvalidTrainingData("The network has to have categories before it's updated".to_string()));
+        }
+        self.anchor = Some(anchor);
+        let mut records: Vec<EpochRecord> = Vec::with_capacity(epochs);
+        let mut updated: Result<(), DarjeelingError> = Ok(());
+        for _ in 0..epochs {
+            match self.step_epoch(data, learning_rate) {
+                Ok(record) => records.push(record),
+                Err(error) => {
+                    updated = Err(error);
+                    break;
+                }
+            }
+        }
+        self.anchor = None;
+        updated.map(|_| records)
+    }
+
+    /// Every link weight then the bias of every node after the input layer, in order
+    pub(crate) fn flat_weights(&self) -> Vec<f32> {
+        self.node_array.iter().skip(1).flatten().flat_map(|node| node.link_weights.iter().copied().chain(node.b_weight)).collect()
+    }
+
+    /// The average squared gradient of every weight over the data, in the same order as [`flat_weights`](fn@flat_weights)
+    pub(crate) fn squared_gradients(&self, data: &[Input]) -> Vec<f32> {
+        // The probe backpropogates without a learning rate, so only its error signals change
+        let mut probe: CatNetwork = self.clone();
+        probe.max_norm = None;
+        let mut squares: Vec<f32> = vec![0.0; self.flat_weights().len()];
+        for input in data {
+            probe.assign_answers(input);
+            probe.push_downstream(&input.inputs);
+            probe.backpropogate(0.0, 1.0);
+            let gradients = probe.node_array.iter().skip(1).flatten().flat_map(|node| {
+                let err_sig: f32 = node.err_sig.unwrap_or(0.0);
+                node.link_vals.iter().map(move |value| err_sig * value.unwrap_or(0.0)).chain(node.b_weight.map(|_| err_sig))
+            });
+            squares.iter_mut().zip(gradients).for_each(|(square, gradient)| *square += gradient * gradient);
+        }
+        squares.iter_mut().for_each(|square| *square /= data.len().max(1) as f32);
+        squares
+    }
+
+    /// Trains one answer node towards a target, leaving the others alone, like fitting the Q value of the action that was taken
+    /// 
+    /// ## Returns
+    /// The node's squared error before the weights were adjusted
+    pub(crate) fn train_output(&mut self, inputs: &[f32], output: usize, target: f32, learning_rate: f32) -> f32 {
+        self.push_downstream(inputs);
+        let answer_layer: usize = self.answer.unwrap();
+        // The other nodes are already right, so only the chosen one has an error to pass back
+        self.node_array[answer_layer].iter_mut().for_each(|node| node.correct_answer = node.cached_output);
+        self.node_array[answer_layer][output].correct_answer = Some(target);
+        let error: f32 = (target - self.node_array[answer_layer][output].cached_output.unwrap()).powi(2);
+        self.backpropogate(learning_rate, 1.0);
+        error
+    }
+
+    /// Reports an epoch that just finished and records it in the history
+    /// 
+    /// ## Returns
+    /// The percent of inputs categorized correctly
+    #[allow(clippy::too_many_arguments)]
+    fn end_epoch(&mut self, epochs: f32, sum: f32, count: f32, learning_rate: f32, predictions: &[Types], truth: &[Types]) -> Result<f32, DarjeelingError> {
+        let err_percent: f32 = (sum/count) * 100.0;
+        println!("Epoch: {:?}", epochs);
+        println!("Training Accuracy: {:?}", err_percent);
+        println!("{}", ClassificationMetrics::compute(predictions, truth));
+
+        let (grad_norm, _, max_grad_norm) = self.grad_norms.stats();
+        let mut record = EpochRecord {
+            epoch: epochs as usize,
+            loss: if count == 0.0 { 0.0 } else { std::mem::take(&mut self.epoch_loss).value() / count },
+            accuracy: err_percent / 100.0,
+            learning_rate,
+            grad_norm,
+            max_grad_norm,
+            layers: if self.collect_layer_stats { self.layer_stats() } else { vec![] },
+            holdout_loss: None,
+            holdout_accuracy: None,
+            order: std::mem::take(&mut self.epoch_order),
+            timings: std::mem::take(&mut self.layer_timings).into_iter().skip(1).collect()
+        };
+        if !self.holdout.is_empty() {
+            let report: EvaluationReport = self.evaluate(&self.holdout);
+            println!("Holdout Accuracy: {:?}", report.metrics.accuracy * 100.0);
+            record.holdout_loss = Some(report.loss);
+            record.holdout_accuracy = Some(report.metrics.accuracy);
+        }
+        record.timings.iter().for_each(|timing| println!("Layer {}: {:?} forward, {:?} backward", timing.layer, timing.forward, timing.backward));
+        self.err_sig_stats = vec![];
+        self.grad_norms = RunningStats::default();
+        if let Some(alarm) = &self.gradient_alarm {
+            if alarm.exceeded(max_grad_norm) {
+                println!("Warning: Gradient norm reached {} in epoch {}, past the bound of {}", max_grad_norm, epochs, alarm.bound);
+            }
+        }
+        if let Some(sender) = &self.metrics_sender {
+            // Nobody listening anymore shouldn't stop training
+            let _ = sender.send(record.clone());
+        }
+        if let Some(writer) = &self.event_writer {
+            writer.log_epoch(&record)?;
+        }
+        if let Some(metrics) = &self.prometheus {
+            metrics.record_epoch(&record);
+        }
+        self.history.record(record);
+        Ok(err_percent)
+    }
+
+    /// The L2 norm of the gradients of the last input trained on, over every link and bias weight
+    fn gradient_norm(&self) -> f32 {
+        self.node_array.iter().skip(1).flatten().map(|node| {
+            let err_sig: f32 = node.err_sig.unwrap_or(0.0);
+            let inputs: f32 = node.link_vals.iter().map(|value| value.unwrap_or(0.0).powi(2)).sum();
+            err_sig.powi(2) * (1.0 + inputs)
+        }).sum::<f32>().sqrt()
+    }
+
+    /// Stops training if the last input's gradient norm set off an aborting alarm
+    /// 
+    /// ## Err
+    /// - ### ExplodingGradient
+    /// The gradient norm is past the alarm's bound
+    fn check_gradient(&self, epoch: f32) -> Result<(), DarjeelingError> {
+        match &self.gradient_alarm {
+            Some(alarm) if alarm.abort && alarm.exceeded(self.gradient_norm()) => Err(DarjeelingError::ExplodingGradient(format!(
+                "Gradient norm reached {} in epoch {}, past the bound of {}", self.gradient_norm(), epoch, alarm.bound
+            ))),
+            _ => Ok(())
+        }
+    }
+
+    /// Adds every node's current output to its running statistics
+    fn record_outputs(&mut self) {
+        let shape_matches: bool = self.output_stats.len() == self.node_array.len()
+            && self.output_stats.iter().zip(&self.node_array).all(|(stats, layer)| stats.len() == layer.len());
+        if !shape_matches {
+            self.output_stats = self.node_array.iter().map(|layer| vec![RunningStats::default(); layer.len()]).collect();
+        }
+        self.node_array.iter().zip(self.output_stats.iter_mut()).for_each(|(layer, stats)| {
+            layer.iter().zip(stats.iter_mut()).for_each(|(node, stats)| stats.add(node.cached_output.unwrap_or(0.0)));
+        });
+    }
+
+    /// Counts how long each hidden node has been dead at the end of an epoch,
+    /// and re-randomizes or removes the ones that have been for as long as the dead neuron policy allows
+    fn tend_dead_neurons<R: Rng>(&mut self, rng: &mut R) {
+        let policy: DeadNeuronPolicy = match self.dead_neuron_policy {
+            Some(policy) => policy,
+            None => return
+        };
+        let stats: Vec<Vec<RunningStats>> = std::mem::take(&mut self.output_stats);
+        if stats.len() != self.node_array.len() {
+            return;
+        }
+        if self.dead_epochs.len() != self.node_array.len() || self.dead_epochs.iter().zip(&self.node_array).any(|(dead, layer)| dead.len() != layer.len()) {
+            self.dead_epochs = self.node_array.iter().map(|layer| vec![0; layer.len()]).collect();
+        }
+        let epoch: usize = self.history.len();
+        for layer in 1..self.answer.unwrap() {
+            // From the last node back, so removing one doesn't move the ones still to check
+            for node in (0..self.node_array[layer].len()).rev() {
+                let (_, std, _) = stats[layer][node].stats();
+                if std > policy.tolerance {
+                    self.dead_epochs[layer][node] = 0;
+                    continue;
+                }
+                self.dead_epochs[layer][node] += 1;
+                if self.dead_epochs[layer][node] < policy.patience.max(1) {
+                    continue;
+                }
+                match policy.action {
+                    NeuronAction::Reinitialize => {
+                        self.node_array[layer][node].randomize(rng, Initialization::Standard);
+                        self.dead_epochs[layer][node] = 0;
+                    },
+                    NeuronAction::Remove if self.node_array[layer].len() > 1 => {
+                        self.remove_hidden_node(layer, node);
+                        self.dead_epochs[layer].remove(node);
+                    },
+                    NeuronAction::Remove => continue
+                }
+                self.history.record_event(NeuronEvent { epoch, layer, node, action: policy.action, std });
+            }
+        }
+    }
+
+    /// Takes a hidden node out, along with the links to it from the next layer
+    fn remove_hidden_node(&mut self, layer: usize, node: usize) {
+        self.node_array[layer].remove(node);
+        self.node_array[layer + 1].iter_mut().for_each(|next| {
+            next.link_weights.remove(node);
+            next.link_vals.remove(node);
+            next.links -= 1;
+        });
+    }
+
+    /// Adds every node's current error signal to the running statistics of its layer
+    fn record_err_sigs(&mut self) {
+        if self.err_sig_stats.len() != self.node_array.len() {
+            self.err_sig_stats = vec![RunningStats::default(); self.node_array.len()];
+        }
+        self.node_array.iter().zip(self.err_sig_stats.iter_mut()).skip(1).for_each(|(layer, stats)| {
+            layer.iter().filter_map(|node| node.err_sig).for_each(|err_sig| stats.add(err_sig));
+        });
+    }
+
+    /// The weight statistics of every layer after the inputs, with the error signals recorded this epoch
+    fn layer_stats(&self) -> Vec<LayerStats> {
+        (1..self.node_array.len()).map(|layer| {
+            let mut weights = RunningStats::default();
+            self.node_array[layer].iter().for_each(|node| {
+                node.link_weights.iter().for_each(|weight| weights.add(*weight));
+                if let Some(bias) = node.b_weight {
+                    weights.add(bias);
+                }
+            });
+            let (weight_mean, weight_std, weight_max) = weights.stats();
+            let (err_sig_mean, err_sig_std, err_sig_max) = self.err_sig_stats.get(layer).map(|stats| stats.stats()).unwrap_or_default();
+            LayerStats { layer, weight_mean, weight_std, weight_max, err_sig_mean, err_sig_std, err_sig_max }
+        }).collect()
+    }
+
+    /// The category an input's answer belongs to.
+    /// Vector answers belong to the category of the node with the largest target.
+    fn answer_category(&self, input: &Input) -> Types {
+        match input.answer.as_ref().unwrap() {
+            Types::Vector(targets) => self.node_array[self.answer.unwrap()][largest_index(targets)].category.clone().unwrap(),
+            answer => self.node_array[self.answer.unwrap()]
+                .iter()
+                .filter_map(|node| node.category.as_ref())
+                .find(|category| category.approx_eq(answer, self.float_tolerance))
+                .unwrap_or(answer)
+                .clone()
+        }
+    }
+
+    /// Writes the model if asked to, and reports how training went
+    fn finish_training(&mut self, name: &str, write: bool, sum: f32, count: f32, err_percent: f32, epochs: f32) -> Result<(Option<String>, f32, f32), DarjeelingError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let model_name: Option<String> = if write { Some(self.write_model(&name)?) } else { None };
+        #[cfg(target_arch = "wasm32")]
+        let model_name: Option<String> = if write {
+            return Err(DarjeelingError::WriteModelFailed(format!("{}: There's no filesystem on wasm, save to_darj_string instead", name)));
+        } else {
+            None
+        };
+
+        let mse: f32 = self.history.last().map(|record| record.loss).unwrap_or(0.0);
+        println!("Training: Finished with accuracy of {:?}/{:?} or {:?} percent after {:?} epochs\nmse: {}", sum, count, err_percent, epochs, mse);
+
+        Ok((model_name, err_percent, mse))
+    }
+
+    /// Tests a pretrained model
+    /// 
+    /// ## Returns
+    /// A prediction for every input, in the same order as the data
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn test(data: Vec<Input>, categories: Vec<Types>, model_name: String) -> Result<Vec<Prediction>, DarjeelingError> {
+        let mut sum = 0.0;
+        let mut count = 0.0;
+        // let mut category: Option<Types> = None;
+        let mut answers: Vec<Prediction> = vec![];
+        let mut squared_error = CompensatedSum::default();
+
+        let mut net: CatNetwork = match CatNetwork::read_model(model_name.clone()) {
+
+            Ok(net) => net,
+            Err(error) => return Err(DarjeelingError::ReadModelFunctionFailed(model_name, Box::new(error)))
+        };
+
+        for node in 0..net.node_array[net.answer.unwrap()].len() {
+            net.node_array[net.answer.unwrap()][node].category = Some(categories[node].clone());
+            dbg_println!("{:?}", net.node_array[net.answer.unwrap()][node].category);
+        }
+
+        for line in 0..data.len() {
+            dbg_println!("Testing Checkpoint One Passed");
+            if data[line].answer.is_some() {
+                net.assign_answers(&data[line]);
+            }
+            // Do we actually want to do this?
+            net.push_downstream(&data[line].inputs);
+            dbg_println!("Sum: {:?} Count: {:?}", sum, count);            
+            if let (_, Some(error)) = net.self_analysis(&mut None, &mut sum, &mut count, &data[line]) {
+                squared_error.add(error);
+            }
+            answers.push(net.predict(&data[line].inputs));
+
+            dbg_println!("Sum: {:?} Count: {:?}", sum, count);
+
+            // println!("Correct answer: {:?}", data[line].answer)
+        }
+
+        // let _old_err_percent = err_percent;
+        let err_percent: f32 = (sum/count) * 100.0;
+        let mse: f32 = squared_error.value() / count;
+        println!("Testing: Finished with accuracy of {:?}/{:?} or {:?} percent\nMSE: {}", sum, count, err_percent, mse);
+
+        Ok(answers)
+    }
+
+    /// Evaluates the network on data without changing it or the data.
+    /// Unlike [`test`](fn@test), the inputs are run in order and nothing is read from or written to a file,
+    /// so the same network and data always give the same report.
+    /// Inputs without an answer are skipped.
+    /// 
+    /// The network's categories have to be set, either by training it or with [`set_categories`](fn@set_categories)
+    /// 
+    /// ## Returns
+    /// The mean squared error of the answer nodes, and the classification metrics of the chosen categories
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input, types::Types};
+    /// 
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// let data = vec![Input::new(vec![0.0, 1.0], Some(Types::Boolean(true)))];
+    /// let report = net.evaluate(&data);
+    /// assert_eq!(report.count, 1);
+    /// ```
+    pub fn evaluate(&self, data: &[Input]) -> EvaluationReport {
+        let mut predictions: Vec<Prediction> = vec![];
+        let mut truth: Vec<Types> = vec![];
+        let mut squared_error = CompensatedSum::default();
+        let mut weight_compared = CompensatedSum::default();
+
+        data.iter().filter(|input| input.answer.is_some()).for_each(|input| {
+            let prediction: Prediction = self.predict(&input.inputs);
+            let targets: Vec<f32> = self.targets(input.answer.as_ref().unwrap());
+            prediction.scores.iter().zip(&targets).enumerate().for_each(|(i, ((_, output), target))| {
+                let weight: f32 = self.output_weights.get(i).copied().unwrap_or(1.0);
+                squared_error.add(weight * (target - output).powi(2));
+                weight_compared.add(weight);
+            });
+
+            predictions.push(prediction);
+            truth.push(self.answer_category(input));
+        });
+
+        let categories: Vec<Types> = predictions.iter().map(|prediction| prediction.category.clone()).collect();
+        EvaluationReport {
+            loss: if weight_compared.value() == 0.0 { 0.0 } else { squared_error.value() / weight_compared.value() },
+            metrics: ClassificationMetrics::compute(&categories, &truth),
+            count: truth.len(),
+            predictions,
+            categories: CategoryBreakdown::compute(&categories, &truth)
+        }
+    }
+
+    /// Evaluates the network like [`evaluate`](fn@evaluate), and bootstraps the test set for confidence intervals around the accuracy and F1 scores.
+    /// On a small test set a point estimate can be far from how the network really does, and the intervals show how far.
+    /// The network predicts every input once, and only the predictions are resampled.
+    /// 
+    /// ## Params
+    /// - Data: The test set. Inputs without an answer are skipped.
+    /// - Resamples: How many resampled test sets to score. A thousand or so gives steady intervals.
+    /// - Confidence: The fraction of resamples inside each interval, like 0.95
+    /// - Seed: Decides the resamples, so the same seed always gives the same intervals
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// The network's categories haven't been set
+    /// - The same as [`BootstrapReport::compute`]
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input, types::Types};
+    /// 
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// let data = vec![
+    ///     Input::new(vec![0.0, 1.0], Some(Types::Boolean(true))),
+    ///     Input::new(vec![1.0, 0.0], Some(Types::Boolean(false)))
+    /// ];
+    /// let report = net.evaluate_bootstrap(&data, 100, 0.95, 42).unwrap();
+    /// assert!(report.accuracy.lower <= report.accuracy.estimate && report.accuracy.estimate <= report.accuracy.upper);
+    /// ```
+    pub fn evaluate_bootstrap(&self, data: &[Input], resamples: usize, confidence: f32, seed: u64) -> Result<BootstrapReport, DarjeelingError> {
+        if self.categories().is_none() {
+            return Err(DarjeelingError::InvalidInput("The network needs its categories to be evaluated".to_string()));
+        }
+        let (predictions, truth): (Vec<Types>, Vec<Types>) = data.iter()
+            .filter(|input| input.answer.is_some())
+            .map(|input| (self.predict(&input.inputs).category, self.answer_category(input)))
+            .unzip();
+        BootstrapReport::compute(&predictions, &truth, resamples, confidence, seed)
+    }
+
+    /// Summarizes the network for sharing it, from what it knows about itself: its architecture, the fingerprint of the data it was trained on,
+    /// its training settings and last epoch, and how it decides. Add what it's for and where it falls short to the card before sharing it.
+    ///
+    /// ## Params
+    /// - Test Data: Evaluated for the card if given, and should be data the network wasn't trained on
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There's test data, but the network's categories aren't set
+    /// - The rest are the same as [`check_compatibility`](fn@check_compatibility), for the test data
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input, types::Types};
+    ///
+    /// let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// let card = net.generate_model_card(Some(&[Input::new(vec![0.0, 1.0], Some(Types::Boolean(true)))])).unwrap()
+    ///     .with_name("Spam filter")
+    ///     .with_intended_use("Flagging spam in a shared inbox for review, not deleting it automatically");
+    /// assert_eq!(card.architecture.parameters, 3 * 3 + 2 * 4);
+    /// assert!(card.to_markdown().starts_with("# Spam filter"));
+    /// ```
+    pub fn generate_model_card(&self, test_data: Option<&[Input]>) -> Result<ModelCard, DarjeelingError> {
+        let evaluation: Option<CardEvaluation> = match test_data {
+            Some(data) => {
+                if self.categories().is_none() {
+                    return Err(DarjeelingError::InvalidInput("The network needs its categories to be evaluated for a model card".to_string()));
+                }
+                self.check_compatibility(data)?;
+                let report: EvaluationReport = self.evaluate(data);
+                Some(CardEvaluation { count: report.count, loss: report.loss, metrics: report.metrics })
+            },
+            None => None
+        };
+        let last: Option<&EpochRecord> = self.history.last();
+        Ok(ModelCard {
+            name: "Model".to_string(),
+            intended_use: None,
+            limitations: None,
+            architecture: CardArchitecture {
+                layers: self.node_array.iter().map(|layer| layer.len()).collect(),
+                activation: self.activation_function,
+                parameters: self.node_array.iter().skip(1).flatten().map(|node| node.link_weights.len() + 1).sum()
+            },
+            features: (0..self.input_size()).map(|feature| self.feature_name(feature)).collect(),
+            categories: self.categories().unwrap_or_default(),
+            training_data: self.data_fingerprint.clone(),
+            training: CardTraining {
+                epochs: self.history.len(),
+                learning_rate: last.map(|record| record.learning_rate),
+                loss: last.map(|record| record.loss),
+                accuracy: last.map(|record| record.accuracy),
+                regularization: self.regularization,
+                max_norm: self.max_norm,
+                schedule: self.schedule,
+                reduction: self.reduction
+            },
+            evaluation,
+            calibrated: self.calibrator.is_some(),
+            cost_sensitive: self.cost_matrix.is_some(),
+            threshold: self.threshold.clone()
+        })
+    }
+
+    /// The value each answer node should output for an answer, the same as [`assign_answers`](fn@assign_answers) sets
+    fn targets(&self, answer: &Types) -> Vec<f32> {
+        match answer {
+            Types::Vector(targets) => (0..self.node_array[self.answer.unwrap()].len()).map(|i| targets.get(i).copied().unwrap_or(0.0)).collect(),
+            _ => self.node_array[self.answer.unwrap()]
+                .iter()
+                .map(|node| if node.category.as_ref().map(|category| category.approx_eq(answer, self.float_tolerance)).unwrap_or(false) { 1.0 } else { 0.0 })
+                .collect()
+        }
+    }
+
+    /// Copies every link weight and bias from a network with the same architecture, like syncing a DQN's target network with the online one.
+    /// Categories, the scaler and every other setting are left as they are.
+    ///
+    /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The networks have different numbers of layers, nodes or links
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    ///
+    /// let online = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let mut target = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// target.clone_weights_from(&online).unwrap();
+    /// assert_eq!(target.raw_outputs(&[0.0, 1.0]), online.raw_outputs(&[0.0, 1.0]));
+    /// assert!(target.clone_weights_from(&CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid)).is_err());
+    /// ```
+    pub fn clone_weights_from(&mut self, other: &CatNetwork) -> Result<(), DarjeelingError> {
+        node::copy_weights(&mut self.node_array, &other.node_array)
+    }
+
+    /// Breeds a new network from two parents with the same architecture, for driving your own evolutionary loop.
+    /// Each node takes all of its link weights and bias from one parent or the other, picked evenly at random,
+    /// so the features a node learned stay together. Everything else, like the categories and scaler, comes from the first parent.
+    ///
+    /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The parents have different numbers of layers, nodes or links
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    ///
+    /// let a = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// let b = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// let mut child = CatNetwork::crossover(&a, &b, &mut rand::thread_rng()).unwrap();
+    /// child.mutate(0.1, 0.5, &mut rand::thread_rng()).unwrap();
+    /// ```
+    pub fn crossover<R: Rng>(a: &CatNetwork, b: &CatNetwork, rng: &mut R) -> Result<CatNetwork, DarjeelingError> {
+        node::check_same_shape(&a.node_array, &b.node_array)?;
+        let mut child: CatNetwork = a.clone();
+        child.node_array.iter_mut().flatten().zip(b.node_array.iter().flatten()).for_each(|(node, other_node)| {
+            if rng.gen_bool(0.5) {
+                node.link_weights.copy_from_slice(&other_node.link_weights);
+                node.b_weight = other_node.b_weight;
+            }
+        });
+        Ok(child)
+    }
+
+    /// Nudges some of the link weights and biases by a random amount, the other half of an evolutionary step alongside [`crossover`](fn@CatNetwork::crossover)
+    ///
+    /// ## Params
+    /// - Rate: The chance of each weight being nudged, from 0 to 1
+    /// - Scale: The largest nudge, in either direction
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The rate isn't between 0 and 1, or the scale is negative or not finite
+    pub fn mutate<R: Rng>(&mut self, rate: f32, scale: f32, rng: &mut R) -> Result<(), DarjeelingError> {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(DarjeelingError::InvalidInput(format!("The mutation rate is a chance from 0 to 1, not {}", rate)));
+        }
+        if !scale.is_finite() || scale < 0.0 {
+            return Err(DarjeelingError::InvalidInput(format!("The mutation scale has to be finite and can't be negative, but it was {}", scale)));
+        }
+        let mut nudge = |weight: &mut f32| {
+            if rng.gen::<f32>() < rate {
+                *weight += rng.gen_range(-scale..=scale);
+            }
+        };
+        self.node_array.iter_mut().skip(1).flatten().for_each(|node| {
+            node.link_weights.iter_mut().for_each(&mut nudge);
+            if let Some(bias) = node.b_weight.as_mut() {
+                nudge(bias);
+            }
+        });
+        Ok(())
+    }
+
+    /// Compares this network with another one of the same architecture,
+    /// to check that saving, quantizing or pruning a model didn't change how it behaves
+    /// 
+    /// ## Params
+    /// - Other: The network to compare with
+    /// - Probe: Inputs both networks are run on to compare their outputs, answers aren't needed
+    /// 
+    /// ## Returns
+    /// How far every layer's weights moved, and how differently the networks answered the probe data
+    /// 
+    /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The networks have different numbers of layers, nodes or links
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input};
+    /// 
+    /// let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let copy = net.clone();
+    /// let diff = net.diff(&copy, &[Input::new(vec![0.0, 1.0], None)]).unwrap();
+    /// assert!(diff.within(0.0));
+    /// ```
+    pub fn diff(&self, other: &CatNetwork, probe: &[Input]) -> Result<ModelDiff, DarjeelingError> {
+        if self.node_array.len() != other.node_array.len() {
+            return Err(DarjeelingError::ArchitectureMismatch(format!("{} layers and {} layers", self.node_array.len(), other.node_array.len())));
+        }
+        let mut layers: Vec<LayerDiff> = vec![];
+        for layer in 1..self.node_array.len() {
+            if self.node_array[layer].len() != other.node_array[layer].len() {
+                return Err(DarjeelingError::ArchitectureMismatch(format!("Layer {} has {} nodes and {} nodes", layer, self.node_array[layer].len(), other.node_array[layer].len())));
+            }
+            let mut deltas: Vec<f32> = vec![];
+            for (node, other_node) in self.node_array[layer].iter().zip(&other.node_array[layer]) {
+                if node.link_weights.len() != other_node.link_weights.len() {
+                    return Err(DarjeelingError::ArchitectureMismatch(format!("Layer {} has nodes with {} links and {} links", layer, node.link_weights.len(), other_node.link_weights.len())));
+                }
+                node.link_weights.iter().zip(&other_node.link_weights).for_each(|(weight, other_weight)| deltas.push((weight - other_weight).abs()));
+                deltas.push((node.b_weight.unwrap_or(0.0) - other_node.b_weight.unwrap_or(0.0)).abs());
+            }
+            layers.push(LayerDiff {
+                layer,
+                mean_delta: deltas.iter().sum::<f32>() / deltas.len().max(1) as f32,
+                max_delta: deltas.iter().cloned().fold(0.0, f32::max)
+            });
+        }
+        if self.node_array[0].len() != other.node_array[0].len() {
+            return Err(DarjeelingError::ArchitectureMismatch(format!("{} inputs and {} inputs", self.node_array[0].len(), other.node_array[0].len())));
+        }
+
+        let mut divergence_sum: f32 = 0.0;
+        let mut divergence_count: usize = 0;
+        let mut max_output_divergence: f32 = 0.0;
+        let mut agreeing: usize = 0;
+        probe.iter().for_each(|input| {
+            let outputs: Vec<f32> = self.forward(&input.inputs);
+            let other_outputs: Vec<f32> = other.forward(&input.inputs);
+            outputs.iter().zip(&other_outputs).for_each(|(output, other_output)| {
+                let divergence: f32 = (output - other_output).abs();
+                divergence_sum += divergence;
+                divergence_count += 1;
+                max_output_divergence = max_output_divergence.max(divergence);
+            });
+            if largest_index(&outputs) == largest_index(&other_outputs) {
+                agreeing += 1;
+            }
+        });
+
+        Ok(ModelDiff {
+            layers,
+            mean_output_divergence: if divergence_count == 0 { 0.0 } else { divergence_sum / divergence_count as f32 },
+            max_output_divergence,
+            agreement: if probe.is_empty() { 1.0 } else { agreeing as f32 / probe.len() as f32 }
+        })
+    }
+
+    /// Runs the inputs through the network and records the output of every node, without changing the network.
+    /// Useful to visualize hidden representations or to find nodes that never activate.
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let snapshot = net.inspect(&[0.0, 1.0]);
+    /// assert_eq!(snapshot.hidden()[0].len(), 3);
+    /// assert_eq!(snapshot.answers().len(), 2);
+    /// ```
+    pub fn inspect(&self, inputs: &[f32]) -> ActivationSnapshot {
+        ActivationSnapshot::new(self.forward_layers(inputs))
+    }
+
+    /// [`inspect`](fn@inspect)s every input, in order
+    pub fn inspect_all(&self, data: &[Input]) -> Vec<ActivationSnapshot> {
+        data.iter().map(|input| self.inspect(&input.inputs)).collect()
+    }
+
+    /// Counts the weights coming into one layer, bias weights included, in equally wide bins from the smallest to the largest.
+    /// Weights bunched up at 0 or pushed out to the edges often explain why adding depth stops helping.
+    ///
+    /// ## Params
+    /// - Layer: The layer the weights lead into, from 1 for the first hidden layer up to the answer layer
+    /// - Bins: How many bins to split the range of weights into
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There's no such layer after the inputs, or there are no bins
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    ///
+    /// let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let histogram = net.layer_weight_histogram(1, 10).unwrap();
+    /// // 3 nodes, each with 2 weights and a bias
+    /// assert_eq!(histogram.total(), 9);
+    /// println!("{}", histogram);
+    /// ```
+    pub fn layer_weight_histogram(&self, layer: usize, bins: usize) -> Result<WeightHistogram, DarjeelingError> {
+        if layer == 0 || layer >= self.node_array.len() {
+            return Err(DarjeelingError::InvalidInput(format!("Layers with weights are 1 to {}, not {}", self.node_array.len() - 1, layer)));
+        }
+        if bins == 0 {
+            return Err(DarjeelingError::InvalidInput("A histogram needs at least one bin".to_string()));
+        }
+        let weights: Vec<f32> = self.node_array[layer].iter().flat_map(|node| node.link_weights.iter().chain(node.b_weight.iter()).copied()).collect();
+        Ok(WeightHistogram::of(layer, &weights, bins))
+    }
+
+    /// Finds the nodes after the inputs whose outputs hardly change across the probe data.
+    /// They pass the same value on no matter the input, usually because they're saturated, so they add nothing the next layer can use.
+    ///
+    /// ## Params
+    /// - Probe: Inputs to run through the network, answers aren't needed
+    /// - Tolerance: Nodes whose outputs have a standard deviation at or under this are dead, like 1e-3
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The probe is empty, or an input isn't as long as the network has sensors
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input};
+    ///
+    /// let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let probe = vec![Input::new(vec![0.0, 1.0], None), Input::new(vec![1.0, 0.0], None)];
+    /// let report = net.dead_neurons(&probe, 1e-3).unwrap();
+    /// println!("{:.0}% of the hidden layer is dead", report.fraction(1) * 100.0);
+    /// ```
+    pub fn dead_neurons(&self, probe: &[Input], tolerance: f32) -> Result<DeadNeuronReport, DarjeelingError> {
+        if probe.is_empty() {
+            return Err(DarjeelingError::InvalidInput("There's no probe data to find dead nodes with".to_string()));
+        }
+        if let Some(i) = probe.iter().position(|input| input.inputs.len() != self.input_size()) {
+            return Err(DarjeelingError::InvalidInput(format!("Probe input {} has {} values, but the network has {} sensors", i, probe[i].inputs.len(), self.input_size())));
+        }
+        let outputs: Vec<Vec<Vec<f32>>> = probe.iter().map(|input| self.forward_layers(&input.inputs)).collect();
+        let mut neurons: Vec<DeadNeuron> = vec![];
+        for layer in 1..self.node_array.len() {
+            for node in 0..self.node_array[layer].len() {
+                let values: Vec<f32> = outputs.iter().map(|layers| layers[layer][node]).collect();
+                let (mean, std) = crate::validation::mean_std(&values);
+                if std <= tolerance {
+                    neurons.push(DeadNeuron { layer, node, mean, std });
+                }
+            }
+        }
+        Ok(DeadNeuronReport {
+            probes: probe.len(),
+            tolerance,
+            layer_sizes: self.node_array.iter().skip(1).map(|layer| layer.len()).collect(),
+            neurons
+        })
+    }
+
+    /// How much each input affects one answer node's output, the derivative of the node's output with respect to every input.
+    /// Large values, positive or negative, mark the inputs that mattered most to the prediction.
+    /// The network's scaler is included, so the gradients are for the raw, unscaled inputs.
+    /// 
+    /// ## Params
+    /// - Inputs: The values to explain the prediction of
+    /// - Answer Node: Which answer node to explain, usually the [largest one](fn@predict_category)
+    /// 
+    /// ## Returns
+    /// One gradient per input, all zero if the answer node doesn't exist
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = CatNetwork::new(3, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// let saliency: Vec<f32> = net.input_gradients(&[0.2, 0.9, 0.4], 1);
+    /// assert_eq!(saliency.len(), 3);
+    /// ```
+    pub fn input_gradients(&self, inputs: &[f32], answer_node: usize) -> Vec<f32> {
+        let answer: usize = self.answer.unwrap();
+        if answer_node >= self.node_array[answer].len() {
+            return vec![0.0; self.node_array[0].len()];
+        }
+        let mut upstream: Vec<f32> = vec![0.0; self.node_array[answer].len()];
+        upstream[answer_node] = 1.0;
+        self.layer_input_gradients(inputs, answer, &upstream)
+    }
+
+    /// The derivative, with respect to every input, of a layer's outputs weighted by `upstream` and added up.
+    /// [`input_gradients`](fn@input_gradients) is the answer layer with a weight of 1 on one node.
+    pub(crate) fn layer_input_gradients(&self, inputs: &[f32], layer: usize, upstream: &[f32]) -> Vec<f32> {
+        let layers: Vec<Vec<f32>> = self.forward_layers(inputs);
+
+        // How much the weighted outputs change with each node's output, starting at the given layer
+        let mut gradients: Vec<f32> = upstream.to_vec();
+        gradients.resize(layers[layer].len(), 0.0);
+        for layer_i in (1..=layer).rev() {
+            let mut previous: Vec<f32> = vec![0.0; layers[layer_i - 1].len()];
+            self.node_array[layer_i].iter().enumerate().for_each(|(node_i, node)| {
+                let signal: f32 = gradients[node_i] * Node::derivative(layers[layer_i][node_i], &self.activation_function);
+                node.link_weights.iter().enumerate().for_each(|(link, weight)| previous[link] += signal * weight);
+            });
+            gradients = previous;
+        }
+
+        if let Some(scaler) = &self.scaler {
+            // Scaling is linear, so its slope is the difference between scaling 1 and 0
+            gradients.iter_mut().enumerate().for_each(|(i, gradient)| *gradient *= scaler.scale(i, 1.0) - scaler.scale(i, 0.0));
+        }
+        gradients
+    }
+
+    /// Explains one answer node's output with layer-wise relevance propagation, using the epsilon rule.
+    /// The node's output is split between the nodes feeding it, in proportion to how much each one's output times its link weight
+    /// added to the node's weighted sum, and so on back to the inputs. Unlike [input gradients](fn@input_gradients),
+    /// relevance doesn't vanish when a sigmoid node is saturated, since it follows what the nodes contributed rather than their slopes.
+    /// 
+    /// Without biases and with a tiny epsilon, the relevances add up to the node's output. Biases and epsilon each absorb a share of it.
+    /// With a scaler, relevance is given to the scaled inputs, one per input as before.
+    /// 
+    /// ## Params
+    /// - Inputs: The values to explain the prediction of
+    /// - Answer Node: Which answer node to explain, usually the [largest one](fn@predict_category)
+    /// - Epsilon: Added to every weighted sum, away from 0, so small sums don't blow up the relevance. Larger values keep only the strongest contributions, and 0.01 is a good start.
+    /// 
+    /// ## Returns
+    /// The relevance of every input, all zero if the answer node doesn't exist
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = CatNetwork::new(3, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// let relevance: Vec<f32> = net.relevance(&[0.2, 0.9, 0.4], 1, 0.01);
+    /// assert_eq!(relevance.len(), 3);
+    /// ```
+    pub fn relevance(&self, inputs: &[f32], answer_node: usize, epsilon: f32) -> Vec<f32> {
+        let layers: Vec<Vec<f32>> = self.forward_layers(inputs);
+        let answer: usize = self.answer.unwrap();
+        if answer_node >= layers[answer].len() {
+            return vec![0.0; self.node_array[0].len()];
+        }
+
+        let mut relevance: Vec<f32> = vec![0.0; layers[answer].len()];
+        relevance[answer_node] = layers[answer][answer_node];
+        for layer_i in (1..self.node_array.len()).rev() {
+            let mut previous: Vec<f32> = vec![0.0; layers[layer_i - 1].len()];
+            self.node_array[layer_i].iter().enumerate().for_each(|(node_i, node)| {
+                if relevance[node_i] == 0.0 {
+                    return;
+                }
+                let sum: f32 = layers[layer_i - 1].iter().zip(&node.link_weights).map(|(value, weight)| value * weight).sum::<f32>() + node.b_weight.unwrap_or(0.0);
+                let share: f32 = relevance[node_i] / (sum + if sum < 0.0 { -epsilon } else { epsilon });
+                layers[layer_i - 1].iter().zip(&node.link_weights).enumerate().for_each(|(link, (value, weight))| previous[link] += value * weight * share);
+            });
+            relevance = previous;
+        }
+        relevance
+    }
+
+    /// Searches for a small change to the inputs that makes the network choose another category, to explain a decision by what would have changed it.
+    /// The search follows the gradient of the target category's lead over its strongest rival until the target wins,
+    /// then pulls the change back towards the original inputs as far as it can, and undoes the changes to any features the target doesn't need.
+    /// 
+    /// ## Params
+    /// - Inputs: The values to change
+    /// - Target: The category to reach
+    /// - Constraints: How each feature may change, in the same order as the inputs. Empty lets every feature change freely.
+    /// 
+    /// ## Returns
+    /// The changed inputs, or None if the search couldn't reach the target within the constraints
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// There isn't one input for every sensor, or the constraints aren't empty and there isn't one for every input
+    /// - ### UnknownCategory
+    /// The target isn't one of the network's categories
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// // Income can only go up, and age can't change
+    /// let constraints = vec![Mutability::Increase, Mutability::Fixed, Mutability::Range { min: 0.0, max: 1.0 }];
+    /// if let Some(counterfactual) = net.counterfactual(&applicant, &Types::String("approved".to_string()), &constraints)? {
+    ///     for (feature, change) in counterfactual.changes {
+    ///         println!("Change {} by {}", feature, change);
+    ///     }
+    /// }
+    /// ```
+    pub fn counterfactual(&self, inputs: &[f32], target: &Types, constraints: &[Mutability]) -> Result<Option<Counterfactual>, DarjeelingError> {
+        let sensors: usize = self.input_size();
+        if inputs.len() != sensors {
+            return Err(DarjeelingError::InvalidInput(format!("The network has {} sensors, but there are {} inputs", sensors, inputs.len())));
+        }
+        if !constraints.is_empty() && constraints.len() != sensors {
+            return Err(DarjeelingError::InvalidInput(format!("There are {} constraints for {} inputs", constraints.len(), sensors)));
+        }
+        let target_node: usize = match self.categories().and_then(|categories| categories.iter().position(|category| category.approx_eq(target, self.float_tolerance))) {
+            Some(node) => node,
+            None => return Err(DarjeelingError::UnknownCategory(format!("{} isn't one of the network's categories", target)))
+        };
+        let constraint = |i: usize| constraints.get(i).copied().unwrap_or_default();
+        // How much a raw unit of each feature moves it once scaled, 0 for features the scaler flattens
+        let slopes: Vec<f32> = (0..sensors).map(|i| match &self.scaler {
+            Some(scaler) => scaler.scale(i, 1.0) - scaler.scale(i, 0.0),
+            None => 1.0
+        }).collect();
+        let reaches = |candidate: &[f32]| {
+            let outputs: Vec<f32> = self.forward(candidate);
+            (0..outputs.len()).all(|i| i == target_node || outputs[i] < outputs[target_node])
+        };
+
+        let mut candidate: Vec<f32> = inputs.to_vec();
+        let mut reached: bool = reaches(&candidate);
+        for _ in 0..COUNTERFACTUAL_STEPS {
+            if reached {
+                break;
+            }
+            let outputs: Vec<f32> = self.forward(&candidate);
+            let rival: usize = match (0..outputs.len()).filter(|i| *i != target_node).max_by(|a, b| outputs[*a].total_cmp(&outputs[*b])) {
+                Some(rival) => rival,
+                None => break
+            };
+            let toward: Vec<f32> = self.input_gradients(&candidate, target_node);
+            let away: Vec<f32> = self.input_gradients(&candidate, rival);
+            // The gradient of the target's lead in scaled units, so features on large scales don't take huge steps
+            let direction: Vec<f32> = (0..sensors)
+                .map(|i| if slopes[i] == 0.0 || constraint(i) == Mutability::Fixed { 0.0 } else { (toward[i] - away[i]) / slopes[i] })
+                .collect();
+            let largest: f32 = direction.iter().fold(0.0, |largest: f32, value| largest.max(value.abs()));
+            if largest == 0.0 || largest.is_nan() {
+                break;
+            }
+            (0..sensors).for_each(|i| if direction[i] != 0.0 {
+                let stepped: f32 = candidate[i] + COUNTERFACTUAL_STEP * direction[i] / largest / slopes[i];
+                candidate[i] = constraint(i).constrain(inputs[i], stepped);
+            });
+            reached = reaches(&candidate);
+        }
+        if !reached {
+            return Ok(None);
+        }
+
+        // When the original inputs meet the constraints, so does every point on the line back to them
+        let along = |fraction: f32| -> Vec<f32> { (0..sensors).map(|i| inputs[i] + fraction * (candidate[i] - inputs[i])).collect() };
+        let (mut low, mut high) = (0.0f32, 1.0f32);
+        (0..20).for_each(|_| {
+            let middle: f32 = (low + high) / 2.0;
+            if reaches(&along(middle)) { high = middle } else { low = middle }
+        });
+        candidate = along(high);
+
+        let mut changed: Vec<usize> = (0..sensors).filter(|i| candidate[*i] != inputs[*i]).collect();
+        changed.sort_by(|a, b| ((candidate[*a] - inputs[*a]) * slopes[*a]).abs().total_cmp(&((candidate[*b] - inputs[*b]) * slopes[*b]).abs()));
+        changed.into_iter().for_each(|i| {
+            let kept: f32 = candidate[i];
+            candidate[i] = inputs[i];
+            if !reaches(&candidate) {
+                candidate[i] = kept;
+            }
+        });
+
+        let changes: Vec<(usize, f32)> = (0..sensors).filter(|i| candidate[*i] != inputs[*i]).map(|i| (i, candidate[i] - inputs[i])).collect();
+        Ok(Some(Counterfactual {
+            distance: changes.iter().map(|(i, change)| (change * slopes[*i]).abs()).sum(),
+            changes,
+            prediction: self.prediction(&self.forward(&candidate), self.lenient_tie_break())?,
+            inputs: candidate
+        }))
+    }
+
+    /// How confident the network is in the category it chooses for the inputs.
+    /// This is the brightness of the brightest answer node, unless a [calibrator](fn@set_calibrator) is set.
+    pub fn confidence(&self, inputs: &[f32]) -> f32 {
+        self.output_confidence(&self.forward(inputs))
+    }
+
+    fn output_confidence(&self, outputs: &[f32]) -> f32 {
+        match &self.calibrator {
+            Some(calibrator) => calibrator.confidence(outputs),
+            None => outputs[largest_index(outputs)].clamp(0.0, 1.0)
+        }
+    }
+
+    /// Compares the network's [confidence](fn@confidence) with how often it's right.
+    /// Inputs without an answer are skipped.
+    /// 
+    /// ## Params
+    /// - Data: The inputs to check, ideally ones the network wasn't trained on
+    /// - Buckets: How many confidence ranges 0 to 1 is split into
+    pub fn calibration_report(&self, data: &[Input], buckets: usize) -> CalibrationReport {
+        let mut confidences: Vec<f32> = vec![];
+        let mut correct: Vec<bool> = vec![];
+        data.iter().filter(|input| input.answer.is_some()).for_each(|input| {
+            confidences.push(self.confidence(&input.inputs));
+            correct.push(self.predict_category(&input.inputs) == self.answer_category(input));
+        });
+        CalibrationReport::compute(&confidences, &correct, buckets)
+    }
+
+    /// Learns a calibrator from held out data and sets it, so [confidence](fn@confidence) matches how often the network is right.
+    /// The calibrator is saved with the model.
+    /// 
+    /// ## Params
+    /// - Data: Answered inputs the network wasn't trained on
+    /// - Method: Whether to fit temperature scaling or Platt scaling
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// use darjeeling::calibration::CalibrationMethod;
+    /// 
+    /// println!("{}", net.calibration_report(&validation, 10));
+    /// net.fit_calibrator(&validation, CalibrationMethod::Temperature);
+    /// println!("{}", net.calibration_report(&validation, 10));
+    /// ```
+    pub fn fit_calibrator(&mut self, data: &[Input], method: CalibrationMethod) -> Calibrator {
+        let answered: Vec<&Input> = data.iter().filter(|input| input.answer.is_some()).collect();
+        let outputs: Vec<Vec<f32>> = answered.iter().map(|input| self.forward(&input.inputs)).collect();
+        let calibrator: Calibrator = match method {
+            CalibrationMethod::Temperature => {
+                let answers: Vec<usize> = answered.iter().map(|input| largest_index(&self.targets(input.answer.as_ref().unwrap()))).collect();
+                Calibrator::fit_temperature(&outputs, &answers)
+            },
+            CalibrationMethod::Platt => {
+                let scores: Vec<f32> = outputs.iter().map(|outputs| outputs[largest_index(outputs)]).collect();
+                let correct: Vec<bool> = answered.iter().zip(&outputs).map(|(input, outputs)| {
+                    self.node_array[self.answer.unwrap()][largest_index(outputs)].category.as_ref() == Some(&self.answer_category(input))
+                }).collect();
+                Calibrator::fit_platt(&scores, &correct)
+            }
+        };
+        self.calibrator = Some(calibrator);
+        calibrator
+    }
+
+    pub fn set_calibrator(&mut self, calibrator: Option<Calibrator>) {
+        self.calibrator = calibrator;
+    }
+
+    pub fn calibrator(&self) -> Option<&Calibrator> {
+        self.calibrator.as_ref()
+    }
+
+    /// Evaluates the network's raw answer node outputs against continuous targets.
+    /// Vector answers are compared to every answer node, numeric answers to the first one.
+    /// Inputs without a numeric answer are skipped.
+    /// 
+    /// ## Returns
+    /// The RMSE, MAE and R² over every compared output
+    pub fn evaluate_regression(&self, data: &[Input]) -> RegressionMetrics {
+        let mut predictions: Vec<f32> = vec![];
+        let mut truth: Vec<f32> = vec![];
+        data.iter().for_each(|input| {
+            let targets: Vec<f32> = match &input.answer {
+                Some(Types::Vector(targets)) => targets.clone(),
+                Some(answer) => match answer.as_f32() {
+                    Some(target) => vec![target],
+                    None => return
+                },
+                None => return
+            };
+            let outputs: Vec<f32> = self.forward(&input.inputs);
+            outputs.into_iter().zip(targets).for_each(|(output, target)| {
+                predictions.push(output);
+                truth.push(target);
+            });
+        });
+
+        RegressionMetrics::compute(&predictions, &truth)
+    }
+
+    /// Evaluates every answer node as its own regression output, for networks trained on vector answers,
+    /// like one predicting both x and y coordinates.
+    /// Inputs without a vector answer are skipped.
+    /// 
+    /// ## Returns
+    /// The RMSE, MAE and R² of every output, named by the categories of the answer nodes, and over all of them
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// net.start_regression(&["x", "y"]).unwrap();
+    /// // Each input's answer is Types::Vector(vec![x, y])
+    /// net.step_epoch(&mut data, 0.5).unwrap();
+    /// let metrics = net.evaluate_regression_heads(&data);
+    /// println!("{}", metrics);
+    /// ```
+    pub fn evaluate_regression_heads(&self, data: &[Input]) -> MultiOutputMetrics {
+        let mut predictions: Vec<Vec<f32>> = vec![];
+        let mut truth: Vec<Vec<f32>> = vec![];
+        data.iter().for_each(|input| {
+            if let Some(Types::Vector(targets)) = &input.answer {
+                predictions.push(self.forward(&input.inputs));
+                truth.push(targets.clone());
+            }
+        });
+
+        let names: Vec<Types> = self.categories().unwrap_or_else(|| (0..self.output_size()).map(|head| Types::Integer(head as i32)).collect());
+        MultiOutputMetrics::compute(names, &predictions, &truth)
+    }
+
+    /// Clamps every link and bias weight after the inputs to `-clip..=clip`
+    pub(crate) fn clip_weights(&mut self, clip: f32) {
+        self.node_array.iter_mut().skip(1).flatten().for_each(|node| {
+            node.link_weights.iter_mut().for_each(|weight| *weight = weight.clamp(-clip, clip));
+            node.b_weight = node.b_weight.map(|bias| bias.clamp(-clip, clip));
+        });
+    }
+
+    /// One update of a Wasserstein critic, whose score is its real answer node minus its generated one.
+    /// The score of a real sample is pushed up and the score of a generated one down, with no target to squash them towards,
+    /// then the weights are [clipped](fn@clip_weights) so the critic stays Lipschitz.
+    /// Call [`start_training`](fn@start_training) with Boolean(true) and Boolean(false) first.
+    pub(crate) fn critic_step(&mut self, sample: &Input, learning_rate: f32, clip: f32) {
+        let real: bool = matches!(sample.answer, Some(Types::Boolean(true)));
+        self.push_downstream(&sample.inputs);
+        let activation_function: ActivationFunction = self.activation_function;
+        self.node_array[self.answer.unwrap()].iter_mut().for_each(|node| {
+            let real_node: bool = matches!(node.category, Some(Types::Boolean(true)));
+            node.compute_answer_err_sig_gen(if real_node == real { 1.0 } else { -1.0 }, &activation_function);
+        });
+        let hidden_layers = (self.node_array.len() - 2) as i32;
+        self.adjust_hidden_weights(learning_rate, hidden_layers);
+        for answer in 0..self.node_array[self.answer.unwrap()].len() {
+            self.node_array[self.answer.unwrap()][answer].adjust_weights(learning_rate);
+        }
+        self.clip_weights(clip);
+    }
+
+    /// Runs the inputs through the network without changing it
+    /// 
+    /// ## Returns
+    /// The outputs of every layer, starting with the (scaled) inputs and ending with the answer layer
+    pub(crate) fn forward_layers(&self, inputs: &[f32]) -> Vec<Vec<f32>> {
+        let sensors: Vec<f32> = (0..self.node_array[0].len())
+            .map(|i| match &self.scaler {
+                Some(scaler) => scaler.scale(i, inputs[i]),
+                None => inputs[i]
+            })
+            .collect();
+        let mut layers: Vec<Vec<f32>> = vec![sensors];
+        (1..self.node_array.len()).for_each(|layer_i| {
+            let outputs: Vec<f32> = self.node_array[layer_i]
+                .iter()
+                .map(|node| node.output_for_reduced(&layers[layer_i - 1], &self.activation_function, self.reduction))
+                .collect();
+            layers.push(outputs);
+        });
+        layers
+    }
+
+    /// Runs the inputs through the network without changing it
+    /// 
+    /// ## Returns
+    /// The chosen category, how confident the network is in it, and the raw output for every category
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, types::Types};
+    /// 
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::String("off".to_string()), Types::String("on".to_string())]);
+    /// let prediction = net.predict(&[0.0, 1.0]);
+    /// assert_eq!(prediction.scores.len(), 2);
+    /// assert_eq!(prediction.score(&prediction.category), Some(prediction.scores.iter().map(|(_, score)| *score).fold(f32::MIN, f32::max)));
+    /// ```
+    pub fn predict(&self, inputs: &[f32]) -> Prediction {
+        let outputs: Vec<f32> = self.forward(inputs);
+        let prediction: Prediction = self.prediction(&outputs, self.lenient_tie_break()).expect("Only the error policy fails");
+        self.audit(inputs, &prediction);
+        prediction
+    }
+
+    /// [`predict`](fn@predict), except that ties are an error when the [tie break](fn@set_tie_break) policy is [`TieBreak::Error`].
+    /// `predict` picks the first tied category under that policy, and lists the ties in the prediction.
+    /// 
+    /// ## Err
+    /// - ### TiedPrediction
+    /// More than one answer node had the largest output, and the policy is to refuse to pick
+    pub fn try_predict(&self, inputs: &[f32]) -> Result<Prediction, DarjeelingError> {
+        let outputs: Vec<f32> = self.forward(inputs);
+        let prediction: Prediction = self.prediction(&outputs, self.tie_break)?;
+        self.audit(inputs, &prediction);
+        Ok(prediction)
+    }
+
+    fn prediction(&self, outputs: &[f32], tie_break: TieBreak) -> Result<Prediction, DarjeelingError> {
+        let answer_layer: &[Node] = &self.node_array[self.answer.unwrap()];
+        let (chosen, tied) = self.decide(outputs, tie_break)?;
+        Ok(Prediction {
+            category: answer_layer[chosen].category.clone().unwrap(),
+            confidence: self.output_confidence(outputs),
+            scores: answer_layer.iter().zip(outputs.iter()).map(|(node, output)| (node.category.clone().unwrap(), *output)).collect(),
+            tied: tied.iter().map(|i| answer_layer[*i].category.clone().unwrap()).collect()
+        })
+    }
+
+    /// The tie break policy, with [`TieBreak::Error`] picking the first tied node instead, for places that have to pick something
+    fn lenient_tie_break(&self) -> TieBreak {
+        match self.tie_break {
+            TieBreak::Error => TieBreak::First,
+            tie_break => tie_break
+        }
+    }
+
+    /// The category of the brightest answer node for the inputs, without changing the network.
+    /// The network's categories have to be set, either by training it or with [`set_categories`](fn@set_categories)
+    pub fn predict_category(&self, inputs: &[f32]) -> Types {
+        if self.audit_hook.is_some() {
+            return self.predict(inputs).category;
+        }
+        let outputs: Vec<f32> = self.forward(inputs);
+        let (chosen, _) = self.decide(&outputs, self.lenient_tie_break()).expect("Only the error policy fails");
+        self.node_array[self.answer.unwrap()][chosen].category.clone().unwrap()
+    }
+
+    /// Hands the prediction to the [audit hook](fn@set_audit_hook), if there is one
+    fn audit(&self, inputs: &[f32], prediction: &Prediction) {
+        if let Some(hook) = &self.audit_hook {
+            hook.record(&PredictionAudit::new(inputs, prediction.scores.clone(), prediction.category.clone()));
+        }
+    }
+
+    /// Sets a hook that's called with a record of every prediction, [`predict`](fn@predict), [`try_predict`](fn@try_predict)
+    /// and [`predict_category`](fn@predict_category) alike, for keeping an audit trail of automated decisions.
+    /// The hook isn't saved with the model, and clones of the network share it.
+    /// Records are timestamped with the system clock, which `wasm32-unknown-unknown` doesn't have, so don't set one there.
+    pub fn set_audit_hook(&mut self, hook: Option<AuditHook>) {
+        self.audit_hook = hook;
+    }
+
+    /// How likely each answer node is, from the [calibrator](fn@set_calibrator) if it spreads its confidence over every node,
+    /// or else the outputs themselves, which are normalized wherever they're used
+    fn output_probabilities(&self, outputs: &[f32]) -> Vec<f32> {
+        self.calibrator.as_ref().and_then(|calibrator| calibrator.probabilities(outputs)).unwrap_or_else(|| outputs.to_vec())
+    }
+
+    /// The inputs the network is least sure about, for picking what to label next in an active learning loop.
+    /// Uncertainty is the [entropy](Uncertainty::Entropy) of the answer probabilities.
+    /// 
+    /// ## Params
+    /// - Pool: Inputs that could be labeled. Answers are ignored.
+    /// - N: How many to pick. Fewer are returned if the pool is smaller.
+    /// 
+    /// ## Returns
+    /// The indexes of the picked inputs in the pool, most uncertain first
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// for i in net.most_uncertain(&pool, 10) {
+    ///     labeled.push(ask_an_annotator(&pool[i]));
+    /// }
+    /// ```
+    pub fn most_uncertain(&self, pool: &[Input], n: usize) -> Vec<usize> {
+        self.most_uncertain_by(pool, n, Uncertainty::Entropy)
+    }
+
+    /// [`most_uncertain`](fn@most_uncertain) with a different measure of uncertainty
+    pub fn most_uncertain_by(&self, pool: &[Input], n: usize, uncertainty: Uncertainty) -> Vec<usize> {
+        let scores: Vec<f32> = pool.iter().map(|input| uncertainty.score(&self.output_probabilities(&self.forward(&input.inputs)))).collect();
+        let mut ranked: Vec<usize> = (0..pool.len()).collect();
+        ranked.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+        ranked.truncate(n);
+        ranked
+    }
+
+    /// Picks the brightest answer node, or the one expected to cost the least if there's a [cost matrix](fn@set_cost_matrix).
+    /// A [decision threshold](fn@set_threshold) picks its category first whenever it's likely enough, and keeps it out of the running when it isn't.
+    fn decide(&self, outputs: &[f32], tie_break: TieBreak) -> Result<(usize, Vec<usize>), DarjeelingError> {
+        match self.threshold.as_ref().and_then(|threshold| Some((self.threshold_node(&threshold.category)?, threshold.threshold))) {
+            Some((node, threshold)) if self.output_probabilities(outputs)[node] >= threshold => Ok((node, vec![])),
+            Some((node, _)) => self.decide_without(outputs, Some(node), tie_break),
+            None => self.decide_without(outputs, None, tie_break)
+        }
+    }
+
+    /// Picks like [`decide`](fn@decide) without a threshold, never picking the excluded answer node if there's another
+    fn decide_without(&self, outputs: &[f32], excluded: Option<usize>, tie_break: TieBreak) -> Result<(usize, Vec<usize>), DarjeelingError> {
+        let mut scores: Vec<f32> = match &self.cost_matrix {
+            Some(costs) => costs.expected_costs(&self.output_probabilities(outputs)).into_iter().map(|cost| -cost).collect(),
+            None => outputs.to_vec()
+        };
+        if let Some(excluded) = excluded.filter(|_| scores.len() > 1) {
+            scores[excluded] = f32::NEG_INFINITY;
+        }
+        tie_break.choose(&scores)
+    }
+
+    /// The answer node of a category
+    fn threshold_node(&self, category: &Types) -> Option<usize> {
+        self.node_array[self.answer?].iter().position(|node| node.category.as_ref() == Some(category))
+    }
+
+    /// Times forward passes through the network, for planning how many predictions a machine can serve with this build and its features.
+    /// A few untimed passes run first, so the caches are warm.
+    /// 
+    /// ## Params
+    /// - N Samples: How many forward passes to time
+    /// - Input Gen: Makes the inputs for each sample, given its index. Making them isn't timed.
+    /// 
+    /// ## Returns
+    /// The latency percentiles and throughput of the forward passes
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// No samples were asked for, or the generator made an input with the wrong number of values
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = CatNetwork::new(4, 8, 3, 2, ActivationFunction::Sigmoid);
+    /// let report = net.benchmark(1000, |i| vec![i as f32 / 1000.0; 4]).unwrap();
+    /// assert_eq!(report.samples, 1000);
+    /// assert!(report.p50 <= report.p99);
+    /// println!("{}", report);
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn benchmark<F: FnMut(usize) -> Vec<f32>>(&self, n_samples: usize, mut input_gen: F) -> Result<BenchmarkReport, DarjeelingError> {
+        if n_samples == 0 {
+            return Err(DarjeelingError::InvalidInput("The benchmark needs at least one sample".to_string()));
+        }
+        let mut latencies: Vec<Duration> = Vec::with_capacity(n_samples);
+        for i in 0..n_samples {
+            let inputs: Vec<f32> = input_gen(i);
+            if inputs.len() != self.input_size() {
+                return Err(DarjeelingError::InvalidInput(format!("Sample {} has {} values, but the model takes {}", i, inputs.len(), self.input_size())));
+            }
+            if i == 0 {
+                (0..n_samples.min(10)).for_each(|_| { std::hint::black_box(self.forward(&inputs)); });
+            }
+            let start = Instant::now();
+            std::hint::black_box(self.forward(std::hint::black_box(&inputs)));
+            latencies.push(start.elapsed());
+        }
+        Ok(BenchmarkReport::from_latencies(latencies))
+    }
+
+    /// The answer layer's activations for the inputs, without picking a category,
+    /// for applying your own softmax, calibration or ranking instead of taking the brightest node.
+    /// They're in the same order as [`categories`](fn@categories), and the network doesn't need its categories set.
+    /// 
+    /// ## Panics
+    /// If there are fewer inputs than the network has sensors
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = CatNetwork::new(2, 2, 3, 1, ActivationFunction::Sigmoid);
+    /// let outputs: Vec<f32> = net.raw_outputs(&[0.0, 1.0]);
+    /// assert_eq!(outputs.len(), 3);
+    /// assert!(outputs.iter().all(|output| (0.0..=1.0).contains(output)));
+    /// ```
+    pub fn raw_outputs(&self, inputs: &[f32]) -> Vec<f32> {
+        self.forward(inputs)
+    }
+
+    /// Runs many inputs through the network at once without changing it, a layer at a time for the whole batch,
+    /// so each node's weights are used for every sample before moving on to the next node.
+    /// The inputs' answers are ignored.
+    /// 
+    /// ## Returns
+    /// The answer layer's outputs for every input, in the same order
+    /// 
+    /// ## Panics
+    /// If an input has fewer values than the network has sensors
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input};
+    /// 
+    /// let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let batch = vec![Input::new(vec![0.0, 1.0], None), Input::new(vec![1.0, 1.0], None)];
+    /// let outputs: Vec<Vec<f32>> = net.forward_batch(&batch);
+    /// assert_eq!(outputs.len(), 2);
+    /// assert_eq!(outputs[1].len(), 2);
+    /// ```
+    pub fn forward_batch(&self, inputs: &[Input]) -> Vec<Vec<f32>> {
+        let mut batch: Vec<Vec<f32>> = inputs.iter()
+            .map(|input| (0..self.node_array[0].len())
+                .map(|i| match &self.scaler {
+                    Some(scaler) => scaler.scale(i, input.inputs[i]),
+                    None => input.inputs[i]
+                })
+                .collect())
+            .collect();
+        self.node_array[1..].iter().for_each(|layer| {
+            let mut outputs: Vec<Vec<f32>> = vec![Vec::with_capacity(layer.len()); batch.len()];
+            layer.iter().for_each(|node| {
+                batch.iter().zip(outputs.iter_mut()).for_each(|(previous, output)| output.push(node.output_for_reduced(previous, &self.activation_function, self.reduction)));
+            });
+            batch = outputs;
+        });
+        batch
+    }
+
+    /// Runs the inputs through the network without changing it, returning the answer layer's outputs
+    pub(crate) fn forward(&self, inputs: &[f32]) -> Vec<f32> {
+        self.forward_layers(inputs).pop().unwrap_or_default()
+    }
+
+    /// Assigns categories to answer nodes based on a list of given categories
+    /// The categories of the answer nodes, in order. None if they haven't been set.
+    /// They're saved with the model, so a read model has the categories it was trained with.
+    pub fn categories(&self) -> Option<Vec<Types>> {
+        self.node_array[self.answer?].iter().map(|node| node.category.clone()).collect()
+    }
+
+    /// The number of sensors, or values every input needs
+    pub fn input_size(&self) -> usize {
+        self.node_array[0].len()
+    }
+
+    /// Names the inputs, so errors, [drift reports](fn@detect_drift) and [pictures](fn@to_dot) of the network use the names instead of indexes.
+    /// The names are saved with the model.
+    ///
+    /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The schema doesn't name as many features as the network has sensors
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, schema::Schema};
+    ///
+    /// let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_schema(Some(Schema::new(vec!["age".to_string(), "income".to_string()]).unwrap())).unwrap();
+    /// assert_eq!(net.feature_name(1), "income");
+    /// assert!(net.set_schema(Some(Schema::new(vec!["age".to_string()]).unwrap())).is_err());
+    /// ```
+    pub fn set_schema(&mut self, schema: Option<Schema>) -> Result<(), DarjeelingError> {
+        if let Some(schema) = &schema {
+            if schema.len() != self.input_size() {
+                return Err(DarjeelingError::ArchitectureMismatch(format!("The schema names {} features, but the network has {} sensors", schema.len(), self.input_size())));
+            }
+        }
+        self.schema = schema;
+        Ok(())
+    }
+
+    pub fn schema(&self) -> Option<&Schema> {
+        self.schema.as_ref()
+    }
+
+    /// The name of an input from the [schema](fn@set_schema), or "feature i" if there isn't one
+    pub fn feature_name(&self, feature: usize) -> String {
+        schema::feature_name(self.schema.as_ref(), feature)
+    }
+
+    /// The number of answer nodes
+    pub fn output_size(&self) -> usize {
+        self.node_array[self.answer.unwrap()].len()
+    }
+
+    /// Checks that the network can be tested on or predict the data before starting a long run,
+    /// instead of panicking or giving wrong answers partway through.
+    /// Inputs without answers only have their values checked.
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// An input doesn't have one value per sensor, or has a value that isn't finite
+    /// - ### ArchitectureMismatch
+    /// The network's scaler was fit on a different number of features than the network has sensors
+    /// - ### UnknownCategory
+    /// An answer isn't one of the network's categories, or a vector answer doesn't have one target per answer node
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input, types::Types};
+    /// 
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// assert!(net.check_compatibility(&[Input::new(vec![0.0, 1.0], Some(Types::Boolean(true)))]).is_ok());
+    /// assert!(net.check_compatibility(&[Input::new(vec![0.0, 1.0, 2.0], None)]).is_err());
+    /// assert!(net.check_compatibility(&[Input::new(vec![0.0, 1.0], Some(Types::Boolean(true))), Input::new(vec![0.0, 1.0], Some(Types::Integer(2)))]).is_err());
+    /// ```
+    pub fn check_compatibility(&self, data: &[Input]) -> Result<(), DarjeelingError> {
+        if let Some(scaler) = &self.scaler {
+            if scaler.features() != self.input_size() {
+                return Err(DarjeelingError::ArchitectureMismatch(format!("The scaler was fit on {} features, but the network has {} sensors", scaler.features(), self.input_size())));
+            }
+        }
+        let categories: Option<Vec<Types>> = self.categories();
+        for (i, input) in data.iter().enumerate() {
+            if input.inputs.len() != self.input_size() {
+                return Err(DarjeelingError::InvalidInput(format!("Input {} has {} values, but the network has {} sensors", i, input.inputs.len(), self.input_size())));
+            }
+            if let Some(feature) = input.inputs.iter().position(|value| !value.is_finite()) {
+                return Err(DarjeelingError::InvalidInput(format!("{} of input {} is {}", self.feature_name(feature), i, input.inputs[feature])));
+            }
+            match (&input.answer, &categories) {
+                (Some(Types::Vector(targets)), _) if targets.len() != self.output_size() => {
+                    return Err(DarjeelingError::UnknownCategory(format!("Input {} has {} targets, but the network has {} answer nodes", i, targets.len(), self.output_size())));
+                },
+                (Some(answer), Some(categories)) if !matches!(answer, Types::Vector(_)) && !categories.iter().any(|category| category.approx_eq(answer, self.float_tolerance)) => {
+                    return Err(DarjeelingError::UnknownCategory(format!("Input {} is answered {}, which isn't one of the network's categories {:?}", i, answer, categories)));
+                },
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Names the answer nodes, in order. Training does this, and models saved since categories were written keep them.
+    /// Older models need this before they can be evaluated.
+    pub fn set_categories(&mut self, categories: Vec<Types>) {
+        self.categorize(categories);
+    }
+
+    /// The category of one answer node, None if the node doesn't exist or hasn't been named
+    pub fn category(&self, output: usize) -> Option<&Types> {
+        self.node_array[self.answer?].get(output)?.category.as_ref()
+    }
+
+    /// Renames the category of one answer node after training, without changing what the network learned,
+    /// like naming the node trained on `Boolean(true)` "approved". The name is saved with the model,
+    /// and predictions answer with it from then on.
+    ///
+    /// ## Params
+    /// - Output: The index of the answer node
+    /// - Category: Its new name
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The answer node doesn't exist, or another answer node already has the name
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, types::Types};
+    ///
+    /// let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// net.rename_category(1, Types::String("approved".to_string())).unwrap();
+    /// assert_eq!(net.category(1), Some(&Types::String("approved".to_string())));
+    /// assert!(net.rename_category(0, Types::String("approved".to_string())).is_err());
+    /// ```
+    pub fn rename_category(&mut self, output: usize, category: Types) -> Result<(), DarjeelingError> {
+        if output >= self.output_size() {
+            return Err(DarjeelingError::InvalidInput(format!("There's no answer node {}, the network has {}", output, self.output_size())));
+        }
+        let mut categories: Vec<Option<Types>> = self.node_array[self.answer.unwrap()].iter().map(|node| node.category.clone()).collect();
+        categories[output] = Some(category);
+        self.rename(categories)
+    }
+
+    /// Renames several categories at once, by their current names, so names can be swapped around
+    ///
+    /// ## Params
+    /// - Names: Pairs of a current category and its new name. Categories that aren't listed keep their names.
+    ///
+    /// ## Err
+    /// - ### UnknownCategory
+    /// A category being renamed isn't one of the network's
+    /// - ### InvalidInput
+    /// Two answer nodes would end up with the same name
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// net.relabel(&[(Types::Integer(0), Types::String("cat".to_string())), (Types::Integer(1), Types::String("dog".to_string()))])?;
+    /// ```
+    pub fn relabel(&mut self, names: &[(Types, Types)]) -> Result<(), DarjeelingError> {
+        let current: Vec<Option<Types>> = self.node_array[self.answer.unwrap()].iter().map(|node| node.category.clone()).collect();
+        if let Some((from, _)) = names.iter().find(|(from, _)| !current.contains(&Some(from.clone()))) {
+            return Err(DarjeelingError::UnknownCategory(format!("{} isn't one of the network's categories", from)));
+        }
+        let renamed: Vec<Option<Types>> = current.into_iter().map(|category| {
+            let name: Option<&Types> = names.iter().find(|(from, _)| Some(from) == category.as_ref()).map(|(_, to)| to);
+            name.cloned().or(category)
+        }).collect();
+        self.rename(renamed)
+    }
+
+    /// Names the answer nodes, keeping the names the [data profile](fn@data_profile) knows the predictions by in step
+    fn rename(&mut self, categories: Vec<Option<Types>>) -> Result<(), DarjeelingError> {
+        if let Some(i) = (1..categories.len()).find(|i| categories[*i].is_some() && categories[..*i].contains(&categories[*i])) {
+            return Err(DarjeelingError::InvalidInput(format!("More than one answer node would be named {}", categories[i].as_ref().expect("Only named nodes are checked"))));
+        }
+        let answer_layer: usize = self.answer.unwrap();
+        if let Some(profile) = self.data_profile.as_mut() {
+            profile.predictions.iter_mut().for_each(|(category, _)| {
+                if let Some(i) = self.node_array[answer_layer].iter().position(|node| node.category.as_ref() == Some(category)) {
+                    if let Some(renamed) = &categories[i] {
+                        *category = renamed.clone();
+                    }
+                }
+            });
+        }
+        self.node_array[answer_layer].iter_mut().zip(categories).for_each(|(node, category)| node.category = category);
+        Ok(())
+    }
+
+    fn categorize(&mut self, categories: Vec<Types>) {
+        let mut count: usize = 0;
+        self.node_array[self.answer.unwrap()].iter_mut().for_each(|node| {
+            node.category = Some(categories[count].clone());
+            count += 1;
+        });
+    }
+    
+    fn assign_answers(&mut self, input: &Input) {
+        let answer: &Types = input.answer.as_ref().unwrap();
+        // Vector answers give every answer node its own target
+        if let Types::Vector(targets) = answer {
+            self.node_array[self.answer.unwrap()].iter_mut().enumerate().for_each(|(i, node)| {
+                node.correct_answer = Some(targets.get(i).copied().unwrap_or(0.0));
+            });
+            return;
+        }
+        let _ = self.node_array[self.answer.unwrap()].iter_mut().for_each(|mut node| {
+            // println!("{:?}", input);
+            if node.category.as_ref().unwrap().approx_eq(answer, self.float_tolerance) {
+                node.correct_answer = Some(1.0);
+            } else {
+                node.correct_answer = Some(0.0);
+            }
+        });
+    }
+
+    /// Passes in data to the sensors, pushs data 'downstream' through the network
+    fn push_downstream(&mut self, inputs: &[f32]) {
+        // Passes in data for input layer
+        (0..self.node_array[0].len()).into_iter().for_each(|i| {
+            let input: f32 = match &self.scaler {
+                Some(scaler) => scaler.scale(i, inputs[i]),
+                None => inputs[i]
+            };
+            self.node_array[0][i].cached_output = Some(input);
+        });
+        #[cfg(feature = "mixed-precision")]
+        if self.mixed_precision.is_some() {
+            self.node_array[0].iter_mut().for_each(|node| node.cached_output = node.cached_output.map(MixedPrecision::reduce));
+        }
+
+        // Feed-forward values for hidden and output layers
+        (1..self.node_array.len()).into_iter().for_each(|layer_i| {
+            #[cfg(not(target_arch = "wasm32"))]
+            let start: Option<Instant> = self.start_layer_clock();
+            (0..self.node_array[layer_i].len()).into_iter().for_each(|node_i| {
+                (0..self.node_array[layer_i - 1].len()).into_iter().for_each(|prev_node_i| {
+                    // self.node_array[layer][node].link_vals.push(self.node_array[layer-1][prev_node].cached_output.unwrap());
+                    self.node_array[layer_i][node_i].link_vals[prev_node_i] = Some(self.node_array[layer_i-1][prev_node_i].cached_output.unwrap());
+                    // I think this line needs to be un-commented
+                    self.node_array[layer_i][node_i].output_reduced(&self.activation_function, self.reduction);
+                    if layer_i == self.answer.unwrap() { dbg_println!("Ran output on answer {:?}", self.node_array[layer_i][node_i].cached_output); }
+                });
+                self.node_array[layer_i][node_i].output_reduced(&self.activation_function, self.reduction);
+                #[cfg(feature = "mixed-precision")]
+                if self.mixed_precision.is_some() {
+                    self.node_array[layer_i][node_i].cached_output = self.node_array[layer_i][node_i].cached_output.map(MixedPrecision::reduce);
+                }
+            });
+            #[cfg(not(target_arch = "wasm32"))]
+            self.stop_layer_clock(layer_i, start, false);
+        });
+    }
+
+    /// Starts timing a layer, if the layers are being [profiled](fn@set_profile_layers)
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_layer_clock(&mut self) -> Option<Instant> {
+        if !self.profile_layers {
+            return None;
+        }
+        if self.layer_timings.len() != self.node_array.len() {
+            self.layer_timings = (0..self.node_array.len()).map(|layer| LayerTiming { layer, ..LayerTiming::default() }).collect();
+        }
+        Some(Instant::now())
+    }
+
+    /// Adds the time since the layer's clock started to its forward or backward time
+    #[cfg(not(target_arch = "wasm32"))]
+    fn stop_layer_clock(&mut self, layer: usize, start: Option<Instant>, backward: bool) {
+        if let (Some(start), Some(timing)) = (start, self.layer_timings.get_mut(layer)) {
+            match backward {
+                true => timing.backward += start.elapsed(),
+                false => timing.forward += start.elapsed()
+            }
+        }
+    }
+
+    /// Analyses the chosen answer node's result.
+    /// Also increments sum and count
+    ///
+    /// ## Returns
+    /// The chosen category, and the chosen node's squared error if the input has an answer
+    fn self_analysis<'b>(
+        &'b self, 
+        epochs: &mut Option<f32>, 
+        sum: &'b mut f32, 
+        count: &'b mut f32, 
+        input: &Input
+    ) -> (Types, Option<f32>) {
+        dbg_println!("answer {}", self.answer.unwrap());
+        dbg_println!("largest index {}", self.largest_node());
+        dbg_println!("{:?}", self);
+        let brightest_node: &Node = &self.node_array[self.answer.unwrap()][self.largest_node()];
+        let brightness: f32 = brightest_node.cached_output.unwrap();
+
+        if !(epochs.is_none()) { // This won't happen during testing
+            if epochs.unwrap() % 10.0 == 0.0 && epochs.unwrap() != 0.0 {
+                println!("\n-------------------------\n");
+                println!("Epoch: {:?}", epochs);
+                println!("Category: {:?} \nBrightness: {:?}", brightest_node.category.as_ref().unwrap(), brightness);
+                if DEBUG {
+                    let dimest_node: &Node = &self.node_array[self.answer.unwrap()][self.node_array[self.answer.unwrap()].len()-1-self.largest_node()];
+                    println!("Chosen category: {:?} \nDimest Brightness: {:?}", dimest_node.category.as_ref().unwrap(), dimest_node.cached_output.unwrap());
+                }
+            }
+        }
+
+        dbg_println!("Category: {:?} \nBrightness: {:?}", brightest_node.category.as_ref().unwrap(), brightness);
+        if let Some(answer) = &input.answer {
+            let correct: bool = match answer {
+                // A vector answer is correct when the brightest node is the one with the largest target
+                Types::Vector(targets) => largest_index(targets) == self.largest_node(),
+                _ => brightest_node.category.as_ref().unwrap().approx_eq(answer, self.float_tolerance)
+            };
+            if correct {
+                dbg_println!("Sum++");
+                *sum += 1.0;
+            }
+            *count += 1.0;
+        }
+        let squared_error: Option<f32> = input.answer.as_ref().map(|_| (brightest_node.correct_answer.unwrap() - brightness).powi(2));
+        (brightest_node.category.clone().unwrap(), squared_error)
+    }
+
+    /// Finds the index of the brightest answer node, breaking ties with the network's [tie break](fn@set_tie_break) policy
+    fn largest_node(&self) -> usize {
+        let outputs: Vec<f32> = self.node_array[self.answer.unwrap()].iter().map(|node| node.cached_output.unwrap_or(f32::NAN)).collect();
+        self.lenient_tie_break().choose(&outputs).expect("Only the error policy fails").0
+    }
+    /// Goes back through the network adjusting the weights of the all the neurons based on their error signal
+    /// 
+    /// ## Params
+    /// - Err Scale: Multiplies every answer node's error signal, 1 for plain training
+    fn backpropogate(&mut self, learning_rate: f32, err_scale: f32) {
+        let hidden_layers = (self.node_array.len() - 2) as i32;
+        #[cfg(not(target_arch = "wasm32"))]
+        let start: Option<Instant> = self.start_layer_clock();
+        for answer in 0..self.node_array[self.answer.unwrap()].len() {
+            dbg_println!("Node: {:?}", self.node_array[self.answer.unwrap()][answer]);
+            self.node_array[self.answer.unwrap()][answer].compute_answer_err_sig(&self.activation_function);
+            let weight: f32 = self.output_weights.get(answer).copied().unwrap_or(1.0) * err_scale;
+            self.node_array[self.answer.unwrap()][answer].err_sig = self.node_array[self.answer.unwrap()][answer].err_sig.map(|err_sig| err_sig * weight);
+            dbg_println!("Error: {:?}", self.node_array[self.answer.unwrap()][answer].err_sig.unwrap());
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        self.stop_layer_clock(self.answer.unwrap(), start, true);
+        #[cfg(feature = "mixed-precision")]
+        if let Some(mut precision) = self.mixed_precision {
+            let answer_layer: usize = self.answer.unwrap();
+            let reduced: Option<Vec<f32>> = self.node_array[answer_layer].iter().map(|node| precision.reduce_grad(node.err_sig.unwrap())).collect();
+            let overflowed: bool = reduced.is_none();
+            precision.update_scale(overflowed);
+            self.mixed_precision = Some(precision);
+            match reduced {
+                Some(err_sigs) => self.node_array[answer_layer].iter_mut().zip(err_sigs).for_each(|(node, err_sig)| node.err_sig = Some(err_sig)),
+                // Skips the update, so the overflow doesn't reach the weights
+                None => {
+                    self.node_array.iter_mut().skip(1).flatten().for_each(|node| node.err_sig = Some(0.0));
+                    return;
+                }
+            }
+        }
+        self.adjust_hidden_weights(learning_rate, hidden_layers);
+        // Adjusts weights for answer neurons
+        #[cfg(not(target_arch = "wasm32"))]
+        let start: Option<Instant> = self.start_layer_clock();
+        for answer in 0..self.node_array[self.answer.unwrap()].len() {
+            self.node_array[self.answer.unwrap()][answer].adjust_weights(learning_rate);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        self.stop_layer_clock(self.answer.unwrap(), start, true);
+        if !self.regularization.is_none() {
+            let regularization: Regularization = self.regularization;
+            self.node_array.iter_mut().skip(1).flatten().flat_map(|node| node.link_weights.iter_mut()).for_each(|weight| regularization.apply(weight, learning_rate));
+        }
+        if let Some(anchor) = &self.anchor {
+            anchor.pull(&mut self.node_array[1..], learning_rate);
+        }
+        if let Some(max_norm) = self.max_norm {
+            self.node_array.iter_mut().skip(1).flatten().for_each(|node| node.constrain_norm(max_norm));
+        }
+    }
+
+    #[allow(non_snake_case)]
+    /// Adjusts the weights of all the hidden neurons in a network
+    fn adjust_hidden_weights(&mut self, learning_rate: f32, hidden_layers: i32) {
+        // HIDDEN represents the layer, while hidden represents the node of the layer
+        for HIDDEN in 1..(hidden_layers + 1) as usize {            
+            #[cfg(not(target_arch = "wasm32"))]
+            let start: Option<Instant> = self.start_layer_clock();
+            for hidden in 0..self.node_array[HIDDEN].len() {
                 for next_layer in 0..self.node_array[HIDDEN + 1 ].len() {
-                    let next_weight = self.node_array[HIDDEN + 1][next_layer].link_weights[hidden];
                     self.node_array[HIDDEN + 1][next_layer].err_sig = match self.node_array[HIDDEN + 1][next_layer].err_sig.is_none() {
                         true => {
                             Some(0.0)
@@ -399,185 +2860,1037 @@ impl CatNetwork {
                             self.node_array[HIDDEN + 1][next_layer].err_sig
                         }
                     };
-                    // This changes based on the activation function
-                    self.node_array[HIDDEN][hidden].err_sig = Some(self.node_array[HIDDEN][hidden].err_sig.unwrap() + (self.node_array[HIDDEN + 1][next_layer].err_sig.unwrap() * next_weight));
+                    dbg_println!("next err sig {:?}", self.node_array[HIDDEN + 1][next_layer].err_sig.unwrap());
+                    dbg_println!("next weight {:?}", self.node_array[HIDDEN + 1][next_layer].link_weights[hidden]);
+                }
+                // This changes based on the activation function
+                let err_sig: f32 = self.reduction.sum(self.node_array[HIDDEN + 1].iter().map(|next| next.err_sig.unwrap() * next.link_weights[hidden]));
+                self.node_array[HIDDEN][hidden].err_sig = Some(err_sig);
+                let hidden_result = self.node_array[HIDDEN][hidden].cached_output.unwrap();
+                let multiplied_value = self.node_array[HIDDEN][hidden].err_sig.unwrap() * (hidden_result) * (1.0 - hidden_result);
+                // The answer layer's error signals fit in f16, so a hidden one that doesn't is dropped rather than skipping the update
+                #[cfg(feature = "mixed-precision")]
+                let multiplied_value: f32 = match &self.mixed_precision {
+                    Some(precision) => precision.reduce_grad(multiplied_value).unwrap_or(0.0),
+                    None => multiplied_value
+                };
+                dbg_println!("new hidden errsig multiply: {:?}", multiplied_value);
+                self.node_array[HIDDEN][hidden].err_sig = Some(multiplied_value);
+
+                dbg_println!("\nLayer: {:?}", HIDDEN);
+                dbg_println!("Node: {:?}", hidden);
+
+                self.node_array[HIDDEN][hidden].adjust_weights(learning_rate);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            self.stop_layer_clock(HIDDEN, start, true);
+        }
+    }
+
+    /// The network as a [Graphviz](https://graphviz.org) digraph, with answer nodes named after their categories.
+    /// Positive links are red and negative ones blue, drawn thicker and darker the larger they are,
+    /// and hovering over a link shows its weight. Render it with `dot -Tsvg network.dot -o network.svg`.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    ///
+    /// let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let dot: String = net.to_dot();
+    /// assert!(dot.starts_with("digraph"));
+    /// // Every link is an edge
+    /// assert_eq!(dot.matches("->").count(), 2 * 3 + 3 * 2);
+    /// ```
+    pub fn to_dot(&self) -> String {
+        visualize::dot(&self.node_array, self.schema.as_ref().map(|schema| schema.features()).unwrap_or_default(), &self.categories().unwrap_or_default().iter().map(|category| category.to_string()).collect::<Vec<String>>())
+    }
+
+    /// A compact text picture of the network, with a row for every node shading the size of each link into it and then its bias,
+    /// from blank for nothing to █ for the largest weight in the network
+    ///
+    /// ## Params
+    /// - Color: Whether to color positive weights red and negative ones blue with ANSI escape codes, for printing to a terminal
+    pub fn to_diagram(&self, color: bool) -> String {
+        visualize::diagram(&self.node_array, self.schema.as_ref().map(|schema| schema.features()).unwrap_or_default(), &self.categories().unwrap_or_default().iter().map(|category| category.to_string()).collect::<Vec<String>>(), &self.activation_function, color)
+    }
+
+    /// Serializes the model in the .darj format without writing it anywhere,
+    /// for saving it somewhere other than a file, like browser storage
+    /// 
+    /// ## Err
+    /// ### UnknownError: 
+    /// The model's metadata couldn't be serialized
+    pub fn to_darj_string(&self) -> Result<String, DarjeelingError> {
+        let mut serialized = "".to_string();
+        println!("write, length: {}", self.node_array.len());
+        for i in 0..self.node_array.len() {
+            if i != 0 {
+                let _ = serialized.push_str("lb\n");
+            }
+            for j in 0..self.node_array[i].len() {
+                for k in 0..self.node_array[i][j].link_weights.len() {
+                    print!("{}", self.node_array[i][j].link_weights[k]);
+                    if k == self.node_array[i][j].link_weights.len() - 1 {
+                        let _ = serialized.push_str(format!("{}", self.node_array[i][j].link_weights[k]).as_str());
+                    } else {
+                        let _ = serialized.push_str(format!("{},", self.node_array[i][j].link_weights[k]).as_str());
+                    }                        
+                }
+                let _ = serialized.push_str(format!(";{}", self.node_array[i][j].b_weight.unwrap().to_string()).as_str()); 
+                let _ = serialized.push_str("\n");
+            }
+        }
+        serialized.push_str("lb\n");                    
+        serialized.push_str(format!("{}", self.activation_function).as_str());
+        if let Some(scaler) = &self.scaler {
+            match serde_json::to_string(scaler) {
+                Ok(json) => serialized.push_str(format!("\nscaler {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        if !self.encoders.is_empty() {
+            match serde_json::to_string(&self.encoders) {
+                Ok(json) => serialized.push_str(format!("\nencoders {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        if let Some(categories) = self.categories() {
+            match serde_json::to_string(&categories) {
+                Ok(json) => serialized.push_str(format!("\ncategories {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        if let Some(calibrator) = &self.calibrator {
+            match serde_json::to_string(calibrator) {
+                Ok(json) => serialized.push_str(format!("\ncalibrator {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        if let Some(fingerprint) = &self.data_fingerprint {
+            match serde_json::to_string(fingerprint) {
+                Ok(json) => serialized.push_str(format!("\nfingerprint {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        if let Some(costs) = &self.cost_matrix {
+            match serde_json::to_string(costs) {
+                Ok(json) => serialized.push_str(format!("\ncosts {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        if let Some(threshold) = &self.threshold {
+            match serde_json::to_string(threshold) {
+                Ok(json) => serialized.push_str(format!("\nthreshold {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        if let Some(profile) = &self.data_profile {
+            match serde_json::to_string(profile) {
+                Ok(json) => serialized.push_str(format!("\nprofile {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        if let Some(schema) = &self.schema {
+            match serde_json::to_string(schema) {
+                Ok(json) => serialized.push_str(format!("\nschema {}", json).as_str()),
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+            }
+        }
+        Ok(serialized)
+    }
+
+    /// Reads a model serialized in the .darj format from memory, the counterpart to [`to_darj_string`](fn@to_darj_string).
+    /// This is how models are loaded where there's no filesystem, like in the browser.
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let serialized: String = net.to_darj_string().unwrap();
+    /// let read = CatNetwork::from_darj_str(&serialized).unwrap();
+    /// assert_eq!(read.to_darj_string().unwrap(), serialized);
+    /// ```
+    /// 
+    /// ## Err
+    /// - ### ModelParse
+    /// A line isn't a node, metadata or an activation function. The error has the line, column and text that couldn't be read.
+    /// - ### ActivationFunctionNotRead
+    /// There's no activation function line
+    /// - ### InvalidArchitecture
+    /// The layers don't fit together, like a node with more links than the layer before it has nodes
+    /// - ### InvalidMetadataRead
+    /// The number of categories doesn't match the number of answer nodes
+    pub fn from_darj_str(serialized: &str) -> Result<CatNetwork, DarjeelingError> {
+        let mut node_array: Vec<Vec<Node>> = vec![];
+        let mut layer: Vec<Node> = vec![];
+        let mut activation: Option<ActivationFunction> = None;
+        let mut scaler: Option<Scaler> = None;
+        let mut encoders: Vec<Option<Encoder>> = vec![];
+        let mut calibrator: Option<Calibrator> = None;
+        let mut categories: Option<Vec<Types>> = None;
+        let mut data_fingerprint: Option<DataFingerprint> = None;
+        let mut cost_matrix: Option<CostMatrix> = None;
+        let mut threshold: Option<DecisionThreshold> = None;
+        let mut data_profile: Option<DataProfile> = None;
+        let mut schema: Option<Schema> = None;
+        for (line_number, i) in serialized.lines().enumerate().map(|(index, line)| (index + 1, line)) {
+            match i {
+                _ if i.starts_with("scaler ") => scaler = Some(darj::parse_metadata(line_number, i, "scaler ", "a scaler")?),
+
+                _ if i.starts_with("encoders ") => encoders = darj::parse_metadata(line_number, i, "encoders ", "a list of encoders")?,
+
+                _ if i.starts_with("categories ") => categories = Some(darj::parse_metadata(line_number, i, "categories ", "a list of categories")?),
+
+                _ if i.starts_with("calibrator ") => calibrator = Some(darj::parse_metadata(line_number, i, "calibrator ", "a calibrator")?),
+
+                _ if i.starts_with("fingerprint ") => data_fingerprint = Some(darj::parse_metadata(line_number, i, "fingerprint ", "a data fingerprint")?),
+
+                _ if i.starts_with("costs ") => cost_matrix = Some(darj::parse_metadata(line_number, i, "costs ", "a cost matrix")?),
+
+                _ if i.starts_with("threshold ") => threshold = Some(darj::parse_metadata(line_number, i, "threshold ", "a decision threshold")?),
+
+                _ if i.starts_with("profile ") => data_profile = Some(darj::parse_metadata(line_number, i, "profile ", "a data profile")?),
+
+                _ if i.starts_with("schema ") => schema = Some(darj::parse_metadata(line_number, i, "schema ", "a schema")?),
+
+                // Signatures are checked before the model is parsed, if they're checked at all
+                _ if i.starts_with("signature ") => {},
+
+                _ if i.trim() == "lb" => {
+                    node_array.push(layer.clone());
+                    layer = vec![];
+                },
+
+                _ if darj::is_activation_line(i) => activation = Some(darj::parse_activation(line_number, i)?),
+
+                _ => layer.push(darj::parse_node(line_number, i, node_array.is_empty())?)
+            }
+        }
+        darj::validate_architecture(&node_array, &layer)?;
+        let answer: Option<usize> = Some(node_array.len() - 1);
+        
+        let mut net = CatNetwork {
+            node_array,
+            answer,
+            parameters: None,
+            activation_function: match activation 
+            {
+                Some(acti) => acti,
+                None => return Err(DarjeelingError::ActivationFunctionNotRead("No activation function line in the model".to_string())),
+            },
+            scaler,
+            encoders,
+            augmenter: None,
+            calibrator,
+            history: TrainingHistory::new(),
+            collect_layer_stats: false,
+            err_sig_stats: vec![],
+            grad_norms: RunningStats::default(),
+            epoch_loss: CompensatedSum::default(),
+            gradient_alarm: None,
+            metrics_sender: None,
+            event_writer: None,
+            prometheus: None,
+            output_dir: None,
+            output_weights: vec![],
+            regularization: Regularization::default(),
+            max_norm: None,
+            schedule: LearningRateSchedule::Constant,
+            reduction: Reduction::Fast,
+            data_fingerprint,
+            tie_break: TieBreak::First,
+            float_tolerance: FLOAT_EPSILON,
+            cost_matrix,
+            threshold,
+            data_profile,
+            schema,
+            audit_hook: None,
+            anchor: None,
+            dead_neuron_policy: None,
+            output_stats: vec![],
+            dead_epochs: vec![],
+            holdout: vec![],
+            record_order: false,
+            epoch_order: vec![],
+            profile_layers: false,
+            layer_timings: vec![],
+            #[cfg(feature = "mixed-precision")]
+            mixed_precision: None
+        };
+        // println!("node array {:?}", net.node_array);
+        if let Some(categories) = categories {
+            if categories.len() != net.node_array[net.answer.unwrap()].len() {
+                return Err(DarjeelingError::InvalidMetadataRead(format!("{} categories for {} answer nodes", categories.len(), net.node_array[net.answer.unwrap()].len())));
+            }
+            net.categorize(categories);
+        }
+        if let Some(costs) = &net.cost_matrix {
+            if costs.size() != net.node_array[net.answer.unwrap()].len() {
+                return Err(DarjeelingError::InvalidMetadataRead(format!("A cost matrix for {} answer nodes, but there are {}", costs.size(), net.node_array[net.answer.unwrap()].len())));
+            }
+        }
+        if let Some(threshold) = &net.threshold {
+            if net.threshold_node(&threshold.category).is_none() {
+                return Err(DarjeelingError::InvalidMetadataRead(format!("A decision threshold for {:?}, which isn't one of the categories", threshold.category)));
+            }
+        }
+        if let Some(profile) = &net.data_profile {
+            if profile.features.len() != net.node_array[0].len() {
+                return Err(DarjeelingError::InvalidMetadataRead(format!("A data profile of {} features for {} inputs", profile.features.len(), net.node_array[0].len())));
+            }
+        }
+        if let Some(schema) = &net.schema {
+            if schema.len() != net.node_array[0].len() {
+                return Err(DarjeelingError::InvalidMetadataRead(format!("A schema naming {} features for {} inputs", schema.len(), net.node_array[0].len())));
+            }
+        }
+
+        Ok(net)
+    }
+
+    /// Serializes a trained model so it can be used later.
+    /// It's saved in the [output directory](fn@set_output_dir) if there is one.
+    /// 
+    /// ## Returns
+    /// The name of the model, including the output directory
+    /// 
+    /// ## Err
+    /// ### WriteModelFailed: 
+    /// Wraps the models name, or the output directory if it couldn't be created
+    /// ### UnknownError: 
+    /// Wraps error
+    ///  
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write_model(&mut self, name: &str) -> Result<String, DarjeelingError> {
+        let serialized: String = self.to_darj_string()?;
+        self.write_darj(name, serialized)
+    }
+
+    /// Writes an already serialized model to a new file, named like [`write_model`](fn@write_model) names them
+    #[cfg(not(target_arch = "wasm32"))]
+    fn write_darj(&self, name: &str, serialized: String) -> Result<String, DarjeelingError> {
+        let mut rng = rand::thread_rng();
+        let file_num: u32 = rng.gen();
+        let model_name: String = config::file_path(config::resolve_dir(self.output_dir.as_deref(), config::OUTPUT_DIR_VAR), &format!("model_{}_{}.darj", name, file_num))?;
+
+        match Path::new(&model_name).try_exists() {
+            Ok(false) => {
+                let _file: fs::File = fs::File::create(&model_name).unwrap();
+                println!("Serialized: {:?}", serialized);
+                println!("{}", model_name);
+                match fs::write(&model_name, serialized) {
+                    Ok(()) => {
+                        println!("Model {:?} Saved", file_num);
+                        Ok(model_name)
+                    },
+                    Err(_error) => {
+                        Err(DarjeelingError::WriteModelFailed(model_name))
+                    }
+                }
+            },
+            Ok(true) => {
+                self.write_darj(name, serialized)
+            },
+            Err(error) => Err(DarjeelingError::UnknownError(error.to_string()))
+        }
+    }
+
+    /// Reads a serizalized Neural Network
+    /// 
+    /// ## Params
+    /// - Model Name: The name(or more helpfully the path) of the model to be read
+    /// 
+    /// ## Returns
+    /// A neural network read from a serialized .darj file
+    /// 
+    /// ## Err
+    /// If the file cannnot be read, or if the file does not contain a valid serialized Neural Network
+    /// - ### InvalidSignature
+    /// A [verifier is set](fn@crate::signing::set_verifier), and the model isn't signed by its signer or was changed after it was signed
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_model(model_name: String) -> Result<CatNetwork, DarjeelingError> {
+        println!("Loading model");
+        // Err if the file reading fails
+        let serialized_net: String = match fs::read_to_string(&model_name) {
+            
+            Ok(serizalized_net) => serizalized_net,
+            Err(error) => return Err(DarjeelingError::ReadModelFailed(model_name.clone() + ";" +  &error.to_string()))
+        };
+        #[cfg(feature = "signing")]
+        if let Some(verifier) = signing::verifier() {
+            return CatNetwork::read_verified(&model_name, &serialized_net, &verifier);
+        }
+        match CatNetwork::from_darj_str(&serialized_net) {
+            Err(DarjeelingError::ActivationFunctionNotRead(_)) => Err(DarjeelingError::ActivationFunctionNotRead(format!("While attempting to read file {}", model_name))),
+            read => read
+        }
+    }
+
+    /// [`write_model`](fn@write_model), with a signature on the last line that [`read_signed_model`](fn@read_signed_model) checks
+    /// 
+    /// ## Err
+    /// The same as [`write_model`](fn@write_model)
+    #[cfg(all(feature = "signing", not(target_arch = "wasm32")))]
+    pub fn write_signed_model(&mut self, name: &str, signer: &ModelSigner) -> Result<String, DarjeelingError> {
+        let serialized: String = signer.append_signature(&self.to_darj_string()?);
+        self.write_darj(name, serialized)
+    }
+
+    /// [`read_model`](fn@read_model), which only loads the model if it was signed by the verifier's signer and hasn't changed since.
+    /// Plain `read_model` ignores signatures unless a verifier is [set for every read](fn@crate::signing::set_verifier).
+    /// 
+    /// ## Err
+    /// - ### InvalidSignature
+    /// The model isn't signed, or wasn't signed by the verifier's signer, or was changed after it was signed
+    /// - The rest are the same as [`read_model`](fn@read_model)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// // In the training pipeline
+    /// let signer = ModelSigner::new(&secret_key);
+    /// let model_name: String = net.write_signed_model("iris", &signer)?;
+    /// // In the service, which only has the public key
+    /// let verifier = ModelVerifier::new(&public_key)?;
+    /// let net = CatNetwork::read_signed_model(model_name, &verifier)?;
+    /// ```
+    #[cfg(all(feature = "signing", not(target_arch = "wasm32")))]
+    pub fn read_signed_model(model_name: String, verifier: &ModelVerifier) -> Result<CatNetwork, DarjeelingError> {
+        let signed: String = match fs::read_to_string(&model_name) {
+            Ok(signed) => signed,
+            Err(error) => return Err(DarjeelingError::ReadModelFailed(model_name.clone() + ";" +  &error.to_string()))
+        };
+        CatNetwork::read_verified(&model_name, &signed, verifier)
+    }
+
+    #[cfg(all(feature = "signing", not(target_arch = "wasm32")))]
+    fn read_verified(model_name: &str, signed: &str, verifier: &ModelVerifier) -> Result<CatNetwork, DarjeelingError> {
+        match verifier.check(signed) {
+            Ok(serialized) => CatNetwork::from_darj_str(serialized),
+            Err(DarjeelingError::InvalidSignature(error)) => Err(DarjeelingError::InvalidSignature(format!("{}: {}", model_name, error))),
+            Err(error) => Err(error)
+        }
+    }
+
+    /// [`write_model`](fn@write_model), encrypted so the model can only be read with the cipher's key
+    /// 
+    /// ## Err
+    /// The same as [`write_model`](fn@write_model)
+    #[cfg(all(feature = "encryption", not(target_arch = "wasm32")))]
+    pub fn write_encrypted_model(&mut self, name: &str, cipher: &ModelCipher) -> Result<String, DarjeelingError> {
+        let serialized: String = cipher.encrypt(&self.to_darj_string()?);
+        self.write_darj(name, serialized)
+    }
+
+    /// Reads a model saved by [`write_encrypted_model`](fn@write_encrypted_model)
+    /// 
+    /// ## Err
+    /// - ### DecryptionFailed
+    /// The model isn't encrypted, was encrypted with another key, or was changed after it was encrypted
+    /// - The rest are the same as [`read_model`](fn@read_model)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// let cipher = ModelCipher::new(key);
+    /// let model_name: String = net.write_encrypted_model("iris", &cipher)?;
+    /// let net = CatNetwork::read_encrypted_model(model_name, &cipher)?;
+    /// ```
+    #[cfg(all(feature = "encryption", not(target_arch = "wasm32")))]
+    pub fn read_encrypted_model(model_name: String, cipher: &ModelCipher) -> Result<CatNetwork, DarjeelingError> {
+        let encrypted: String = match fs::read_to_string(&model_name) {
+            Ok(encrypted) => encrypted,
+            Err(error) => return Err(DarjeelingError::ReadModelFailed(model_name.clone() + ";" +  &error.to_string()))
+        };
+        match cipher.decrypt(&encrypted) {
+            Ok(serialized) => CatNetwork::from_darj_str(&serialized),
+            Err(DarjeelingError::DecryptionFailed(error)) => Err(DarjeelingError::DecryptionFailed(format!("{}: {}", model_name, error))),
+            Err(error) => Err(error)
+        }
+    }
+
+    /// The loss and accuracy of every epoch of the last training run.
+    /// Save it with [`to_csv`](fn@crate::history::TrainingHistory::to_csv) to plot the loss curve.
+    pub fn history(&self) -> &TrainingHistory {
+        &self.history
+    }
+
+    /// Turns on collecting the mean, standard deviation and largest weight and error signal of every layer each epoch.
+    /// They're stored in the [history](fn@history), and are the fastest way to spot vanishing or exploding gradients.
+    /// Collecting them slows training down a little, so it's off by default.
+    pub fn set_collect_layer_stats(&mut self, collect: bool) {
+        self.collect_layer_stats = collect;
+    }
+
+    /// Turns on recording the order every epoch's inputs were shuffled into, so the order a run trained in can be rebuilt when debugging it.
+    /// Each epoch's [order](EpochRecord::order) is stored in the [history](fn@history), and [`TrainingHistory::sample_order`] puts them together.
+    /// Recording it doesn't change the order, but it keeps an index for every input every epoch, so it's off by default.
+    pub fn set_record_order(&mut self, record: bool) {
+        self.record_order = record;
+    }
+
+    /// Turns on timing the forward and backward pass of every layer each epoch, to see which layers are worth making faster.
+    /// Each epoch's [timings](EpochRecord::timings) are stored in the [history](fn@history), and [`TrainingHistory::layer_timings`] adds them up over the run.
+    /// Reading the clock adds a little time to every layer, so it's off by default.
+    /// `wasm32-unknown-unknown` doesn't have a clock, so it isn't there.
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// net.set_profile_layers(true);
+    /// net.learn(&mut data, categories, 0.5, "profiled", 95.0, false).unwrap();
+    /// for timing in net.history().layer_timings() {
+    ///     println!("Layer {}: {:?} forward, {:?} backward", timing.layer, timing.forward, timing.backward);
+    /// }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_profile_layers(&mut self, profile: bool) {
+        self.profile_layers = profile;
+        self.layer_timings = vec![];
+    }
+
+    /// Sends every epoch's metrics down a channel as soon as the epoch finishes,
+    /// so a dashboard on another thread can chart training while it runs.
+    /// Training carries on if the receiver is dropped.
+    /// 
+    /// ## Examples
+    /// ```
+    /// use std::sync::mpsc;
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    /// 
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// let (sender, receiver) = mpsc::channel();
+    /// net.set_metrics_sender(Some(sender));
+    /// // Train on another thread, then
+    /// // for record in receiver { println!("{} {}", record.epoch, record.loss) }
+    /// ```
+    pub fn set_metrics_sender(&mut self, sender: Option<Sender<EpochRecord>>) {
+        self.metrics_sender = sender;
+    }
 
-                    dbg_println!("next err sig {:?}", self.node_array[HIDDEN + 1][next_layer].err_sig.unwrap());
-                    dbg_println!("next weight {:?}", next_weight);
-                }
-                let hidden_result = self.node_array[HIDDEN][hidden].cached_output.unwrap();
-                let multiplied_value = self.node_array[HIDDEN][hidden].err_sig.unwrap() * (hidden_result) * (1.0 - hidden_result);
-                dbg_println!("new hidden errsig multiply: {:?}", multiplied_value);
-                self.node_array[HIDDEN][hidden].err_sig = Some(multiplied_value);
+    /// Writes every epoch's loss, accuracy and learning rate to a TensorBoard event file as training runs.
+    /// Training fails with WriteDataFailed if the file can't be written.
+    pub fn set_event_writer(&mut self, writer: Option<EventWriter>) {
+        self.event_writer = writer;
+    }
 
-                dbg_println!("\nLayer: {:?}", HIDDEN);
-                dbg_println!("Node: {:?}", hidden);
+    /// Counts epochs and keeps the latest loss and accuracy in Prometheus metrics as training runs.
+    /// Keep a clone of the Arc to [render](fn@crate::prometheus::Metrics::render) them from a scrape endpoint.
+    pub fn set_prometheus_metrics(&mut self, metrics: Option<Arc<Metrics>>) {
+        self.prometheus = metrics;
+    }
 
-                self.node_array[HIDDEN][hidden].adjust_weights(learning_rate);
-            }
+    /// Saves the model to a directory instead of the working directory, creating it if it has to.
+    /// None goes back to using `DARJEELING_OUTPUT_DIR`, or the working directory if that isn't set.
+    pub fn set_output_dir(&mut self, dir: Option<PathBuf>) {
+        self.output_dir = dir;
+    }
+
+    /// Watches the gradient norm of every input while training, warning or stopping when it grows past a bound.
+    /// Every epoch's gradient norms are recorded in the [history](fn@history) either way.
+    pub fn set_gradient_alarm(&mut self, alarm: Option<GradientAlarm>) {
+        self.gradient_alarm = alarm;
+    }
+
+    /// Watches hidden nodes while training, and re-randomizes or removes the ones whose outputs stay the same for too many epochs.
+    /// Every node acted on is recorded in the [history](fn@TrainingHistory::events).
+    pub fn set_dead_neuron_policy(&mut self, policy: Option<DeadNeuronPolicy>) {
+        self.dead_neuron_policy = policy;
+        self.output_stats = vec![];
+        self.dead_epochs = vec![];
+    }
+
+    pub fn dead_neuron_policy(&self) -> Option<&DeadNeuronPolicy> {
+        self.dead_neuron_policy.as_ref()
+    }
+
+    /// Sets data to [evaluate](fn@evaluate) at the end of every epoch, whose loss and accuracy are recorded in the [history](fn@history).
+    /// Training metrics come from the shuffled data being fit, while the holdout is never trained on or shuffled,
+    /// so its metrics are the same from run to run for the same weights. Empty turns it off.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input, types::Types};
+    ///
+    /// let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// let mut data = vec![Input::new(vec![0.0, 1.0], Some(Types::Boolean(true))), Input::new(vec![1.0, 0.0], Some(Types::Boolean(false)))];
+    /// net.set_holdout(vec![Input::new(vec![0.1, 0.9], Some(Types::Boolean(true)))]);
+    /// net.start_training(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// net.step_epoch(&mut data, 0.5).unwrap();
+    /// assert!(net.history().last().unwrap().holdout_accuracy.is_some());
+    /// ```
+    pub fn set_holdout(&mut self, holdout: Vec<Input>) {
+        self.holdout = holdout;
+    }
+
+    pub fn holdout(&self) -> &[Input] {
+        &self.holdout
+    }
+
+    /// Makes some answer nodes count for more than others while training, so important outputs dominate.
+    /// Each node's error signal is multiplied by its weight, and [evaluation](fn@evaluate) losses are weighted the same way.
+    /// 
+    /// ## Params
+    /// - Weights: One weight for every answer node, in order. Empty counts every node the same.
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// There isn't one weight for every answer node, or a weight is negative
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
+    /// 
+    /// let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// // Mistakes on the second output cost three times as much
+    /// net.set_output_weights(vec![1.0, 3.0]).unwrap();
+    /// assert!(net.set_output_weights(vec![1.0]).is_err());
+    /// ```
+    pub fn set_output_weights(&mut self, weights: Vec<f32>) -> Result<(), DarjeelingError> {
+        if !weights.is_empty() && weights.len() != self.output_size() {
+            return Err(DarjeelingError::InvalidInput(format!("There are {} output weights for {} answer nodes", weights.len(), self.output_size())));
         }
+        if weights.iter().any(|weight| *weight < 0.0) {
+            return Err(DarjeelingError::InvalidInput("Output weights can't be negative".to_string()));
+        }
+        self.output_weights = weights;
+        Ok(())
+    }
+
+    pub fn output_weights(&self) -> &[f32] {
+        &self.output_weights
+    }
+
+    /// Penalizes large link weights while training, with L1, L2, or both as an elastic net.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, config::Regularization};
+    ///
+    /// let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_regularization(Regularization::elastic_net(0.001, 0.01));
+    /// assert_eq!(net.regularization().l2, 0.01);
+    /// ```
+    pub fn set_regularization(&mut self, regularization: Regularization) {
+        self.regularization = regularization;
+    }
+
+    pub fn regularization(&self) -> Regularization {
+        self.regularization
+    }
+
+    /// Changes how weighted inputs and error signals are added up, and how epochs are shuffled.
+    /// A [deterministic reduction](Reduction::Deterministic) makes training runs that start from the same weights repeat bit for bit,
+    /// for when a model has to be rebuilt exactly, at some cost in speed. Predictions use it too, so they match what training saw.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, config::Reduction, input::Input, types::Types};
+    ///
+    /// let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_reduction(Reduction::Deterministic { seed: 42 });
+    /// let mut copy = net.clone();
+    /// let mut data = vec![Input::new(vec![0.0, 1.0], Some(Types::Integer(1))), Input::new(vec![1.0, 1.0], Some(Types::Integer(0)))];
+    /// let mut same_data = data.clone();
+    /// net.start_training(vec![Types::Integer(0), Types::Integer(1)]);
+    /// copy.start_training(vec![Types::Integer(0), Types::Integer(1)]);
+    /// for _ in 0..5 {
+    ///     net.step_epoch(&mut data, 0.5).unwrap();
+    ///     copy.step_epoch(&mut same_data, 0.5).unwrap();
+    /// }
+    /// assert_eq!(net.raw_outputs(&[0.5, 0.5]), copy.raw_outputs(&[0.5, 0.5]));
+    /// ```
+    pub fn set_reduction(&mut self, reduction: Reduction) {
+        self.reduction = reduction;
+    }
+
+    pub fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    /// Changes the learning rate given to training from epoch to epoch, like [warm restarts](LearningRateSchedule::WarmRestarts).
+    /// The epochs are counted from the start of training, and each one's learning rate is recorded in the [history](fn@history).
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, config::LearningRateSchedule};
+    ///
+    /// let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_schedule(LearningRateSchedule::warm_restarts(10, 2));
+    /// ```
+    pub fn set_schedule(&mut self, schedule: LearningRateSchedule) {
+        self.schedule = schedule;
+    }
+
+    pub fn schedule(&self) -> LearningRateSchedule {
+        self.schedule
     }
 
-    /// Serializes a trained model so it can be used later
+    /// How a category is picked when more than one answer node has exactly the largest output.
+    /// The first tied node is picked by default.
     /// 
-    /// ## Returns
-    /// The name of the model
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, prediction::TieBreak};
+    /// 
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_tie_break(TieBreak::Random { seed: 42 });
+    /// assert_eq!(net.tie_break(), TieBreak::Random { seed: 42 });
+    /// ```
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.tie_break = tie_break;
+    }
+
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    /// How far apart a float answer can be from a float category and still belong to it, while training and evaluating.
+    /// Answers within the tolerance of each other are also the same category when the categories are found from the data.
+    /// [`FLOAT_EPSILON`] by default, and it isn't saved with the model.
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction};
     /// 
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_float_tolerance(0.01);
+    /// assert_eq!(net.float_tolerance(), 0.01);
+    /// ```
+    pub fn set_float_tolerance(&mut self, tolerance: f32) {
+        self.float_tolerance = tolerance;
+    }
+
+    pub fn float_tolerance(&self) -> f32 {
+        self.float_tolerance
+    }
+
+    /// Makes predictions pick the answer node that's expected to cost the least, instead of the brightest one.
+    /// The expected costs use the [calibrator's](fn@set_calibrator) probabilities when it has them,
+    /// so fitting a temperature calibrator first makes for better decisions. Training isn't changed.
+    /// The cost matrix is saved with the model, and None goes back to picking the brightest node.
+    ///
     /// ## Err
-    /// ### WriteModelFailed: 
-    /// Wraps the models name
-    /// ### UnknownError: 
-    /// Wraps error
-    ///  
-    pub fn write_model(&mut self, name: &str) -> Result<String, DarjeelingError> {
-        
-        let mut rng = rand::thread_rng();
-        let file_num: u32 = rng.gen();
-        let model_name: String = format!("model_{}_{}.darj", name, file_num);
+    /// - ### InvalidInput
+    /// The matrix isn't the size of the answer layer
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, cost::CostMatrix};
+    ///
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// // Missing the second category costs 20 times as much as wrongly answering with it
+    /// net.set_cost_matrix(Some(CostMatrix::new(vec![vec![0.0, 1.0], vec![20.0, 0.0]]).unwrap())).unwrap();
+    /// assert!(net.set_cost_matrix(Some(CostMatrix::new(vec![vec![0.0]]).unwrap())).is_err());
+    /// ```
+    pub fn set_cost_matrix(&mut self, costs: Option<CostMatrix>) -> Result<(), DarjeelingError> {
+        if let Some(costs) = &costs {
+            if costs.size() != self.output_size() {
+                return Err(DarjeelingError::InvalidInput(format!("The cost matrix is for {} answer nodes, but there are {}", costs.size(), self.output_size())));
+            }
+        }
+        self.cost_matrix = costs;
+        Ok(())
+    }
 
-        match Path::new(&model_name).try_exists() {
-            Ok(false) => {
-                let _file: fs::File = fs::File::create(&model_name).unwrap();
-                let mut serialized = "".to_string();
-                println!("write, length: {}", self.node_array.len());
-                for i in 0..self.node_array.len() {
-                    if i != 0 {
-                        let _ = serialized.push_str("lb\n");
-                    }
-                    for j in 0..self.node_array[i].len() {
-                        for k in 0..self.node_array[i][j].link_weights.len() {
-                            print!("{}", self.node_array[i][j].link_weights[k]);
-                            if k == self.node_array[i][j].link_weights.len() - 1 {
-                                let _ = serialized.push_str(format!("{}", self.node_array[i][j].link_weights[k]).as_str());
-                            } else {
-                                let _ = serialized.push_str(format!("{},", self.node_array[i][j].link_weights[k]).as_str());
-                            }                        
-                        }
-                        let _ = serialized.push_str(format!(";{}", self.node_array[i][j].b_weight.unwrap().to_string()).as_str()); 
-                        let _ = serialized.push_str("\n");
-                    }
-                }
-                serialized.push_str("lb\n");                    
-                serialized.push_str(format!("{}", self.activation_function).as_str());
-                println!("Serialized: {:?}", serialized);
-                println!("{}", model_name);
-                match fs::write(&model_name, serialized) {
-                    Ok(()) => {
-                        println!("Model {:?} Saved", file_num);
-                        Ok(model_name)
-                    },
-                    Err(_error) => {
-                        Err(DarjeelingError::WriteModelFailed(model_name))
-                    }
-                }
-            },
-            Ok(true) => {
-                self.write_model(name)
-            },
-            Err(error) => Err(DarjeelingError::UnknownError(error.to_string()))
+    pub fn cost_matrix(&self) -> Option<&CostMatrix> {
+        self.cost_matrix.as_ref()
+    }
+
+    /// Makes predictions answer with a category whenever its probability reaches the threshold, and with the best of the others when it doesn't.
+    /// The probability comes from the [calibrator](fn@set_calibrator) when it has one, so it's best set after calibrating.
+    /// A threshold is picked ahead of the [cost matrix](fn@set_cost_matrix), which still decides between the other categories.
+    /// It's saved with the model, and None goes back to picking the brightest node.
+    /// [`optimize_threshold`](fn@optimize_threshold) picks one from validation data.
+    ///
+    /// ## Err
+    /// - ### UnknownCategory
+    /// The category isn't one of the network's
+    pub fn set_threshold(&mut self, threshold: Option<DecisionThreshold>) -> Result<(), DarjeelingError> {
+        if let Some(threshold) = &threshold {
+            if self.threshold_node(&threshold.category).is_none() {
+                return Err(DarjeelingError::UnknownCategory(format!("{:?} isn't one of the network's categories", threshold.category)));
+            }
         }
+        self.threshold = threshold;
+        Ok(())
     }
 
-    /// Reads a serizalized Neural Network
-    /// 
+    pub fn threshold(&self) -> Option<&DecisionThreshold> {
+        self.threshold.as_ref()
+    }
+
+    /// Picks the decision threshold for a category that does best on validation data, and [sets](fn@set_threshold) it.
+    /// Every threshold that changes a decision is tried. Calibrate the network first for the probabilities the threshold is on to mean something.
+    ///
     /// ## Params
-    /// - Model Name: The name(or more helpfully the path) of the model to be read
-    /// 
+    /// - Validation: Inputs the network wasn't trained on. Inputs without an answer, or with one that isn't a category, are skipped.
+    /// - Category: The category the threshold is for, usually the rare one
+    /// - Objective: What the threshold is picked for
+    ///
     /// ## Returns
-    /// A neural network read from a serialized .darj file
-    /// 
+    /// How the network does on the validation data with the threshold
+    ///
     /// ## Err
-    /// If the file cannnot be read, or if the file does not contain a valid serialized Neural Network
-    pub fn read_model(model_name: String) -> Result<CatNetwork, DarjeelingError> {
-        println!("Loading model");
-        // Err if the file reading fails
-        let serialized_net: String = match fs::read_to_string(&model_name) {
-            
-            Ok(serizalized_net) => serizalized_net,
-            Err(error) => return Err(DarjeelingError::ReadModelFailed(model_name.clone() + ";" +  &error.to_string()))
+    /// - ### UnknownCategory
+    /// The category isn't one of the network's
+    /// - ### InvalidTrainingData
+    /// None of the validation inputs have an answer that's a category
+    /// - ### InvalidInput
+    /// No threshold reaches the precision, the precision isn't above 0 and at most 1, or the cost matrix isn't the size of the answer layer
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// use darjeeling::threshold::ThresholdObjective;
+    ///
+    /// // Catch as much fraud as possible while at least 90% of the alarms are real
+    /// let report = net.optimize_threshold(&validation, &Types::String("fraud".to_string()), &ThresholdObjective::Precision(0.9)).unwrap();
+    /// println!("{}", report);
+    /// ```
+    pub fn optimize_threshold(&mut self, validation: &[Input], category: &Types, objective: &ThresholdObjective) -> Result<ThresholdReport, DarjeelingError> {
+        let node: usize = self.threshold_node(category).ok_or_else(|| DarjeelingError::UnknownCategory(format!("{:?} isn't one of the network's categories", category)))?;
+        if let ThresholdObjective::Cost(costs) = objective {
+            if costs.size() != self.output_size() {
+                return Err(DarjeelingError::InvalidInput(format!("The cost matrix is for {} answer nodes, but there are {}", costs.size(), self.output_size())));
+            }
+        }
+        let answer_layer: &[Node] = &self.node_array[self.answer.unwrap()];
+        let tie_break: TieBreak = self.lenient_tie_break();
+        let mut probabilities: Vec<f32> = vec![];
+        let mut others: Vec<usize> = vec![];
+        let mut truth: Vec<usize> = vec![];
+        for input in validation {
+            let Some(answer) = answer_layer.iter().position(|node| matches!((&node.category, &input.answer), (Some(category), Some(answer)) if category.approx_eq(answer, self.float_tolerance))) else {
+                continue;
+            };
+            let outputs: Vec<f32> = self.forward(&input.inputs);
+            probabilities.push(self.output_probabilities(&outputs)[node]);
+            others.push(self.decide_without(&outputs, Some(node), tie_break)?.0);
+            truth.push(answer);
+        }
+        if truth.is_empty() {
+            return Err(DarjeelingError::InvalidTrainingData("None of the validation inputs have an answer that's one of the network's categories".to_string()));
+        }
+        let report: ThresholdReport = threshold::sweep(&probabilities, &others, &truth, node, objective)?;
+        self.threshold = Some(DecisionThreshold::new(category.clone(), report.threshold));
+        Ok(report)
+    }
+
+    /// The average cost of the network's predictions under its [cost matrix](fn@set_cost_matrix).
+    /// Inputs without an answer, or with one that isn't a category, are skipped.
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The network doesn't have a cost matrix or categories
+    pub fn average_cost(&self, data: &[Input]) -> Result<f32, DarjeelingError> {
+        let (costs, categories) = match (&self.cost_matrix, self.categories()) {
+            (Some(costs), Some(categories)) => (costs, categories),
+            _ => return Err(DarjeelingError::InvalidInput("The network needs a cost matrix and categories to work out costs".to_string()))
         };
-        let mut node_array: Vec<Vec<Node>> = vec![];
-        let mut layer: Vec<Node> = vec![];
-        let mut activation: Option<ActivationFunction> = None;
-        for i in serialized_net.lines() {
-            match i {
-                "sigmoid" => activation = Some(ActivationFunction::Sigmoid),
+        let (predictions, truth): (Vec<usize>, Vec<usize>) = data.iter().filter_map(|input| {
+            let answer: usize = categories.iter().position(|category| input.answer.as_ref().map(|answer| category.approx_eq(answer, self.float_tolerance)).unwrap_or(false))?;
+            let predicted: Types = self.predict_category(&input.inputs);
+            Some((categories.iter().position(|category| *category == predicted)?, answer))
+        }).unzip();
+        Ok(costs.average_cost(&predictions, &truth))
+    }
 
-                "linear" => activation = Some(ActivationFunction::Linear),
+    /// What the network's inputs and answers look like for some data, to save as the [profile](fn@set_data_profile) of the data it was trained on
+    pub fn profile(&self, data: &[Input]) -> DataProfile {
+        let predictions: Vec<Types> = data.iter().map(|input| self.predict_category(&input.inputs)).collect();
+        DataProfile::of(data, &predictions)
+    }
 
-                // "tanh" => activation = Some(ActivationFunction::Tanh),
+    /// Records what the training data looked like, which is saved with the model and checked against by [`detect_drift`](fn@detect_drift).
+    /// [`learn`](fn@learn) does this itself, so this is for networks trained any other way.
+    pub fn set_data_profile(&mut self, profile: Option<DataProfile>) {
+        self.data_profile = profile;
+    }
 
-                // "step" => activation = Some(ActivationFunction::Step),
+    pub fn data_profile(&self) -> Option<&DataProfile> {
+        self.data_profile.as_ref()
+    }
 
-                _ => {
-                
-                    if i.trim() == "lb" {
-                        node_array.push(layer.clone());
-                        // println!("pushed layer {:?}", layer.clone());
-                        layer = vec![];
-                        continue;
-                    }
-                    #[allow(unused_mut)]
-                    let mut node: Option<Node>;
-                    if node_array.len() == 0 {
-                        let b_weight: Vec<&str> = i.split(";").collect();
-                        // println!("b_weight: {:?}", b_weight);
-                        node = Some(Node::new(&vec![], match b_weight[1].parse()
-                        {
-                            Ok(weight) => Some(weight),
-                            Err(err) => return Err(DarjeelingError::InvalidNodeValueRead(err.to_string() + "; Bias: " + b_weight[1])),
-                        }));
-                    } else {
-                        let node_data: Vec<&str> = i.trim().split(";").collect();
-                        let str_weight_array: Vec<&str> = node_data[0].split(",").collect();
-                        let mut weight_array: Vec<f32> = vec![];
-                        let b_weight: &str = node_data[1];
-                        // println!("node_data: {:?}", node_data);
-                        // println!("array {:?}", str_weight_array);
-                        for weight in 0..str_weight_array.len() {
-                            // println!("testing here {:?}", str_weight_array[weight]);
-                            let val: f32 = match str_weight_array[weight].parse() 
-                            {
-                                Ok(v) => v,
-                                Err(err) => return Err(DarjeelingError::InvalidNodeValueRead(err.to_string() + "; Weight: " + str_weight_array[weight])),
-                            };
-                            weight_array.push(val);
-                        }
-                        // print!("{}", b_weight);
-                        node = Some(Node::new(&weight_array, match b_weight.parse()
-                        {
-                            Ok(weight) => Some(weight),
-                            Err(err) => return Err(DarjeelingError::InvalidNodeValueRead(err.to_string() + " ;" + b_weight)),
-                        }));
-                    }
-                    
-                    layer.push(node.expect("Both cases provide a Some value for node"));
-                    // println!("layer: {:?}", layer.clone())
-                }
-            }  
+    /// Compares a batch of inputs seen in production with the data the network was trained on,
+    /// feature by feature and in how often the network answers with each category, for noticing when the model needs retraining
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// The network doesn't have a [profile](fn@set_data_profile) of its training data, the batch is empty,
+    /// or an input doesn't have a value for every feature in the profile
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// let report = net.detect_drift(&todays_requests)?;
+    /// for feature in report.drifted(0.25) {
+    ///     println!("Feature {} drifted with a PSI of {}", feature.feature, feature.psi);
+    /// }
+    /// ```
+    pub fn detect_drift(&self, batch: &[Input]) -> Result<DriftReport, DarjeelingError> {
+        let profile: &DataProfile = match &self.data_profile {
+            Some(profile) => profile,
+            None => return Err(DarjeelingError::InvalidInput("The network doesn't have a profile of its training data to compare with".to_string()))
+        };
+        if batch.is_empty() {
+            return Err(DarjeelingError::InvalidInput("There's no data in the batch to check for drift".to_string()));
         }
-        //println!("node array size {}", node_array.len());
-        let answer: Option<usize> = Some(node_array.len() - 1);
-        
-        let net = CatNetwork {
-            node_array,
-            answer,
-            parameters: None,
-            activation_function: match activation 
-            {
-                Some(acti) => acti,
-                None => return Err(DarjeelingError::ActivationFunctionNotRead(format!("While attempting to read file {}", model_name))),
+        if let Some(input) = batch.iter().find(|input| input.inputs.len() != profile.features.len()) {
+            return Err(DarjeelingError::InvalidInput(format!("An input has {} values, but the training data had {} features", input.inputs.len(), profile.features.len())));
+        }
+        let predictions: Vec<Types> = batch.iter().map(|input| self.predict_category(&input.inputs)).collect();
+        Ok(DriftReport::compare(profile, batch, &predictions, self.schema.as_ref()))
+    }
+
+    /// Records the data the network was trained on, which is saved with the model.
+    /// [`learn`](fn@learn) does this itself, so this is for networks trained with [`step_epoch`](fn@step_epoch) or from a data source.
+    pub fn set_data_fingerprint(&mut self, fingerprint: Option<DataFingerprint>) {
+        self.data_fingerprint = fingerprint;
+    }
+
+    /// What the network was trained on, if it was recorded
+    pub fn data_fingerprint(&self) -> Option<&DataFingerprint> {
+        self.data_fingerprint.as_ref()
+    }
+
+    /// Rescales each node's incoming link weights after every update, so their L2 norm never goes past a bound.
+    /// This is often used alongside dropout, with a bound of 3 or 4. None removes the constraint.
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The bound isn't positive
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{activation::ActivationFunction, categorize::CatNetwork};
+    ///
+    /// let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_max_norm(Some(3.0)).unwrap();
+    /// assert!(net.set_max_norm(Some(0.0)).is_err());
+    /// ```
+    pub fn set_max_norm(&mut self, max_norm: Option<f32>) -> Result<(), DarjeelingError> {
+        if let Some(bound) = max_norm {
+            if bound <= 0.0 || bound.is_nan() {
+                return Err(DarjeelingError::InvalidInput(format!("The max norm has to be positive, not {}", bound)));
             }
-        };
-        // println!("node array {:?}", net.node_array);
+        }
+        self.max_norm = max_norm;
+        Ok(())
+    }
 
-        Ok(net)
+    pub fn max_norm(&self) -> Option<f32> {
+        self.max_norm
+    }
+
+    /// Trains with activations and error signals rounded to f16, keeping the weights in f32.
+    /// None trains in full precision again.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, precision::MixedPrecision};
+    ///
+    /// let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_mixed_precision(Some(MixedPrecision::new(1024.0)));
+    /// assert_eq!(net.mixed_precision().unwrap().loss_scale, 1024.0);
+    /// ```
+    #[cfg(feature = "mixed-precision")]
+    pub fn set_mixed_precision(&mut self, precision: Option<MixedPrecision>) {
+        self.mixed_precision = precision;
+    }
+
+    /// The mixed precision settings, with the loss scale training has reached
+    #[cfg(feature = "mixed-precision")]
+    pub fn mixed_precision(&self) -> Option<&MixedPrecision> {
+        self.mixed_precision.as_ref()
+    }
+
+    /// Uses the output directory of a config
+    pub fn set_dirs(&mut self, config: &TrainingConfig) {
+        self.output_dir = config.output_dir.clone();
     }
 
     pub fn set_activation_func(&mut self, new_activation_function: ActivationFunction) {
         self.activation_function = new_activation_function;
     }
 
+    pub fn activation_func(&self) -> ActivationFunction {
+        self.activation_function
+    }
+
+    /// Every layer of nodes, from the sensors to the answer layer
+    pub(crate) fn layers(&self) -> &[Vec<Node>] {
+        &self.node_array
+    }
+
+    /// Sets the scaler applied to every input before it reaches the sensors.
+    /// The scaler is saved with the model, so testing a read model scales inputs the same way training did.
+    /// Don't also transform the data yourself, or it'll be scaled twice.
+    pub fn set_scaler(&mut self, scaler: Option<Scaler>) {
+        self.scaler = scaler;
+    }
+
+    pub fn scaler(&self) -> Option<&Scaler> {
+        self.scaler.as_ref()
+    }
+
+    /// Sets the augmentations applied to every input as it's trained on.
+    /// Testing always uses the original inputs, and the augmenter isn't saved with the model.
+    pub fn set_augmenter(&mut self, augmenter: Option<Augmenter>) {
+        self.augmenter = augmenter;
+    }
+
+    /// Sets the encoders used by [`encode_row`](fn@encode_row), one per raw column.
+    /// None means the column is already numeric. The encoders are saved with the model.
+    pub fn set_encoders(&mut self, encoders: Vec<Option<Encoder>>) {
+        self.encoders = encoders;
+    }
+
+    pub fn encoders(&self) -> &[Option<Encoder>] {
+        &self.encoders
+    }
+
+    /// Fits an encoder for every column of typed rows that holds strings or categories, and sets them as this model's encoders.
+    /// Integer, float and boolean columns are passed through as numbers.
+    /// Rows encoded with [`encode_rows`](fn@encode_rows) afterwards are as wide as the sensors need to be.
+    pub fn fit_encoders(&mut self, rows: &[Vec<Types>], encoding: Encoding) {
+        self.encoders = encoder::fit_columns(rows, encoding);
+    }
+
+    /// Encodes raw rows of typed values into inputs using this model's encoders
+    ///
+    /// ## Params
+    /// - Answers: The answer of every row, in the same order, or None if the rows don't have them
+    ///
+    /// ## Err
+    /// The same as [`encode_row`](fn@encode_row), along with
+    /// - ### InvalidInput
+    /// There isn't one answer for every row
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, encoder::Encoding, types::Types};
+    ///
+    /// let rows = vec![
+    ///     vec![Types::Integer(2), Types::String("cash".to_string())],
+    ///     vec![Types::Integer(5), Types::String("card".to_string())]
+    /// ];
+    /// let mut net = CatNetwork::new(3, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// net.fit_encoders(&rows, Encoding::OneHot);
+    /// let data = net.encode_rows(&rows, Some(&[Types::Boolean(false), Types::Boolean(true)])).unwrap();
+    /// assert_eq!(data[0].inputs, vec![2.0, 1.0, 0.0]);
+    /// ```
+    pub fn encode_rows(&self, rows: &[Vec<Types>], answers: Option<&[Types]>) -> Result<Vec<Input>, DarjeelingError> {
+        encoder::encode_rows(&self.encoders, rows, answers)
+    }
+
+    /// Encodes a raw row of typed values into an Input using this model's encoders
+    ///
+    /// ## Err
+    /// - ### UnknownCategory
+    /// A category wasn't seen when the encoder was fit
+    /// - ### UnencodedColumn
+    /// A column holding a string doesn't have an encoder
+    pub fn encode_row(&self, row: &[Types], answer: Option<Types>) -> Result<Input, DarjeelingError> {
+        encoder::encode_row(&self.encoders, row, answer)
+    }
+
     pub fn add_hidden_layer_with_size(&mut self, size: usize) {
         let mut rng = rand::thread_rng();
         let a = self.answer.expect("initialized network");
@@ -594,6 +3907,134 @@ impl CatNetwork {
         });
         self.answer = Some(a + 1);
     }
+
+    /// Adds nodes to a hidden layer of a trained network without changing what it answers, to give it more capacity without starting over.
+    /// The new nodes get small random incoming weights and links of 0 to the next layer,
+    /// so they only start to count as training moves those links.
+    ///
+    /// ## Params
+    /// - Layer: The hidden layer to grow, from 1 for the first
+    /// - Extra Nodes: How many nodes to add to it
+    ///
+    /// ## Err
+    /// - ### InvalidArchitecture
+    /// There's no such hidden layer
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, types::Types};
+    ///
+    /// let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// let before = net.predict(&[0.5, 0.25]).scores;
+    /// net.grow_hidden(1, 5).unwrap();
+    /// assert_eq!(net.predict(&[0.5, 0.25]).scores, before);
+    /// ```
+    pub fn grow_hidden(&mut self, layer: usize, extra_nodes: usize) -> Result<(), DarjeelingError> {
+        let answer: usize = self.answer.unwrap();
+        if layer == 0 || layer >= answer {
+            return Err(DarjeelingError::InvalidArchitecture(format!("The network has {} hidden layers, so there's no hidden layer {}", answer - 1, layer)));
+        }
+        let mut rng = thread_rng();
+        let links: usize = self.node_array[layer - 1].len();
+        (0..extra_nodes).for_each(|_| {
+            let weights: Vec<f32> = (0..links).map(|_| rng.gen_range(-GROWN_WEIGHT..GROWN_WEIGHT)).collect();
+            self.node_array[layer].push(Node::new(&weights, Some(0.0)));
+        });
+        self.node_array[layer + 1].iter_mut().for_each(|node| {
+            node.link_weights.extend(std::iter::repeat(0.0).take(extra_nodes));
+            node.link_vals.extend(std::iter::repeat(None).take(extra_nodes));
+            node.links += extra_nodes;
+        });
+        Ok(())
+    }
+
+    /// Adds a hidden layer to a trained network that passes the layer before it through, so the network answers about the same as before,
+    /// to give it more depth without starting over. The layer uses the network's activation function, which every layer shares;
+    /// a layer can't have an activation function of its own.
+    ///
+    /// Each of the previous layer's outputs goes through one of the new nodes with a small weight, where the activation function is nearly a straight line,
+    /// and the next layer's weights are scaled up to undo it, by 400 times for sigmoid and 100 times for tanh.
+    /// Those weights are then much larger than the rest of the network's, so the same learning rate moves the next layer's answers much further;
+    /// train the grown network with a smaller learning rate than before. Linear networks aren't scaled and answer exactly the same.
+    /// Sigmoid and tanh networks answer very nearly the same as long as the previous layer's outputs are small,
+    /// which hidden layers' always are, but unscaled inputs might not be, so put a [scaler](fn@set_scaler) in front of them before inserting a first layer.
+    /// Nodes past the previous layer's width start like the ones [`grow_hidden`](fn@grow_hidden) adds.
+    ///
+    /// ## Params
+    /// - Index: Where the layer goes, from 1 for right after the inputs up to the answer layer's index for right before the answers
+    /// - Width: How many nodes the layer has, at least as many as the layer before it
+    ///
+    /// ## Err
+    /// - ### InvalidArchitecture
+    /// The index is the inputs or past the answers, the layer is narrower than the one before it,
+    /// or scaling up the next layer's weights would overflow them. The network is left as it was.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, types::Types};
+    ///
+    /// let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// let before = net.predict(&[0.5, 0.25]).scores;
+    /// // A second hidden layer of 4, between the first and the answers
+    /// net.insert_layer(2, 4).unwrap();
+    /// let after = net.predict(&[0.5, 0.25]).scores;
+    /// assert!(before.iter().zip(&after).all(|((_, before), (_, after))| (before - after).abs() < 1e-3));
+    /// ```
+    pub fn insert_layer(&mut self, index: usize, width: usize) -> Result<(), DarjeelingError> {
+        let answer: usize = self.answer.unwrap();
+        if index == 0 || index > answer {
+            return Err(DarjeelingError::InvalidArchitecture(format!("A layer can go from 1, right after the inputs, up to {}, right before the answers, not {}", answer, index)));
+        }
+        let previous: usize = self.node_array[index - 1].len();
+        if width < previous {
+            return Err(DarjeelingError::InvalidArchitecture(format!("The layer needs at least {} nodes to pass every output of layer {} through, not {}", previous, index - 1, width)));
+        }
+        // The activation function's value and slope at 0, where a small weight keeps it nearly straight
+        let (rest, slope, gain): (f32, f32, f32) = match self.activation_function {
+            ActivationFunction::Sigmoid => (0.5, 0.25, INSERTED_GAIN),
+            ActivationFunction::Tanh => (0.0, 1.0, INSERTED_GAIN),
+            // Linear nodes output twice their sum, so a weight of 0.5 passes values through exactly
+            ActivationFunction::Linear => (0.0, 2.0, 0.5)
+        };
+
+        // The next layer reads each value back out of the node passing it through
+        let scale: f32 = 1.0 / (slope * gain);
+        let overflows = |node: &Node| node.link_weights.iter().map(|weight| weight * scale * rest).sum::<f32>().is_infinite()
+            || node.link_weights.iter().any(|weight| (weight * scale).is_infinite());
+        if self.node_array[index].iter().any(overflows) {
+            return Err(DarjeelingError::InvalidArchitecture(format!("Scaling layer {}'s weights by {} to make room for the new layer would overflow them", index, scale)));
+        }
+
+        let mut rng = thread_rng();
+        let layer: Vec<Node> = (0..width).map(|i| {
+            let weights: Vec<f32> = (0..previous).map(|link| match i < previous {
+                true => if i == link { gain } else { 0.0 },
+                false => rng.gen_range(-GROWN_WEIGHT..GROWN_WEIGHT)
+            }).collect();
+            Node::new(&weights, Some(0.0))
+        }).collect();
+
+        self.node_array[index].iter_mut().for_each(|node| {
+            let shift: f32 = node.link_weights.iter().map(|weight| weight * scale * rest).sum();
+            node.link_weights.iter_mut().for_each(|weight| *weight *= scale);
+            node.link_weights.extend(std::iter::repeat(0.0).take(width - previous));
+            node.link_vals = vec![None; width];
+            node.links = width;
+            node.b_weight = Some(node.b_weight.unwrap_or(0.0) - shift);
+        });
+        self.node_array.insert(index, layer);
+        self.answer = Some(answer + 1);
+        Ok(())
+    }
+}
+
+/// Finds the index of the largest value, the first one if there's a tie
+pub(crate) fn largest_index(values: &[f32]) -> usize {
+    let mut largest = 0;
+    (0..values.len()).for_each(|i| if values[i] > values[largest] { largest = i });
+    largest
 }
 
 impl fmt::Display for CatNetwork {
@@ -0,0 +1,40 @@
+//! The ChaCha20 stream cipher from RFC 8439, so encrypting models doesn't need another dependency
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]); state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]); state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]); state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]); state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+/// One 64 byte block of keystream
+fn block(key: &[u8; 32], counter: u32, nonce: &[u8; 12]) -> [u8; 64] {
+    let word = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let mut initial: [u32; 16] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574, 0, 0, 0, 0, 0, 0, 0, 0, counter, 0, 0, 0];
+    (0..8).for_each(|i| initial[4 + i] = word(&key[i * 4..]));
+    (0..3).for_each(|i| initial[13 + i] = word(&nonce[i * 4..]));
+
+    let mut state: [u32; 16] = initial;
+    (0..10).for_each(|_| {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    });
+
+    let mut keystream: [u8; 64] = [0; 64];
+    (0..16).for_each(|i| keystream[i * 4..i * 4 + 4].copy_from_slice(&state[i].wrapping_add(initial[i]).to_le_bytes()));
+    keystream
+}
+
+/// XORs the bytes with the keystream, starting from block 1. Encrypting and decrypting are the same operation.
+pub(crate) fn chacha20(key: &[u8; 32], nonce: &[u8; 12], bytes: &[u8]) -> Vec<u8> {
+    bytes.chunks(64).enumerate().flat_map(|(i, chunk)| {
+        let keystream: [u8; 64] = block(key, i as u32 + 1, nonce);
+        chunk.iter().zip(keystream).map(|(byte, key_byte)| byte ^ key_byte).collect::<Vec<u8>>()
+    }).collect()
+}
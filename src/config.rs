@@ -0,0 +1,418 @@
+use std::{env, fs, path::{Path, PathBuf}};
+use serde::{Serialize, Deserialize};
+use crate::{activation::ActivationFunction, adversarial::{DiscriminatorEnsemble, EquilibriumStopping}, error::DarjeelingError, history::CompensatedSum};
+
+/// The environment variable holding the directory models are saved to, when a network isn't given one
+pub const OUTPUT_DIR_VAR: &str = "DARJEELING_OUTPUT_DIR";
+/// The environment variable holding the directory intermediate models are saved to, when a network isn't given one
+pub const TEMP_DIR_VAR: &str = "DARJEELING_TEMP_DIR";
+
+/// Everything needed to build and train a categorization network, so it can be done again the same way.
+/// Start from the [defaults](fn@TrainingConfig::default) and change what you need,
+/// so new settings don't break your code when they're added.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{config::TrainingConfig, activation::ActivationFunction};
+///
+/// let config = TrainingConfig::default()
+///     .with_hidden_num(4)
+///     .with_activation_function(ActivationFunction::Tanh)
+///     .with_learning_rate(0.1);
+/// assert_eq!(config.hidden_layers, 1);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrainingConfig {
+    /// The number of hidden nodes per hidden layer
+    pub hidden_num: i32,
+    /// The number of hidden layers
+    pub hidden_layers: i32,
+    pub activation_function: ActivationFunction,
+    /// The modifier that is applied to link weights as they're adjusted
+    pub learning_rate: f32,
+    /// The error percent at which training will stop
+    pub target_err_percent: f32,
+    /// Whether the model is saved when training finishes
+    #[serde(default = "default_write")]
+    pub write: bool,
+    /// Where trained models are saved. None uses `DARJEELING_OUTPUT_DIR`, or the working directory if that isn't set.
+    #[serde(default)]
+    pub output_dir: Option<PathBuf>,
+    /// Where models made along the way, like each cycle's distinguishing model, are saved.
+    /// None uses `DARJEELING_TEMP_DIR`, or doesn't save them if that isn't set.
+    #[serde(default)]
+    pub temp_dir: Option<PathBuf>,
+    /// The penalties on large link weights
+    #[serde(default)]
+    pub regularization: Regularization,
+    /// How the learning rate changes from epoch to epoch
+    #[serde(default)]
+    pub schedule: LearningRateSchedule,
+    /// How sums are added up, and whether training repeats bit for bit
+    #[serde(default)]
+    pub reduction: Reduction
+}
+
+fn default_write() -> bool {
+    true
+}
+
+/// 8 sigmoid nodes in 1 hidden layer, trained at a learning rate of 0.5 until 99% of the data is right, then saved
+impl Default for TrainingConfig {
+    fn default() -> TrainingConfig {
+        TrainingConfig::new(8, 1, ActivationFunction::Sigmoid, 0.5, 99.0)
+    }
+}
+
+impl TrainingConfig {
+
+    pub fn new(hidden_num: i32, hidden_layers: i32, activation_function: ActivationFunction, learning_rate: f32, target_err_percent: f32) -> TrainingConfig {
+        TrainingConfig { hidden_num, hidden_layers, activation_function, learning_rate, target_err_percent, write: true, output_dir: None, temp_dir: None, regularization: Regularization::default(), schedule: LearningRateSchedule::Constant, reduction: Reduction::Fast }
+    }
+
+    pub fn with_hidden_num(mut self, hidden_num: i32) -> TrainingConfig {
+        self.hidden_num = hidden_num;
+        self
+    }
+
+    pub fn with_hidden_layers(mut self, hidden_layers: i32) -> TrainingConfig {
+        self.hidden_layers = hidden_layers;
+        self
+    }
+
+    pub fn with_activation_function(mut self, activation_function: ActivationFunction) -> TrainingConfig {
+        self.activation_function = activation_function;
+        self
+    }
+
+    pub fn with_learning_rate(mut self, learning_rate: f32) -> TrainingConfig {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn with_target_err_percent(mut self, target_err_percent: f32) -> TrainingConfig {
+        self.target_err_percent = target_err_percent;
+        self
+    }
+
+    pub fn with_write(mut self, write: bool) -> TrainingConfig {
+        self.write = write;
+        self
+    }
+
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> TrainingConfig {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_temp_dir(mut self, dir: impl Into<PathBuf>) -> TrainingConfig {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_regularization(mut self, regularization: Regularization) -> TrainingConfig {
+        self.regularization = regularization;
+        self
+    }
+
+    pub fn with_schedule(mut self, schedule: LearningRateSchedule) -> TrainingConfig {
+        self.schedule = schedule;
+        self
+    }
+
+    pub fn with_reduction(mut self, reduction: Reduction) -> TrainingConfig {
+        self.reduction = reduction;
+        self
+    }
+}
+
+/// How the learning rate given to training changes from epoch to epoch
+///
+/// ## Examples
+/// ```
+/// use darjeeling::config::LearningRateSchedule;
+///
+/// let schedule = LearningRateSchedule::warm_restarts(10, 2);
+/// assert_eq!(schedule.learning_rate(1.0, 0), 1.0);
+/// assert!(schedule.learning_rate(1.0, 9) < 0.1);
+/// // The first cycle is 10 epochs, so the second starts over at epoch 10 and lasts 20
+/// assert_eq!(schedule.learning_rate(1.0, 10), 1.0);
+/// assert_eq!(schedule.learning_rate(1.0, 30), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum LearningRateSchedule {
+    /// The learning rate never changes
+    #[default]
+    Constant,
+    /// Cosine annealing with warm restarts, as in SGDR.
+    /// Each cycle, the learning rate falls along a cosine curve from the given learning rate to `min_learning_rate`,
+    /// then jumps back up, which shakes training out of plateaus.
+    WarmRestarts {
+        /// How many epochs the first cycle is
+        cycle_length: usize,
+        /// How many times longer each cycle is than the one before it
+        cycle_multiplier: usize,
+        /// The learning rate at the end of every cycle
+        min_learning_rate: f32
+    }
+}
+
+impl LearningRateSchedule {
+
+    /// Warm restarts that anneal all the way to 0
+    pub fn warm_restarts(cycle_length: usize, cycle_multiplier: usize) -> LearningRateSchedule {
+        LearningRateSchedule::WarmRestarts { cycle_length, cycle_multiplier, min_learning_rate: 0.0 }
+    }
+
+    /// The learning rate for an epoch, counting from 0
+    ///
+    /// ## Params
+    /// - Learning Rate: The learning rate training was given, which is the highest the schedule goes
+    /// - Epoch: How many epochs have already been trained
+    pub fn learning_rate(&self, learning_rate: f32, epoch: usize) -> f32 {
+        match *self {
+            LearningRateSchedule::Constant => learning_rate,
+            LearningRateSchedule::WarmRestarts { cycle_length: 0, .. } => learning_rate,
+            LearningRateSchedule::WarmRestarts { cycle_length, cycle_multiplier, min_learning_rate } => {
+                let (mut start, mut length) = (0, cycle_length);
+                while epoch >= start + length {
+                    start += length;
+                    length *= cycle_multiplier.max(1);
+                }
+                let progress: f32 = (epoch - start) as f32 / length as f32;
+                min_learning_rate + (learning_rate - min_learning_rate) * (1.0 + (std::f32::consts::PI * progress).cos()) / 2.0
+            }
+        }
+    }
+}
+
+/// Penalties on the size of the link weights, which keep a model from leaning too hard on a few inputs.
+/// The L1 penalty, `l1 * |w|`, pulls every weight towards 0 by the same amount, and sets the ones it crosses to 0.
+/// The L2 penalty, `l2 * w^2 / 2`, pulls each weight towards 0 in proportion to its size.
+/// Using both at once is an elastic net, which keeps some of L1's sparsity while being gentler on inputs that are correlated.
+/// Biases aren't penalized.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::config::Regularization;
+///
+/// let mut weight: f32 = 1.0;
+/// Regularization::elastic_net(0.1, 0.5).apply(&mut weight, 0.1);
+/// assert!((weight - 0.94).abs() < 0.0001);
+///
+/// let mut weight: f32 = 0.005;
+/// Regularization::l1(0.1).apply(&mut weight, 0.1);
+/// assert_eq!(weight, 0.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct Regularization {
+    #[serde(default)]
+    pub l1: f32,
+    #[serde(default)]
+    pub l2: f32
+}
+
+impl Regularization {
+
+    pub fn l1(l1: f32) -> Regularization {
+        Regularization { l1, l2: 0.0 }
+    }
+
+    pub fn l2(l2: f32) -> Regularization {
+        Regularization { l1: 0.0, l2 }
+    }
+
+    pub fn elastic_net(l1: f32, l2: f32) -> Regularization {
+        Regularization { l1, l2 }
+    }
+
+    /// Whether there's no penalty at all
+    pub fn is_none(&self) -> bool {
+        self.l1 == 0.0 && self.l2 == 0.0
+    }
+
+    /// Moves a weight down the gradient of the penalties
+    pub fn apply(&self, weight: &mut f32, learning_rate: f32) {
+        *weight -= learning_rate * self.l2 * *weight;
+        let shrink: f32 = learning_rate * self.l1;
+        *weight = weight.signum() * (weight.abs() - shrink).max(0.0);
+    }
+}
+
+/// How a categorization network adds up weighted inputs and error signals, and shuffles its epochs.
+/// Floating point addition isn't associative, so the same sum taken in another order can come out a little different,
+/// and the differences grow over a training run. The deterministic reduction takes every sum in a fixed order with
+/// Kahan summation, which carries the rounding error of each addition into the next, and shuffles every epoch with a generator
+/// seeded from the seed and the epoch's number instead of the thread's generator. Two runs from the same weights,
+/// like a [saved model](fn@crate::categorize::CatNetwork::from_darj_str), on the same data in the same order then train bit for bit the same,
+/// on any machine with IEEE floats and whatever else is running.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::config::Reduction;
+///
+/// // Each small value is lost to rounding when it's added to 1 on its own
+/// let values = || std::iter::once(1.0).chain(std::iter::repeat(1.0e-8).take(10_000));
+/// assert_eq!(Reduction::Fast.sum(values()), 1.0);
+/// assert!((Reduction::Deterministic { seed: 7 }.sum(values()) - 1.0001).abs() < 1.0e-6);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Reduction {
+    /// Plain sums, with epochs shuffled by the thread's generator
+    #[default]
+    Fast,
+    /// Compensated sums in a fixed order, with every epoch shuffled by a generator seeded from the seed and the epoch's number
+    Deterministic { seed: u64 }
+}
+
+impl Reduction {
+
+    /// Adds up the values in the order they come
+    pub fn sum<I: IntoIterator<Item = f32>>(&self, values: I) -> f32 {
+        match self {
+            Reduction::Fast => values.into_iter().fold(0.0, |sum, value| sum + value),
+            Reduction::Deterministic { .. } => CompensatedSum::of(values)
+        }
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        matches!(self, Reduction::Deterministic { .. })
+    }
+}
+
+/// Everything needed to train a generation network against a distinguishing network.
+/// Like [TrainingConfig](TrainingConfig), start from the [defaults](fn@GanConfig::default) and change what you need.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::config::{GanConfig, TrainingConfig};
+///
+/// let config = GanConfig::default()
+///     .with_cycles(10)
+///     .with_discriminator(TrainingConfig::default().with_target_err_percent(60.0));
+/// assert_eq!(config.learning_rate, 0.5);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GanConfig {
+    /// The generation model's learning rate
+    pub learning_rate: f32,
+    /// The number of adversarial training cycles
+    pub cycles: i32,
+    /// How to build and train the distinguishing model each cycle. Its directories and write setting aren't used.
+    pub discriminator: TrainingConfig,
+    /// Where the generation model is saved. None leaves the network's [output directory](fn@crate::generation::GenNetwork::set_output_dir) as it is.
+    #[serde(default)]
+    pub output_dir: Option<PathBuf>,
+    /// Where each cycle's distinguishing model is saved. None leaves the network's [temp directory](fn@crate::generation::GenNetwork::set_temp_dir) as it is.
+    #[serde(default)]
+    pub temp_dir: Option<PathBuf>,
+    /// How many distinguishing models to train against each cycle. None leaves the network's [ensemble](fn@crate::generation::GenNetwork::set_ensemble) as it is.
+    #[serde(default)]
+    pub ensemble: Option<DiscriminatorEnsemble>,
+    /// When to stop before the last cycle. None leaves the network's [equilibrium stopping](fn@crate::generation::GenNetwork::set_equilibrium_stopping) as it is.
+    #[serde(default)]
+    pub equilibrium: Option<EquilibriumStopping>,
+    /// How the generation and distinguishing models' learning rates change over the cycles.
+    /// None leaves the network's [schedules](fn@crate::generation::GenNetwork::set_schedules) as they are.
+    /// The distinguishing model's own schedule is over the epochs it trains for within a cycle, and isn't used.
+    #[serde(default)]
+    pub schedule: Option<LearningRateSchedule>,
+    #[serde(default)]
+    pub discriminator_schedule: Option<LearningRateSchedule>
+}
+
+/// 100 cycles at a learning rate of 0.5, against the [default](fn@TrainingConfig::default) distinguishing model
+impl Default for GanConfig {
+    fn default() -> GanConfig {
+        GanConfig { learning_rate: 0.5, cycles: 100, discriminator: TrainingConfig::default(), output_dir: None, temp_dir: None, ensemble: None, equilibrium: None, schedule: None, discriminator_schedule: None }
+    }
+}
+
+impl GanConfig {
+
+    pub fn with_learning_rate(mut self, learning_rate: f32) -> GanConfig {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn with_cycles(mut self, cycles: i32) -> GanConfig {
+        self.cycles = cycles;
+        self
+    }
+
+    pub fn with_discriminator(mut self, discriminator: TrainingConfig) -> GanConfig {
+        self.discriminator = discriminator;
+        self
+    }
+
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> GanConfig {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_temp_dir(mut self, dir: impl Into<PathBuf>) -> GanConfig {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_ensemble(mut self, ensemble: DiscriminatorEnsemble) -> GanConfig {
+        self.ensemble = Some(ensemble);
+        self
+    }
+
+    pub fn with_equilibrium_stopping(mut self, equilibrium: EquilibriumStopping) -> GanConfig {
+        self.equilibrium = Some(equilibrium);
+        self
+    }
+
+    /// Schedules the generation model's learning rate over the cycles
+    pub fn with_schedule(mut self, schedule: LearningRateSchedule) -> GanConfig {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Schedules the distinguishing model's learning rate over the cycles
+    pub fn with_discriminator_schedule(mut self, schedule: LearningRateSchedule) -> GanConfig {
+        self.discriminator_schedule = Some(schedule);
+        self
+    }
+
+    /// Trains the distinguishing model with a learning rate some times the generation model's, as in the two time-scale update rule (TTUR),
+    /// which often balances the two better than sharing one. Set the generation model's learning rate first.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::config::GanConfig;
+    ///
+    /// let config = GanConfig::default().with_learning_rate(0.1).with_two_time_scale(4.0);
+    /// assert!((config.discriminator.learning_rate - 0.4).abs() < 0.0001);
+    /// ```
+    pub fn with_two_time_scale(mut self, ratio: f32) -> GanConfig {
+        self.discriminator.learning_rate = self.learning_rate * ratio;
+        self
+    }
+}
+
+/// The directory given, or the one in the environment variable if there isn't one
+pub(crate) fn resolve_dir(dir: Option<&Path>, var: &str) -> Option<PathBuf> {
+    match dir {
+        Some(dir) => Some(dir.to_path_buf()),
+        None => env::var_os(var).filter(|dir| !dir.is_empty()).map(PathBuf::from)
+    }
+}
+
+/// Puts the file name in the directory, creating the directory if it doesn't exist yet.
+/// Without a directory the file name is used as it is, relative to the working directory.
+///
+/// ## Err
+/// - ### WriteModelFailed
+/// The directory couldn't be created
+pub(crate) fn file_path(dir: Option<PathBuf>, file_name: &str) -> Result<String, DarjeelingError> {
+    match dir {
+        Some(dir) => match fs::create_dir_all(&dir) {
+            Ok(()) => Ok(dir.join(file_name).to_string_lossy().into_owned()),
+            Err(error) => Err(DarjeelingError::WriteModelFailed(dir.to_string_lossy().into_owned() + ";" + &error.to_string()))
+        },
+        None => Ok(file_name.to_string())
+    }
+}
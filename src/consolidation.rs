@@ -0,0 +1,90 @@
+use serde::{Serialize, Deserialize};
+use crate::{categorize::CatNetwork, error::DarjeelingError, input::Input, node::Node};
+
+/// The weights of a trained network to pull it back towards while it's [updated](fn@CatNetwork::update) on new data,
+/// so fine-tuning on this month's data doesn't forget what was learned from the months before.
+///
+/// Every link and bias weight is pulled towards its anchored value by `strength * importance * (w - anchored)`.
+/// With [`l2`](fn@Anchor::l2) every weight is equally important.
+/// With [`ewc`](fn@Anchor::ewc), elastic weight consolidation, the weights the old data leaned on most are held the tightest,
+/// and the rest are free to learn the new data.
+///
+/// ## Examples
+/// ```ignore
+/// // Remembers what last month's data needed before training on this month's
+/// let anchor = Anchor::ewc(&net, &last_month, 1.0)?;
+/// net.update(&mut this_month, anchor, 0.1, 20)?;
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Anchor {
+    /// Every link weight then the bias of every node after the input layer, in order
+    weights: Vec<f32>,
+    /// How much each weight matters, in the same order, averaging 1
+    importance: Vec<f32>,
+    strength: f32
+}
+
+impl Anchor {
+
+    /// Anchors every weight of the network equally
+    ///
+    /// ## Params
+    /// - Strength: How hard the weights are pulled back, relative to the learning rate
+    pub fn l2(net: &CatNetwork, strength: f32) -> Anchor {
+        let weights: Vec<f32> = net.flat_weights();
+        Anchor { importance: vec![1.0; weights.len()], weights, strength }
+    }
+
+    /// Anchors each weight by how much the old data's error depends on it,
+    /// the diagonal of the Fisher information estimated from the squared gradients of every input
+    ///
+    /// ## Params
+    /// - Data: Some of the data the network was trained on. It doesn't need to be all of it.
+    /// - Strength: How hard the weights are pulled back, relative to the learning rate
+    ///
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// There's no data, or an input doesn't have an answer
+    /// - ### InvalidInput
+    /// An input isn't the network's input size
+    pub fn ewc(net: &CatNetwork, data: &[Input], strength: f32) -> Result<Anchor, DarjeelingError> {
+        if data.is_empty() || data.iter().any(|input| input.answer.is_none()) {
+            return Err(DarjeelingError::InvalidTrainingData("Estimating which weights matter needs data where every input has an answer".to_string()));
+        }
+        if let Some(input) = data.iter().find(|input| input.inputs.len() != net.input_size()) {
+            return Err(DarjeelingError::InvalidInput(format!("An input has {} values, but the network has {} sensors", input.inputs.len(), net.input_size())));
+        }
+        let mut importance: Vec<f32> = net.squared_gradients(data);
+        let mean: f32 = importance.iter().sum::<f32>() / importance.len().max(1) as f32;
+        if mean > 0.0 {
+            importance.iter_mut().for_each(|value| *value /= mean);
+        }
+        Ok(Anchor { weights: net.flat_weights(), importance, strength })
+    }
+
+    /// How much each weight matters, averaging 1, in the order of every link weight then the bias of every node after the input layer
+    pub fn importance(&self) -> &[f32] {
+        &self.importance
+    }
+
+    pub fn strength(&self) -> f32 {
+        self.strength
+    }
+
+    /// How many weights are anchored, which has to match the network being updated
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Moves every weight of the layers after the input layer down the gradient of the penalty
+    pub(crate) fn pull(&self, layers: &mut [Vec<Node>], learning_rate: f32) {
+        let weights = layers.iter_mut().flatten().flat_map(|node| node.link_weights.iter_mut().chain(node.b_weight.iter_mut()));
+        weights.zip(self.weights.iter().zip(&self.importance)).for_each(|(weight, (anchored, importance))| {
+            *weight -= learning_rate * self.strength * importance * (*weight - anchored);
+        });
+    }
+}
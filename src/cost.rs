@@ -0,0 +1,79 @@
+use serde::{Serialize, Deserialize};
+use crate::{error::DarjeelingError, prediction::normalize};
+
+/// What each kind of mistake costs, for networks that should make the cheapest decision rather than the likeliest one.
+/// Row `i`, column `j` is the cost of answering with the `j`th answer node when the answer is really the `i`th.
+/// Being right usually costs nothing, so the diagonal is usually zeros.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::cost::CostMatrix;
+///
+/// // Missing a positive costs 20 times as much as a false alarm
+/// let costs = CostMatrix::new(vec![vec![0.0, 1.0], vec![20.0, 0.0]]).unwrap();
+/// // Only 10% likely to be positive, but still cheaper to say it is
+/// assert_eq!(costs.decide(&[0.9, 0.1]), 1);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CostMatrix {
+    costs: Vec<Vec<f32>>
+}
+
+impl CostMatrix {
+
+    /// ## Params
+    /// - Costs: One row for every answer node, each with a cost for answering with every answer node
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The matrix is empty or isn't square, or a cost is negative or not finite
+    pub fn new(costs: Vec<Vec<f32>>) -> Result<CostMatrix, DarjeelingError> {
+        if costs.is_empty() {
+            return Err(DarjeelingError::InvalidInput("A cost matrix needs at least one row".to_string()));
+        }
+        if let Some(row) = costs.iter().position(|row| row.len() != costs.len()) {
+            return Err(DarjeelingError::InvalidInput(format!("Row {} of the cost matrix has {} costs, but there are {} rows", row, costs[row].len(), costs.len())));
+        }
+        if costs.iter().flatten().any(|cost| !cost.is_finite() || *cost < 0.0) {
+            return Err(DarjeelingError::InvalidInput("Costs have to be finite and can't be negative".to_string()));
+        }
+        Ok(CostMatrix { costs })
+    }
+
+    /// The number of answer nodes the matrix is for
+    pub fn size(&self) -> usize {
+        self.costs.len()
+    }
+
+    /// The cost of answering with `predicted` when the answer is `truth`, both answer node indexes
+    pub fn cost(&self, truth: usize, predicted: usize) -> f32 {
+        self.costs[truth][predicted]
+    }
+
+    /// How much answering with each answer node is expected to cost
+    ///
+    /// ## Params
+    /// - Probabilities: How likely each answer node is to be right. They're normalized to add up to 1,
+    ///   so raw outputs work too, but calibrated probabilities make much better decisions.
+    pub fn expected_costs(&self, probabilities: &[f32]) -> Vec<f32> {
+        let normalized: Vec<f32> = normalize(probabilities);
+        (0..self.size()).map(|predicted| {
+            normalized.iter().enumerate().map(|(truth, probability)| probability * self.costs[truth][predicted]).sum()
+        }).collect()
+    }
+
+    /// The answer node that's expected to cost the least, the first one if several tie
+    pub fn decide(&self, probabilities: &[f32]) -> usize {
+        let expected: Vec<f32> = self.expected_costs(probabilities);
+        (0..expected.len()).fold(0, |best, i| if expected[i] < expected[best] { i } else { best })
+    }
+
+    /// The average cost of a list of decisions, all as answer node indexes
+    pub fn average_cost(&self, predictions: &[usize], truth: &[usize]) -> f32 {
+        let count: usize = predictions.len().min(truth.len());
+        if count == 0 {
+            return 0.0;
+        }
+        predictions.iter().zip(truth).map(|(predicted, truth)| self.costs[*truth][*predicted]).sum::<f32>() / count as f32
+    }
+}
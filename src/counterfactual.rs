@@ -0,0 +1,46 @@
+use serde::{Serialize, Deserialize};
+use crate::prediction::Prediction;
+
+/// How a [counterfactual](fn@crate::categorize::CatNetwork::counterfactual) search may change one feature
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum Mutability {
+    /// The feature can be any value
+    #[default]
+    Free,
+    /// The feature can't change, like someone's age or a past event
+    Fixed,
+    /// The feature has to stay between the bounds
+    Range { min: f32, max: f32 },
+    /// The feature can only go up from where it is
+    Increase,
+    /// The feature can only go down from where it is
+    Decrease
+}
+
+impl Mutability {
+
+    /// Moves a changed value back to the nearest one the constraint allows
+    pub fn constrain(&self, original: f32, value: f32) -> f32 {
+        match *self {
+            Mutability::Free => value,
+            Mutability::Fixed => original,
+            Mutability::Range { min, max } => value.max(min).min(max),
+            Mutability::Increase => value.max(original),
+            Mutability::Decrease => value.min(original)
+        }
+    }
+}
+
+/// The changed inputs a [counterfactual](fn@crate::categorize::CatNetwork::counterfactual) search found,
+/// which the network puts in the target category
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Counterfactual {
+    /// Every input, changed or not
+    pub inputs: Vec<f32>,
+    /// The index of every feature that changed and how much it changed by, in the order of the features
+    pub changes: Vec<(usize, f32)>,
+    /// The sum of how far every feature moved, in scaled units if the network has a scaler
+    pub distance: f32,
+    /// The network's prediction for the changed inputs
+    pub prediction: Prediction
+}
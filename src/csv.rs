@@ -0,0 +1,221 @@
+use std::fs;
+use crate::{
+    categorize::CatNetwork,
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+#[cfg(feature = "parallel-csv")]
+use rayon::prelude::*;
+
+/// The number of lines each thread parses at a time with the `parallel-csv` feature
+#[cfg(feature = "parallel-csv")]
+const CHUNK_LINES: usize = 16_384;
+
+/// Reads a comma separated file of numbers into Inputs.
+/// Quoted fields aren't supported.
+///
+/// ## Params
+/// - Path: The file to read
+/// - Has Header: Whether the first line is column names, which are skipped
+/// - Label Column: The column holding each row's answer, read as Types::String. None if the rows have no answers.
+///
+/// ## Err
+/// - ### ReadDataFailed
+/// The file couldn't be read, a value wasn't a number, or a row didn't have the label column
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::csv;
+///
+/// // sepal_length,sepal_width,species
+/// // 5.1,3.5,setosa
+/// let data = csv::read_csv("iris.csv", true, Some(2)).unwrap();
+/// ```
+pub fn read_csv(path: &str, has_header: bool, label_column: Option<usize>) -> Result<Vec<Input>, DarjeelingError> {
+    let text: String = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+    };
+    match parse_csv(&text, has_header, label_column) {
+        Ok(data) => Ok(data),
+        Err(DarjeelingError::ReadDataFailed(error)) => Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error))),
+        Err(error) => Err(error)
+    }
+}
+
+/// The same as [`read_csv`](fn@read_csv), but for text that's already in memory.
+/// With the `parallel-csv` feature, large files are split into chunks of lines parsed on every core,
+/// and the rows come out in the same order either way.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{csv, types::Types};
+///
+/// let data = csv::parse_csv("x,y,label\n0,1,on\n1,0,off\n", true, Some(2)).unwrap();
+/// assert_eq!(data[0].inputs, vec![0.0, 1.0]);
+/// assert_eq!(data[1].answer, Some(Types::String("off".to_string())));
+/// ```
+pub fn parse_csv(text: &str, has_header: bool, label_column: Option<usize>) -> Result<Vec<Input>, DarjeelingError> {
+    let lines = text
+        .lines()
+        .enumerate()
+        .skip(if has_header { 1 } else { 0 })
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    // Chunks are parsed on every core and put back in order, and the first bad line is reported no matter which chunk finishes first
+    #[cfg(feature = "parallel-csv")]
+    {
+        let lines: Vec<(usize, &str)> = lines.collect();
+        let chunks: Vec<Result<Vec<Input>, DarjeelingError>> = crate::parallel::install(|| lines
+            .par_chunks(CHUNK_LINES)
+            .map(|chunk| chunk.iter().map(|(line_i, line)| parse_row(line, line_i + 1, label_column)).collect())
+            .collect());
+        let mut data: Vec<Input> = Vec::with_capacity(lines.len());
+        for chunk in chunks {
+            data.extend(chunk?);
+        }
+        Ok(data)
+    }
+    #[cfg(not(feature = "parallel-csv"))]
+    lines.map(|(line_i, line)| parse_row(line, line_i + 1, label_column)).collect()
+}
+
+/// Predicts every row of a CSV file and writes the rows back out with the predicted category and confidence added as two more columns.
+/// Rows are copied as they are, so IDs and other columns the model doesn't use come through untouched.
+///
+/// When the file has a header and the model has a [schema](fn@CatNetwork::set_schema), the model's features are picked out by name,
+/// in whatever order the columns are in. Otherwise every column is a feature, in order.
+///
+/// ## Params
+/// - Model: The network to predict with, which needs its categories
+/// - Input CSV: The file to read
+/// - Output CSV: Where to write the scored rows
+/// - Has Header: Whether the first line is column names
+///
+/// ## Returns
+/// How many rows were scored
+///
+/// ## Err
+/// - ### InvalidInput
+/// The model's categories haven't been set
+/// - ### ReadDataFailed
+/// The file couldn't be read, a value wasn't a number, or a row didn't have the model's features
+/// - ### WriteDataFailed
+/// The output file couldn't be written
+///
+/// ## Examples
+/// ```ignore
+/// let net = CatNetwork::read_model("loans.darj".to_string())?;
+/// let scored: usize = csv::score_file(&net, "applications.csv", "decisions.csv", true)?;
+/// ```
+pub fn score_file(model: &CatNetwork, input_csv: &str, output_csv: &str, has_header: bool) -> Result<usize, DarjeelingError> {
+    let text: String = match fs::read_to_string(input_csv) {
+        Ok(text) => text,
+        Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", input_csv, error)))
+    };
+    let (scored, rows) = match score_csv(model, &text, has_header) {
+        Ok(scored) => scored,
+        Err(DarjeelingError::ReadDataFailed(error)) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", input_csv, error))),
+        Err(error) => return Err(error)
+    };
+    match fs::write(output_csv, scored) {
+        Ok(()) => Ok(rows),
+        Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", output_csv, error)))
+    }
+}
+
+/// The same as [`score_file`](fn@score_file), but for text that's already in memory
+///
+/// ## Returns
+/// The scored CSV, and how many rows were scored
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{csv, categorize::CatNetwork, activation::ActivationFunction, types::Types};
+///
+/// let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+/// net.set_categories(vec![Types::String("off".to_string()), Types::String("on".to_string())]);
+/// let (scored, rows) = csv::score_csv(&net, "x,y\n0,1\n1,0\n", true).unwrap();
+/// assert_eq!(rows, 2);
+/// assert!(scored.starts_with("x,y,prediction,confidence\n0,1,o"));
+/// ```
+pub fn score_csv(model: &CatNetwork, text: &str, has_header: bool) -> Result<(String, usize), DarjeelingError> {
+    if model.categories().is_none() {
+        return Err(DarjeelingError::InvalidInput("The model needs its categories to score rows".to_string()));
+    }
+    let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    let mut scored: String = String::new();
+    // Which column holds each of the model's features
+    let mut columns: Vec<usize> = (0..model.input_size()).collect();
+    let by_name: bool = has_header && model.schema().is_some();
+    if has_header {
+        if let Some((_, header)) = lines.next() {
+            if let Some(schema) = model.schema() {
+                let names: Vec<&str> = header.split(',').map(|name| name.trim()).collect();
+                columns = schema.features().iter().map(|feature| match names.iter().position(|name| name == feature) {
+                    Some(column) => Ok(column),
+                    None => Err(DarjeelingError::ReadDataFailed(format!("The header has no {} column", feature)))
+                }).collect::<Result<Vec<usize>, DarjeelingError>>()?;
+            }
+            scored.push_str(&format!("{},prediction,confidence\n", header));
+        }
+    }
+
+    let mut rows: usize = 0;
+    for (line_i, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+        if !by_name && fields.len() != model.input_size() {
+            return Err(DarjeelingError::ReadDataFailed(format!("Line {} has {} columns, but the model has {} features", line_i + 1, fields.len(), model.input_size())));
+        }
+        let mut inputs: Vec<f32> = Vec::with_capacity(columns.len());
+        for column in &columns {
+            match fields.get(*column).map(|field| field.parse::<f32>()) {
+                Some(Ok(value)) => inputs.push(value),
+                Some(Err(error)) => return Err(DarjeelingError::ReadDataFailed(format!("Line {} column {}: {:?} {}", line_i + 1, column, fields[*column], error))),
+                None => return Err(DarjeelingError::ReadDataFailed(format!("Line {} has no column {}", line_i + 1, column)))
+            }
+        }
+        let prediction = model.predict(&inputs);
+        scored.push_str(&format!("{},{},{}\n", line, quote(&prediction.category.to_string()), prediction.confidence));
+        rows += 1;
+    }
+    Ok((scored, rows))
+}
+
+/// Quotes a field that has a comma or quote in it, so it stays one column
+fn quote(field: &str) -> String {
+    match field.contains(',') || field.contains('"') {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string()
+    }
+}
+
+/// The number of columns in the first line of the text
+pub fn column_count(text: &str) -> usize {
+    text.lines().next().map(|line| line.split(',').count()).unwrap_or(0)
+}
+
+fn parse_row(line: &str, line_number: usize, label_column: Option<usize>) -> Result<Input, DarjeelingError> {
+    let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+    let mut inputs: Vec<f32> = vec![];
+    let mut answer: Option<Types> = None;
+
+    if let Some(label_column) = label_column {
+        match fields.get(label_column) {
+            Some(label) => answer = Some(Types::String(label.to_string())),
+            None => return Err(DarjeelingError::ReadDataFailed(format!("Line {} has no column {}", line_number, label_column)))
+        }
+    }
+
+    for (column, field) in fields.iter().enumerate() {
+        if Some(column) == label_column {
+            continue;
+        }
+        match field.parse::<f32>() {
+            Ok(value) => inputs.push(value),
+            Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("Line {} column {}: {:?} {}", line_number, column, field, error)))
+        }
+    }
+    Ok(Input::new(inputs, answer))
+}
@@ -0,0 +1,103 @@
+//! Reading the lines of .darj models, shared by categorization and generation models.
+//! Every error says which line and column it's at, what was found there and what was expected.
+
+use serde::de::DeserializeOwned;
+use crate::{activation::ActivationFunction, error::DarjeelingError, node::Node};
+
+pub(crate) fn parse_error(line: usize, column: usize, token: &str, expected: &str) -> DarjeelingError {
+    DarjeelingError::ModelParse { line, column, token: token.to_string(), expected: expected.to_string() }
+}
+
+/// Reads a `weight,weight;bias` node line. Sensors don't have links, so their lines are `;bias`.
+///
+/// ## Params
+/// - Line Number: Where the line is in the model, counting from 1
+pub(crate) fn parse_node(line_number: usize, line: &str, sensor: bool) -> Result<Node, DarjeelingError> {
+    let line: &str = line.trim_end();
+    let (weights, bias) = match line.split_once(';') {
+        Some((weights, bias)) if !bias.contains(';') => (weights, bias),
+        Some((weights, _)) => return Err(parse_error(line_number, weights.chars().count() + 1, line, "one ; between the weights and the bias")),
+        None => return Err(parse_error(line_number, 1, line, match sensor {
+            true => "a sensor node like ;bias",
+            false => "a node like weight,weight;bias"
+        }))
+    };
+
+    let mut link_weights: Vec<f32> = vec![];
+    if sensor {
+        if !weights.is_empty() {
+            return Err(parse_error(line_number, 1, weights, "no weights, because sensor nodes don't have links"));
+        }
+    } else {
+        let mut column: usize = 1;
+        for weight in weights.split(',') {
+            link_weights.push(parse_number(line_number, column, weight, "a finite weight")?);
+            column += weight.chars().count() + 1;
+        }
+    }
+    let bias: f32 = parse_number(line_number, weights.chars().count() + 2, bias, "a finite bias")?;
+    Ok(Node::new(&link_weights, Some(bias)))
+}
+
+fn parse_number(line_number: usize, column: usize, token: &str, expected: &str) -> Result<f32, DarjeelingError> {
+    match token.parse::<f32>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(parse_error(line_number, column, token, expected))
+    }
+}
+
+/// Checks that the layers read from a model make a network that can run
+///
+/// ## Params
+/// - Unfinished: The nodes read after the last `lb`, which don't belong to any layer
+///
+/// ## Err
+/// - ### InvalidArchitecture
+/// There's no hidden or answer layer, a layer is empty, there are nodes after the last layer,
+/// or a node doesn't have one link for every node in the layer before it
+pub(crate) fn validate_architecture(node_array: &[Vec<Node>], unfinished: &[Node]) -> Result<(), DarjeelingError> {
+    if node_array.len() < 2 {
+        return Err(DarjeelingError::InvalidArchitecture(format!("The model has {} layers, but it needs sensors and answers at least", node_array.len())));
+    }
+    if !unfinished.is_empty() {
+        return Err(DarjeelingError::InvalidArchitecture(format!("{} nodes come after the last layer, which should end with lb", unfinished.len())));
+    }
+    if let Some(empty) = node_array.iter().position(|layer| layer.is_empty()) {
+        return Err(DarjeelingError::InvalidArchitecture(format!("Layer {} has no nodes", empty)));
+    }
+    for (layer_i, layer) in node_array.iter().enumerate().skip(1) {
+        let previous: usize = node_array[layer_i - 1].len();
+        if let Some((node_i, node)) = layer.iter().enumerate().find(|(_, node)| node.links() != previous) {
+            return Err(DarjeelingError::InvalidArchitecture(format!("Node {} of layer {} has {} links, but layer {} has {} nodes", node_i, layer_i, node.links(), layer_i - 1, previous)));
+        }
+    }
+    Ok(())
+}
+
+/// Whether the line is a single word, which is how the activation function is saved
+pub(crate) fn is_activation_line(line: &str) -> bool {
+    !line.trim().is_empty() && line.trim().chars().all(char::is_alphabetic)
+}
+
+pub(crate) fn parse_activation(line_number: usize, line: &str) -> Result<ActivationFunction, DarjeelingError> {
+    match line.parse() {
+        Ok(activation) => Ok(activation),
+        Err(_) => Err(parse_error(line_number, 1, line.trim(), &format!("an activation function, one of {}",
+            ActivationFunction::all().map(|function| function.to_string()).join(", ")
+        )))
+    }
+}
+
+/// Reads a `name {json}` metadata line
+///
+/// ## Params
+/// - Prefix: The name and the space after it
+/// - Expected: What the JSON should describe, for the error message
+pub(crate) fn parse_metadata<T: DeserializeOwned>(line_number: usize, line: &str, prefix: &str, expected: &str) -> Result<T, DarjeelingError> {
+    match serde_json::from_str(&line[prefix.len()..]) {
+        Ok(read) => Ok(read),
+        Err(error) => Err(parse_error(line_number, prefix.chars().count() + error.column().max(1), &line[prefix.len()..],
+            &format!("{} as JSON ({})", expected, error)
+        ))
+    }
+}
@@ -0,0 +1,142 @@
+//! A compact binary dataset format, so large datasets load in the time it takes to read them instead of parsing CSV.
+//!
+//! A `.darjdata` file is a 32 byte header, the labels, every row's answer, then every row's features:
+//! - The magic bytes `DARJDAT1`
+//! - The number of rows, as a little endian u64
+//! - The number of features in each row, as a little endian u64
+//! - The length of the labels in bytes, as a little endian u64
+//! - The labels, a JSON array of every distinct answer in the order they first appear, in the same form as [`Types`]
+//! - Each row's answer, as the little endian u32 index of its label, or `u32::MAX` if it doesn't have one
+//! - Every row's features one row after another, as little endian f32s
+//!
+//! Unlike [mapped datasets](crate::mapped), answers can be any type and the format works on every platform.
+
+use std::fs;
+use crate::{
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+const MAGIC: &[u8; 8] = b"DARJDAT1";
+const HEADER: usize = 32;
+/// The answer index of a row without an answer
+const UNANSWERED: u32 = u32::MAX;
+
+/// Encodes inputs in the `.darjdata` format
+///
+/// ## Err
+/// - ### InvalidTrainingData
+/// The inputs aren't all the same length
+/// - ### WriteDataFailed
+/// The labels couldn't be serialized
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{darjdata, input::Input, types::Types};
+///
+/// let data = vec![Input::new(vec![0.0, 1.0], Some(Types::String("cat".to_string()))), Input::new(vec![1.0, 1.0], None)];
+/// let bytes = darjdata::to_bytes(&data).unwrap();
+/// assert_eq!(darjdata::from_bytes(&bytes).unwrap(), data);
+/// ```
+pub fn to_bytes(data: &[Input]) -> Result<Vec<u8>, DarjeelingError> {
+    let columns: usize = data.first().map(|input| input.inputs.len()).unwrap_or(0);
+    let mut labels: Vec<Types> = vec![];
+    let mut answers: Vec<u32> = Vec::with_capacity(data.len());
+    for (i, input) in data.iter().enumerate() {
+        if input.inputs.len() != columns {
+            return Err(DarjeelingError::InvalidTrainingData(format!("Input {} has {} values, but the first has {}", i, input.inputs.len(), columns)));
+        }
+        answers.push(match &input.answer {
+            Some(answer) => match labels.iter().position(|label| label == answer) {
+                Some(index) => index as u32,
+                None => {
+                    labels.push(answer.clone());
+                    labels.len() as u32 - 1
+                }
+            },
+            None => UNANSWERED
+        });
+    }
+    let labels: Vec<u8> = serde_json::to_vec(&labels).map_err(|error| DarjeelingError::WriteDataFailed(format!("The labels couldn't be serialized: {}", error)))?;
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(HEADER + labels.len() + data.len() * (columns + 1) * 4);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&(columns as u64).to_le_bytes());
+    bytes.extend_from_slice(&(labels.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&labels);
+    answers.iter().for_each(|answer| bytes.extend_from_slice(&answer.to_le_bytes()));
+    data.iter().for_each(|input| input.inputs.iter().for_each(|value| bytes.extend_from_slice(&value.to_le_bytes())));
+    Ok(bytes)
+}
+
+/// Decodes inputs from the `.darjdata` format
+///
+/// ## Err
+/// - ### ReadDataFailed
+/// The bytes aren't in the format, are cut short, or an answer isn't one of the labels
+pub fn from_bytes(bytes: &[u8]) -> Result<Vec<Input>, DarjeelingError> {
+    let failed = |reason: &str| DarjeelingError::ReadDataFailed(reason.to_string());
+    if bytes.len() < HEADER || &bytes[..8] != MAGIC {
+        return Err(failed("It isn't a darjdata dataset"));
+    }
+    let field = |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().expect("Header fields are 8 bytes")) as usize;
+    let (rows, columns, label_length) = (field(8), field(16), field(24));
+    let answers_start: usize = HEADER.checked_add(label_length).ok_or_else(|| failed("The labels are longer than the file"))?;
+    let values_start: Option<usize> = rows.checked_mul(4).and_then(|length| length.checked_add(answers_start));
+    let expected: Option<usize> = rows.checked_mul(columns).and_then(|values| values.checked_mul(4)).zip(values_start).and_then(|(length, start)| length.checked_add(start));
+    let (values_start, expected) = match (values_start, expected) {
+        (Some(values_start), Some(expected)) if expected == bytes.len() => (values_start, expected),
+        _ => return Err(DarjeelingError::ReadDataFailed(format!("The header says {} rows of {} values, which doesn't match the {} bytes", rows, columns, bytes.len())))
+    };
+
+    let labels: Vec<Types> = serde_json::from_slice(&bytes[HEADER..answers_start]).map_err(|error| DarjeelingError::ReadDataFailed(format!("The labels couldn't be read: {}", error)))?;
+    let answers = bytes[answers_start..values_start].chunks_exact(4).map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+    let mut values = bytes[values_start..expected].chunks_exact(4).map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+
+    let mut data: Vec<Input> = Vec::with_capacity(rows);
+    for answer in answers {
+        let answer: Option<Types> = match answer {
+            UNANSWERED => None,
+            index => Some(labels.get(index as usize).cloned().ok_or_else(|| DarjeelingError::ReadDataFailed(format!("Answer {} isn't one of the {} labels", index, labels.len())))?)
+        };
+        data.push(Input::new(values.by_ref().take(columns).collect(), answer));
+    }
+    Ok(data)
+}
+
+/// Writes inputs to a `.darjdata` file, to be read with [`read_darjdata`](fn@read_darjdata)
+///
+/// ## Err
+/// - The same as [`to_bytes`](fn@to_bytes), along with
+/// - ### WriteDataFailed
+/// The file couldn't be written
+pub fn write_darjdata(path: &str, data: &[Input]) -> Result<(), DarjeelingError> {
+    fs::write(path, to_bytes(data)?).map_err(|error| DarjeelingError::WriteDataFailed(format!("{}: {}", path, error)))
+}
+
+/// Reads every input from a `.darjdata` file written by [`write_darjdata`](fn@write_darjdata)
+///
+/// ## Err
+/// - ### ReadDataFailed
+/// The file couldn't be read, or it isn't a darjdata file
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{darjdata, input::Input, types::Types};
+///
+/// let path = std::env::temp_dir().join("darjdata_example.darjdata");
+/// let path = path.to_str().unwrap();
+/// darjdata::write_darjdata(path, &[Input::new(vec![0.5, 0.25], Some(Types::Integer(3)))]).unwrap();
+/// let data = darjdata::read_darjdata(path).unwrap();
+/// assert_eq!(data[0].answer, Some(Types::Integer(3)));
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+pub fn read_darjdata(path: &str) -> Result<Vec<Input>, DarjeelingError> {
+    let bytes: Vec<u8> = fs::read(path).map_err(|error| DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))?;
+    from_bytes(&bytes).map_err(|error| match error {
+        DarjeelingError::ReadDataFailed(reason) => DarjeelingError::ReadDataFailed(format!("{}: {}", path, reason)),
+        error => error
+    })
+}
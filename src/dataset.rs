@@ -0,0 +1,598 @@
+use std::{borrow::Cow, collections::HashMap, fmt};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Serialize, Deserialize};
+use crate::{
+    activation::ActivationFunction,
+    categorize::CatNetwork,
+    error::DarjeelingError,
+    hash,
+    input::Input,
+    types::{Types, FLOAT_EPSILON}
+};
+
+/// Problems found in a dataset by [`audit`](fn@Dataset::audit), as indexes into the data
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AuditReport {
+    /// Groups of inputs with the same values and the same answer
+    pub duplicates: Vec<Vec<usize>>,
+    /// Groups of inputs with the same values but different answers, which no model can get all of right
+    pub conflicts: Vec<Vec<usize>>,
+    /// Input values that are the same in every input, so they can't help tell inputs apart
+    pub constant_features: Vec<usize>
+}
+
+impl AuditReport {
+
+    /// Whether nothing was found
+    pub fn is_clean(&self) -> bool {
+        self.duplicates.is_empty() && self.conflicts.is_empty() && self.constant_features.is_empty()
+    }
+}
+
+impl fmt::Display for AuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Duplicate groups: {} ({} inputs)", self.duplicates.len(), self.duplicates.iter().map(Vec::len).sum::<usize>())?;
+        writeln!(f, "Conflicting groups: {} ({} inputs)", self.conflicts.len(), self.conflicts.iter().map(Vec::len).sum::<usize>())?;
+        write!(f, "Constant features: {:?}", self.constant_features)
+    }
+}
+
+/// The spread of one feature of a dataset, from [`describe`](fn@Dataset::describe)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeatureSummary {
+    /// The statistics are over the values that aren't missing, and 0 if every one is
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub std: f32,
+    /// The number of inputs where the value is NaN, or the input is too short to have it
+    pub missing: usize
+}
+
+/// The basic statistics of a dataset, from [`describe`](fn@Dataset::describe)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DatasetSummary {
+    pub rows: usize,
+    /// One for every value of the longest input
+    pub features: Vec<FeatureSummary>,
+    /// The number of inputs with each answer, in the order the answers first appear
+    pub categories: Vec<(Types, usize)>,
+    /// The number of inputs without an answer
+    pub unanswered: usize
+}
+
+impl fmt::Display for DatasetSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} rows, {} features", self.rows, self.features.len())?;
+        writeln!(f, "{:<10} {:>10} {:>10} {:>10} {:>10} {:>8}", "Feature", "Min", "Max", "Mean", "Std", "Missing")?;
+        for (i, feature) in self.features.iter().enumerate() {
+            writeln!(f, "{:<10} {:>10.4} {:>10.4} {:>10.4} {:>10.4} {:>8}", i, feature.min, feature.max, feature.mean, feature.std, feature.missing)?;
+        }
+        write!(f, "{:<16} {:>8} {:>8}", "Category", "Count", "Share")?;
+        for (category, count) in &self.categories {
+            write!(f, "\n{:<16} {:>8} {:>8.4}", format!("{:?}", category), count, *count as f32 / self.rows.max(1) as f32)?;
+        }
+        if self.unanswered > 0 {
+            write!(f, "\n{:<16} {:>8} {:>8.4}", "No answer", self.unanswered, self.unanswered as f32 / self.rows.max(1) as f32)?;
+        }
+        Ok(())
+    }
+}
+
+/// The layer sizes [`suggest_architecture`](fn@Dataset::suggest_architecture) thinks suit a dataset, in the order [`CatNetwork::new`] takes them.
+/// They're a starting point rather than the best sizes, which [tuning](crate::tuning) can search for.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchitectureSuggestion {
+    /// The number of sensors, one for every value of an input
+    pub inputs: i32,
+    /// The number of nodes in each hidden layer
+    pub hidden: i32,
+    /// The number of answer nodes, one for every category
+    pub answers: i32,
+    pub hidden_layers: i32
+}
+
+impl ArchitectureSuggestion {
+
+    /// Makes a network with the suggested sizes
+    pub fn network(&self, activation_function: ActivationFunction) -> CatNetwork {
+        CatNetwork::new(self.inputs, self.hidden, self.answers, self.hidden_layers, activation_function)
+    }
+}
+
+impl fmt::Display for ArchitectureSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} inputs, {} hidden layer(s) of {}, {} answers", self.inputs, self.hidden_layers, self.hidden, self.answers)
+    }
+}
+
+/// A summary of a dataset that's saved with models trained on it, so an audit can check which data a model came from.
+/// The digest is a SHA-256 hash of every input and answer. Inputs are hashed one at a time and the hashes are sorted first,
+/// so shuffling the data, like training does, doesn't change it.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{dataset::DataFingerprint, input::Input, types::Types};
+///
+/// let mut data = vec![Input::new(vec![0.0, 1.0], Some(Types::Integer(1))), Input::new(vec![1.0, 1.0], Some(Types::Integer(0)))];
+/// let fingerprint = DataFingerprint::of(&data);
+/// assert_eq!((fingerprint.rows, fingerprint.features), (2, 2));
+/// data.reverse();
+/// assert!(fingerprint.matches(&data));
+/// data[0].answer = Some(Types::Integer(2));
+/// assert!(!fingerprint.matches(&data));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataFingerprint {
+    pub rows: usize,
+    /// The number of values in the longest input
+    pub features: usize,
+    /// The hexadecimal SHA-256 digest of the data
+    pub digest: String
+}
+
+impl DataFingerprint {
+
+    pub fn of(data: &[Input]) -> DataFingerprint {
+        let mut rows: Vec<[u8; 32]> = data.iter().map(|input| {
+            let mut bytes: Vec<u8> = (input.inputs.len() as u64).to_le_bytes().to_vec();
+            input.inputs.iter().for_each(|value| bytes.extend_from_slice(&value.to_le_bytes()));
+            // Serializing an answer can't fail, since they're all plain data
+            bytes.extend_from_slice(serde_json::to_string(&input.answer).unwrap_or_default().as_bytes());
+            hash::sha256(&bytes)
+        }).collect();
+        rows.sort();
+
+        DataFingerprint {
+            rows: data.len(),
+            features: data.iter().map(|input| input.inputs.len()).max().unwrap_or(0),
+            digest: hash::to_hex(&hash::sha256(&rows.concat()))
+        }
+    }
+
+    /// Whether the data is the same as the data the fingerprint was taken of, in any order
+    pub fn matches(&self, data: &[Input]) -> bool {
+        DataFingerprint::of(data) == *self
+    }
+}
+
+impl fmt::Display for DataFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} rows, {} features, sha256 {}", self.rows, self.features, self.digest)
+    }
+}
+
+/// A list of inputs, with helpers for looking at and reshaping the data before training
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    inputs: Vec<Input>,
+    /// How far apart two float answers can be and still be the same category
+    float_tolerance: f32
+}
+
+impl Default for Dataset {
+    fn default() -> Dataset {
+        Dataset::new(vec![])
+    }
+}
+
+impl Dataset {
+
+    pub fn new(inputs: Vec<Input>) -> Dataset {
+        Dataset { inputs, float_tolerance: FLOAT_EPSILON }
+    }
+
+    /// How far apart two float answers can be and still be the same category, when the data is split up by answer.
+    /// [`FLOAT_EPSILON`] by default.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{dataset::Dataset, input::Input, types::Types};
+    ///
+    /// let mut data = Dataset::new(vec![Input::new(vec![0.0], Some(Types::Float(0.5))), Input::new(vec![1.0], Some(Types::Float(0.501)))]);
+    /// assert_eq!(data.categories().len(), 2);
+    /// data.set_float_tolerance(0.01);
+    /// assert_eq!(data.categories(), vec![Types::Float(0.5)]);
+    /// ```
+    pub fn set_float_tolerance(&mut self, tolerance: f32) {
+        self.float_tolerance = tolerance;
+    }
+
+    pub fn float_tolerance(&self) -> f32 {
+        self.float_tolerance
+    }
+
+    /// A dataset of other inputs, with the same settings as this one
+    fn derived(&self, inputs: Vec<Input>) -> Dataset {
+        Dataset { inputs, float_tolerance: self.float_tolerance }
+    }
+
+    pub fn inputs(&self) -> &[Input] {
+        &self.inputs
+    }
+
+    pub fn inputs_mut(&mut self) -> &mut Vec<Input> {
+        &mut self.inputs
+    }
+
+    pub fn into_inputs(self) -> Vec<Input> {
+        self.inputs
+    }
+
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    pub fn fingerprint(&self) -> DataFingerprint {
+        DataFingerprint::of(&self.inputs)
+    }
+
+    /// Every distinct answer in the data, in the order they first appear
+    pub fn categories(&self) -> Vec<Types> {
+        self.groups().into_iter().map(|(category, _)| category).collect()
+    }
+
+    /// Every distinct answer in the data, sorted by [`Types::total_cmp`](fn@Types::total_cmp),
+    /// so shuffling the data doesn't change the order
+    pub fn sorted_categories(&self) -> Vec<Types> {
+        let mut categories: Vec<Types> = self.categories();
+        categories.sort_by(|a, b| a.total_cmp(b));
+        categories
+    }
+
+    /// The number of inputs with each answer, in the order the answers first appear
+    pub fn category_counts(&self) -> Vec<(Types, usize)> {
+        self.groups().into_iter().map(|(category, indexes)| (category, indexes.len())).collect()
+    }
+
+    /// The minimum, maximum, mean, standard deviation and number of missing values of every feature, and how many inputs have each answer,
+    /// for checking data looks right before training on it
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{dataset::Dataset, input::Input, types::Types};
+    ///
+    /// let data = Dataset::new(vec![
+    ///     Input::new(vec![1.0, f32::NAN], Some(Types::Integer(0))),
+    ///     Input::new(vec![3.0, 2.0], Some(Types::Integer(1))),
+    ///     Input::new(vec![5.0, 4.0], None)
+    /// ]);
+    /// let summary = data.describe();
+    /// assert_eq!((summary.features[0].min, summary.features[0].mean), (1.0, 3.0));
+    /// assert_eq!(summary.features[1].missing, 1);
+    /// assert_eq!(summary.unanswered, 1);
+    /// println!("{}", summary);
+    /// ```
+    pub fn describe(&self) -> DatasetSummary {
+        let width: usize = self.inputs.iter().map(|input| input.inputs.len()).max().unwrap_or(0);
+        let features: Vec<FeatureSummary> = (0..width).map(|i| {
+            let values: Vec<f32> = self.inputs.iter().filter_map(|input| input.inputs.get(i).copied()).filter(|value| !value.is_nan()).collect();
+            let (mean, std) = crate::validation::mean_std(&values);
+            match values.is_empty() {
+                true => FeatureSummary { min: 0.0, max: 0.0, mean: 0.0, std: 0.0, missing: self.inputs.len() },
+                false => FeatureSummary {
+                    min: values.iter().copied().fold(f32::INFINITY, f32::min),
+                    max: values.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+                    mean,
+                    std,
+                    missing: self.inputs.len() - values.len()
+                }
+            }
+        }).collect();
+
+        DatasetSummary {
+            rows: self.inputs.len(),
+            features,
+            categories: self.category_counts(),
+            unanswered: self.inputs.iter().filter(|input| input.answer.is_none()).count()
+        }
+    }
+
+    /// Suggests the sizes of a network for the data, so a first model doesn't need its architecture guessed.
+    /// There's a sensor for every input value and an answer node for every category, or for every value of a vector answer.
+    /// The single hidden layer has two thirds as many nodes as the inputs, plus one for every answer node,
+    /// but no more than there are inputs to train each link on twice over, and never fewer than 2.
+    ///
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// No input has an answer, or the inputs aren't all the same length
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{activation::ActivationFunction, dataset::Dataset, input::Input, types::Types};
+    ///
+    /// let data = Dataset::new((0..300).map(|i| Input::new(vec![i as f32; 6], Some(Types::Integer(i % 3)))).collect());
+    /// let suggestion = data.suggest_architecture().unwrap();
+    /// assert_eq!((suggestion.inputs, suggestion.hidden, suggestion.answers), (6, 7, 3));
+    /// let mut net = suggestion.network(ActivationFunction::Sigmoid);
+    /// ```
+    pub fn suggest_architecture(&self) -> Result<ArchitectureSuggestion, DarjeelingError> {
+        let inputs: usize = self.inputs.first().map(|input| input.inputs.len()).unwrap_or(0);
+        if let Some(i) = self.inputs.iter().position(|input| input.inputs.len() != inputs) {
+            return Err(DarjeelingError::InvalidTrainingData(format!("Input {} has {} values, but the first has {}", i, self.inputs[i].inputs.len(), inputs)));
+        }
+        let answers: usize = match self.inputs.iter().find_map(|input| input.answer.as_ref()) {
+            Some(Types::Vector(values)) => values.len(),
+            Some(_) => self.groups().len(),
+            None => return Err(DarjeelingError::InvalidTrainingData("Suggesting an architecture needs inputs with answers".to_string()))
+        };
+
+        let hidden: usize = (inputs * 2 / 3 + answers)
+            .min(self.inputs.len() / (2 * (inputs + answers)).max(1))
+            .max(2);
+        Ok(ArchitectureSuggestion { inputs: inputs as i32, hidden: hidden as i32, answers: answers as i32, hidden_layers: 1 })
+    }
+
+    /// The indexes of the inputs with each answer. Inputs without an answer aren't included.
+    pub(crate) fn groups(&self) -> Vec<(Types, Vec<usize>)> {
+        let mut groups: Vec<(Types, Vec<usize>)> = vec![];
+        self.inputs.iter().enumerate().for_each(|(i, input)| {
+            if let Some(answer) = &input.answer {
+                match groups.iter_mut().find(|(category, _)| category.approx_eq(answer, self.float_tolerance)) {
+                    Some((_, indexes)) => indexes.push(i),
+                    None => groups.push((answer.clone(), vec![i]))
+                }
+            }
+        });
+        groups
+    }
+
+    /// Randomly duplicates inputs of every category until they all have as many inputs as the largest category
+    pub fn oversample<R: Rng>(&self, rng: &mut R) -> Dataset {
+        let groups = self.groups();
+        let largest: usize = groups.iter().map(|(_, indexes)| indexes.len()).max().unwrap_or(0);
+        let mut inputs: Vec<Input> = self.inputs.clone();
+        groups.iter().for_each(|(_, indexes)| {
+            (indexes.len()..largest).for_each(|_| {
+                let chosen: usize = indexes[rng.gen_range(0..indexes.len())];
+                inputs.push(self.inputs[chosen].clone());
+            })
+        });
+
+        self.derived(inputs)
+    }
+
+    /// Randomly drops inputs of every category until they all have as many inputs as the smallest category.
+    /// Inputs without an answer are kept.
+    pub fn undersample<R: Rng>(&self, rng: &mut R) -> Dataset {
+        let groups = self.groups();
+        let smallest: usize = groups.iter().map(|(_, indexes)| indexes.len()).min().unwrap_or(0);
+        let mut kept: Vec<usize> = self.inputs
+            .iter()
+            .enumerate()
+            .filter(|(_, input)| input.answer.is_none())
+            .map(|(i, _)| i)
+            .collect();
+        groups.into_iter().for_each(|(_, mut indexes)| {
+            indexes.shuffle(rng);
+            kept.extend(indexes.into_iter().take(smallest));
+        });
+        kept.sort();
+
+        self.derived(kept.into_iter().map(|i| self.inputs[i].clone()).collect())
+    }
+
+    /// Looks for inputs that are repeated, inputs whose answers contradict each other, and values that never change,
+    /// so they can be cleaned up before training. Values are compared exactly.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{dataset::Dataset, input::Input, types::Types};
+    ///
+    /// let data = Dataset::new(vec![
+    ///     Input::new(vec![0.0, 1.0], Some(Types::Integer(0))),
+    ///     Input::new(vec![0.0, 1.0], Some(Types::Integer(0))),
+    ///     Input::new(vec![1.0, 1.0], Some(Types::Integer(0))),
+    ///     Input::new(vec![1.0, 1.0], Some(Types::Integer(1)))
+    /// ]);
+    /// let report = data.audit();
+    /// assert_eq!(report.duplicates, vec![vec![0, 1]]);
+    /// assert_eq!(report.conflicts, vec![vec![2, 3]]);
+    /// assert_eq!(report.constant_features, vec![1]);
+    /// ```
+    pub fn audit(&self) -> AuditReport {
+        let mut rows: HashMap<Vec<u32>, Vec<usize>> = HashMap::new();
+        self.inputs.iter().enumerate().for_each(|(i, input)| {
+            // -0.0 and 0.0 are the same value
+            let key: Vec<u32> = input.inputs.iter().map(|value| (value + 0.0).to_bits()).collect();
+            rows.entry(key).or_default().push(i);
+        });
+        let mut groups: Vec<Vec<usize>> = rows.into_values().filter(|indexes| indexes.len() > 1).collect();
+        groups.sort();
+
+        let mut report = AuditReport::default();
+        groups.into_iter().for_each(|indexes| {
+            let answer: &Option<Types> = &self.inputs[indexes[0]].answer;
+            let same = |other: &Option<Types>| match (answer, other) {
+                (Some(answer), Some(other)) => answer.approx_eq(other, self.float_tolerance),
+                (answer, other) => answer == other
+            };
+            match indexes.iter().all(|i| same(&self.inputs[*i].answer)) {
+                true => report.duplicates.push(indexes),
+                false => report.conflicts.push(indexes)
+            }
+        });
+
+        let columns: usize = self.inputs.iter().map(|input| input.inputs.len()).min().unwrap_or(0);
+        report.constant_features = (0..columns)
+            .filter(|column| self.inputs.iter().all(|input| input.inputs[*column] == self.inputs[0].inputs[*column]))
+            .collect();
+        report
+    }
+
+    /// Randomly splits the data into training and validation sets, keeping each category's share of the data the same in both.
+    /// Every category with at least 2 inputs has at least 1 of them in each set.
+    /// Inputs without an answer all go in the training set, and both sets keep the data's order.
+    ///
+    /// ## Params
+    /// - Validation Fraction: How much of each category goes in the validation set, between 0 and 1
+    ///
+    /// ## Returns
+    /// The training set and the validation set
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{dataset::Dataset, input::Input, types::Types};
+    /// use rand::{SeedableRng, rngs::StdRng};
+    ///
+    /// let data = Dataset::new((0..10).map(|i| Input::new(vec![i as f32], Some(Types::Boolean(i < 8)))).collect());
+    /// let (train, validation) = data.stratified_split(0.25, &mut StdRng::seed_from_u64(1));
+    /// // 2 of the 8 trues and 1 of the 2 falses
+    /// assert_eq!(validation.category_counts(), vec![(Types::Boolean(true), 2), (Types::Boolean(false), 1)]);
+    /// assert_eq!(train.len(), 7);
+    /// ```
+    pub fn stratified_split<R: Rng>(&self, validation_fraction: f32, rng: &mut R) -> (Dataset, Dataset) {
+        let validation_fraction: f32 = validation_fraction.clamp(0.0, 1.0);
+        let mut validation: Vec<usize> = vec![];
+        self.groups().into_iter().for_each(|(_, mut indexes)| {
+            indexes.shuffle(rng);
+            let mut held_out: usize = (indexes.len() as f32 * validation_fraction).round() as usize;
+            if indexes.len() >= 2 && validation_fraction > 0.0 && validation_fraction < 1.0 {
+                held_out = held_out.clamp(1, indexes.len() - 1);
+            }
+            validation.extend(indexes.into_iter().take(held_out));
+        });
+        validation.sort();
+
+        let (mut train_inputs, mut validation_inputs): (Vec<Input>, Vec<Input>) = (vec![], vec![]);
+        self.inputs.iter().enumerate().for_each(|(i, input)| match validation.binary_search(&i) {
+            Ok(_) => validation_inputs.push(input.clone()),
+            Err(_) => train_inputs.push(input.clone())
+        });
+        (self.derived(train_inputs), self.derived(validation_inputs))
+    }
+
+    /// Randomly splits the data into training, validation and test sets in one go, keeping each category's share of the data the same in all three.
+    /// Every input ends up in exactly one set, so nothing leaks between them the way it can when two-way splits are chained.
+    /// Every category with at least 3 inputs has at least 1 of them in each set with a fraction above 0.
+    /// Inputs without an answer all go in the training set, and every set keeps the data's order.
+    ///
+    /// ## Params
+    /// - Validation Fraction: How much of each category goes in the validation set
+    /// - Test Fraction: How much of each category goes in the test set. The two fractions have to add up to less than 1.
+    /// - Seed: Decides the split, so the same seed and data always split the same way
+    ///
+    /// ## Returns
+    /// The training, validation and test sets
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// A fraction is negative, or they add up to 1 or more
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{dataset::Dataset, input::Input, types::Types};
+    ///
+    /// let data = Dataset::new((0..20).map(|i| Input::new(vec![i as f32], Some(Types::Boolean(i < 10)))).collect());
+    /// let (train, validation, test) = data.three_way_split(0.2, 0.2, 42).unwrap();
+    /// assert_eq!((train.len(), validation.len(), test.len()), (12, 4, 4));
+    /// assert_eq!(test.category_counts(), vec![(Types::Boolean(true), 2), (Types::Boolean(false), 2)]);
+    /// ```
+    pub fn three_way_split(&self, validation_fraction: f32, test_fraction: f32, seed: u64) -> Result<(Dataset, Dataset, Dataset), DarjeelingError> {
+        if validation_fraction.is_nan() || test_fraction.is_nan() || validation_fraction < 0.0 || test_fraction < 0.0 || validation_fraction + test_fraction >= 1.0 {
+            return Err(DarjeelingError::InvalidInput(format!("The validation and test fractions have to be at least 0 and add up to less than 1, not {} and {}", validation_fraction, test_fraction)));
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        // 0 for training, 1 for validation and 2 for test
+        let mut sets: Vec<u8> = vec![0; self.inputs.len()];
+        self.groups().into_iter().for_each(|(_, mut indexes)| {
+            indexes.shuffle(&mut rng);
+            let count: usize = indexes.len();
+            let share = |fraction: f32| {
+                let share: usize = (count as f32 * fraction).round() as usize;
+                if count >= 3 && fraction > 0.0 { share.max(1) } else { share }
+            };
+            let test: usize = share(test_fraction).min(count.saturating_sub(1));
+            let validation: usize = share(validation_fraction).min(count.saturating_sub(1 + test));
+            indexes.iter().take(test).for_each(|i| sets[*i] = 2);
+            indexes.iter().skip(test).take(validation).for_each(|i| sets[*i] = 1);
+        });
+
+        let mut split: [Vec<Input>; 3] = [vec![], vec![], vec![]];
+        self.inputs.iter().zip(&sets).for_each(|(input, set)| split[*set as usize].push(input.clone()));
+        let [train, validation, test] = split;
+        Ok((self.derived(train), self.derived(validation), self.derived(test)))
+    }
+
+    /// Creates synthetic inputs for every category until they all have as many inputs as the largest category.
+    /// Each new input is a random point on the line between an input and one of its k nearest neighbors with the same answer,
+    /// like SMOTE.
+    pub fn smote<R: Rng>(&self, k: usize, rng: &mut R) -> Dataset {
+        let groups = self.groups();
+        let largest: usize = groups.iter().map(|(_, indexes)| indexes.len()).max().unwrap_or(0);
+        let mut inputs: Vec<Input> = self.inputs.clone();
+        groups.iter().for_each(|(category, indexes)| {
+            (indexes.len()..largest).for_each(|_| {
+                let chosen: &Input = &self.inputs[indexes[rng.gen_range(0..indexes.len())]];
+                let mut neighbors: Vec<&Input> = indexes.iter().map(|i| &self.inputs[*i]).collect();
+                neighbors.sort_by(|a, b| distance(&a.inputs, &chosen.inputs).total_cmp(&distance(&b.inputs, &chosen.inputs)));
+                // The closest neighbor is the chosen input itself, unless there are duplicates
+                let neighbor: &Input = match neighbors.len() {
+                    1 => neighbors[0],
+                    len => neighbors[rng.gen_range(1..=k.clamp(1, len - 1))]
+                };
+                let gap: f32 = rng.gen();
+                let synthetic: Vec<f32> = chosen.inputs
+                    .iter()
+                    .zip(&neighbor.inputs)
+                    .map(|(a, b)| a + gap * (b - a))
+                    .collect();
+                inputs.push(Input::new(synthetic, Some(category.clone())));
+            })
+        });
+
+        self.derived(inputs)
+    }
+}
+
+/// Anything training data can be read from, one pass at a time.
+/// Each call to pass starts reading the data from the beginning again, which happens once per epoch.
+/// Data that's already in memory is lent out instead of being copied.
+pub trait DataSource {
+    fn pass(&mut self) -> Box<dyn Iterator<Item = Cow<'_, Input>> + '_>;
+}
+
+impl DataSource for Dataset {
+    fn pass(&mut self) -> Box<dyn Iterator<Item = Cow<'_, Input>> + '_> {
+        Box::new(self.inputs.iter().map(Cow::Borrowed))
+    }
+}
+
+impl DataSource for Vec<Input> {
+    fn pass(&mut self) -> Box<dyn Iterator<Item = Cow<'_, Input>> + '_> {
+        Box::new(self.iter().map(Cow::Borrowed))
+    }
+}
+
+impl DataSource for &[Input] {
+    fn pass(&mut self) -> Box<dyn Iterator<Item = Cow<'_, Input>> + '_> {
+        Box::new(self.iter().map(Cow::Borrowed))
+    }
+}
+
+/// Lets a closure that opens a new iterator be used as a data source,
+/// for example one that reopens a file and parses it line by line
+impl<F, I> DataSource for F
+where
+    F: FnMut() -> I,
+    I: Iterator<Item = Input> + 'static {
+    fn pass(&mut self) -> Box<dyn Iterator<Item = Cow<'_, Input>> + '_> {
+        Box::new(self().map(Cow::Owned))
+    }
+}
+
+impl From<Vec<Input>> for Dataset {
+    fn from(inputs: Vec<Input>) -> Self {
+        Dataset::new(inputs)
+    }
+}
+
+/// The euclidean distance between two lists of values
+pub(crate) fn distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum::<f32>().sqrt()
+}
@@ -0,0 +1,69 @@
+use rand::{SeedableRng, rngs::StdRng};
+use serde::{Serialize, Deserialize};
+use crate::{
+    activation::ActivationFunction,
+    categorize::CatNetwork,
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// The seed the sanity check's weights and shuffling come from, so every run trains the same way
+pub const SANITY_SEED: u64 = 42;
+/// How many epochs the sanity check gives XOR before calling it unconverged
+pub const SANITY_MAX_EPOCHS: usize = 5000;
+
+/// How the XOR sanity check went
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SanityReport {
+    /// Whether the network got all four XOR inputs right
+    pub converged: bool,
+    /// How many epochs it trained for
+    pub epochs: usize,
+    /// The mean squared error of the last epoch
+    pub loss: f32,
+    /// The fraction of XOR inputs categorized correctly by the trained network
+    pub accuracy: f32
+}
+
+/// Trains a tiny network on XOR with a fixed seed, to check that the build works before debugging your own data.
+/// Nothing is written to disk, and the same build always gives the same report.
+///
+/// If this doesn't converge, the problem is in the build, the feature flags or the library, not in your data.
+///
+/// ## Err
+/// - ### WriteDataFailed
+/// Only if an epoch couldn't be reported, which the sanity check's network never does
+///
+/// ## Examples
+/// ```
+/// use darjeeling::diagnostics;
+///
+/// let report = diagnostics::sanity_check().unwrap();
+/// assert!(report.converged, "{:?}", report);
+/// ```
+pub fn sanity_check() -> Result<SanityReport, DarjeelingError> {
+    let mut rng = StdRng::seed_from_u64(SANITY_SEED);
+    let mut data: Vec<Input> = xor();
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    net.randomize_weights(&mut rng);
+    net.start_training(vec![Types::Integer(0), Types::Integer(1)]);
+
+    let mut epochs: usize = 0;
+    let mut loss: f32 = 0.0;
+    let mut accuracy: f32 = 0.0;
+    while epochs < SANITY_MAX_EPOCHS && accuracy < 1.0 {
+        loss = net.step_epoch_with_rng(&mut data, 0.5, &mut rng)?.loss;
+        accuracy = net.evaluate(&data).metrics.accuracy;
+        epochs += 1;
+    }
+
+    Ok(SanityReport { converged: accuracy >= 1.0, epochs, loss, accuracy })
+}
+
+fn xor() -> Vec<Input> {
+    [(0.0, 0.0, 0), (0.0, 1.0, 1), (1.0, 0.0, 1), (1.0, 1.0, 0)]
+        .into_iter()
+        .map(|(a, b, answer)| Input::new(vec![a, b], Some(Types::Integer(answer))))
+        .collect()
+}
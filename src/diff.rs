@@ -0,0 +1,46 @@
+use core::fmt;
+use serde::{Serialize, Deserialize};
+
+/// How far one layer's weights moved between two networks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct LayerDiff {
+    /// The index of the layer, where 0 is the inputs
+    pub layer: usize,
+    /// The average absolute difference between matching weights, including the biases
+    pub mean_delta: f32,
+    /// The largest absolute difference between matching weights
+    pub max_delta: f32
+}
+
+/// How two networks with the same architecture differ, from [`diff`](fn@crate::categorize::CatNetwork::diff)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModelDiff {
+    /// Every layer after the inputs
+    pub layers: Vec<LayerDiff>,
+    /// The average absolute difference between matching answer node outputs on the probe data
+    pub mean_output_divergence: f32,
+    /// The largest absolute difference between matching answer node outputs on the probe data
+    pub max_output_divergence: f32,
+    /// The fraction of probe inputs where both networks chose the same answer node
+    pub agreement: f32
+}
+
+impl ModelDiff {
+
+    /// Whether no weight and no output moved by more than the tolerance,
+    /// for example to check that saving and reading a model didn't change it
+    pub fn within(&self, tolerance: f32) -> bool {
+        self.layers.iter().all(|layer| layer.max_delta <= tolerance) && self.max_output_divergence <= tolerance
+    }
+}
+
+impl fmt::Display for ModelDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:<8} {:>12} {:>12}", "Layer", "Mean Delta", "Max Delta")?;
+        for layer in &self.layers {
+            writeln!(f, "{:<8} {:>12.6} {:>12.6}", layer.layer, layer.mean_delta, layer.max_delta)?;
+        }
+        writeln!(f, "Output divergence: mean {:.6}, max {:.6}", self.mean_output_divergence, self.max_output_divergence)?;
+        write!(f, "Agreement: {:.4}", self.agreement)
+    }
+}
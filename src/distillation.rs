@@ -0,0 +1,46 @@
+use serde::{Serialize, Deserialize};
+
+/// How a small student network learns from a larger trained teacher, so the student can answer nearly as well with far fewer weights.
+/// The student is trained towards the teacher's softened answers rather than only the right ones,
+/// which also teaches it which wrong answers the teacher thought were close.
+///
+/// The teacher's answer node outputs are turned into a distribution with a softmax divided by the temperature.
+/// A temperature of 1 keeps the teacher's preferences as they are, and higher ones spread the distribution out so the close calls show more.
+///
+/// ## Examples
+/// ```ignore
+/// // Soft answers at a temperature of 2, mixed 70/30 with the right answers where the data has them
+/// let distillation = Distillation::new(2.0, 0.7, 50, 42);
+/// let mut student = CatNetwork::new(4, 4, 3, 1, ActivationFunction::Sigmoid);
+/// let report = student.distill(&teacher, &data, 0.5, &distillation).unwrap();
+/// println!("{} weights instead of {}, agreeing on {:.1}%", report.student_parameters, report.teacher_parameters, report.agreement * 100.0);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Distillation {
+    /// Softens the teacher's answers, above 0
+    pub temperature: f32,
+    /// How much the teacher's answers count against the data's own, from 0 to 1.
+    /// Inputs without an answer only ever learn from the teacher.
+    pub alpha: f32,
+    pub epochs: usize,
+    /// Decides the order inputs are trained in
+    pub seed: u64
+}
+
+impl Distillation {
+
+    pub fn new(temperature: f32, alpha: f32, epochs: usize, seed: u64) -> Distillation {
+        Distillation { temperature, alpha, epochs, seed }
+    }
+}
+
+/// How [distilling](Distillation) a teacher into a student went
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DistillationReport {
+    pub epochs: usize,
+    /// The fraction of the data the student and teacher put in the same category after training
+    pub agreement: f32,
+    /// The number of link and bias weights in the teacher
+    pub teacher_parameters: usize,
+    pub student_parameters: usize
+}
@@ -0,0 +1,168 @@
+use core::fmt;
+use serde::{Serialize, Deserialize};
+use crate::{input::Input, schema::{self, Schema}, types::Types};
+
+/// How many equally wide bins each feature's training range is split into.
+/// Values outside the range get a bin of their own on each side.
+const BINS: usize = 10;
+
+/// Keeps bins that are empty on one side from making the drift infinite
+const SMOOTHING: f32 = 1e-4;
+
+/// The distribution of one feature in the training data
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeatureProfile {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub std: f32,
+    /// The fraction of values below the range, in each bin across it, then above it
+    pub bins: Vec<f32>
+}
+
+impl FeatureProfile {
+
+    fn of(values: &[f32]) -> FeatureProfile {
+        let min: f32 = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let max: f32 = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let (mean, std) = crate::validation::mean_std(values);
+        let mut profile = FeatureProfile { min, max, mean, std, bins: vec![] };
+        profile.bins = profile.histogram(values);
+        profile
+    }
+
+    /// The fraction of the values in each of the training data's bins
+    fn histogram(&self, values: &[f32]) -> Vec<f32> {
+        let mut counts: Vec<usize> = vec![0; BINS + 2];
+        let width: f32 = (self.max - self.min) / BINS as f32;
+        values.iter().for_each(|value| {
+            let bin: usize = match value {
+                value if *value < self.min => 0,
+                value if *value > self.max => BINS + 1,
+                _ if width <= 0.0 => 1,
+                value => 1 + (((value - self.min) / width) as usize).min(BINS - 1)
+            };
+            counts[bin] += 1;
+        });
+        counts.into_iter().map(|count| count as f32 / values.len().max(1) as f32).collect()
+    }
+}
+
+/// What the data a network was trained on looked like, saved with the model so batches seen later can be checked for
+/// [drift](fn@crate::categorize::CatNetwork::detect_drift)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataProfile {
+    pub rows: usize,
+    pub features: Vec<FeatureProfile>,
+    /// The fraction of the training data the network answered with each category
+    pub predictions: Vec<(Types, f32)>
+}
+
+impl DataProfile {
+
+    /// ## Params
+    /// - Data: The inputs, all the same length
+    /// - Predictions: What the network answered each input with, in the same order
+    pub fn of(data: &[Input], predictions: &[Types]) -> DataProfile {
+        let width: usize = data.first().map(|input| input.inputs.len()).unwrap_or(0);
+        DataProfile {
+            rows: data.len(),
+            features: (0..width).map(|feature| FeatureProfile::of(&column(data, feature))).collect(),
+            predictions: shares(predictions)
+        }
+    }
+}
+
+/// How far one feature of a batch drifted from the training data
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeatureDrift {
+    pub feature: usize,
+    /// The feature's name from the network's [schema](Schema), or "feature i" if it doesn't have one
+    pub name: String,
+    /// The population stability index between the training data's bins and the batch's.
+    /// Under 0.1 is usually no real change, and over 0.25 a large one.
+    pub psi: f32,
+    /// How far the batch's mean moved, in training standard deviations
+    pub mean_shift: f32,
+    /// The fraction of the batch outside the training range
+    pub out_of_range: f32
+}
+
+/// How much a batch drifted from the data a network was trained on, feature by feature and in what the network answers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DriftReport {
+    pub rows: usize,
+    pub features: Vec<FeatureDrift>,
+    /// The population stability index between how often each category was predicted in training and in the batch
+    pub prediction_psi: f32
+}
+
+impl DriftReport {
+
+    pub(crate) fn compare(profile: &DataProfile, batch: &[Input], predictions: &[Types], schema: Option<&Schema>) -> DriftReport {
+        let features: Vec<FeatureDrift> = profile.features.iter().enumerate().map(|(feature, expected)| {
+            let values: Vec<f32> = column(batch, feature);
+            let actual: Vec<f32> = expected.histogram(&values);
+            let (mean, _) = crate::validation::mean_std(&values);
+            FeatureDrift {
+                feature,
+                name: schema::feature_name(schema, feature),
+                psi: psi(&expected.bins, &actual),
+                mean_shift: if expected.std > 0.0 { (mean - expected.mean) / expected.std } else { 0.0 },
+                out_of_range: actual[0] + actual[BINS + 1]
+            }
+        }).collect();
+
+        // Categories only seen on one side still count, as a share of 0 on the other
+        let actual: Vec<(Types, f32)> = shares(predictions);
+        let mut categories: Vec<&Types> = profile.predictions.iter().map(|(category, _)| category).collect();
+        for (category, _) in &actual {
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        let share = |shares: &[(Types, f32)], category: &Types| shares.iter().find(|(other, _)| other == category).map(|(_, share)| *share).unwrap_or(0.0);
+        let expected_shares: Vec<f32> = categories.iter().map(|category| share(&profile.predictions, category)).collect();
+        let actual_shares: Vec<f32> = categories.iter().map(|category| share(&actual, category)).collect();
+
+        DriftReport { rows: batch.len(), features, prediction_psi: psi(&expected_shares, &actual_shares) }
+    }
+
+    /// The features whose population stability index is over the threshold, most drifted first
+    pub fn drifted(&self, threshold: f32) -> Vec<&FeatureDrift> {
+        let mut drifted: Vec<&FeatureDrift> = self.features.iter().filter(|feature| feature.psi > threshold).collect();
+        drifted.sort_by(|a, b| b.psi.total_cmp(&a.psi));
+        drifted
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} rows, prediction PSI {:.4}", self.rows, self.prediction_psi)?;
+        for feature in &self.features {
+            writeln!(f, "{}: PSI {:.4}, mean shift {:.2} std, {:.1}% out of range", feature.name, feature.psi, feature.mean_shift, feature.out_of_range * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+fn column(data: &[Input], feature: usize) -> Vec<f32> {
+    data.iter().filter_map(|input| input.inputs.get(feature).copied()).collect()
+}
+
+/// Every distinct value with the fraction of the list it makes up, in the order they first appear
+fn shares(values: &[Types]) -> Vec<(Types, f32)> {
+    let mut counts: Vec<(Types, usize)> = vec![];
+    values.iter().for_each(|value| match counts.iter_mut().find(|(other, _)| other == value) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((value.clone(), 1))
+    });
+    counts.into_iter().map(|(value, count)| (value, count as f32 / values.len() as f32)).collect()
+}
+
+fn psi(expected: &[f32], actual: &[f32]) -> f32 {
+    expected.iter().zip(actual).map(|(expected, actual)| {
+        let (expected, actual) = (expected.max(SMOOTHING), actual.max(SMOOTHING));
+        (actual - expected) * (actual / expected).ln()
+    }).sum()
+}
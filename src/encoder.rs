@@ -0,0 +1,205 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// How an encoder turns a category into sensor values
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Encoding {
+    /// One sensor per category, 1.0 for the matching category and 0.0 for the rest
+    OneHot,
+    /// A single sensor holding the index of the category
+    Label,
+    /// The hashing trick. Each category is hashed to one of a fixed number of sensors, so a column with millions of categories
+    /// still fits in a few hundred sensors, and categories never seen in training still encode. Categories that hash to the same sensor share it.
+    /// No vocabulary is kept, so hashed values can't be decoded.
+    Hashed {
+        /// The number of sensors, at least 1
+        dimension: usize,
+        /// Whether the sensor is set to 1.0 or -1.0 by another bit of the hash, so collisions tend to cancel out instead of adding up
+        signed: bool
+    }
+}
+
+/// Maps the categories of one column to numbers, using the vocabulary it was fit on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Encoder {
+    encoding: Encoding,
+    vocabulary: Vec<Types>
+}
+
+impl Encoder {
+
+    /// Learns the vocabulary of a column.
+    /// Categories are numbered in the order they first appear
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{encoder::{Encoder, Encoding}, types::Types};
+    ///
+    /// let colors = vec![Types::String("red".to_string()), Types::String("blue".to_string()), Types::String("red".to_string())];
+    /// let encoder = Encoder::fit(&colors, Encoding::OneHot);
+    /// assert_eq!(encoder.encode(&Types::String("blue".to_string())).unwrap(), vec![0.0, 1.0]);
+    /// assert!(encoder.encode(&Types::String("green".to_string())).is_err());
+    /// ```
+    pub fn fit(column: &[Types], encoding: Encoding) -> Encoder {
+        let mut vocabulary: Vec<Types> = vec![];
+        if let Encoding::Hashed { .. } = encoding {
+            return Encoder { encoding, vocabulary };
+        }
+        column.iter().for_each(|value| {
+            if !vocabulary.contains(value) {
+                vocabulary.push(value.clone());
+            }
+        });
+
+        Encoder { encoding, vocabulary }
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn vocabulary(&self) -> &[Types] {
+        &self.vocabulary
+    }
+
+    /// The number of sensors an encoded value takes up
+    pub fn width(&self) -> usize {
+        match self.encoding {
+            Encoding::OneHot => self.vocabulary.len(),
+            Encoding::Label => 1,
+            Encoding::Hashed { dimension, .. } => dimension.max(1),
+        }
+    }
+
+    /// Encodes a single category
+    ///
+    /// ## Err
+    /// - ### UnknownCategory
+    /// The category wasn't in the data the encoder was fit on. Hashed encoders encode every category.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{encoder::{Encoder, Encoding}, types::Types};
+    ///
+    /// let encoder = Encoder::fit(&[], Encoding::Hashed { dimension: 8, signed: true });
+    /// let encoded = encoder.encode(&Types::String("never seen".to_string())).unwrap();
+    /// assert_eq!(encoded.len(), 8);
+    /// assert_eq!(encoded.iter().map(|value| value.abs()).sum::<f32>(), 1.0);
+    /// ```
+    pub fn encode(&self, value: &Types) -> Result<Vec<f32>, DarjeelingError> {
+        Ok(match self.encoding {
+            Encoding::OneHot => {
+                let index: usize = self.index(value)?;
+                (0..self.vocabulary.len()).map(|i| if i == index { 1.0 } else { 0.0 }).collect()
+            },
+            Encoding::Label => vec![self.index(value)? as f32],
+            Encoding::Hashed { dimension, signed } => {
+                let hash: u64 = fnv1a(value.to_string().as_bytes());
+                let mut encoded: Vec<f32> = vec![0.0; dimension.max(1)];
+                encoded[(hash % dimension.max(1) as u64) as usize] = if signed && hash >> 63 == 1 { -1.0 } else { 1.0 };
+                encoded
+            },
+        })
+    }
+
+    /// The index of a category in the vocabulary
+    fn index(&self, value: &Types) -> Result<usize, DarjeelingError> {
+        match self.vocabulary.iter().position(|category| category == value) {
+            Some(index) => Ok(index),
+            None => Err(DarjeelingError::UnknownCategory(format!("{:?}", value)))
+        }
+    }
+
+    /// Turns encoded values back into the category they represent.
+    /// One-hot values decode to their largest sensor, label values to the nearest index, and hashed values to None.
+    pub fn decode(&self, encoded: &[f32]) -> Option<Types> {
+        let index: usize = match self.encoding {
+            Encoding::OneHot => {
+                let mut largest = 0;
+                (0..encoded.len()).for_each(|i| if encoded[i] > encoded[largest] { largest = i });
+                largest
+            },
+            Encoding::Label => encoded.first()?.round().max(0.0) as usize,
+            Encoding::Hashed { .. } => return None,
+        };
+
+        self.vocabulary.get(index).cloned()
+    }
+}
+
+/// Encodes a row of typed values into an Input.
+/// Each column uses the encoder at the same index, and columns without an encoder are passed through as numbers.
+///
+/// ## Err
+/// - ### UnknownCategory
+/// A category wasn't in the data its encoder was fit on
+/// - ### UnencodedColumn
+/// A column holding a string doesn't have an encoder
+pub fn encode_row(encoders: &[Option<Encoder>], row: &[Types], answer: Option<Types>) -> Result<Input, DarjeelingError> {
+    let mut inputs: Vec<f32> = vec![];
+    for (i, value) in row.iter().enumerate() {
+        match encoders.get(i) {
+            Some(Some(encoder)) => inputs.append(&mut encoder.encode(value)?),
+            _ => match value.as_f32() {
+                Some(number) => inputs.push(number),
+                None => return Err(DarjeelingError::UnencodedColumn(format!("Column {} holds {:?}", i, value)))
+            }
+        }
+    }
+
+    Ok(Input::new(inputs, answer))
+}
+
+/// Picks an encoder for every column of typed rows, fit on the values in it.
+/// Columns holding a string or a category get an encoder, while integers, floats and booleans are passed through as numbers.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{encoder::{self, Encoding}, types::Types};
+///
+/// let rows = vec![
+///     vec![Types::Integer(34), Types::String("red".to_string()), Types::Boolean(true)],
+///     vec![Types::Integer(51), Types::String("blue".to_string()), Types::Boolean(false)]
+/// ];
+/// let encoders = encoder::fit_columns(&rows, Encoding::OneHot);
+/// assert!(encoders[0].is_none() && encoders[2].is_none());
+/// let inputs = encoder::encode_rows(&encoders, &rows, None).unwrap();
+/// assert_eq!(inputs[1].inputs, vec![51.0, 0.0, 1.0, 0.0]);
+/// ```
+pub fn fit_columns(rows: &[Vec<Types>], encoding: Encoding) -> Vec<Option<Encoder>> {
+    let width: usize = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    (0..width).map(|i| {
+        let column: Vec<Types> = rows.iter().filter_map(|row| row.get(i).cloned()).collect();
+        match column.iter().any(|value| matches!(value, Types::String(_) | Types::Category { .. })) {
+            true => Some(Encoder::fit(&column, encoding)),
+            false => None
+        }
+    }).collect()
+}
+
+/// Encodes every row the same way as [`encode_row`](fn@encode_row)
+///
+/// ## Params
+/// - Answers: The answer of every row, in the same order, or None if the rows don't have them
+///
+/// ## Err
+/// The same as [`encode_row`](fn@encode_row), along with
+/// - ### InvalidInput
+/// There isn't one answer for every row
+pub fn encode_rows(encoders: &[Option<Encoder>], rows: &[Vec<Types>], answers: Option<&[Types]>) -> Result<Vec<Input>, DarjeelingError> {
+    if let Some(answers) = answers {
+        if answers.len() != rows.len() {
+            return Err(DarjeelingError::InvalidInput(format!("There are {} answers for {} rows", answers.len(), rows.len())));
+        }
+    }
+    rows.iter().enumerate().map(|(i, row)| encode_row(encoders, row, answers.map(|answers| answers[i].clone()))).collect()
+}
+
+/// The 64 bit FNV-1a hash, which is the same on every platform and version, unlike the standard library's hasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash: u64, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
@@ -0,0 +1,156 @@
+use rand::{Rng, thread_rng};
+use crate::{cipher, error::DarjeelingError, hash};
+
+/// Starts the first line of an encrypted model, followed by the format version
+pub const ENCRYPTED_HEADER: &str = "darj-encrypted 1";
+
+/// Follows the header on the first line of a model encrypted with a passphrase, then the iteration count and the salt
+pub const PASSPHRASE_HEADER: &str = "pbkdf2-sha256";
+
+/// How many PBKDF2 iterations [`from_passphrase`](fn@ModelCipher::from_passphrase) uses, as OWASP recommends for PBKDF2-HMAC-SHA256
+pub const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Encrypts serialized models with a key only the caller has, so models trained on private data can be shipped without exposing their weights.
+///
+/// Models are encrypted with ChaCha20 under a new random nonce every time, then authenticated with HMAC-SHA256,
+/// so a model that was changed, or encrypted with another key, is refused instead of loading as garbage.
+/// The encryption and authentication keys are both derived from the caller's key.
+/// A passphrase is stretched into a key with PBKDF2-HMAC-SHA256 under a new random salt every time, which goes in the header with the iteration count.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::encryption::ModelCipher;
+///
+/// let cipher = ModelCipher::new([7; 32]);
+/// let encrypted: String = cipher.encrypt("0.5;0.1\nlb\nsigmoid");
+/// assert!(!encrypted.contains("sigmoid"));
+/// assert_eq!(cipher.decrypt(&encrypted).unwrap(), "0.5;0.1\nlb\nsigmoid");
+/// assert!(ModelCipher::new([8; 32]).decrypt(&encrypted).is_err());
+/// ```
+#[derive(Clone)]
+pub struct ModelCipher {
+    secret: Secret
+}
+
+#[derive(Clone)]
+enum Secret {
+    Key(Keys),
+    Passphrase { passphrase: String, iterations: u32 }
+}
+
+#[derive(Clone)]
+struct Keys {
+    encryption: [u8; 32],
+    authentication: [u8; 32]
+}
+
+impl Keys {
+
+    fn derive(key: [u8; 32]) -> Keys {
+        Keys {
+            encryption: hash::hmac_sha256(&key, b"darjeeling model encryption"),
+            authentication: hash::hmac_sha256(&key, b"darjeeling model authentication")
+        }
+    }
+
+    /// Authenticates the nonce along with the encrypted model
+    fn tag(&self, nonce: &[u8; 12], encrypted: &[u8]) -> [u8; 32] {
+        let message: Vec<u8> = nonce.iter().chain(encrypted).copied().collect();
+        hash::hmac_sha256(&self.authentication, &message)
+    }
+}
+
+impl ModelCipher {
+
+    /// ## Params
+    /// - Key: 32 secret bytes, best made by a cryptographically secure random number generator
+    pub fn new(key: [u8; 32]) -> ModelCipher {
+        ModelCipher { secret: Secret::Key(Keys::derive(key)) }
+    }
+
+    /// A cipher whose key is stretched from a passphrase with [`PBKDF2_ITERATIONS`](constant@PBKDF2_ITERATIONS) of PBKDF2-HMAC-SHA256.
+    /// Passphrases are much easier to guess than random keys, so only use a long one.
+    /// Every encryption and decryption stretches the passphrase again, which takes about a second on purpose.
+    pub fn from_passphrase(passphrase: &str) -> ModelCipher {
+        ModelCipher { secret: Secret::Passphrase { passphrase: passphrase.to_string(), iterations: PBKDF2_ITERATIONS } }
+    }
+
+    /// Encrypts with a different number of PBKDF2 iterations, if the cipher is [made from a passphrase](fn@ModelCipher::from_passphrase).
+    /// Decrypting always uses the number in the model's header.
+    /// More iterations make a passphrase slower to guess, and slower to use.
+    pub fn with_iterations(mut self, iterations: u32) -> ModelCipher {
+        if let Secret::Passphrase { iterations: current, .. } = &mut self.secret {
+            *current = iterations.max(1);
+        }
+        self
+    }
+
+    /// Encrypts a serialized model
+    ///
+    /// ## Returns
+    /// The header line, then the nonce, the encrypted model and the authentication tag in hexadecimal, each on their own line.
+    /// With a passphrase, the header line ends with [`PASSPHRASE_HEADER`](constant@PASSPHRASE_HEADER), the iteration count and the salt in hexadecimal.
+    pub fn encrypt(&self, serialized: &str) -> String {
+        let mut rng = thread_rng();
+        let (header, keys) = match &self.secret {
+            Secret::Key(keys) => (ENCRYPTED_HEADER.to_string(), keys.clone()),
+            Secret::Passphrase { passphrase, iterations } => {
+                let salt: [u8; 16] = rng.gen();
+                let header: String = format!("{} {} {} {}", ENCRYPTED_HEADER, PASSPHRASE_HEADER, iterations, hash::to_hex(&salt));
+                (header, Keys::derive(hash::pbkdf2_sha256(passphrase.as_bytes(), &salt, *iterations)))
+            }
+        };
+        let nonce: [u8; 12] = rng.gen();
+        let encrypted: Vec<u8> = cipher::chacha20(&keys.encryption, &nonce, serialized.as_bytes());
+        let tag: [u8; 32] = keys.tag(&nonce, &encrypted);
+        format!("{}\n{}\n{}\n{}", header, hash::to_hex(&nonce), hash::to_hex(&encrypted), hash::to_hex(&tag))
+    }
+
+    /// Decrypts a model encrypted by [`encrypt`](fn@ModelCipher::encrypt)
+    ///
+    /// ## Err
+    /// - ### DecryptionFailed
+    /// The model isn't encrypted, was encrypted with another key or passphrase, or was changed after it was encrypted
+    pub fn decrypt(&self, encrypted: &str) -> Result<String, DarjeelingError> {
+        let lines: Vec<&str> = encrypted.trim_end().lines().collect();
+        if lines.len() != 4 || !lines[0].starts_with(ENCRYPTED_HEADER) {
+            return Err(DarjeelingError::DecryptionFailed("The model isn't encrypted".to_string()));
+        }
+        let keys: Keys = self.keys(&lines[0][ENCRYPTED_HEADER.len()..])?;
+        let (nonce, body, tag) = match (hash::from_hex(lines[1]), hash::from_hex(lines[2]), hash::from_hex(lines[3])) {
+            (Some(nonce), Some(body), Some(tag)) if nonce.len() == 12 => (nonce, body, tag),
+            _ => return Err(DarjeelingError::DecryptionFailed("The encrypted model is malformed".to_string()))
+        };
+        let mut nonce_bytes: [u8; 12] = [0; 12];
+        nonce_bytes.copy_from_slice(&nonce);
+        if !hash::constant_time_eq(&keys.tag(&nonce_bytes, &body), &tag) {
+            return Err(DarjeelingError::DecryptionFailed("The key is wrong, or the model was changed after it was encrypted".to_string()));
+        }
+        match String::from_utf8(cipher::chacha20(&keys.encryption, &nonce_bytes, &body)) {
+            Ok(serialized) => Ok(serialized),
+            Err(error) => Err(DarjeelingError::DecryptionFailed(error.to_string()))
+        }
+    }
+
+    /// The keys a model was encrypted with, from what follows the header on its first line
+    fn keys(&self, header: &str) -> Result<Keys, DarjeelingError> {
+        let fields: Vec<&str> = header.split_whitespace().collect();
+        match (&self.secret, fields.as_slice()) {
+            (Secret::Key(keys), []) => Ok(keys.clone()),
+            (Secret::Passphrase { passphrase, .. }, [PASSPHRASE_HEADER, iterations, salt]) => match (iterations.parse::<u32>(), hash::from_hex(salt)) {
+                (Ok(iterations), Some(salt)) if iterations > 0 => Ok(Keys::derive(hash::pbkdf2_sha256(passphrase.as_bytes(), &salt, iterations))),
+                _ => Err(DarjeelingError::DecryptionFailed("The encrypted model's salt or iteration count is malformed".to_string()))
+            },
+            (Secret::Key(_), [PASSPHRASE_HEADER, ..]) => Err(DarjeelingError::DecryptionFailed("The model was encrypted with a passphrase, not a key".to_string())),
+            (Secret::Passphrase { .. }, []) => Err(DarjeelingError::DecryptionFailed("The model was encrypted with a key, not a passphrase".to_string())),
+            _ => Err(DarjeelingError::DecryptionFailed("The encrypted model's header is malformed".to_string()))
+        }
+    }
+}
+
+impl std::fmt::Debug for ModelCipher {
+    /// Leaves the keys and passphrase out, so they don't end up in logs
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ModelCipher").finish_non_exhaustive()
+    }
+}
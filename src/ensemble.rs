@@ -0,0 +1,95 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    categorize::CatNetwork,
+    error::DarjeelingError,
+    input::Input,
+    metrics::ClassificationMetrics,
+    prediction::{Prediction, TieBreak},
+    types::Types
+};
+
+/// A committee of categorization networks with the same categories, which predict together by averaging their answer node outputs.
+/// Usually made by [snapshot ensembling](fn@crate::categorize::CatNetwork::learn_snapshot_ensemble),
+/// where one training run saves a model at the end of every learning rate cycle.
+///
+/// ## Examples
+/// ```ignore
+/// let ensemble: Ensemble = net.learn_snapshot_ensemble(&mut data, categories, 1.0, 5, 40).unwrap();
+/// let prediction = ensemble.predict(&[0.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "EnsembleMembers")]
+pub struct Ensemble {
+    members: Vec<CatNetwork>
+}
+
+/// An ensemble as it's read, before it's checked the way [`Ensemble::new`] checks one
+#[derive(Deserialize)]
+struct EnsembleMembers {
+    members: Vec<CatNetwork>
+}
+
+impl TryFrom<EnsembleMembers> for Ensemble {
+    type Error = DarjeelingError;
+
+    fn try_from(read: EnsembleMembers) -> Result<Ensemble, DarjeelingError> {
+        Ensemble::new(read.members)
+    }
+}
+
+impl Ensemble {
+
+    /// ## Err
+    /// - ### InvalidInput
+    /// There are no members, their categories aren't set, or they don't all have the same inputs and categories
+    pub fn new(members: Vec<CatNetwork>) -> Result<Ensemble, DarjeelingError> {
+        let first: &CatNetwork = match members.first() {
+            Some(first) => first,
+            None => return Err(DarjeelingError::InvalidInput("An ensemble needs at least one member".to_string()))
+        };
+        if first.categories().is_none() {
+            return Err(DarjeelingError::InvalidInput("Ensemble members need their categories set".to_string()));
+        }
+        if members.iter().any(|member| member.input_size() != first.input_size() || member.categories() != first.categories()) {
+            return Err(DarjeelingError::InvalidInput("Ensemble members need the same inputs and categories".to_string()));
+        }
+        Ok(Ensemble { members })
+    }
+
+    pub fn members(&self) -> &[CatNetwork] {
+        &self.members
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Averages every member's answer node outputs, and picks the category with the largest average.
+    /// The confidence is that largest average.
+    pub fn predict(&self, inputs: &[f32]) -> Prediction {
+        let predictions: Vec<Prediction> = self.members.iter().map(|member| member.predict(inputs)).collect();
+        let categories: Vec<Types> = predictions[0].scores.iter().map(|(category, _)| category.clone()).collect();
+        let averages: Vec<f32> = (0..categories.len())
+            .map(|i| predictions.iter().map(|prediction| prediction.scores[i].1).sum::<f32>() / predictions.len() as f32)
+            .collect();
+        let (largest, tied) = TieBreak::First.choose(&averages).expect("Only the error policy fails");
+        Prediction {
+            category: categories[largest].clone(),
+            confidence: averages[largest].clamp(0.0, 1.0),
+            tied: tied.iter().map(|i| categories[*i].clone()).collect(),
+            scores: categories.into_iter().zip(averages).collect()
+        }
+    }
+
+    /// How well the ensemble's predictions match the answers. Inputs without an answer are skipped.
+    pub fn evaluate(&self, data: &[Input]) -> ClassificationMetrics {
+        let answered: Vec<&Input> = data.iter().filter(|input| input.answer.is_some()).collect();
+        let predictions: Vec<Types> = answered.iter().map(|input| self.predict(&input.inputs).category).collect();
+        let truth: Vec<Types> = answered.iter().filter_map(|input| input.answer.clone()).collect();
+        ClassificationMetrics::compute(&predictions, &truth)
+    }
+}
@@ -20,7 +20,24 @@ pub enum DarjeelingError {
     SelfAnalysisStringConversion(String),
     RemoveModelFailed(String),
     ActivationFunctionNotRead(String),
+    UnknownActivationFunction(String),
     InvalidNodeValueRead(String),
+    /// A line of a .darj model couldn't be read. Lines and columns count from 1.
+    ModelParse { line: usize, column: usize, token: String, expected: String },
+    ReadDataFailed(String),
+    WriteDataFailed(String),
+    InvalidIdxFormat(String),
+    InvalidMetadataRead(String),
+    UnknownCategory(String),
+    UnencodedColumn(String),
+    InvalidTrainingData(String),
+    ArchitectureMismatch(String),
+    InvalidArchitecture(String),
+    InvalidInput(String),
+    ExplodingGradient(String),
+    InvalidSignature(String),
+    DecryptionFailed(String),
+    TiedPrediction(String),
 
     UnknownError(String)
 }
@@ -72,10 +89,74 @@ impl<'a> fmt::Display for DarjeelingError {
                 "Tried to read a .darj file without a valid activation function. Error message: {}",
                 err
             ),
+            DarjeelingError::UnknownActivationFunction(err) => write!(f,
+                "Unknown activation function {}",
+                err
+            ),
             DarjeelingError::InvalidNodeValueRead(err) => write!(f,
                 "Tried to parse a value from a .darj file that wasn't a valid f32. Error message: {}",
                 err
             ),
+            DarjeelingError::ModelParse { line, column, token, expected } => write!(f,
+                "Couldn't read the model at line {}, column {}: found {:?}, expected {}",
+                line, column, token, expected
+            ),
+            DarjeelingError::ReadDataFailed(err) => write!(f,
+                "Unable to read data file. Error message: {}",
+                err
+            ),
+            DarjeelingError::WriteDataFailed(err) => write!(f,
+                "Unable to write data file. Error message: {}",
+                err
+            ),
+            DarjeelingError::InvalidIdxFormat(err) => write!(f,
+                "Tried to read a file that isn't a valid IDX file. Error message: {}",
+                err
+            ),
+            DarjeelingError::InvalidMetadataRead(err) => write!(f,
+                "Tried to read model metadata from a .darj file that wasn't valid. Error message: {}",
+                err
+            ),
+            DarjeelingError::UnknownCategory(category) => write!(f,
+                "Tried to encode the category {}, which wasn't in the data the encoder was fit on",
+                category
+            ),
+            DarjeelingError::UnencodedColumn(err) => write!(f,
+                "Tried to pass a non-numeric value to a sensor without an encoder. Error message: {}",
+                err
+            ),
+            DarjeelingError::InvalidTrainingData(err) => write!(f,
+                "The training data can't be used this way. Error message: {}",
+                err
+            ),
+            DarjeelingError::ArchitectureMismatch(err) => write!(f,
+                "The networks don't have the same architecture. Error message: {}",
+                err
+            ),
+            DarjeelingError::InvalidArchitecture(err) => write!(f,
+                "The model's layers don't fit together. Error message: {}",
+                err
+            ),
+            DarjeelingError::InvalidInput(err) => write!(f,
+                "The input can't be used by this model. Error message: {}",
+                err
+            ),
+            DarjeelingError::ExplodingGradient(err) => write!(f,
+                "Training was stopped because the gradients grew too large. Error message: {}",
+                err
+            ),
+            DarjeelingError::InvalidSignature(err) => write!(f,
+                "The model's signature couldn't be verified, so it wasn't loaded. Error message: {}",
+                err
+            ),
+            DarjeelingError::TiedPrediction(err) => write!(f,
+                "More than one category is equally likely. Error message: {}",
+                err
+            ),
+            DarjeelingError::DecryptionFailed(err) => write!(f,
+                "The model couldn't be decrypted. Error message: {}",
+                err
+            ),
             DarjeelingError::ColumnAlreadyExists(label) => write!(f,
                 "Attempted to add a column labeled: {}, that already exist in the dataframe",
                 label
@@ -0,0 +1,263 @@
+use std::{fs, io::Write, path::{Path, PathBuf}};
+use rand::{SeedableRng, rngs::StdRng};
+use serde::{Serialize, Deserialize};
+use crate::{
+    cache::DatasetCache,
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    csv,
+    dataset::Dataset,
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// A training run described in a JSON file, so it can be version controlled and replayed exactly.
+/// The seed decides the starting weights and the order the data is shuffled in each epoch,
+/// so running the same experiment on the same data always trains the same model.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::experiment::Experiment;
+///
+/// let experiment = Experiment::from_json_str(r#"{
+///     "name": "xor",
+///     "data": "xor.csv",
+///     "label_column": 2,
+///     "seed": 42,
+///     "config": {
+///         "hidden_num": 4,
+///         "hidden_layers": 1,
+///         "activation_function": "Sigmoid",
+///         "learning_rate": 0.5,
+///         "target_err_percent": 99.0
+///     }
+/// }"#).unwrap();
+/// assert_eq!(experiment.config.hidden_num, 4);
+/// assert!(experiment.config.write);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Experiment {
+    /// The model name
+    pub name: String,
+    /// The CSV file holding the training data. When read from a file, a relative path is relative to that file.
+    pub data: PathBuf,
+    /// Whether the CSV file's first line is column names
+    #[serde(default)]
+    pub has_header: bool,
+    /// The column holding each row's answer. None uses the last column.
+    #[serde(default)]
+    pub label_column: Option<usize>,
+    /// The categories the rows can be put in. Empty uses every answer in the data, in the order they first appear.
+    #[serde(default)]
+    pub categories: Vec<Types>,
+    pub seed: u64,
+    /// Stops training after this many epochs, even if the target error percent hasn't been reached
+    #[serde(default)]
+    pub max_epochs: Option<usize>,
+    /// Where to make a directory for every run of the experiment, holding everything about it, so runs document themselves.
+    /// None doesn't make one. When read from a file, a relative path is relative to that file.
+    ///
+    /// Each run gets the first of `name-1`, `name-2`, ... that isn't taken, holding
+    /// - `config.json`: The experiment
+    /// - `train.log`: A line for every epoch, written as it finishes
+    /// - `history.csv`: The [training history](crate::history::TrainingHistory)
+    /// - `checkpoints/`: The model every [few epochs](Experiment::checkpoint_every)
+    /// - `model.darj`: The final model
+    #[serde(default)]
+    pub runs_dir: Option<PathBuf>,
+    /// How many epochs go by between saving the model to the run directory's checkpoints. None never does.
+    #[serde(default)]
+    pub checkpoint_every: Option<usize>,
+    /// Where to [cache](DatasetCache) the parsed data, so running the experiment again on an unchanged file skips parsing it.
+    /// None parses it every time. When read from a file, a relative path is relative to that file.
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+    pub config: TrainingConfig
+}
+
+/// What running an experiment made
+#[derive(Debug, Clone)]
+pub struct ExperimentResult {
+    pub net: CatNetwork,
+    /// The file the model was saved to, if the config writes it
+    pub model_name: Option<String>,
+    /// The percent of the training data categorized correctly in the last epoch
+    pub err_percent: f32,
+    pub epochs: usize,
+    /// The directory made for the run, if the experiment [makes one](Experiment::runs_dir)
+    pub run_dir: Option<PathBuf>
+}
+
+impl Experiment {
+
+    /// ## Err
+    /// - ### InvalidInput
+    /// The JSON isn't an experiment
+    pub fn from_json_str(serialized: &str) -> Result<Experiment, DarjeelingError> {
+        match serde_json::from_str(serialized) {
+            Ok(experiment) => Ok(experiment),
+            Err(error) => Err(DarjeelingError::InvalidInput(format!("Not a valid experiment: {}", error)))
+        }
+    }
+
+    /// ## Err
+    /// - ### InvalidInput
+    /// The experiment couldn't be serialized, which only happens if its categories can't be
+    pub fn to_json_string(&self) -> Result<String, DarjeelingError> {
+        match serde_json::to_string_pretty(self) {
+            Ok(serialized) => Ok(serialized),
+            Err(error) => Err(DarjeelingError::InvalidInput(error.to_string()))
+        }
+    }
+
+    /// Reads an experiment from a JSON file
+    ///
+    /// ## Err
+    /// - ### ReadDataFailed
+    /// The file couldn't be read
+    /// - ### InvalidInput
+    /// The file isn't an experiment
+    pub fn read(path: &str) -> Result<Experiment, DarjeelingError> {
+        let serialized: String = match fs::read_to_string(path) {
+            Ok(serialized) => serialized,
+            Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+        };
+        let mut experiment: Experiment = Experiment::from_json_str(&serialized)?;
+        if let Some(dir) = Path::new(path).parent() {
+            if experiment.data.is_relative() {
+                experiment.data = dir.join(&experiment.data);
+            }
+            // The data can be somewhere else entirely, but runs and the cache still go next to the experiment
+            if let Some(runs_dir) = experiment.runs_dir.as_mut().filter(|runs_dir| runs_dir.is_relative()) {
+                *runs_dir = dir.join(&runs_dir);
+            }
+            if let Some(cache_dir) = experiment.cache_dir.as_mut().filter(|cache_dir| cache_dir.is_relative()) {
+                *cache_dir = dir.join(&cache_dir);
+            }
+        }
+        Ok(experiment)
+    }
+
+    /// Writes the experiment to a JSON file
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The file couldn't be written
+    pub fn write(&self, path: &str) -> Result<(), DarjeelingError> {
+        match fs::write(path, self.to_json_string()?) {
+            Ok(()) => Ok(()),
+            Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", path, error)))
+        }
+    }
+
+    /// Reads the data and trains a new network the way the experiment describes
+    ///
+    /// ## Err
+    /// - ### ReadDataFailed
+    /// The data couldn't be read
+    /// - ### InvalidTrainingData
+    /// The data is empty or has no answers
+    /// - ### WriteDataFailed
+    /// The [run directory](Experiment::runs_dir) or something in it, or the [cached data](Experiment::cache_dir), couldn't be written
+    /// - The rest are the same as [`CatNetwork::write_model`](fn@CatNetwork::write_model)
+    pub fn run(&self) -> Result<ExperimentResult, DarjeelingError> {
+        let text: String = match fs::read_to_string(&self.data) {
+            Ok(text) => text,
+            Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", self.data.display(), error)))
+        };
+        let label_column: usize = self.label_column.unwrap_or(csv::column_count(&text).saturating_sub(1));
+        let parse = || match csv::parse_csv(&text, self.has_header, Some(label_column)) {
+            Err(DarjeelingError::ReadDataFailed(error)) => Err(DarjeelingError::ReadDataFailed(format!("{}: {}", self.data.display(), error))),
+            parsed => parsed
+        };
+        let mut data: Vec<Input> = match &self.cache_dir {
+            Some(cache_dir) => {
+                let key: String = DatasetCache::key(text.as_bytes(), &format!("csv header {} label column {:?}", self.has_header, Some(label_column)));
+                DatasetCache::new(cache_dir).get_or_insert_with(&key, parse)?
+            },
+            None => parse()?
+        };
+        self.run_on(&mut data)
+    }
+
+    /// Trains a new network the way the experiment describes, on data that's already been read
+    ///
+    /// ## Err
+    /// The same as [`run`](fn@Experiment::run), besides reading the data
+    pub fn run_on(&self, data: &mut [Input]) -> Result<ExperimentResult, DarjeelingError> {
+        let categories: Vec<Types> = if self.categories.is_empty() { Dataset::new(data.to_vec()).categories() } else { self.categories.clone() };
+        if data.is_empty() || categories.is_empty() {
+            return Err(DarjeelingError::InvalidTrainingData(format!("Experiment {} has no answered data to train on", self.name)));
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut net: CatNetwork = CatNetwork::from_config(data[0].inputs.len() as i32, categories.len() as i32, &self.config);
+        net.randomize_weights(&mut rng);
+        net.start_training(categories);
+        let run_dir: Option<PathBuf> = match &self.runs_dir {
+            Some(runs_dir) => Some(self.start_run(runs_dir)?),
+            None => None
+        };
+
+        let mut err_percent: f32 = 0.0;
+        let mut epochs: usize = 0;
+        while err_percent < self.config.target_err_percent && self.max_epochs.map_or(true, |max_epochs| epochs < max_epochs) {
+            let record = net.step_epoch_with_rng(data, self.config.learning_rate, &mut rng)?;
+            err_percent = record.accuracy * 100.0;
+            epochs += 1;
+            if let Some(run_dir) = &run_dir {
+                append(&run_dir.join("train.log"), &format!("epoch {}: loss {}, accuracy {}, learning rate {}\n", record.epoch, record.loss, record.accuracy, record.learning_rate))?;
+                if matches!(self.checkpoint_every, Some(every) if every > 0 && epochs % every == 0) {
+                    write(&run_dir.join("checkpoints").join(format!("epoch-{}.darj", epochs)), &net.to_darj_string()?)?;
+                }
+            }
+        }
+        if let Some(run_dir) = &run_dir {
+            append(&run_dir.join("train.log"), &format!("finished after {} epochs at {}% accuracy\n", epochs, err_percent))?;
+            write(&run_dir.join("history.csv"), &net.history().csv())?;
+            write(&run_dir.join("model.darj"), &net.to_darj_string()?)?;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let model_name: Option<String> = if self.config.write {
+            net.set_dirs(&self.config);
+            Some(net.write_model(&self.name)?)
+        } else {
+            None
+        };
+        #[cfg(target_arch = "wasm32")]
+        let model_name: Option<String> = if self.config.write {
+            return Err(DarjeelingError::WriteModelFailed(format!("{}: There's no filesystem on wasm, save to_darj_string instead", self.name)));
+        } else {
+            None
+        };
+        Ok(ExperimentResult { net, model_name, err_percent, epochs, run_dir })
+    }
+
+    /// Makes the next free run directory with its checkpoints directory, and saves the experiment in it
+    fn start_run(&self, runs_dir: &Path) -> Result<PathBuf, DarjeelingError> {
+        let run_dir: PathBuf = (1..).map(|run| runs_dir.join(format!("{}-{}", self.name, run))).find(|run_dir| !run_dir.exists()).expect("Some run number is free");
+        if let Err(error) = fs::create_dir_all(run_dir.join("checkpoints")) {
+            return Err(DarjeelingError::WriteDataFailed(format!("{}: {}", run_dir.display(), error)));
+        }
+        write(&run_dir.join("config.json"), &self.to_json_string()?)?;
+        write(&run_dir.join("train.log"), &format!("experiment {} with seed {}\n", self.name, self.seed))?;
+        Ok(run_dir)
+    }
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), DarjeelingError> {
+    match fs::write(path, contents) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", path.display(), error)))
+    }
+}
+
+fn append(path: &Path, line: &str) -> Result<(), DarjeelingError> {
+    let appended = fs::OpenOptions::new().append(true).open(path).and_then(|mut file| file.write_all(line.as_bytes()));
+    match appended {
+        Ok(()) => Ok(()),
+        Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", path.display(), error)))
+    }
+}
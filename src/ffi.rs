@@ -0,0 +1,118 @@
+//! A C interface for embedding categorization networks in C, C++, Swift and anything else that can call C.
+//! The matching header is `include/darjeeling.h`.
+//!
+//! Build a shared or static library with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+//!
+//! Networks are handed out as opaque pointers, which have to be given back to [`darj_free`] exactly once.
+//! Panics are caught before they reach the caller, since unwinding into C is undefined behaviour.
+
+use std::{ffi::{c_char, c_int, CStr}, panic::{self, AssertUnwindSafe}, ptr, slice};
+use crate::{
+    activation::ActivationFunction,
+    categorize::CatNetwork
+};
+
+/// The inputs or outputs pointer was null
+pub const DARJ_NULL_POINTER: c_int = -1;
+/// The number of inputs or outputs didn't match the network
+pub const DARJ_WRONG_SIZE: c_int = -2;
+/// The network panicked while predicting
+pub const DARJ_PANICKED: c_int = -3;
+
+pub const DARJ_SIGMOID: c_int = 0;
+pub const DARJ_TANH: c_int = 1;
+pub const DARJ_LINEAR: c_int = 2;
+
+/// Creates a network with random weights.
+/// The activation is one of DARJ_SIGMOID, DARJ_TANH or DARJ_LINEAR.
+///
+/// Returns null if any size isn't positive or the activation isn't known.
+#[no_mangle]
+pub extern "C" fn darj_create(inputs: c_int, hidden: c_int, answers: c_int, hidden_layers: c_int, activation: c_int) -> *mut CatNetwork {
+    let activation_function: ActivationFunction = match activation {
+        DARJ_SIGMOID => ActivationFunction::Sigmoid,
+        DARJ_TANH => ActivationFunction::Tanh,
+        DARJ_LINEAR => ActivationFunction::Linear,
+        _ => return ptr::null_mut()
+    };
+    if inputs <= 0 || hidden <= 0 || answers <= 0 || hidden_layers <= 0 {
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(CatNetwork::new(inputs, hidden, answers, hidden_layers, activation_function)))
+}
+
+/// Reads a network from a .darj file.
+///
+/// Returns null if the file couldn't be read, or reading it panicked.
+///
+/// # Safety
+/// The path has to be a valid, null terminated string
+#[no_mangle]
+pub unsafe extern "C" fn darj_load(path: *const c_char) -> *mut CatNetwork {
+    if path.is_null() {
+        return ptr::null_mut();
+    }
+    let path: String = CStr::from_ptr(path).to_string_lossy().into_owned();
+    match panic::catch_unwind(|| CatNetwork::read_model(path)) {
+        Ok(Ok(net)) => Box::into_raw(Box::new(net)),
+        Ok(Err(_)) | Err(_) => ptr::null_mut()
+    }
+}
+
+/// Runs the inputs through the network, writing every answer node's output into the caller's buffer.
+///
+/// Returns the index of the brightest answer node, or a negative DARJ_ error code.
+///
+/// # Safety
+/// The network has to come from [`darj_create`] or [`darj_load`] and not be freed.
+/// Inputs has to point to `input_len` floats and outputs to `output_len` floats.
+#[no_mangle]
+pub unsafe extern "C" fn darj_predict(net: *const CatNetwork, inputs: *const f32, input_len: usize, outputs: *mut f32, output_len: usize) -> c_int {
+    if net.is_null() || inputs.is_null() || outputs.is_null() {
+        return DARJ_NULL_POINTER;
+    }
+    let net: &CatNetwork = &*net;
+    if input_len != net.input_size() || output_len != net.output_size() {
+        return DARJ_WRONG_SIZE;
+    }
+    let inputs: &[f32] = slice::from_raw_parts(inputs, input_len);
+    let answers: Vec<f32> = match panic::catch_unwind(AssertUnwindSafe(|| net.forward(inputs))) {
+        Ok(answers) => answers,
+        Err(_) => return DARJ_PANICKED
+    };
+    slice::from_raw_parts_mut(outputs, output_len).copy_from_slice(&answers);
+
+    let mut brightest: usize = 0;
+    answers.iter().enumerate().for_each(|(i, output)| if *output > answers[brightest] { brightest = i });
+    brightest as c_int
+}
+
+/// The number of inputs the network takes, or 0 if it's null
+///
+/// # Safety
+/// The network has to come from [`darj_create`] or [`darj_load`] and not be freed
+#[no_mangle]
+pub unsafe extern "C" fn darj_input_size(net: *const CatNetwork) -> usize {
+    if net.is_null() { 0 } else { (*net).input_size() }
+}
+
+/// The number of answer nodes, or 0 if the network is null
+///
+/// # Safety
+/// The network has to come from [`darj_create`] or [`darj_load`] and not be freed
+#[no_mangle]
+pub unsafe extern "C" fn darj_output_size(net: *const CatNetwork) -> usize {
+    if net.is_null() { 0 } else { (*net).output_size() }
+}
+
+/// Frees a network. Passing null does nothing.
+///
+/// # Safety
+/// The network has to come from [`darj_create`] or [`darj_load`], and can't be used again
+#[no_mangle]
+pub unsafe extern "C" fn darj_free(net: *mut CatNetwork) {
+    if !net.is_null() {
+        drop(Box::from_raw(net));
+    }
+}
@@ -0,0 +1,221 @@
+//! How closely generated data matches real data, feature by feature,
+//! and how useful it is for training a categorization network in place of the real thing.
+
+use core::fmt;
+use serde::{Serialize, Deserialize};
+use crate::{
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    error::DarjeelingError,
+    history::CompensatedSum,
+    input::Input,
+    metrics::ClassificationMetrics
+};
+
+/// The Kolmogorov-Smirnov statistic of two samples, the largest gap between their cumulative distributions.
+/// 0 means they're spread out the same, and 1 means they don't overlap at all. 0 if either is empty.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::fidelity::ks_statistic;
+///
+/// assert_eq!(ks_statistic(&[1.0, 2.0, 3.0], &[3.0, 1.0, 2.0]), 0.0);
+/// assert_eq!(ks_statistic(&[0.0, 1.0], &[5.0, 6.0]), 1.0);
+/// ```
+pub fn ks_statistic(real: &[f32], generated: &[f32]) -> f32 {
+    distances(real, generated).0
+}
+
+/// The Wasserstein-1 or earth mover's distance between two samples,
+/// how far on average values have to move to turn one into the other, in the units of the values. 0 if either is empty.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::fidelity::wasserstein_distance;
+///
+/// assert_eq!(wasserstein_distance(&[0.0, 1.0], &[2.0, 3.0]), 2.0);
+/// ```
+pub fn wasserstein_distance(real: &[f32], generated: &[f32]) -> f32 {
+    distances(real, generated).1
+}
+
+/// Walks the cumulative distributions of both samples together, for the KS statistic and the Wasserstein distance at once
+fn distances(real: &[f32], generated: &[f32]) -> (f32, f32) {
+    if real.is_empty() || generated.is_empty() {
+        return (0.0, 0.0);
+    }
+    let sorted = |values: &[f32]| {
+        let mut values: Vec<f32> = values.to_vec();
+        values.sort_by(f32::total_cmp);
+        values
+    };
+    let (real, generated) = (sorted(real), sorted(generated));
+    let mut points: Vec<f32> = real.iter().chain(&generated).copied().collect();
+    points.sort_by(f32::total_cmp);
+    points.dedup();
+
+    let (mut i, mut j) = (0, 0);
+    let mut ks: f32 = 0.0;
+    let mut wasserstein = CompensatedSum::default();
+    for (k, point) in points.iter().enumerate() {
+        while i < real.len() && real[i] <= *point {
+            i += 1;
+        }
+        while j < generated.len() && generated[j] <= *point {
+            j += 1;
+        }
+        let gap: f32 = (i as f32 / real.len() as f32 - j as f32 / generated.len() as f32).abs();
+        ks = ks.max(gap);
+        if let Some(next) = points.get(k + 1) {
+            wasserstein.add(gap * (next - point));
+        }
+    }
+    (ks, wasserstein.value())
+}
+
+/// How closely one generated feature is distributed like the real one
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FeatureFidelity {
+    pub feature: usize,
+    /// See [`ks_statistic`](fn@ks_statistic)
+    pub ks: f32,
+    /// See [`wasserstein_distance`](fn@wasserstein_distance)
+    pub wasserstein: f32
+}
+
+/// How closely every generated feature is distributed like the real one.
+/// Each feature is compared on its own, so it doesn't show whether the generated features go together the way real ones do;
+/// [`train_synthetic_test_real`](fn@train_synthetic_test_real) gets at that.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{fidelity::FidelityReport, input::Input};
+///
+/// let real = vec![Input::new(vec![0.0, 1.0], None), Input::new(vec![1.0, 1.0], None)];
+/// let generated = vec![vec![0.0, 1.0], vec![1.0, 3.0]];
+/// let report = FidelityReport::compare(&real, &generated).unwrap();
+/// assert_eq!(report.features[0].ks, 0.0);
+/// assert_eq!(report.worst().unwrap().feature, 1);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FidelityReport {
+    pub features: Vec<FeatureFidelity>,
+    /// The number of real and generated samples compared
+    pub real: usize,
+    pub generated: usize
+}
+
+impl FidelityReport {
+
+    /// Compares every feature of the real inputs to the same value of the generated samples
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// A generated sample or real input doesn't have as many values as the first real input
+    pub fn compare(real: &[Input], generated: &[Vec<f32>]) -> Result<FidelityReport, DarjeelingError> {
+        let width: usize = real.first().map(|input| input.inputs.len()).unwrap_or(0);
+        if let Some(input) = real.iter().find(|input| input.inputs.len() != width) {
+            return Err(DarjeelingError::InvalidInput(format!("A real input has {} values, but the first has {}", input.inputs.len(), width)));
+        }
+        if let Some(sample) = generated.iter().find(|sample| sample.len() != width) {
+            return Err(DarjeelingError::InvalidInput(format!("A generated sample has {} values, but the real inputs have {}", sample.len(), width)));
+        }
+        let features: Vec<FeatureFidelity> = (0..width).map(|feature| {
+            let real: Vec<f32> = real.iter().map(|input| input.inputs[feature]).collect();
+            let generated: Vec<f32> = generated.iter().map(|sample| sample[feature]).collect();
+            let (ks, wasserstein) = distances(&real, &generated);
+            FeatureFidelity { feature, ks, wasserstein }
+        }).collect();
+        Ok(FidelityReport { features, real: real.len(), generated: generated.len() })
+    }
+
+    /// The average KS statistic of every feature
+    pub fn mean_ks(&self) -> f32 {
+        if self.features.is_empty() { 0.0 } else { self.features.iter().map(|feature| feature.ks).sum::<f32>() / self.features.len() as f32 }
+    }
+
+    pub fn mean_wasserstein(&self) -> f32 {
+        if self.features.is_empty() { 0.0 } else { self.features.iter().map(|feature| feature.wasserstein).sum::<f32>() / self.features.len() as f32 }
+    }
+
+    /// The feature with the largest KS statistic, the one the generation model matches worst
+    pub fn worst(&self) -> Option<&FeatureFidelity> {
+        self.features.iter().max_by(|a, b| a.ks.total_cmp(&b.ks))
+    }
+}
+
+impl fmt::Display for FidelityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} real and {} generated samples, mean KS {:.4}, mean Wasserstein {:.4}", self.real, self.generated, self.mean_ks(), self.mean_wasserstein())?;
+        self.features.iter().try_for_each(|feature| writeln!(f, "Feature {}: KS {:.4}, Wasserstein {:.4}", feature.feature, feature.ks, feature.wasserstein))
+    }
+}
+
+/// How a categorization network trained on generated data does on real data,
+/// next to one trained on real data if there was some to compare with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UtilityReport {
+    /// Trained on the generated data, tested on the real data
+    pub synthetic: ClassificationMetrics,
+    /// Trained on the real training data, tested on the same real data
+    pub real: Option<ClassificationMetrics>
+}
+
+impl UtilityReport {
+
+    /// How much accuracy is lost by training on generated data instead of real data
+    pub fn gap(&self) -> Option<f32> {
+        self.real.as_ref().map(|real| real.accuracy - self.synthetic.accuracy)
+    }
+}
+
+impl fmt::Display for UtilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Trained on synthetic: accuracy {:.4}, macro F1 {:.4}", self.synthetic.accuracy, self.synthetic.macro_f1)?;
+        if let Some(real) = &self.real {
+            write!(f, "\nTrained on real: accuracy {:.4}, macro F1 {:.4}", real.accuracy, real.macro_f1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Trains a categorization network on labelled generated data and tests it on real data, the train-on-synthetic, test-on-real measure.
+/// Generated data that's good enough to stand in for the real thing gives close to the accuracy of training on real data,
+/// which is trained on too if it's given, with the same config. The networks aren't saved.
+///
+/// ## Params
+/// - Synthetic: The generated data, with answers
+/// - Real Test: The real data both networks are tested on
+/// - Real Train: Real data to train the network compared against, if there is any. It shouldn't overlap with the test data.
+/// - Config: How both networks are built and trained
+///
+/// ## Err
+/// - ### InvalidTrainingData
+/// The synthetic or real training data is empty, or has no answers
+/// - The same as [`learn`](fn@crate::categorize::CatNetwork::learn)
+///
+/// ## Examples
+/// ```ignore
+/// let samples = synthesizer.generate(7, 1000)?;
+/// let report = fidelity::train_synthetic_test_real(&labelled(samples), &real_test, Some(&real_train), &TrainingConfig::default())?;
+/// println!("{}", report);
+/// ```
+pub fn train_synthetic_test_real(synthetic: &[Input], real_test: &[Input], real_train: Option<&[Input]>, config: &TrainingConfig) -> Result<UtilityReport, DarjeelingError> {
+    let config: TrainingConfig = TrainingConfig { write: false, ..config.clone() };
+    let trained = |data: &[Input]| -> Result<ClassificationMetrics, DarjeelingError> {
+        let mut net: CatNetwork = CatNetwork::new_from_data(data, config.hidden_num, config.hidden_layers, config.activation_function)?;
+        net.set_regularization(config.regularization);
+        net.set_schedule(config.schedule);
+        net.set_reduction(config.reduction);
+        let categories = net.categories().unwrap_or_default();
+        net.learn_with_config(&mut data.to_vec(), categories, &config, "synthetic_utility")?;
+        Ok(net.evaluate(real_test).metrics)
+    };
+    Ok(UtilityReport {
+        synthetic: trained(synthetic)?,
+        real: match real_train {
+            Some(real_train) => Some(trained(real_train)?),
+            None => None
+        }
+    })
+}
@@ -1,27 +1,288 @@
 use ascii_converter::decimals_to_string;
-use rand::{Rng, seq::SliceRandom, thread_rng}; 
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom, thread_rng}; 
 use serde::{Serialize, Deserialize};
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
 use std::{fs, path::Path};
 use crate::{
     categorize::CatNetwork,
-    node::Node, 
+    node::{self, Node}, 
     activation::ActivationFunction, 
+    adversarial::{self, Aggregation, InstanceNoise, AdversarialLoss, CycleRecord, DiscriminatorEnsemble, ReplayBuffer, EquilibriumStopping},
     DEBUG, 
     error::DarjeelingError,
+    config::{self, TrainingConfig, GanConfig, LearningRateSchedule},
+    fidelity::FidelityReport,
     input::Input, 
     types::{Types, Types::Boolean},
+    darj,
+    visualize,
+    parallel,
     dbg_println
 };
-use rayon::prelude::*;
 
 /// The generation Neural Network struct
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenNetwork {
     node_array: Vec<Vec<Node>>,
     sensor: Option<usize>,
     answer: Option<usize>,
     parameters: Option<u128>,
-    activation_function: ActivationFunction
+    activation_function: ActivationFunction,
+    #[serde(default)]
+    instance_noise: Option<InstanceNoise>,
+    #[serde(default)]
+    adversarial_loss: AdversarialLoss,
+    #[serde(default = "default_collapse_threshold")]
+    collapse_threshold: f32,
+    /// Every cycle the network has trained for
+    #[serde(skip)]
+    cycles: Vec<CycleRecord>,
+    #[serde(default)]
+    constraints: Vec<Option<OutputConstraint>>,
+    #[serde(default)]
+    ensemble: Option<DiscriminatorEnsemble>,
+    #[serde(default)]
+    replay: Option<ReplayBuffer>,
+    #[serde(default)]
+    equilibrium: Option<EquilibriumStopping>,
+    /// How the generation and distinguishing models' learning rates change over the cycles
+    #[serde(default)]
+    schedule: LearningRateSchedule,
+    #[serde(default)]
+    discriminator_schedule: LearningRateSchedule,
+    #[serde(skip)]
+    sample_dump: Option<SampleDump>,
+    /// How the generated data compared to the real data at the end of the last training run
+    #[serde(skip)]
+    fidelity: Option<FidelityReport>,
+    /// Where the model and its bundles are saved
+    #[serde(skip)]
+    output_dir: Option<PathBuf>,
+    /// Where each cycle's distinguishing model is saved
+    #[serde(skip)]
+    temp_dir: Option<PathBuf>,
+    /// The distinguishing models from the last cycle of training, which aren't saved with the model
+    #[serde(skip)]
+    discriminators: Vec<Discriminator>
+}
+
+/// The distinguishing model trained alongside a generation model, which tells real data from generated data
+#[derive(Debug, Clone)]
+pub struct Discriminator {
+    net: CatNetwork
+}
+
+impl Discriminator {
+
+    /// How real the sample looks, from 0 for certainly generated to 1 for certainly real.
+    /// Useful for spotting anomalies, or throwing out poor generated outputs.
+    /// 
+    /// The sample has to have as many values as the generation model has answer nodes.
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// net.learn(&mut data, 0.5, "gen", 100, 0.5, 10, 1, ActivationFunction::Sigmoid, 99.0).unwrap();
+    /// let discriminator = net.discriminator().unwrap();
+    /// let unusual: Vec<&Input> = data.iter().filter(|input| discriminator.realism(input) < 0.2).collect();
+    /// ```
+    pub fn realism(&self, sample: &Input) -> f32 {
+        self.score(&sample.inputs)
+    }
+
+    /// How unusual the sample looks, the opposite of its [realism](fn@Discriminator::realism).
+    /// Use an [AnomalyDetector](crate::anomaly::AnomalyDetector) to decide how high a score counts as an anomaly.
+    pub fn anomaly_score(&self, sample: &Input) -> f32 {
+        1.0 - self.realism(sample)
+    }
+
+    fn score(&self, values: &[f32]) -> f32 {
+        self.net.predict(values).score(&Boolean(true)).unwrap_or(0.0).clamp(0.0, 1.0)
+    }
+
+    /// The categorization network underneath, whose categories are Boolean(true) for real and Boolean(false) for generated
+    pub fn network(&self) -> &CatNetwork {
+        &self.net
+    }
+}
+
+/// The legal values of one generated output feature
+/// 
+/// ## Examples
+/// ```
+/// use darjeeling::generation::OutputConstraint;
+/// 
+/// let age = OutputConstraint::range(0.0, 120.0).integer();
+/// assert_eq!(age.apply(37.6), 38.0);
+/// assert_eq!(age.apply(-3.0), 0.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct OutputConstraint {
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+    /// Whether values are rounded to the nearest whole number
+    pub integer: bool
+}
+
+impl OutputConstraint {
+
+    /// Values between min and max, inclusive
+    pub fn range(min: f32, max: f32) -> OutputConstraint {
+        OutputConstraint { min: Some(min), max: Some(max), integer: false }
+    }
+
+    /// Rounds values to whole numbers as well
+    pub fn integer(mut self) -> OutputConstraint {
+        self.integer = true;
+        self
+    }
+
+    /// Rounds the value if it has to be whole, then clamps it into the range
+    pub fn apply(&self, value: f32) -> f32 {
+        let mut value: f32 = if self.integer { value.round() } else { value };
+        if let Some(min) = self.min {
+            value = value.max(min);
+        }
+        if let Some(max) = self.max {
+            value = value.min(max);
+        }
+        value
+    }
+}
+
+/// The format of a [sample dump](SampleDump)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum DumpFormat {
+    /// A `cycle,output_0,output_1,...,realism` header, then a row for every sample
+    #[default]
+    Csv,
+    /// A `{"cycle":1,"output":[...],"realism":0.5}` object on every line
+    Jsonl
+}
+
+/// Appends some generated samples to a file every few cycles of [training](fn@GenNetwork::learn), to watch their quality change without stopping the run.
+/// Every dump generates from the same seeded random inputs, like [`generate_with_seed`](fn@GenNetwork::generate_with_seed),
+/// so a row from one cycle can be compared to the same row from another.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::generation::{SampleDump, DumpFormat};
+///
+/// let dump = SampleDump::new("samples.jsonl", 10, 5).with_format(DumpFormat::Jsonl);
+/// assert_eq!(dump.seed, 0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleDump {
+    /// The file the samples are appended to, which is made if it doesn't exist
+    pub path: PathBuf,
+    /// Samples are dumped after every this many cycles
+    pub every: usize,
+    /// The number of samples in each dump
+    pub count: usize,
+    pub format: DumpFormat,
+    /// Decides the random inputs the samples are generated from
+    pub seed: u64
+}
+
+impl SampleDump {
+
+    pub fn new(path: impl Into<PathBuf>, every: usize, count: usize) -> SampleDump {
+        SampleDump { path: path.into(), every, count, format: DumpFormat::Csv, seed: 0 }
+    }
+
+    pub fn with_format(mut self, format: DumpFormat) -> SampleDump {
+        self.format = format;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> SampleDump {
+        self.seed = seed;
+        self
+    }
+
+    /// Whether the samples are dumped after the cycle, which starts at 1
+    pub fn due(&self, cycle: usize) -> bool {
+        self.every > 0 && cycle % self.every == 0
+    }
+
+    /// Formats the samples generated after a cycle, with a CSV header first if it's the start of the file
+    pub fn format_samples(&self, cycle: usize, samples: &[GeneratedSample], start: bool) -> String {
+        let mut text: String = String::new();
+        match self.format {
+            DumpFormat::Csv => {
+                if start {
+                    let outputs: usize = samples.first().map(|sample| sample.output.len()).unwrap_or(0);
+                    text += &format!("cycle,{}realism\n", (0..outputs).map(|i| format!("output_{},", i)).collect::<String>());
+                }
+                samples.iter().for_each(|sample| {
+                    let realism: String = sample.realism.map(|realism| realism.to_string()).unwrap_or_default();
+                    text += &format!("{},{}{}\n", cycle, sample.output.iter().map(|value| format!("{},", value)).collect::<String>(), realism);
+                });
+            },
+            DumpFormat::Jsonl => samples.iter().for_each(|sample| {
+                text += &format!("{}\n", serde_json::json!({ "cycle": cycle, "output": sample.output, "realism": sample.realism }));
+            })
+        }
+        text
+    }
+
+    /// Appends the samples to the file
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The file couldn't be opened or written
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write(&self, cycle: usize, samples: &[GeneratedSample]) -> Result<(), DarjeelingError> {
+        use std::io::Write;
+
+        let start: bool = fs::metadata(&self.path).map(|metadata| metadata.len() == 0).unwrap_or(true);
+        let text: String = self.format_samples(cycle, samples, start);
+        fs::OpenOptions::new().create(true).append(true).open(&self.path)
+            .and_then(|mut file| file.write_all(text.as_bytes()))
+            .map_err(|error| DarjeelingError::WriteDataFailed(format!("{}: {}", self.path.display(), error)))
+    }
+}
+
+/// A generation model with its distinguishing model and training state, saved together as JSON
+#[derive(Debug, Serialize, Deserialize)]
+struct Bundle {
+    generator: String,
+    discriminator: Option<String>,
+    instance_noise: Option<InstanceNoise>,
+    adversarial_loss: AdversarialLoss,
+    collapse_threshold: f32,
+    cycles: Vec<CycleRecord>,
+    #[serde(default)]
+    constraints: Vec<Option<OutputConstraint>>,
+    #[serde(default)]
+    ensemble: Option<DiscriminatorEnsemble>,
+    #[serde(default)]
+    replay: Option<ReplayBuffer>,
+    #[serde(default)]
+    equilibrium: Option<EquilibriumStopping>,
+    #[serde(default)]
+    schedule: LearningRateSchedule,
+    #[serde(default)]
+    discriminator_schedule: LearningRateSchedule,
+    /// Every distinguishing model in the ensemble after the first
+    #[serde(default)]
+    other_discriminators: Vec<String>
+}
+
+fn default_collapse_threshold() -> f32 {
+    0.001
+}
+
+/// One output of a generation model, from [`test`](fn@GenNetwork::test)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedSample {
+    /// The values of the answer nodes
+    pub output: Vec<f32>,
+    /// The input the output was generated from, if there was one
+    pub seed: Option<Input>,
+    /// How real the distinguishing model thinks the output is, from 0 to 1.
+    /// None if the network hasn't been trained since it was made or read.
+    pub realism: Option<f32>
 }
 #[warn(clippy::unwrap_in_result)]
 impl GenNetwork {
@@ -50,7 +311,7 @@ impl GenNetwork {
     /// let mut net: GenNetwork = GenNetwork::new(inputs, hidden, answer, hidden_layers, ActivationFunction::Sigmoid);
     /// ```
     pub fn new(input_num: i32, hidden_num: i32, answer_num: i32, hidden_layers: i32, activation_function: ActivationFunction) -> GenNetwork {
-        let mut net: GenNetwork = GenNetwork { node_array: vec![], sensor: Some(0), answer: Some(hidden_layers as usize + 1), parameters: None, activation_function};
+        let mut net: GenNetwork = GenNetwork { node_array: vec![], sensor: Some(0), answer: Some(hidden_layers as usize + 1), parameters: None, activation_function, instance_noise: None, adversarial_loss: AdversarialLoss::Standard, collapse_threshold: default_collapse_threshold(), cycles: vec![], constraints: vec![], ensemble: None, replay: None, equilibrium: None, schedule: LearningRateSchedule::Constant, discriminator_schedule: LearningRateSchedule::Constant, sample_dump: None, fidelity: None, output_dir: None, temp_dir: None, discriminators: vec![] };
         let mut rng = rand::thread_rng();
         net.node_array.push(vec![]);    
         for _i in 0..input_num {
@@ -135,6 +396,7 @@ impl GenNetwork {
     /// ## TODO: Refactor to pass around the neural net, not the model name
     /// 
     /// ## Examples
+    /// A runnable version is in `examples/generation.rs`, try it with `cargo run --example generation`
     /// ```ignore
     /// use darjeeling::{
     ///     generation::GenNetwork,
@@ -157,7 +419,7 @@ impl GenNetwork {
     /// let mut data: Vec<Input> = file();
     /// let mut net = GenNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
     /// let model_name: String = net.learn(&mut data, 0.5, "gen", 100, 0.5, 10, 1, ActivationFunction::Sigmoid, 99.0).unwrap();
-    /// let new_data: Vec<Input> = net.test(data).unwrap();
+    /// let samples: Vec<GeneratedSample> = net.test(&data).unwrap();
     /// ```
     pub fn learn( // Frankly this whole function is disgusting and needs to be burned; I concur from the future
         &mut self, 
@@ -169,72 +431,530 @@ impl GenNetwork {
         distinguishing_target_err_percent: f32
     ) -> Result<String, DarjeelingError> {
         let mut epochs: f32 = 0.0;
-        let distinguishing_model: *mut CatNetwork = &mut CatNetwork::new(self.node_array[self.answer.unwrap()].len() as i32, distinguising_hidden_neurons, 2, distinguising_hidden_layers, distinguising_activation);
-        let mut outputs: Vec<Input> = vec![];
+        let mut rng = thread_rng();
         for _i in 0..max_cycles {
-            let mse: f32;
-            data.shuffle(&mut thread_rng());
+            // Carries on from a bundle's cycles when training is resumed
+            let cycle: usize = self.cycles.len();
+            let generator_learning_rate: f32 = self.schedule.learning_rate(learning_rate, cycle);
+            let discriminator_learning_rate: f32 = self.discriminator_schedule.learning_rate(distinguising_learning_rate, cycle);
+            let mut outputs: Vec<Input> = vec![];
+            let mut generated: Vec<Vec<f32>> = vec![];
+            data.shuffle(&mut rng);
             for line in 0..data.len() {
                 dbg_println!("Training Checkpoint One Passed");
-                self.push_downstream(data, line as i32);
+                self.push_downstream(&data[line].inputs);
                 let mut output = vec![];
                 for i in 0..self.node_array[self.answer.unwrap()].len() {
                     output.push(self.node_array[self.answer.unwrap()][i].output(&self.activation_function));
                 }
+                generated.push(output.clone());
                 outputs.push(Input::new(output, Some(Boolean(false)))); // false indicates not real data
                 data[line].answer = Some(Boolean(true));
                 outputs.push(data[line].clone());
             }
+            // A quarter of the real and generated pairs are held out of the distinguishing model's training,
+            // so its accuracy is measured on samples it hasn't seen. A single record is too few to split.
+            let held_out_pairs: usize = if data.len() > 1 { (data.len() / 4).max(1) } else { 0 };
+            let held_out: Vec<Input> = outputs.split_off(outputs.len() - held_out_pairs * 2);
+            if let Some(replay) = &mut self.replay {
+                replay.mix(&mut outputs, &mut rng);
+                replay.add(&generated, &mut rng);
+            }
             // Do we train a new one from scratch or do we continue training the old one
             // We still need to figure out how to accurately deal with distinguishing error affecting the generative model
-            let mut new_model: CatNetwork = CatNetwork::new(self.node_array[self.answer.unwrap()].len() as i32, distinguising_hidden_neurons, 2, distinguising_hidden_layers, distinguising_activation);
-            match new_model.learn(
-                data,
-                vec![Boolean(true), Boolean(false)],
-                distinguising_learning_rate,
-                &("distinguishing".to_owned() + &name), distinguishing_target_err_percent, false) 
-                {
-                    Ok((_name, _err_percent, errmse)) => mse = errmse,
+            // Distinguishing models are only kept when there's somewhere to put them
+            let temp_dir: Option<PathBuf> = config::resolve_dir(self.temp_dir.as_deref(), config::TEMP_DIR_VAR);
+            let write_distinguishing: bool = temp_dir.is_some() && cfg!(not(target_arch = "wasm32"));
+            let members: usize = self.ensemble.map(|ensemble| ensemble.count.max(1)).unwrap_or(1);
+            let mut discriminators: Vec<Discriminator> = Vec::with_capacity(members);
+            let mut feedback: Vec<f32> = Vec::with_capacity(members);
+            let mut member_signals: Vec<Vec<f32>> = Vec::with_capacity(members);
+            let mut accuracy: f32 = 0.0;
+            for member in 0..members {
+                // Every member draws its own noise, so they don't all lean the same way
+                let mut samples: Vec<Input> = outputs.clone();
+                if let Some(noise) = &self.instance_noise {
+                    noise.at_cycle(cycle).apply(&mut samples, &mut rng);
+                }
+                let mut new_model: CatNetwork = CatNetwork::new(self.node_array[self.answer.unwrap()].len() as i32, distinguising_hidden_neurons, 2, distinguising_hidden_layers, distinguising_activation);
+                new_model.set_output_dir(temp_dir.clone());
+                let distinguishing_name: String = format!("distinguishing{}_cycle{}_member{}", name, cycle + 1, member);
+                let trained: Result<f32, DarjeelingError> = match self.adversarial_loss {
+                    // A critic learns to widen the gap between its scores instead of which samples are real, so it has no mse
+                    AdversarialLoss::Wasserstein { clip } => {
+                        adversarial::train_critic(&mut new_model, &mut samples, discriminator_learning_rate, clip, &mut rng);
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if write_distinguishing {
+                            if let Err(error) = new_model.write_model(&distinguishing_name) {
+                                return Err(DarjeelingError::DisinguishingModelError(error.to_string()));
+                            }
+                        }
+                        Ok(0.0)
+                    },
+                    _ => new_model.learn(
+                        &mut samples,
+                        vec![Boolean(true), Boolean(false)],
+                        discriminator_learning_rate,
+                        &distinguishing_name, distinguishing_target_err_percent, write_distinguishing
+                    ).map(|(_name, _err_percent, errmse)| errmse)
+                };
+                let mse: f32 = match trained {
+                    Ok(mse) => mse,
                     Err(error) => return Err(DarjeelingError::DisinguishingModelError(error.to_string()))
                 };
 
-            unsafe { distinguishing_model.write(new_model) };
+                feedback.push(match self.adversarial_loss {
+                    AdversarialLoss::Standard => mse,
+                    AdversarialLoss::Wasserstein { .. } => adversarial::wasserstein_estimate(&new_model, &samples),
+                    AdversarialLoss::FeatureMatching => {
+                        member_signals.push(adversarial::feature_matching_signals(&new_model, &samples));
+                        adversarial::feature_matching_distance(&new_model, &samples)
+                    }
+                });
+                accuracy += new_model.evaluate(if held_out.is_empty() { &outputs } else { &held_out }).metrics.accuracy / members as f32;
+                discriminators.push(Discriminator { net: new_model });
+            }
+            let aggregation: Aggregation = self.ensemble.map(|ensemble| ensemble.aggregation).unwrap_or_default();
+            let signal: f32 = aggregation.aggregate(&feedback, &self.adversarial_loss);
+            let width: usize = self.node_array[self.answer.unwrap()].len();
+            // Feature matching has a signal for every output, the others share one
+            let signals: Vec<f32> = match self.adversarial_loss {
+                AdversarialLoss::FeatureMatching => {
+                    let weights: Vec<f32> = aggregation.weights(&feedback, &self.adversarial_loss);
+                    (0..width).map(|output| member_signals.iter().zip(&weights).map(|(signals, weight)| signals[output] * weight).sum()).collect()
+                },
+                _ => vec![signal; width]
+            };
+            let spread: f32 = feedback.iter().copied().fold(f32::NEG_INFINITY, f32::max) - feedback.iter().copied().fold(f32::INFINITY, f32::min);
+            self.discriminators = discriminators;
 
-            self.backpropogate(learning_rate, mse);
+            self.backpropogate(generator_learning_rate, &signals);
             epochs += 1.0;
             println!("Epoch: {:?}", epochs);
+
+            let diversity: f32 = adversarial::diversity(&generated);
+            let collapsed: bool = diversity < self.collapse_threshold;
+            if collapsed {
+                println!("Warning: Generated outputs have collapsed to nearly the same values, with a diversity of {}", diversity);
+            }
+            self.cycles.push(CycleRecord {
+                cycle: cycle + 1, loss: signal, diversity, collapsed, spread, discriminator_accuracy: accuracy,
+                learning_rate: generator_learning_rate, discriminator_learning_rate
+            });
+            println!("Distinguishing accuracy: {}", accuracy);
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(dump) = self.sample_dump.as_ref().filter(|dump| dump.due(cycle + 1)) {
+                dump.write(cycle + 1, &self.generate_with_seed(dump.seed, dump.count))?;
+            }
+            if let Some(equilibrium) = self.equilibrium.filter(|equilibrium| equilibrium.reached(&self.cycles)) {
+                println!("Stopping at equilibrium, the distinguishing model has been within {} of 50% accuracy for {} cycles", equilibrium.tolerance, equilibrium.patience.max(1));
+                break;
+            }
         }
+        let generated: Vec<Vec<f32>> = data.iter().map(|input| {
+            let mut output: Vec<f32> = self.forward(&input.inputs);
+            self.constrain(&mut output);
+            output
+        }).collect();
+        // Generators whose outputs aren't shaped like the data can't be compared to it
+        self.fidelity = FidelityReport::compare(data, &generated).ok();
+        if let Some(fidelity) = &self.fidelity {
+            print!("{}", fidelity);
+        }
+        #[cfg(target_arch = "wasm32")]
+        return Err(DarjeelingError::WriteModelFailed(format!("{}: There's no filesystem on wasm, save to_darj_string instead", name)));
         #[allow(unused_mut)]
+        #[cfg(not(target_arch = "wasm32"))]
         let mut model_name: String;
+        #[cfg(not(target_arch = "wasm32"))]
         match self.write_model(&name) {
             Ok(m_name) => {
                 model_name = m_name;
             },
             Err(error) => return Err(error)
         }
+        #[cfg(not(target_arch = "wasm32"))]
         Ok(model_name)
     }
 
-    pub fn test(&mut self, data: &mut Vec<Input>) -> Result<Vec<Input>, DarjeelingError> {
-        data.shuffle(&mut thread_rng());
-        let mut outputs: Vec<Input> = vec![];
-        for i in 0..data.len() {
-            self.push_downstream(data, i as i32);
-            let mut output = vec![];
-            for i in 0..self.node_array[self.answer.unwrap()].len() {
-                output.push(self.node_array[self.answer.unwrap()][i].output(&self.activation_function));
-            }
-            outputs.push(Input::new(output, None)); // false indicates not real data
+    /// [`learn`](fn@learn) with its settings taken from a config instead of a long list of arguments.
+    /// The config's directories, ensemble, equilibrium stopping and schedules replace the network's if it has them.
+    /// 
+    /// ## Params
+    /// - Data: List of inputs
+    /// - Config: The generation model's learning rate, the number of cycles, and how the distinguishing model is built and trained
+    /// - Name: The model name
+    /// 
+    /// ## Returns
+    /// The same as [`learn`](fn@learn)
+    /// 
+    /// ## Err
+    /// The same as [`learn`](fn@learn)
+    /// 
+    /// ## Examples
+    /// ```ignore
+    /// let config = GanConfig::default().with_cycles(10).with_discriminator(TrainingConfig::default().with_hidden_num(10));
+    /// let model_name: String = net.learn_with_config(&mut data, &config, "gen").unwrap();
+    /// ```
+    pub fn learn_with_config(&mut self, data: &mut Vec<Input>, config: &GanConfig, name: &str) -> Result<String, DarjeelingError> {
+        if config.output_dir.is_some() {
+            self.output_dir = config.output_dir.clone();
+        }
+        if config.temp_dir.is_some() {
+            self.temp_dir = config.temp_dir.clone();
+        }
+        if config.ensemble.is_some() {
+            self.ensemble = config.ensemble;
+        }
+        if config.equilibrium.is_some() {
+            self.equilibrium = config.equilibrium;
+        }
+        if let Some(schedule) = config.schedule {
+            self.schedule = schedule;
+        }
+        if let Some(schedule) = config.discriminator_schedule {
+            self.discriminator_schedule = schedule;
+        }
+        let discriminator: &TrainingConfig = &config.discriminator;
+        self.learn(
+            data,
+            config.learning_rate,
+            name,
+            config.cycles,
+            discriminator.learning_rate,
+            discriminator.hidden_num,
+            discriminator.hidden_layers,
+            discriminator.activation_function,
+            discriminator.target_err_percent
+        )
+    }
+
+    /// Adds noise to the real and generated samples the distinguishing model trains on each cycle.
+    /// None trains it on the samples as they are.
+    pub fn set_instance_noise(&mut self, noise: Option<InstanceNoise>) {
+        self.instance_noise = noise;
+    }
+
+    /// Chooses what the network learns from each cycle of [training](fn@learn), the distinguishing model's mse by default
+    pub fn set_adversarial_loss(&mut self, loss: AdversarialLoss) {
+        self.adversarial_loss = loss;
+    }
+
+    /// Sets the [diversity](fn@crate::adversarial::diversity) under which a cycle's outputs count as collapsed, and a warning is printed
+    pub fn set_collapse_threshold(&mut self, threshold: f32) {
+        self.collapse_threshold = threshold;
+    }
+
+    /// Every cycle the network has trained for, with the generated outputs' diversity
+    pub fn cycles(&self) -> &[CycleRecord] {
+        &self.cycles
+    }
+
+    /// Whether the last cycle of training collapsed
+    pub fn collapsed(&self) -> bool {
+        self.cycles.last().map(|cycle| cycle.collapsed).unwrap_or(false)
+    }
+
+    /// Sets the legal values of each output feature, in the order of the answer nodes.
+    /// Generated samples are rounded and clamped into them. None leaves a feature as it is.
+    /// They're applied to the samples that are returned, not to what the distinguishing model sees during training.
+    pub fn set_output_constraints(&mut self, constraints: Vec<Option<OutputConstraint>>) {
+        self.constraints = constraints;
+    }
+
+    pub fn output_constraints(&self) -> &[Option<OutputConstraint>] {
+        &self.constraints
+    }
+
+    /// Saves the model and its bundles to a directory instead of the working directory, creating it if it has to.
+    /// None goes back to using `DARJEELING_OUTPUT_DIR`, or the working directory if that isn't set.
+    pub fn set_output_dir(&mut self, dir: Option<PathBuf>) {
+        self.output_dir = dir;
+    }
+
+    /// Saves each cycle's distinguishing model to a directory, as `model_distinguishing<name>_cycle<cycle>_member<member>_<number>.darj`,
+    /// where the member is its index among the [distinguishing models](fn@discriminators) of the cycle.
+    /// None goes back to using `DARJEELING_TEMP_DIR`, or not saving them if that isn't set.
+    pub fn set_temp_dir(&mut self, dir: Option<PathBuf>) {
+        self.temp_dir = dir;
+    }
+
+    /// Uses the output and temp directories of a config
+    pub fn set_dirs(&mut self, config: &TrainingConfig) {
+        self.output_dir = config.output_dir.clone();
+        self.temp_dir = config.temp_dir.clone();
+    }
+
+    /// The distinguishing model from the last cycle of [training](fn@learn), the first of them with an [ensemble](fn@set_ensemble).
+    /// None if the network hasn't been trained since it was made or read, since it isn't saved with the model.
+    pub fn discriminator(&self) -> Option<&Discriminator> {
+        self.discriminators.first()
+    }
+
+    /// Every distinguishing model from the last cycle of [training](fn@learn), empty if the network hasn't been trained since it was made or read
+    pub fn discriminators(&self) -> &[Discriminator] {
+        &self.discriminators
+    }
+
+    /// Trains against several distinguishing models each cycle and learns from their combined feedback. None trains against one.
+    pub fn set_ensemble(&mut self, ensemble: Option<DiscriminatorEnsemble>) {
+        self.ensemble = ensemble;
+    }
+
+    pub fn ensemble(&self) -> Option<DiscriminatorEnsemble> {
+        self.ensemble
+    }
+
+    /// Mixes generated samples from earlier cycles into what the distinguishing model trains on. None only uses the current cycle's.
+    pub fn set_replay_buffer(&mut self, replay: Option<ReplayBuffer>) {
+        self.replay = replay;
+    }
+
+    /// The replay buffer with every sample it holds so far
+    pub fn replay_buffer(&self) -> Option<&ReplayBuffer> {
+        self.replay.as_ref()
+    }
+
+    /// Appends generated samples to a file every few cycles of [training](fn@learn). None stops dumping them.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_sample_dump(&mut self, dump: Option<SampleDump>) {
+        self.sample_dump = dump;
+    }
+
+    pub fn sample_dump(&self) -> Option<&SampleDump> {
+        self.sample_dump.as_ref()
+    }
+
+    /// Changes the generation and distinguishing models' learning rates over the cycles of [training](fn@learn), independently of each other.
+    /// Each cycle's learning rate comes from its schedule, starting from the learning rate training was given, and is kept in the [cycle's record](fn@cycles).
+    /// Both are constant by default.
+    pub fn set_schedules(&mut self, generator: LearningRateSchedule, discriminator: LearningRateSchedule) {
+        self.schedule = generator;
+        self.discriminator_schedule = discriminator;
+    }
+
+    pub fn schedules(&self) -> (LearningRateSchedule, LearningRateSchedule) {
+        (self.schedule, self.discriminator_schedule)
+    }
+
+    /// Stops [training](fn@learn) early once the distinguishing model can't tell real and generated samples apart. None always trains every cycle.
+    pub fn set_equilibrium_stopping(&mut self, equilibrium: Option<EquilibriumStopping>) {
+        self.equilibrium = equilibrium;
+    }
+
+    /// The distinguishing model's accuracy every cycle the network has trained for, which settles near 0.5 at equilibrium
+    pub fn equilibrium_trace(&self) -> Vec<f32> {
+        self.cycles.iter().map(|cycle| cycle.discriminator_accuracy).collect()
+    }
+
+    /// Whether the last cycles of training were at the [equilibrium](fn@set_equilibrium_stopping), false without equilibrium stopping
+    pub fn reached_equilibrium(&self) -> bool {
+        self.equilibrium.map(|equilibrium| equilibrium.reached(&self.cycles)).unwrap_or(false)
+    }
+
+    /// How closely each feature of the generated data matched the real data at the end of the last [training](fn@learn) run,
+    /// from generating an output for every input. None if the network hasn't been trained since it was made or read,
+    /// or if its outputs don't have a value for every feature of the data.
+    pub fn fidelity(&self) -> Option<&FidelityReport> {
+        self.fidelity.as_ref()
+    }
+
+    /// The average realism every distinguishing model gives the output
+    fn realism(&self, output: &[f32]) -> Option<f32> {
+        match self.discriminators.is_empty() {
+            true => None,
+            false => Some(self.discriminators.iter().map(|discriminator| discriminator.score(output)).sum::<f32>() / self.discriminators.len() as f32)
         }
-        Ok(outputs)
+    }
+
+    /// Runs every input through the network, without changing the order of the data
+    /// 
+    /// ## Returns
+    /// A generated sample for every input, in the same order as the data
+    pub fn test(&mut self, data: &[Input]) -> Result<Vec<GeneratedSample>, DarjeelingError> {
+        let mut samples: Vec<GeneratedSample> = vec![];
+        for input in data {
+            self.push_downstream(&input.inputs);
+            let activation_function: ActivationFunction = self.activation_function;
+            let mut output: Vec<f32> = self.node_array[self.answer.unwrap()].iter_mut().map(|node| node.output(&activation_function)).collect();
+            self.constrain(&mut output);
+            samples.push(GeneratedSample {
+                realism: self.realism(&output),
+                output,
+                seed: Some(input.clone())
+            });
+        }
+        Ok(samples)
+    }
+
+    /// Generates samples from random inputs drawn with a seeded generator,
+    /// so the same seed and network always give the same samples.
+    /// Every input value is drawn uniformly between 0 and 1.
+    /// 
+    /// ## Params
+    /// - Seed: Decides the random inputs
+    /// - Count: The number of samples to generate
+    /// 
+    /// ## Returns
+    /// The samples, each with the random input it was generated from as its seed
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{generation::GenNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = GenNetwork::new(3, 4, 2, 1, ActivationFunction::Sigmoid);
+    /// let samples = net.generate_with_seed(42, 5);
+    /// assert_eq!(samples.len(), 5);
+    /// assert_eq!(samples, net.generate_with_seed(42, 5));
+    /// ```
+    pub fn generate_with_seed(&self, seed: u64, count: usize) -> Vec<GeneratedSample> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input_num: usize = self.node_array[self.sensor.unwrap()].len();
+        (0..count)
+            .map(|_| (0..input_num).map(|_| rng.gen::<f32>()).collect::<Vec<f32>>())
+            .map(|inputs| self.generate(Input::new(inputs, None)))
+            .collect()
+    }
+
+    /// Generates samples from inputs evenly spaced on the line between two inputs,
+    /// to see how the output changes as the input moves from one to the other
+    /// 
+    /// ## Params
+    /// - A: The input of the first sample
+    /// - B: The input of the last sample
+    /// - Steps: The number of samples, including both ends
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// A or B doesn't have a value for every sensor
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{generation::GenNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = GenNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    /// let samples = net.interpolate(&[0.0, 0.0], &[1.0, 0.5], 3).unwrap();
+    /// assert_eq!(samples[1].seed.as_ref().unwrap().inputs, vec![0.5, 0.25]);
+    /// ```
+    pub fn interpolate(&self, a: &[f32], b: &[f32], steps: usize) -> Result<Vec<GeneratedSample>, DarjeelingError> {
+        let input_num: usize = self.node_array[self.sensor.unwrap()].len();
+        if a.len() != input_num || b.len() != input_num {
+            return Err(DarjeelingError::InvalidInput(format!("Interpolating between {} and {} values, but the model takes {}", a.len(), b.len(), input_num)));
+        }
+        Ok((0..steps)
+            .map(|step| if steps == 1 { 0.0 } else { step as f32 / (steps - 1) as f32 })
+            .map(|t| a.iter().zip(b).map(|(a, b)| a + (b - a) * t).collect::<Vec<f32>>())
+            .map(|inputs| self.generate(Input::new(inputs, None)))
+            .collect())
+    }
+
+    /// Copies every link weight and bias from a generation model with the same architecture,
+    /// for target networks, averaging snapshots or starting several runs from the same weights.
+    /// Everything else, like the constraints and the distinguishing model, is left as it is.
+    /// `clone()` copies the whole model instead.
+    /// 
+    /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The models have different numbers of layers, nodes or links
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{generation::GenNetwork, activation::ActivationFunction};
+    /// 
+    /// let trained = GenNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    /// let mut copy = GenNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    /// copy.copy_weights_from(&trained).unwrap();
+    /// assert_eq!(copy.to_darj_string(), trained.to_darj_string());
+    /// assert!(copy.copy_weights_from(&GenNetwork::new(2, 5, 3, 1, ActivationFunction::Sigmoid)).is_err());
+    /// ```
+    pub fn copy_weights_from(&mut self, other: &GenNetwork) -> Result<(), DarjeelingError> {
+        node::copy_weights(&mut self.node_array, &other.node_array)
+    }
+
+    /// Starts the network from the weights of a trained categorization network, which can make adversarial training much shorter,
+    /// since the layers start out already picking up on structure in the data instead of from noise.
+    /// The layers are lined up either from the inputs or from the answer layers, whichever matches more of them,
+    /// and every layer whose nodes and links match its partner's is copied. The rest, like a first layer reading a different number of inputs, keep their weights.
+    ///
+    /// ## Returns
+    /// The indexes of the layers that were copied, where 0 is the sensors
+    ///
+    /// ## Err
+    /// - ### ArchitectureMismatch
+    /// The networks have different activation functions, or no layer has the same shape as its partner
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{generation::GenNetwork, categorize::CatNetwork, activation::ActivationFunction};
+    ///
+    /// // Trained elsewhere on 4 features and 3 categories
+    /// let categorizer = CatNetwork::new(4, 8, 3, 2, ActivationFunction::Sigmoid);
+    /// // Turns 2 noise values into 4 features
+    /// let mut generator = GenNetwork::new(2, 8, 4, 2, ActivationFunction::Sigmoid);
+    /// // The second hidden layer reads 8 values from the first in both, so it's the one that's copied
+    /// assert_eq!(generator.warm_start_from(&categorizer).unwrap(), vec![2]);
+    /// ```
+    pub fn warm_start_from(&mut self, categorizer: &CatNetwork) -> Result<Vec<usize>, DarjeelingError> {
+        if categorizer.activation_func() != self.activation_function {
+            return Err(DarjeelingError::ArchitectureMismatch(format!("The categorizer uses {} and the generator uses {}", categorizer.activation_func(), self.activation_function)));
+        }
+        let from: &[Vec<Node>] = categorizer.layers();
+        let same_shape = |layer: &[Node], other: &[Node]| layer.len() == other.len() && layer.iter().zip(other).all(|(node, other_node)| node.links == other_node.links);
+        // Pairs of (generator layer, categorizer layer), skipping the sensors, which have no weights
+        let from_inputs: Vec<(usize, usize)> = (1..self.node_array.len().min(from.len())).map(|layer| (layer, layer)).collect();
+        let from_answers: Vec<(usize, usize)> = (1..self.node_array.len().min(from.len()))
+            .map(|back| (self.node_array.len() - back, from.len() - back))
+            .collect();
+        let matching = |pairs: Vec<(usize, usize)>| -> Vec<(usize, usize)> {
+            pairs.into_iter().filter(|(layer, other)| same_shape(&self.node_array[*layer], &from[*other])).collect()
+        };
+        let (from_inputs, from_answers) = (matching(from_inputs), matching(from_answers));
+        let mut pairs: Vec<(usize, usize)> = if from_answers.len() > from_inputs.len() { from_answers } else { from_inputs };
+        if pairs.is_empty() {
+            return Err(DarjeelingError::ArchitectureMismatch("No layer of the generator has the same shape as the categorizer's layer in its place".to_string()));
+        }
+        pairs.sort_unstable();
+        for (layer, other) in &pairs {
+            node::copy_weights(std::slice::from_mut(&mut self.node_array[*layer]), std::slice::from_ref(&from[*other]))?;
+        }
+        Ok(pairs.into_iter().map(|(layer, _)| layer).collect())
+    }
+
+    /// Runs one input through the network without changing it
+    fn generate(&self, seed: Input) -> GeneratedSample {
+        let mut output: Vec<f32> = self.forward(&seed.inputs);
+        self.constrain(&mut output);
+        GeneratedSample {
+            realism: self.realism(&output),
+            output,
+            seed: Some(seed)
+        }
+    }
+
+    /// Applies each answer node's constraint, if it has one, to its value
+    fn constrain(&self, output: &mut [f32]) {
+        output.iter_mut().zip(&self.constraints).for_each(|(value, constraint)| {
+            if let Some(constraint) = constraint {
+                *value = constraint.apply(*value);
+            }
+        });
+    }
+
+    /// The values of the answer nodes for the inputs, without changing the network
+    fn forward(&self, inputs: &[f32]) -> Vec<f32> {
+        let mut values: Vec<f32> = inputs.to_vec();
+        self.node_array.iter().skip(1).for_each(|layer| {
+            values = layer.iter().map(|node| node.output_for(&values, &self.activation_function)).collect();
+        });
+        values
     }
 
     /// Passes in data to the sensors, pushs data 'downstream' through the network
-    fn push_downstream(&mut self, data: &mut Vec<Input>, line: i32) {
+    fn push_downstream(&mut self, inputs: &[f32]) {
 
         // Passes in data for input layer
         for i in 0..self.node_array[self.sensor.unwrap()].len() {
-            let input  = data[line as usize].inputs[i];
+            let input  = inputs[i];
 
             self.node_array[self.sensor.unwrap()][i].cached_output = Some(input);
         }
@@ -270,22 +990,20 @@ impl GenNetwork {
         largest_node
     }
     /// Goes back through the network adjusting the weights of the all the neurons based on their error signal
-    fn backpropogate(&mut self, learning_rate: f32, mse: f32) {
+    /// Adjusts every weight from one signal per answer node, how much and which way its output should move
+    fn backpropogate(&mut self, learning_rate: f32, signals: &[f32]) {
         let hidden_layers = (self.node_array.len() - 2) as i32;
-        (self.node_array[self.answer.unwrap()])
-            .par_iter_mut()
-            .for_each(|answer_node| {
-                println!("Node: {:?}", answer_node);
-                answer_node.compute_answer_err_sig_gen(mse, &self.activation_function);
-                dbg_println!("Error: {:?}", answer_node.err_sig.unwrap());
-            });
+        let answer: usize = self.answer.unwrap();
+        let activation_function: &ActivationFunction = &self.activation_function;
+        self.node_array[answer].iter_mut().zip(signals).for_each(|(answer_node, signal)| {
+            println!("Node: {:?}", answer_node);
+            answer_node.compute_answer_err_sig_gen(*signal, activation_function);
+            dbg_println!("Error: {:?}", answer_node.err_sig.unwrap());
+        });
         self.adjust_hidden_weights(learning_rate, hidden_layers);
         // Adjusts weights for answer neurons
-        (self.node_array[self.answer.unwrap()])
-            .par_iter_mut()
-            .for_each(|node| {
-                node.adjust_weights(learning_rate);
-            });
+        let answer_nodes: &mut Vec<Node> = &mut self.node_array[self.answer.unwrap()];
+        parallel::for_each_mut(answer_nodes, |node| node.adjust_weights(learning_rate));
     }
 
     #[allow(non_snake_case)]
@@ -383,10 +1101,129 @@ impl GenNetwork {
                     ret.push(Types::String(buff));
                 }
             }
+            Types::Vector(_) => {
+                ret.push(Types::Vector(self.node_array[self.answer.unwrap()].iter().map(|node| node.cached_output.unwrap()).collect()));
+            }
+            Types::Category { .. } => {
+                let index = self.largest_node();
+                let name = match &brightest_node.category {
+                    Some(category) => format!("{:?}", category),
+                    None => index.to_string()
+                };
+                ret.push(Types::Category { index, name });
+            }
         };
         Ok(ret)
     }
 
+    /// The network as a [Graphviz](https://graphviz.org) digraph, with numbered answer nodes.
+    /// Positive links are red and negative ones blue, drawn thicker and darker the larger they are,
+    /// and hovering over a link shows its weight. Render it with `dot -Tsvg network.dot -o network.svg`.
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// std::fs::write("generator.dot", gen.to_dot()).unwrap();
+    /// ```
+    pub fn to_dot(&self) -> String {
+        visualize::dot(&self.node_array, &[], &[])
+    }
+
+    /// A compact text picture of the network, with a row for every node shading the size of each link into it and then its bias,
+    /// from blank for nothing to █ for the largest weight in the network
+    ///
+    /// ## Params
+    /// - Color: Whether to color positive weights red and negative ones blue with ANSI escape codes, for printing to a terminal
+    pub fn to_diagram(&self, color: bool) -> String {
+        visualize::diagram(&self.node_array, &[], &[], &self.activation_function, color)
+    }
+
+    /// Serializes the model in the .darj format without writing it anywhere,
+    /// for saving it somewhere other than a file, like browser storage
+    pub fn to_darj_string(&self) -> String {
+        let mut serialized = "".to_string();
+        println!("write, length: {}", self.node_array.len());
+        for i in 0..self.node_array.len() {
+            if i != 0 {
+                let _ = serialized.push_str("lb\n");
+            }
+            for j in 0..self.node_array[i].len() {
+                for k in 0..self.node_array[i][j].link_weights.len() {
+                    print!("{}", self.node_array[i][j].link_weights[k]);
+                    if k == self.node_array[i][j].link_weights.len() - 1 {
+                        let _ = serialized.push_str(format!("{}", self.node_array[i][j].link_weights[k]).as_str());
+                    } else {
+                        let _ = serialized.push_str(format!("{},", self.node_array[i][j].link_weights[k]).as_str());
+                    }                        
+                }
+                let _ = serialized.push_str(format!(";{}", self.node_array[i][j].b_weight.unwrap().to_string()).as_str()); 
+                let _ = serialized.push_str("\n");
+            }
+        }
+        serialized.push_str("lb\n");                    
+        serialized.push_str(format!("{}", self.activation_function).as_str());
+        serialized
+    }
+
+    /// Reads a model serialized in the .darj format from memory, the counterpart to [`to_darj_string`](fn@to_darj_string).
+    /// This is how models are loaded where there's no filesystem, like in the browser.
+    /// 
+    /// ## Err
+    /// - ### ModelParse
+    /// A line isn't a node or an activation function. The error has the line, column and text that couldn't be read.
+    /// - ### ActivationFunctionNotRead
+    /// There's no activation function line
+    /// - ### InvalidArchitecture
+    /// The layers don't fit together, like a node with more links than the layer before it has nodes
+    pub fn from_darj_str(serialized: &str) -> Result<GenNetwork, DarjeelingError> {
+        let mut node_array: Vec<Vec<Node>> = vec![];
+        let mut layer: Vec<Node> = vec![];
+        let mut activation: Option<ActivationFunction> = None;
+        for (line_number, i) in serialized.lines().enumerate().map(|(index, line)| (index + 1, line)) {
+            match i {
+                _ if i.trim() == "lb" => {
+                    node_array.push(layer.clone());
+                    layer = vec![];
+                },
+
+                _ if darj::is_activation_line(i) => activation = Some(darj::parse_activation(line_number, i)?),
+
+                _ => layer.push(darj::parse_node(line_number, i, node_array.is_empty())?)
+            }
+        }
+        darj::validate_architecture(&node_array, &layer)?;
+        let sensor: Option<usize> = Some(0);
+        let answer: Option<usize> = Some(node_array.len() - 1);
+        
+        let net = GenNetwork {
+            node_array,
+            sensor,
+            answer,
+            parameters: None,
+            activation_function: match activation {
+                Some(activation) => activation,
+                None => return Err(DarjeelingError::ActivationFunctionNotRead("No activation function line in the model".to_string()))
+            },
+            instance_noise: None,
+            adversarial_loss: AdversarialLoss::Standard,
+            collapse_threshold: default_collapse_threshold(),
+            cycles: vec![],
+            constraints: vec![],
+            ensemble: None,
+            replay: None,
+            equilibrium: None,
+            schedule: LearningRateSchedule::Constant,
+            discriminator_schedule: LearningRateSchedule::Constant,
+            sample_dump: None,
+            fidelity: None,
+            output_dir: None,
+            temp_dir: None,
+            discriminators: vec![]
+        };
+        // println!("node array {:?}", net.node_array);
+
+        Ok(net)
+    }
+
     /// Serializes a trained model as a .darj file so it can be used later
     /// 
     /// ## Returns
@@ -401,36 +1238,17 @@ impl GenNetwork {
     /// Something else went wrong
     /// 
     /// Wraps error
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn write_model(&mut self, name: &str) -> Result<String, DarjeelingError> {
         let mut rng = rand::thread_rng();
         let file_num: u32 = rng.gen();
-        let model_name: String = format!("model_{}_{}.darj", name, file_num);
+        let model_name: String = config::file_path(config::resolve_dir(self.output_dir.as_deref(), config::OUTPUT_DIR_VAR), &format!("model_{}_{}.darj", name, file_num))?;
 
         match Path::new(&model_name).try_exists() {
 
             Ok(false) => {
                 let _file: fs::File = fs::File::create(&model_name).unwrap();
-                let mut serialized = "".to_string();
-                println!("write, length: {}", self.node_array.len());
-                for i in 0..self.node_array.len() {
-                    if i != 0 {
-                        let _ = serialized.push_str("lb\n");
-                    }
-                    for j in 0..self.node_array[i].len() {
-                        for k in 0..self.node_array[i][j].link_weights.len() {
-                            print!("{}", self.node_array[i][j].link_weights[k]);
-                            if k == self.node_array[i][j].link_weights.len() - 1 {
-                                let _ = serialized.push_str(format!("{}", self.node_array[i][j].link_weights[k]).as_str());
-                            } else {
-                                let _ = serialized.push_str(format!("{},", self.node_array[i][j].link_weights[k]).as_str());
-                            }                        
-                        }
-                        let _ = serialized.push_str(format!(";{}", self.node_array[i][j].b_weight.unwrap().to_string()).as_str()); 
-                        let _ = serialized.push_str("\n");
-                    }
-                }
-                serialized.push_str("lb\n");                    
-                serialized.push_str(format!("{}", self.activation_function).as_str());
+                let serialized: String = self.to_darj_string();
                 // println!("Serialized: {:?}", serialized);
                 match fs::write(&model_name, serialized) {
                     Ok(()) => {
@@ -459,6 +1277,7 @@ impl GenNetwork {
     /// 
     /// ## Err
     /// If the file cannot be read, or if the file does not contain a valid serialized Neural Network
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn read_model(model_name: String) -> Result<GenNetwork, DarjeelingError> {
 
         println!("Loading model");
@@ -470,69 +1289,118 @@ impl GenNetwork {
             Err(error) => return Err(DarjeelingError::ReadModelFailed(model_name.clone() + ";" +  &error.to_string()))
         };
  
-        let mut node_array: Vec<Vec<Node>> = vec![];
-        let mut layer: Vec<Node> = vec![];
-        let mut activation: Option<ActivationFunction> = None;
-        for i in serialized_net.lines() {
-            match i {
-                "sigmoid" => activation = Some(ActivationFunction::Sigmoid),
+        GenNetwork::from_darj_str(&serialized_net)
+    }
 
-                "linear" => activation = Some(ActivationFunction::Linear),
+    /// Serializes the network together with its distinguishing model and training state,
+    /// so an adversarial run can be saved and [resumed](fn@from_bundle_str) as one
+    /// 
+    /// ## Err
+    /// - ### UnknownError
+    /// The bundle couldn't be serialized
+    pub fn to_bundle_string(&self) -> Result<String, DarjeelingError> {
+        let bundle = Bundle {
+            generator: self.to_darj_string(),
+            discriminator: match self.discriminators.first() {
+                Some(discriminator) => Some(discriminator.net.to_darj_string()?),
+                None => None
+            },
+            instance_noise: self.instance_noise,
+            adversarial_loss: self.adversarial_loss,
+            collapse_threshold: self.collapse_threshold,
+            cycles: self.cycles.clone(),
+            constraints: self.constraints.clone(),
+            ensemble: self.ensemble,
+            replay: self.replay.clone(),
+            equilibrium: self.equilibrium,
+            schedule: self.schedule,
+            discriminator_schedule: self.discriminator_schedule,
+            other_discriminators: self.discriminators.iter().skip(1).map(|discriminator| discriminator.net.to_darj_string()).collect::<Result<Vec<String>, DarjeelingError>>()?
+        };
+        match serde_json::to_string(&bundle) {
+            Ok(json) => Ok(json),
+            Err(error) => Err(DarjeelingError::UnknownError(error.to_string()))
+        }
+    }
 
-                "tanh" => activation = Some(ActivationFunction::Tanh),
- 
-                // "step" => activation = Some(ActivationFunction::Step),
-
-                _ => {
-                
-                    if i.trim() == "lb" {
-                        node_array.push(layer.clone());
-                        // println!("pushed layer {:?}", layer.clone());
-                        layer = vec![];
-                        continue;
-                    }
-                    #[allow(unused_mut)]
-                    let mut node: Option<Node>;
-                    if node_array.len() == 0 {
-                        let b_weight: Vec<&str> = i.split(";").collect();
-                        // println!("b_weight: {:?}", b_weight);
-                        node = Some(Node::new(&vec![], Some(b_weight[1].parse().unwrap())));
-                    } else {
-                        let node_data: Vec<&str> = i.trim().split(";").collect();
-                        let str_weight_array: Vec<&str> = node_data[0].split(",").collect();
-                        let mut weight_array: Vec<f32> = vec![];
-                        let b_weight: &str = node_data[1];
-                        // println!("node_data: {:?}", node_data);
-                        // println!("array {:?}", str_weight_array);
-                        for weight in 0..str_weight_array.len() {
-                            // println!("testing here {:?}", str_weight_array[weight]);
-                            let val: f32 = str_weight_array[weight].parse().unwrap();
-                            weight_array.push(val);
-                        }
-                        // print!("{}", b_weight);
-                        node = Some(Node::new(&weight_array, Some(b_weight.parse().unwrap())));
-                    }
-                    
-                    layer.push(node.expect("Both cases provide a Some value for node"));
-                    // println!("layer: {:?}", layer.clone())
-                }
+    /// Reads a network saved with [`to_bundle_string`](fn@to_bundle_string).
+    /// Training it again carries on counting cycles and fading instance noise from where it stopped.
+    /// 
+    /// ## Err
+    /// - ### InvalidMetadataRead
+    /// The text isn't a bundle
+    /// - ### ModelParse
+    /// One of the networks in the bundle isn't valid
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{generation::GenNetwork, activation::ActivationFunction};
+    /// 
+    /// let net = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    /// let bundle: String = net.to_bundle_string().unwrap();
+    /// let read: GenNetwork = GenNetwork::from_bundle_str(&bundle).unwrap();
+    /// assert_eq!(read.to_darj_string(), net.to_darj_string());
+    /// ```
+    pub fn from_bundle_str(serialized: &str) -> Result<GenNetwork, DarjeelingError> {
+        let bundle: Bundle = match serde_json::from_str(serialized) {
+            Ok(bundle) => bundle,
+            Err(error) => return Err(DarjeelingError::InvalidMetadataRead(error.to_string() + "; Bundle"))
+        };
+        let mut net: GenNetwork = GenNetwork::from_darj_str(&bundle.generator)?;
+        net.discriminators = bundle.discriminator.iter().chain(&bundle.other_discriminators)
+            .map(|discriminator| Ok(Discriminator { net: CatNetwork::from_darj_str(discriminator)? }))
+            .collect::<Result<Vec<Discriminator>, DarjeelingError>>()?;
+        net.instance_noise = bundle.instance_noise;
+        net.adversarial_loss = bundle.adversarial_loss;
+        net.collapse_threshold = bundle.collapse_threshold;
+        net.cycles = bundle.cycles;
+        net.constraints = bundle.constraints;
+        net.ensemble = bundle.ensemble;
+        net.replay = bundle.replay;
+        net.equilibrium = bundle.equilibrium;
+        net.schedule = bundle.schedule;
+        net.discriminator_schedule = bundle.discriminator_schedule;
+        Ok(net)
+    }
+
+    /// Writes the [bundle](fn@to_bundle_string) to a file named `bundle_<name>_<number>.json`, in the [output directory](fn@set_output_dir) if there is one
+    /// 
+    /// ## Returns
+    /// The name of the file
+    /// 
+    /// ## Err
+    /// - ### WriteModelFailed
+    /// The file couldn't be written
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write_bundle(&self, name: &str) -> Result<String, DarjeelingError> {
+        let serialized: String = self.to_bundle_string()?;
+        let dir: Option<PathBuf> = config::resolve_dir(self.output_dir.as_deref(), config::OUTPUT_DIR_VAR);
+        let mut rng = rand::thread_rng();
+        loop {
+            let bundle_name: String = config::file_path(dir.clone(), &format!("bundle_{}_{}.json", name, rng.gen::<u32>()))?;
+            match Path::new(&bundle_name).try_exists() {
+                Ok(false) => return match fs::write(&bundle_name, &serialized) {
+                    Ok(()) => Ok(bundle_name),
+                    Err(error) => Err(DarjeelingError::WriteModelFailed(bundle_name + ";" + &error.to_string()))
+                },
+                Ok(true) => continue,
+                Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
             }
-            
         }
-        // println!("node array size {}", node_array.len());
-        let sensor: Option<usize> = Some(0);
-        let answer: Option<usize> = Some(node_array.len() - 1);
-        
-        let net = GenNetwork {
-            node_array,
-            sensor,
-            answer,
-            parameters: None,
-            activation_function: activation.unwrap()
-        };
-        // println!("node array {:?}", net.node_array);
+    }
 
-        Ok(net)
+    /// Reads a bundle written by [`write_bundle`](fn@write_bundle)
+    /// 
+    /// ## Err
+    /// - ### ReadModelFailed
+    /// The file couldn't be read
+    /// - The rest are the same as [`from_bundle_str`](fn@from_bundle_str)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_bundle(bundle_name: &str) -> Result<GenNetwork, DarjeelingError> {
+        match fs::read_to_string(bundle_name) {
+            Ok(serialized) => GenNetwork::from_bundle_str(&serialized),
+            Err(error) => Err(DarjeelingError::ReadModelFailed(bundle_name.to_string() + ";" + &error.to_string()))
+        }
     }
 
     pub fn add_hidden_layer_with_size(&mut self, size: usize) {
@@ -0,0 +1,167 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    parallel,
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    dataset::Dataset,
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// The classifier that picks between the subcategories of one category in a [`HierarchicalClassifier`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HierarchyBranch {
+    parent: Option<Types>,
+    children: Vec<Types>,
+    network: Option<CatNetwork>
+}
+
+impl HierarchyBranch {
+
+    /// The category being split up, or None for the top of the tree
+    pub fn parent(&self) -> Option<&Types> {
+        self.parent.as_ref()
+    }
+
+    /// The subcategories seen in the training data, sorted
+    pub fn children(&self) -> &[Types] {
+        &self.children
+    }
+
+    /// None when there's only one subcategory, which is always chosen
+    pub fn network(&self) -> Option<&CatNetwork> {
+        self.network.as_ref()
+    }
+}
+
+/// Classifies through a tree of categories, like a coarse label then a fine label.
+/// A network at the top picks a coarse category, then that category's own network picks between its subcategories,
+/// and so on down the tree, so every network only has to tell apart a few categories that really are alike.
+///
+/// ## Examples
+/// ```ignore
+/// let tree = vec![
+///     (Types::String("cat".to_string()), Types::String("animal".to_string())),
+///     (Types::String("dog".to_string()), Types::String("animal".to_string())),
+///     (Types::String("car".to_string()), Types::String("vehicle".to_string()))
+/// ];
+/// let classifier = HierarchicalClassifier::train(&data, &tree, &TrainingConfig::default(), 500, true)?;
+/// // [animal, dog]
+/// let path = classifier.predict_path(&input);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HierarchicalClassifier {
+    /// Every category in the tree with its parent
+    parents: Vec<(Types, Types)>,
+    branches: Vec<HierarchyBranch>
+}
+
+impl HierarchicalClassifier {
+
+    /// Trains a network for the top of the tree and for every category with subcategories in the data.
+    /// Each network trains on the inputs whose answers are under its category, labeled with the subcategory on their way down.
+    ///
+    /// ## Params
+    /// - Data: Every input needs an answer. Answers can be at any depth, and an answer that's a coarse category
+    ///   only trains the networks above it.
+    /// - Tree: Every subcategory with its parent category. Categories without a parent are at the top.
+    /// - Config: The architecture, learning rate and target error percent of every network
+    /// - Max Epochs: Stops a network after this many epochs, even if it hasn't reached the target error percent
+    /// - Parallel: Whether to train the networks on every core at once, which needs the `parallel` feature
+    ///
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// There's no data, or an input doesn't have an answer
+    /// - ### InvalidInput
+    /// A category has more than one parent, or is its own ancestor
+    /// - The rest are the same as [`step_epoch`](fn@CatNetwork::step_epoch)
+    pub fn train(data: &[Input], tree: &[(Types, Types)], config: &TrainingConfig, max_epochs: usize, parallel: bool) -> Result<HierarchicalClassifier, DarjeelingError> {
+        if data.is_empty() || data.iter().any(|input| input.answer.is_none()) {
+            return Err(DarjeelingError::InvalidTrainingData("A hierarchical classifier needs data where every input has an answer".to_string()));
+        }
+        if let Some((i, (child, _))) = tree.iter().enumerate().find(|(i, (child, _))| tree[..*i].iter().any(|(other, _)| other == child)) {
+            return Err(DarjeelingError::InvalidInput(format!("{} has more than one parent, the second at pair {}", child, i)));
+        }
+        let parents: Vec<(Types, Types)> = tree.to_vec();
+
+        let mut paths: Vec<Vec<Types>> = Vec::with_capacity(data.len());
+        for input in data {
+            paths.push(path_to(&parents, input.answer.as_ref().expect("Every input has an answer"))?);
+        }
+
+        // The top of the tree, then every category that inputs pass through
+        let mut splits: Vec<Option<Types>> = vec![None];
+        for path in &paths {
+            for category in &path[..path.len() - 1] {
+                if !splits.contains(&Some(category.clone())) {
+                    splits.push(Some(category.clone()));
+                }
+            }
+        }
+
+        let branches: Vec<Result<HierarchyBranch, DarjeelingError>> = parallel::map(&splits, parallel, |parent| train_branch(data, &paths, parent.clone(), config, max_epochs));
+        Ok(HierarchicalClassifier { parents, branches: branches.into_iter().collect::<Result<_, _>>()? })
+    }
+
+    pub fn branches(&self) -> &[HierarchyBranch] {
+        &self.branches
+    }
+
+    /// The category a subcategory is under, or None if it's at the top of the tree
+    pub fn parent(&self, category: &Types) -> Option<&Types> {
+        self.parents.iter().find(|(child, _)| child == category).map(|(_, parent)| parent)
+    }
+
+    /// Picks a category at the top of the tree, then one of its subcategories, and so on,
+    /// until a category with no subcategories in the training data
+    ///
+    /// ## Returns
+    /// Every category picked, coarsest first
+    pub fn predict_path(&self, input: &Input) -> Vec<Types> {
+        let mut path: Vec<Types> = vec![];
+        while let Some(branch) = self.branches.iter().find(|branch| branch.parent.as_ref() == path.last()) {
+            let chosen: Types = match &branch.network {
+                Some(network) => network.predict_category(&input.inputs),
+                None => branch.children[0].clone()
+            };
+            path.push(chosen);
+        }
+        path
+    }
+}
+
+/// Every category from the top of the tree down to the answer
+fn path_to(parents: &[(Types, Types)], answer: &Types) -> Result<Vec<Types>, DarjeelingError> {
+    let mut path: Vec<Types> = vec![answer.clone()];
+    while let Some((_, parent)) = parents.iter().find(|(child, _)| child == path.last().expect("The path starts with the answer")) {
+        if path.contains(parent) {
+            return Err(DarjeelingError::InvalidInput(format!("{} is its own ancestor", parent)));
+        }
+        path.push(parent.clone());
+    }
+    path.reverse();
+    Ok(path)
+}
+
+/// Trains the network that picks between the subcategories of one category
+fn train_branch(data: &[Input], paths: &[Vec<Types>], parent: Option<Types>, config: &TrainingConfig, max_epochs: usize) -> Result<HierarchyBranch, DarjeelingError> {
+    let depth: usize = match &parent {
+        Some(parent) => paths.iter().find_map(|path| path.iter().position(|category| category == parent)).expect("Splits come from the paths") + 1,
+        None => 0
+    };
+    let mut branch_data: Vec<Input> = data.iter().zip(paths).filter(|(_, path)| {
+        path.len() > depth && (depth == 0 || path.get(depth - 1) == parent.as_ref())
+    }).map(|(input, path)| Input::new(input.inputs.clone(), Some(path[depth].clone()))).collect();
+
+    let children: Vec<Types> = Dataset::new(branch_data.clone()).sorted_categories();
+    let network: Option<CatNetwork> = match children.len() {
+        1 => None,
+        _ => {
+            let mut network: CatNetwork = CatNetwork::from_config(data[0].inputs.len() as i32, children.len() as i32, config);
+            network.learn_epochs(&mut branch_data, children.clone(), config, max_epochs)?;
+            Some(network)
+        }
+    };
+    Ok(HierarchyBranch { parent, children, network })
+}
@@ -0,0 +1,391 @@
+use std::{fs, time::Duration};
+use serde::{Serialize, Deserialize};
+use crate::error::DarjeelingError;
+
+/// How training went during one epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EpochRecord {
+    /// Counted from 1
+    pub epoch: usize,
+    /// The mean squared error of the chosen answer nodes over the epoch
+    pub loss: f32,
+    /// The fraction of inputs categorized correctly
+    pub accuracy: f32,
+    pub learning_rate: f32,
+    /// The L2 norm of every weight's gradient, averaged over the epoch's inputs
+    #[serde(default)]
+    pub grad_norm: f32,
+    /// The largest gradient norm of any input in the epoch
+    #[serde(default)]
+    pub max_grad_norm: f32,
+    /// Weight and error signal statistics for every layer after the inputs,
+    /// empty unless [collecting them](fn@crate::categorize::CatNetwork::set_collect_layer_stats) is turned on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<LayerStats>,
+    /// The loss on the [holdout](fn@crate::categorize::CatNetwork::set_holdout) at the end of the epoch, None without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holdout_loss: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holdout_accuracy: Option<f32>,
+    /// Where every input trained on came from in the data as it was before the epoch was shuffled, in the order they were trained on.
+    /// Empty unless [recording it](fn@crate::categorize::CatNetwork::set_record_order) is turned on, or the data wasn't shuffled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<usize>,
+    /// How long every layer after the inputs took to train over the epoch,
+    /// empty unless [profiling them](fn@crate::categorize::CatNetwork::set_profile_layers) is turned on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timings: Vec<LayerTiming>
+}
+
+/// The spread of one layer's weights at the end of an epoch, and of its error signals during it.
+/// Error signals shrinking towards 0 layer after layer means vanishing gradients, growing means exploding ones.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct LayerStats {
+    /// The index of the layer, where 0 is the inputs
+    pub layer: usize,
+    /// Includes the bias weights
+    pub weight_mean: f32,
+    pub weight_std: f32,
+    /// The largest weight, ignoring its sign
+    pub weight_max: f32,
+    pub err_sig_mean: f32,
+    pub err_sig_std: f32,
+    /// The largest error signal, ignoring its sign
+    pub err_sig_max: f32
+}
+
+/// The time one layer spent in the forward and backward passes, over every input of an epoch.
+/// The layers that take the most time are where faster math pays off the most.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub struct LayerTiming {
+    /// The index of the layer, where 0 is the inputs
+    pub layer: usize,
+    /// Working out the layer's outputs from the layer before it
+    pub forward: Duration,
+    /// Working out the layer's error signals and adjusting its weights.
+    /// Regularization and weight constraints run over every layer at once, so they aren't counted.
+    pub backward: Duration
+}
+
+impl LayerTiming {
+
+    pub fn total(&self) -> Duration {
+        self.forward + self.backward
+    }
+}
+
+/// What to do when a gradient norm grows past a bound, which usually means the weights are about to become NaN
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, history::GradientAlarm};
+///
+/// let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+/// // Stops training with an ExplodingGradient error instead of carrying on with broken weights
+/// net.set_gradient_alarm(Some(GradientAlarm::abort(100.0)));
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct GradientAlarm {
+    /// The largest gradient norm allowed
+    pub bound: f32,
+    /// Whether training stops with an error, instead of only printing a warning at the end of the epoch
+    pub abort: bool
+}
+
+impl GradientAlarm {
+
+    /// Prints a warning at the end of any epoch with a gradient norm past the bound
+    pub fn warn(bound: f32) -> GradientAlarm {
+        GradientAlarm { bound, abort: false }
+    }
+
+    /// Stops training as soon as a gradient norm is past the bound
+    pub fn abort(bound: f32) -> GradientAlarm {
+        GradientAlarm { bound, abort: true }
+    }
+
+    /// Whether the norm is past the bound. A NaN or infinite norm always is.
+    pub fn exceeded(&self, grad_norm: f32) -> bool {
+        !grad_norm.is_finite() || grad_norm > self.bound
+    }
+}
+
+/// What's done to a hidden node that's stayed dead for long enough
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum NeuronAction {
+    /// Draws the node's weights and bias again, the same way new networks start
+    Reinitialize,
+    /// Takes the node and its links out of the network. The last node of a layer is never removed.
+    Remove
+}
+
+/// Watches the output of every hidden node while training, and re-randomizes or removes the ones that
+/// stay [dead](crate::inspect::DeadNeuron) for a number of epochs in a row
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, history::DeadNeuronPolicy};
+///
+/// let mut net = CatNetwork::new(2, 8, 2, 3, ActivationFunction::Sigmoid);
+/// // Gives any node whose output moved less than 1e-3 over 5 epochs in a row new weights
+/// net.set_dead_neuron_policy(Some(DeadNeuronPolicy::reinitialize(5, 1e-3)));
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct DeadNeuronPolicy {
+    /// How many epochs in a row a node has to be dead before it's acted on
+    pub patience: usize,
+    /// A node is dead for an epoch when the standard deviation of its outputs over the epoch is at or under this
+    pub tolerance: f32,
+    pub action: NeuronAction
+}
+
+impl DeadNeuronPolicy {
+
+    pub fn reinitialize(patience: usize, tolerance: f32) -> DeadNeuronPolicy {
+        DeadNeuronPolicy { patience, tolerance, action: NeuronAction::Reinitialize }
+    }
+
+    pub fn remove(patience: usize, tolerance: f32) -> DeadNeuronPolicy {
+        DeadNeuronPolicy { patience, tolerance, action: NeuronAction::Remove }
+    }
+}
+
+/// A hidden node that a [dead neuron policy](DeadNeuronPolicy) acted on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct NeuronEvent {
+    /// The epoch that had just finished, counted from 1
+    pub epoch: usize,
+    pub layer: usize,
+    /// The node's index in its layer when it was acted on
+    pub node: usize,
+    pub action: NeuronAction,
+    /// The standard deviation of the node's outputs in its last epoch
+    pub std: f32
+}
+
+/// Keeps a mean, standard deviation and largest magnitude without storing every value
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub(crate) struct RunningStats {
+    count: usize,
+    sum: f64,
+    sum_squares: f64,
+    max: f32
+}
+
+impl RunningStats {
+
+    pub(crate) fn add(&mut self, value: f32) {
+        self.count += 1;
+        self.sum += value as f64;
+        self.sum_squares += (value as f64).powi(2);
+        self.max = self.max.max(value.abs());
+    }
+
+    /// The mean, standard deviation and largest magnitude
+    pub(crate) fn stats(&self) -> (f32, f32, f32) {
+        if self.count == 0 {
+            return (0.0, 0.0, 0.0);
+        }
+        let mean: f64 = self.sum / self.count as f64;
+        let variance: f64 = (self.sum_squares / self.count as f64 - mean.powi(2)).max(0.0);
+        (mean as f32, variance.sqrt() as f32, self.max)
+    }
+}
+
+/// Adds up values with Kahan summation, carrying the rounding error of each addition into the next,
+/// so a long run of small values isn't lost against a large total
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct CompensatedSum {
+    sum: f32,
+    compensation: f32
+}
+
+impl CompensatedSum {
+
+    pub(crate) fn add(&mut self, value: f32) {
+        let adjusted: f32 = value - self.compensation;
+        let next: f32 = self.sum + adjusted;
+        self.compensation = (next - self.sum) - adjusted;
+        self.sum = next;
+    }
+
+    pub(crate) fn value(&self) -> f32 {
+        self.sum
+    }
+
+    /// The compensated sum of every value, in order
+    pub(crate) fn of<I: IntoIterator<Item = f32>>(values: I) -> f32 {
+        let mut sum = CompensatedSum::default();
+        values.into_iter().for_each(|value| sum.add(value));
+        sum.value()
+    }
+}
+
+/// Every epoch of a training run, in order
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TrainingHistory {
+    epochs: Vec<EpochRecord>,
+    /// Hidden nodes re-randomized or removed along the way
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    events: Vec<NeuronEvent>
+}
+
+impl TrainingHistory {
+
+    pub fn new() -> TrainingHistory {
+        TrainingHistory { epochs: vec![], events: vec![] }
+    }
+
+    pub fn record(&mut self, record: EpochRecord) {
+        self.epochs.push(record);
+    }
+
+    pub fn record_event(&mut self, event: NeuronEvent) {
+        self.events.push(event);
+    }
+
+    /// Every hidden node a [dead neuron policy](DeadNeuronPolicy) acted on, in order
+    pub fn events(&self) -> &[NeuronEvent] {
+        &self.events
+    }
+
+    pub fn epochs(&self) -> &[EpochRecord] {
+        &self.epochs
+    }
+
+    /// The order an epoch trained on the data in, as indexes into the data as it was first given to training.
+    /// Training shuffles the data in place, so each epoch's [order](EpochRecord::order) starts from where the last one left it,
+    /// and this follows them all back to the start. None if the epoch isn't in the history, or any epoch up to it has no order recorded.
+    ///
+    /// ## Params
+    /// - Epoch: Counted from 1
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::history::{TrainingHistory, EpochRecord};
+    ///
+    /// let epoch = |epoch: usize, order: Vec<usize>| EpochRecord { epoch, loss: 0.0, accuracy: 0.0, learning_rate: 0.1, grad_norm: 0.0, max_grad_norm: 0.0, layers: vec![], holdout_loss: None, holdout_accuracy: None, order, timings: vec![] };
+    /// let mut history = TrainingHistory::new();
+    /// history.record(epoch(1, vec![2, 0, 1]));
+    /// history.record(epoch(2, vec![1, 2, 0]));
+    /// // The second epoch started from [c, a, b], and trained on a, b, then c
+    /// assert_eq!(history.sample_order(2), Some(vec![0, 1, 2]));
+    /// ```
+    pub fn sample_order(&self, epoch: usize) -> Option<Vec<usize>> {
+        if epoch == 0 || epoch > self.epochs.len() {
+            return None;
+        }
+        let mut order: Vec<usize> = (0..self.epochs[0].order.len()).collect();
+        for record in &self.epochs[..epoch] {
+            if record.order.is_empty() || record.order.len() != order.len() {
+                return None;
+            }
+            order = record.order.iter().map(|i| order[*i]).collect();
+        }
+        Some(order)
+    }
+
+    /// The most recent epoch, None if nothing has been trained
+    pub fn last(&self) -> Option<&EpochRecord> {
+        self.epochs.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.epochs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.epochs.is_empty()
+    }
+
+    /// The [layer timings](EpochRecord::timings) of every epoch added together, empty if none were profiled
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::history::{TrainingHistory, EpochRecord, LayerTiming};
+    /// use std::time::Duration;
+    ///
+    /// let timing = |forward: u64, backward: u64| LayerTiming { layer: 1, forward: Duration::from_millis(forward), backward: Duration::from_millis(backward) };
+    /// let epoch = |epoch: usize, timings: Vec<LayerTiming>| EpochRecord { epoch, loss: 0.0, accuracy: 0.0, learning_rate: 0.1, grad_norm: 0.0, max_grad_norm: 0.0, layers: vec![], holdout_loss: None, holdout_accuracy: None, order: vec![], timings };
+    /// let mut history = TrainingHistory::new();
+    /// history.record(epoch(1, vec![timing(3, 5)]));
+    /// history.record(epoch(2, vec![timing(2, 4)]));
+    /// assert_eq!(history.layer_timings(), vec![timing(5, 9)]);
+    /// ```
+    pub fn layer_timings(&self) -> Vec<LayerTiming> {
+        let mut totals: Vec<LayerTiming> = vec![];
+        self.epochs.iter().flat_map(|record| record.timings.iter()).for_each(|timing| {
+            match totals.iter_mut().find(|total| total.layer == timing.layer) {
+                Some(total) => {
+                    total.forward += timing.forward;
+                    total.backward += timing.backward;
+                },
+                None => totals.push(*timing)
+            }
+        });
+        totals.sort_by_key(|timing| timing.layer);
+        totals
+    }
+
+    /// Formats the history as CSV, with a header row of `epoch,loss,accuracy,learning_rate`,
+    /// followed by `holdout_loss,holdout_accuracy` if any epoch was evaluated on a holdout.
+    /// Layer statistics are only included in [JSON](fn@to_json).
+    pub fn csv(&self) -> String {
+        let holdout: bool = self.epochs.iter().any(|record| record.holdout_loss.is_some());
+        let mut csv: String = match holdout {
+            true => "epoch,loss,accuracy,learning_rate,holdout_loss,holdout_accuracy\n".to_string(),
+            false => "epoch,loss,accuracy,learning_rate\n".to_string()
+        };
+        let optional = |value: Option<f32>| value.map(|value| value.to_string()).unwrap_or_default();
+        self.epochs.iter().for_each(|record| {
+            csv += &format!("{},{},{},{}", record.epoch, record.loss, record.accuracy, record.learning_rate);
+            if holdout {
+                csv += &format!(",{},{}", optional(record.holdout_loss), optional(record.holdout_accuracy));
+            }
+            csv += "\n";
+        });
+        csv
+    }
+
+    /// Saves the history as CSV, so loss curves can be plotted in a spreadsheet or other tools
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The file couldn't be written
+    pub fn to_csv(&self, path: &str) -> Result<(), DarjeelingError> {
+        match fs::write(path, self.csv()) {
+            Ok(()) => Ok(()),
+            Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", path, error)))
+        }
+    }
+
+    /// Saves a [chart](fn@crate::plot::history_svg) of the loss and accuracy of every epoch as an SVG
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The file couldn't be written
+    #[cfg(feature = "plot")]
+    pub fn to_svg(&self, path: &str) -> Result<(), DarjeelingError> {
+        match fs::write(path, crate::plot::history_svg(self)) {
+            Ok(()) => Ok(()),
+            Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", path, error)))
+        }
+    }
+
+    /// Formats the history as a JSON list of epochs, with their layer statistics if there are any
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::history::{TrainingHistory, EpochRecord};
+    ///
+    /// let mut history = TrainingHistory::new();
+    /// history.record(EpochRecord { epoch: 1, loss: 0.5, accuracy: 0.75, learning_rate: 0.1, grad_norm: 0.25, max_grad_norm: 0.5, layers: vec![], holdout_loss: None, holdout_accuracy: None, order: vec![], timings: vec![] });
+    /// assert_eq!(history.to_json().unwrap(), r#"[{"epoch":1,"loss":0.5,"accuracy":0.75,"learning_rate":0.1,"grad_norm":0.25,"max_grad_norm":0.5}]"#);
+    /// ```
+    pub fn to_json(&self) -> Result<String, DarjeelingError> {
+        match serde_json::to_string(&self.epochs) {
+            Ok(json) => Ok(json),
+            Err(error) => Err(DarjeelingError::UnknownError(error.to_string()))
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use crate::{
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// The IDX type code for unsigned bytes, the only type used by MNIST-style datasets
+static IDX_UNSIGNED_BYTE: u8 = 0x08;
+
+/// Reads an IDX image file and an IDX label file into normalized Inputs.
+/// Every pixel is divided by 255 so the sensors receive values between 0 and 1,
+/// and the label is stored as an integer answer.
+///
+/// ## Params
+/// - Images Path: The path to the IDX file containing the images (ex: train-images-idx3-ubyte)
+/// - Labels Path: The path to the IDX file containing the labels (ex: train-labels-idx1-ubyte)
+///
+/// ## Returns
+/// One Input per image, in the same order as the file
+///
+/// ## Err
+/// - ### ReadDataFailed
+/// One of the files couldn't be read
+/// - ### InvalidIdxFormat
+/// One of the files isn't a valid unsigned byte IDX file, or the number of images and labels don't match
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::{idx, input::Input};
+///
+/// let data: Vec<Input> = idx::load_idx("train-images-idx3-ubyte", "train-labels-idx1-ubyte").unwrap();
+/// ```
+pub fn load_idx(images_path: &str, labels_path: &str) -> Result<Vec<Input>, DarjeelingError> {
+    let images: Vec<Vec<f32>> = read_idx_images(images_path)?;
+    let labels: Vec<u8> = read_idx_labels(labels_path)?;
+
+    if images.len() != labels.len() {
+        return Err(DarjeelingError::InvalidIdxFormat(format!(
+            "{} has {} images but {} has {} labels", images_path, images.len(), labels_path, labels.len()
+        )));
+    }
+
+    Ok(images
+        .into_iter()
+        .zip(labels)
+        .map(|(pixels, label)| Input::new(pixels, Some(Types::Integer(label as i32))))
+        .collect())
+}
+
+/// Reads an IDX image file, returning every image flattened row by row with pixels normalized between 0 and 1
+pub fn read_idx_images(path: &str) -> Result<Vec<Vec<f32>>, DarjeelingError> {
+    let bytes: Vec<u8> = read_file(path)?;
+    let (dims, data) = parse_idx(&bytes, path)?;
+    if dims.is_empty() {
+        return Err(DarjeelingError::InvalidIdxFormat(format!("{} has no dimensions", path)));
+    }
+
+    let count = dims[0];
+    // With no images the data is empty whatever the image size, so it has to be checked on its own
+    let image_size: usize = product(&dims[1..], path)?;
+    if image_size == 0 {
+        return Err(DarjeelingError::InvalidIdxFormat(format!("{} has images with no pixels", path)));
+    }
+
+    Ok((0..count)
+        .map(|i| {
+            data[i * image_size..(i + 1) * image_size]
+                .iter()
+                .map(|pixel| *pixel as f32 / 255.0)
+                .collect()
+        })
+        .collect())
+}
+
+/// Reads an IDX label file, returning the raw label of every item
+pub fn read_idx_labels(path: &str) -> Result<Vec<u8>, DarjeelingError> {
+    let bytes: Vec<u8> = read_file(path)?;
+    let (dims, data) = parse_idx(&bytes, path)?;
+    if dims.len() != 1 {
+        return Err(DarjeelingError::InvalidIdxFormat(format!("{} should have 1 dimension, found {}", path, dims.len())));
+    }
+
+    Ok(data.to_vec())
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, DarjeelingError> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(bytes),
+        Err(error) => Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+    }
+}
+
+/// Splits an IDX file into its dimensions and its data, checking the header along the way
+fn parse_idx<'a>(bytes: &'a [u8], path: &str) -> Result<(Vec<usize>, &'a [u8]), DarjeelingError> {
+    if bytes.len() < 4 || bytes[0] != 0 || bytes[1] != 0 {
+        return Err(DarjeelingError::InvalidIdxFormat(format!("{} doesn't start with an IDX magic number", path)));
+    }
+    if bytes[2] != IDX_UNSIGNED_BYTE {
+        return Err(DarjeelingError::InvalidIdxFormat(format!("{} has data type {:#04x}, only unsigned bytes (0x08) are supported", path, bytes[2])));
+    }
+
+    let dim_count = bytes[3] as usize;
+    let header_len = 4 + dim_count * 4;
+    if bytes.len() < header_len {
+        return Err(DarjeelingError::InvalidIdxFormat(format!("{} ends before its header is finished", path)));
+    }
+
+    let dims: Vec<usize> = (0..dim_count)
+        .map(|i| {
+            let start = 4 + i * 4;
+            u32::from_be_bytes([bytes[start], bytes[start + 1], bytes[start + 2], bytes[start + 3]]) as usize
+        })
+        .collect();
+
+    let data = &bytes[header_len..];
+    let expected: usize = product(&dims, path)?;
+    if data.len() != expected {
+        return Err(DarjeelingError::InvalidIdxFormat(format!("{} should contain {} bytes of data, found {}", path, expected, data.len())));
+    }
+
+    Ok((dims, data))
+}
+
+/// The number of bytes the dimensions hold
+///
+/// ## Err
+/// - ### InvalidIdxFormat
+/// The dimensions hold more bytes than fit in a usize, so the file can't be the size it claims
+fn product(dims: &[usize], path: &str) -> Result<usize, DarjeelingError> {
+    match dims.iter().try_fold(1usize, |product, dim| product.checked_mul(*dim)) {
+        Some(product) => Ok(product),
+        None => Err(DarjeelingError::InvalidIdxFormat(format!("{} has dimensions {:?}, which are too large", path, dims)))
+    }
+}
@@ -0,0 +1,86 @@
+use ::image::{imageops::FilterType, DynamicImage};
+use crate::{
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// Which channels of an image are turned into sensor values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorMode {
+    /// One sensor per pixel
+    Grayscale,
+    /// Three sensors per pixel, in red, green, blue order
+    Rgb,
+}
+
+impl ColorMode {
+    /// The number of sensors each pixel takes up
+    pub fn channels(&self) -> usize {
+        match self {
+            ColorMode::Grayscale => 1,
+            ColorMode::Rgb => 3,
+        }
+    }
+}
+
+/// The number of sensors an image of the given size and color mode needs in the input layer
+pub fn sensor_count(width: u32, height: u32, color_mode: ColorMode) -> usize {
+    width as usize * height as usize * color_mode.channels()
+}
+
+/// Converts a PNG or JPEG file into an Input.
+/// The image is resized to width x height, flattened row by row, and every channel is normalized between 0 and 1
+///
+/// ## Params
+/// - Path: The path to the image file
+/// - Width: The width the image is resized to
+/// - Height: The height the image is resized to
+/// - Color Mode: Whether the image is read as grayscale or RGB
+/// - Answer: The category of the image, None if it's being tested
+///
+/// ## Returns
+/// An Input with [`sensor_count`](fn@sensor_count) inputs
+///
+/// ## Err
+/// - ### ReadDataFailed
+/// The file couldn't be opened or decoded
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::{images::{self, ColorMode}, types::Types};
+///
+/// let input = images::image_to_input("bee.png", 28, 28, ColorMode::Grayscale, Some(Types::String("Bee".to_string()))).unwrap();
+/// assert_eq!(input.inputs.len(), images::sensor_count(28, 28, ColorMode::Grayscale));
+/// ```
+pub fn image_to_input(path: &str, width: u32, height: u32, color_mode: ColorMode, answer: Option<Types>) -> Result<Input, DarjeelingError> {
+    let image: DynamicImage = match ::image::open(path) {
+        Ok(image) => image,
+        Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+    };
+
+    Ok(Input::new(image_to_inputs(&image, width, height, color_mode), answer))
+}
+
+/// Converts every image in a list of (path, answer) pairs into Inputs
+///
+/// ## Err
+/// - ### ReadDataFailed
+/// One of the files couldn't be opened or decoded
+pub fn images_to_inputs(files: &[(&str, Option<Types>)], width: u32, height: u32, color_mode: ColorMode) -> Result<Vec<Input>, DarjeelingError> {
+    files
+        .iter()
+        .map(|(path, answer)| image_to_input(path, width, height, color_mode, answer.clone()))
+        .collect()
+}
+
+/// Resizes and flattens an already decoded image into normalized sensor values
+pub fn image_to_inputs(image: &DynamicImage, width: u32, height: u32, color_mode: ColorMode) -> Vec<f32> {
+    let resized: DynamicImage = image.resize_exact(width, height, FilterType::Triangle);
+    let raw: Vec<u8> = match color_mode {
+        ColorMode::Grayscale => resized.to_luma8().into_raw(),
+        ColorMode::Rgb => resized.to_rgb8().into_raw(),
+    };
+
+    raw.into_iter().map(|channel| channel as f32 / 255.0).collect()
+}
@@ -1,11 +1,16 @@
 use std::fmt::Display;
-use crate::types::Types;
+use serde::{Serialize, Deserialize};
+use crate::{encoder, error::DarjeelingError, types::Types};
 
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 /// Represents the input for the neural network
+/// 
+/// In JSON, an input is `{"inputs": [0.0, 1.0], "answer": {"String": "1"}}`.
+/// The answer is in the same form as [Types](crate::types::Types), and can be null or left out if there isn't one.
 pub struct Input {
     pub inputs: Vec<f32>,
+    #[serde(default)]
     pub answer: Option<Types>
 }
 
@@ -30,9 +35,49 @@ impl Input  {
         Input { inputs, answer}
     }
 
+    /// Creates new input from borrowed values, copying them once
+    /// 
+    /// # Examples
+    /// ```
+    /// use darjeeling::input::Input;
+    /// use darjeeling::types::Types;
+    /// let row: [f32; 3] = [0.0, 1.0, 1.0];
+    /// // The last value of the row is the answer
+    /// let formated_input: Input = Input::from_slice(&row[..2], Some(&Types::Float(row[2])));
+    /// ```
+    pub fn from_slice(inputs: &[f32], answer: Option<&Types>) -> Input {
+
+        Input { inputs: inputs.to_vec(), answer: answer.cloned() }
+    }
+
+    /// Creates new input from typed values, with integers as they are, floats, and booleans as 1.0 or 0.0.
+    /// Strings and categories need an [encoder](crate::encoder::fit_columns) to become numbers.
+    ///
+    /// ## Err
+    /// - ### UnencodedColumn
+    /// A value is a string, a category or a vector
+    ///
+    /// # Examples
+    /// ```
+    /// use darjeeling::input::Input;
+    /// use darjeeling::types::Types;
+    /// let formated_input: Input = Input::from_types(&[Types::Integer(3), Types::Float(0.5), Types::Boolean(true)], None).unwrap();
+    /// assert_eq!(formated_input.inputs, vec![3.0, 0.5, 1.0]);
+    /// ```
+    pub fn from_types(row: &[Types], answer: Option<Types>) -> Result<Input, DarjeelingError> {
+        encoder::encode_row(&[], row, answer)
+    }
+
     // TODO: Write format_as_input function
 }
 
+/// Lends out the input values, so inputs can be passed wherever a row of values is expected
+impl AsRef<[f32]> for Input {
+    fn as_ref(&self) -> &[f32] {
+        &self.inputs
+    }
+}
+
 impl Display for Input {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut buffer = String::from("");
@@ -0,0 +1,144 @@
+use core::fmt;
+use serde::{Serialize, Deserialize};
+
+/// The output of every node during one forward pass, layer by layer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActivationSnapshot {
+    /// The first layer is the (scaled) inputs and the last is the answer layer
+    pub layers: Vec<Vec<f32>>
+}
+
+impl ActivationSnapshot {
+
+    pub fn new(layers: Vec<Vec<f32>>) -> ActivationSnapshot {
+        ActivationSnapshot { layers }
+    }
+
+    /// The outputs of one layer, None if there's no such layer
+    pub fn layer(&self, layer: usize) -> Option<&[f32]> {
+        self.layers.get(layer).map(|layer| layer.as_slice())
+    }
+
+    /// The output of one node, None if there's no such node
+    pub fn node(&self, layer: usize, node: usize) -> Option<f32> {
+        self.layers.get(layer).and_then(|layer| layer.get(node)).copied()
+    }
+
+    /// The outputs of the hidden layers, without the inputs or answers
+    pub fn hidden(&self) -> &[Vec<f32>] {
+        if self.layers.len() < 2 {
+            return &[];
+        }
+        &self.layers[1..self.layers.len() - 1]
+    }
+
+    pub fn answers(&self) -> &[f32] {
+        self.layers.last().map(|layer| layer.as_slice()).unwrap_or(&[])
+    }
+}
+
+/// How the weights coming into one layer are spread out, bias weights included
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeightHistogram {
+    pub layer: usize,
+    pub min: f32,
+    pub max: f32,
+    /// The number of weights in each equally wide bin from min to max
+    pub counts: Vec<usize>
+}
+
+impl WeightHistogram {
+
+    pub(crate) fn of(layer: usize, weights: &[f32], bins: usize) -> WeightHistogram {
+        let min: f32 = weights.iter().copied().fold(f32::INFINITY, f32::min);
+        let max: f32 = weights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let mut histogram = WeightHistogram { layer, min, max, counts: vec![0; bins] };
+        let width: f32 = histogram.bin_width();
+        weights.iter().for_each(|weight| {
+            let bin: usize = if width > 0.0 { ((weight - min) / width) as usize } else { 0 };
+            histogram.counts[bin.min(bins - 1)] += 1;
+        });
+        histogram
+    }
+
+    pub fn bin_width(&self) -> f32 {
+        match self.counts.is_empty() || self.max < self.min {
+            true => 0.0,
+            false => (self.max - self.min) / self.counts.len() as f32
+        }
+    }
+
+    /// The range of weights that fall in a bin
+    pub fn bin_range(&self, bin: usize) -> (f32, f32) {
+        let start: f32 = self.min + self.bin_width() * bin as f32;
+        (start, start + self.bin_width())
+    }
+
+    /// The number of weights counted
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+impl fmt::Display for WeightHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let largest: usize = self.counts.iter().copied().max().unwrap_or(0).max(1);
+        writeln!(f, "Layer {}, {} weights", self.layer, self.total())?;
+        for (bin, count) in self.counts.iter().enumerate() {
+            let (start, end) = self.bin_range(bin);
+            writeln!(f, "{:>9.4} to {:>9.4} | {:<40} {}", start, end, "#".repeat(count * 40 / largest), count)?;
+        }
+        Ok(())
+    }
+}
+
+/// A node whose output barely changed across the probe data, so it passes the same value on whatever the input is
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeadNeuron {
+    pub layer: usize,
+    pub node: usize,
+    /// The node's average output, near the edges of the activation function's range when the node is saturated
+    pub mean: f32,
+    pub std: f32
+}
+
+/// The nodes that are [dead](DeadNeuron) across a probe set, found by [`dead_neurons`](fn@crate::categorize::CatNetwork::dead_neurons)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeadNeuronReport {
+    /// The number of probe inputs the outputs were measured across
+    pub probes: usize,
+    /// Nodes whose outputs had a standard deviation at or under this were counted as dead
+    pub tolerance: f32,
+    /// The number of nodes in every layer after the inputs, starting with layer 1
+    pub layer_sizes: Vec<usize>,
+    pub neurons: Vec<DeadNeuron>
+}
+
+impl DeadNeuronReport {
+
+    /// The dead nodes in one layer
+    pub fn in_layer(&self, layer: usize) -> Vec<&DeadNeuron> {
+        self.neurons.iter().filter(|neuron| neuron.layer == layer).collect()
+    }
+
+    /// The fraction of a layer's nodes that are dead, 0 if there's no such layer
+    pub fn fraction(&self, layer: usize) -> f32 {
+        match layer.checked_sub(1).and_then(|i| self.layer_sizes.get(i)) {
+            Some(size) if *size > 0 => self.in_layer(layer).len() as f32 / *size as f32,
+            _ => 0.0
+        }
+    }
+}
+
+impl fmt::Display for DeadNeuronReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} dead nodes across {} probes, with a tolerance of {}", self.neurons.len(), self.probes, self.tolerance)?;
+        for (i, size) in self.layer_sizes.iter().enumerate() {
+            writeln!(f, "Layer {}: {}/{} dead", i + 1, self.in_layer(i + 1).len(), size)?;
+        }
+        for neuron in &self.neurons {
+            writeln!(f, "  layer {} node {}: mean {:.4}, std {:.6}", neuron.layer, neuron.node, neuron.mean, neuron.std)?;
+        }
+        Ok(())
+    }
+}
@@ -17,6 +17,7 @@
 #![feature(iter_collect_into, try_blocks)]
 pub static DEBUG: bool = false;
 
+pub mod prelude;
 pub mod input;
 pub mod categorize;
 pub mod node;
@@ -26,6 +27,79 @@ pub mod error;
 pub mod activation;
 pub mod types;
 pub mod generation;
+pub mod adversarial;
+pub mod tabular;
+pub mod anomaly;
+pub mod fidelity;
+pub mod idx;
+pub mod scaler;
+pub mod encoder;
+pub mod augment;
+pub mod dataset;
+pub mod text;
+pub mod timeseries;
+pub mod metrics;
+pub mod prediction;
+pub mod ensemble;
+pub mod cascade;
+pub mod multiclass;
+pub mod hierarchy;
+pub mod ordinal;
+pub mod multitask;
+pub mod baseline;
+pub mod qlearning;
+pub mod config;
+pub mod experiment;
+pub mod pipeline;
+pub mod validation;
+pub mod semisupervised;
+pub mod distillation;
+pub mod counterfactual;
+pub mod tuning;
+pub mod diagnostics;
+pub mod modelcard;
+pub mod history;
+pub mod tensorboard;
+pub mod prometheus;
+pub mod audit;
+pub mod calibration;
+pub mod consolidation;
+pub mod cost;
+pub mod threshold;
+pub mod drift;
+pub mod online;
+pub mod schema;
+pub mod inspect;
+pub mod diff;
+pub mod csv;
+pub mod darjdata;
+pub mod cache;
+pub mod reload;
+pub mod benchmark;
+pub mod parallel;
+#[cfg(feature = "image")]
+pub mod images;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "parquet")]
+pub mod arrow;
+#[cfg(feature = "mixed-precision")]
+pub mod precision;
+#[cfg(feature = "signing")]
+pub mod signing;
+#[cfg(feature = "encryption")]
+pub mod encryption;
+#[cfg(feature = "plot")]
+pub mod plot;
+#[cfg(all(feature = "mmap", unix, target_endian = "little"))]
+pub mod mapped;
 mod utils;
+mod hash;
+mod darj;
+mod visualize;
+#[cfg(feature = "encryption")]
+mod cipher;
 #[cfg(test)]
 pub mod tests;
@@ -0,0 +1,224 @@
+//! Datasets kept on disk and memory-mapped, so training sets larger than RAM can be iterated without loading them.
+//!
+//! A mapped dataset file is a 32 byte header followed by the rows, one after another:
+//! - The magic bytes `DARJMAT1`
+//! - The number of rows, as a little endian u64
+//! - The number of features in each row, as a little endian u64
+//! - 1 if every row ends with its answer and 0 if not, as a little endian u64
+//! - Every row's features, then its answer if there is one, as little endian f32s
+
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{BufWriter, Write},
+    os::unix::io::AsRawFd,
+    ptr,
+    slice
+};
+use crate::{
+    dataset::DataSource,
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+const MAGIC: &[u8; 8] = b"DARJMAT1";
+const HEADER: usize = 32;
+
+/// Writes inputs as a mapped dataset file, to be opened with [`MappedDataset::open`](fn@MappedDataset::open)
+///
+/// ## Params
+/// - Path: Where to write the file
+/// - Data: The inputs, all the same length. Answers have to be integers, like the index of each category, and either every input has one or none do.
+///
+/// ## Err
+/// - ### InvalidTrainingData
+/// The inputs aren't all the same length, an answer isn't an integer, or only some inputs have answers
+/// - ### WriteDataFailed
+/// The file couldn't be written
+pub fn write_mapped(path: &str, data: &[Input]) -> Result<(), DarjeelingError> {
+    let columns: usize = data.first().map(|input| input.inputs.len()).unwrap_or(0);
+    let labeled: bool = data.first().map(|input| input.answer.is_some()).unwrap_or(false);
+    let mut rows: Vec<u8> = Vec::with_capacity(data.len() * (columns + labeled as usize) * 4);
+    for (i, input) in data.iter().enumerate() {
+        if input.inputs.len() != columns {
+            return Err(DarjeelingError::InvalidTrainingData(format!("Input {} has {} values, but the first has {}", i, input.inputs.len(), columns)));
+        }
+        input.inputs.iter().for_each(|value| rows.extend_from_slice(&value.to_le_bytes()));
+        match (&input.answer, labeled) {
+            (Some(Types::Integer(answer)), true) => rows.extend_from_slice(&(*answer as f32).to_le_bytes()),
+            (None, false) => {},
+            (Some(answer), true) => return Err(DarjeelingError::InvalidTrainingData(format!("Input {} is answered {}, but mapped datasets only hold integer answers", i, answer))),
+            _ => return Err(DarjeelingError::InvalidTrainingData(format!("Input {} {} an answer, but the first {}", i, if labeled { "doesn't have" } else { "has" }, if labeled { "does" } else { "doesn't" })))
+        }
+    }
+
+    let write = || -> std::io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&(data.len() as u64).to_le_bytes())?;
+        file.write_all(&(columns as u64).to_le_bytes())?;
+        file.write_all(&(labeled as u64).to_le_bytes())?;
+        file.write_all(&rows)?;
+        file.flush()
+    };
+    write().map_err(|error| DarjeelingError::WriteDataFailed(format!("{}: {}", path, error)))
+}
+
+/// A dataset file mapped into memory rather than read into it.
+/// The operating system pages rows in as they're read and can drop them again under memory pressure,
+/// so iterating over a file many times the size of RAM only ever holds a small part of it.
+/// It's a [`DataSource`], so it can be trained on with [`learn_from_source`](fn@crate::categorize::CatNetwork::learn_from_source).
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{mapped::{self, MappedDataset}, input::Input, types::Types};
+///
+/// let path = std::env::temp_dir().join("mapped_example.bin");
+/// let path = path.to_str().unwrap();
+/// mapped::write_mapped(path, &[Input::new(vec![0.0, 1.0], Some(Types::Integer(1))), Input::new(vec![1.0, 1.0], Some(Types::Integer(0)))]).unwrap();
+///
+/// // Nothing else writes to the file while it's mapped
+/// let data = unsafe { MappedDataset::open(path) }.unwrap();
+/// assert_eq!(data.len(), 2);
+/// assert_eq!(data.row(1), &[1.0, 1.0]);
+/// assert_eq!(data.answer(0), Some(Types::Integer(1)));
+/// # drop(data);
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MappedDataset {
+    map: *const u8,
+    /// The size of the file, in bytes
+    size: usize,
+    rows: usize,
+    columns: usize,
+    labeled: bool
+}
+
+// The mapping is read only, nothing writes to the file while it's open, and it lives until the dataset is dropped
+unsafe impl Send for MappedDataset {}
+unsafe impl Sync for MappedDataset {}
+
+impl MappedDataset {
+
+    /// Maps a file written by [`write_mapped`](fn@write_mapped)
+    ///
+    /// ## Err
+    /// - ### ReadDataFailed
+    /// The file couldn't be opened or mapped, or it isn't a mapped dataset file or is cut short
+    ///
+    /// # Safety
+    /// The rows are read straight from the file, so nothing, in this process or any other, may write to or truncate it
+    /// until the dataset is dropped. The mapping is read only, but changes to the file can still show through it,
+    /// and reading past a truncated end kills the process.
+    pub unsafe fn open(path: &str) -> Result<MappedDataset, DarjeelingError> {
+        let failed = |reason: String| DarjeelingError::ReadDataFailed(format!("{}: {}", path, reason));
+        let file: File = File::open(path).map_err(|error| failed(error.to_string()))?;
+        let size: usize = file.metadata().map_err(|error| failed(error.to_string()))?.len() as usize;
+        if size < HEADER {
+            return Err(failed("It's too short to be a mapped dataset".to_string()));
+        }
+
+        // Safety: the file is open, the size is its own, and the mapping is private and read only
+        let map = libc::mmap(ptr::null_mut(), size, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0);
+        if map == libc::MAP_FAILED {
+            return Err(failed(std::io::Error::last_os_error().to_string()));
+        }
+        libc::madvise(map, size, libc::MADV_SEQUENTIAL);
+
+        let mut dataset = MappedDataset { map: map as *const u8, size, rows: 0, columns: 0, labeled: false };
+        let header: &[u8] = &dataset.bytes()[..HEADER];
+        if &header[..8] != MAGIC {
+            return Err(failed("It isn't a mapped dataset".to_string()));
+        }
+        let field = |i: usize| u64::from_le_bytes(header[i..i + 8].try_into().expect("Header fields are 8 bytes")) as usize;
+        let (rows, columns, labeled) = (field(8), field(16), field(24) == 1);
+        let expected: Option<usize> = rows.checked_mul(columns + labeled as usize).and_then(|values| values.checked_mul(4)).and_then(|bytes| bytes.checked_add(HEADER));
+        if expected != Some(size) {
+            return Err(failed(format!("The header says {} rows of {} values, which doesn't match the file's {} bytes", rows, columns + labeled as usize, size)));
+        }
+        dataset.rows = rows;
+        dataset.columns = columns;
+        dataset.labeled = labeled;
+        Ok(dataset)
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// The number of features in each row
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Whether every row has an answer
+    pub fn labeled(&self) -> bool {
+        self.labeled
+    }
+
+    /// The features of a row, read straight from the mapping
+    ///
+    /// ## Panics
+    /// The row is past the end of the dataset
+    pub fn row(&self, row: usize) -> &[f32] {
+        let start: usize = row * self.stride();
+        &self.values()[start..start + self.columns]
+    }
+
+    /// The answer of a row, None if the dataset doesn't have answers
+    ///
+    /// ## Panics
+    /// The row is past the end of the dataset
+    pub fn answer(&self, row: usize) -> Option<Types> {
+        match self.labeled {
+            true => Some(Types::Integer(self.values()[row * self.stride() + self.columns] as i32)),
+            false => None
+        }
+    }
+
+    /// A row copied into an Input
+    ///
+    /// ## Panics
+    /// The row is past the end of the dataset
+    pub fn input(&self, row: usize) -> Input {
+        Input::from_slice(self.row(row), self.answer(row).as_ref())
+    }
+
+    /// Every row in order, copied into an Input one at a time
+    pub fn iter(&self) -> impl Iterator<Item = Input> + '_ {
+        (0..self.rows).map(|row| self.input(row))
+    }
+
+    fn stride(&self) -> usize {
+        self.columns + self.labeled as usize
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // Safety: the mapping is size bytes long and lives as long as self
+        unsafe { slice::from_raw_parts(self.map, self.size) }
+    }
+
+    fn values(&self) -> &[f32] {
+        // Safety: mappings start on a page boundary, so the values after the 32 byte header are aligned for f32,
+        // and open checked that the file holds exactly this many of them
+        unsafe { slice::from_raw_parts(self.map.add(HEADER) as *const f32, self.rows * self.stride()) }
+    }
+}
+
+impl DataSource for MappedDataset {
+    fn pass(&mut self) -> Box<dyn Iterator<Item = Cow<'_, Input>> + '_> {
+        Box::new(self.iter().map(Cow::Owned))
+    }
+}
+
+impl Drop for MappedDataset {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.map as *mut libc::c_void, self.size) };
+    }
+}
@@ -0,0 +1,476 @@
+use core::fmt;
+use serde::{Serialize, Deserialize};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use crate::{error::DarjeelingError, types::Types, prediction::Prediction, history::CompensatedSum};
+
+/// How well a model did on a single category
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassMetrics {
+    pub category: Types,
+    /// Of everything predicted as this category, the fraction that really was
+    pub precision: f32,
+    /// Of everything that really was this category, the fraction predicted as it
+    pub recall: f32,
+    pub f1: f32,
+    /// The number of inputs that really were this category
+    pub support: usize
+}
+
+/// Accuracy, and precision, recall and F1 for every category and averaged over them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassificationMetrics {
+    pub accuracy: f32,
+    pub classes: Vec<ClassMetrics>,
+    /// The average of every category's precision
+    pub macro_precision: f32,
+    pub macro_recall: f32,
+    pub macro_f1: f32,
+    /// Precision counted over every prediction at once
+    pub micro_precision: f32,
+    pub micro_recall: f32,
+    pub micro_f1: f32
+}
+
+impl ClassificationMetrics {
+
+    /// Compares predicted categories with the correct ones
+    ///
+    /// ## Params
+    /// - Predictions: The category the model chose for each input
+    /// - Truth: The correct category of each input, in the same order
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{metrics::ClassificationMetrics, types::Types};
+    ///
+    /// let predictions = vec![Types::Integer(1), Types::Integer(0), Types::Integer(1)];
+    /// let truth = vec![Types::Integer(1), Types::Integer(1), Types::Integer(1)];
+    /// let metrics = ClassificationMetrics::compute(&predictions, &truth);
+    /// assert_eq!(metrics.classes[0].precision, 1.0);
+    /// ```
+    pub fn compute(predictions: &[Types], truth: &[Types]) -> ClassificationMetrics {
+        let mut categories: Vec<Types> = vec![];
+        truth.iter().chain(predictions).for_each(|category| {
+            if !categories.contains(category) {
+                categories.push(category.clone());
+            }
+        });
+
+        let total: usize = predictions.len().min(truth.len());
+        let correct: usize = predictions.iter().zip(truth).filter(|(prediction, answer)| prediction == answer).count();
+
+        let classes: Vec<ClassMetrics> = categories
+            .into_iter()
+            .map(|category| {
+                let true_positives = predictions.iter().zip(truth).filter(|(p, a)| **p == category && **a == category).count();
+                let predicted = predictions.iter().take(total).filter(|p| **p == category).count();
+                let support = truth.iter().take(total).filter(|a| **a == category).count();
+                let precision = ratio(true_positives, predicted);
+                let recall = ratio(true_positives, support);
+                ClassMetrics { category, precision, recall, f1: f1(precision, recall), support }
+            })
+            .collect();
+
+        let class_count: f32 = classes.len().max(1) as f32;
+        let macro_precision: f32 = classes.iter().map(|class| class.precision).sum::<f32>() / class_count;
+        let macro_recall: f32 = classes.iter().map(|class| class.recall).sum::<f32>() / class_count;
+        let macro_f1: f32 = classes.iter().map(|class| class.f1).sum::<f32>() / class_count;
+
+        // Every input gets exactly one prediction, so every wrong answer is both a false positive and a false negative
+        let micro_precision: f32 = ratio(correct, total);
+        let micro_recall: f32 = ratio(correct, total);
+
+        ClassificationMetrics {
+            accuracy: ratio(correct, total),
+            classes,
+            macro_precision,
+            macro_recall,
+            macro_f1,
+            micro_precision,
+            micro_recall,
+            micro_f1: f1(micro_precision, micro_recall)
+        }
+    }
+}
+
+impl fmt::Display for ClassificationMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Accuracy: {:.4}", self.accuracy)?;
+        writeln!(f, "{:<16} {:>9} {:>9} {:>9} {:>9}", "Category", "Precision", "Recall", "F1", "Support")?;
+        for class in &self.classes {
+            writeln!(f, "{:<16} {:>9.4} {:>9.4} {:>9.4} {:>9}", format!("{:?}", class.category), class.precision, class.recall, class.f1, class.support)?;
+        }
+        writeln!(f, "{:<16} {:>9.4} {:>9.4} {:>9.4}", "Macro", self.macro_precision, self.macro_recall, self.macro_f1)?;
+        write!(f, "{:<16} {:>9.4} {:>9.4} {:>9.4}", "Micro", self.micro_precision, self.micro_recall, self.micro_f1)
+    }
+}
+
+/// How a categorization model did on a set of data, from [`evaluate`](fn@crate::categorize::CatNetwork::evaluate)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EvaluationReport {
+    /// The mean squared error of every answer node against its target,
+    /// weighted by the [output weights](fn@crate::categorize::CatNetwork::set_output_weights) if there are any
+    pub loss: f32,
+    pub metrics: ClassificationMetrics,
+    /// The number of inputs with an answer that were evaluated
+    pub count: usize,
+    /// The prediction for every input with an answer, in the same order as the data
+    pub predictions: Vec<Prediction>,
+    /// How the network did on every category, in the same order as the metrics' classes
+    #[serde(default)]
+    pub categories: Vec<CategoryBreakdown>
+}
+
+impl fmt::Display for EvaluationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Loss: {:.4} ({} inputs)", self.loss, self.count)?;
+        writeln!(f, "Accuracy: {:.4}", self.metrics.accuracy)?;
+        writeln!(f, "{:<16} {:>7} {:>9} {:>9} {:>9}  Most confused with", "Category", "Count", "Accuracy", "Precision", "Recall")?;
+        for category in &self.categories {
+            let confusion: String = match &category.confused_with {
+                Some((other, count)) => format!("{:?} ({})", other, count),
+                None => "-".to_string()
+            };
+            writeln!(f, "{:<16} {:>7} {:>9.4} {:>9.4} {:>9.4}  {}", format!("{:?}", category.category), category.count, category.accuracy, category.precision, category.recall, confusion)?;
+        }
+        writeln!(f, "{:<34} {:>9.4} {:>9.4}", "Macro", self.metrics.macro_precision, self.metrics.macro_recall)?;
+        write!(f, "{:<34} {:>9.4} {:>9.4}", "Micro", self.metrics.micro_precision, self.metrics.micro_recall)
+    }
+}
+
+/// How a model did on one category of an [`EvaluationReport`], and which category it mistook it for most
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CategoryBreakdown {
+    pub category: Types,
+    /// The number of inputs that really were this category
+    pub count: usize,
+    /// The fraction of inputs the model was right about this category for,
+    /// both predicting it when it was and not predicting it when it wasn't
+    pub accuracy: f32,
+    pub precision: f32,
+    pub recall: f32,
+    /// The category inputs of this one were most often predicted as, and how many times.
+    /// None if none of them were wrong.
+    pub confused_with: Option<(Types, usize)>
+}
+
+impl CategoryBreakdown {
+
+    /// Breaks the comparison of predicted and correct categories down by category,
+    /// in the same order as the classes of [`ClassificationMetrics::compute`]
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{metrics::CategoryBreakdown, types::Types};
+    ///
+    /// let predictions = vec![Types::Integer(1), Types::Integer(0), Types::Integer(1)];
+    /// let truth = vec![Types::Integer(1), Types::Integer(1), Types::Integer(0)];
+    /// let categories = CategoryBreakdown::compute(&predictions, &truth);
+    /// assert_eq!(categories[0].count, 2);
+    /// assert_eq!(categories[0].confused_with, Some((Types::Integer(0), 1)));
+    /// ```
+    pub fn compute(predictions: &[Types], truth: &[Types]) -> Vec<CategoryBreakdown> {
+        let total: usize = predictions.len().min(truth.len());
+        ClassificationMetrics::compute(predictions, truth).classes.into_iter().map(|class| {
+            let mut confusions: Vec<(Types, usize)> = vec![];
+            let mut right: usize = 0;
+            for (prediction, answer) in predictions.iter().zip(truth) {
+                if (*prediction == class.category) == (*answer == class.category) {
+                    right += 1;
+                }
+                if *answer == class.category && *prediction != class.category {
+                    match confusions.iter_mut().find(|(other, _)| other == prediction) {
+                        Some((_, count)) => *count += 1,
+                        None => confusions.push((prediction.clone(), 1))
+                    }
+                }
+            }
+            // The first one to appear on a tie
+            let confused_with: Option<(Types, usize)> = confusions.into_iter().fold(None, |most, (other, count)| match most {
+                Some((_, most_count)) if most_count >= count => most,
+                _ => Some((other, count))
+            });
+            CategoryBreakdown {
+                category: class.category,
+                count: class.support,
+                accuracy: ratio(right, total),
+                precision: class.precision,
+                recall: class.recall,
+                confused_with
+            }
+        }).collect()
+    }
+}
+
+/// How close a model's continuous outputs were to the correct values
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RegressionMetrics {
+    /// Root mean squared error
+    pub rmse: f32,
+    /// Mean absolute error
+    pub mae: f32,
+    /// Coefficient of determination, 1.0 is a perfect fit and 0.0 is no better than guessing the mean
+    pub r2: f32,
+    pub count: usize
+}
+
+impl RegressionMetrics {
+
+    /// Compares predicted values with the correct ones
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::metrics::RegressionMetrics;
+    ///
+    /// let metrics = RegressionMetrics::compute(&[1.0, 2.0, 3.0], &[1.0, 2.0, 4.0]);
+    /// assert_eq!(metrics.mae, 1.0 / 3.0);
+    /// ```
+    pub fn compute(predictions: &[f32], truth: &[f32]) -> RegressionMetrics {
+        let count: usize = predictions.len().min(truth.len());
+        if count == 0 {
+            return RegressionMetrics { rmse: 0.0, mae: 0.0, r2: 0.0, count };
+        }
+        let n: f32 = count as f32;
+        let pairs = || predictions.iter().zip(truth);
+
+        let squared_error: f32 = CompensatedSum::of(pairs().map(|(p, t)| (p - t).powi(2)));
+        let absolute_error: f32 = CompensatedSum::of(pairs().map(|(p, t)| (p - t).abs()));
+        let mean: f32 = CompensatedSum::of(truth.iter().take(count).copied()) / n;
+        let total_variance: f32 = CompensatedSum::of(truth.iter().take(count).map(|t| (t - mean).powi(2)));
+
+        RegressionMetrics {
+            rmse: (squared_error / n).sqrt(),
+            mae: absolute_error / n,
+            r2: if total_variance == 0.0 { 0.0 } else { 1.0 - squared_error / total_variance },
+            count
+        }
+    }
+}
+
+impl fmt::Display for RegressionMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RMSE: {:.4} MAE: {:.4} R2: {:.4} ({} values)", self.rmse, self.mae, self.r2, self.count)
+    }
+}
+
+/// How close an ordinal model's levels were to the correct ones, counting how far off it was and not just whether it was
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrdinalMetrics {
+    /// The fraction of exactly right levels
+    pub accuracy: f32,
+    /// The mean number of levels away from the correct one
+    pub mae: f32,
+    /// The fraction of levels at most one away from the correct one
+    pub within_one: f32,
+    pub count: usize
+}
+
+impl OrdinalMetrics {
+
+    /// Compares predicted levels with the correct ones, both as indexes into the ordered levels
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::metrics::OrdinalMetrics;
+    ///
+    /// let metrics = OrdinalMetrics::compute(&[0, 2, 4], &[0, 1, 1]);
+    /// assert_eq!(metrics.within_one, 2.0 / 3.0);
+    /// ```
+    pub fn compute(predictions: &[usize], truth: &[usize]) -> OrdinalMetrics {
+        let count: usize = predictions.len().min(truth.len());
+        if count == 0 {
+            return OrdinalMetrics { accuracy: 0.0, mae: 0.0, within_one: 0.0, count };
+        }
+        let n: f32 = count as f32;
+        let distances: Vec<usize> = predictions.iter().zip(truth).map(|(p, t)| p.abs_diff(*t)).collect();
+        OrdinalMetrics {
+            accuracy: distances.iter().filter(|distance| **distance == 0).count() as f32 / n,
+            mae: distances.iter().sum::<usize>() as f32 / n,
+            within_one: distances.iter().filter(|distance| **distance <= 1).count() as f32 / n,
+            count
+        }
+    }
+}
+
+impl fmt::Display for OrdinalMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Accuracy: {:.4} MAE: {:.4} Within One: {:.4} ({} values)", self.accuracy, self.mae, self.within_one, self.count)
+    }
+}
+
+/// Regression metrics for every output of a multi-output model, and over all of them together,
+/// from [`evaluate_regression_heads`](fn@crate::categorize::CatNetwork::evaluate_regression_heads)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MultiOutputMetrics {
+    /// The name of every output, the category of its answer node
+    pub names: Vec<Types>,
+    /// The metrics of every output, in the same order as the names
+    pub heads: Vec<RegressionMetrics>,
+    pub overall: RegressionMetrics
+}
+
+impl MultiOutputMetrics {
+
+    /// Compares predicted outputs with the correct ones, output by output.
+    /// Targets shorter than the predictions leave the missing outputs out.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{metrics::MultiOutputMetrics, types::Types};
+    ///
+    /// let names = vec![Types::String("x".to_string()), Types::String("y".to_string())];
+    /// let metrics = MultiOutputMetrics::compute(names, &[vec![1.0, 2.0], vec![3.0, 4.0]], &[vec![1.0, 2.5], vec![3.0, 3.5]]);
+    /// assert_eq!(metrics.heads[0].mae, 0.0);
+    /// assert_eq!(metrics.heads[1].mae, 0.5);
+    /// ```
+    pub fn compute(names: Vec<Types>, predictions: &[Vec<f32>], truth: &[Vec<f32>]) -> MultiOutputMetrics {
+        let mut head_predictions: Vec<Vec<f32>> = vec![vec![]; names.len()];
+        let mut head_truth: Vec<Vec<f32>> = vec![vec![]; names.len()];
+        predictions.iter().zip(truth).for_each(|(outputs, targets)| {
+            outputs.iter().zip(targets).take(names.len()).enumerate().for_each(|(head, (output, target))| {
+                head_predictions[head].push(*output);
+                head_truth[head].push(*target);
+            });
+        });
+
+        let heads: Vec<RegressionMetrics> = head_predictions.iter().zip(&head_truth).map(|(predictions, truth)| RegressionMetrics::compute(predictions, truth)).collect();
+        let overall: RegressionMetrics = RegressionMetrics::compute(&head_predictions.concat(), &head_truth.concat());
+        MultiOutputMetrics { names, heads, overall }
+    }
+}
+
+impl fmt::Display for MultiOutputMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, head) in self.names.iter().zip(&self.heads) {
+            writeln!(f, "{:<16} {}", format!("{:?}", name), head)?;
+        }
+        write!(f, "{:<16} {}", "Overall", self.overall)
+    }
+}
+
+/// A metric on the whole test set, and the range it fell in across bootstrap resamples
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ConfidenceInterval {
+    /// The metric on the test set as it is
+    pub estimate: f32,
+    pub lower: f32,
+    pub upper: f32
+}
+
+impl fmt::Display for ConfidenceInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.4} [{:.4}, {:.4}]", self.estimate, self.lower, self.upper)
+    }
+}
+
+/// Confidence intervals for accuracy and F1, from resampling the test set with replacement.
+/// On small test sets one accuracy can be far from the model's real one, and the interval shows how far.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BootstrapReport {
+    pub accuracy: ConfidenceInterval,
+    pub macro_f1: ConfidenceInterval,
+    pub micro_f1: ConfidenceInterval,
+    /// The fraction of resamples inside each interval, like 0.95
+    pub confidence: f32,
+    pub resamples: usize,
+    /// The number of inputs in the test set
+    pub count: usize
+}
+
+impl BootstrapReport {
+
+    /// Computes the metrics on resamples of the predictions, each as large as the test set and drawn from it with replacement,
+    /// and takes the intervals from the percentiles of the resampled metrics
+    ///
+    /// ## Params
+    /// - Predictions: The category the model chose for each input
+    /// - Truth: The correct category of each input, in the same order
+    /// - Resamples: How many resampled test sets to score. A thousand or so gives steady intervals.
+    /// - Confidence: The fraction of resamples inside each interval, between 0 and 1
+    /// - Seed: Decides the resamples, so the same seed always gives the same intervals
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There are no resamples, or the confidence isn't between 0 and 1
+    /// - ### InvalidTrainingData
+    /// There are no predictions
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{metrics::BootstrapReport, types::Types};
+    ///
+    /// let predictions = vec![Types::Integer(1), Types::Integer(0), Types::Integer(1), Types::Integer(1)];
+    /// let truth = vec![Types::Integer(1), Types::Integer(1), Types::Integer(1), Types::Integer(1)];
+    /// let report = BootstrapReport::compute(&predictions, &truth, 200, 0.9, 42).unwrap();
+    /// assert_eq!(report.accuracy.estimate, 0.75);
+    /// assert!(report.accuracy.lower <= 0.75 && report.accuracy.upper >= 0.75);
+    /// ```
+    pub fn compute(predictions: &[Types], truth: &[Types], resamples: usize, confidence: f32, seed: u64) -> Result<BootstrapReport, DarjeelingError> {
+        if resamples == 0 {
+            return Err(DarjeelingError::InvalidInput("Bootstrapping needs at least one resample".to_string()));
+        }
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(DarjeelingError::InvalidInput(format!("The confidence has to be between 0 and 1, not {}", confidence)));
+        }
+        let count: usize = predictions.len().min(truth.len());
+        if count == 0 {
+            return Err(DarjeelingError::InvalidTrainingData("There are no predictions to bootstrap".to_string()));
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut accuracies: Vec<f32> = Vec::with_capacity(resamples);
+        let mut macro_f1s: Vec<f32> = Vec::with_capacity(resamples);
+        let mut micro_f1s: Vec<f32> = Vec::with_capacity(resamples);
+        for _ in 0..resamples {
+            let (resampled_predictions, resampled_truth): (Vec<Types>, Vec<Types>) = (0..count)
+                .map(|_| {
+                    let i: usize = rng.gen_range(0..count);
+                    (predictions[i].clone(), truth[i].clone())
+                })
+                .unzip();
+            let metrics: ClassificationMetrics = ClassificationMetrics::compute(&resampled_predictions, &resampled_truth);
+            accuracies.push(metrics.accuracy);
+            macro_f1s.push(metrics.macro_f1);
+            micro_f1s.push(metrics.micro_f1);
+        }
+
+        let metrics: ClassificationMetrics = ClassificationMetrics::compute(&predictions[..count], &truth[..count]);
+        Ok(BootstrapReport {
+            accuracy: interval(metrics.accuracy, accuracies, confidence),
+            macro_f1: interval(metrics.macro_f1, macro_f1s, confidence),
+            micro_f1: interval(metrics.micro_f1, micro_f1s, confidence),
+            confidence,
+            resamples,
+            count
+        })
+    }
+}
+
+impl fmt::Display for BootstrapReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:.0}% intervals from {} resamples of {} inputs", self.confidence * 100.0, self.resamples, self.count)?;
+        writeln!(f, "Accuracy: {}", self.accuracy)?;
+        writeln!(f, "Macro F1: {}", self.macro_f1)?;
+        write!(f, "Micro F1: {}", self.micro_f1)
+    }
+}
+
+/// The percentile interval of the resampled values that holds the confidence fraction of them
+fn interval(estimate: f32, mut resampled: Vec<f32>, confidence: f32) -> ConfidenceInterval {
+    resampled.sort_by(|a, b| a.total_cmp(b));
+    let tail: f32 = (1.0 - confidence) / 2.0;
+    let last: f32 = (resampled.len() - 1) as f32;
+    ConfidenceInterval {
+        estimate,
+        lower: resampled[(tail * last).floor() as usize],
+        upper: resampled[((1.0 - tail) * last).ceil() as usize]
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f32 {
+    if denominator == 0 { 0.0 } else { numerator as f32 / denominator as f32 }
+}
+
+fn f1(precision: f32, recall: f32) -> f32 {
+    if precision + recall == 0.0 { 0.0 } else { 2.0 * precision * recall / (precision + recall) }
+}
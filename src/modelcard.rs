@@ -0,0 +1,179 @@
+//! Model cards, short documents that go with a model when it's shared,
+//! saying what it is, what it was trained on, how well it does and what it's meant for.
+
+use core::fmt;
+use std::fs;
+use serde::{Serialize, Deserialize};
+use crate::{
+    activation::ActivationFunction,
+    config::{LearningRateSchedule, Reduction, Regularization},
+    dataset::DataFingerprint,
+    error::DarjeelingError,
+    metrics::ClassificationMetrics,
+    threshold::DecisionThreshold,
+    types::Types
+};
+
+/// The layers of a network and how many weights they hold
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CardArchitecture {
+    /// The number of nodes in every layer, from the inputs to the answer layer
+    pub layers: Vec<usize>,
+    pub activation: ActivationFunction,
+    /// Every link weight and bias
+    pub parameters: usize
+}
+
+/// How a network was trained
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CardTraining {
+    /// The epochs in the network's [history](fn@crate::categorize::CatNetwork::history), 0 for a network read from a file
+    pub epochs: usize,
+    /// The learning rate of the last epoch
+    pub learning_rate: Option<f32>,
+    /// The loss and accuracy of the last epoch, on the training data
+    pub loss: Option<f32>,
+    pub accuracy: Option<f32>,
+    pub regularization: Regularization,
+    pub max_norm: Option<f32>,
+    pub schedule: LearningRateSchedule,
+    pub reduction: Reduction
+}
+
+/// How a network did on the data it was evaluated on for its card
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CardEvaluation {
+    /// The number of inputs with answers
+    pub count: usize,
+    pub loss: f32,
+    pub metrics: ClassificationMetrics
+}
+
+/// A summary of a network for sharing it responsibly, from [`generate_model_card`](fn@crate::categorize::CatNetwork::generate_model_card).
+/// Everything the network knows about itself is filled in. What it's for and where it falls short only its makers know,
+/// so add them with [`with_intended_use`](fn@ModelCard::with_intended_use) and [`with_limitations`](fn@ModelCard::with_limitations).
+///
+/// It serializes as structured data, and [`to_markdown`](fn@ModelCard::to_markdown) renders it to read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModelCard {
+    pub name: String,
+    #[serde(default)]
+    pub intended_use: Option<String>,
+    #[serde(default)]
+    pub limitations: Option<String>,
+    pub architecture: CardArchitecture,
+    /// The name of every input, from the network's schema if it has one
+    pub features: Vec<String>,
+    /// The categories the network answers with, empty if they haven't been set
+    pub categories: Vec<Types>,
+    /// The data the network was last trained on
+    pub training_data: Option<DataFingerprint>,
+    pub training: CardTraining,
+    pub evaluation: Option<CardEvaluation>,
+    /// Whether predictions are calibrated, weighed by a cost matrix, or use a decision threshold
+    pub calibrated: bool,
+    pub cost_sensitive: bool,
+    pub threshold: Option<DecisionThreshold>
+}
+
+impl ModelCard {
+
+    pub fn with_name(mut self, name: &str) -> ModelCard {
+        self.name = name.to_string();
+        self
+    }
+
+    /// What the model is for and who should use it
+    pub fn with_intended_use(mut self, intended_use: &str) -> ModelCard {
+        self.intended_use = Some(intended_use.to_string());
+        self
+    }
+
+    /// Where the model shouldn't be trusted, like groups that were rare in the training data
+    pub fn with_limitations(mut self, limitations: &str) -> ModelCard {
+        self.limitations = Some(limitations.to_string());
+        self
+    }
+
+    /// Renders the card as a Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut markdown: String = format!("# {}\n", self.name);
+        let written = |text: &Option<String>| text.clone().unwrap_or_else(|| "Not documented.".to_string());
+        markdown += &format!("\n## Intended Use\n{}\n", written(&self.intended_use));
+
+        markdown += "\n## Architecture\n";
+        markdown += &format!("- Layers: {}\n", self.architecture.layers.iter().map(|size| size.to_string()).collect::<Vec<String>>().join(" → "));
+        markdown += &format!("- Activation: {}\n", self.architecture.activation);
+        markdown += &format!("- Parameters: {}\n", self.architecture.parameters);
+        markdown += &format!("- Features: {}\n", self.features.join(", "));
+        markdown += &format!("- Categories: {}\n", match self.categories.is_empty() {
+            true => "not set".to_string(),
+            false => self.categories.iter().map(|category| category.to_string()).collect::<Vec<String>>().join(", ")
+        });
+        let mut decisions: Vec<String> = vec![];
+        if self.calibrated {
+            decisions.push("calibrated probabilities".to_string());
+        }
+        if self.cost_sensitive {
+            decisions.push("a cost matrix".to_string());
+        }
+        if let Some(threshold) = &self.threshold {
+            decisions.push(format!("a threshold of {} for {}", threshold.threshold, threshold.category));
+        }
+        if !decisions.is_empty() {
+            markdown += &format!("- Decisions use {}\n", decisions.join(", "));
+        }
+
+        markdown += "\n## Training Data\n";
+        markdown += &match &self.training_data {
+            Some(data) => format!("- Rows: {}\n- Features: {}\n- SHA-256: `{}`\n", data.rows, data.features, data.digest),
+            None => "Not recorded.\n".to_string()
+        };
+
+        markdown += "\n## Hyperparameters\n";
+        let training: &CardTraining = &self.training;
+        markdown += &format!("- Epochs: {}\n", training.epochs);
+        if let Some(learning_rate) = training.learning_rate {
+            markdown += &format!("- Final learning rate: {}\n", learning_rate);
+        }
+        if let (Some(loss), Some(accuracy)) = (training.loss, training.accuracy) {
+            markdown += &format!("- Final training loss: {:.4}, accuracy: {:.4}\n", loss, accuracy);
+        }
+        markdown += &format!("- Regularization: L1 {}, L2 {}\n", training.regularization.l1, training.regularization.l2);
+        if let Some(max_norm) = training.max_norm {
+            markdown += &format!("- Max norm: {}\n", max_norm);
+        }
+        markdown += &format!("- Schedule: {:?}\n", training.schedule);
+        markdown += &format!("- Reduction: {:?}\n", training.reduction);
+
+        markdown += "\n## Evaluation\n";
+        match &self.evaluation {
+            Some(evaluation) => {
+                markdown += &format!("- Inputs: {}\n- Loss: {:.4}\n- Accuracy: {:.4}\n- Macro F1: {:.4}\n", evaluation.count, evaluation.loss, evaluation.metrics.accuracy, evaluation.metrics.macro_f1);
+                markdown += "\n| Category | Precision | Recall | F1 | Support |\n| --- | --- | --- | --- | --- |\n";
+                evaluation.metrics.classes.iter().for_each(|class| {
+                    markdown += &format!("| {} | {:.4} | {:.4} | {:.4} | {} |\n", class.category, class.precision, class.recall, class.f1, class.support);
+                });
+            },
+            None => markdown += "Not evaluated.\n"
+        }
+
+        markdown += &format!("\n## Limitations\n{}\n", written(&self.limitations));
+        markdown
+    }
+
+    /// Saves the card as Markdown
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The file couldn't be written
+    pub fn write_markdown(&self, path: &str) -> Result<(), DarjeelingError> {
+        fs::write(path, self.to_markdown()).map_err(|error| DarjeelingError::WriteDataFailed(format!("{}: {}", path, error)))
+    }
+}
+
+impl fmt::Display for ModelCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_markdown())
+    }
+}
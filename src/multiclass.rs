@@ -0,0 +1,104 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    parallel,
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    dataset::Dataset,
+    error::DarjeelingError,
+    input::Input,
+    metrics::ClassificationMetrics,
+    prediction::{Prediction, TieBreak},
+    types::Types
+};
+
+/// A multiclass classifier made of one binary network per category, each answering whether an input is that category or not.
+/// The category whose network is the most sure it's a yes wins.
+/// Many small yes or no questions can be easier to learn than one answer layer with dozens of categories,
+/// and every network trains on its own, so they can all be trained at once.
+///
+/// ## Examples
+/// ```ignore
+/// let classifier = OneVsRest::train(&data, &TrainingConfig::default().with_hidden_num(8), 500, true)?;
+/// let prediction = classifier.predict(&[5.1, 3.5, 1.4, 0.2]);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OneVsRest {
+    categories: Vec<Types>,
+    /// One network per category, in the same order, with the categories false and true
+    members: Vec<CatNetwork>
+}
+
+impl OneVsRest {
+
+    /// Trains a binary network for every category in the data, [sorted](fn@Dataset::sorted_categories).
+    /// Each network sees every input of its category as many times as it takes to roughly match the rest of the data,
+    /// so it doesn't learn to always answer no.
+    ///
+    /// ## Params
+    /// - Data: Every input needs an answer
+    /// - Config: The architecture, learning rate and target error percent of every network
+    /// - Max Epochs: Stops a network after this many epochs, even if it hasn't reached the target error percent
+    /// - Parallel: Whether to train the networks on every core at once, which needs the `parallel` feature
+    ///
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// There's no data, an input doesn't have an answer, or there are fewer than two categories
+    /// - The rest are the same as [`step_epoch`](fn@CatNetwork::step_epoch)
+    pub fn train(data: &[Input], config: &TrainingConfig, max_epochs: usize, parallel: bool) -> Result<OneVsRest, DarjeelingError> {
+        if data.is_empty() || data.iter().any(|input| input.answer.is_none()) {
+            return Err(DarjeelingError::InvalidTrainingData("One-vs-rest needs data where every input has an answer".to_string()));
+        }
+        let categories: Vec<Types> = Dataset::new(data.to_vec()).sorted_categories();
+        if categories.len() < 2 {
+            return Err(DarjeelingError::InvalidTrainingData(format!("One-vs-rest needs at least two categories, but the data has {}", categories.len())));
+        }
+
+        let members: Vec<Result<CatNetwork, DarjeelingError>> = parallel::map(&categories, parallel, |category| train_member(data, category, config, max_epochs));
+        Ok(OneVsRest { categories, members: members.into_iter().collect::<Result<_, _>>()? })
+    }
+
+    pub fn categories(&self) -> &[Types] {
+        &self.categories
+    }
+
+    /// The binary network of every category, in the same order as [`categories`](fn@OneVsRest::categories)
+    pub fn members(&self) -> &[CatNetwork] {
+        &self.members
+    }
+
+    /// Asks every category's network how sure it is that the input is that category, and picks the surest.
+    /// The scores are those yes outputs, and the confidence is the largest of them.
+    pub fn predict(&self, inputs: &[f32]) -> Prediction {
+        let scores: Vec<f32> = self.members.iter().map(|member| member.raw_outputs(inputs)[1]).collect();
+        let (largest, tied) = TieBreak::First.choose(&scores).expect("Only the error policy fails");
+        Prediction {
+            category: self.categories[largest].clone(),
+            confidence: scores[largest].clamp(0.0, 1.0),
+            tied: tied.iter().map(|i| self.categories[*i].clone()).collect(),
+            scores: self.categories.iter().cloned().zip(scores).collect()
+        }
+    }
+
+    /// How well the predictions match the answers. Inputs without an answer are skipped.
+    pub fn evaluate(&self, data: &[Input]) -> ClassificationMetrics {
+        let answered: Vec<&Input> = data.iter().filter(|input| input.answer.is_some()).collect();
+        let predictions: Vec<Types> = answered.iter().map(|input| self.predict(&input.inputs).category).collect();
+        let truth: Vec<Types> = answered.iter().filter_map(|input| input.answer.clone()).collect();
+        ClassificationMetrics::compute(&predictions, &truth)
+    }
+}
+
+/// Trains the network that tells one category from the rest
+fn train_member(data: &[Input], category: &Types, config: &TrainingConfig, max_epochs: usize) -> Result<CatNetwork, DarjeelingError> {
+    let positives: usize = data.iter().filter(|input| input.answer.as_ref() == Some(category)).count();
+    let repeats: usize = ((data.len() - positives) / positives.max(1)).max(1);
+    let mut binary: Vec<Input> = data.iter().flat_map(|input| {
+        let is_category: bool = input.answer.as_ref() == Some(category);
+        let copies: usize = if is_category { repeats } else { 1 };
+        (0..copies).map(move |_| Input::new(input.inputs.clone(), Some(Types::Boolean(is_category))))
+    }).collect();
+
+    let mut net: CatNetwork = CatNetwork::from_config(data[0].inputs.len() as i32, 2, config);
+    net.learn_epochs(&mut binary, vec![Types::Boolean(false), Types::Boolean(true)], config, max_epochs)?;
+    Ok(net)
+}
@@ -0,0 +1,262 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    error::DarjeelingError,
+    input::Input,
+    metrics::{ClassificationMetrics, RegressionMetrics},
+    prediction::Prediction,
+    types::Types
+};
+
+/// What one task in a [`MultiTaskNetwork`] predicts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TaskKind {
+    /// Picks one of the categories, with an answer node for each
+    Classification(Vec<Types>),
+    /// Predicts a number, with one answer node
+    Regression
+}
+
+/// One prediction a [`MultiTaskNetwork`] learns, and how much its mistakes count while training
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Task {
+    pub name: String,
+    pub kind: TaskKind,
+    /// Multiplies the error of the task's answer nodes, so a task that matters more pulls the shared layers harder
+    pub weight: f32
+}
+
+impl Task {
+
+    pub fn classification(name: &str, categories: Vec<Types>, weight: f32) -> Task {
+        Task { name: name.to_string(), kind: TaskKind::Classification(categories), weight }
+    }
+
+    pub fn regression(name: &str, weight: f32) -> Task {
+        Task { name: name.to_string(), kind: TaskKind::Regression, weight }
+    }
+
+    /// The number of answer nodes in the task's head
+    pub fn outputs(&self) -> usize {
+        match &self.kind {
+            TaskKind::Classification(categories) => categories.len(),
+            TaskKind::Regression => 1
+        }
+    }
+}
+
+/// An input with an answer for every task, in the same order as the tasks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskInput {
+    pub inputs: Vec<f32>,
+    /// One of the categories for a classification task, and a number for a regression task
+    pub answers: Vec<Types>
+}
+
+impl TaskInput {
+
+    pub fn new(inputs: Vec<f32>, answers: Vec<Types>) -> TaskInput {
+        TaskInput { inputs, answers }
+    }
+}
+
+/// What a [`MultiTaskNetwork`] predicts for one task
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TaskOutput {
+    Category(Prediction),
+    Value(f32)
+}
+
+/// How well a [`MultiTaskNetwork`] did at one task
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TaskMetrics {
+    Classification(ClassificationMetrics),
+    Regression(RegressionMetrics)
+}
+
+/// Learns several related predictions at once, like a category and a number, from the same inputs.
+/// Every task has its own head of answer nodes, and the hidden layers are shared between them,
+/// so what one task learns about the inputs helps the others.
+///
+/// The heads sit side by side in the answer layer of one network. Regression answers are scaled to between 0 and 1
+/// by the smallest and largest answers in the training data, and scaled back when predicting.
+///
+/// ## Examples
+/// ```ignore
+/// let tasks = vec![
+///     Task::classification("species", species, 1.0),
+///     // Mistakes on the weight count half as much as the species
+///     Task::regression("weight", 0.5)
+/// ];
+/// let net = MultiTaskNetwork::train(&data, tasks, &TrainingConfig::default(), 500)?;
+/// if let Some(TaskOutput::Value(weight)) = net.predict_task(&inputs, "weight") {
+///     println!("{}", weight);
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiTaskNetwork {
+    tasks: Vec<Task>,
+    /// The smallest and largest training answer of every regression task, None for classification tasks
+    ranges: Vec<Option<(f32, f32)>>,
+    /// Every task's answer nodes, one head after another
+    network: CatNetwork
+}
+
+impl MultiTaskNetwork {
+
+    /// Trains the shared hidden layers and every head together, for the given number of epochs
+    ///
+    /// ## Params
+    /// - Data: Every input needs an answer for every task
+    /// - Tasks: The predictions to learn. Names have to be unique.
+    /// - Config: The hidden layers and learning rate of the network
+    /// - Epochs: How many times to train on the data
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There are no tasks, two have the same name, a weight is negative, or a classification task has no categories
+    /// - ### InvalidTrainingData
+    /// There's no data, or an input doesn't have an answer for every task, or a regression answer isn't a number
+    /// - ### UnknownCategory
+    /// A classification answer isn't one of its task's categories
+    /// - The rest are the same as [`step_epoch`](fn@CatNetwork::step_epoch)
+    pub fn train(data: &[TaskInput], tasks: Vec<Task>, config: &TrainingConfig, epochs: usize) -> Result<MultiTaskNetwork, DarjeelingError> {
+        if tasks.is_empty() {
+            return Err(DarjeelingError::InvalidInput("A multi-task network needs at least one task".to_string()));
+        }
+        if let Some(i) = (1..tasks.len()).find(|i| tasks[..*i].iter().any(|task| task.name == tasks[*i].name)) {
+            return Err(DarjeelingError::InvalidInput(format!("There's more than one task called {}", tasks[i].name)));
+        }
+        if let Some(task) = tasks.iter().find(|task| task.weight.is_nan() || task.weight < 0.0 || task.outputs() == 0) {
+            return Err(DarjeelingError::InvalidInput(format!("{} needs a weight of at least 0 and at least one category", task.name)));
+        }
+        if data.is_empty() || data.iter().any(|input| input.answers.len() != tasks.len()) {
+            return Err(DarjeelingError::InvalidTrainingData(format!("A multi-task network needs data where every input has an answer for each of the {} tasks", tasks.len())));
+        }
+
+        let mut ranges: Vec<Option<(f32, f32)>> = Vec::with_capacity(tasks.len());
+        for (i, task) in tasks.iter().enumerate() {
+            ranges.push(match task.kind {
+                TaskKind::Classification(_) => None,
+                TaskKind::Regression => {
+                    let mut range: (f32, f32) = (f32::MAX, f32::MIN);
+                    for input in data {
+                        match input.answers[i].as_f32() {
+                            Some(answer) => range = (range.0.min(answer), range.1.max(answer)),
+                            None => return Err(DarjeelingError::InvalidTrainingData(format!("{} is a regression task, but {} isn't a number", task.name, input.answers[i])))
+                        }
+                    }
+                    Some(range)
+                }
+            });
+        }
+
+        let outputs: usize = tasks.iter().map(Task::outputs).sum();
+        let mut network: CatNetwork = CatNetwork::from_config(data[0].inputs.len() as i32, outputs as i32, config);
+        network.start_training(tasks.iter().flat_map(|task| match &task.kind {
+            TaskKind::Classification(categories) => categories.iter().map(|category| Types::String(format!("{} {}", task.name, category))).collect(),
+            TaskKind::Regression => vec![Types::String(task.name.clone())]
+        }).collect());
+        network.set_output_weights(tasks.iter().flat_map(|task| vec![task.weight; task.outputs()]).collect())?;
+        let mut multitask = MultiTaskNetwork { tasks, ranges, network };
+
+        let mut targets: Vec<Input> = Vec::with_capacity(data.len());
+        for input in data {
+            targets.push(Input::new(input.inputs.clone(), Some(Types::Vector(multitask.targets(&input.answers)?))));
+        }
+        for _ in 0..epochs {
+            multitask.network.step_epoch(&mut targets, config.learning_rate)?;
+        }
+        Ok(multitask)
+    }
+
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
+    }
+
+    /// The network underneath, with every task's answer nodes one head after another
+    pub fn network(&self) -> &CatNetwork {
+        &self.network
+    }
+
+    /// Every task's prediction, in the same order as the tasks
+    pub fn predict(&self, inputs: &[f32]) -> Vec<TaskOutput> {
+        let outputs: Vec<f32> = self.network.raw_outputs(inputs);
+        let mut start: usize = 0;
+        self.tasks.iter().zip(&self.ranges).map(|(task, range)| {
+            let head: &[f32] = &outputs[start..start + task.outputs()];
+            start += task.outputs();
+            match (&task.kind, range) {
+                (TaskKind::Classification(categories), _) => {
+                    let chosen: usize = (1..head.len()).fold(0, |chosen, i| if head[i] > head[chosen] { i } else { chosen });
+                    TaskOutput::Category(Prediction {
+                        category: categories[chosen].clone(),
+                        confidence: head[chosen],
+                        scores: categories.iter().cloned().zip(head.iter().copied()).collect(),
+                        tied: vec![]
+                    })
+                },
+                (TaskKind::Regression, Some((min, max))) => TaskOutput::Value(min + head[0] * (max - min)),
+                (TaskKind::Regression, None) => TaskOutput::Value(head[0])
+            }
+        }).collect()
+    }
+
+    /// One task's prediction, None if there isn't a task with the name
+    pub fn predict_task(&self, inputs: &[f32], task: &str) -> Option<TaskOutput> {
+        let i: usize = self.tasks.iter().position(|other| other.name == task)?;
+        self.predict(inputs).into_iter().nth(i)
+    }
+
+    /// How well every task was predicted, in the same order as the tasks.
+    /// Inputs without an answer for every task are skipped.
+    pub fn evaluate(&self, data: &[TaskInput]) -> Vec<TaskMetrics> {
+        let data: Vec<&TaskInput> = data.iter().filter(|input| input.answers.len() == self.tasks.len()).collect();
+        let predictions: Vec<Vec<TaskOutput>> = data.iter().map(|input| self.predict(&input.inputs)).collect();
+        self.tasks.iter().enumerate().map(|(i, task)| match task.kind {
+            TaskKind::Classification(_) => {
+                let predicted: Vec<Types> = predictions.iter().map(|outputs| match &outputs[i] {
+                    TaskOutput::Category(prediction) => prediction.category.clone(),
+                    TaskOutput::Value(value) => Types::Float(*value)
+                }).collect();
+                let truth: Vec<Types> = data.iter().map(|input| input.answers[i].clone()).collect();
+                TaskMetrics::Classification(ClassificationMetrics::compute(&predicted, &truth))
+            },
+            TaskKind::Regression => {
+                let (predicted, truth): (Vec<f32>, Vec<f32>) = predictions.iter().zip(&data).filter_map(|(outputs, input)| {
+                    let predicted: f32 = match &outputs[i] {
+                        TaskOutput::Value(value) => *value,
+                        TaskOutput::Category(_) => return None
+                    };
+                    Some((predicted, input.answers[i].as_f32()?))
+                }).unzip();
+                TaskMetrics::Regression(RegressionMetrics::compute(&predicted, &truth))
+            }
+        }).collect()
+    }
+
+    /// The target of every answer node for one input's answers
+    fn targets(&self, answers: &[Types]) -> Result<Vec<f32>, DarjeelingError> {
+        let mut targets: Vec<f32> = Vec::with_capacity(self.network.output_size());
+        for ((task, range), answer) in self.tasks.iter().zip(&self.ranges).zip(answers) {
+            match (&task.kind, range) {
+                (TaskKind::Classification(categories), _) => {
+                    let chosen: usize = match categories.iter().position(|category| category.matches(answer)) {
+                        Some(chosen) => chosen,
+                        None => return Err(DarjeelingError::UnknownCategory(format!("{} isn't one of the categories of {}", answer, task.name)))
+                    };
+                    targets.extend((0..categories.len()).map(|i| if i == chosen { 1.0 } else { 0.0 }));
+                },
+                (TaskKind::Regression, range) => {
+                    let answer: f32 = answer.as_f32().unwrap_or(0.0);
+                    targets.push(match range {
+                        Some((min, max)) if max > min => (answer - min) / (max - min),
+                        _ => 0.5
+                    });
+                }
+            }
+        }
+        Ok(targets)
+    }
+}
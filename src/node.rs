@@ -1,17 +1,37 @@
 use serde::{Deserialize, Serialize};
-use crate::{DEBUG, types::Types, activation::ActivationFunction, dbg_println};
+use rand::Rng;
+use crate::{DEBUG, error::DarjeelingError, types::Types, activation::ActivationFunction, config::Reduction, dbg_println};
 
 /// Represents a node in the network
+/// 
+/// The fields are only reachable inside the crate, so training can change how it keeps its state without breaking anyone.
+/// Read them through the methods of the same name, and change them with [`set_weights`](fn@Node::set_weights), [`set_category`](fn@Node::set_category) and [`randomize`](fn@Node::randomize).
+/// Code that assigned to the fields when they were public can use the deprecated `set_` method of each field while it moves over.
+/// The serialized form hasn't changed, so serialized nodes still read.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Node {
-    pub link_weights: Vec<f32>,
-    pub link_vals: Vec<Option<f32>>,
-    pub links: usize,
-    pub err_sig: Option<f32>,
-    pub correct_answer: Option<f32>,
-    pub cached_output: Option<f32>,
-    pub category: Option<Types>,
-    pub b_weight: Option<f32>,
+    pub(crate) link_weights: Vec<f32>,
+    pub(crate) link_vals: Vec<Option<f32>>,
+    pub(crate) links: usize,
+    pub(crate) err_sig: Option<f32>,
+    pub(crate) correct_answer: Option<f32>,
+    pub(crate) cached_output: Option<f32>,
+    pub(crate) category: Option<Types>,
+    pub(crate) b_weight: Option<f32>,
+}
+
+/// How [`randomize`](fn@Node::randomize) draws a node's weights
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum Initialization {
+    /// Weights and bias uniformly between -limit and limit
+    Uniform { limit: f32 },
+    /// The default, the same as `Uniform { limit: 0.5 }`, which is how new networks start
+    #[default]
+    Standard,
+    /// Weights uniformly within ±√(3 / links) and a bias of 0, which suits sigmoid and tanh
+    LeCun,
+    /// Weights uniformly within ±√(6 / links) and a bias of 0, which suits linear activations
+    He
 }
 
 impl Node {
@@ -26,6 +46,143 @@ impl Node {
         Node { link_weights: link_weights.to_vec(), link_vals, links: link_weights.len(), err_sig: None, correct_answer: None, cached_output: None, category: None, b_weight }
     }
 
+    /// The weight of the link from every node in the previous layer
+    pub fn link_weights(&self) -> &[f32] {
+        &self.link_weights
+    }
+
+    /// The number of links from the previous layer
+    pub fn links(&self) -> usize {
+        self.links
+    }
+
+    pub fn b_weight(&self) -> Option<f32> {
+        self.b_weight
+    }
+
+    /// The category of an answer node, None for other nodes or before the categories are set
+    pub fn category(&self) -> Option<&Types> {
+        self.category.as_ref()
+    }
+
+    /// The node's output from the last input that went through the network while training
+    pub fn cached_output(&self) -> Option<f32> {
+        self.cached_output
+    }
+
+    /// The node's error signal from the last backpropagation
+    pub fn err_sig(&self) -> Option<f32> {
+        self.err_sig
+    }
+
+    /// Replaces the link weights and bias. The number of links can change, so the node has to be in a layer that matches it.
+    /// 
+    /// ## Err
+    /// - ### InvalidInput
+    /// A weight or the bias isn't finite
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::node::Node;
+    /// 
+    /// let mut node = Node::new(&vec![0.1, 0.2], Some(0.0));
+    /// node.set_weights(vec![0.5, -0.5, 0.25], 1.0).unwrap();
+    /// assert_eq!(node.link_weights(), &[0.5, -0.5, 0.25]);
+    /// assert_eq!(node.links(), 3);
+    /// assert!(node.set_weights(vec![f32::NAN], 0.0).is_err());
+    /// ```
+    pub fn set_weights(&mut self, link_weights: Vec<f32>, b_weight: f32) -> Result<(), DarjeelingError> {
+        if let Some(weight) = link_weights.iter().chain([b_weight].iter()).find(|weight| !weight.is_finite()) {
+            return Err(DarjeelingError::InvalidInput(format!("Weights have to be finite, not {}", weight)));
+        }
+        self.links = link_weights.len();
+        self.link_vals = vec![None; link_weights.len()];
+        self.link_weights = link_weights;
+        self.b_weight = Some(b_weight);
+        Ok(())
+    }
+
+    pub fn set_category(&mut self, category: Option<Types>) {
+        self.category = category;
+    }
+
+    /// The value that came in on every link during the last input that went through the network while training
+    #[deprecated(note = "Link values are training state, use output_for to see what a node makes of some values")]
+    pub fn link_vals(&self) -> &[Option<f32>] {
+        &self.link_vals
+    }
+
+    /// The output an answer node was trained towards on the last input
+    #[deprecated(note = "The correct answer is training state that's only kept for the last input")]
+    pub fn correct_answer(&self) -> Option<f32> {
+        self.correct_answer
+    }
+
+    /// The same as assigning to the field, without checking the weights or changing the number of links
+    #[deprecated(note = "Use set_weights, which checks the weights and keeps the number of links in step")]
+    pub fn set_link_weights(&mut self, link_weights: Vec<f32>) {
+        self.link_weights = link_weights;
+    }
+
+    /// The same as assigning to the field
+    #[deprecated(note = "Use set_weights, which keeps the number of links in step with the weights")]
+    pub fn set_links(&mut self, links: usize) {
+        self.links = links;
+    }
+
+    /// The same as assigning to the field, without checking the bias
+    #[deprecated(note = "Use set_weights, which checks the bias is finite")]
+    pub fn set_b_weight(&mut self, b_weight: Option<f32>) {
+        self.b_weight = b_weight;
+    }
+
+    /// The same as assigning to the field
+    #[deprecated(note = "Link values are training state that's set by every input that goes through the network")]
+    pub fn set_link_vals(&mut self, link_vals: Vec<Option<f32>>) {
+        self.link_vals = link_vals;
+    }
+
+    /// The same as assigning to the field
+    #[deprecated(note = "The error signal is training state that's set by every backpropagation")]
+    pub fn set_err_sig(&mut self, err_sig: Option<f32>) {
+        self.err_sig = err_sig;
+    }
+
+    /// The same as assigning to the field
+    #[deprecated(note = "The correct answer is training state that's set for every input")]
+    pub fn set_correct_answer(&mut self, correct_answer: Option<f32>) {
+        self.correct_answer = correct_answer;
+    }
+
+    /// The same as assigning to the field
+    #[deprecated(note = "The cached output is training state that's set by every input that goes through the network")]
+    pub fn set_cached_output(&mut self, cached_output: Option<f32>) {
+        self.cached_output = cached_output;
+    }
+
+    /// Draws the link weights and bias again, keeping the number of links
+    /// 
+    /// ## Examples
+    /// ```
+    /// use darjeeling::node::{Node, Initialization};
+    /// 
+    /// let mut node = Node::new(&vec![0.0; 12], Some(0.0));
+    /// node.randomize(&mut rand::thread_rng(), Initialization::He);
+    /// assert!(node.link_weights().iter().all(|weight| weight.abs() <= (6.0_f32 / 12.0).sqrt()));
+    /// assert_eq!(node.b_weight(), Some(0.0));
+    /// ```
+    pub fn randomize<R: Rng>(&mut self, rng: &mut R, init: Initialization) {
+        let (limit, random_bias): (f32, bool) = match init {
+            Initialization::Uniform { limit } => (limit, true),
+            Initialization::Standard => (0.5, true),
+            Initialization::LeCun => ((3.0 / self.links.max(1) as f32).sqrt(), false),
+            Initialization::He => ((6.0 / self.links.max(1) as f32).sqrt(), false)
+        };
+        let draw = |rng: &mut R| if limit > 0.0 { rng.gen_range(-limit..limit) } else { 0.0 };
+        self.b_weight = Some(if random_bias { draw(rng) } else { 0.0 });
+        self.link_weights.iter_mut().for_each(|weight| *weight = draw(rng));
+    }
+
     fn input(&mut self) -> f32 {
         let mut sum: f32 = 0.00;
         for i in 0..self.links {
@@ -54,7 +211,65 @@ impl Node {
         
         self.cached_output.unwrap()
     }
+
+    /// [`output`](fn@output), adding up the weighted inputs with the reduction
+    pub(crate) fn output_reduced(&mut self, activation: &ActivationFunction, reduction: Reduction) -> f32 {
+        if !reduction.is_deterministic() {
+            return self.output(activation);
+        }
+        let sum: f32 = reduction.sum((0..self.links)
+            .map(|i| self.link_vals[i].unwrap_or(0.0) * self.link_weights[i])
+            .chain(std::iter::once(self.b_weight.unwrap())));
+        let output: f32 = Node::activate(sum, activation);
+        self.cached_output = Some(output);
+        output
+    }
+    
+    /// Computes what the node would output for the given values from the previous layer, without changing the node
+    pub fn output_for(&self, values: &[f32], activation: &ActivationFunction) -> f32 {
+        let sum: f32 = values
+            .iter()
+            .zip(&self.link_weights)
+            .map(|(value, weight)| value * weight)
+            .sum::<f32>() + self.b_weight.unwrap_or(0.0);
+
+        Node::activate(sum, activation)
+    }
+
+    /// [`output_for`](fn@output_for), adding up the weighted values with the reduction
+    pub(crate) fn output_for_reduced(&self, values: &[f32], activation: &ActivationFunction, reduction: Reduction) -> f32 {
+        if !reduction.is_deterministic() {
+            return self.output_for(values, activation);
+        }
+        let sum: f32 = reduction.sum(values
+            .iter()
+            .zip(&self.link_weights)
+            .map(|(value, weight)| value * weight)
+            .chain(std::iter::once(self.b_weight.unwrap_or(0.0))));
+        Node::activate(sum, activation)
+    }
+
+    fn activate(sum: f32, activation: &ActivationFunction) -> f32 {
+        match *activation {
+            ActivationFunction::Sigmoid => Node::sigmoid(sum),
+
+            ActivationFunction::Linear => Node::linear(sum),
+
+            ActivationFunction::Tanh => Node::tanh(sum),
+        }
+    }
     
+    /// The slope of the activation function at a node whose output was `y`
+    pub(crate) fn derivative(y: f32, activation: &ActivationFunction) -> f32 {
+        match activation {
+            ActivationFunction::Sigmoid => y * (1.0 - y),
+
+            ActivationFunction::Linear => 2.0,
+
+            ActivationFunction::Tanh => 1.0 - y * y,
+        }
+    }
+
     pub fn compute_answer_err_sig(&mut self, activation: &ActivationFunction) {
         if DEBUG { println!("Err Signal Pre: {:?}", self.err_sig); }
         let y = self.cached_output.unwrap();
@@ -74,7 +289,9 @@ impl Node {
         if DEBUG { println!("Err Signal Post: {:?}", self.err_sig.unwrap()) }
     }
 
-    pub fn compute_answer_err_sig_gen(&mut self, mse: f32, activation: &ActivationFunction) {
+    /// Sets the error signal of a generation model's answer node from how much, and which way, its output should move.
+    /// Positive grows the output, since [`adjust_weights`](fn@Node::adjust_weights) adds the error signal.
+    pub fn compute_answer_err_sig_gen(&mut self, signal: f32, activation: &ActivationFunction) {
         if DEBUG { println!("Err Signal Pre: {:?}", self.err_sig); }
         // This is where the derivative of the activation function goes I think
         let y = self.cached_output.unwrap();
@@ -90,7 +307,7 @@ impl Node {
                 derivative = 1.0 - unsafe { std::intrinsics::powf32(y, 2.0) };
             }
         }
-        self.err_sig = Some(mse * derivative);
+        self.err_sig = Some(signal * derivative);
         if DEBUG { println!("Err Signal Post: {:?}", self.err_sig.unwrap()) }
     }
 
@@ -103,6 +320,14 @@ impl Node {
         }
     }
 
+    /// Scales the link weights down so their L2 norm is at most max_norm, leaving the bias as it is
+    pub fn constrain_norm(&mut self, max_norm: f32) {
+        let norm: f32 = self.link_weights.iter().map(|weight| weight * weight).sum::<f32>().sqrt();
+        if norm > max_norm {
+            self.link_weights.iter_mut().for_each(|weight| *weight *= max_norm / norm);
+        }
+    }
+
     fn sigmoid(x: f32) -> f32 {
 
         1.0/(1.0+((-x).exp()))
@@ -125,3 +350,36 @@ impl Node {
         else { 1.00 }
     }
 }
+
+/// Checks that two networks' layers have the same shape, so their weights line up one to one
+///
+/// ## Err
+/// - ### ArchitectureMismatch
+/// The layers have different numbers of layers, nodes or links
+pub(crate) fn check_same_shape(a: &[Vec<Node>], b: &[Vec<Node>]) -> Result<(), DarjeelingError> {
+    if a.len() != b.len() {
+        return Err(DarjeelingError::ArchitectureMismatch(format!("{} layers and {} layers", a.len(), b.len())));
+    }
+    for (layer, (nodes, other_nodes)) in a.iter().zip(b).enumerate() {
+        if nodes.len() != other_nodes.len() {
+            return Err(DarjeelingError::ArchitectureMismatch(format!("Layer {} has {} nodes and {} nodes", layer, nodes.len(), other_nodes.len())));
+        }
+        if let Some((node, other_node)) = nodes.iter().zip(other_nodes).find(|(node, other_node)| node.links != other_node.links) {
+            return Err(DarjeelingError::ArchitectureMismatch(format!("Layer {} has nodes with {} links and {} links", layer, node.links, other_node.links)));
+        }
+    }
+    Ok(())
+}
+
+/// Copies every link weight and bias from one network's layers into another's
+///
+/// ## Err
+/// The same as [`check_same_shape`]
+pub(crate) fn copy_weights(into: &mut [Vec<Node>], from: &[Vec<Node>]) -> Result<(), DarjeelingError> {
+    check_same_shape(into, from)?;
+    into.iter_mut().flatten().zip(from.iter().flatten()).for_each(|(node, other_node)| {
+        node.link_weights.copy_from_slice(&other_node.link_weights);
+        node.b_weight = other_node.b_weight;
+    });
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use core::fmt;
+use std::collections::{HashMap, VecDeque};
+use serde::{Serialize, Deserialize};
+use crate::{error::DarjeelingError, metrics::ClassificationMetrics, prediction::Prediction, types::Types};
+
+/// The metrics of the most recent answers an [`OnlineEvaluator`] has seen
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindowMetrics {
+    /// The most answers the window holds
+    pub size: usize,
+    /// The number of answers in the window, less than the size until that many have been seen
+    pub count: usize,
+    pub metrics: ClassificationMetrics
+}
+
+/// Measures how a deployed model does as the correct answers to its predictions come in, over rolling windows of the most recent answers.
+/// Comparing a short window with a long one, or with every answer so far, shows when the model starts to decay.
+///
+/// Answers can be recorded with their predictions when both are known at once,
+/// or a prediction can be [tracked](fn@OnlineEvaluator::track) under an id and [resolved](fn@OnlineEvaluator::resolve) when its answer arrives.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{online::OnlineEvaluator, types::Types};
+///
+/// let mut evaluator = OnlineEvaluator::new(&[2, 100]).unwrap();
+/// evaluator.record(Types::Integer(1), Types::Integer(1));
+/// evaluator.track("order 7", Types::Integer(0));
+/// evaluator.record(Types::Integer(1), Types::Integer(0));
+/// // The answer to order 7 comes in a day later
+/// assert!(evaluator.resolve("order 7", Types::Integer(1)));
+/// let windows = evaluator.windows();
+/// assert_eq!(windows[0].metrics.accuracy, 0.0);
+/// assert!((windows[1].metrics.accuracy - 1.0 / 3.0).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OnlineEvaluator {
+    /// From smallest to largest
+    sizes: Vec<usize>,
+    /// The predicted and correct category of the most recent answers, oldest first, as many as the largest window
+    recent: VecDeque<(Types, Types)>,
+    /// Predictions still waiting for their answers
+    pending: HashMap<String, Types>,
+    seen: usize,
+    correct: usize
+}
+
+impl OnlineEvaluator {
+
+    /// ## Params
+    /// - Sizes: How many of the most recent answers each window holds
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There are no windows, or one holds no answers
+    pub fn new(sizes: &[usize]) -> Result<OnlineEvaluator, DarjeelingError> {
+        if sizes.is_empty() || sizes.contains(&0) {
+            return Err(DarjeelingError::InvalidInput("An online evaluator needs at least one window of at least one answer".to_string()));
+        }
+        let mut sizes: Vec<usize> = sizes.to_vec();
+        sizes.sort_unstable();
+        sizes.dedup();
+        let largest: usize = sizes[sizes.len() - 1];
+        Ok(OnlineEvaluator { sizes, recent: VecDeque::with_capacity(largest), pending: HashMap::new(), seen: 0, correct: 0 })
+    }
+
+    /// Records a prediction and the correct answer to it
+    pub fn record(&mut self, prediction: Types, truth: Types) {
+        self.seen += 1;
+        if prediction == truth {
+            self.correct += 1;
+        }
+        if self.recent.len() == self.sizes[self.sizes.len() - 1] {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((prediction, truth));
+    }
+
+    /// Records a [`Prediction`] from [`predict`](fn@crate::categorize::CatNetwork::predict) and the correct answer to it
+    pub fn record_prediction(&mut self, prediction: &Prediction, truth: Types) {
+        self.record(prediction.category.clone(), truth);
+    }
+
+    /// Keeps a prediction until its answer comes in with [`resolve`](fn@OnlineEvaluator::resolve).
+    /// Tracking another prediction under the same id replaces the first.
+    pub fn track(&mut self, id: &str, prediction: Types) {
+        self.pending.insert(id.to_string(), prediction);
+    }
+
+    /// Records the answer to a tracked prediction
+    ///
+    /// ## Returns
+    /// Whether a prediction was tracked under the id
+    pub fn resolve(&mut self, id: &str, truth: Types) -> bool {
+        match self.pending.remove(id) {
+            Some(prediction) => {
+                self.record(prediction, truth);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// The number of tracked predictions still waiting for their answers
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// The number of answers recorded since the evaluator was made
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// The accuracy over every answer recorded since the evaluator was made, 0 before the first
+    pub fn lifetime_accuracy(&self) -> f32 {
+        if self.seen == 0 { 0.0 } else { self.correct as f32 / self.seen as f32 }
+    }
+
+    /// The metrics of every window, from smallest to largest
+    pub fn windows(&self) -> Vec<WindowMetrics> {
+        self.sizes.iter().map(|size| {
+            let (predictions, truth): (Vec<Types>, Vec<Types>) = self.recent.iter()
+                .skip(self.recent.len().saturating_sub(*size))
+                .cloned()
+                .unzip();
+            WindowMetrics { size: *size, count: truth.len(), metrics: ClassificationMetrics::compute(&predictions, &truth) }
+        }).collect()
+    }
+
+    /// The metrics of the window with the size, None if there isn't one
+    pub fn window(&self, size: usize) -> Option<WindowMetrics> {
+        self.windows().into_iter().find(|window| window.size == size)
+    }
+}
+
+impl fmt::Display for OnlineEvaluator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} answers, {} pending, lifetime accuracy {:.4}", self.seen, self.pending.len(), self.lifetime_accuracy())?;
+        write!(f, "{:>8} {:>8} {:>9} {:>9}", "Window", "Count", "Accuracy", "Macro F1")?;
+        for window in self.windows() {
+            write!(f, "\n{:>8} {:>8} {:>9.4} {:>9.4}", window.size, window.count, window.metrics.accuracy, window.metrics.macro_f1)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    error::DarjeelingError,
+    input::Input,
+    metrics::OrdinalMetrics,
+    prediction::Prediction,
+    types::Types
+};
+
+/// A network for answers that come in order, like ratings from 1 to 5.
+/// Plain categorization treats being off by one the same as being off by four,
+/// and plain regression answers with values between the levels.
+///
+/// There's an answer node for every threshold between two levels, which learns how likely the answer is above it.
+/// The predicted level is the number of thresholds the input is likely above, so the network learns the order of the levels
+/// and only ever answers with one of them.
+///
+/// ## Examples
+/// ```ignore
+/// let levels: Vec<Types> = (1..=5).map(Types::Integer).collect();
+/// let rater = OrdinalNetwork::train(&data, levels, &TrainingConfig::default(), 1000)?;
+/// let rating = rater.predict(&inputs).category;
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrdinalNetwork {
+    /// Every level, lowest first
+    levels: Vec<Types>,
+    /// One answer node for each threshold between two levels
+    network: CatNetwork
+}
+
+impl OrdinalNetwork {
+
+    /// Trains a network with an answer node for every threshold between the levels,
+    /// until its exact accuracy on the data reaches the config's target error percent or the epoch limit
+    ///
+    /// ## Params
+    /// - Data: Every input needs an answer that's one of the levels
+    /// - Levels: Every level, lowest first
+    /// - Config: The architecture, learning rate and target error percent of the network
+    /// - Max Epochs: Stops training after this many epochs, even if it hasn't reached the target error percent
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There are fewer than two levels, or a level is listed twice
+    /// - ### InvalidTrainingData
+    /// There's no data, or an input doesn't have an answer
+    /// - ### UnknownCategory
+    /// An answer isn't one of the levels
+    /// - The rest are the same as [`step_epoch`](fn@CatNetwork::step_epoch)
+    pub fn train(data: &[Input], levels: Vec<Types>, config: &TrainingConfig, max_epochs: usize) -> Result<OrdinalNetwork, DarjeelingError> {
+        if levels.len() < 2 {
+            return Err(DarjeelingError::InvalidInput(format!("An ordinal network needs at least two levels, but there are {}", levels.len())));
+        }
+        if let Some(i) = (1..levels.len()).find(|i| levels[..*i].contains(&levels[*i])) {
+            return Err(DarjeelingError::InvalidInput(format!("{} is listed as a level more than once", levels[i])));
+        }
+        if data.is_empty() || data.iter().any(|input| input.answer.is_none()) {
+            return Err(DarjeelingError::InvalidTrainingData("An ordinal network needs data where every input has an answer".to_string()));
+        }
+
+        let mut cumulative: Vec<Input> = Vec::with_capacity(data.len());
+        for input in data {
+            let answer: &Types = input.answer.as_ref().expect("Every input has an answer");
+            let level: usize = match levels.iter().position(|level| level == answer) {
+                Some(level) => level,
+                None => return Err(DarjeelingError::UnknownCategory(format!("{} isn't one of the levels", answer)))
+            };
+            let targets: Vec<f32> = (0..levels.len() - 1).map(|threshold| if level > threshold { 1.0 } else { 0.0 }).collect();
+            cumulative.push(Input::new(input.inputs.clone(), Some(Types::Vector(targets))));
+        }
+
+        let mut network: CatNetwork = CatNetwork::from_config(data[0].inputs.len() as i32, levels.len() as i32 - 1, config);
+        network.start_training(levels[..levels.len() - 1].iter().map(|level| Types::String(format!("above {}", level))).collect());
+        let mut ordinal = OrdinalNetwork { levels, network };
+        for _ in 0..max_epochs {
+            ordinal.network.step_epoch(&mut cumulative, config.learning_rate)?;
+            if ordinal.evaluate(data).accuracy * 100.0 >= config.target_err_percent {
+                break;
+            }
+        }
+        Ok(ordinal)
+    }
+
+    pub fn levels(&self) -> &[Types] {
+        &self.levels
+    }
+
+    /// The network underneath, with an answer node for each threshold
+    pub fn network(&self) -> &CatNetwork {
+        &self.network
+    }
+
+    /// How likely the answer is above each threshold, lowest first.
+    /// Each is at most the one before it, so a higher threshold is never more likely than a lower one.
+    pub fn cumulative_probabilities(&self, inputs: &[f32]) -> Vec<f32> {
+        let mut previous: f32 = 1.0;
+        self.network.raw_outputs(inputs).into_iter().map(|output| {
+            previous = output.clamp(0.0, previous);
+            previous
+        }).collect()
+    }
+
+    /// The index of the predicted level, which is the number of thresholds the input is more likely above than not
+    pub fn predict_level(&self, inputs: &[f32]) -> usize {
+        self.cumulative_probabilities(inputs).iter().filter(|probability| **probability > 0.5).count()
+    }
+
+    /// The predicted level, with how likely every level is as the scores, and the chosen level's likelihood as the confidence
+    pub fn predict(&self, inputs: &[f32]) -> Prediction {
+        let cumulative: Vec<f32> = self.cumulative_probabilities(inputs);
+        let level: usize = cumulative.iter().filter(|probability| **probability > 0.5).count();
+        let scores: Vec<(Types, f32)> = self.levels.iter().enumerate().map(|(i, category)| {
+            let above_previous: f32 = if i == 0 { 1.0 } else { cumulative[i - 1] };
+            let above: f32 = cumulative.get(i).copied().unwrap_or(0.0);
+            (category.clone(), above_previous - above)
+        }).collect();
+        Prediction { category: self.levels[level].clone(), confidence: scores[level].1, scores, tied: vec![] }
+    }
+
+    /// How close the predicted levels are to the answers. Inputs without an answer, or with one that isn't a level, are skipped.
+    pub fn evaluate(&self, data: &[Input]) -> OrdinalMetrics {
+        let (predictions, truth): (Vec<usize>, Vec<usize>) = data.iter().filter_map(|input| {
+            let answer: usize = self.levels.iter().position(|level| Some(level) == input.answer.as_ref())?;
+            Some((self.predict_level(&input.inputs), answer))
+        }).unzip();
+        OrdinalMetrics::compute(&predictions, &truth)
+    }
+}
@@ -0,0 +1,115 @@
+//! How many threads darjeeling's parallel features run on.
+//!
+//! Training several networks at once, [parallel CSV parsing](crate::csv::parse_csv) and the rest all run on one thread pool of darjeeling's own,
+//! instead of rayon's global pool, so a service with pools of its own can keep darjeeling to a few threads.
+//! Until [`set_threads`] is called, the pool has as many threads as `RAYON_NUM_THREADS` says, or one for every core if it isn't set.
+//!
+//! Without the `parallel` feature there's no pool, rayon isn't a dependency, and everything runs on the calling thread.
+
+use std::sync::Mutex;
+#[cfg(feature = "parallel")]
+use std::sync::Arc;
+#[cfg(feature = "parallel")]
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
+use crate::error::DarjeelingError;
+
+struct Pool {
+    /// None for rayon's default
+    threads: Option<usize>,
+    /// Built the first time something runs in parallel after the number of threads is set
+    #[cfg(feature = "parallel")]
+    pool: Option<Arc<ThreadPool>>
+}
+
+impl Pool {
+
+    const fn new(threads: Option<usize>) -> Pool {
+        Pool { threads, #[cfg(feature = "parallel")] pool: None }
+    }
+}
+
+static POOL: Mutex<Pool> = Mutex::new(Pool::new(None));
+
+/// Sets the number of threads every parallel feature shares. Work already running carries on with the threads it had.
+///
+/// ## Params
+/// - Threads: None goes back to `RAYON_NUM_THREADS`, or one thread for every core
+///
+/// ## Err
+/// - ### InvalidInput
+/// The number of threads is 0
+///
+/// ## Examples
+/// ```
+/// use darjeeling::parallel;
+///
+/// parallel::set_threads(Some(2)).unwrap();
+/// assert_eq!(parallel::configured_threads(), Some(2));
+/// assert!(parallel::set_threads(Some(0)).is_err());
+/// # parallel::set_threads(None).unwrap();
+/// ```
+pub fn set_threads(threads: Option<usize>) -> Result<(), DarjeelingError> {
+    if threads == Some(0) {
+        return Err(DarjeelingError::InvalidInput("Parallel features need at least one thread".to_string()));
+    }
+    let mut pool = POOL.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *pool = Pool::new(threads);
+    Ok(())
+}
+
+/// The number of threads set with [`set_threads`], None if it hasn't been
+pub fn configured_threads() -> Option<usize> {
+    POOL.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).threads
+}
+
+/// The number of threads parallel features run on, 1 without the `parallel` feature or if the platform can't start threads
+pub fn threads() -> usize {
+    #[cfg(feature = "parallel")]
+    return pool().map(|pool| pool.current_num_threads()).unwrap_or(1);
+    #[cfg(not(feature = "parallel"))]
+    1
+}
+
+/// Runs work on darjeeling's pool, so any rayon iterators in it use the pool's threads.
+/// The work runs on the calling thread instead if the pool couldn't be started.
+#[cfg(feature = "parallel")]
+pub(crate) fn install<R: Send, F: FnOnce() -> R + Send>(work: F) -> R {
+    match pool() {
+        Some(pool) => pool.install(work),
+        None => work()
+    }
+}
+
+/// Calls the closure on every item, on the pool when parallel is true and the `parallel` feature is on,
+/// and one after another otherwise. The results are in the same order as the items either way.
+pub(crate) fn map<T: Sync, R: Send, F: Fn(&T) -> R + Sync + Send>(items: &[T], parallel: bool, f: F) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        return install(|| items.par_iter().map(f).collect());
+    }
+    let _ = parallel;
+    items.iter().map(f).collect()
+}
+
+/// Calls the closure on every item, on the pool with the `parallel` feature and one after another without it
+pub(crate) fn for_each_mut<T: Send, F: Fn(&mut T) + Sync + Send>(items: &mut [T], f: F) {
+    #[cfg(feature = "parallel")]
+    install(|| items.par_iter_mut().for_each(f));
+    #[cfg(not(feature = "parallel"))]
+    items.iter_mut().for_each(f);
+}
+
+#[cfg(feature = "parallel")]
+fn pool() -> Option<Arc<ThreadPool>> {
+    let mut pool = POOL.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if pool.pool.is_none() {
+        // 0 threads is rayon's default, which reads RAYON_NUM_THREADS
+        pool.pool = ThreadPoolBuilder::new()
+            .num_threads(pool.threads.unwrap_or(0))
+            .thread_name(|i| format!("darjeeling-{}", i))
+            .build()
+            .ok()
+            .map(Arc::new);
+    }
+    pool.pool.clone()
+}
@@ -0,0 +1,433 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+use serde::{Serialize, Deserialize};
+use crate::{
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    encoder::{self, Encoder, Encoding},
+    error::DarjeelingError,
+    input::Input,
+    prediction::Prediction,
+    scaler::Scaler,
+    types::Types
+};
+
+/// What a missing value is filled in with
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum Imputation {
+    /// The mean of the column, or its most frequent value if it isn't numeric
+    #[default]
+    Mean,
+    /// The median of the column, or its most frequent value if it isn't numeric
+    Median,
+    /// The most frequent value of the column
+    MostFrequent
+}
+
+/// Fills in the missing values of raw rows, with a value for every column learned from the data it was fit on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Imputer {
+    imputation: Imputation,
+    /// The value for every column, None if the column never had one
+    fills: Vec<Option<Types>>
+}
+
+impl Imputer {
+
+    /// Learns the value to fill in for every column
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{pipeline::{Imputer, Imputation}, types::Types};
+    ///
+    /// let rows = vec![
+    ///     vec![Some(Types::Float(1.0)), Some(Types::String("red".to_string()))],
+    ///     vec![Some(Types::Float(3.0)), None],
+    ///     vec![None, Some(Types::String("red".to_string()))]
+    /// ];
+    /// let imputer = Imputer::fit(&rows, Imputation::Mean);
+    /// assert_eq!(imputer.impute(&rows[2]).unwrap(), vec![Types::Float(2.0), Types::String("red".to_string())]);
+    /// ```
+    pub fn fit(rows: &[Vec<Option<Types>>], imputation: Imputation) -> Imputer {
+        let columns: usize = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let fills: Vec<Option<Types>> = (0..columns).map(|column| {
+            let values: Vec<&Types> = rows.iter().filter_map(|row| row.get(column).and_then(Option::as_ref)).collect();
+            let numbers: Option<Vec<f32>> = values.iter().map(|value| value.as_f32()).collect();
+            match (imputation, numbers) {
+                (_, _) if values.is_empty() => None,
+                (Imputation::Mean, Some(numbers)) => Some(Types::Float(numbers.iter().sum::<f32>() / numbers.len() as f32)),
+                (Imputation::Median, Some(mut numbers)) => {
+                    numbers.sort_by(|a, b| a.total_cmp(b));
+                    let middle: usize = numbers.len() / 2;
+                    Some(Types::Float(match numbers.len() % 2 {
+                        0 => (numbers[middle - 1] + numbers[middle]) / 2.0,
+                        _ => numbers[middle]
+                    }))
+                },
+                _ => most_frequent(&values)
+            }
+        }).collect();
+
+        Imputer { imputation, fills }
+    }
+
+    pub fn imputation(&self) -> Imputation {
+        self.imputation
+    }
+
+    /// The number of columns the imputer was fit on
+    pub fn columns(&self) -> usize {
+        self.fills.len()
+    }
+
+    /// Fills in the missing values of a row
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// A value is missing from a column that never had one in the data the imputer was fit on
+    pub fn impute(&self, row: &[Option<Types>]) -> Result<Vec<Types>, DarjeelingError> {
+        row.iter().enumerate().map(|(column, value)| match (value, self.fills.get(column)) {
+            (Some(value), _) => Ok(value.clone()),
+            (None, Some(Some(fill))) => Ok(fill.clone()),
+            (None, _) => Err(DarjeelingError::InvalidInput(format!("Column {} is missing a value, and there's nothing to fill it with", column)))
+        }).collect()
+    }
+}
+
+/// The value that appears the most, the first one to appear if there's a tie
+fn most_frequent(values: &[&Types]) -> Option<Types> {
+    let mut counts: Vec<(&Types, usize)> = vec![];
+    values.iter().for_each(|value| match counts.iter_mut().find(|(counted, _)| counted == value) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((value, 1))
+    });
+    let mut most: Option<(&Types, usize)> = None;
+    counts.into_iter().for_each(|(value, count)| {
+        if most.map_or(true, |(_, most_count)| count > most_count) {
+            most = Some((value, count));
+        }
+    });
+    most.map(|(value, _)| value.clone())
+}
+
+/// How the encoded inputs are scaled before they reach the sensors
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Scaling {
+    MinMax,
+    ZScore
+}
+
+/// Raw feature rows in, predictions out. Missing values are [imputed](Imputer), columns that aren't numbers are [encoded](Encoder),
+/// and the encoded inputs are [scaled](Scaler) by the network. Everything is fit on the training rows,
+/// and the whole pipeline is saved as one JSON file, so callers never have to preprocess rows themselves.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{config::TrainingConfig, encoder::Encoding, pipeline::{Pipeline, Imputation, Scaling}, types::Types};
+///
+/// let rows = vec![
+///     vec![Some(Types::Float(0.1)), Some(Types::String("low".to_string()))],
+///     vec![None, Some(Types::String("high".to_string()))],
+///     vec![Some(Types::Float(0.2)), None],
+///     vec![Some(Types::Float(0.9)), Some(Types::String("high".to_string()))]
+/// ];
+/// let answers = vec![Types::Boolean(false), Types::Boolean(true), Types::Boolean(false), Types::Boolean(true)];
+/// let mut pipeline = Pipeline::new(TrainingConfig::default().with_hidden_num(4).with_write(false))
+///     .with_imputation(Imputation::MostFrequent)
+///     .with_encoding(Encoding::OneHot)
+///     .with_scaling(Scaling::MinMax)
+///     .with_max_epochs(50);
+/// pipeline.fit(&rows, &answers).unwrap();
+///
+/// let read = Pipeline::from_json_str(&pipeline.to_json_string().unwrap()).unwrap();
+/// let prediction = read.predict(&[Some(Types::Float(0.8)), None]).unwrap();
+/// assert_eq!(prediction.scores.len(), 2);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pipeline {
+    config: TrainingConfig,
+    /// Missing values are an error when there's no imputation
+    imputation: Option<Imputation>,
+    encoding: Encoding,
+    scaling: Option<Scaling>,
+    max_epochs: Option<usize>,
+    imputer: Option<Imputer>,
+    /// None until the pipeline is fit
+    network: Option<CatNetwork>,
+    /// The number of raw columns the network's preprocessing expects, worked out when the pipeline is fit or loaded
+    #[serde(skip)]
+    columns: usize
+}
+
+impl Pipeline {
+
+    /// A pipeline that trains a network built from the config, without imputing or scaling, one-hot encoding columns that aren't numbers.
+    /// The config's directories and write setting aren't used, save the whole pipeline with [`write`](fn@Pipeline::write) instead.
+    pub fn new(config: TrainingConfig) -> Pipeline {
+        Pipeline { config, imputation: None, encoding: Encoding::OneHot, scaling: None, max_epochs: None, imputer: None, network: None, columns: 0 }
+    }
+
+    /// A pipeline around a network that's already trained, like one read from a .darj file,
+    /// so raw rows go through the scaler and encoders that were saved with it.
+    /// Missing values are an error unless an imputer is added with [`with_imputer`](fn@Pipeline::with_imputer).
+    ///
+    /// ## Err
+    /// - ### InvalidMetadataRead
+    /// The network's encoders or scaler don't fit its sensors
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// let pipeline = Pipeline::from_network(CatNetwork::read_model("model_iris_123.darj".to_string())?)?;
+    /// let prediction = pipeline.predict(&[Some(Types::Float(5.1)), Some(Types::String("long".to_string()))])?;
+    /// ```
+    pub fn from_network(network: CatNetwork) -> Result<Pipeline, DarjeelingError> {
+        let mut pipeline: Pipeline = Pipeline::new(TrainingConfig::default());
+        pipeline.scaling = match network.scaler() {
+            Some(Scaler::MinMax { .. }) => Some(Scaling::MinMax),
+            Some(Scaler::ZScore { .. }) => Some(Scaling::ZScore),
+            Some(Scaler::Clipped { scaler, .. }) if matches!(**scaler, Scaler::MinMax { .. }) => Some(Scaling::MinMax),
+            Some(Scaler::Clipped { .. }) => Some(Scaling::ZScore),
+            None => None
+        };
+        if let Some(encoder) = network.encoders().iter().flatten().next() {
+            pipeline.encoding = encoder.encoding();
+        }
+        pipeline.network = Some(network);
+        pipeline.rewire()?;
+        Ok(pipeline)
+    }
+
+    /// Fills in missing values with an imputer fit elsewhere, replacing the one the pipeline was fit with
+    ///
+    /// ## Err
+    /// - ### InvalidMetadataRead
+    /// The imputer was fit on a different number of columns than the network takes
+    pub fn with_imputer(mut self, imputer: Imputer) -> Result<Pipeline, DarjeelingError> {
+        self.imputation = Some(imputer.imputation());
+        self.imputer = Some(imputer);
+        self.rewire()?;
+        Ok(self)
+    }
+
+    pub fn with_imputation(mut self, imputation: Imputation) -> Pipeline {
+        self.imputation = Some(imputation);
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: Encoding) -> Pipeline {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn with_scaling(mut self, scaling: Scaling) -> Pipeline {
+        self.scaling = Some(scaling);
+        self
+    }
+
+    /// Stops training after this many epochs, even if the target error percent hasn't been reached
+    pub fn with_max_epochs(mut self, max_epochs: usize) -> Pipeline {
+        self.max_epochs = Some(max_epochs);
+        self
+    }
+
+    /// The trained network, None until the pipeline is fit
+    pub fn network(&self) -> Option<&CatNetwork> {
+        self.network.as_ref()
+    }
+
+    /// Fits every preprocessing step on the rows, then trains a new network on them.
+    /// The categories are every answer, [sorted](fn@crate::dataset::Dataset::sorted_categories).
+    ///
+    /// ## Params
+    /// - Rows: The raw feature rows, with None for missing values
+    /// - Answers: The answer for every row
+    ///
+    /// ## Returns
+    /// The percent of the rows categorized correctly in the last epoch
+    ///
+    /// ## Err
+    /// - ### InvalidTrainingData
+    /// There are no rows, or there isn't an answer for every row
+    /// - ### InvalidInput
+    /// A value is missing and the pipeline doesn't impute, or a column has no values at all
+    /// - The rest are the same as [`step_epoch`](fn@CatNetwork::step_epoch)
+    pub fn fit(&mut self, rows: &[Vec<Option<Types>>], answers: &[Types]) -> Result<f32, DarjeelingError> {
+        if rows.is_empty() || rows.len() != answers.len() {
+            return Err(DarjeelingError::InvalidTrainingData(format!("{} rows and {} answers", rows.len(), answers.len())));
+        }
+        let imputer: Option<Imputer> = self.imputation.map(|imputation| Imputer::fit(rows, imputation));
+        let complete: Vec<Vec<Types>> = rows.iter().map(|row| impute(imputer.as_ref(), row)).collect::<Result<_, _>>()?;
+
+        let columns: usize = complete.iter().map(Vec::len).max().unwrap_or(0);
+        let encoders: Vec<Option<Encoder>> = (0..columns).map(|column| {
+            let values: Vec<Types> = complete.iter().filter_map(|row| row.get(column).cloned()).collect();
+            match values.iter().all(|value| value.as_f32().is_some()) {
+                true => None,
+                false => Some(Encoder::fit(&values, self.encoding))
+            }
+        }).collect();
+        let mut data: Vec<Input> = complete.iter().zip(answers)
+            .map(|(row, answer)| encoder::encode_row(&encoders, row, Some(answer.clone())))
+            .collect::<Result<_, _>>()?;
+
+        let mut net: CatNetwork = CatNetwork::new_from_data(&data, self.config.hidden_num, self.config.hidden_layers, self.config.activation_function)?;
+        net.set_regularization(self.config.regularization);
+        net.set_schedule(self.config.schedule);
+        net.set_reduction(self.config.reduction);
+        net.set_encoders(encoders);
+        net.set_scaler(match self.scaling {
+            Some(Scaling::MinMax) => Some(Scaler::fit_min_max(&data)),
+            Some(Scaling::ZScore) => Some(Scaler::fit_z_score(&data)),
+            None => None
+        });
+        let categories: Vec<Types> = net.categories().unwrap_or_default();
+        net.start_training(categories);
+
+        let mut err_percent: f32 = 0.0;
+        let mut epochs: usize = 0;
+        while err_percent < self.config.target_err_percent && self.max_epochs.map_or(true, |max_epochs| epochs < max_epochs) {
+            err_percent = net.step_epoch(&mut data, self.config.learning_rate)?.accuracy * 100.0;
+            epochs += 1;
+        }
+
+        self.imputer = imputer;
+        self.network = Some(net);
+        self.rewire()?;
+        Ok(err_percent)
+    }
+
+    /// Works out how many raw columns the preprocessing takes, and checks that the imputer, encoders and scaler agree with each other and the network,
+    /// so a loaded pipeline transforms rows exactly the way training did, or fails to load
+    fn rewire(&mut self) -> Result<(), DarjeelingError> {
+        let net: &CatNetwork = match &self.network {
+            Some(net) => net,
+            None => return Ok(())
+        };
+        let encoders: &[Option<Encoder>] = net.encoders();
+        let (columns, width): (usize, usize) = match encoders.is_empty() {
+            true => (net.input_size(), net.input_size()),
+            false => (encoders.len(), encoders.iter().map(|encoder| encoder.as_ref().map_or(1, Encoder::width)).sum())
+        };
+        if width != net.input_size() {
+            return Err(DarjeelingError::InvalidMetadataRead(format!("The encoders make {} values, but the network has {} sensors", width, net.input_size())));
+        }
+        if let Some(scaler) = net.scaler() {
+            if scaler.features() != net.input_size() {
+                return Err(DarjeelingError::InvalidMetadataRead(format!("The scaler was fit on {} features, but the network has {} sensors", scaler.features(), net.input_size())));
+            }
+        }
+        if let Some(imputer) = &self.imputer {
+            if imputer.columns() != columns {
+                return Err(DarjeelingError::InvalidMetadataRead(format!("The imputer was fit on {} columns, but the network takes {}", imputer.columns(), columns)));
+            }
+        }
+        self.columns = columns;
+        Ok(())
+    }
+
+    /// The number of raw columns [`predict`](fn@Pipeline::predict) takes, 0 until the pipeline is fit
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Imputes, encodes and scales a raw row, giving exactly what the network sees for it
+    ///
+    /// ## Err
+    /// The same as [`predict`](fn@Pipeline::predict)
+    pub fn transform(&self, row: &[Option<Types>]) -> Result<Input, DarjeelingError> {
+        let net: &CatNetwork = match &self.network {
+            Some(net) => net,
+            None => return Err(DarjeelingError::InvalidInput("The pipeline has to be fit before it can predict".to_string()))
+        };
+        if row.len() != self.columns {
+            return Err(DarjeelingError::InvalidInput(format!("The row has {} columns, but the pipeline was fit on {}", row.len(), self.columns)));
+        }
+        let mut input: Input = net.encode_row(&impute(self.imputer.as_ref(), row)?, None)?;
+        if let Some(scaler) = net.scaler() {
+            scaler.transform(&mut input);
+        }
+        Ok(input)
+    }
+
+    /// Runs a raw row through every step of the pipeline
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The pipeline hasn't been fit, the row doesn't have as many columns as the pipeline was fit on, or a value is missing and can't be imputed
+    /// - The rest are the same as [`encode_row`](fn@crate::encoder::encode_row)
+    pub fn predict(&self, row: &[Option<Types>]) -> Result<Prediction, DarjeelingError> {
+        let net: &CatNetwork = match &self.network {
+            Some(net) => net,
+            None => return Err(DarjeelingError::InvalidInput("The pipeline has to be fit before it can predict".to_string()))
+        };
+        if row.len() != self.columns {
+            return Err(DarjeelingError::InvalidInput(format!("The row has {} columns, but the pipeline was fit on {}", row.len(), self.columns)));
+        }
+        // The network scales its inputs itself
+        let input: Input = net.encode_row(&impute(self.imputer.as_ref(), row)?, None)?;
+        Ok(net.predict(&input.inputs))
+    }
+
+    /// Reads a pipeline, wiring its imputer, encoders and scaler back up so it predicts from raw rows the way it did after fitting
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The JSON isn't a pipeline
+    /// - ### InvalidMetadataRead
+    /// The pipeline's imputer, encoders, scaler and network don't fit together
+    pub fn from_json_str(serialized: &str) -> Result<Pipeline, DarjeelingError> {
+        let mut pipeline: Pipeline = match serde_json::from_str(serialized) {
+            Ok(pipeline) => pipeline,
+            Err(error) => return Err(DarjeelingError::InvalidInput(format!("Not a valid pipeline: {}", error)))
+        };
+        pipeline.rewire()?;
+        Ok(pipeline)
+    }
+
+    /// ## Err
+    /// - ### UnknownError
+    /// The pipeline couldn't be serialized
+    pub fn to_json_string(&self) -> Result<String, DarjeelingError> {
+        match serde_json::to_string(self) {
+            Ok(serialized) => Ok(serialized),
+            Err(error) => Err(DarjeelingError::UnknownError(error.to_string()))
+        }
+    }
+
+    /// Saves the pipeline, with its network, as one JSON file
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The file couldn't be written
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write(&self, path: &str) -> Result<(), DarjeelingError> {
+        match fs::write(path, self.to_json_string()?) {
+            Ok(()) => Ok(()),
+            Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", path, error)))
+        }
+    }
+
+    /// ## Err
+    /// - ### ReadDataFailed
+    /// The file couldn't be read
+    /// - ### InvalidInput
+    /// The file isn't a pipeline
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read(path: &str) -> Result<Pipeline, DarjeelingError> {
+        match fs::read_to_string(path) {
+            Ok(serialized) => Pipeline::from_json_str(&serialized),
+            Err(error) => Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+        }
+    }
+}
+
+/// Fills in a row's missing values, or fails on the first one if there's no imputer
+fn impute(imputer: Option<&Imputer>, row: &[Option<Types>]) -> Result<Vec<Types>, DarjeelingError> {
+    match imputer {
+        Some(imputer) => imputer.impute(row),
+        None => row.iter().enumerate().map(|(column, value)| match value {
+            Some(value) => Ok(value.clone()),
+            None => Err(DarjeelingError::InvalidInput(format!("Column {} is missing a value, and the pipeline doesn't impute", column)))
+        }).collect()
+    }
+}
@@ -0,0 +1,81 @@
+use crate::history::TrainingHistory;
+
+const WIDTH: f32 = 800.0;
+const HEIGHT: f32 = 320.0;
+/// Room around each chart for its title and axis labels
+const MARGIN: f32 = 48.0;
+const TICKS: usize = 4;
+
+/// Draws the loss and accuracy of every epoch as two line charts side by side in an SVG,
+/// for seeing how training went without exporting the history to another tool
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{plot, history::{TrainingHistory, EpochRecord}};
+///
+/// let mut history = TrainingHistory::new();
+/// history.record(EpochRecord { epoch: 1, loss: 0.5, accuracy: 0.5, learning_rate: 0.1, grad_norm: 0.0, max_grad_norm: 0.0, layers: vec![], holdout_loss: None, holdout_accuracy: None, order: vec![], timings: vec![] });
+/// history.record(EpochRecord { epoch: 2, loss: 0.25, accuracy: 0.75, learning_rate: 0.1, grad_norm: 0.0, max_grad_norm: 0.0, layers: vec![], holdout_loss: None, holdout_accuracy: None, order: vec![], timings: vec![] });
+/// let svg: String = plot::history_svg(&history);
+/// assert!(svg.starts_with("<svg") && svg.contains("Loss") && svg.contains("Accuracy"));
+/// ```
+pub fn history_svg(history: &TrainingHistory) -> String {
+    let epochs: Vec<usize> = history.epochs().iter().map(|record| record.epoch).collect();
+    let losses: Vec<f32> = history.epochs().iter().map(|record| record.loss).collect();
+    let accuracies: Vec<f32> = history.epochs().iter().map(|record| record.accuracy).collect();
+    let largest_loss: f32 = losses.iter().copied().filter(|loss| loss.is_finite()).fold(0.0, f32::max);
+
+    let mut svg: String = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"11\">\n<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+        WIDTH, HEIGHT, WIDTH, HEIGHT
+    );
+    svg.push_str(&chart(0.0, "Loss", &epochs, &losses, if largest_loss > 0.0 { largest_loss } else { 1.0 }, "#d62728"));
+    svg.push_str(&chart(WIDTH / 2.0, "Accuracy", &epochs, &accuracies, 1.0, "#1f77b4"));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// One line chart filling half the width, from 0 up to the top value
+fn chart(left: f32, title: &str, epochs: &[usize], values: &[f32], top: f32, color: &str) -> String {
+    let (x0, x1) = (left + MARGIN, left + WIDTH / 2.0 - MARGIN / 2.0);
+    let (y0, y1) = (HEIGHT - MARGIN, MARGIN);
+    let first: usize = epochs.first().copied().unwrap_or(1);
+    let last: usize = epochs.last().copied().unwrap_or(1);
+    let x = |epoch: usize| if last > first { x0 + (epoch - first) as f32 / (last - first) as f32 * (x1 - x0) } else { (x0 + x1) / 2.0 };
+    let y = |value: f32| y0 - (value / top).clamp(0.0, 1.0) * (y0 - y1);
+
+    let mut chart: String = format!("<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" font-size=\"14\">{}</text>\n", (x0 + x1) / 2.0, y1 - 16.0, title);
+    for tick in 0..=TICKS {
+        let value: f32 = top * tick as f32 / TICKS as f32;
+        chart.push_str(&format!("<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#e0e0e0\"/>\n", x0, y(value), x1, y(value)));
+        chart.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>\n", x0 - 6.0, y(value) + 4.0, tick_label(value)));
+    }
+    chart.push_str(&format!("<polyline points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" fill=\"none\" stroke=\"black\"/>\n", x0, y1, x0, y0, x1, y0));
+    chart.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n", x0, y0 + 16.0, first));
+    chart.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n", x1, y0 + 16.0, last));
+    chart.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">Epoch</text>\n", (x0 + x1) / 2.0, y0 + 32.0));
+
+    let points: Vec<(f32, f32)> = epochs.iter().zip(values).filter(|(_, value)| value.is_finite()).map(|(epoch, value)| (x(*epoch), y(*value))).collect();
+    match points.as_slice() {
+        [] => {},
+        // A line needs two points, so a single epoch is a dot
+        [(x, y)] => chart.push_str(&format!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"/>\n", x, y, color)),
+        _ => {
+            let points: Vec<String> = points.iter().map(|(x, y)| format!("{:.1},{:.1}", x, y)).collect();
+            chart.push_str(&format!("<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n", points.join(" "), color));
+        }
+    }
+    chart
+}
+
+fn tick_label(value: f32) -> String {
+    if value == 0.0 {
+        "0".to_string()
+    } else if value >= 10.0 {
+        format!("{:.0}", value)
+    } else if value >= 0.1 {
+        format!("{:.2}", value)
+    } else {
+        format!("{:.1e}", value)
+    }
+}
@@ -0,0 +1,93 @@
+use half::f16;
+use serde::{Serialize, Deserialize};
+
+/// Trains with activations and error signals rounded to half precision, while the weights stay in f32.
+/// f16 can't hold error signals much smaller than 6e-8, so they're multiplied by the loss scale before they're rounded,
+/// and divided by it again before the weights are updated.
+///
+/// The loss scale is dynamic. When an error signal overflows f16, that input's update is skipped and the scale is halved.
+/// After `growth_interval` updates in a row without overflowing, the scale is doubled.
+///
+/// The nodes still store their values as f32, so this shows how a model trains at half precision,
+/// but doesn't cut memory on its own.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::precision::MixedPrecision;
+///
+/// let mut precision = MixedPrecision::new(1024.0);
+/// assert_eq!(MixedPrecision::reduce(0.1), 0.099975586);
+/// assert!(precision.reduce_grad(1e-6).is_some());
+/// assert!(precision.reduce_grad(1e6).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct MixedPrecision {
+    /// What the error signals are multiplied by before they're rounded to f16
+    pub loss_scale: f32,
+    /// How many updates in a row have to succeed before the loss scale doubles
+    pub growth_interval: usize,
+    /// Updates that succeeded since the loss scale last changed
+    good_steps: usize,
+    /// Updates that were skipped because an error signal overflowed
+    skipped_steps: usize
+}
+
+impl MixedPrecision {
+
+    /// Mixed precision with a starting loss scale, which doubles after 2000 updates in a row without overflowing
+    pub fn new(loss_scale: f32) -> MixedPrecision {
+        MixedPrecision { loss_scale, growth_interval: 2000, good_steps: 0, skipped_steps: 0 }
+    }
+
+    pub fn with_growth_interval(mut self, growth_interval: usize) -> MixedPrecision {
+        self.growth_interval = growth_interval;
+        self
+    }
+
+    /// Rounds a value to the nearest f16
+    pub fn reduce(value: f32) -> f32 {
+        f16::from_f32(value).to_f32()
+    }
+
+    /// Rounds an error signal to f16 at the current loss scale, then unscales it
+    ///
+    /// ## Returns
+    /// None if the scaled error signal overflows f16
+    pub fn reduce_grad(&self, err_sig: f32) -> Option<f32> {
+        let scaled: f16 = f16::from_f32(err_sig * self.loss_scale);
+        match scaled.is_finite() {
+            true => Some(scaled.to_f32() / self.loss_scale),
+            false => None
+        }
+    }
+
+    /// Updates the loss scale after an input trains
+    ///
+    /// ## Params
+    /// - Overflowed: Whether any error signal overflowed, so the update was skipped
+    pub(crate) fn update_scale(&mut self, overflowed: bool) {
+        if overflowed {
+            self.loss_scale = (self.loss_scale / 2.0).max(1.0);
+            self.good_steps = 0;
+            self.skipped_steps += 1;
+        } else {
+            self.good_steps += 1;
+            if self.good_steps >= self.growth_interval {
+                self.loss_scale *= 2.0;
+                self.good_steps = 0;
+            }
+        }
+    }
+
+    /// How many updates have been skipped because an error signal overflowed
+    pub fn skipped_steps(&self) -> usize {
+        self.skipped_steps
+    }
+}
+
+impl Default for MixedPrecision {
+    /// Starts the loss scale at 2^16
+    fn default() -> Self {
+        MixedPrecision::new(65536.0)
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+use serde::{Serialize, Deserialize};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use crate::{categorize::CatNetwork, error::DarjeelingError, types::Types};
+
+/// What a categorization model thinks about one input, from [`predict`](fn@crate::categorize::CatNetwork::predict)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Prediction {
+    /// The category of the brightest answer node
+    pub category: Types,
+    /// How sure the model is of the category, calibrated if the model has a calibrator
+    pub confidence: f32,
+    /// Every category with its answer node's raw output, in the order of the answer nodes
+    pub scores: Vec<(Types, f32)>,
+    /// Every category whose answer node tied for the largest output, including the chosen one. Empty if there was no tie.
+    #[serde(default)]
+    pub tied: Vec<Types>
+}
+
+impl Prediction {
+
+    /// The raw output of a category's answer node
+    pub fn score(&self, category: &Types) -> Option<f32> {
+        self.scores.iter().find(|(score_category, _)| score_category == category).map(|(_, score)| *score)
+    }
+
+    /// Whether more than one answer node had the largest output
+    pub fn is_tie(&self) -> bool {
+        !self.tied.is_empty()
+    }
+}
+
+/// Lets any iterator of inputs be scored lazily with [`predictions`](fn@PredictionsExt::predictions),
+/// so rows can be read, predicted and written one at a time without holding them all in memory
+pub trait PredictionsExt: Iterator + Sized {
+
+    /// Predicts every input as the iterator reaches it, the same as [`predict`](fn@CatNetwork::predict).
+    /// Items can be anything that lends out the input values, like [`Input`](crate::input::Input)s, `Vec<f32>`s or references to them.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{categorize::CatNetwork, activation::ActivationFunction, input::Input, prediction::PredictionsExt, types::Types};
+    ///
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    /// let inputs = vec![Input::new(vec![0.0, 1.0], None), Input::new(vec![1.0, 0.0], None)];
+    /// for (input, prediction) in inputs.iter().zip(inputs.iter().predictions(&net)) {
+    ///     assert_eq!(prediction, net.predict(&input.inputs));
+    /// }
+    /// ```
+    fn predictions(self, net: &CatNetwork) -> Predictions<'_, Self> {
+        Predictions { inputs: self, net }
+    }
+}
+
+impl<I: Iterator> PredictionsExt for I {}
+
+/// An iterator of the predictions for another iterator's inputs, from [`predictions`](fn@PredictionsExt::predictions)
+#[derive(Debug, Clone)]
+pub struct Predictions<'a, I> {
+    inputs: I,
+    net: &'a CatNetwork
+}
+
+impl<'a, I> Iterator for Predictions<'a, I>
+where
+    I: Iterator,
+    I::Item: AsRef<[f32]> {
+    type Item = Prediction;
+
+    fn next(&mut self) -> Option<Prediction> {
+        self.inputs.next().map(|input| self.net.predict(input.as_ref()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inputs.size_hint()
+    }
+}
+
+impl<'a, I> ExactSizeIterator for Predictions<'a, I>
+where
+    I: ExactSizeIterator,
+    I::Item: AsRef<[f32]> {}
+
+/// How to pick a category when more than one answer node has exactly the largest output
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// The tied node that comes first
+    #[default]
+    First,
+    /// One of the tied nodes at random. The same seed and outputs always pick the same node.
+    Random { seed: u64 },
+    /// Refuse to pick, with a [`TiedPrediction`](DarjeelingError::TiedPrediction) error
+    Error
+}
+
+impl TieBreak {
+
+    /// Picks the index of a largest value. NaNs are never picked unless every value is NaN.
+    ///
+    /// ## Returns
+    /// The picked index, and the indices of every value that tied for the largest, empty if there was no tie
+    ///
+    /// ## Err
+    /// - ### TiedPrediction
+    /// The policy is [`Error`](TieBreak::Error) and there was a tie
+    pub fn choose(&self, values: &[f32]) -> Result<(usize, Vec<usize>), DarjeelingError> {
+        let largest: f32 = values.iter().copied().filter(|value| !value.is_nan()).fold(f32::NEG_INFINITY, f32::max);
+        let tied: Vec<usize> = (0..values.len()).filter(|i| values[*i] == largest).collect();
+        if tied.is_empty() {
+            return Ok((0, vec![]));
+        }
+        if tied.len() == 1 {
+            return Ok((tied[0], vec![]));
+        }
+        let chosen: usize = match self {
+            TieBreak::First => tied[0],
+            TieBreak::Random { seed } => {
+                // Mixing the outputs into the seed keeps the pick the same for the same outputs
+                let outputs: u64 = values.iter().fold(0, |mixed: u64, value| mixed.rotate_left(7) ^ value.to_bits() as u64);
+                let mut rng = StdRng::seed_from_u64(seed ^ outputs);
+                tied[rng.gen_range(0..tied.len())]
+            },
+            TieBreak::Error => return Err(DarjeelingError::TiedPrediction(format!("Answer nodes {:?} tied with an output of {}", tied, largest)))
+        };
+        Ok((chosen, tied))
+    }
+}
+
+/// How unsure a network is of an input, for picking which inputs are most worth labeling next
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum Uncertainty {
+    /// The entropy of the answer probabilities, which counts doubt spread over every category
+    #[default]
+    Entropy,
+    /// How little the most likely category wins by over the second, which only counts the top two
+    Margin,
+    /// How far the most likely category is from certain
+    LeastConfident
+}
+
+impl Uncertainty {
+
+    /// How uncertain a set of answer probabilities is, higher being more uncertain.
+    /// The probabilities are [normalized](normalize) first.
+    pub fn score(&self, probabilities: &[f32]) -> f32 {
+        let mut probabilities: Vec<f32> = normalize(probabilities);
+        match self {
+            Uncertainty::Entropy => -probabilities.iter().filter(|p| **p > 0.0).map(|p| p * p.ln()).sum::<f32>(),
+            Uncertainty::Margin => {
+                probabilities.sort_by(|a, b| b.total_cmp(a));
+                1.0 - (probabilities.first().copied().unwrap_or(0.0) - probabilities.get(1).copied().unwrap_or(0.0))
+            },
+            Uncertainty::LeastConfident => 1.0 - probabilities.iter().copied().fold(0.0, f32::max)
+        }
+    }
+}
+
+/// Turns outputs into probabilities that add up to 1, treating negative and non-finite outputs as 0.
+/// If nothing is left, every answer is equally likely.
+pub(crate) fn normalize(outputs: &[f32]) -> Vec<f32> {
+    let clamped: Vec<f32> = outputs.iter().map(|output| if output.is_finite() { output.max(0.0) } else { 0.0 }).collect();
+    let total: f32 = clamped.iter().sum();
+    match total > 0.0 {
+        true => clamped.iter().map(|output| output / total).collect(),
+        false => vec![1.0 / clamped.len() as f32; clamped.len()]
+    }
+}
+
+impl fmt::Display for Prediction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({:.4})", self.category, self.confidence)
+    }
+}
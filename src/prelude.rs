@@ -0,0 +1,34 @@
+//! The types most programs need, in one import.
+//!
+//! Everything here keeps its name and path across minor versions, even when the modules it comes from are reorganized,
+//! so downstream crates can rely on `use darjeeling::prelude::*;` where a deeper path might move.
+//! Types are only added to the prelude, or removed from it in a major version.
+//!
+//! ## Examples
+//! ```
+//! use darjeeling::prelude::*;
+//!
+//! let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+//! net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+//! let input = Input::new(vec![0.0, 1.0], None);
+//! let prediction: Prediction = net.predict(&input.inputs);
+//! assert!(prediction.category == Types::Boolean(false) || prediction.category == Types::Boolean(true));
+//! ```
+
+pub use crate::{
+    activation::ActivationFunction,
+    categorize::CatNetwork,
+    config::{GanConfig, LearningRateSchedule, Regularization, TrainingConfig},
+    dataset::{DataSource, Dataset},
+    ensemble::Ensemble,
+    error::DarjeelingError,
+    generation::GenNetwork,
+    hierarchy::HierarchicalClassifier,
+    input::Input,
+    multiclass::OneVsRest,
+    multitask::MultiTaskNetwork,
+    ordinal::OrdinalNetwork,
+    prediction::{Prediction, PredictionsExt},
+    scaler::Scaler,
+    types::Types
+};
@@ -0,0 +1,92 @@
+use std::{fmt::Write, sync::atomic::{AtomicU32, AtomicU64, Ordering}, time::Duration};
+use crate::history::EpochRecord;
+
+/// The upper bounds of the latency histogram's buckets, in seconds
+pub const LATENCY_BUCKETS: [f64; 8] = [0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0];
+
+/// Counters for a long running training or inference service, rendered in the Prometheus text format.
+/// Everything is atomic, so one set of metrics can be shared between threads in an Arc.
+///
+/// ## Examples
+/// ```
+/// use std::{sync::Arc, time::Duration};
+/// use darjeeling::prometheus::Metrics;
+///
+/// let metrics = Arc::new(Metrics::new());
+/// metrics.record_predictions(2, Duration::from_millis(3));
+/// assert!(metrics.render().contains("darjeeling_predictions_total 2\n"));
+/// ```
+#[derive(Debug, Default)]
+pub struct Metrics {
+    epochs: AtomicU64,
+    /// The bits of the last epoch's loss, since there's no atomic float
+    loss: AtomicU32,
+    accuracy: AtomicU32,
+    predictions: AtomicU64,
+    requests: AtomicU64,
+    latency_micros: AtomicU64,
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS.len()]
+}
+
+impl Metrics {
+
+    pub fn new() -> Metrics {
+        Metrics::default()
+    }
+
+    /// Counts a finished epoch and keeps its loss and accuracy
+    pub fn record_epoch(&self, record: &EpochRecord) {
+        self.epochs.fetch_add(1, Ordering::Relaxed);
+        self.loss.store(record.loss.to_bits(), Ordering::Relaxed);
+        self.accuracy.store(record.accuracy.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Counts the predictions made by one request, and how long the request took
+    pub fn record_predictions(&self, predictions: u64, latency: Duration) {
+        self.predictions.fetch_add(predictions, Ordering::Relaxed);
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.latency_micros.fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+        let seconds: f64 = latency.as_secs_f64();
+        // Buckets are cumulative, so a request counts towards every bound it's under
+        LATENCY_BUCKETS.iter().zip(self.latency_buckets.iter()).filter(|(bound, _)| seconds <= **bound).for_each(|(_, bucket)| {
+            bucket.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
+    pub fn epochs(&self) -> u64 {
+        self.epochs.load(Ordering::Relaxed)
+    }
+
+    pub fn predictions(&self) -> u64 {
+        self.predictions.load(Ordering::Relaxed)
+    }
+
+    /// The metrics in the Prometheus text exposition format, ready to be scraped
+    pub fn render(&self) -> String {
+        let mut text: String = String::new();
+        let requests: u64 = self.requests.load(Ordering::Relaxed);
+
+        // Writing to a String can't fail
+        let _ = writeln!(text, "# HELP darjeeling_epochs_completed_total Training epochs completed");
+        let _ = writeln!(text, "# TYPE darjeeling_epochs_completed_total counter");
+        let _ = writeln!(text, "darjeeling_epochs_completed_total {}", self.epochs());
+        let _ = writeln!(text, "# HELP darjeeling_training_loss Loss of the last training epoch");
+        let _ = writeln!(text, "# TYPE darjeeling_training_loss gauge");
+        let _ = writeln!(text, "darjeeling_training_loss {}", f32::from_bits(self.loss.load(Ordering::Relaxed)));
+        let _ = writeln!(text, "# HELP darjeeling_training_accuracy Accuracy of the last training epoch");
+        let _ = writeln!(text, "# TYPE darjeeling_training_accuracy gauge");
+        let _ = writeln!(text, "darjeeling_training_accuracy {}", f32::from_bits(self.accuracy.load(Ordering::Relaxed)));
+        let _ = writeln!(text, "# HELP darjeeling_predictions_total Predictions served");
+        let _ = writeln!(text, "# TYPE darjeeling_predictions_total counter");
+        let _ = writeln!(text, "darjeeling_predictions_total {}", self.predictions());
+        let _ = writeln!(text, "# HELP darjeeling_prediction_latency_seconds Time taken to answer a prediction request");
+        let _ = writeln!(text, "# TYPE darjeeling_prediction_latency_seconds histogram");
+        LATENCY_BUCKETS.iter().zip(self.latency_buckets.iter()).for_each(|(bound, bucket)| {
+            let _ = writeln!(text, "darjeeling_prediction_latency_seconds_bucket{{le=\"{}\"}} {}", bound, bucket.load(Ordering::Relaxed));
+        });
+        let _ = writeln!(text, "darjeeling_prediction_latency_seconds_bucket{{le=\"+Inf\"}} {}", requests);
+        let _ = writeln!(text, "darjeeling_prediction_latency_seconds_sum {}", self.latency_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0);
+        let _ = writeln!(text, "darjeeling_prediction_latency_seconds_count {}", requests);
+        text
+    }
+}
@@ -0,0 +1,164 @@
+//! The pieces of a DQN, for using a categorization network to learn Q values:
+//! a replay buffer of past transitions, an epsilon-greedy action picker, and a step that fits the online network
+//! to targets from a second, slowly synced target network.
+//! The target network is a copy made with `clone()` and kept in sync with [`clone_weights_from`](fn@CatNetwork::clone_weights_from).
+//!
+//! The answer nodes are the Q values of each action, so the network needs outputs that can reach the rewards,
+//! like the [linear](crate::activation::ActivationFunction::Linear) activation function, or rewards scaled to between 0 and 1.
+//!
+//! ## Examples
+//! ```ignore
+//! let mut online = CatNetwork::new(4, 32, 2, 1, ActivationFunction::Linear);
+//! let mut target = online.clone();
+//! let mut buffer = ReplayBuffer::new(10_000);
+//! let mut policy = EpsilonGreedy::new(1.0, 0.05, 0.995);
+//! for step in 0..steps {
+//!     let action = policy.select(&online, &state, &mut rng);
+//!     let (next_state, reward, done) = env.step(action);
+//!     buffer.push(Transition { state, action, reward, next_state: next_state.clone(), done });
+//!     q_learning_step(&mut online, &target, &buffer.sample(32, &mut rng), 0.99, 0.001)?;
+//!     policy.decay();
+//!     if step % 500 == 0 {
+//!         target.clone_weights_from(&online)?;
+//!     }
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use crate::{categorize::CatNetwork, error::DarjeelingError};
+
+/// One step of an environment: what was seen, what was done, and what came of it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Transition {
+    pub state: Vec<f32>,
+    /// The index of the answer node that was acted on
+    pub action: usize,
+    pub reward: f32,
+    pub next_state: Vec<f32>,
+    /// Whether the episode ended, so nothing comes after the next state
+    pub done: bool
+}
+
+/// The most recent transitions, for training on random batches of them instead of on each one as it happens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayBuffer {
+    capacity: usize,
+    transitions: VecDeque<Transition>
+}
+
+impl ReplayBuffer {
+
+    /// ## Params
+    /// - Capacity: How many transitions are kept before the oldest are dropped. At least 1.
+    pub fn new(capacity: usize) -> ReplayBuffer {
+        ReplayBuffer { capacity: capacity.max(1), transitions: VecDeque::with_capacity(capacity.max(1)) }
+    }
+
+    /// Adds a transition, dropping the oldest one if the buffer is full
+    pub fn push(&mut self, transition: Transition) {
+        if self.transitions.len() == self.capacity {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(transition);
+    }
+
+    /// Picks transitions at random, with replacement. Empty if the buffer is.
+    pub fn sample<R: Rng>(&self, batch_size: usize, rng: &mut R) -> Vec<&Transition> {
+        if self.transitions.is_empty() {
+            return vec![];
+        }
+        (0..batch_size).map(|_| &self.transitions[rng.gen_range(0..self.transitions.len())]).collect()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+    }
+}
+
+/// Picks a random action some of the time, and the action with the largest Q value the rest,
+/// exploring less and less as epsilon decays
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct EpsilonGreedy {
+    /// The chance of a random action
+    pub epsilon: f32,
+    /// Epsilon never decays below this
+    pub min_epsilon: f32,
+    /// What epsilon is multiplied by on every decay
+    pub decay: f32
+}
+
+impl EpsilonGreedy {
+
+    pub fn new(epsilon: f32, min_epsilon: f32, decay: f32) -> EpsilonGreedy {
+        EpsilonGreedy { epsilon, min_epsilon, decay }
+    }
+
+    /// The index of the answer node to act on
+    pub fn select<R: Rng>(&self, net: &CatNetwork, state: &[f32], rng: &mut R) -> usize {
+        if rng.gen::<f32>() < self.epsilon {
+            return rng.gen_range(0..net.output_size());
+        }
+        greedy(&net.raw_outputs(state))
+    }
+
+    /// Multiplies epsilon by the decay, stopping at the minimum
+    pub fn decay(&mut self) {
+        self.epsilon = (self.epsilon * self.decay).max(self.min_epsilon);
+    }
+}
+
+/// Fits the online network's Q value of each transition's action to the reward plus the discounted best Q value
+/// the target network sees in the next state
+///
+/// ## Params
+/// - Gamma: How much future rewards count, from 0 to 1
+/// - Learning Rate: The modifier that is applied to link weights as they're adjusted
+///
+/// ## Returns
+/// The mean squared temporal difference error of the batch, before each update
+///
+/// ## Err
+/// - ### ArchitectureMismatch
+/// The networks have different numbers of inputs or answer nodes
+/// - ### InvalidInput
+/// A state isn't the network's input size, or an action isn't an answer node
+pub fn q_learning_step(online: &mut CatNetwork, target: &CatNetwork, batch: &[&Transition], gamma: f32, learning_rate: f32) -> Result<f32, DarjeelingError> {
+    if online.input_size() != target.input_size() || online.output_size() != target.output_size() {
+        return Err(DarjeelingError::ArchitectureMismatch(format!("The online network has {} inputs and {} actions, but the target network has {} and {}",
+            online.input_size(), online.output_size(), target.input_size(), target.output_size()
+        )));
+    }
+    if let Some(transition) = batch.iter().find(|transition| transition.state.len() != online.input_size() || transition.next_state.len() != online.input_size()) {
+        return Err(DarjeelingError::InvalidInput(format!("A transition has states of {} and {} values, but the network has {} inputs", transition.state.len(), transition.next_state.len(), online.input_size())));
+    }
+    if let Some(transition) = batch.iter().find(|transition| transition.action >= online.output_size()) {
+        return Err(DarjeelingError::InvalidInput(format!("Action {} was taken, but the network has {} actions", transition.action, online.output_size())));
+    }
+
+    let errors: f32 = batch.iter().map(|transition| {
+        let future: f32 = match transition.done {
+            true => 0.0,
+            false => target.raw_outputs(&transition.next_state).into_iter().fold(f32::MIN, f32::max)
+        };
+        online.train_output(&transition.state, transition.action, transition.reward + gamma * future, learning_rate)
+    }).sum();
+    Ok(errors / batch.len().max(1) as f32)
+}
+
+fn greedy(q_values: &[f32]) -> usize {
+    (0..q_values.len()).fold(0, |best, i| if q_values[i] > q_values[best] { i } else { best })
+}
@@ -0,0 +1,156 @@
+use std::sync::{Arc, RwLock, atomic::{AtomicU64, Ordering}};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs, path::{Path, PathBuf}, sync::Mutex, thread::{self, JoinHandle}, time::{Duration, SystemTime}};
+use crate::categorize::CatNetwork;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::error::DarjeelingError;
+
+/// Holds the network an inference service answers with, and swaps in a new one without stopping the service.
+///
+/// Each request takes the current network with [`get`](fn@ModelHandle::get) and keeps it until it's answered,
+/// so a request is answered by one model from start to finish, even if a new one is swapped in halfway through.
+/// New models are swapped in by calling [`swap`](fn@ModelHandle::swap), or by watching a .darj file and reloading it when it changes.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, reload::ModelHandle};
+///
+/// let handle = ModelHandle::new(CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid));
+/// let serving = handle.get();
+/// handle.swap(CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid));
+/// assert_eq!(serving.input_size(), 2);
+/// assert_eq!(handle.version(), 1);
+/// ```
+#[derive(Debug)]
+pub struct ModelHandle {
+    current: RwLock<Arc<CatNetwork>>,
+    version: AtomicU64,
+    #[cfg(not(target_arch = "wasm32"))]
+    watched: Option<PathBuf>,
+    /// When the watched file was last loaded, and how long it was
+    #[cfg(not(target_arch = "wasm32"))]
+    loaded_stamp: Mutex<Option<(SystemTime, u64)>>
+}
+
+impl ModelHandle {
+
+    pub fn new(net: CatNetwork) -> ModelHandle {
+        ModelHandle {
+            current: RwLock::new(Arc::new(net)),
+            version: AtomicU64::new(0),
+            #[cfg(not(target_arch = "wasm32"))]
+            watched: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            loaded_stamp: Mutex::new(None)
+        }
+    }
+
+    /// Loads a .darj model, and remembers its path so [`reload_if_changed`](fn@ModelHandle::reload_if_changed) can pick up new versions of it.
+    ///
+    /// ## Err
+    /// - ### ReadModelFailed
+    /// The model couldn't be read
+    /// - The rest are the same as [`read_model`](fn@CatNetwork::read_model)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn watch<P: AsRef<Path>>(path: P) -> Result<ModelHandle, DarjeelingError> {
+        let path: PathBuf = path.as_ref().to_path_buf();
+        let stamp: Option<(SystemTime, u64)> = ModelHandle::stamp(&path);
+        let net: CatNetwork = CatNetwork::read_model(path.to_string_lossy().to_string())?;
+        let mut handle = ModelHandle::new(net);
+        handle.watched = Some(path);
+        handle.loaded_stamp = Mutex::new(stamp);
+        Ok(handle)
+    }
+
+    /// The network to answer a request with. Swapping in a new network doesn't change networks that were already taken.
+    pub fn get(&self) -> Arc<CatNetwork> {
+        self.current.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    /// Serves the new network from the next [`get`](fn@ModelHandle::get) onwards
+    ///
+    /// ## Returns
+    /// The network that was being served
+    pub fn swap(&self, net: CatNetwork) -> Arc<CatNetwork> {
+        let mut current = self.current.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let previous: Arc<CatNetwork> = std::mem::replace(&mut *current, Arc::new(net));
+        self.version.fetch_add(1, Ordering::SeqCst);
+        previous
+    }
+
+    /// How many times a new network has been swapped in
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// The file being watched, if the handle was made by [`watch`](fn@ModelHandle::watch)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn watched(&self) -> Option<&Path> {
+        self.watched.as_deref()
+    }
+
+    /// Reads the watched file again if it was modified since it was last loaded.
+    /// A file that can't be read, like one that's only half written, leaves the current network in place and is tried again next time.
+    /// Writing the new model next to the old one and renaming it over the top avoids reading half written files at all.
+    /// A model that reads fine but can't answer the same requests, because it has no categories or takes a different number of inputs,
+    /// also leaves the current network in place, and isn't tried again until the file changes.
+    ///
+    /// ## Returns
+    /// Whether a new network was swapped in
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The new model's categories haven't been set
+    /// - ### ArchitectureMismatch
+    /// The new model takes a different number of inputs than the one being served
+    /// - The rest are the same as [`read_model`](fn@CatNetwork::read_model)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reload_if_changed(&self) -> Result<bool, DarjeelingError> {
+        let path: &Path = match &self.watched {
+            Some(path) => path,
+            None => return Ok(false)
+        };
+        let stamp: Option<(SystemTime, u64)> = ModelHandle::stamp(path);
+        let mut loaded_stamp = self.loaded_stamp.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if stamp.is_none() || stamp == *loaded_stamp {
+            return Ok(false);
+        }
+        let net: CatNetwork = CatNetwork::read_model(path.to_string_lossy().to_string())?;
+        let serving: usize = self.get().input_size();
+        if net.categories().is_none() {
+            *loaded_stamp = stamp;
+            return Err(DarjeelingError::InvalidInput(format!("{} doesn't have its categories", path.display())));
+        }
+        if net.input_size() != serving {
+            *loaded_stamp = stamp;
+            return Err(DarjeelingError::ArchitectureMismatch(format!("{} takes {} inputs, but the model being served takes {}", path.display(), net.input_size(), serving)));
+        }
+        self.swap(net);
+        *loaded_stamp = stamp;
+        Ok(true)
+    }
+
+    /// Checks the watched file for changes every interval on a background thread, until the handle is dropped everywhere else
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_watcher(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let handle = Arc::downgrade(self);
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            let handle: Arc<ModelHandle> = match handle.upgrade() {
+                Some(handle) => handle,
+                None => return
+            };
+            match handle.reload_if_changed() {
+                Ok(true) => println!("Reloaded {}", handle.watched().map(|path| path.display().to_string()).unwrap_or_default()),
+                Ok(false) => {},
+                Err(error) => println!("Failed to reload the model, still serving the previous one: {}", error)
+            }
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Serialize, Deserialize};
+use crate::{error::DarjeelingError, input::Input};
+
+/// Rescales every feature of an input so they all fall in a similar range.
+/// Fit a scaler on the training data, then either transform the data yourself,
+/// or give it to a network with `set_scaler` so it's applied during training and saved with the model.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Scaler {
+    /// Maps each feature from [min, max] to [0, 1]
+    MinMax { min: Vec<f32>, max: Vec<f32> },
+    /// Maps each feature to (value - mean) / standard deviation
+    ZScore { mean: Vec<f32>, std_dev: Vec<f32> },
+    /// Clips each feature to [lower, upper] before scaling it with another scaler, so outliers can't stretch the range the rest of the data is scaled by
+    Clipped { lower: Vec<f32>, upper: Vec<f32>, scaler: Box<Scaler> },
+}
+
+impl Scaler {
+
+    /// Fits a min-max scaler on the given data
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{input::Input, scaler::Scaler};
+    ///
+    /// let mut data = vec![Input::new(vec![0.0, 10.0], None), Input::new(vec![2.0, 20.0], None)];
+    /// let scaler = Scaler::fit_min_max(&data);
+    /// scaler.transform_all(&mut data);
+    /// assert_eq!(data[1].inputs, vec![1.0, 1.0]);
+    /// ```
+    pub fn fit_min_max(data: &[Input]) -> Scaler {
+        let features = feature_count(data);
+        let mut min: Vec<f32> = vec![f32::MAX; features];
+        let mut max: Vec<f32> = vec![f32::MIN; features];
+        data.iter().for_each(|input| {
+            input.inputs.iter().enumerate().for_each(|(i, value)| {
+                min[i] = min[i].min(*value);
+                max[i] = max[i].max(*value);
+            })
+        });
+
+        Scaler::MinMax { min, max }
+    }
+
+    /// Fits a z-score scaler on the given data
+    pub fn fit_z_score(data: &[Input]) -> Scaler {
+        let features = feature_count(data);
+        let count = data.len().max(1) as f32;
+        let mut mean: Vec<f32> = vec![0.0; features];
+        let mut std_dev: Vec<f32> = vec![0.0; features];
+        data.iter().for_each(|input| {
+            input.inputs.iter().enumerate().for_each(|(i, value)| mean[i] += value / count)
+        });
+        data.iter().for_each(|input| {
+            input.inputs.iter().enumerate().for_each(|(i, value)| std_dev[i] += (value - mean[i]).powi(2) / count)
+        });
+        std_dev.iter_mut().for_each(|variance| *variance = variance.sqrt());
+
+        Scaler::ZScore { mean, std_dev }
+    }
+
+    /// Fits a scaler that winsorizes each feature, clipping it to percentiles of the training data, before scaling it.
+    /// The other scaler is fit on the clipped data, so a few extreme values don't squash the rest into a sliver of the range.
+    ///
+    /// ## Params
+    /// - Lower: The percentile to clip small values to, as a fraction like 0.01
+    /// - Upper: The percentile to clip large values to, like 0.99
+    /// - Fit: Fits the scaler applied after clipping, like [`fit_z_score`](fn@Scaler::fit_z_score)
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The percentiles aren't between 0 and 1, or the lower one isn't below the upper one
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::{input::Input, scaler::Scaler};
+    ///
+    /// let mut data: Vec<Input> = (0..100).map(|i| Input::new(vec![i as f32], None)).collect();
+    /// // A faulty sensor reading
+    /// data.push(Input::new(vec![1e9], None));
+    /// let scaler = Scaler::fit_clipped(&data, 0.0, 0.99, Scaler::fit_min_max).unwrap();
+    /// assert_eq!(scaler.scale(0, 1e9), 1.0);
+    /// assert!((scaler.scale(0, 50.0) - 0.5).abs() < 0.01);
+    /// ```
+    pub fn fit_clipped(data: &[Input], lower: f32, upper: f32, fit: fn(&[Input]) -> Scaler) -> Result<Scaler, DarjeelingError> {
+        if !(0.0..=1.0).contains(&lower) || !(0.0..=1.0).contains(&upper) || lower >= upper {
+            return Err(DarjeelingError::InvalidInput(format!("Clipping needs percentiles between 0 and 1 with the lower one first, not {} and {}", lower, upper)));
+        }
+        let features = feature_count(data);
+        let mut bounds: (Vec<f32>, Vec<f32>) = (Vec::with_capacity(features), Vec::with_capacity(features));
+        for i in 0..features {
+            let mut values: Vec<f32> = data.iter().filter_map(|input| input.inputs.get(i).copied()).filter(|value| !value.is_nan()).collect();
+            values.sort_by(|a, b| a.total_cmp(b));
+            bounds.0.push(percentile(&values, lower));
+            bounds.1.push(percentile(&values, upper));
+        }
+
+        let (lower, upper) = bounds;
+        let clipped: Vec<Input> = data.iter().map(|input| {
+            let inputs: Vec<f32> = input.inputs.iter().enumerate().map(|(i, value)| value.max(lower[i]).min(upper[i])).collect();
+            Input::new(inputs, input.answer.clone())
+        }).collect();
+        Ok(Scaler::Clipped { scaler: Box::new(fit(&clipped)), lower, upper })
+    }
+
+    /// Scales a single value of the given feature.
+    /// Features the scaler wasn't fit on, or that never changed in the training data, are scaled to 0
+    pub fn scale(&self, feature: usize, value: f32) -> f32 {
+        match self {
+            Scaler::MinMax { min, max } => {
+                if feature >= min.len() || max[feature] - min[feature] == 0.0 { return 0.0; }
+                (value - min[feature]) / (max[feature] - min[feature])
+            },
+            Scaler::ZScore { mean, std_dev } => {
+                if feature >= mean.len() || std_dev[feature] == 0.0 { return 0.0; }
+                (value - mean[feature]) / std_dev[feature]
+            },
+            Scaler::Clipped { lower, upper, scaler } => match feature < lower.len() {
+                true => scaler.scale(feature, value.max(lower[feature]).min(upper[feature])),
+                false => scaler.scale(feature, value)
+            }
+        }
+    }
+
+    /// Scales every feature of an input in place
+    pub fn transform(&self, input: &mut Input) {
+        input.inputs.iter_mut().enumerate().for_each(|(i, value)| *value = self.scale(i, *value));
+    }
+
+    /// Scales every input in the data in place
+    pub fn transform_all(&self, data: &mut [Input]) {
+        data.iter_mut().for_each(|input| self.transform(input));
+    }
+
+    /// The number of features the scaler was fit on
+    pub fn features(&self) -> usize {
+        match self {
+            Scaler::MinMax { min, .. } => min.len(),
+            Scaler::ZScore { mean, .. } => mean.len(),
+            Scaler::Clipped { lower, .. } => lower.len()
+        }
+    }
+}
+
+/// The value the fraction of sorted values are below, interpolated between the two nearest. 0 if there are none.
+fn percentile(sorted: &[f32], fraction: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let position: f32 = fraction * (sorted.len() - 1) as f32;
+    let below: usize = position.floor() as usize;
+    let above: usize = (below + 1).min(sorted.len() - 1);
+    sorted[below] + (sorted[above] - sorted[below]) * (position - below as f32)
+}
+
+fn feature_count(data: &[Input]) -> usize {
+    data.iter().map(|input| input.inputs.len()).max().unwrap_or(0)
+}
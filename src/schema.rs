@@ -0,0 +1,89 @@
+use serde::{Serialize, Deserialize};
+use crate::error::DarjeelingError;
+
+/// The names of a network's inputs, saved with the model,
+/// so errors, drift reports and pictures of the network say "age" and "income" instead of feature 0 and feature 1
+///
+/// ## Examples
+/// ```
+/// use darjeeling::schema::Schema;
+///
+/// let schema = Schema::new(vec!["age".to_string(), "income".to_string()]).unwrap();
+/// assert_eq!(schema.index("income"), Some(1));
+/// assert_eq!(schema.name(0), "age");
+///
+/// let from_csv = Schema::from_csv_header("age,income,approved\n34,52000,yes\n", Some(2)).unwrap();
+/// assert_eq!(from_csv, schema);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Schema {
+    features: Vec<String>
+}
+
+impl Schema {
+
+    /// ## Params
+    /// - Features: The name of every input, in order
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// A name is empty or used twice
+    pub fn new(features: Vec<String>) -> Result<Schema, DarjeelingError> {
+        if let Some(i) = features.iter().position(|feature| feature.trim().is_empty()) {
+            return Err(DarjeelingError::InvalidInput(format!("Feature {} doesn't have a name", i)));
+        }
+        if let Some(i) = (1..features.len()).find(|i| features[..*i].contains(&features[*i])) {
+            return Err(DarjeelingError::InvalidInput(format!("{} is the name of more than one feature", features[i])));
+        }
+        Ok(Schema { features })
+    }
+
+    /// Names the features after the columns in the first line of a CSV file,
+    /// the same ones [`parse_csv`](fn@crate::csv::parse_csv) reads as inputs
+    ///
+    /// ## Params
+    /// - Text: The file's text, starting with its header
+    /// - Label Column: The column holding each row's answer, which isn't a feature
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There's no header, or a column name is empty or used twice
+    pub fn from_csv_header(text: &str, label_column: Option<usize>) -> Result<Schema, DarjeelingError> {
+        let header: &str = match text.lines().next() {
+            Some(header) => header,
+            None => return Err(DarjeelingError::InvalidInput("There's no header to name the features after".to_string()))
+        };
+        Schema::new(header.split(',').enumerate().filter(|(column, _)| Some(*column) != label_column).map(|(_, name)| name.trim().to_string()).collect())
+    }
+
+    /// Every feature's name, in order
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// The name of a feature, or "feature i" if there isn't one
+    pub fn name(&self, feature: usize) -> String {
+        feature_name(Some(self), feature)
+    }
+
+    /// Where a feature is in the inputs
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.features.iter().position(|feature| feature == name)
+    }
+}
+
+/// The feature's name if there's a schema naming it, or "feature i" if not
+pub(crate) fn feature_name(schema: Option<&Schema>, feature: usize) -> String {
+    match schema.and_then(|schema| schema.features.get(feature)) {
+        Some(name) => name.clone(),
+        None => format!("feature {}", feature)
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Serialize, Deserialize};
+
+/// Trains on unlabeled data too, by answering it with the network itself and keeping the answers it's confident about.
+/// After a few warm up epochs on the labeled data alone, the unlabeled data is pseudo-labeled again every few epochs,
+/// so the pseudo-labels improve as the network does.
+///
+/// ## Examples
+/// ```ignore
+/// // Pseudo-labels anything the network is 95% sure of, every 5 epochs after the first 10
+/// let pseudo_labeling = PseudoLabeling::new(0.95, 10, 5, 42);
+/// let report = net.learn_semi_supervised(&data, vec![], 0.5, 100, &pseudo_labeling).unwrap();
+/// println!("{} unlabeled inputs were used", report.pseudo_labeled.last().unwrap_or(&0));
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PseudoLabeling {
+    /// How [confident](fn@crate::categorize::CatNetwork::confidence) the network has to be in an answer, from 0 to 1, to train on it
+    pub threshold: f32,
+    /// How many epochs train on the labeled data alone before the first pseudo-labels
+    pub warmup_epochs: usize,
+    /// How many epochs go by between pseudo-labeling the unlabeled data again
+    pub relabel_every: usize,
+    /// Decides the order inputs are trained in
+    pub seed: u64
+}
+
+impl PseudoLabeling {
+
+    pub fn new(threshold: f32, warmup_epochs: usize, relabel_every: usize, seed: u64) -> PseudoLabeling {
+        PseudoLabeling { threshold, warmup_epochs, relabel_every, seed }
+    }
+}
+
+/// How training with [pseudo-labels](PseudoLabeling) went
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PseudoLabelingReport {
+    pub epochs: usize,
+    pub labeled_size: usize,
+    pub unlabeled_size: usize,
+    /// How many unlabeled inputs were confident enough to train on, every time they were pseudo-labeled
+    pub pseudo_labeled: Vec<usize>
+}
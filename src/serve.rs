@@ -0,0 +1,133 @@
+use std::{sync::Arc, time::{Duration, Instant}};
+use serde::{Serialize, Deserialize};
+use tiny_http::{Header, Method, Response, Server};
+use crate::{
+    categorize::CatNetwork,
+    error::DarjeelingError,
+    prediction::Prediction,
+    prometheus::Metrics,
+    reload::ModelHandle
+};
+
+/// The body of a `POST /predict` request, one list of values per input
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PredictRequest {
+    pub inputs: Vec<Vec<f32>>
+}
+
+/// The body of a `POST /predict` response, one prediction per input in the same order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PredictResponse {
+    pub predictions: Vec<ServedPrediction>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServedPrediction {
+    pub category: String,
+    pub confidence: f32
+}
+
+/// Loads a .darj model and answers `POST /predict` requests until the process is stopped.
+/// `GET /metrics` returns the predictions served and their latency in the Prometheus text format.
+/// The model file is checked for changes every second, and a new version is served from the next request on, without restarting.
+///
+/// ## Params
+/// - Model Name: The .darj file to serve, which has to have been saved with its categories
+/// - Address: Where to listen, like `0.0.0.0:8080`
+///
+/// ## Err
+/// - ### ReadModelFailed
+/// The model couldn't be read
+/// - ### InvalidMetadataRead
+/// The model was saved without its categories
+/// - ### UnknownError
+/// The server couldn't listen on the address
+///
+/// ## Examples
+/// ```ignore
+/// darjeeling::serve::serve("model_iris_123.darj", "0.0.0.0:8080").unwrap();
+/// // curl -X POST localhost:8080/predict -d '{"inputs": [[5.1, 3.5, 1.4, 0.2]]}'
+/// // {"predictions":[{"category":"setosa","confidence":0.93}]}
+/// ```
+pub fn serve(model_name: &str, address: &str) -> Result<(), DarjeelingError> {
+    let handle: Arc<ModelHandle> = Arc::new(ModelHandle::watch(model_name)?);
+    if handle.get().categories().is_none() {
+        return Err(DarjeelingError::InvalidMetadataRead(format!("{} was saved without its categories", model_name)));
+    }
+    let server: Server = match Server::http(address) {
+        Ok(server) => server,
+        Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+    };
+    println!("Serving {} on {}", model_name, address);
+    let metrics = Metrics::new();
+    handle.spawn_watcher(Duration::from_secs(1));
+
+    for mut request in server.incoming_requests() {
+        let start = Instant::now();
+        let (status, body, content_type) = match (request.method(), request.url()) {
+            (Method::Post, "/predict") => {
+                let mut body: String = String::new();
+                let net: Arc<CatNetwork> = handle.get();
+                match request.as_reader().read_to_string(&mut body) {
+                    Ok(_) => match predict(&net, &body).and_then(|response| Ok((response.predictions.len(), to_json(&response)?))) {
+                        Ok((predictions, json)) => {
+                            metrics.record_predictions(predictions as u64, start.elapsed());
+                            (200, json, "application/json")
+                        },
+                        Err(error) => (400, error_json(&error.to_string()), "application/json")
+                    },
+                    Err(error) => (400, error_json(&error.to_string()), "application/json")
+                }
+            },
+            (_, "/predict") => (405, error_json("Only POST is supported"), "application/json"),
+            (Method::Get, "/metrics") => (200, metrics.render(), "text/plain; version=0.0.4"),
+            _ => (404, error_json("Not found"), "application/json")
+        };
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(format!("Content-Type: {}", content_type).parse::<Header>().expect("The header is valid"));
+        if let Err(error) = request.respond(response) {
+            println!("Failed to respond: {}", error);
+        }
+    }
+    Ok(())
+}
+
+/// Answers the JSON body of a predict request, without any HTTP
+///
+/// ## Err
+/// - ### InvalidInput
+/// The body isn't a valid request, or an input has the wrong number of values
+pub fn predict_json(net: &CatNetwork, body: &str) -> Result<String, DarjeelingError> {
+    to_json(&predict(net, body)?)
+}
+
+fn predict(net: &CatNetwork, body: &str) -> Result<PredictResponse, DarjeelingError> {
+    let request: PredictRequest = match serde_json::from_str(body) {
+        Ok(request) => request,
+        Err(error) => return Err(DarjeelingError::InvalidInput(error.to_string()))
+    };
+    let mut predictions: Vec<ServedPrediction> = vec![];
+    for (i, inputs) in request.inputs.iter().enumerate() {
+        if inputs.len() != net.input_size() {
+            return Err(DarjeelingError::InvalidInput(format!("Input {} has {} values, but the model takes {}", i, inputs.len(), net.input_size())));
+        }
+        let prediction: Prediction = net.predict(inputs);
+        predictions.push(ServedPrediction {
+            category: prediction.category.to_string(),
+            confidence: prediction.confidence
+        });
+    }
+    Ok(PredictResponse { predictions })
+}
+
+fn to_json(response: &PredictResponse) -> Result<String, DarjeelingError> {
+    match serde_json::to_string(response) {
+        Ok(json) => Ok(json),
+        Err(error) => Err(DarjeelingError::UnknownError(error.to_string()))
+    }
+}
+
+fn error_json(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
@@ -0,0 +1,160 @@
+use std::sync::Mutex;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::{RngCore, rngs::OsRng};
+use crate::{error::DarjeelingError, hash};
+
+/// Starts the line a signature is appended to a .darj model on. Readers that don't check signatures skip it.
+pub const SIGNATURE_PREFIX: &str = "signature ";
+
+static VERIFIER: Mutex<Option<ModelVerifier>> = Mutex::new(None);
+
+/// Signs serialized models with an ed25519 secret key, so a service only runs models made by a trusted training pipeline.
+///
+/// Only the training pipeline needs the secret key. Services that load models check them with the [public key](fn@ModelSigner::public_key),
+/// which can't sign anything, so it's safe to ship with them.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::signing::{ModelSigner, ModelVerifier};
+///
+/// let signer = ModelSigner::new(&[7; 32]);
+/// let verifier = ModelVerifier::new(&signer.public_key()).unwrap();
+/// let signature: String = signer.sign("0.5;0.1\nlb\nsigmoid");
+/// assert!(verifier.verify("0.5;0.1\nlb\nsigmoid", &signature));
+/// assert!(!verifier.verify("0.6;0.1\nlb\nsigmoid", &signature));
+/// assert!(!ModelSigner::new(&[8; 32]).verifier().verify("0.5;0.1\nlb\nsigmoid", &signature));
+/// ```
+#[derive(Clone)]
+pub struct ModelSigner {
+    key: SigningKey
+}
+
+impl ModelSigner {
+
+    /// ## Params
+    /// - Secret Key: 32 secret bytes, best made by [`generate`](fn@ModelSigner::generate) and kept away from the services that load models
+    pub fn new(secret_key: &[u8; 32]) -> ModelSigner {
+        ModelSigner { key: SigningKey::from_bytes(secret_key) }
+    }
+
+    /// A signer with a new secret key from the operating system's random number generator
+    pub fn generate() -> ModelSigner {
+        let mut secret_key: [u8; 32] = [0; 32];
+        OsRng.fill_bytes(&mut secret_key);
+        ModelSigner::new(&secret_key)
+    }
+
+    /// The secret key, to store somewhere only the training pipeline can read it
+    pub fn secret_key(&self) -> [u8; 32] {
+        self.key.to_bytes()
+    }
+
+    /// The public key that [`ModelVerifier`](struct@ModelVerifier)s check this signer's models with
+    pub fn public_key(&self) -> [u8; 32] {
+        self.key.verifying_key().to_bytes()
+    }
+
+    /// A verifier for this signer's models
+    pub fn verifier(&self) -> ModelVerifier {
+        ModelVerifier { key: self.key.verifying_key() }
+    }
+
+    /// The hexadecimal signature of a serialized model
+    pub fn sign(&self, serialized: &str) -> String {
+        hash::to_hex(&self.key.sign(serialized.as_bytes()).to_bytes())
+    }
+
+    /// The serialized model with its signature on the last line
+    pub fn append_signature(&self, serialized: &str) -> String {
+        format!("{}\n{}{}", serialized, SIGNATURE_PREFIX, self.sign(serialized))
+    }
+}
+
+impl std::fmt::Debug for ModelSigner {
+    /// Leaves the secret key out, so it doesn't end up in logs
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ModelSigner").finish_non_exhaustive()
+    }
+}
+
+/// Checks the signatures of models before they're loaded, with the public key of the [`ModelSigner`](struct@ModelSigner) that signed them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelVerifier {
+    key: VerifyingKey
+}
+
+impl ModelVerifier {
+
+    /// ## Params
+    /// - Public Key: The signer's [public key](fn@ModelSigner::public_key)
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The bytes aren't an ed25519 public key
+    pub fn new(public_key: &[u8; 32]) -> Result<ModelVerifier, DarjeelingError> {
+        match VerifyingKey::from_bytes(public_key) {
+            Ok(key) => Ok(ModelVerifier { key }),
+            Err(error) => Err(DarjeelingError::InvalidInput(format!("Not an ed25519 public key: {}", error)))
+        }
+    }
+
+    /// Whether the signature was made with the signer's secret key, for exactly this serialized model
+    pub fn verify(&self, serialized: &str, signature: &str) -> bool {
+        let signature: Signature = match hash::from_hex(signature.trim()).and_then(|bytes| <[u8; 64]>::try_from(bytes).ok()) {
+            Some(bytes) => Signature::from_bytes(&bytes),
+            None => return false
+        };
+        self.key.verify(serialized.as_bytes(), &signature).is_ok()
+    }
+
+    /// Checks the signature on the last line of a signed model
+    ///
+    /// ## Returns
+    /// The model without its signature
+    ///
+    /// ## Err
+    /// - ### InvalidSignature
+    /// The model isn't signed, or wasn't signed by this verifier's signer, or was changed after it was signed
+    pub fn check<'a>(&self, signed: &'a str) -> Result<&'a str, DarjeelingError> {
+        let (serialized, signature) = match signed.trim_end().rsplit_once('\n') {
+            Some((serialized, last)) if last.starts_with(SIGNATURE_PREFIX) => (serialized, &last[SIGNATURE_PREFIX.len()..]),
+            _ => return Err(DarjeelingError::InvalidSignature("The model isn't signed".to_string()))
+        };
+        match self.verify(serialized, signature) {
+            true => Ok(serialized),
+            false => Err(DarjeelingError::InvalidSignature("The signature doesn't match the model".to_string()))
+        }
+    }
+}
+
+/// Makes every [`read_model`](fn@crate::categorize::CatNetwork::read_model) check signatures with the verifier,
+/// refusing models that aren't signed by its signer. Models already loaded aren't checked again.
+///
+/// ## Params
+/// - Verifier: None goes back to loading models without checking their signatures
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{activation::ActivationFunction, categorize::CatNetwork, error::DarjeelingError, signing::{self, ModelSigner}, types::Types};
+///
+/// let signer = ModelSigner::new(&[7; 32]);
+/// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+/// net.set_categories(vec![Types::Boolean(true), Types::Boolean(false)]);
+/// net.set_output_dir(Some(std::env::temp_dir().join("darjeeling_verifier_example")));
+/// let signed: String = net.write_signed_model("signed", &signer).unwrap();
+/// let unsigned: String = net.write_model("unsigned").unwrap();
+///
+/// signing::set_verifier(Some(signer.verifier()));
+/// assert!(CatNetwork::read_model(signed).is_ok());
+/// assert!(matches!(CatNetwork::read_model(unsigned), Err(DarjeelingError::InvalidSignature(_))));
+/// # signing::set_verifier(None);
+/// # std::fs::remove_dir_all(std::env::temp_dir().join("darjeeling_verifier_example")).unwrap();
+/// ```
+pub fn set_verifier(verifier: Option<ModelVerifier>) {
+    *VERIFIER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = verifier;
+}
+
+/// The verifier set with [`set_verifier`], None if models are loaded without checking their signatures
+pub fn verifier() -> Option<ModelVerifier> {
+    VERIFIER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
@@ -0,0 +1,213 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    activation::ActivationFunction,
+    config::GanConfig,
+    dataset::Dataset,
+    error::DarjeelingError,
+    generation::GenNetwork,
+    input::Input,
+    types::Types
+};
+
+/// One column of a table of synthetic data
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Column {
+    /// A number between min and max, inclusive. Integer columns are generated as Types::Integer, the rest as Types::Float.
+    Numeric { name: String, min: f32, max: f32, integer: bool },
+    /// One of a list of levels. In a Dataset, its value is the index of the level.
+    Categorical { name: String, levels: Vec<Types> }
+}
+
+impl Column {
+
+    pub fn name(&self) -> &str {
+        match self {
+            Column::Numeric { name, .. } => name,
+            Column::Categorical { name, .. } => name
+        }
+    }
+
+    /// The number of network values the column takes up
+    pub fn width(&self) -> usize {
+        match self {
+            Column::Numeric { .. } => 1,
+            Column::Categorical { levels, .. } => levels.len()
+        }
+    }
+}
+
+/// The columns of a table, and how their values are turned into network values and back.
+/// Numeric columns are scaled to between 0 and 1, categorical columns are one hot encoded.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{tabular::TabularSchema, types::Types};
+///
+/// let schema = TabularSchema::new()
+///     .integer("age", 0.0, 100.0)
+///     .categorical("plan", vec![Types::String("free".to_string()), Types::String("paid".to_string())]);
+/// assert_eq!(schema.encode(&[50.0, 1.0]).unwrap(), vec![0.5, 0.0, 1.0]);
+/// assert_eq!(schema.decode(&[0.254, 0.9, 0.2]).unwrap(), vec![Types::Integer(25), Types::String("free".to_string())]);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TabularSchema {
+    columns: Vec<Column>
+}
+
+impl TabularSchema {
+
+    pub fn new() -> TabularSchema {
+        TabularSchema { columns: vec![] }
+    }
+
+    /// Adds a column of decimal numbers
+    pub fn numeric(mut self, name: &str, min: f32, max: f32) -> TabularSchema {
+        self.columns.push(Column::Numeric { name: name.to_string(), min, max, integer: false });
+        self
+    }
+
+    /// Adds a column of whole numbers
+    pub fn integer(mut self, name: &str, min: f32, max: f32) -> TabularSchema {
+        self.columns.push(Column::Numeric { name: name.to_string(), min, max, integer: true });
+        self
+    }
+
+    /// Adds a column that's one of the levels
+    pub fn categorical(mut self, name: &str, levels: Vec<Types>) -> TabularSchema {
+        self.columns.push(Column::Categorical { name: name.to_string(), levels });
+        self
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// The number of network values a row takes up
+    pub fn width(&self) -> usize {
+        self.columns.iter().map(|column| column.width()).sum()
+    }
+
+    /// Turns a row, with one value per column, into network values
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The row doesn't have a value for every column, or a categorical value isn't the index of a level
+    pub fn encode(&self, row: &[f32]) -> Result<Vec<f32>, DarjeelingError> {
+        if row.len() != self.columns.len() {
+            return Err(DarjeelingError::InvalidInput(format!("The row has {} values, but the schema has {} columns", row.len(), self.columns.len())));
+        }
+        let mut encoded: Vec<f32> = Vec::with_capacity(self.width());
+        for (column, value) in self.columns.iter().zip(row) {
+            match column {
+                Column::Numeric { min, max, .. } => encoded.push(if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 }),
+                Column::Categorical { name, levels } => {
+                    let level: usize = *value as usize;
+                    if *value < 0.0 || value.fract() != 0.0 || level >= levels.len() {
+                        return Err(DarjeelingError::InvalidInput(format!("{} isn't a level of {}", value, name)));
+                    }
+                    encoded.extend((0..levels.len()).map(|i| if i == level { 1.0 } else { 0.0 }));
+                }
+            }
+        }
+        Ok(encoded)
+    }
+
+    /// Turns network values back into a row of the declared types.
+    /// Numbers are clamped into their range, and categorical columns take the level with the largest value.
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// There are fewer values than the schema's [width](fn@TabularSchema::width)
+    pub fn decode(&self, values: &[f32]) -> Result<Vec<Types>, DarjeelingError> {
+        if values.len() < self.width() {
+            return Err(DarjeelingError::InvalidInput(format!("There are {} values, but the schema takes up {}", values.len(), self.width())));
+        }
+        let mut offset: usize = 0;
+        Ok(self.columns.iter().map(|column| {
+            let column_values: &[f32] = &values[offset..offset + column.width()];
+            offset += column.width();
+            match column {
+                Column::Numeric { min, max, integer, .. } => {
+                    let value: f32 = min + column_values[0].clamp(0.0, 1.0) * (max - min);
+                    if *integer { Types::Integer(value.round() as i32) } else { Types::Float(value) }
+                },
+                Column::Categorical { levels, .. } => {
+                    let mut largest: usize = 0;
+                    column_values.iter().enumerate().for_each(|(i, value)| if *value > column_values[largest] { largest = i });
+                    levels[largest].clone()
+                }
+            }
+        }).collect())
+    }
+}
+
+/// Learns to make synthetic rows that look like a table of real ones
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::{tabular::{TabularSchema, TabularSynthesizer}, config::{GanConfig, TrainingConfig}, activation::ActivationFunction};
+///
+/// let schema = TabularSchema::new().integer("age", 18.0, 90.0).categorical("plan", plans);
+/// let mut synthesizer = TabularSynthesizer::new(schema, 8, 1, ActivationFunction::Sigmoid);
+/// let config = GanConfig::default().with_cycles(50).with_discriminator(TrainingConfig::default().with_target_err_percent(60.0));
+/// let model_name = synthesizer.train(&customers, &config, "customers").unwrap();
+/// let rows: Vec<Vec<Types>> = synthesizer.generate(42, 100).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct TabularSynthesizer {
+    schema: TabularSchema,
+    net: GenNetwork
+}
+
+impl TabularSynthesizer {
+
+    /// Makes an untrained generation model as wide as the schema
+    pub fn new(schema: TabularSchema, hidden_num: i32, hidden_layers: i32, activation_function: ActivationFunction) -> TabularSynthesizer {
+        let width: i32 = schema.width() as i32;
+        TabularSynthesizer { net: GenNetwork::new(width, hidden_num, width, hidden_layers, activation_function), schema }
+    }
+
+    pub fn schema(&self) -> &TabularSchema {
+        &self.schema
+    }
+
+    /// The generation model underneath, which takes and makes encoded rows
+    pub fn network(&self) -> &GenNetwork {
+        &self.net
+    }
+
+    pub fn network_mut(&mut self) -> &mut GenNetwork {
+        &mut self.net
+    }
+
+    /// Trains the generation model on the rows of a dataset, with one value per column.
+    /// Their answers are ignored.
+    ///
+    /// ## Params
+    /// - Dataset: The real rows
+    /// - Config: How the generation and distinguishing models are trained, and where they're saved
+    /// - Name: The model name
+    ///
+    /// ## Returns
+    /// The name of the file the generation model was saved to
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// A row couldn't be [encoded](fn@TabularSchema::encode)
+    /// - The rest are the same as [`GenNetwork::learn`](fn@GenNetwork::learn)
+    pub fn train(&mut self, dataset: &Dataset, config: &GanConfig, name: &str) -> Result<String, DarjeelingError> {
+        let mut data: Vec<Input> = dataset.inputs().iter()
+            .map(|input| Ok(Input::new(self.schema.encode(&input.inputs)?, None)))
+            .collect::<Result<Vec<Input>, DarjeelingError>>()?;
+        self.net.learn_with_config(&mut data, config, name)
+    }
+
+    /// Generates rows of the schema's types, the same rows every time for the same seed
+    ///
+    /// ## Err
+    /// - ### InvalidInput
+    /// The generation model has fewer outputs than the schema's [width](fn@TabularSchema::width)
+    pub fn generate(&self, seed: u64, count: usize) -> Result<Vec<Vec<Types>>, DarjeelingError> {
+        self.net.generate_with_seed(seed, count).iter().map(|sample| self.schema.decode(&sample.output)).collect()
+    }
+}
@@ -0,0 +1,154 @@
+use std::{fs::{self, OpenOptions}, io::Write, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+use crate::{
+    error::DarjeelingError,
+    history::EpochRecord
+};
+
+/// Writes scalar summaries to a TensorBoard event file,
+/// so training can be watched with `tensorboard --logdir <dir>` next to other experiments.
+///
+/// The file is reopened for every write, so a writer can be cloned and nothing is lost if training stops early.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventWriter {
+    path: PathBuf
+}
+
+impl EventWriter {
+
+    /// Creates a new event file in a log directory, making the directory if it doesn't exist
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The directory or file couldn't be written
+    ///
+    /// ## Examples
+    /// ```ignore
+    /// use darjeeling::{tensorboard::EventWriter, categorize::CatNetwork, activation::ActivationFunction};
+    ///
+    /// let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    /// net.set_event_writer(Some(EventWriter::create("runs/xor").unwrap()));
+    /// // Loss, accuracy and learning rate show up in TensorBoard as each epoch finishes
+    /// ```
+    pub fn create(log_dir: &str) -> Result<EventWriter, DarjeelingError> {
+        if let Err(error) = fs::create_dir_all(log_dir) {
+            return Err(DarjeelingError::WriteDataFailed(format!("{}: {}", log_dir, error)));
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let path: PathBuf = Path::new(log_dir).join(format!("events.out.tfevents.{}.darjeeling", now.as_secs()));
+
+        let writer = EventWriter { path };
+        let mut event: Vec<u8> = vec![];
+        event_header(&mut event, 0);
+        // file_version
+        field_bytes(&mut event, 3, b"brain.Event:2");
+        writer.write_event(&event)?;
+        Ok(writer)
+    }
+
+    /// The event file being written to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes one named value at a step, like the loss at an epoch
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The file couldn't be written
+    pub fn add_scalar(&self, tag: &str, value: f32, step: i64) -> Result<(), DarjeelingError> {
+        let mut summary_value: Vec<u8> = vec![];
+        field_bytes(&mut summary_value, 1, tag.as_bytes());
+        // simple_value, a 32 bit float
+        summary_value.push(2 << 3 | 5);
+        summary_value.extend_from_slice(&value.to_le_bytes());
+
+        let mut summary: Vec<u8> = vec![];
+        field_bytes(&mut summary, 1, &summary_value);
+
+        let mut event: Vec<u8> = vec![];
+        event_header(&mut event, step);
+        field_bytes(&mut event, 5, &summary);
+        self.write_event(&event)
+    }
+
+    /// Writes the loss, accuracy and learning rate of an epoch, and its layer statistics if there are any
+    pub fn log_epoch(&self, record: &EpochRecord) -> Result<(), DarjeelingError> {
+        let step: i64 = record.epoch as i64;
+        self.add_scalar("loss", record.loss, step)?;
+        self.add_scalar("accuracy", record.accuracy, step)?;
+        self.add_scalar("learning_rate", record.learning_rate, step)?;
+        if let (Some(loss), Some(accuracy)) = (record.holdout_loss, record.holdout_accuracy) {
+            self.add_scalar("holdout/loss", loss, step)?;
+            self.add_scalar("holdout/accuracy", accuracy, step)?;
+        }
+        for stats in &record.layers {
+            self.add_scalar(&format!("layer_{}/weight_std", stats.layer), stats.weight_std, step)?;
+            self.add_scalar(&format!("layer_{}/weight_max", stats.layer), stats.weight_max, step)?;
+            self.add_scalar(&format!("layer_{}/err_sig_std", stats.layer), stats.err_sig_std, step)?;
+            self.add_scalar(&format!("layer_{}/err_sig_max", stats.layer), stats.err_sig_max, step)?;
+        }
+        Ok(())
+    }
+
+    /// Frames an event the way TensorFlow's record reader expects:
+    /// the length, its checksum, the event, and the event's checksum
+    fn write_event(&self, event: &[u8]) -> Result<(), DarjeelingError> {
+        let length: [u8; 8] = (event.len() as u64).to_le_bytes();
+        let mut record: Vec<u8> = vec![];
+        record.extend_from_slice(&length);
+        record.extend_from_slice(&masked_crc32c(&length).to_le_bytes());
+        record.extend_from_slice(event);
+        record.extend_from_slice(&masked_crc32c(event).to_le_bytes());
+
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(&record));
+        match written {
+            Ok(()) => Ok(()),
+            Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", self.path.display(), error)))
+        }
+    }
+}
+
+/// The wall time and step every event starts with
+fn event_header(event: &mut Vec<u8>, step: i64) {
+    let wall_time: f64 = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64();
+    event.push(1 << 3 | 1);
+    event.extend_from_slice(&wall_time.to_le_bytes());
+    event.push(2 << 3);
+    varint(event, step as u64);
+}
+
+/// A length delimited protobuf field
+fn field_bytes(buffer: &mut Vec<u8>, field: u8, bytes: &[u8]) {
+    buffer.push(field << 3 | 2);
+    varint(buffer, bytes.len() as u64);
+    buffer.extend_from_slice(bytes);
+}
+
+fn varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// The Castagnoli CRC used by TensorFlow records
+pub(crate) fn crc32c(bytes: &[u8]) -> u32 {
+    let mut crc: u32 = !0;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0x82f6_3b78 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn masked_crc32c(bytes: &[u8]) -> u32 {
+    let crc: u32 = crc32c(bytes);
+    crc.rotate_right(15).wrapping_add(0xa282_ead8)
+}
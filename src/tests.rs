@@ -9,9 +9,49 @@ use crate::{
     // series::Series, 
     types::{Types, self},
     activation::ActivationFunction, 
-    generation::GenNetwork, bench
+    generation::{GenNetwork, GeneratedSample, OutputConstraint}, bench,
+    idx,
+    scaler::Scaler,
+    encoder::{Encoder, Encoding},
+    dataset::Dataset,
+    text::{self, Vectorizer, Vocabulary},
+    metrics::{ClassificationMetrics, RegressionMetrics},
+    config::{TrainingConfig, GanConfig},
+    validation,
+    tuning::{self, SearchSpace},
+    tensorboard::{self, EventWriter},
+    calibration::{Calibrator, CalibrationMethod, CalibrationReport},
+    error::DarjeelingError,
+    csv
 };
 
+/// Rows that alternate between two categories a network can tell apart quickly,
+/// "low" with the first value near 0 and "high" with it near 1
+fn two_classes(rows: usize) -> Vec<Input> {
+    (0..rows).map(|i| {
+        let (x, category) = if i % 2 == 0 { (0.01 * i as f32, "low") } else { (1.0 - 0.01 * i as f32, "high") };
+        Input::new(vec![x, 1.0 - x], Some(Types::String(category.to_string())))
+    }).collect()
+}
+
+fn two_class_categories() -> Vec<Types> {
+    categories_str_format(vec!["low", "high"])
+}
+
+/// Four unlabelled rows for the generation tests to learn from
+fn gan_data() -> Vec<Input> {
+    (0..4).map(|i| Input::new(vec![i as f32 / 4.0, 0.5], None)).collect()
+}
+
+/// A small generation model set up by `setup`, then trained on [`gan_data`] for some cycles, with the model it saves deleted
+fn trained_gan(name: &str, cycles: i32, setup: impl FnOnce(&mut GenNetwork)) -> GenNetwork {
+    let mut net = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    setup(&mut net);
+    let model_name: String = net.learn(&mut gan_data(), 0.5, name, cycles, 0.5, 2, 1, ActivationFunction::Sigmoid, 0.1).unwrap();
+    fs::remove_file(model_name).unwrap();
+    net
+}
+
 // #[test]
 // pub fn bench() {
 //     bench!(CatNetwork::new(4, 4, 4, 1, ActivationFunction::Sigmoid));
@@ -143,9 +183,9 @@ fn train_test_gen() {
     let data = gen_data_file();
     let data1 = data[data.len() - 1].clone();
     let mut model: GenNetwork = GenNetwork::read_model(model_name).unwrap();
-    let output: Vec<Input> = model.test(&mut vec![data1]).unwrap();
+    let output: Vec<GeneratedSample> = model.test(&[data1]).unwrap();
     for i in 0..output.len() {
-        println!("{}", output[i]);
+        println!("{:?}", output[i].output);
     }
 }
 
@@ -194,6 +234,3582 @@ pub fn test_add_hidden_layers() {
     // let _ = net.learn(&mut xor_file(), vec![types::Types::Integer(1), types::Types::Integer(0)], 0.5, "test", 99.0, true);
 }
 
+#[test]
+fn read_idx_sample() {
+    let data: Vec<Input> = idx::load_idx("training_data/sample-images-idx3-ubyte", "training_data/sample-labels-idx1-ubyte").unwrap();
+    assert_eq!(data.len(), 3);
+    assert_eq!(data[0].inputs, vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(data[1].answer, Some(Types::Integer(7)));
+    assert!(idx::load_idx("training_data/sample-images-idx3-ubyte", "training_data/sample-images-idx3-ubyte").is_err());
+
+    // Headers whose sizes overflow, or hold no pixels, are refused before anything is allocated
+    let path = std::env::temp_dir().join("darjeeling_idx_test");
+    let header = |dims: &[u32]| -> Vec<u8> {
+        [0, 0, 8, dims.len() as u8].into_iter().chain(dims.iter().flat_map(|dim| dim.to_be_bytes())).collect()
+    };
+    for dims in [vec![u32::MAX; 4], vec![0, u32::MAX, u32::MAX, u32::MAX], vec![u32::MAX, 0, 0]] {
+        fs::write(&path, header(&dims)).unwrap();
+        let read = idx::read_idx_images(path.to_str().unwrap());
+        assert!(matches!(read, Err(DarjeelingError::InvalidIdxFormat(_))), "{:?}", dims);
+    }
+    let _ = fs::remove_file(&path);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image_file_to_input() {
+    use crate::images::{self, ColorMode};
+    let path = std::env::temp_dir().join("darjeeling_image_test.png");
+    let picture = image::RgbImage::from_fn(4, 4, |x, _y| if x < 2 { image::Rgb([255, 0, 0]) } else { image::Rgb([0, 0, 255]) });
+    picture.save(&path).unwrap();
+
+    let gray: Input = images::image_to_input(path.to_str().unwrap(), 2, 2, ColorMode::Grayscale, None).unwrap();
+    assert_eq!(gray.inputs.len(), images::sensor_count(2, 2, ColorMode::Grayscale));
+    let rgb: Input = images::image_to_input(path.to_str().unwrap(), 2, 2, ColorMode::Rgb, Some(Types::String("red".to_string()))).unwrap();
+    assert_eq!(rgb.inputs.len(), 12);
+    assert!(rgb.inputs[0] > 0.5 && rgb.inputs[2] < 0.5);
+    assert!(rgb.inputs[5] > 0.5 && rgb.inputs[3] < 0.5);
+    assert!(images::image_to_input("does_not_exist.png", 2, 2, ColorMode::Rgb, None).is_err());
+}
+
+#[test]
+fn scaler_saved_with_model() {
+    let data: Vec<Input> = xor_file();
+    let scaler: Scaler = Scaler::fit_z_score(&data);
+    assert_eq!(scaler.scale(0, 0.5), 0.0);
+
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_scaler(Some(scaler.clone()));
+    let model_name: String = net.write_model("scaler_test").unwrap();
+    let read: CatNetwork = CatNetwork::read_model(model_name.clone()).unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(read.scaler(), Some(&scaler));
+}
+
+#[test]
+fn clipped_scaler_saved_with_model() {
+    assert!(matches!(Scaler::fit_clipped(&[], 0.9, 0.1, Scaler::fit_z_score), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(Scaler::fit_clipped(&[], -0.1, 0.9, Scaler::fit_z_score), Err(DarjeelingError::InvalidInput(_))));
+
+    let mut data: Vec<Input> = (0..=10).map(|i| Input::new(vec![i as f32, 1.0], None)).collect();
+    data.push(Input::new(vec![-1e6, 1.0], None));
+    data.push(Input::new(vec![1e6, 1.0], None));
+    let scaler: Scaler = Scaler::fit_clipped(&data, 0.1, 0.9, Scaler::fit_min_max).unwrap();
+    let Scaler::Clipped { lower, upper, .. } = &scaler else { panic!("Expected a clipped scaler") };
+    assert!((lower[0] - 0.2).abs() < 1e-4 && lower[1] == 1.0);
+    assert!((upper[0] - 9.8).abs() < 1e-4);
+    assert_eq!(scaler.features(), 2);
+    assert_eq!(scaler.scale(0, -1e6), 0.0);
+    assert_eq!(scaler.scale(0, 1e6), 1.0);
+    assert!((scaler.scale(0, 5.0) - 0.5).abs() < 1e-4);
+    // A feature that never changed still scales to 0
+    assert_eq!(scaler.scale(1, 1.0), 0.0);
+
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_scaler(Some(scaler.clone()));
+    let model_name: String = net.write_model("clipped_scaler_test").unwrap();
+    let read: CatNetwork = CatNetwork::read_model(model_name.clone()).unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(read.scaler(), Some(&scaler));
+}
+
+#[test]
+fn encoders_saved_with_model() {
+    let colors: Vec<Types> = categories_str_format(vec!["red", "green", "red", "blue"]);
+    let encoder: Encoder = Encoder::fit(&colors, Encoding::OneHot);
+    assert_eq!(encoder.width(), 3);
+    assert_eq!(encoder.decode(&[0.1, 0.2, 0.9]), Some(Types::String("blue".to_string())));
+
+    let mut net = CatNetwork::new(4, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_encoders(vec![Some(encoder), None]);
+    let model_name: String = net.write_model("encoder_test").unwrap();
+    let read: CatNetwork = CatNetwork::read_model(model_name.clone()).unwrap();
+    fs::remove_file(model_name).unwrap();
+
+    let input: Input = read.encode_row(&[Types::String("green".to_string()), Types::Integer(3)], None).unwrap();
+    assert_eq!(input.inputs, vec![0.0, 1.0, 0.0, 3.0]);
+    assert!(read.encode_row(&[Types::String("purple".to_string()), Types::Integer(3)], None).is_err());
+    assert!(read.encode_row(&[Types::String("red".to_string()), Types::String("3".to_string())], None).is_err());
+}
+
+#[test]
+fn typed_columns_encoded() {
+    let rows: Vec<Vec<Types>> = vec![
+        vec![Types::Integer(34), Types::String("red".to_string()), Types::Boolean(true), Types::Float(0.5)],
+        vec![Types::Integer(51), Types::String("blue".to_string()), Types::Boolean(false), Types::Float(1.5)],
+        vec![Types::Integer(29), Types::String("red".to_string()), Types::Boolean(false), Types::Float(2.5)]
+    ];
+    let mut net = CatNetwork::new(5, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.fit_encoders(&rows, Encoding::OneHot);
+    assert_eq!(net.encoders().iter().map(|encoder| encoder.is_some()).collect::<Vec<bool>>(), vec![false, true, false, false]);
+
+    let answers: Vec<Types> = vec![Types::Boolean(true), Types::Boolean(false), Types::Boolean(true)];
+    let data: Vec<Input> = net.encode_rows(&rows, Some(&answers)).unwrap();
+    assert_eq!(data[1], Input::new(vec![51.0, 0.0, 1.0, 0.0, 1.5], Some(Types::Boolean(false))));
+    assert!(data.iter().all(|input| input.inputs.len() == net.input_size()));
+    assert!(net.encode_rows(&rows, Some(&answers[..2])).is_err());
+    assert!(net.encode_rows(&[vec![Types::Integer(1), Types::String("green".to_string()), Types::Boolean(true), Types::Float(0.0)]], None).is_err());
+
+    assert_eq!(Input::from_types(&[Types::Integer(-2), Types::Boolean(true)], None).unwrap().inputs, vec![-2.0, 1.0]);
+    assert!(Input::from_types(&[Types::String("red".to_string())], None).is_err());
+}
+
+#[test]
+fn hashed_encoding() {
+    let ids: Vec<Types> = (0..1000).map(|i| Types::String(format!("user {}", i))).collect();
+    let encoder: Encoder = Encoder::fit(&ids, Encoding::Hashed { dimension: 16, signed: false });
+    assert!(encoder.vocabulary().is_empty());
+    assert_eq!(encoder.width(), 16);
+    // The same category always hashes to the same sensor, even after the model is saved
+    let encoded = encoder.encode(&ids[3]).unwrap();
+    assert_eq!(encoded, encoder.encode(&ids[3]).unwrap());
+    assert_eq!(encoded.iter().filter(|value| **value == 1.0).count(), 1);
+    assert_eq!(encoder.decode(&encoded), None);
+    let used: usize = (0..16).filter(|sensor| ids.iter().any(|id| encoder.encode(id).unwrap()[*sensor] == 1.0)).count();
+    assert_eq!(used, 16);
+
+    let signed: Encoder = Encoder::fit(&[], Encoding::Hashed { dimension: 16, signed: true });
+    let values: Vec<f32> = ids.iter().map(|id| signed.encode(id).unwrap().into_iter().sum::<f32>()).collect();
+    assert!(values.iter().all(|value| value.abs() == 1.0));
+    assert!(values.contains(&1.0) && values.contains(&-1.0));
+    assert_eq!(Encoder::fit(&[], Encoding::Hashed { dimension: 0, signed: false }).width(), 1);
+
+    let rows: Vec<Vec<Types>> = ids.iter().take(3).map(|id| vec![id.clone(), Types::Float(0.5)]).collect();
+    let mut net = CatNetwork::new(9, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.fit_encoders(&rows, Encoding::Hashed { dimension: 8, signed: true });
+    let model_name: String = net.write_model("hashed_encoder_test").unwrap();
+    let read: CatNetwork = CatNetwork::read_model(model_name.clone()).unwrap();
+    fs::remove_file(model_name).unwrap();
+    let input: Input = read.encode_row(&[Types::String("user 5000".to_string()), Types::Float(0.5)], None).unwrap();
+    assert_eq!(input.inputs.len(), 9);
+    assert_eq!(input.inputs, net.encode_row(&[Types::String("user 5000".to_string()), Types::Float(0.5)], None).unwrap().inputs);
+}
+
+#[test]
+fn darjdata_round_trip() {
+    use crate::darjdata;
+
+    let data: Vec<Input> = vec![
+        Input::new(vec![0.0, -1.5, 3.25], Some(Types::String("a".to_string()))),
+        Input::new(vec![f32::MAX, 0.5, 1e-9], Some(Types::Integer(7))),
+        Input::new(vec![1.0, 2.0, 3.0], None),
+        Input::new(vec![4.0, 5.0, 6.0], Some(Types::String("a".to_string())))
+    ];
+    let bytes: Vec<u8> = darjdata::to_bytes(&data).unwrap();
+    assert_eq!(darjdata::from_bytes(&bytes).unwrap(), data);
+    assert_eq!(darjdata::from_bytes(&darjdata::to_bytes(&[]).unwrap()).unwrap(), vec![]);
+
+    assert!(matches!(darjdata::to_bytes(&[Input::new(vec![0.0], None), Input::new(vec![0.0, 1.0], None)]), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(darjdata::from_bytes(&bytes[..bytes.len() - 1]), Err(DarjeelingError::ReadDataFailed(_))));
+    assert!(matches!(darjdata::from_bytes(b"not a dataset"), Err(DarjeelingError::ReadDataFailed(_))));
+    let mut corrupted: Vec<u8> = bytes.clone();
+    let answers: usize = bytes.len() - 4 * 12 - 4 * 4;
+    corrupted[answers..answers + 4].copy_from_slice(&9u32.to_le_bytes());
+    assert!(matches!(darjdata::from_bytes(&corrupted), Err(DarjeelingError::ReadDataFailed(_))));
+
+    darjdata::write_darjdata("darjdata_test.darjdata", &data).unwrap();
+    let read: Vec<Input> = darjdata::read_darjdata("darjdata_test.darjdata").unwrap();
+    fs::remove_file("darjdata_test.darjdata").unwrap();
+    assert_eq!(read, data);
+    assert!(darjdata::read_darjdata("darjdata_test.darjdata").is_err());
+}
+
+#[test]
+fn dataset_balancing() {
+    let mut inputs: Vec<Input> = (0..6).map(|i| Input::new(vec![i as f32, 0.0], Some(Types::String("a".to_string())))).collect();
+    inputs.push(Input::new(vec![0.0, 1.0], Some(Types::String("b".to_string()))));
+    inputs.push(Input::new(vec![1.0, 1.0], Some(Types::String("b".to_string()))));
+    let data = Dataset::new(inputs);
+    let mut rng = rand::thread_rng();
+
+    assert_eq!(data.category_counts()[1].1, 2);
+    assert!(data.oversample(&mut rng).category_counts().iter().all(|(_, count)| *count == 6));
+    assert!(data.undersample(&mut rng).category_counts().iter().all(|(_, count)| *count == 2));
+    let synthetic: Dataset = data.smote(3, &mut rng);
+    assert_eq!(synthetic.len(), 12);
+    assert!(synthetic.inputs()[8..].iter().all(|input| input.inputs[1] == 1.0 && input.inputs[0] >= 0.0 && input.inputs[0] <= 1.0));
+}
+
+#[test]
+fn learn_from_lazy_source() {
+    let mut passes = 0;
+    let mut source = || {
+        passes += 1;
+        two_classes(4).into_iter()
+    };
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    let (model_name, err_percent, _mse) = net.learn_from_source(&mut source, two_class_categories(), 0.5, "source", 100.0, false).unwrap();
+    assert_eq!(model_name, None);
+    assert_eq!(err_percent, 100.0);
+    // The source is passed over once an epoch
+    assert_eq!(passes, net.history().len());
+
+    // In memory data is lent to the network instead of being copied every epoch
+    let data: Vec<Input> = two_classes(4);
+    let (_, err_percent, _) = net.learn_from_source(&mut data.as_slice(), two_class_categories(), 0.5, "source", 100.0, false).unwrap();
+    assert_eq!(err_percent, 100.0);
+    assert_eq!(net.predict_category(&data[1].inputs), Types::String("high".to_string()));
+}
+
+#[test]
+fn text_vectorizers() {
+    assert_eq!(text::tokenize("Bees, bees & MORE bees!"), vec!["bees", "bees", "more", "bees"]);
+    let documents = vec!["bees make honey", "bees sting", "wasps sting"];
+
+    let bag = Vectorizer::bag_of_words(Vocabulary::build(&documents, 2, None));
+    assert_eq!(bag.vocabulary().words(), vec!["bees", "sting"]);
+    assert_eq!(bag.vectorize("bees bees honey"), vec![2.0, 0.0]);
+
+    let tf_idf = Vectorizer::fit_tf_idf(&documents, 1, None);
+    let path = std::env::temp_dir().join("darjeeling_vectorizer_test.json");
+    tf_idf.write(path.to_str().unwrap()).unwrap();
+    let read = Vectorizer::read(path.to_str().unwrap()).unwrap();
+    assert_eq!(read, tf_idf);
+    let values = read.vectorize("bees honey");
+    let bees = read.vocabulary().index("bees").unwrap();
+    let honey = read.vocabulary().index("honey").unwrap();
+    assert!(values[honey] > values[bees]);
+}
+
+#[test]
+fn vector_and_category_answers() {
+    let categories: Vec<Types> = types::fmt_category_type_vec(vec!["cold", "hot"]);
+    assert_eq!(categories[1], Types::category(1, "hot"));
+    assert_eq!(categories[1].as_f32(), Some(1.0));
+
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    let mut source = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::Vector(vec![1.0, 0.0]))),
+        Input::new(vec![1.0, 0.0], Some(Types::Vector(vec![1.0, 0.0]))),
+    ];
+    let (_, err_percent, _) = net.learn(&mut source, categories, 0.5, "vector", 0.1, false).unwrap();
+    assert!(err_percent >= 0.1);
+}
+
+#[test]
+fn classification_metrics() {
+    let predictions: Vec<Types> = categories_str_format(vec!["a", "a", "b", "b", "a"]);
+    let truth: Vec<Types> = categories_str_format(vec!["a", "b", "b", "b", "a"]);
+    let metrics = ClassificationMetrics::compute(&predictions, &truth);
+    assert_eq!(metrics.accuracy, 0.8);
+    assert_eq!(metrics.classes[0].precision, 2.0 / 3.0);
+    assert_eq!(metrics.classes[1].recall, 2.0 / 3.0);
+    assert_eq!(metrics.classes[1].precision, 1.0);
+    assert!((metrics.micro_f1 - 0.8).abs() < 0.0001);
+    assert!((metrics.macro_f1 - 0.8).abs() < 0.0001);
+}
+
+#[test]
+fn regression_metrics() {
+    let metrics = RegressionMetrics::compute(&[2.0, 4.0], &[1.0, 5.0]);
+    assert_eq!(metrics.rmse, 1.0);
+    assert_eq!(metrics.r2, 0.75);
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.add_hidden_layer_with_size(2);
+    let data = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::Vector(vec![0.5, 0.5]))),
+        Input::new(vec![1.0, 1.0], Some(Types::Float(0.2))),
+        Input::new(vec![1.0, 1.0], None),
+    ];
+    assert_eq!(net.evaluate_regression(&data).count, 3);
+}
+
+#[test]
+fn cross_validation_folds() {
+    let data: Vec<Input> = two_classes(6);
+    let config = TrainingConfig::new(4, 1, ActivationFunction::Sigmoid, 1.0, 100.0);
+    let report = validation::cross_validate(&config, &data, 3, 7, 500).unwrap();
+    assert_eq!(report.folds.len(), 3);
+    assert!(report.folds.iter().all(|fold| fold.train_size == 4 && fold.validation_size == 2 && fold.training_err_percent == 100.0));
+    let accuracies: Vec<f32> = report.folds.iter().map(|fold| fold.metrics.accuracy).collect();
+    assert!((report.mean_accuracy - accuracies.iter().sum::<f32>() / 3.0).abs() < 1e-6);
+    // Every held out row sits next to rows of its category the fold trained on
+    assert!(report.mean_accuracy > 0.5);
+    assert!(validation::cross_validate(&config, &data, 7, 7, 500).is_err());
+
+    // Unanswered inputs are left out instead of shifting every later prediction onto the wrong answer
+    let mut unanswered: Vec<Input> = data.clone();
+    unanswered.insert(1, Input::new(vec![0.5, 1.0], None));
+    let report = validation::cross_validate(&config, &unanswered, 3, 7, 500).unwrap();
+    assert_eq!(report.folds.iter().map(|fold| fold.validation_size).sum::<usize>(), 6);
+}
+
+#[test]
+fn rolling_origin_folds() {
+    assert_eq!(validation::rolling_origin_splits(7, 3, 2, 1), vec![(0..3, 3..5), (0..4, 4..6), (0..5, 5..7)]);
+    assert_eq!(validation::rolling_origin_splits(7, 3, 5, 1), vec![]);
+    assert_eq!(validation::rolling_origin_splits(7, 0, 2, 1), vec![]);
+
+    let data: Vec<Input> = two_classes(8);
+    let config = TrainingConfig::new(4, 1, ActivationFunction::Sigmoid, 1.0, 100.0);
+    let report = validation::rolling_origin_validate(&config, &data, 4, 2, 2, 500).unwrap();
+    assert_eq!(report.folds.iter().map(|fold| (fold.fold, fold.train_size, fold.validation_size)).collect::<Vec<_>>(), vec![(0, 4, 2), (1, 6, 2)]);
+    assert!(report.mean_accuracy > 0.5);
+    assert!(matches!(validation::rolling_origin_validate(&config, &data, 7, 2, 1, 500), Err(DarjeelingError::InvalidTrainingData(_))));
+    let mut unanswered: Vec<Input> = data.clone();
+    unanswered[5].answer = None;
+    assert!(matches!(validation::rolling_origin_validate(&config, &unanswered, 4, 2, 2, 500), Err(DarjeelingError::InvalidTrainingData(_))));
+}
+
+#[test]
+fn hyperparameter_search() {
+    let data: Vec<Input> = two_classes(4);
+    // Tanh networks can stall short of 100% on this data, which the epoch limit has to stop
+    let space = SearchSpace {
+        learning_rates: vec![0.5, 1.0],
+        hidden_nums: vec![2, 3, 4, 5],
+        hidden_layers: vec![1],
+        activation_functions: vec![ActivationFunction::Sigmoid, ActivationFunction::Tanh],
+        target_err_percent: 100.0,
+        max_epochs: 500
+    };
+    let grid = tuning::grid_search(&space, &data, &data, true).unwrap();
+    assert_eq!(grid.trials.len(), 16);
+    assert_eq!(grid.score, grid.trials.iter().map(|trial| trial.score).fold(0.0, f32::max));
+    assert!(grid.score > 0.5);
+    assert_eq!(grid.model.evaluate(&data).metrics.accuracy, grid.score);
+
+    let random = tuning::random_search(&space, &data, &data, 3, 1, false).unwrap();
+    assert_eq!(random.trials.len(), 3);
+    assert!(random.trials.iter().all(|trial| trial.config.learning_rate >= 0.5 && trial.config.learning_rate <= 1.0));
+}
+
+#[test]
+fn training_history() {
+    let mut data: Vec<Input> = two_classes(4);
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.learn(&mut data, two_class_categories(), 0.5, "history", 100.0, false).unwrap();
+
+    let history = net.history();
+    assert_eq!(history.epochs().iter().map(|record| record.epoch).collect::<Vec<usize>>(), (1..=history.len()).collect::<Vec<usize>>());
+    // Training stops at the first epoch that gets everything right
+    assert_eq!(history.last().unwrap().accuracy, 1.0);
+    assert!(history.epochs()[..history.len() - 1].iter().all(|record| record.accuracy < 1.0));
+    assert!(history.epochs().iter().all(|record| record.learning_rate == 0.5));
+
+    let path = std::env::temp_dir().join("darjeeling_history_test.csv");
+    history.to_csv(path.to_str().unwrap()).unwrap();
+    let csv = fs::read_to_string(&path).unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(csv.lines().count(), history.len() + 1);
+    assert!(csv.starts_with("epoch,loss,accuracy,learning_rate\n1,"));
+    assert!(history.to_json().unwrap().starts_with("[{\"epoch\":1,"));
+}
+
+#[test]
+fn holdout_evaluated_every_epoch() {
+    let answer = |x: f32| Some(Types::String(if x < 0.5 { "low" } else { "high" }.to_string()));
+    let mut data: Vec<Input> = (0..8).map(|i| Input::new(vec![i as f32 / 7.0, 1.0], answer(i as f32 / 7.0))).collect();
+    let holdout: Vec<Input> = [0.2, 0.45, 0.9].iter().map(|x| Input::new(vec![*x, 1.0], answer(*x))).collect();
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_holdout(holdout.clone());
+    assert_eq!(net.holdout(), &holdout[..]);
+
+    net.start_training(categories_str_format(vec!["low", "high"]));
+    for _ in 0..3 {
+        let record = net.step_epoch(&mut data, 0.5).unwrap();
+        // Evaluated after the epoch's last update, on the holdout in its own order
+        let report = net.evaluate(&holdout);
+        assert_eq!(record.holdout_loss, Some(report.loss));
+        assert_eq!(record.holdout_accuracy, Some(report.metrics.accuracy));
+    }
+    assert_eq!(net.holdout(), &holdout[..]);
+    assert!(net.history().csv().starts_with("epoch,loss,accuracy,learning_rate,holdout_loss,holdout_accuracy\n1,"));
+    assert!(net.history().to_json().unwrap().contains("\"holdout_accuracy\":"));
+
+    net.set_holdout(vec![]);
+    assert_eq!(net.step_epoch(&mut data, 0.5).unwrap().holdout_loss, None);
+}
+
+#[test]
+fn recorded_sample_order() {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let original: Vec<Input> = (0..6).map(|i| Input::new(vec![i as f32 / 5.0, 1.0], Some(Types::String(if i < 3 { "low" } else { "high" }.to_string())))).collect();
+    let mut recorded = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let mut plain = CatNetwork::from_darj_str(&recorded.to_darj_string().unwrap()).unwrap();
+    recorded.set_record_order(true);
+    recorded.start_training(categories_str_format(vec!["low", "high"]));
+    plain.start_training(categories_str_format(vec!["low", "high"]));
+
+    let (mut recorded_data, mut plain_data) = (original.clone(), original.clone());
+    let (mut recorded_rng, mut plain_rng) = (StdRng::seed_from_u64(3), StdRng::seed_from_u64(3));
+    for _ in 0..3 {
+        let record = recorded.step_epoch_with_rng(&mut recorded_data, 0.5, &mut recorded_rng).unwrap();
+        assert_eq!(record.order.len(), 6);
+        assert!(plain.step_epoch_with_rng(&mut plain_data, 0.5, &mut plain_rng).unwrap().order.is_empty());
+    }
+    // Recording the order trains in the same order
+    assert_eq!(recorded_data, plain_data);
+    assert_eq!(recorded.to_darj_string().unwrap(), plain.to_darj_string().unwrap());
+
+    let order: Vec<usize> = recorded.history().sample_order(3).unwrap();
+    assert_eq!(order.iter().map(|i| original[*i].clone()).collect::<Vec<Input>>(), recorded_data);
+    assert_eq!(recorded.history().sample_order(4), None);
+    assert_eq!(plain.history().sample_order(1), None);
+}
+
+#[test]
+fn step_epochs() {
+    let mut data: Vec<Input> = two_classes(4);
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.start_training(two_class_categories());
+    // Each step carries on from the last, so the network gets there without starting over
+    let mut epochs = 0;
+    while epochs < 1000 {
+        epochs += 1;
+        let record = net.step_epoch(&mut data, 0.5).unwrap();
+        assert_eq!(record.epoch, epochs);
+        if record.accuracy == 1.0 {
+            break;
+        }
+    }
+    assert_eq!(net.history().len(), epochs);
+    assert_eq!(net.history().last().unwrap().accuracy, 1.0);
+
+    net.start_training(two_class_categories());
+    assert_eq!(net.step_epoch(&mut data, 0.5).unwrap().epoch, 1);
+}
+
+#[test]
+fn stream_epoch_metrics() {
+    let mut data: Vec<Input> = two_classes(4);
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    net.set_metrics_sender(Some(sender));
+
+    let trainer = std::thread::spawn(move || {
+        net.learn(&mut data, two_class_categories(), 0.5, "stream", 100.0, false).unwrap();
+        net.history().len()
+    });
+    // Every epoch is sent as it ends, and the network, and with it the sender, is gone once training finishes
+    let records: Vec<crate::history::EpochRecord> = receiver.iter().collect();
+    let epochs: usize = trainer.join().unwrap();
+    assert_eq!(records.iter().map(|record| record.epoch).collect::<Vec<usize>>(), (1..=epochs).collect::<Vec<usize>>());
+    assert_eq!(records.last().unwrap().accuracy, 1.0);
+}
+
+#[test]
+fn tensorboard_event_file() {
+    assert_eq!(tensorboard::crc32c(b"123456789"), 0xe306_9283);
+
+    let dir = std::env::temp_dir().join("darjeeling_tensorboard_test");
+    let _ = fs::remove_dir_all(&dir);
+    let writer = EventWriter::create(dir.to_str().unwrap()).unwrap();
+    let mut data: Vec<Input> = two_classes(4);
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_event_writer(Some(writer.clone()));
+    net.learn(&mut data, two_class_categories(), 0.5, "tensorboard", 100.0, false).unwrap();
+
+    // The file version event, then loss, accuracy and learning rate for every epoch
+    let bytes = fs::read(writer.path()).unwrap();
+    let mut records = 0;
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let length = u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap()) as usize;
+        offset += 8 + 4 + length + 4;
+        records += 1;
+    }
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(offset, bytes.len());
+    assert_eq!(records, 1 + 3 * net.history().len());
+}
+
+#[test]
+fn prometheus_metrics() {
+    use std::sync::Arc;
+    use crate::prometheus::Metrics;
+
+    let metrics = Arc::new(Metrics::new());
+    let mut data: Vec<Input> = two_classes(4);
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_prometheus_metrics(Some(metrics.clone()));
+    net.start_training(two_class_categories());
+    net.step_epoch(&mut data, 0.5).unwrap();
+    let last = net.step_epoch(&mut data, 0.5).unwrap();
+    assert_eq!(metrics.epochs(), 2);
+
+    metrics.record_predictions(3, Duration::from_millis(2));
+    metrics.record_predictions(1, Duration::from_secs(2));
+    let text: String = metrics.render();
+    assert!(text.contains("darjeeling_epochs_completed_total 2\n"));
+    // The gauges hold the last epoch's
+    assert!(text.contains(&format!("darjeeling_training_accuracy {}\n", last.accuracy)));
+    assert!(text.contains(&format!("darjeeling_training_loss {}\n", last.loss)));
+    assert!(text.contains("darjeeling_predictions_total 4\n"));
+    assert!(text.contains("darjeeling_prediction_latency_seconds_bucket{le=\"0.001\"} 0\n"));
+    assert!(text.contains("darjeeling_prediction_latency_seconds_bucket{le=\"0.005\"} 1\n"));
+    assert!(text.contains("darjeeling_prediction_latency_seconds_bucket{le=\"+Inf\"} 2\n"));
+    assert!(text.contains("darjeeling_prediction_latency_seconds_count 2\n"));
+}
+
+#[test]
+fn evaluate_without_mutating() {
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["a", "b"]));
+    let data: Vec<Input> = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::String("a".to_string()))),
+        Input::new(vec![1.0, 0.0], Some(Types::String("b".to_string()))),
+        Input::new(vec![1.0, 1.0], None)
+    ];
+    let before = format!("{:?}", net);
+    let report = net.evaluate(&data);
+    assert_eq!(format!("{:?}", net), before);
+    assert_eq!(report, net.evaluate(&data));
+    assert_eq!(report.count, 2);
+    assert!(report.loss > 0.0 && report.loss < 1.0);
+    assert_eq!(report.metrics.classes.iter().map(|class| class.support).sum::<usize>(), 2);
+
+    let prediction = net.predict(&[1.0, 0.0]);
+    assert_eq!(report.predictions[1], prediction);
+    assert_eq!(prediction.category, net.predict_category(&[1.0, 0.0]));
+    assert_eq!(prediction.confidence, net.confidence(&[1.0, 0.0]));
+    assert_eq!(prediction.scores.iter().map(|(category, _)| category.clone()).collect::<Vec<Types>>(), categories_str_format(vec!["a", "b"]));
+    assert_eq!(prediction.score(&Types::String("c".to_string())), None);
+}
+
+#[test]
+fn calibration() {
+    let report = CalibrationReport::compute(&[0.95, 0.95, 0.95, 0.95, 0.1], &[true, false, true, false, false], 10);
+    assert_eq!(report.buckets.len(), 10);
+    assert_eq!(report.buckets[9].count, 4);
+    assert!((report.expected_calibration_error - (0.45 * 0.8 + 0.1 * 0.2)).abs() < 1e-5);
+
+    // An overconfident model that's right half the time should end up about 50% confident
+    let outputs: Vec<Vec<f32>> = (0..10).map(|_| vec![1.0, 0.0]).collect();
+    let answers: Vec<usize> = (0..10).map(|i| i % 2).collect();
+    let temperature = Calibrator::fit_temperature(&outputs, &answers);
+    assert!(temperature.confidence(&[1.0, 0.0]) < 0.55);
+    let correct: Vec<bool> = answers.iter().map(|answer| *answer == 0).collect();
+    let platt = Calibrator::fit_platt(&[0.9; 10], &correct);
+    assert!((platt.confidence(&[0.9, 0.1]) - 0.5).abs() < 0.05);
+
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["0", "1"]));
+    let data: Vec<Input> = (0..6).map(|i| Input::new(vec![i as f32, 1.0], Some(Types::String((i % 2).to_string())))).collect();
+    let fitted = net.fit_calibrator(&data, CalibrationMethod::Platt);
+    assert_eq!(net.calibration_report(&data, 5).buckets.iter().map(|bucket| bucket.count).sum::<usize>(), 6);
+    let model_name: String = net.write_model("calibrator_test").unwrap();
+    let read: CatNetwork = CatNetwork::read_model(model_name.clone()).unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(read.calibrator(), Some(&fitted));
+}
+
+#[test]
+fn input_gradients_match_finite_differences() {
+    let data: Vec<Input> = vec![Input::new(vec![0.0, 4.0, 1.0], None), Input::new(vec![2.0, 0.0, 3.0], None)];
+    let mut net = CatNetwork::new(3, 4, 2, 2, ActivationFunction::Tanh);
+    net.set_scaler(Some(Scaler::fit_min_max(&data)));
+    let inputs: Vec<f32> = vec![1.0, 2.0, 2.5];
+    let gradients: Vec<f32> = net.input_gradients(&inputs, 1);
+
+    let step: f32 = 1e-2;
+    (0..inputs.len()).for_each(|i| {
+        let mut up: Vec<f32> = inputs.clone();
+        let mut down: Vec<f32> = inputs.clone();
+        up[i] += step;
+        down[i] -= step;
+        let estimate: f32 = (net.forward(&up)[1] - net.forward(&down)[1]) / (2.0 * step);
+        assert!((gradients[i] - estimate).abs() < 1e-3, "input {}: {} vs {}", i, gradients[i], estimate);
+    });
+    assert_eq!(net.input_gradients(&inputs, 5), vec![0.0; 3]);
+}
+
+#[test]
+fn relevance_propagation() {
+    // Every bias zeroed
+    let serialized: String = CatNetwork::new(3, 4, 2, 2, ActivationFunction::Sigmoid).to_darj_string().unwrap().lines()
+        .map(|line| match line.split_once(';') {
+            Some((weights, _)) => format!("{};0", weights),
+            None => line.to_string()
+        })
+        .collect::<Vec<String>>().join("\n");
+    let net = CatNetwork::from_darj_str(&serialized).unwrap();
+    let inputs: Vec<f32> = vec![0.5, 1.0, 0.25];
+    // Without biases or epsilon, the relevance adds up to the answer node's output
+    let relevance: Vec<f32> = net.relevance(&inputs, 1, 0.0);
+    assert_eq!(relevance.len(), 3);
+    assert!((relevance.iter().sum::<f32>() - net.forward(&inputs)[1]).abs() < 1e-4);
+    assert_eq!(net.relevance(&inputs, 5, 0.01), vec![0.0; 3]);
+    // An input of 0 contributes nothing
+    assert_eq!(net.relevance(&[0.0, 1.0, 0.25], 0, 0.01)[0], 0.0);
+
+    // A saturated hidden node has almost no slope, but still passes its contribution back
+    let saturated = CatNetwork::from_darj_str(";0\n;0\nlb\n40,0;0\nlb\n2;0\nlb\nsigmoid").unwrap();
+    assert!(saturated.input_gradients(&[1.0, 1.0], 0)[0].abs() < 1e-6);
+    let relevance: Vec<f32> = saturated.relevance(&[1.0, 1.0], 0, 0.0);
+    assert!((relevance[0] - saturated.forward(&[1.0, 1.0])[0]).abs() < 1e-4);
+    assert_eq!(relevance[1], 0.0);
+}
+
+#[test]
+fn counterfactual_search() {
+    use crate::counterfactual::Mutability;
+
+    let mut data: Vec<Input> = (0..40).map(|i| {
+        let x: f32 = i as f32 / 40.0;
+        Input::new(vec![x, (i % 5) as f32 / 5.0], Some(Types::String(if x > 0.5 { "high" } else { "low" }.to_string())))
+    }).collect();
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    net.start_training(categories_str_format(vec!["low", "high"]));
+    (0..300).for_each(|_| { net.step_epoch(&mut data, 1.0).unwrap(); });
+    let high = Types::String("high".to_string());
+    assert_eq!(net.predict_category(&[0.1, 0.4]), Types::String("low".to_string()));
+
+    assert!(matches!(net.counterfactual(&[0.1], &high, &[]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.counterfactual(&[0.1, 0.4], &high, &[Mutability::Free]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.counterfactual(&[0.1, 0.4], &Types::String("medium".to_string()), &[]), Err(DarjeelingError::UnknownCategory(_))));
+
+    let counterfactual = net.counterfactual(&[0.1, 0.4], &high, &[Mutability::Free, Mutability::Fixed]).unwrap().unwrap();
+    assert_eq!(counterfactual.prediction.category, high);
+    assert_eq!(net.predict_category(&counterfactual.inputs), high);
+    assert_eq!(counterfactual.changes.len(), 1);
+    assert_eq!(counterfactual.changes[0].0, 0);
+    assert!(counterfactual.changes[0].1 > 0.0);
+    assert_eq!(counterfactual.inputs[1], 0.4);
+    assert!((counterfactual.distance - counterfactual.changes[0].1).abs() < 1e-6);
+    // It's pulled back to about where the prediction flips
+    assert!(net.predict_category(&[counterfactual.inputs[0] - 0.05, 0.4]) != high);
+
+    // Already in the target category, so nothing changes
+    let unchanged = net.counterfactual(&[0.9, 0.4], &high, &[]).unwrap().unwrap();
+    assert!(unchanged.changes.is_empty() && unchanged.distance == 0.0);
+    // Only moving x down can't make it high
+    assert!(net.counterfactual(&[0.1, 0.4], &high, &[Mutability::Decrease, Mutability::Range { min: 0.0, max: 1.0 }]).unwrap().is_none());
+    assert_eq!(Mutability::Range { min: 0.0, max: 1.0 }.constrain(0.5, 3.0), 1.0);
+    assert_eq!(Mutability::Increase.constrain(0.5, 0.2), 0.5);
+}
+
+#[test]
+fn layer_profiling() {
+    let mut net = CatNetwork::new(2, 3, 2, 2, ActivationFunction::Sigmoid);
+    let mut data: Vec<Input> = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::String("a".to_string()))),
+        Input::new(vec![1.0, 0.0], Some(Types::String("b".to_string())))
+    ];
+    net.start_training(categories_str_format(vec!["a", "b"]));
+    assert!(net.step_epoch(&mut data, 0.5).unwrap().timings.is_empty());
+
+    net.set_profile_layers(true);
+    let record = net.step_epoch(&mut data, 0.5).unwrap();
+    assert_eq!(record.timings.iter().map(|timing| timing.layer).collect::<Vec<usize>>(), vec![1, 2, 3]);
+    assert!(record.timings.iter().all(|timing| timing.forward >= Duration::ZERO && timing.backward >= Duration::ZERO));
+    net.step_epoch(&mut data, 0.5).unwrap();
+    let totals = net.history().layer_timings();
+    assert_eq!(totals.len(), 3);
+    assert!(totals.iter().zip(&record.timings).all(|(total, timing)| total.total() > timing.total()));
+
+    net.set_profile_layers(false);
+    assert!(net.step_epoch(&mut data, 0.5).unwrap().timings.is_empty());
+}
+
+#[test]
+fn deterministic_reduction() {
+    use crate::config::{Reduction, TrainingConfig};
+
+    let config = TrainingConfig::new(4, 2, ActivationFunction::Sigmoid, 0.5, 99.0).with_reduction(Reduction::Deterministic { seed: 11 });
+    let net = CatNetwork::from_config(2, 2, &config);
+    assert_eq!(net.reduction(), Reduction::Deterministic { seed: 11 });
+    let data: Vec<Input> = (0..12).map(|i| {
+        let (x, y) = ((i % 4) as f32 / 3.0, (i / 4) as f32 / 2.0);
+        Input::new(vec![x, y], Some(Types::String(if x > y { "above" } else { "below" }.to_string())))
+    }).collect();
+    let train = |mut net: CatNetwork| {
+        let mut data: Vec<Input> = data.clone();
+        net.start_training(categories_str_format(vec!["above", "below"]));
+        (0..4).for_each(|_| { net.step_epoch(&mut data, 0.5).unwrap(); });
+        net
+    };
+
+    let (first, second) = (train(net.clone()), train(net.clone()));
+    assert_eq!(first.to_darj_string().unwrap(), second.to_darj_string().unwrap());
+    assert_eq!(first.history(), second.history());
+    let mut reseeded = net.clone();
+    reseeded.set_reduction(Reduction::Deterministic { seed: 12 });
+    assert_ne!(train(reseeded).to_darj_string().unwrap(), first.to_darj_string().unwrap());
+
+    // The sums are compensated, but still land where plain ones do to within rounding
+    let mut fast = first.clone();
+    fast.set_reduction(Reduction::Fast);
+    data.iter().for_each(|input| {
+        fast.raw_outputs(&input.inputs).iter().zip(first.raw_outputs(&input.inputs)).for_each(|(a, b)| assert!((a - b).abs() < 1e-5));
+    });
+}
+
+#[test]
+fn compensated_sums() {
+    use crate::{history::CompensatedSum, metrics::RegressionMetrics};
+
+    let small = || std::iter::once(1.0).chain(std::iter::repeat(1.0e-8).take(10_000));
+    assert_eq!(small().sum::<f32>(), 1.0);
+    assert!((CompensatedSum::of(small()) - 1.0001).abs() < 1.0e-6);
+
+    // A plain f32 sum of a million tenths drifts by most of a percent
+    let predictions: Vec<f32> = vec![0.1; 1_000_000];
+    let truth: Vec<f32> = vec![0.0; 1_000_000];
+    assert!((predictions.iter().sum::<f32>() / 1_000_000.0 - 0.1).abs() > 1.0e-4);
+    let metrics = RegressionMetrics::compute(&predictions, &truth);
+    assert!((metrics.mae - 0.1).abs() < 1.0e-6);
+    assert!((metrics.rmse - 0.1).abs() < 1.0e-6);
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let mut data: Vec<Input> = vec![Input::new(vec![0.0, 1.0], Some(Types::Integer(0))), Input::new(vec![1.0, 0.0], Some(Types::Integer(1)))];
+    net.start_training(vec![Types::Integer(0), Types::Integer(1)]);
+    let losses: Vec<f32> = (0..3).map(|_| net.step_epoch(&mut data, 0.5).unwrap().loss).collect();
+    assert!(losses.iter().all(|loss| *loss > 0.0 && *loss < 1.0));
+
+    // An aborted epoch's partial loss doesn't carry into the next one. Without learning, every epoch's loss is the same
+    use crate::history::GradientAlarm;
+    let clean: f32 = net.step_epoch(&mut data, 0.0).unwrap().loss;
+    net.set_gradient_alarm(Some(GradientAlarm::abort(0.0)));
+    assert!(net.step_epoch(&mut data, 0.0).is_err());
+    net.set_gradient_alarm(None);
+    assert!((net.step_epoch(&mut data, 0.0).unwrap().loss - clean).abs() < 1e-6);
+}
+
+#[test]
+fn model_card() {
+    use crate::{dataset::DataFingerprint, modelcard::ModelCard, schema::Schema, threshold::DecisionThreshold};
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let mut data: Vec<Input> = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::String("ham".to_string()))),
+        Input::new(vec![1.0, 0.0], Some(Types::String("spam".to_string())))
+    ];
+    assert!(matches!(net.generate_model_card(Some(&data)), Err(DarjeelingError::InvalidInput(_))));
+    let card: ModelCard = net.generate_model_card(None).unwrap();
+    assert!(card.categories.is_empty() && card.training_data.is_none() && card.evaluation.is_none());
+    assert!(card.to_markdown().contains("Not evaluated."));
+
+    net.start_training(categories_str_format(vec!["ham", "spam"]));
+    (0..3).for_each(|_| { net.step_epoch(&mut data, 0.5).unwrap(); });
+    net.set_data_fingerprint(Some(DataFingerprint::of(&data)));
+    net.set_schema(Some(Schema::new(vec!["links".to_string(), "caps".to_string()]).unwrap())).unwrap();
+    net.set_threshold(Some(DecisionThreshold::new(Types::String("spam".to_string()), 0.8))).unwrap();
+    assert!(net.generate_model_card(Some(&[Input::new(vec![0.0], None)])).is_err());
+
+    let card: ModelCard = net.generate_model_card(Some(&data)).unwrap().with_name("Spam filter").with_intended_use("Sorting mail").with_limitations("English only");
+    assert_eq!(card.architecture.layers, vec![2, 3, 2]);
+    assert_eq!(card.architecture.parameters, 17);
+    assert_eq!(card.features, vec!["links".to_string(), "caps".to_string()]);
+    assert_eq!(card.training.epochs, 3);
+    assert_eq!(card.training_data.as_ref().unwrap().rows, 2);
+    assert_eq!(card.evaluation.as_ref().unwrap().count, 2);
+
+    let markdown: String = card.to_markdown();
+    ["# Spam filter", "## Intended Use\nSorting mail", "- Layers: 2 → 3 → 2", "- Features: links, caps", "a threshold of 0.8 for spam", "| spam |", "## Limitations\nEnglish only"]
+        .iter().for_each(|section| assert!(markdown.contains(section), "{} isn't in\n{}", section, markdown));
+    let read: ModelCard = serde_json::from_str(&serde_json::to_string(&card).unwrap()).unwrap();
+    assert_eq!(read, card);
+}
+
+#[test]
+fn record_activations() {
+    let net = CatNetwork::new(2, 3, 2, 2, ActivationFunction::Sigmoid);
+    let data: Vec<Input> = vec![Input::new(vec![0.0, 1.0], None), Input::new(vec![1.0, 0.0], None)];
+    let snapshots = net.inspect_all(&data);
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[0].layer(0), Some(&[0.0, 1.0][..]));
+    assert_eq!(snapshots[0].hidden().len(), 2);
+    assert_eq!(snapshots[1].answers(), net.forward(&[1.0, 0.0]).as_slice());
+    assert_eq!(snapshots[1].node(3, 1), Some(snapshots[1].answers()[1]));
+    assert_eq!(snapshots[1].node(4, 0), None);
+}
+
+#[test]
+fn layer_stats_in_history() {
+    let mut data: Vec<Input> = two_classes(4);
+    let mut net = CatNetwork::new(2, 3, 2, 2, ActivationFunction::Sigmoid);
+    net.start_training(two_class_categories());
+    assert!(net.step_epoch(&mut data, 0.5).unwrap().layers.is_empty());
+
+    net.set_collect_layer_stats(true);
+    let record = net.step_epoch(&mut data, 0.5).unwrap();
+    let layers = &record.layers;
+    assert_eq!(layers.iter().map(|stats| stats.layer).collect::<Vec<usize>>(), vec![1, 2, 3]);
+    assert!(layers.iter().all(|stats| stats.weight_max > 0.0 && stats.weight_std > 0.0 && stats.err_sig_max > 0.0));
+    assert!(net.history().to_json().unwrap().contains("\"err_sig_std\""));
+}
+
+#[test]
+fn diff_models() {
+    let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let probe: Vec<Input> = xor_file();
+
+    // Saving rounds nothing away, so a read model should behave the same
+    let mut saved = net.clone();
+    let model_name: String = saved.write_model("diff_test").unwrap();
+    let read: CatNetwork = CatNetwork::read_model(model_name.clone()).unwrap();
+    fs::remove_file(model_name).unwrap();
+    let diff = net.diff(&read, &probe).unwrap();
+    assert!(diff.within(1e-6));
+    assert_eq!(diff.layers.len(), 2);
+    assert_eq!(diff.agreement, 1.0);
+
+    let other = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let diff = net.diff(&other, &probe).unwrap();
+    assert!(diff.layers.iter().all(|layer| layer.max_delta > 0.0));
+    assert!(diff.max_output_divergence > 0.0);
+
+    let wider = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    assert!(matches!(net.diff(&wider, &probe), Err(DarjeelingError::ArchitectureMismatch(_))));
+}
+
+#[test]
+fn csv_with_saved_categories() {
+    let data: Vec<Input> = csv::parse_csv("1, 2, yes\n\n3, 4, no\n", false, Some(2)).unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[1].inputs, vec![3.0, 4.0]);
+    assert_eq!(csv::parse_csv("label,x\nyes,1\n", true, Some(0)).unwrap()[0].inputs, vec![1.0]);
+    assert!(matches!(csv::parse_csv("1,two,yes\n", false, Some(2)), Err(DarjeelingError::ReadDataFailed(_))));
+    assert!(matches!(csv::parse_csv("1,2\n", false, Some(2)), Err(DarjeelingError::ReadDataFailed(_))));
+
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Tanh);
+    assert_eq!(net.categories(), None);
+    net.set_categories(categories_str_format(vec!["yes", "no"]));
+    let model_name: String = net.write_model("categories_test").unwrap();
+    let read: CatNetwork = CatNetwork::read_model(model_name.clone()).unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(read.categories(), Some(categories_str_format(vec!["yes", "no"])));
+    assert_eq!(read.input_size(), 2);
+    assert_eq!(read.predict_category(&[1.0, 2.0]), net.predict_category(&[1.0, 2.0]));
+}
+
+#[test]
+fn input_and_types_json() {
+    let data: Vec<Input> = vec![
+        Input::new(vec![0.0, 1.5], Some(Types::String("on".to_string()))),
+        Input::new(vec![1.0], Some(Types::category(2, "off"))),
+        Input::new(vec![], None)
+    ];
+    let json: String = serde_json::to_string(&data).unwrap();
+    assert_eq!(json, r#"[{"inputs":[0.0,1.5],"answer":{"String":"on"}},{"inputs":[1.0],"answer":{"Category":{"index":2,"name":"off"}}},{"inputs":[],"answer":null}]"#);
+    assert_eq!(serde_json::from_str::<Vec<Input>>(&json).unwrap(), data);
+
+    let unlabeled: Input = serde_json::from_str(r#"{"inputs": [2.0]}"#).unwrap();
+    assert_eq!(unlabeled.answer, None);
+    let values: Vec<Types> = serde_json::from_str(r#"[{"Integer": 3}, {"Float": 0.5}, {"Boolean": true}, {"Vector": [1.0]}]"#).unwrap();
+    assert_eq!(values, vec![Types::Integer(3), Types::Float(0.5), Types::Boolean(true), Types::Vector(vec![1.0])]);
+}
+
+#[test]
+fn generated_samples() {
+    let data: Vec<Input> = gan_data();
+    let mut net = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let samples: Vec<GeneratedSample> = net.test(&data).unwrap();
+    assert_eq!(samples.iter().map(|sample| sample.seed.clone().unwrap()).collect::<Vec<Input>>(), data);
+    assert_eq!(samples[0].output.len(), 2);
+    assert!(samples.iter().all(|sample| sample.realism.is_none()));
+
+    let mut net: GenNetwork = trained_gan("samples", 1, |_| {});
+    let samples: Vec<GeneratedSample> = net.test(&data[..2]).unwrap();
+    assert_eq!(samples.len(), 2);
+    assert!(samples.iter().all(|sample| (0.0..=1.0).contains(&sample.realism.unwrap())));
+
+    let discriminator = net.discriminator().unwrap();
+    assert_eq!(discriminator.realism(&Input::new(samples[1].output.clone(), None)), samples[1].realism.unwrap());
+    assert_eq!(discriminator.network().categories(), Some(vec![Types::Boolean(true), Types::Boolean(false)]));
+    assert!(GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid).discriminator().is_none());
+
+    let seeded: Vec<GeneratedSample> = net.generate_with_seed(7, 3);
+    assert_eq!(seeded, net.generate_with_seed(7, 3));
+    assert_ne!(seeded, net.generate_with_seed(8, 3));
+    let tested: Vec<GeneratedSample> = net.test(&[seeded[0].seed.clone().unwrap()]).unwrap();
+    assert!(tested[0].output.iter().zip(&seeded[0].output).all(|(a, b)| (a - b).abs() < 0.0001));
+    assert!(seeded.iter().all(|sample| sample.realism.is_some()));
+
+    let path: Vec<GeneratedSample> = net.interpolate(&[0.0, 1.0], &[1.0, 0.0], 5).unwrap();
+    assert_eq!(path.len(), 5);
+    assert_eq!(path[0].seed.as_ref().unwrap().inputs, vec![0.0, 1.0]);
+    assert_eq!(path[2].seed.as_ref().unwrap().inputs, vec![0.5, 0.5]);
+    assert_eq!(path[4].seed.as_ref().unwrap().inputs, vec![1.0, 0.0]);
+    assert_eq!(net.interpolate(&[0.0, 1.0], &[1.0, 0.0], 1).unwrap().len(), 1);
+    assert!(matches!(net.interpolate(&[0.0], &[1.0, 0.0], 3), Err(DarjeelingError::InvalidInput(_))));
+
+    net.set_output_constraints(vec![None, Some(OutputConstraint::range(2.0, 3.0).integer())]);
+    let constrained: Vec<GeneratedSample> = net.generate_with_seed(7, 3);
+    assert!(constrained.iter().zip(&seeded).all(|(constrained, seeded)| constrained.output[0] == seeded.output[0] && constrained.output[1] == 2.0));
+    assert_eq!(net.test(&data[..1]).unwrap()[0].output[1], 2.0);
+    assert_eq!(OutputConstraint { min: None, max: Some(0.5), integer: false }.apply(0.7), 0.5);
+}
+
+#[test]
+fn instance_noise() {
+    use crate::adversarial::InstanceNoise;
+
+    let mut samples: Vec<Input> = vec![
+        Input::new(vec![0.5, 0.5], Some(Types::Boolean(true))),
+        Input::new(vec![0.5, 0.5], Some(Types::Boolean(false))),
+        Input::new(vec![0.5, 0.5], None)
+    ];
+    InstanceNoise::new(0.0, 1.0, 0.5).apply(&mut samples, &mut rand::thread_rng());
+    assert_eq!(samples[0].answer, Some(Types::Boolean(false)));
+    assert_eq!(samples[1].answer, Some(Types::Boolean(true)));
+    assert_eq!(samples[2].answer, None);
+    assert!(samples.iter().all(|sample| sample.inputs == vec![0.5, 0.5]));
+
+    InstanceNoise::new(0.1, 0.0, 0.5).at_cycle(1).apply(&mut samples, &mut rand::thread_rng());
+    assert_eq!(samples[0].answer, Some(Types::Boolean(false)));
+    assert!(samples.iter().any(|sample| sample.inputs != vec![0.5, 0.5]));
+    assert_eq!(InstanceNoise::new(0.1, 0.2, 0.5).at_cycle(1), InstanceNoise::new(0.05, 0.1, 0.5));
+
+    let net: GenNetwork = trained_gan("noise", 2, |net| net.set_instance_noise(Some(InstanceNoise::new(0.05, 0.1, 0.8))));
+    assert!(net.discriminator().is_some());
+}
+
+#[test]
+fn wasserstein_loss() {
+    use crate::adversarial::AdversarialLoss;
+
+    let mut data: Vec<Input> = gan_data();
+    let mut net = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_adversarial_loss(AdversarialLoss::Wasserstein { clip: 0.05 });
+    let model_name: String = net.learn(&mut data, 0.5, "wasserstein", 2, 0.01, 2, 1, ActivationFunction::Linear, 0.1).unwrap();
+    fs::remove_file(model_name).unwrap();
+
+    let critic: &CatNetwork = net.discriminator().unwrap().network();
+    let snapshot = critic.inspect(&[0.5, 0.5]);
+    assert_eq!(snapshot.answers().len(), 2);
+    assert!(weights_within(critic, 0.05), "{}", critic.to_darj_string().unwrap());
+}
+
+/// Whether every weight of a linear network is within `-clip..=clip`
+fn weights_within(net: &CatNetwork, clip: f32) -> bool {
+    net.to_darj_string().unwrap().lines().skip(3).take_while(|line| *line != "linear").filter(|line| *line != "lb")
+        .flat_map(|line| line.split([',', ';'])).filter(|value| !value.is_empty())
+        .all(|value| value.parse::<f32>().unwrap().abs() <= clip)
+}
+
+#[test]
+fn wasserstein_critic() {
+    let clip: f32 = 0.5;
+    // Hidden layers are carried back through with the sigmoid's slope whatever the activation, which can turn a linear critic the wrong way
+    let mut critic = CatNetwork::new(2, 3, 2, 0, ActivationFunction::Linear);
+    critic.start_training(vec![Types::Boolean(true), Types::Boolean(false)]);
+    let samples: Vec<Input> = (0..8).map(|i| match i % 2 {
+        0 => Input::new(vec![1.0, 1.0], Some(Types::Boolean(true))),
+        _ => Input::new(vec![0.0, 0.0], Some(Types::Boolean(false)))
+    }).collect();
+    for _ in 0..20 {
+        for sample in &samples {
+            critic.critic_step(sample, 0.01, clip);
+            assert!(weights_within(&critic, clip), "{}", critic.to_darj_string().unwrap());
+        }
+    }
+
+    let score = |inputs: &[f32]| {
+        let prediction = critic.predict(inputs);
+        prediction.score(&Types::Boolean(true)).unwrap() - prediction.score(&Types::Boolean(false)).unwrap()
+    };
+    let gap: f32 = score(&[1.0, 1.0]) - score(&[0.0, 0.0]);
+    assert!(gap > 0.0);
+    // Nothing squashes the score, so it keeps growing with the input instead of stopping at 1
+    let far: f32 = score(&[100.0, 100.0]) - score(&[0.0, 0.0]);
+    assert!(far > 1.0 && (far - 100.0 * gap).abs() < 0.01 * far, "{} {}", far, gap);
+}
+
+#[test]
+fn mode_collapse() {
+    use crate::adversarial::diversity;
+
+    assert_eq!(diversity(&[]), 0.0);
+    assert_eq!(diversity(&[vec![0.0], vec![1.0], vec![3.0]]), 2.0);
+
+    let net: GenNetwork = trained_gan("collapse", 3, |net| net.set_collapse_threshold(f32::MAX));
+    assert_eq!(net.cycles().iter().map(|cycle| cycle.cycle).collect::<Vec<usize>>(), vec![1, 2, 3]);
+    assert!(net.cycles().iter().all(|cycle| cycle.diversity >= 0.0 && cycle.collapsed));
+    assert!(net.collapsed());
+}
+
+#[test]
+fn feature_matching_loss() {
+    use crate::adversarial::{self, AdversarialLoss};
+
+    let critic: CatNetwork = CatNetwork::from_darj_str(";0\nlb\n1;0\n-1;0\nlb\n1,0;0\n0,1;0\nlb\nlinear").unwrap();
+    let same: Vec<Input> = vec![Input::new(vec![0.5], Some(Types::Boolean(true))), Input::new(vec![0.5], Some(Types::Boolean(false)))];
+    assert_eq!(adversarial::feature_matching_distance(&critic, &same), 0.0);
+    let apart: Vec<Input> = vec![
+        Input::new(vec![1.0], Some(Types::Boolean(true))),
+        Input::new(vec![3.0], Some(Types::Boolean(true))),
+        Input::new(vec![0.0], Some(Types::Boolean(false)))
+    ];
+    // Linear doubles, so the real features average to (4, -4), each 4 away from the generated (0, 0)
+    assert_eq!(adversarial::feature_matching_distance(&critic, &apart), 16.0);
+    assert_eq!(adversarial::feature_matching_distance(&critic, &apart[..2]), 0.0);
+    // The generated output is below the real ones, so it should grow, by the distance's slope carried back through both doubling features
+    assert_eq!(adversarial::feature_matching_signals(&critic, &apart), vec![16.0]);
+    let above: Vec<Input> = vec![apart[0].clone(), apart[1].clone(), Input::new(vec![5.0], Some(Types::Boolean(false)))];
+    assert_eq!(adversarial::feature_matching_signals(&critic, &above), vec![-24.0]);
+    assert_eq!(adversarial::feature_matching_signals(&critic, &apart[..2]), vec![0.0]);
+
+    let net: GenNetwork = trained_gan("feature_matching", 2, |net| net.set_adversarial_loss(AdversarialLoss::FeatureMatching));
+    assert!(net.cycles().iter().all(|cycle| cycle.loss >= 0.0));
+    let read: GenNetwork = GenNetwork::from_bundle_str(&net.to_bundle_string().unwrap()).unwrap();
+    assert_eq!(read.to_bundle_string().unwrap(), net.to_bundle_string().unwrap());
+}
+
+#[test]
+fn discriminator_ensemble() {
+    use crate::adversarial::{Aggregation, AdversarialLoss, DiscriminatorEnsemble};
+
+    let standard = AdversarialLoss::Standard;
+    assert_eq!(Aggregation::Mean.aggregate(&[0.2, 0.4], &standard), 0.3);
+    assert_eq!(Aggregation::Median.aggregate(&[0.4, 0.1, 0.2, 0.9], &standard), 0.3);
+    assert_eq!(Aggregation::Harshest.aggregate(&[0.4, 0.1, 0.9], &standard), 0.1);
+    assert_eq!(Aggregation::Mean.aggregate(&[], &standard), 0.0);
+    assert!((Aggregation::Softmax { temperature: 0.0 }.aggregate(&[0.2, 0.4], &standard) - 0.3).abs() < 0.0001);
+    let sharp: f32 = Aggregation::Softmax { temperature: 100.0 }.aggregate(&[0.2, 0.4], &standard);
+    assert!((sharp - 0.2).abs() < 0.001, "{}", sharp);
+    let wasserstein = AdversarialLoss::Wasserstein { clip: 0.1 };
+    assert!((Aggregation::Softmax { temperature: 100.0 }.aggregate(&[0.2, 0.4], &wasserstein) - 0.4).abs() < 0.001);
+
+    let mut net: GenNetwork = trained_gan("ensemble", 2, |net| net.set_ensemble(Some(DiscriminatorEnsemble::new(3, Aggregation::Median))));
+    assert_eq!(net.discriminators().len(), 3);
+    assert!(net.cycles().iter().all(|cycle| cycle.spread >= 0.0));
+
+    let probe = Input::new(vec![0.3, 0.7], None);
+    let sample: GeneratedSample = net.test(std::slice::from_ref(&probe)).unwrap().remove(0);
+    let generated = Input::new(sample.output, None);
+    let average: f32 = net.discriminators().iter().map(|discriminator| discriminator.realism(&generated)).sum::<f32>() / 3.0;
+    assert!((sample.realism.unwrap() - average).abs() < 0.0001);
+    let read: GenNetwork = GenNetwork::from_bundle_str(&net.to_bundle_string().unwrap()).unwrap();
+    assert_eq!(read.discriminators().len(), 3);
+    assert_eq!(read.ensemble(), net.ensemble());
+    assert_eq!(read.discriminators()[2].realism(&probe), net.discriminators()[2].realism(&probe));
+}
+
+#[test]
+fn replay_buffer() {
+    use crate::adversarial::ReplayBuffer;
+
+    let mut rng = rand::thread_rng();
+    let mut buffer = ReplayBuffer::new(3, 1.0);
+    let mut samples: Vec<Input> = vec![Input::new(vec![0.5], Some(Types::Boolean(false))), Input::new(vec![0.5], Some(Types::Boolean(true)))];
+    buffer.mix(&mut samples, &mut rng);
+    assert_eq!(samples[0].inputs, vec![0.5]);
+    buffer.add(&(0..100).map(|i| vec![i as f32]).collect::<Vec<Vec<f32>>>(), &mut rng);
+    assert_eq!(buffer.len(), 3);
+    assert!(buffer.samples().iter().any(|sample| sample[0] >= 3.0), "{:?}", buffer.samples());
+    buffer.mix(&mut samples, &mut rng);
+    assert!(buffer.samples().contains(&samples[0].inputs));
+    assert_eq!(samples[1].inputs, vec![0.5]);
+    assert_eq!(ReplayBuffer::new(3, 2.0).ratio, 1.0);
+
+    let net: GenNetwork = trained_gan("replay", 3, |net| net.set_replay_buffer(Some(ReplayBuffer::new(6, 0.5))));
+    assert_eq!(net.replay_buffer().unwrap().len(), 6);
+    let read: GenNetwork = GenNetwork::from_bundle_str(&net.to_bundle_string().unwrap()).unwrap();
+    assert_eq!(read.replay_buffer(), net.replay_buffer());
+    buffer.clear();
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn sample_dumps() {
+    use crate::generation::{SampleDump, DumpFormat};
+
+    let sample = GeneratedSample { output: vec![0.25, 0.5], seed: None, realism: Some(0.75) };
+    let dump = SampleDump::new("unused.csv", 2, 1);
+    assert_eq!(dump.format_samples(4, std::slice::from_ref(&sample), true), "cycle,output_0,output_1,realism\n4,0.25,0.5,0.75\n");
+    assert_eq!(dump.format_samples(4, &[GeneratedSample { realism: None, ..sample.clone() }], false), "4,0.25,0.5,\n");
+    let jsonl = dump.with_format(DumpFormat::Jsonl);
+    let line: serde_json::Value = serde_json::from_str(jsonl.format_samples(4, &[sample], true).trim()).unwrap();
+    assert_eq!(line["cycle"], 4);
+    assert_eq!(line["output"][1], 0.5);
+    assert!(!jsonl.due(3) && jsonl.due(4));
+    assert!(!SampleDump::new("unused.csv", 0, 1).due(4));
+
+    let path = std::env::temp_dir().join(format!("sample_dump_{}.csv", rand::random::<u32>()));
+    let net: GenNetwork = trained_gan("dump", 4, |net| net.set_sample_dump(Some(SampleDump::new(&path, 2, 3).with_seed(5))));
+    let dumped: String = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    let lines: Vec<&str> = dumped.lines().collect();
+    assert_eq!(lines.len(), 7, "{}", dumped);
+    assert_eq!(lines[0], "cycle,output_0,output_1,realism");
+    assert!(lines[1..4].iter().all(|line| line.starts_with("2,")) && lines[4..].iter().all(|line| line.starts_with("4,")));
+    assert_eq!(lines[6].split(',').nth(1).unwrap().parse::<f32>().unwrap(), net.generate_with_seed(5, 3)[2].output[0]);
+}
+
+#[test]
+fn synthetic_fidelity() {
+    use crate::fidelity::{self, FidelityReport};
+
+    assert_eq!(fidelity::ks_statistic(&[0.0, 1.0, 2.0, 3.0], &[2.0, 3.0]), 0.5);
+    assert_eq!(fidelity::ks_statistic(&[], &[1.0]), 0.0);
+    assert!((fidelity::wasserstein_distance(&[0.0, 0.0, 1.0], &[0.5]) - 0.5).abs() < 0.0001);
+    assert_eq!(fidelity::wasserstein_distance(&[1.0, 2.0], &[2.0, 1.0]), 0.0);
+
+    let real: Vec<Input> = (0..4).map(|i| Input::new(vec![i as f32, 1.0], None)).collect();
+    let report = FidelityReport::compare(&real, &[vec![0.0, 1.0], vec![3.0, 1.0]]).unwrap();
+    assert_eq!((report.real, report.generated), (4, 2));
+    assert_eq!(report.features[1].ks, 0.0);
+    assert_eq!(report.worst().unwrap().feature, 0);
+    assert!(report.to_string().contains("Feature 1: KS 0.0000"));
+    assert!(matches!(FidelityReport::compare(&real, &[vec![0.0]]), Err(DarjeelingError::InvalidInput(_))));
+
+    assert!(GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid).fidelity().is_none());
+    let net: GenNetwork = trained_gan("fidelity", 1, |_| {});
+    let trained: &FidelityReport = net.fidelity().unwrap();
+    assert_eq!(trained.features.len(), 2);
+    assert!(trained.features.iter().all(|feature| (0.0..=1.0).contains(&feature.ks)));
+
+    let labelled = |shift: f32| (0..8).map(|i| Input::new(vec![(i % 2) as f32 + shift, 0.5], Some(Types::Boolean(i % 2 == 0)))).collect::<Vec<Input>>();
+    let config = TrainingConfig::new(4, 1, ActivationFunction::Sigmoid, 0.5, 0.1);
+    let utility = fidelity::train_synthetic_test_real(&labelled(0.0), &labelled(0.0), Some(&labelled(0.0)), &config).unwrap();
+    let real_accuracy: f32 = utility.real.as_ref().unwrap().accuracy;
+    assert!((0.0..=1.0).contains(&real_accuracy) && (0.0..=1.0).contains(&utility.synthetic.accuracy));
+    assert_eq!(utility.gap().unwrap(), real_accuracy - utility.synthetic.accuracy);
+    assert!(utility.to_string().contains("Trained on real"));
+    assert!(fidelity::train_synthetic_test_real(&[], &labelled(0.0), None, &config).is_err());
+}
+
+#[test]
+fn equilibrium_stopping() {
+    use crate::adversarial::{CycleRecord, EquilibriumStopping};
+
+    let cycle = |cycle: usize, discriminator_accuracy: f32| CycleRecord {
+        cycle, loss: 0.0, diversity: 1.0, collapsed: false, spread: 0.0, discriminator_accuracy, learning_rate: 0.5, discriminator_learning_rate: 0.5
+    };
+    let stopping = EquilibriumStopping::new(0.1, 3);
+    assert!(!stopping.reached(&[cycle(1, 0.5), cycle(2, 0.5)]));
+    assert!(!stopping.reached(&[cycle(1, 0.5), cycle(2, 0.7), cycle(3, 0.5)]));
+    assert!(stopping.reached(&[cycle(1, 1.0), cycle(2, 0.45), cycle(3, 0.55), cycle(4, 0.58)]));
+    assert!(EquilibriumStopping::new(0.1, 0).reached(&[cycle(1, 0.5)]));
+
+    // A generation model with no weights outputs exactly the real records, so the distinguishing model can only guess
+    let mut data: Vec<Input> = (0..8).map(|_| Input::new(vec![0.5, 0.5], None)).collect();
+    let mut net = GenNetwork::from_darj_str(";0\n;0\nlb\n0,0;0\n0,0;0\nlb\n0,0;0\n0,0;0\nlb\nsigmoid").unwrap();
+    net.set_equilibrium_stopping(Some(EquilibriumStopping::new(0.01, 1)));
+    let model_name: String = net.learn(&mut data, 0.5, "equilibrium", 10, 0.5, 2, 1, ActivationFunction::Sigmoid, 0.1).unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(net.cycles().len(), 1);
+    assert!(net.reached_equilibrium());
+    assert_eq!(net.equilibrium_trace(), vec![0.5]);
+
+    // Sigmoid outputs can't reach real records at 5, so the distinguishing model always tells them apart.
+    // It stops at the first epoch it gets right, so it learns quickly enough to leave a margin the held-out samples fall on the right side of.
+    let mut data: Vec<Input> = (0..8).map(|_| Input::new(vec![5.0, 5.0], None)).collect();
+    let mut net = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_equilibrium_stopping(Some(EquilibriumStopping::new(0.1, 1)));
+    let model_name: String = net.learn(&mut data, 0.5, "disequilibrium", 3, 2.0, 2, 1, ActivationFunction::Sigmoid, 99.0).unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(net.cycles().len(), 3);
+    assert!(!net.reached_equilibrium());
+    assert!(net.equilibrium_trace().iter().all(|accuracy| *accuracy > 0.6));
+
+    let config = GanConfig::default().with_equilibrium_stopping(EquilibriumStopping::new(0.05, 4));
+    assert_eq!(config.equilibrium.unwrap().patience, 4);
+    assert!(!GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid).reached_equilibrium());
+}
+
+#[test]
+fn adversarial_schedules() {
+    use crate::config::LearningRateSchedule;
+
+    let mut data: Vec<Input> = gan_data();
+    let mut net = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let config = GanConfig::default()
+        .with_cycles(3)
+        .with_learning_rate(0.1)
+        .with_discriminator(TrainingConfig::new(2, 1, ActivationFunction::Sigmoid, 0.5, 0.1))
+        .with_two_time_scale(4.0)
+        .with_schedule(LearningRateSchedule::warm_restarts(2, 1));
+    let model_name: String = net.learn_with_config(&mut data, &config, "schedules").unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(net.schedules(), (LearningRateSchedule::warm_restarts(2, 1), LearningRateSchedule::Constant));
+    let rates: Vec<f32> = net.cycles().iter().map(|cycle| cycle.learning_rate).collect();
+    assert!((rates[0] - 0.1).abs() < 0.0001 && (rates[1] - 0.05).abs() < 0.0001 && (rates[2] - 0.1).abs() < 0.0001, "{:?}", rates);
+    assert!(net.cycles().iter().all(|cycle| (cycle.discriminator_learning_rate - 0.4).abs() < 0.0001));
+
+    net.set_schedules(LearningRateSchedule::Constant, LearningRateSchedule::warm_restarts(4, 1));
+    let read: GenNetwork = GenNetwork::from_bundle_str(&net.to_bundle_string().unwrap()).unwrap();
+    assert_eq!(read.schedules(), net.schedules());
+}
+
+#[test]
+fn generation_bundle() {
+    use crate::adversarial::{InstanceNoise, AdversarialLoss};
+
+    let net: GenNetwork = trained_gan("bundle", 2, |net| {
+        net.set_instance_noise(Some(InstanceNoise::new(0.05, 0.0, 0.9)));
+        net.set_adversarial_loss(AdversarialLoss::Wasserstein { clip: 0.5 });
+    });
+
+    let bundle_name: String = net.write_bundle("bundle_test").unwrap();
+    let mut read: GenNetwork = GenNetwork::read_bundle(&bundle_name).unwrap();
+    fs::remove_file(&bundle_name).unwrap();
+    assert_eq!(read.to_bundle_string().unwrap(), net.to_bundle_string().unwrap());
+    assert_eq!(read.cycles(), net.cycles());
+    let probe = Input::new(vec![0.3, 0.7], None);
+    assert_eq!(read.discriminator().unwrap().realism(&probe), net.discriminator().unwrap().realism(&probe));
+
+    let model_name: String = read.learn(&mut gan_data(), 0.5, "bundle", 1, 0.5, 2, 1, ActivationFunction::Sigmoid, 0.1).unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(read.cycles().last().unwrap().cycle, 3);
+    assert!(matches!(GenNetwork::from_bundle_str("{}"), Err(DarjeelingError::InvalidMetadataRead(_))));
+}
+
+#[test]
+fn tabular_synthesis() {
+    use crate::tabular::{TabularSchema, TabularSynthesizer, Column};
+
+    let plans: Vec<Types> = categories_str_format(vec!["free", "team", "paid"]);
+    let schema = TabularSchema::new().integer("age", 18.0, 90.0).numeric("spend", 0.0, 10.0).categorical("plan", plans.clone());
+    assert_eq!(schema.width(), 5);
+    assert_eq!(schema.columns()[2].name(), "plan");
+    assert_eq!(schema.encode(&[18.0, 5.0, 2.0]).unwrap(), vec![0.0, 0.5, 0.0, 0.0, 1.0]);
+    assert!(matches!(schema.encode(&[18.0, 5.0, 3.0]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(schema.encode(&[18.0, 5.0]), Err(DarjeelingError::InvalidInput(_))));
+    assert_eq!(schema.decode(&[2.0, 0.25, 0.1, 0.7, 0.2]).unwrap(), vec![Types::Integer(90), Types::Float(2.5), plans[1].clone()]);
+    assert!(matches!(schema.decode(&[2.0, 0.25, 0.1]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(schema.columns()[0], Column::Numeric { integer: true, .. }));
+
+    let dataset = Dataset::new((0..6).map(|i| Input::new(vec![20.0 + i as f32, i as f32, (i % 3) as f32], None)).collect());
+    let mut synthesizer = TabularSynthesizer::new(schema, 6, 1, ActivationFunction::Sigmoid);
+    let config = GanConfig::default().with_cycles(2).with_discriminator(TrainingConfig::new(4, 1, ActivationFunction::Sigmoid, 0.5, 0.1));
+    let model_name: String = synthesizer.train(&dataset, &config, "tabular").unwrap();
+    fs::remove_file(model_name).unwrap();
+
+    let rows: Vec<Vec<Types>> = synthesizer.generate(3, 4).unwrap();
+    assert_eq!(rows, synthesizer.generate(3, 4).unwrap());
+    assert!(rows.iter().all(|row| matches!(row[0], Types::Integer(age) if (18..=90).contains(&age))));
+    assert!(rows.iter().all(|row| matches!(row[1], Types::Float(spend) if (0.0..=10.0).contains(&spend))));
+    assert!(rows.iter().all(|row| plans.contains(&row[2])));
+}
+
+#[test]
+fn anomaly_detection() {
+    use crate::anomaly::AnomalyDetector;
+
+    let mut data: Vec<Input> = (0..8).map(|i| Input::new(vec![i as f32 / 8.0, 0.5], None)).collect();
+    let mut net = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let model_name: String = net.learn(&mut data, 0.5, "anomaly", 2, 0.5, 2, 1, ActivationFunction::Sigmoid, 0.1).unwrap();
+    fs::remove_file(model_name).unwrap();
+    let discriminator = net.discriminator().unwrap();
+
+    let score: f32 = discriminator.anomaly_score(&data[0]);
+    assert!((score + discriminator.realism(&data[0]) - 1.0).abs() < 0.0001);
+
+    let strict = AnomalyDetector::fit(discriminator, &data, 0.0).unwrap();
+    assert_eq!(strict.evaluate(&data).false_positive_rate, 0.0);
+    let loose = AnomalyDetector::fit(discriminator, &data, 0.25).unwrap();
+    assert!(loose.evaluate(&data).false_positive_rate <= 0.25);
+    assert!(loose.threshold() <= strict.threshold());
+    assert!(!AnomalyDetector::with_threshold(discriminator, 2.0).is_anomaly(&data[0]));
+
+    assert!(AnomalyDetector::fit(discriminator, &[], 0.1).is_err());
+    assert!(AnomalyDetector::fit(discriminator, &data, 1.5).is_err());
+}
+
+#[test]
+fn sanity_check() {
+    let report = crate::diagnostics::sanity_check().unwrap();
+    assert!(report.converged, "{:?}", report);
+    assert_eq!(report.accuracy, 1.0);
+    assert!(report.epochs <= crate::diagnostics::SANITY_MAX_EPOCHS);
+    // The seed makes every run the same
+    assert_eq!(crate::diagnostics::sanity_check().unwrap(), report);
+}
+
+#[test]
+fn output_dirs() {
+    let root = std::env::temp_dir().join(format!("darjeeling_output_dirs_{}", rand::random::<u32>()));
+    let config = TrainingConfig::new(2, 1, ActivationFunction::Sigmoid, 0.5, 0.1)
+        .with_output_dir(root.join("models"))
+        .with_temp_dir(root.join("tmp"));
+
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_dirs(&config);
+    let model_name: String = net.write_model("dirs").unwrap();
+    assert!(std::path::Path::new(&model_name).starts_with(root.join("models")));
+    assert!(CatNetwork::read_model(model_name).is_ok());
+
+    let mut data: Vec<Input> = gan_data();
+    let mut gen = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    gen.set_dirs(&config);
+    let model_name: String = gen.learn(&mut data, 0.5, "dirs", 2, 0.5, 2, 1, ActivationFunction::Sigmoid, 0.1).unwrap();
+    assert!(std::path::Path::new(&model_name).starts_with(root.join("models")));
+    // One distinguishing model for every cycle
+    assert_eq!(fs::read_dir(root.join("tmp")).unwrap().count(), 2);
+    // And one for every member of an ensemble, told apart by its index
+    gen.set_ensemble(Some(crate::adversarial::DiscriminatorEnsemble::new(2, crate::adversarial::Aggregation::Mean)));
+    fs::remove_file(gen.learn(&mut data, 0.5, "members", 1, 0.5, 2, 1, ActivationFunction::Sigmoid, 0.1).unwrap()).unwrap();
+    let names: Vec<String> = fs::read_dir(root.join("tmp")).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+    assert_eq!(names.len(), 4);
+    assert!((0..2).all(|member| names.iter().any(|name| name.contains(&format!("distinguishingmembers_cycle3_member{}", member)))));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn learn_with_configs() {
+    let config = TrainingConfig::default()
+        .with_hidden_num(3)
+        .with_hidden_layers(2)
+        .with_learning_rate(0.5)
+        .with_target_err_percent(0.1)
+        .with_write(false);
+    assert_eq!(config, TrainingConfig { hidden_num: 3, hidden_layers: 2, write: false, target_err_percent: 0.1, ..TrainingConfig::default() });
+
+    let mut data: Vec<Input> = xor_file();
+    let mut net = CatNetwork::from_config(2, 2, &config);
+    assert_eq!(net.input_size(), 2);
+    let (model_name, _, _) = net.learn_with_config(&mut data, categories_float_format(vec![1.0, 0.0]), &config, "configured").unwrap();
+    assert_eq!(model_name, None);
+
+    let mut data: Vec<Input> = gan_data();
+    let mut gen = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let config = GanConfig::default().with_cycles(3).with_discriminator(config);
+    let model_name: String = gen.learn_with_config(&mut data, &config, "configured").unwrap();
+    fs::remove_file(model_name).unwrap();
+    assert_eq!(gen.cycles().len(), 3);
+}
+
+#[test]
+fn experiment_replay() {
+    use crate::experiment::Experiment;
+
+    let dir = std::env::temp_dir().join(format!("darjeeling_experiment_{}", rand::random::<u32>()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("xor.csv"), "a,b,output\n0,0,0\n0,1,1\n1,0,1\n1,1,0\n").unwrap();
+    let experiment = Experiment {
+        name: "replay".to_string(),
+        data: "xor.csv".into(),
+        has_header: true,
+        label_column: None,
+        categories: vec![],
+        seed: 7,
+        max_epochs: Some(50),
+        runs_dir: None,
+        checkpoint_every: None,
+        cache_dir: None,
+        config: TrainingConfig::default().with_hidden_num(3).with_write(false)
+    };
+    let path: String = dir.join("xor.json").to_string_lossy().into_owned();
+    experiment.write(&path).unwrap();
+    assert_eq!(Experiment::read(&path).unwrap().data, dir.join("xor.csv"));
+
+    let first = CatNetwork::train_from_config(&path).unwrap();
+    let second = CatNetwork::train_from_config(&path).unwrap();
+    assert_eq!(first.model_name, None);
+    assert!(first.epochs <= 50);
+    assert_eq!(first.epochs, second.epochs);
+    assert_eq!(first.net.to_darj_string().unwrap(), second.net.to_darj_string().unwrap());
+    assert_eq!(first.net.categories(), Some(vec![Types::String("0".to_string()), Types::String("1".to_string())]));
+
+    assert_eq!(first.run_dir, None);
+    assert!(Experiment::from_json_str("{}").is_err());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn experiment_run_directories() {
+    use crate::experiment::Experiment;
+
+    let dir = std::env::temp_dir().join(format!("darjeeling_runs_{}", rand::random::<u32>()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("xor.csv"), "a,b,output\n0,0,0\n0,1,1\n1,0,1\n1,1,0\n").unwrap();
+    let experiment = Experiment {
+        name: "xor".to_string(),
+        data: "xor.csv".into(),
+        has_header: true,
+        label_column: None,
+        categories: vec![],
+        seed: 7,
+        max_epochs: Some(4),
+        runs_dir: Some("runs".into()),
+        checkpoint_every: Some(2),
+        cache_dir: None,
+        config: TrainingConfig::default().with_hidden_num(3).with_write(false).with_target_err_percent(101.0)
+    };
+    let path: String = dir.join("xor.json").to_string_lossy().into_owned();
+    experiment.write(&path).unwrap();
+    let read: Experiment = Experiment::read(&path).unwrap();
+    assert_eq!(read.runs_dir, Some(dir.join("runs")));
+    // Even when the data isn't relative to the experiment
+    let elsewhere: String = dir.join("elsewhere.json").to_string_lossy().into_owned();
+    Experiment { data: dir.join("xor.csv"), cache_dir: Some("cache".into()), ..experiment.clone() }.write(&elsewhere).unwrap();
+    let elsewhere: Experiment = Experiment::read(&elsewhere).unwrap();
+    assert_eq!((elsewhere.runs_dir, elsewhere.cache_dir), (Some(dir.join("runs")), Some(dir.join("cache"))));
+
+    let first = read.run().unwrap();
+    let run_dir = first.run_dir.clone().unwrap();
+    assert_eq!(run_dir, dir.join("runs").join("xor-1"));
+    assert_eq!(Experiment::read(&run_dir.join("config.json").to_string_lossy()).unwrap().seed, 7);
+    assert_eq!(fs::read_to_string(run_dir.join("history.csv")).unwrap(), first.net.history().csv());
+    assert_eq!(fs::read_to_string(run_dir.join("train.log")).unwrap().lines().count(), 6);
+    let mut checkpoints: Vec<String> = fs::read_dir(run_dir.join("checkpoints")).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+    checkpoints.sort();
+    assert_eq!(checkpoints, vec!["epoch-2.darj", "epoch-4.darj"]);
+    let model = CatNetwork::from_darj_str(&fs::read_to_string(run_dir.join("model.darj")).unwrap()).unwrap();
+    assert_eq!(model.to_darj_string().unwrap(), first.net.to_darj_string().unwrap());
+
+    // Runs never overwrite each other
+    assert_eq!(read.run().unwrap().run_dir, Some(dir.join("runs").join("xor-2")));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn multi_output_regression() {
+    // Learns the point a quarter turn around a circle from an angle
+    let mut data: Vec<Input> = (0..16).map(|i| {
+        let angle: f32 = i as f32 / 16.0;
+        let (sin, cos) = (angle * std::f32::consts::TAU).sin_cos();
+        Input::new(vec![angle], Some(Types::Vector(vec![(cos + 1.0) / 2.0, (sin + 1.0) / 2.0])))
+    }).collect();
+    let mut net = CatNetwork::new(1, 8, 2, 1, ActivationFunction::Sigmoid);
+    assert!(matches!(net.start_regression(&["x"]), Err(DarjeelingError::InvalidInput(_))));
+    net.start_regression(&["x", "y"]).unwrap();
+    let before = net.evaluate_regression_heads(&data);
+    for _ in 0..300 {
+        net.step_epoch(&mut data, 0.5).unwrap();
+    }
+    let after = net.evaluate_regression_heads(&data);
+
+    assert_eq!(after.names, vec![Types::String("x".to_string()), Types::String("y".to_string())]);
+    assert_eq!(after.heads.len(), 2);
+    assert!(after.heads.iter().all(|head| head.count == 16));
+    assert_eq!(after.overall.count, 32);
+    assert!(after.overall.rmse < before.overall.rmse);
+    // The same values as evaluate_regression, summed in a different order
+    assert!((after.overall.rmse - net.evaluate_regression(&data).rmse).abs() < 0.0001);
+}
+
+#[test]
+fn output_weights() {
+    let mut data: Vec<Input> = (0..8).map(|i| Input::new(vec![i as f32 / 8.0], Some(Types::Vector(vec![0.9, i as f32 / 8.0])))).collect();
+    let mut net = CatNetwork::new(1, 4, 2, 1, ActivationFunction::Sigmoid);
+    assert!(matches!(net.set_output_weights(vec![1.0, 1.0, 1.0]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.set_output_weights(vec![1.0, -1.0]), Err(DarjeelingError::InvalidInput(_))));
+    net.set_output_weights(vec![0.0, 1.0]).unwrap();
+    assert_eq!(net.output_weights(), &[0.0, 1.0]);
+    net.start_regression(&["ignored", "learned"]).unwrap();
+
+    let answer_layer = |net: &CatNetwork| net.to_darj_string().unwrap().split("lb\n").nth(2).unwrap().lines().next().unwrap().to_string();
+    let ignored_node: String = answer_layer(&net);
+    for _ in 0..20 {
+        net.step_epoch(&mut data, 0.5).unwrap();
+    }
+    // A node whose error doesn't count never has its weights adjusted
+    assert_eq!(answer_layer(&net), ignored_node);
+
+    // Only the second output counts towards the loss
+    let heads = net.evaluate_regression_heads(&data);
+    assert!((net.evaluate(&data).loss - heads.heads[1].rmse.powi(2)).abs() < 0.0001);
+}
+
+#[test]
+fn learning_rate_finder() {
+    let data: Vec<Input> = xor_file();
+    let categories: Vec<Types> = categories_float_format(vec![1.0, 0.0]);
+    let net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    let before: String = net.to_darj_string().unwrap();
+
+    let found = tuning::lr_find(&net, &data, categories.clone(), 0.001, 100.0, 200, 3).unwrap();
+    assert_eq!(net.to_darj_string().unwrap(), before);
+    assert!(!found.losses.is_empty() && found.losses.len() <= 200);
+    assert_eq!(found.learning_rates.len(), found.losses.len());
+    assert!(found.learning_rates.windows(2).all(|pair| pair[1] > pair[0]));
+    assert!(found.suggested >= 0.0001 && found.suggested <= 10.0);
+    assert_eq!(tuning::lr_find(&net, &data, categories.clone(), 0.001, 100.0, 200, 3).unwrap(), found);
+
+    assert!(tuning::lr_find(&net, &data, categories.clone(), 1.0, 0.1, 200, 3).is_err());
+    assert!(tuning::lr_find(&net, &[], categories, 0.001, 1.0, 200, 3).is_err());
+}
+
+#[test]
+fn snapshot_ensemble() {
+    use crate::ensemble::Ensemble;
+
+    let mut data: Vec<Input> = xor_file();
+    let categories: Vec<Types> = categories_float_format(vec![1.0, 0.0]);
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    assert!(net.learn_snapshot_ensemble(&mut data, categories.clone(), 1.0, 0, 10).is_err());
+
+    let ensemble: Ensemble = net.learn_snapshot_ensemble(&mut data, categories.clone(), 1.0, 3, 10).unwrap();
+    assert_eq!(ensemble.len(), 3);
+    assert_eq!(net.history().len(), 30);
+    assert_eq!(ensemble.members()[2].to_darj_string().unwrap(), net.to_darj_string().unwrap());
+
+    let prediction = ensemble.predict(&[0.0, 1.0]);
+    let average: f32 = ensemble.members().iter().map(|member| member.predict(&[0.0, 1.0]).scores[0].1).sum::<f32>() / 3.0;
+    assert!((prediction.scores[0].1 - average).abs() < 0.0001);
+    assert_eq!(ensemble.evaluate(&data).classes.iter().map(|class| class.support).sum::<usize>(), 4);
+
+    let mut other = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    other.set_categories(vec![Types::String("a".to_string()), Types::String("b".to_string())]);
+    assert!(Ensemble::new(vec![net, other]).is_err());
+    assert!(Ensemble::new(vec![]).is_err());
+
+    // A read ensemble is checked the same way
+    let read: Ensemble = serde_json::from_str(&serde_json::to_string(&ensemble).unwrap()).unwrap();
+    assert_eq!(read.len(), 3);
+    assert!(serde_json::from_str::<Ensemble>(r#"{"members":[]}"#).is_err());
+}
+
+#[test]
+fn gradient_norms() {
+    use crate::history::GradientAlarm;
+
+    let mut data: Vec<Input> = xor_file();
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    net.start_training(categories_float_format(vec![1.0, 0.0]));
+    let record = net.step_epoch(&mut data, 0.5).unwrap();
+    assert!(record.grad_norm > 0.0);
+    assert!(record.max_grad_norm >= record.grad_norm);
+
+    net.set_gradient_alarm(Some(GradientAlarm::warn(0.0)));
+    assert!(net.step_epoch(&mut data, 0.5).is_ok());
+    net.set_gradient_alarm(Some(GradientAlarm::abort(0.0)));
+    assert!(matches!(net.step_epoch(&mut data, 0.5), Err(DarjeelingError::ExplodingGradient(_))));
+    net.set_gradient_alarm(Some(GradientAlarm::abort(f32::MAX)));
+    assert!(net.step_epoch(&mut data, 0.5).is_ok());
+    assert!(GradientAlarm::abort(f32::MAX).exceeded(f32::NAN));
+}
+
+#[test]
+fn elastic_net() {
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::config::Regularization;
+
+    let weight_sum = |net: &CatNetwork| net.to_darj_string().unwrap().split("lb\n").skip(1).take(2)
+        .flat_map(|layer| layer.lines().flat_map(|node| node.split(';').next().unwrap().split(',').filter(|weight| !weight.is_empty()).map(|weight| weight.parse::<f32>().unwrap().abs()).collect::<Vec<f32>>()).collect::<Vec<f32>>())
+        .collect::<Vec<f32>>();
+    let mut data: Vec<Input> = xor_file();
+    let mut plain = CatNetwork::from_config(2, 2, &TrainingConfig::default().with_hidden_num(4));
+    plain.randomize_weights(&mut StdRng::seed_from_u64(7));
+    plain.start_training(categories_float_format(vec![1.0, 0.0]));
+    let mut elastic = plain.clone();
+    elastic.set_regularization(Regularization::elastic_net(0.01, 0.1));
+    let mut lasso = plain.clone();
+    lasso.set_regularization(Regularization::l1(1.0));
+    for _ in 0..50 {
+        plain.step_epoch(&mut data, 0.5).unwrap();
+        elastic.step_epoch(&mut data, 0.5).unwrap();
+        lasso.step_epoch(&mut data, 0.5).unwrap();
+    }
+    assert!(weight_sum(&elastic).iter().sum::<f32>() < weight_sum(&plain).iter().sum::<f32>());
+    // A strong L1 penalty sets weights to exactly 0
+    assert!(weight_sum(&lasso).contains(&0.0));
+
+    let config = TrainingConfig::default().with_regularization(Regularization::l2(0.1));
+    assert_eq!(CatNetwork::from_config(2, 2, &config).regularization(), Regularization::l2(0.1));
+}
+
+#[test]
+fn warm_restarts() {
+    use crate::config::LearningRateSchedule;
+
+    let schedule = LearningRateSchedule::WarmRestarts { cycle_length: 2, cycle_multiplier: 2, min_learning_rate: 0.1 };
+    let mut data: Vec<Input> = xor_file();
+    let mut net = CatNetwork::from_config(2, 2, &TrainingConfig::default().with_hidden_num(4).with_schedule(schedule));
+    assert_eq!(net.schedule(), schedule);
+    net.start_training(categories_float_format(vec![1.0, 0.0]));
+    for _ in 0..7 {
+        net.step_epoch(&mut data, 1.0).unwrap();
+    }
+    let rates: Vec<f32> = net.history().epochs().iter().map(|record| record.learning_rate).collect();
+    // Cycles of 2 then 4 epochs, each starting over at the full learning rate
+    assert_eq!(rates[0], 1.0);
+    assert!((rates[1] - 0.55).abs() < 0.0001);
+    assert_eq!(rates[2], 1.0);
+    assert!(rates[3] < rates[2] && rates[4] < rates[3] && rates[5] < rates[4]);
+    assert!(rates[5] >= 0.1);
+    assert_eq!(rates[6], 1.0);
+    assert_eq!(LearningRateSchedule::Constant.learning_rate(0.3, 100), 0.3);
+}
+
+#[test]
+fn max_norm_constraint() {
+    let node_norms = |net: &CatNetwork| net.to_darj_string().unwrap().split("lb\n").skip(1).take(2)
+        .flat_map(|layer| layer.lines().map(|node| node.split(';').next().unwrap().split(',').filter(|weight| !weight.is_empty()).map(|weight| weight.parse::<f32>().unwrap().powi(2)).sum::<f32>().sqrt()).collect::<Vec<f32>>())
+        .collect::<Vec<f32>>();
+    let mut data: Vec<Input> = xor_file();
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    assert!(matches!(net.set_max_norm(Some(-1.0)), Err(DarjeelingError::InvalidInput(_))));
+    net.set_max_norm(Some(0.2)).unwrap();
+    net.start_training(categories_float_format(vec![1.0, 0.0]));
+    for _ in 0..20 {
+        net.step_epoch(&mut data, 2.0).unwrap();
+    }
+    assert!(node_norms(&net).iter().all(|norm| *norm <= 0.2001));
+}
+
+#[cfg(feature = "mixed-precision")]
+#[test]
+fn mixed_precision() {
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::precision::MixedPrecision;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut data: Vec<Input> = xor_file();
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    net.randomize_weights(&mut rng);
+    net.set_mixed_precision(Some(MixedPrecision::new(1e9).with_growth_interval(50)));
+    net.start_training(categories_float_format(vec![1.0, 0.0]));
+    let first = net.step_epoch_with_rng(&mut data, 0.5, &mut rng).unwrap();
+    for _ in 0..300 {
+        net.step_epoch_with_rng(&mut data, 0.5, &mut rng).unwrap();
+    }
+    let precision: &MixedPrecision = net.mixed_precision().unwrap();
+    // The starting scale overflows f16, so it has to come down before anything trains
+    assert!(precision.skipped_steps() > 0);
+    assert!(precision.loss_scale < 1e9);
+    let report = net.evaluate(&data);
+    assert!(report.loss.is_finite());
+    assert!(report.loss < first.loss);
+}
+
+#[test]
+fn size_from_data() {
+    let mut data: Vec<Input> = xor_file();
+    let mut net = CatNetwork::new_from_data(&data, 4, 1, ActivationFunction::Sigmoid).unwrap();
+    assert_eq!(net.input_size(), 2);
+    assert_eq!(net.output_size(), 2);
+    let categories: Vec<Types> = net.categories().unwrap();
+    assert!(net.learn(&mut data, categories, 0.5, "sized", 50.0, false).is_ok());
+
+    assert!(matches!(CatNetwork::new_from_data(&[], 4, 1, ActivationFunction::Sigmoid), Err(DarjeelingError::InvalidTrainingData(_))));
+    let ragged = vec![Input::new(vec![0.0, 1.0], Some(Types::Float(1.0))), Input::new(vec![0.0], Some(Types::Float(0.0)))];
+    assert!(matches!(CatNetwork::new_from_data(&ragged, 4, 1, ActivationFunction::Sigmoid), Err(DarjeelingError::InvalidTrainingData(_))));
+    let unanswered = vec![Input::new(vec![0.0, 1.0], None)];
+    assert!(matches!(CatNetwork::new_from_data(&unanswered, 4, 1, ActivationFunction::Sigmoid), Err(DarjeelingError::InvalidTrainingData(_))));
+}
+
+#[test]
+fn discover_categories() {
+    let mut data: Vec<Input> = xor_file();
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    net.learn(&mut data, vec![], 0.5, "discovered", 50.0, false).unwrap();
+    assert_eq!(net.categories().unwrap(), categories_float_format(vec![0.0, 1.0]));
+
+    // The order doesn't depend on the order of the data
+    let mut reversed: Vec<Input> = data.iter().rev().cloned().collect();
+    assert_eq!(Dataset::new(reversed.clone()).sorted_categories(), Dataset::new(data.clone()).sorted_categories());
+    let mut explicit = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    explicit.learn(&mut reversed, categories_float_format(vec![1.0, 0.0]), 0.5, "explicit", 50.0, false).unwrap();
+    assert_eq!(explicit.categories().unwrap(), categories_float_format(vec![1.0, 0.0]));
+
+    let mut too_many = CatNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    assert!(matches!(too_many.learn(&mut data, vec![], 0.5, "mismatch", 50.0, false), Err(DarjeelingError::ArchitectureMismatch(_))));
+    let mixed = vec![Types::Boolean(true), Types::String("b".to_string()), Types::Integer(2), Types::String("a".to_string())];
+    let mut sorted = mixed.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    assert_eq!(sorted, vec![Types::String("a".to_string()), Types::String("b".to_string()), Types::Integer(2), Types::Boolean(true)]);
+}
+
+#[test]
+fn three_way_split() {
+    let mut inputs: Vec<Input> = (0..53).map(|i| Input::new(vec![i as f32], Some(Types::Integer(if i < 40 { 0 } else if i < 50 { 1 } else { 2 })))).collect();
+    inputs.push(Input::new(vec![53.0], None));
+    let data = Dataset::new(inputs);
+    assert!(matches!(data.three_way_split(0.5, 0.5, 1), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(data.three_way_split(-0.1, 0.2, 1), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(data.three_way_split(f32::NAN, 0.2, 1), Err(DarjeelingError::InvalidInput(_))));
+
+    let (train, validation, test) = data.three_way_split(0.1, 0.2, 9).unwrap();
+    assert_eq!(test.category_counts(), vec![(Types::Integer(0), 8), (Types::Integer(1), 2), (Types::Integer(2), 1)]);
+    assert_eq!(validation.category_counts(), vec![(Types::Integer(0), 4), (Types::Integer(1), 1), (Types::Integer(2), 1)]);
+    assert_eq!(train.category_counts(), vec![(Types::Integer(0), 28), (Types::Integer(1), 7), (Types::Integer(2), 1)]);
+    assert!(train.inputs().last().unwrap().answer.is_none());
+    // Every input is in exactly one set, and each set keeps the data's order
+    let mut rejoined: Vec<f32> = train.inputs().iter().chain(validation.inputs()).chain(test.inputs()).map(|input| input.inputs[0]).collect();
+    assert!(test.inputs().windows(2).all(|pair| pair[0].inputs[0] < pair[1].inputs[0]));
+    rejoined.sort_by(|a, b| a.total_cmp(b));
+    assert_eq!(rejoined, (0..54).map(|i| i as f32).collect::<Vec<f32>>());
+
+    // The same seed splits the same way, and a different one doesn't
+    assert_eq!(data.three_way_split(0.1, 0.2, 9).unwrap().2.inputs(), test.inputs());
+    assert_ne!(data.three_way_split(0.1, 0.2, 10).unwrap().2.inputs(), test.inputs());
+}
+
+#[test]
+fn suggested_architecture() {
+    assert!(matches!(Dataset::new(vec![]).suggest_architecture(), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(Dataset::new(vec![Input::new(vec![0.0], None)]).suggest_architecture(), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(Dataset::new(vec![Input::new(vec![0.0], Some(Types::Integer(0))), Input::new(vec![0.0, 1.0], None)]).suggest_architecture(), Err(DarjeelingError::InvalidTrainingData(_))));
+
+    // Too few inputs for a wide layer
+    let suggestion = Dataset::new(xor_file()).suggest_architecture().unwrap();
+    assert_eq!((suggestion.inputs, suggestion.hidden, suggestion.answers, suggestion.hidden_layers), (2, 2, 2, 1));
+    let net = suggestion.network(ActivationFunction::Sigmoid);
+    assert_eq!((net.input_size(), net.output_size()), (2, 2));
+
+    let many: Vec<Input> = (0..10000).map(|i| Input::new(vec![0.5; 30], Some(Types::Integer(i % 4)))).collect();
+    assert_eq!(Dataset::new(many).suggest_architecture().unwrap().hidden, 24);
+    let vectors: Vec<Input> = (0..100).map(|_| Input::new(vec![0.5; 3], Some(Types::Vector(vec![0.0; 5])))).collect();
+    assert_eq!(Dataset::new(vectors).suggest_architecture().unwrap().answers, 5);
+}
+
+#[test]
+fn stratified_early_stopping() {
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::validation::EarlyStopping;
+
+    let inputs: Vec<Input> = (0..40).map(|i| Input::new(vec![i as f32 / 40.0, (i % 3) as f32], Some(Types::Integer(if i < 30 { 0 } else { 1 })))).collect();
+    let data = Dataset::new(inputs);
+    let (train, validation) = data.stratified_split(0.2, &mut StdRng::seed_from_u64(5));
+    assert_eq!(train.category_counts(), vec![(Types::Integer(0), 24), (Types::Integer(1), 8)]);
+    assert_eq!(validation.category_counts(), vec![(Types::Integer(0), 6), (Types::Integer(1), 2)]);
+    // Nothing is lost or repeated
+    let mut rejoined: Vec<f32> = train.inputs().iter().chain(validation.inputs()).map(|input| input.inputs[0]).collect();
+    rejoined.sort_by(|a, b| a.total_cmp(b));
+    assert_eq!(rejoined, data.inputs().iter().map(|input| input.inputs[0]).collect::<Vec<f32>>());
+
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    let report = net.learn_with_early_stopping(&data, vec![], 0.5, 200, &EarlyStopping::new(0.2, 5, 5)).unwrap();
+    assert_eq!((report.train_size, report.validation_size), (32, 8));
+    assert!(report.epochs <= 200 && report.epochs >= report.best_epoch);
+    assert!(report.epochs == 200 || report.epochs - report.best_epoch == 6);
+    assert_eq!(net.history().len(), report.epochs);
+    assert!((net.evaluate(validation.inputs()).loss - report.best_loss).abs() < 0.0001);
+    assert!(matches!(
+        net.learn_with_early_stopping(&data, vec![], 0.5, 200, &EarlyStopping::new(0.0, 5, 5)),
+        Err(DarjeelingError::InvalidTrainingData(_))
+    ));
+}
+
+#[test]
+fn dataset_audit() {
+    let data = Dataset::new(xor_file());
+    assert!(data.audit().is_clean());
+
+    let mut inputs: Vec<Input> = xor_file().into_iter().map(|mut input| { input.inputs.push(3.0); input }).collect();
+    inputs.push(inputs[1].clone());
+    inputs.push(Input::new(inputs[2].inputs.clone(), Some(Types::Float(-1.0))));
+    inputs.push(Input::new(vec![-0.0, 5.0, 3.0], None));
+    inputs.push(Input::new(vec![0.0, 5.0, 3.0], None));
+    let report = Dataset::new(inputs).audit();
+    assert_eq!(report.duplicates, vec![vec![1, 4], vec![6, 7]]);
+    assert_eq!(report.conflicts, vec![vec![2, 5]]);
+    assert_eq!(report.constant_features, vec![2]);
+    assert!(!report.is_clean());
+    assert!(report.to_string().contains("Conflicting groups: 1 (2 inputs)"));
+}
+
+#[test]
+fn dataset_description() {
+    let empty = Dataset::new(vec![]).describe();
+    assert_eq!((empty.rows, empty.features.len(), empty.categories.len()), (0, 0, 0));
+
+    let data = Dataset::new(vec![
+        Input::new(vec![2.0, f32::NAN, 1.0], Some(Types::String("a".to_string()))),
+        Input::new(vec![4.0, f32::NAN, 1.0], Some(Types::String("b".to_string()))),
+        Input::new(vec![6.0, f32::NAN], Some(Types::String("a".to_string()))),
+        Input::new(vec![8.0, f32::NAN, 1.0], None)
+    ]);
+    let summary = data.describe();
+    assert_eq!(summary.rows, 4);
+    assert_eq!((summary.features[0].min, summary.features[0].max, summary.features[0].mean), (2.0, 8.0, 5.0));
+    assert!((summary.features[0].std - 5.0f32.sqrt()).abs() < 1e-5);
+    assert_eq!(summary.features[1], crate::dataset::FeatureSummary { min: 0.0, max: 0.0, mean: 0.0, std: 0.0, missing: 4 });
+    assert_eq!((summary.features[2].missing, summary.features[2].std), (1, 0.0));
+    assert_eq!(summary.categories, vec![(Types::String("a".to_string()), 2), (Types::String("b".to_string()), 1)]);
+    assert_eq!(summary.unanswered, 1);
+    let text = summary.to_string();
+    assert!(text.starts_with("4 rows, 3 features"));
+    assert!(text.contains("No answer"));
+}
+
+#[test]
+fn preprocessing_pipeline() {
+    use crate::pipeline::{Pipeline, Imputer, Imputation, Scaling};
+
+    let gate = |a: bool, b: bool| Types::String(if a != b { "different" } else { "same" }.to_string());
+    let side = |on: bool| Some(Types::String(if on { "on" } else { "off" }.to_string()));
+    let mut rows: Vec<Vec<Option<Types>>> = vec![];
+    let mut answers: Vec<Types> = vec![];
+    for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+        rows.push(vec![side(a), Some(Types::Integer(b as i32 * 10)), Some(Types::Float(1.0))]);
+        answers.push(gate(a, b));
+    }
+    rows.push(vec![None, Some(Types::Integer(10)), None]);
+    answers.push(gate(false, true));
+
+    assert!(matches!(Pipeline::new(TrainingConfig::default()).fit(&rows, &answers), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(Pipeline::new(TrainingConfig::default()).fit(&rows, &answers[1..]), Err(DarjeelingError::InvalidTrainingData(_))));
+    let unfit = Pipeline::new(TrainingConfig::default());
+    assert!(matches!(unfit.predict(&rows[0]), Err(DarjeelingError::InvalidInput(_))));
+
+    let mut pipeline = Pipeline::new(TrainingConfig::default().with_hidden_num(6).with_learning_rate(1.0).with_target_err_percent(100.0))
+        .with_imputation(Imputation::MostFrequent)
+        .with_scaling(Scaling::MinMax)
+        .with_max_epochs(3000);
+    pipeline.fit(&rows, &answers).unwrap();
+    let net = pipeline.network().unwrap();
+    // The side column is one-hot encoded into 2 sensors, next to the 2 numeric columns
+    assert_eq!(net.input_size(), 4);
+    assert!(net.scaler().is_some());
+
+    let path = std::env::temp_dir().join("darjeeling_pipeline_test.json");
+    pipeline.write(path.to_str().unwrap()).unwrap();
+    let read = Pipeline::read(path.to_str().unwrap());
+    let _ = fs::remove_file(&path);
+    let read = read.unwrap();
+    for row in &rows {
+        assert_eq!(read.predict(row).unwrap(), pipeline.predict(row).unwrap());
+    }
+    assert!(matches!(read.predict(&[side(true), Some(Types::String("ten".to_string())), None]), Err(DarjeelingError::UnknownCategory(_)) | Err(DarjeelingError::UnencodedColumn(_))));
+
+    let numbers = vec![vec![Some(Types::Float(1.0))], vec![Some(Types::Float(2.0))], vec![Some(Types::Float(9.0))], vec![None]];
+    assert_eq!(Imputer::fit(&numbers, Imputation::Median).impute(&[None]).unwrap(), vec![Types::Float(2.0)]);
+    assert!(Imputer::fit(&numbers, Imputation::Mean).impute(&[None, None]).is_err());
+}
+
+#[test]
+fn training_data_fingerprint() {
+    use crate::{dataset::DataFingerprint, hash};
+
+    assert_eq!(hash::to_hex(&hash::sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    assert_eq!(hash::to_hex(&hash::sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    assert_eq!(hash::to_hex(&hash::sha256(&[b'a'; 64])), "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb");
+
+    let mut data: Vec<Input> = xor_file();
+    let fingerprint = DataFingerprint::of(&data);
+    let mut net = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    net.learn(&mut data, vec![], 0.5, "fingerprinted", 50.0, false).unwrap();
+    // Training shuffles the data, which doesn't change the fingerprint
+    assert_eq!(net.data_fingerprint(), Some(&fingerprint));
+    assert_eq!((fingerprint.rows, fingerprint.features), (4, 2));
+
+    let read = CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).unwrap();
+    assert!(read.data_fingerprint().unwrap().matches(&xor_file()));
+    let mut changed: Vec<Input> = xor_file();
+    changed[0].inputs[0] += 0.5;
+    assert!(!read.data_fingerprint().unwrap().matches(&changed));
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn signed_models() {
+    use crate::{hash, signing::{ModelSigner, ModelVerifier}};
+
+    // RFC 4231 test case 2
+    assert_eq!(hash::to_hex(&hash::hmac_sha256(b"Jefe", b"what do ya want for nothing?")), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    // RFC 8032 section 7.1, test 1
+    let rfc_signer = ModelSigner::new(&<[u8; 32]>::try_from(hash::from_hex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60").unwrap()).unwrap());
+    assert_eq!(hash::to_hex(&rfc_signer.public_key()), "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    assert_eq!(rfc_signer.sign(""), "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b");
+
+    let signer = ModelSigner::generate();
+    let verifier = ModelVerifier::new(&signer.public_key()).unwrap();
+    assert_eq!(verifier, ModelSigner::new(&signer.secret_key()).verifier());
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["yes", "no"]));
+    let dir = std::env::temp_dir().join("darjeeling_signing_test");
+    net.set_output_dir(Some(dir.clone()));
+    let signed_name: String = net.write_signed_model("signed", &signer).unwrap();
+    let unsigned_name: String = net.write_model("unsigned").unwrap();
+
+    let read = CatNetwork::read_signed_model(signed_name.clone(), &verifier);
+    let wrong_key = CatNetwork::read_signed_model(signed_name.clone(), &ModelSigner::generate().verifier());
+    let unsigned = CatNetwork::read_signed_model(unsigned_name, &verifier);
+    // Readers that don't check signatures can still load signed models
+    let ignored = CatNetwork::read_model(signed_name.clone());
+    let signed: String = fs::read_to_string(&signed_name).unwrap();
+    let _ = fs::remove_dir_all(&dir);
+
+    assert_eq!(read.unwrap().to_darj_string().unwrap(), net.to_darj_string().unwrap());
+    assert!(matches!(wrong_key, Err(DarjeelingError::InvalidSignature(_))));
+    assert!(matches!(unsigned, Err(DarjeelingError::InvalidSignature(_))));
+    assert_eq!(ignored.unwrap().to_darj_string().unwrap(), net.to_darj_string().unwrap());
+    assert!(matches!(verifier.check(&signed.replacen("sigmoid", "tanh", 1)), Err(DarjeelingError::InvalidSignature(_))));
+    assert!(matches!(verifier.check(&format!("{}00", signed.trim_end())), Err(DarjeelingError::InvalidSignature(_))));
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn encrypted_models() {
+    use crate::{cipher, encryption::ModelCipher, hash};
+
+    // RFC 8439 section 2.4.2
+    let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+    let nonce: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+    let encrypted = cipher::chacha20(&key, &nonce, b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.");
+    assert_eq!(encrypted[..16], [0x6e, 0x2e, 0x35, 0x9a, 0x25, 0x68, 0xf9, 0x80, 0x41, 0xba, 0x07, 0x28, 0xdd, 0x0d, 0x69, 0x81]);
+    assert_eq!(encrypted[encrypted.len() - 2..], [0x87, 0x4d]);
+
+    let cipher = ModelCipher::new([3; 32]);
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["yes", "no"]));
+    let dir = std::env::temp_dir().join("darjeeling_encryption_test");
+    net.set_output_dir(Some(dir.clone()));
+    let encrypted_name: String = net.write_encrypted_model("encrypted", &cipher).unwrap();
+    let contents: String = fs::read_to_string(&encrypted_name).unwrap();
+    let read = CatNetwork::read_encrypted_model(encrypted_name.clone(), &cipher);
+    let wrong_key = CatNetwork::read_encrypted_model(encrypted_name.clone(), &ModelCipher::from_passphrase("guess"));
+    let plain = CatNetwork::read_model(encrypted_name);
+    let _ = fs::remove_dir_all(&dir);
+
+    assert!(!contents.contains("sigmoid") && !contents.contains("yes"));
+    assert_eq!(read.unwrap().to_darj_string().unwrap(), net.to_darj_string().unwrap());
+    assert!(matches!(wrong_key, Err(DarjeelingError::DecryptionFailed(_))));
+    assert!(plain.is_err());
+    // Every encryption uses a new nonce
+    assert_ne!(cipher.encrypt("same model"), cipher.encrypt("same model"));
+    let tampered: String = contents.replacen('0', "1", 1);
+    assert!(matches!(cipher.decrypt(&tampered), Err(DarjeelingError::DecryptionFailed(_))));
+
+    // RFC 7914 section 11 and the matching vectors with the same password and salt
+    assert_eq!(hash::to_hex(&hash::pbkdf2_sha256(b"passwd", b"salt", 1)), "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc");
+    assert_eq!(hash::to_hex(&hash::pbkdf2_sha256(b"password", b"salt", 2)), "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43");
+    assert_eq!(hash::to_hex(&hash::pbkdf2_sha256(b"password", b"salt", 4096)), "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a");
+
+    let phrased: ModelCipher = ModelCipher::from_passphrase("correct horse battery staple").with_iterations(1000);
+    let first: String = phrased.encrypt("same model");
+    let second: String = phrased.encrypt("same model");
+    let header = |encrypted: &str| encrypted.lines().next().unwrap().to_string();
+    assert!(header(&first).starts_with("darj-encrypted 1 pbkdf2-sha256 1000 "));
+    // Every encryption uses a new salt
+    assert_ne!(header(&first), header(&second));
+    // The iteration count is read from the header
+    assert_eq!(ModelCipher::from_passphrase("correct horse battery staple").decrypt(&first).unwrap(), "same model");
+    assert!(matches!(ModelCipher::from_passphrase("wrong horse battery staple").decrypt(&first), Err(DarjeelingError::DecryptionFailed(_))));
+    assert!(matches!(cipher.decrypt(&first), Err(DarjeelingError::DecryptionFailed(_))));
+    let salt: String = header(&first).rsplit(' ').next().unwrap().to_string();
+    let resalted: String = first.replacen(&salt, &"0".repeat(salt.len()), 1);
+    assert!(matches!(phrased.decrypt(&resalted), Err(DarjeelingError::DecryptionFailed(_))));
+}
+
+#[test]
+fn model_hot_reload() {
+    use crate::reload::ModelHandle;
+    use std::sync::Arc;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ModelHandle>();
+
+    let dir = std::env::temp_dir().join("darjeeling_reload_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let served: std::path::PathBuf = dir.join("served.darj");
+    let categories: Vec<Types> = vec![Types::Boolean(false), Types::Boolean(true)];
+    let mut small = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    small.set_categories(categories.clone());
+    let mut large = CatNetwork::new(2, 5, 2, 1, ActivationFunction::Sigmoid);
+    large.set_categories(categories.clone());
+    fs::write(&served, small.to_darj_string().unwrap()).unwrap();
+
+    let handle: Arc<ModelHandle> = Arc::new(ModelHandle::watch(&served).unwrap());
+    let in_flight: Arc<CatNetwork> = handle.get();
+    assert!(!handle.reload_if_changed().unwrap());
+
+    fs::write(&served, large.to_darj_string().unwrap()).unwrap();
+    assert!(handle.reload_if_changed().unwrap());
+    assert_eq!(handle.version(), 1);
+    assert_eq!(handle.get().to_darj_string().unwrap(), large.to_darj_string().unwrap());
+    // Requests that already took the old model finish with it
+    assert_eq!(in_flight.to_darj_string().unwrap(), small.to_darj_string().unwrap());
+
+    // A broken file keeps the current model
+    fs::write(&served, "not a model").unwrap();
+    assert!(handle.reload_if_changed().is_err());
+    assert_eq!(handle.get().to_darj_string().unwrap(), large.to_darj_string().unwrap());
+
+    // So does a model that can't answer the same requests
+    fs::write(&served, CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid).to_darj_string().unwrap()).unwrap();
+    assert!(matches!(handle.reload_if_changed(), Err(DarjeelingError::InvalidInput(_))));
+    assert!(!handle.reload_if_changed().unwrap());
+    let mut wider = CatNetwork::new(3, 3, 2, 1, ActivationFunction::Sigmoid);
+    wider.set_categories(categories);
+    fs::write(&served, wider.to_darj_string().unwrap()).unwrap();
+    assert!(matches!(handle.reload_if_changed(), Err(DarjeelingError::ArchitectureMismatch(_))));
+    assert_eq!(handle.get().to_darj_string().unwrap(), large.to_darj_string().unwrap());
+    assert_eq!(handle.version(), 1);
+
+    let previous: Arc<CatNetwork> = handle.swap(small.clone());
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(previous.to_darj_string().unwrap(), large.to_darj_string().unwrap());
+    assert_eq!(handle.get().to_darj_string().unwrap(), small.to_darj_string().unwrap());
+    assert_eq!(handle.version(), 2);
+}
+
+#[test]
+fn inference_benchmark() {
+    let net = CatNetwork::new(3, 6, 2, 1, ActivationFunction::Sigmoid);
+    let mut generated: usize = 0;
+    let report = net.benchmark(200, |i| { generated += 1; vec![i as f32; 3] }).unwrap();
+
+    assert_eq!(generated, 200);
+    assert_eq!(report.samples, 200);
+    assert!(report.p50 <= report.p90 && report.p90 <= report.p99 && report.p99 <= report.max);
+    assert!(report.throughput > 0.0);
+    assert_eq!(report.release, !cfg!(debug_assertions));
+    assert_eq!(report.features.contains(&"signing".to_string()), cfg!(feature = "signing"));
+
+    assert!(matches!(net.benchmark(0, |_| vec![0.0; 3]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.benchmark(5, |_| vec![0.0; 2]), Err(DarjeelingError::InvalidInput(_))));
+}
+
+#[test]
+fn batch_forward_pass() {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut net = CatNetwork::new(3, 4, 2, 2, ActivationFunction::Tanh);
+    net.randomize_weights(&mut rng);
+    let batch: Vec<Input> = (0..5).map(|i| Input::new(vec![i as f32, 1.0 - i as f32, 0.5], None)).collect();
+
+    let outputs: Vec<Vec<f32>> = net.forward_batch(&batch);
+    assert_eq!(outputs.len(), 5);
+    batch.iter().zip(&outputs).for_each(|(input, output)| assert_eq!(output, &net.forward(&input.inputs)));
+    assert!(net.forward_batch(&[]).is_empty());
+}
+
+#[test]
+fn raw_answer_outputs() {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut net = CatNetwork::new(2, 3, 3, 1, ActivationFunction::Sigmoid);
+    net.randomize_weights(&mut rng);
+    net.set_categories(categories_str_format(vec!["a", "b", "c"]));
+
+    let outputs: Vec<f32> = net.raw_outputs(&[0.3, 0.9]);
+    let prediction = net.predict(&[0.3, 0.9]);
+    assert_eq!(outputs, prediction.scores.iter().map(|(_, score)| *score).collect::<Vec<f32>>());
+    let brightest: usize = (0..outputs.len()).fold(0, |best, i| if outputs[i] > outputs[best] { i } else { best });
+    assert_eq!(net.categories().unwrap()[brightest], prediction.category);
+}
+
+#[test]
+fn tie_breaking() {
+    use crate::prediction::TieBreak;
+
+    assert_eq!(TieBreak::First.choose(&[0.2, 0.7, 0.1]).unwrap(), (1, vec![]));
+    assert_eq!(TieBreak::First.choose(&[0.5, 0.7, 0.7]).unwrap(), (1, vec![1, 2]));
+    assert_eq!(TieBreak::First.choose(&[f32::NAN, 0.3]).unwrap(), (1, vec![]));
+    assert!(matches!(TieBreak::Error.choose(&[0.7, 0.7]), Err(DarjeelingError::TiedPrediction(_))));
+    assert_eq!(TieBreak::Error.choose(&[0.7, 0.6]).unwrap(), (0, vec![]));
+    let tied: Vec<f32> = vec![0.4; 8];
+    let picks: Vec<usize> = (0..20).map(|seed| TieBreak::Random { seed }.choose(&tied).unwrap().0).collect();
+    assert_eq!(picks, (0..20).map(|seed| TieBreak::Random { seed }.choose(&tied).unwrap().0).collect::<Vec<usize>>());
+    assert!(picks.iter().any(|pick| *pick != picks[0]));
+
+    // Every weight zeroed makes every answer node output the same
+    let mut net = CatNetwork::new(2, 2, 3, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["a", "b", "c"]));
+    let zeroed: String = net.to_darj_string().unwrap().lines()
+        .map(|line| match line.contains(';') {
+            true => line.split(';').map(|part| part.split(',').map(|weight| if weight.is_empty() { "" } else { "0" }).collect::<Vec<&str>>().join(",")).collect::<Vec<String>>().join(";"),
+            false => line.to_string()
+        })
+        .collect::<Vec<String>>().join("\n");
+    let mut net = CatNetwork::from_darj_str(&zeroed).unwrap();
+
+    let prediction = net.predict(&[0.1, 0.2]);
+    assert!(prediction.is_tie());
+    assert_eq!(prediction.tied.len(), 3);
+    assert_eq!(prediction.category, net.categories().unwrap()[0]);
+    net.set_tie_break(TieBreak::Error);
+    assert!(matches!(net.try_predict(&[0.1, 0.2]), Err(DarjeelingError::TiedPrediction(_))));
+    assert_eq!(net.predict(&[0.1, 0.2]).category, net.categories().unwrap()[0]);
+}
+
+#[test]
+fn node_accessors() {
+    use crate::node::{Node, Initialization};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut node = Node::new(&vec![0.3, -0.2], Some(0.1));
+    assert_eq!(node.link_weights(), &[0.3, -0.2]);
+    assert_eq!((node.links(), node.b_weight(), node.category()), (2, Some(0.1), None));
+    node.set_category(Some(Types::String("on".to_string())));
+    assert_eq!(node.category(), Some(&Types::String("on".to_string())));
+
+    assert!(matches!(node.set_weights(vec![1.0, f32::INFINITY], 0.0), Err(DarjeelingError::InvalidInput(_))));
+    assert_eq!(node.link_weights(), &[0.3, -0.2]);
+    node.set_weights(vec![0.0; 27], 0.5).unwrap();
+    assert_eq!(node.links(), 27);
+    assert_eq!(node.output_for(&[1.0; 27], &ActivationFunction::Linear), 1.0);
+
+    let mut rng = StdRng::seed_from_u64(11);
+    node.randomize(&mut rng, Initialization::LeCun);
+    assert_eq!(node.b_weight(), Some(0.0));
+    assert!(node.link_weights().iter().all(|weight| weight.abs() <= (1.0_f32 / 9.0).sqrt()));
+    node.randomize(&mut rng, Initialization::Uniform { limit: 0.0 });
+    assert!(node.link_weights().iter().all(|weight| *weight == 0.0));
+    node.randomize(&mut rng, Initialization::Standard);
+    assert!(node.link_weights().iter().chain([node.b_weight().unwrap()].iter()).all(|weight| weight.abs() <= 0.5));
+
+    // The serialized form keeps the old field names
+    let json: String = serde_json::to_string(&node).unwrap();
+    assert!(json.contains("\"link_weights\"") && json.contains("\"b_weight\""));
+    let read: Node = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.link_weights(), node.link_weights());
+
+    // What used to be assigned to the public fields still can be, through deprecated setters
+    #[allow(deprecated)]
+    {
+        node.set_link_weights(vec![0.25, 0.5]);
+        node.set_links(2);
+        node.set_link_vals(vec![None; 2]);
+        node.set_b_weight(Some(1.0));
+        node.set_err_sig(Some(0.5));
+        node.set_correct_answer(Some(1.0));
+        node.set_cached_output(Some(0.75));
+        assert_eq!(node.output_for(&[1.0, 2.0], &ActivationFunction::Linear), 2.0 * (0.25 + 1.0 + 1.0));
+        assert_eq!((node.err_sig(), node.correct_answer(), node.cached_output()), (Some(0.5), Some(1.0), Some(0.75)));
+        assert_eq!(node.link_vals(), &[None, None]);
+    }
+}
+
+#[test]
+fn activation_function_names() {
+    assert_eq!(ActivationFunction::all().len(), 3);
+    ActivationFunction::all().iter().for_each(|function| {
+        assert_eq!(function.to_string().to_uppercase().parse::<ActivationFunction>().unwrap(), *function);
+        assert_eq!(ActivationFunction::try_from(function.to_string().as_str()).unwrap(), *function);
+    });
+    assert!(matches!("relu".parse::<ActivationFunction>(), Err(DarjeelingError::UnknownActivationFunction(_))));
+
+    let net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Tanh);
+    let serialized: String = net.to_darj_string().unwrap();
+    assert!(CatNetwork::from_darj_str(&serialized.replace("\ntanh", "\nTanh")).is_ok());
+    let unknown = CatNetwork::from_darj_str(&serialized.replace("\ntanh", "\nrelu"));
+    assert!(matches!(unknown, Err(DarjeelingError::ModelParse { token, .. }) if token == "relu"));
+    let unknown = GenNetwork::from_darj_str(&GenNetwork::new(2, 2, 2, 1, ActivationFunction::Tanh).to_darj_string().replace("\ntanh", "\nrelu"));
+    assert!(matches!(unknown, Err(DarjeelingError::ModelParse { .. })));
+}
+
+#[test]
+fn strict_model_parsing() {
+    let parse_error = |serialized: &str| match CatNetwork::from_darj_str(serialized) {
+        Err(DarjeelingError::ModelParse { line, column, token, .. }) => (line, column, token),
+        other => panic!("Expected a parse error, got {:?}", other.map(|_| ()))
+    };
+
+    assert_eq!(parse_error(";0.1\n;0.2\nlb\n0.5,abc;0.1\nlb\n0.3;0.0\nlb\nsigmoid"), (4, 5, "abc".to_string()));
+    assert_eq!(parse_error(";0.1\nlb\n0.5;0.1;0.2\nlb\nsigmoid"), (3, 4, "0.5;0.1;0.2".to_string()));
+    assert_eq!(parse_error(";0.1\nlb\n0.5;NaN\nlb\nsigmoid"), (3, 5, "NaN".to_string()));
+    assert_eq!(parse_error(";0.1\nlb\n0.5 0.1\nlb\nsigmoid"), (3, 1, "0.5 0.1".to_string()));
+    assert_eq!(parse_error("0.4;0.1\nlb\n0.5;0.1\nlb\nsigmoid"), (1, 1, "0.4".to_string()));
+    assert_eq!(parse_error(";0.1\nlb\n0.5;0.1\nlb\nsigmoid\ncategories [\"a\""), (6, 15, "[\"a\"".to_string()));
+    let message: String = CatNetwork::from_darj_str(";0.1\nlb\n0.5,abc;0.1").unwrap_err().to_string();
+    assert!(message.contains("line 3") && message.contains("column 5") && message.contains("weight"));
+
+    let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    assert!(CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).is_ok());
+}
+
+#[test]
+fn model_architecture_validation() {
+    let invalid = |serialized: &str| matches!(CatNetwork::from_darj_str(serialized), Err(DarjeelingError::InvalidArchitecture(_)));
+
+    // Two sensors, but the answer node only has one link
+    assert!(invalid(";0.1\n;0.2\nlb\n0.5;0.1\nlb\nsigmoid"));
+    assert!(invalid(";0.1\nlb\nsigmoid"));
+    assert!(invalid("sigmoid"));
+    assert!(invalid(";0.1\nlb\nlb\n0.5;0.1\nlb\nsigmoid"));
+    // The answer layer doesn't end with lb
+    assert!(invalid(";0.1\nlb\n0.5;0.1\nsigmoid"));
+    assert!(matches!(CatNetwork::from_darj_str(";0.1\nlb\n0.5;0.1\nlb"), Err(DarjeelingError::ActivationFunctionNotRead(_))));
+    assert!(matches!(GenNetwork::from_darj_str(";0.1\nlb\n0.5;0.1\nlb"), Err(DarjeelingError::ActivationFunctionNotRead(_))));
+    assert!(matches!(GenNetwork::from_darj_str(";0.1\n;0.2\nlb\n0.5;0.1\nlb\nsigmoid"), Err(DarjeelingError::InvalidArchitecture(_))));
+
+    let net = CatNetwork::from_darj_str(";0.1\n;0.2\nlb\n0.5,0.5;0.1\n-0.5,0.5;0.1\nlb\n0.5,0.5;0.1\nlb\nsigmoid").unwrap();
+    assert_eq!(net.raw_outputs(&[1.0, 0.0]).len(), 1);
+}
+
+#[test]
+fn dataset_compatibility() {
+    use crate::scaler::Scaler;
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["cat", "dog"]));
+    let answered = |values: Vec<f32>, answer: &str| Input::new(values, Some(Types::String(answer.to_string())));
+    let data: Vec<Input> = vec![answered(vec![0.1, 0.2], "cat"), answered(vec![0.3, 0.4], "dog"), Input::new(vec![0.5, 0.6], None)];
+
+    assert!(net.check_compatibility(&data).is_ok());
+    assert!(net.check_compatibility(&[Input::new(vec![0.0, 1.0], Some(Types::Vector(vec![0.0, 1.0])))]).is_ok());
+    assert!(matches!(net.check_compatibility(&[answered(vec![0.1], "cat")]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.check_compatibility(&[answered(vec![0.1, f32::NAN], "cat")]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.check_compatibility(&[answered(vec![0.1, 0.2], "bird")]), Err(DarjeelingError::UnknownCategory(message)) if message.contains("bird")));
+    assert!(matches!(net.check_compatibility(&[Input::new(vec![0.0, 1.0], Some(Types::Vector(vec![1.0])))]), Err(DarjeelingError::UnknownCategory(_))));
+
+    net.set_scaler(Some(Scaler::fit_min_max(&[Input::new(vec![0.0, 1.0, 2.0], None)])));
+    assert!(matches!(net.check_compatibility(&data), Err(DarjeelingError::ArchitectureMismatch(_))));
+}
+
+#[test]
+fn pipeline_rewiring_on_load() {
+    use crate::pipeline::{Pipeline, Imputer, Imputation, Scaling};
+
+    let colour = |red: bool| Some(Types::String(if red { "red" } else { "blue" }.to_string()));
+    let rows: Vec<Vec<Option<Types>>> = (0..6).map(|i| vec![colour(i % 2 == 0), Some(Types::Float(i as f32 * 2.0))]).collect();
+    let answers: Vec<Types> = (0..6).map(|i| Types::Boolean(i % 2 == 0)).collect();
+    let mut pipeline = Pipeline::new(TrainingConfig::default().with_hidden_num(3))
+        .with_imputation(Imputation::Mean)
+        .with_scaling(Scaling::MinMax)
+        .with_max_epochs(5);
+    pipeline.fit(&rows, &answers).unwrap();
+    assert_eq!(pipeline.columns(), 2);
+
+    let read = Pipeline::from_json_str(&pipeline.to_json_string().unwrap()).unwrap();
+    assert_eq!(read.columns(), 2);
+    // Red is one-hot encoded first, and 10 is scaled between the smallest and largest values, 0 and 10
+    assert_eq!(read.transform(&[colour(true), Some(Types::Float(10.0))]).unwrap().inputs, vec![1.0, 0.0, 1.0]);
+    assert_eq!(read.transform(&[colour(false), None]).unwrap().inputs, vec![0.0, 1.0, 0.5]);
+    assert!(matches!(read.predict(&[colour(true)]), Err(DarjeelingError::InvalidInput(_))));
+
+    // A .darj model keeps its scaler and encoders, so it predicts raw rows the same way
+    let net = CatNetwork::from_darj_str(&pipeline.network().unwrap().to_darj_string().unwrap()).unwrap();
+    let wrapped = Pipeline::from_network(net).unwrap();
+    rows.iter().for_each(|row| assert_eq!(wrapped.predict(row).unwrap().scores, pipeline.predict(row).unwrap().scores));
+    assert!(wrapped.predict(&[colour(true), None]).is_err());
+    let wrapped = wrapped.with_imputer(Imputer::fit(&rows, Imputation::Mean)).unwrap();
+    assert_eq!(wrapped.predict(&[colour(true), None]).unwrap(), pipeline.predict(&[colour(true), None]).unwrap());
+    assert!(matches!(Pipeline::from_network(CatNetwork::new(3, 2, 2, 1, ActivationFunction::Sigmoid)).unwrap().with_imputer(Imputer::fit(&rows, Imputation::Mean)), Err(DarjeelingError::InvalidMetadataRead(_))));
+
+    let mut tampered: serde_json::Value = serde_json::from_str(&pipeline.to_json_string().unwrap()).unwrap();
+    tampered["imputer"]["fills"].as_array_mut().unwrap().pop();
+    assert!(matches!(Pipeline::from_json_str(&tampered.to_string()), Err(DarjeelingError::InvalidMetadataRead(_))));
+}
+
+#[test]
+fn cascade_inference() {
+    use crate::cascade::Cascade;
+
+    let categorized = |hidden: i32| {
+        let mut net = CatNetwork::new(2, hidden, 2, 1, ActivationFunction::Sigmoid);
+        net.set_categories(categories_str_format(vec!["no", "yes"]));
+        net
+    };
+    let data: Vec<Input> = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::String("yes".to_string()))),
+        Input::new(vec![1.0, 1.0], Some(Types::String("no".to_string()))),
+        Input::new(vec![0.5, 0.5], None)
+    ];
+
+    assert!(matches!(Cascade::new(vec![]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(Cascade::new(vec![(categorized(2), 1.5)]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(Cascade::new(vec![(categorized(2), 0.5), (CatNetwork::new(3, 2, 2, 1, ActivationFunction::Sigmoid), 0.0)]), Err(DarjeelingError::InvalidInput(_))));
+
+    // Nothing is ever fully confident, so everything goes past the first stage
+    let (cheap, expensive) = (categorized(2), categorized(6));
+    let cascade = Cascade::new(vec![(cheap.clone(), 1.0), (expensive.clone(), 0.0)]).unwrap();
+    let answer = cascade.predict(&[0.0, 1.0]);
+    assert_eq!((answer.stage, answer.confident), (1, true));
+    assert_eq!(answer.prediction, expensive.predict(&[0.0, 1.0]));
+    let report = cascade.evaluate(&data);
+    assert_eq!(report.answered_by, vec![0, 2]);
+
+    let cascade = Cascade::new(vec![(cheap.clone(), 0.0), (expensive, 0.0)]).unwrap();
+    assert_eq!(cascade.predict(&[0.0, 1.0]).prediction, cheap.predict(&[0.0, 1.0]));
+    assert_eq!(cascade.evaluate(&data).answered_by, vec![2, 0]);
+
+    let unsure = Cascade::new(vec![(cheap, 1.0)]).unwrap().predict(&[0.0, 1.0]);
+    assert_eq!((unsure.stage, unsure.confident), (0, false));
+}
+
+#[test]
+fn one_vs_rest_classification() {
+    use crate::{config::TrainingConfig, multiclass::OneVsRest};
+
+    let point = |x: f32, y: f32, category: &str| Input::new(vec![x, y], Some(Types::String(category.to_string())));
+    let data: Vec<Input> = vec![
+        point(0.0, 0.0, "low"), point(0.1, 0.0, "low"),
+        point(1.0, 1.0, "high"), point(0.9, 1.0, "high"),
+        point(0.0, 1.0, "left"), point(0.0, 0.9, "left")
+    ];
+    let config = TrainingConfig::new(4, 1, ActivationFunction::Sigmoid, 1.0, 100.0);
+
+    assert!(matches!(OneVsRest::train(&[], &config, 10, false), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(OneVsRest::train(&data[..2], &config, 10, false), Err(DarjeelingError::InvalidTrainingData(_))));
+    let mut unanswered: Vec<Input> = data.clone();
+    unanswered.push(Input::new(vec![0.5, 0.5], None));
+    assert!(matches!(OneVsRest::train(&unanswered, &config, 10, false), Err(DarjeelingError::InvalidTrainingData(_))));
+
+    let classifier = OneVsRest::train(&data, &config, 2000, true).unwrap();
+    assert_eq!(classifier.categories(), &categories_str_format(vec!["high", "left", "low"])[..]);
+    assert_eq!(classifier.members().len(), 3);
+    assert!(classifier.members().iter().all(|member| member.categories() == Some(vec![Types::Boolean(false), Types::Boolean(true)])));
+
+    let prediction = classifier.predict(&[0.0, 1.0]);
+    assert_eq!(prediction.scores.len(), 3);
+    assert_eq!(prediction.category, Types::String("left".to_string()));
+    assert!(classifier.evaluate(&data).accuracy > 0.8);
+}
+
+#[test]
+fn hierarchical_classification() {
+    use crate::{config::TrainingConfig, hierarchy::HierarchicalClassifier};
+
+    let label = |name: &str| Types::String(name.to_string());
+    let point = |x: f32, y: f32, category: &str| Input::new(vec![x, y], Some(label(category)));
+    let tree: Vec<(Types, Types)> = vec![
+        (label("cat"), label("animal")), (label("dog"), label("animal")),
+        (label("car"), label("vehicle"))
+    ];
+    let data: Vec<Input> = vec![
+        point(0.0, 0.0, "cat"), point(0.1, 0.0, "cat"),
+        point(0.0, 1.0, "dog"), point(0.1, 1.0, "dog"),
+        point(1.0, 0.5, "car"), point(0.9, 0.5, "car"), point(1.0, 0.0, "car"), point(1.0, 1.0, "car"),
+        point(0.0, 0.5, "animal")
+    ];
+    let config = TrainingConfig::new(8, 1, ActivationFunction::Sigmoid, 2.0, 100.0);
+
+    assert!(matches!(HierarchicalClassifier::train(&[], &tree, &config, 10, false), Err(DarjeelingError::InvalidTrainingData(_))));
+    let twice: Vec<(Types, Types)> = vec![(label("cat"), label("animal")), (label("cat"), label("vehicle"))];
+    assert!(matches!(HierarchicalClassifier::train(&data, &twice, &config, 10, false), Err(DarjeelingError::InvalidInput(_))));
+    let cycle: Vec<(Types, Types)> = vec![(label("cat"), label("animal")), (label("animal"), label("cat"))];
+    assert!(matches!(HierarchicalClassifier::train(&data, &cycle, &config, 10, false), Err(DarjeelingError::InvalidInput(_))));
+
+    let classifier = HierarchicalClassifier::train(&data, &tree, &config, 2000, true).unwrap();
+    assert_eq!(classifier.parent(&label("dog")), Some(&label("animal")));
+    assert_eq!(classifier.parent(&label("animal")), None);
+    // The top, animal and vehicle, which only has cars so it has no network
+    assert_eq!(classifier.branches().len(), 3);
+    let vehicle = classifier.branches().iter().find(|branch| branch.parent() == Some(&label("vehicle"))).unwrap();
+    assert_eq!((vehicle.children(), vehicle.network().is_none()), (&[label("car")][..], true));
+    let animal = classifier.branches().iter().find(|branch| branch.parent() == Some(&label("animal"))).unwrap();
+    assert_eq!(animal.children(), &[label("cat"), label("dog")][..]);
+
+    // Every path goes all the way down the tree, through each category's parent
+    for input in &data {
+        let path: Vec<Types> = classifier.predict_path(input);
+        assert_eq!(path.len(), 2);
+        assert_eq!(classifier.parent(&path[1]), Some(&path[0]));
+    }
+}
+
+#[test]
+fn ordinal_regression() {
+    use crate::{config::TrainingConfig, metrics::OrdinalMetrics, ordinal::OrdinalNetwork};
+
+    let levels: Vec<Types> = (1..=4).map(Types::Integer).collect();
+    let data: Vec<Input> = (0..12).map(|i| {
+        let x: f32 = i as f32 / 11.0;
+        Input::new(vec![x], Some(Types::Integer(1 + (x * 3.999) as i32)))
+    }).collect();
+    let config = TrainingConfig::new(4, 1, ActivationFunction::Sigmoid, 1.0, 100.0);
+
+    assert!(matches!(OrdinalNetwork::train(&data, vec![Types::Integer(1)], &config, 10), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(OrdinalNetwork::train(&data, vec![Types::Integer(1), Types::Integer(1)], &config, 10), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(OrdinalNetwork::train(&data, levels[..3].to_vec(), &config, 10), Err(DarjeelingError::UnknownCategory(_))));
+    assert!(matches!(OrdinalNetwork::train(&[], levels.clone(), &config, 10), Err(DarjeelingError::InvalidTrainingData(_))));
+
+    let rater = OrdinalNetwork::train(&data, levels.clone(), &config, 50).unwrap();
+    assert_eq!(rater.levels(), &levels[..]);
+    assert_eq!(rater.network().output_size(), 3);
+    let cumulative: Vec<f32> = rater.cumulative_probabilities(&[0.5]);
+    assert!(cumulative.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    let prediction = rater.predict(&[0.5]);
+    assert_eq!(prediction.category, levels[rater.predict_level(&[0.5])]);
+    assert_eq!(prediction.scores.len(), 4);
+    assert!((prediction.scores.iter().map(|(_, score)| score).sum::<f32>() - 1.0).abs() < 1e-5);
+    assert_eq!(rater.evaluate(&data).count, 12);
+
+    assert_eq!(OrdinalMetrics::compute(&[0, 1, 3], &[0, 2, 1]), OrdinalMetrics { accuracy: 1.0 / 3.0, mae: 1.0, within_one: 2.0 / 3.0, count: 3 });
+}
+
+#[test]
+fn multi_task_heads() {
+    use crate::{config::TrainingConfig, multitask::{MultiTaskNetwork, Task, TaskInput, TaskOutput, TaskMetrics}};
+
+    let sizes: Vec<Types> = categories_str_format(vec!["small", "large"]);
+    let data: Vec<TaskInput> = (0..10).map(|i| {
+        let x: f32 = i as f32 / 9.0;
+        TaskInput::new(vec![x, 1.0 - x], vec![sizes[(i >= 5) as usize].clone(), Types::Float(10.0 + 20.0 * x)])
+    }).collect();
+    let tasks = || vec![Task::classification("size", sizes.clone(), 1.0), Task::regression("mass", 0.5)];
+    let config = TrainingConfig::new(4, 1, ActivationFunction::Sigmoid, 1.0, 100.0);
+
+    assert!(matches!(MultiTaskNetwork::train(&data, vec![], &config, 10), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(MultiTaskNetwork::train(&data, vec![Task::regression("mass", 1.0), Task::regression("mass", 1.0)], &config, 10), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(MultiTaskNetwork::train(&data, vec![Task::classification("size", sizes.clone(), -1.0), Task::regression("mass", 1.0)], &config, 10), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(MultiTaskNetwork::train(&[TaskInput::new(vec![0.0, 1.0], vec![sizes[0].clone()])], tasks(), &config, 10), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(MultiTaskNetwork::train(&data, vec![Task::classification("size", sizes[..1].to_vec(), 1.0), Task::regression("mass", 1.0)], &config, 10), Err(DarjeelingError::UnknownCategory(_))));
+    assert!(matches!(MultiTaskNetwork::train(&data, vec![Task::regression("size", 1.0)], &config, 10), Err(DarjeelingError::InvalidTrainingData(_))));
+
+    let net = MultiTaskNetwork::train(&data, tasks(), &config, 30).unwrap();
+    assert_eq!(net.network().output_size(), 3);
+    assert_eq!(net.network().output_weights(), &[1.0, 1.0, 0.5]);
+    assert_eq!(net.network().history().len(), 30);
+
+    let outputs = net.predict(&[0.5, 0.5]);
+    assert_eq!(outputs.len(), 2);
+    match &outputs[0] {
+        TaskOutput::Category(prediction) => assert!(sizes.contains(&prediction.category)),
+        output => panic!("The size task predicted {:?}", output)
+    }
+    // Sigmoid outputs scale back to between the smallest and largest masses
+    match net.predict_task(&[0.5, 0.5], "mass") {
+        Some(TaskOutput::Value(mass)) => assert!((10.0..=30.0).contains(&mass)),
+        output => panic!("The mass task predicted {:?}", output)
+    }
+    assert_eq!(net.predict_task(&[0.5, 0.5], "colour"), None);
+
+    let metrics = net.evaluate(&data);
+    assert!(matches!(&metrics[0], TaskMetrics::Classification(metrics) if (0.0..=1.0).contains(&metrics.accuracy)));
+    assert!(matches!(&metrics[1], TaskMetrics::Regression(metrics) if metrics.count == 10));
+}
+
+#[test]
+fn nearest_centroid_baseline() {
+    use crate::{baseline::NearestCentroid, dataset::Dataset};
+
+    let point = |x: f32, y: f32, answer: Option<&str>| Input::new(vec![x, y], answer.map(|answer| Types::String(answer.to_string())));
+    assert!(matches!(NearestCentroid::fit(&Dataset::new(vec![point(0.0, 0.0, None)])), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(NearestCentroid::fit(&Dataset::new(vec![point(0.0, 0.0, Some("a")), Input::new(vec![1.0], Some(Types::String("a".to_string())))])), Err(DarjeelingError::InvalidTrainingData(_))));
+
+    let data = Dataset::new(vec![point(0.0, 0.0, Some("a")), point(0.0, 2.0, Some("a")), point(4.0, 4.0, Some("b")), point(9.0, 9.0, None)]);
+    let baseline = NearestCentroid::fit(&data).unwrap();
+    assert_eq!(baseline.categories(), &categories_str_format(vec!["a", "b"])[..]);
+    assert_eq!(baseline.centroids(), &[vec![0.0, 1.0], vec![4.0, 4.0]]);
+    assert_eq!(baseline.distances(&[0.0, 4.0]), vec![3.0, 4.0]);
+
+    let prediction = baseline.predict(&[0.0, 4.0]);
+    assert_eq!(prediction.category, Types::String("a".to_string()));
+    assert_eq!(prediction.scores[1].1, -4.0);
+    assert!((prediction.confidence - 1.0 / (1.0 + (-1.0f32).exp())).abs() < 1e-5);
+    // The unanswered input is skipped
+    let metrics = baseline.evaluate(data.inputs());
+    assert_eq!(metrics.accuracy, 1.0);
+}
+
+#[test]
+fn bootstrapped_metrics() {
+    use crate::metrics::BootstrapReport;
+
+    let truth: Vec<Types> = (0..20).map(|i| Types::Integer(i % 2)).collect();
+    let predictions: Vec<Types> = (0..20).map(|i| Types::Integer(if i < 15 { i % 2 } else { 1 - i % 2 })).collect();
+    assert!(matches!(BootstrapReport::compute(&predictions, &truth, 0, 0.95, 1), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(BootstrapReport::compute(&predictions, &truth, 100, 1.0, 1), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(BootstrapReport::compute(&[], &[], 100, 0.95, 1), Err(DarjeelingError::InvalidTrainingData(_))));
+
+    let report = BootstrapReport::compute(&predictions, &truth, 500, 0.9, 7).unwrap();
+    assert_eq!(report.count, 20);
+    assert_eq!(report.accuracy.estimate, 0.75);
+    assert!(report.accuracy.lower < 0.75 && report.accuracy.upper > 0.75);
+    assert!(report.accuracy.lower >= 0.0 && report.accuracy.upper <= 1.0);
+    assert!(report.macro_f1.lower <= report.macro_f1.upper);
+    // The same seed gives the same intervals
+    assert_eq!(BootstrapReport::compute(&predictions, &truth, 500, 0.9, 7).unwrap(), report);
+
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    let data = vec![Input::new(vec![0.0, 1.0], Some(Types::Boolean(true))), Input::new(vec![1.0, 0.0], Some(Types::Boolean(false))), Input::new(vec![1.0, 1.0], None)];
+    let report = net.evaluate_bootstrap(&data, 50, 0.95, 3).unwrap();
+    assert_eq!(report.count, 2);
+    assert_eq!(report.accuracy.estimate, net.evaluate(&data).metrics.accuracy);
+    let uncategorized = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    assert!(matches!(uncategorized.evaluate_bootstrap(&data, 50, 0.95, 3), Err(DarjeelingError::InvalidInput(_))));
+}
+
+#[test]
+fn per_category_breakdown() {
+    use crate::metrics::CategoryBreakdown;
+
+    let [a, b, c] = [Types::Integer(0), Types::Integer(1), Types::Integer(2)];
+    let truth = vec![a.clone(), a.clone(), a.clone(), a.clone(), b.clone(), b.clone(), c.clone()];
+    let predictions = vec![a.clone(), c.clone(), c.clone(), b.clone(), b.clone(), a.clone(), c.clone()];
+    let categories = CategoryBreakdown::compute(&predictions, &truth);
+    assert_eq!(categories.iter().map(|category| category.count).collect::<Vec<usize>>(), vec![4, 2, 1]);
+    assert_eq!(categories[0].confused_with, Some((c.clone(), 2)));
+    assert_eq!(categories[0].recall, 0.25);
+    assert_eq!(categories[1].confused_with, Some((a, 1)));
+    assert_eq!(categories[2].confused_with, None);
+    assert_eq!(categories[2].precision, 1.0 / 3.0);
+    // Right about c for every input but the two a's predicted as c
+    assert_eq!(categories[2].accuracy, 5.0 / 7.0);
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["x", "y"]));
+    let data: Vec<Input> = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::String("x".to_string()))),
+        Input::new(vec![1.0, 0.0], Some(Types::String("y".to_string())))
+    ];
+    let report = net.evaluate(&data);
+    assert_eq!(report.categories.len(), report.metrics.classes.len());
+    assert_eq!(report.categories.iter().map(|category| category.count).sum::<usize>(), 2);
+    assert!(report.to_string().contains("Most confused with"));
+}
+
+#[test]
+fn streaming_predictions() {
+    use crate::prediction::{Prediction, PredictionsExt};
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["a", "b"]));
+    let inputs: Vec<Input> = vec![Input::new(vec![0.0, 1.0], None), Input::new(vec![1.0, 0.0], None), Input::new(vec![1.0, 1.0], None)];
+    let expected: Vec<Prediction> = inputs.iter().map(|input| net.predict(&input.inputs)).collect();
+
+    let predictions = inputs.iter().predictions(&net);
+    assert_eq!(predictions.len(), 3);
+    assert_eq!(predictions.collect::<Vec<Prediction>>(), expected);
+    let rows: Vec<Vec<f32>> = inputs.into_iter().map(|input| input.inputs).collect();
+    assert_eq!(rows.into_iter().predictions(&net).collect::<Vec<Prediction>>(), expected);
+
+    // Nothing is predicted until it's asked for
+    let mut seen: usize = 0;
+    let mut lazy = (0..1000).map(|i| { seen += 1; vec![i as f32, 0.0] }).predictions(&net);
+    lazy.next();
+    lazy.next();
+    assert_eq!(seen, 2);
+}
+
+#[test]
+fn online_evaluation_windows() {
+    use crate::online::OnlineEvaluator;
+
+    assert!(matches!(OnlineEvaluator::new(&[]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(OnlineEvaluator::new(&[5, 0]), Err(DarjeelingError::InvalidInput(_))));
+
+    let mut evaluator = OnlineEvaluator::new(&[10, 4]).unwrap();
+    assert_eq!(evaluator.lifetime_accuracy(), 0.0);
+    assert_eq!(evaluator.window(4).unwrap().count, 0);
+    // Right at first, then every prediction wrong as the model decays
+    for i in 0..12 {
+        let truth = Types::Integer(i % 2);
+        let prediction = if i < 8 { truth.clone() } else { Types::Integer(1 - i % 2) };
+        evaluator.record(prediction, truth);
+    }
+    let windows = evaluator.windows();
+    assert_eq!(windows.iter().map(|window| window.size).collect::<Vec<usize>>(), vec![4, 10]);
+    assert_eq!(windows[0].metrics.accuracy, 0.0);
+    assert_eq!(windows[1].count, 10);
+    assert_eq!(windows[1].metrics.accuracy, 0.6);
+    assert_eq!(evaluator.seen(), 12);
+    assert!((evaluator.lifetime_accuracy() - 8.0 / 12.0).abs() < 1e-6);
+    assert!(evaluator.window(7).is_none());
+
+    evaluator.track("a", Types::Integer(1));
+    evaluator.track("b", Types::Integer(0));
+    assert_eq!(evaluator.pending(), 2);
+    assert!(evaluator.resolve("a", Types::Integer(1)));
+    assert!(!evaluator.resolve("a", Types::Integer(1)));
+    assert!(!evaluator.resolve("c", Types::Integer(1)));
+    assert_eq!(evaluator.pending(), 1);
+    assert_eq!(evaluator.window(4).unwrap().metrics.accuracy, 0.25);
+    assert!(evaluator.to_string().contains("1 pending"));
+}
+
+#[test]
+fn decision_thresholds() {
+    use crate::{cost::CostMatrix, threshold::{DecisionThreshold, ThresholdObjective}};
+
+    // The rare node gets brighter as the input grows, but only passes the common node's 0.5 above about 1.1
+    let mut net = CatNetwork::from_darj_str(";0\nlb\n1;0\nlb\n4;-3\n0;0\nlb\nsigmoid").unwrap();
+    net.set_categories(categories_str_format(vec!["rare", "common"]));
+    let rare = Types::String("rare".to_string());
+    let label = |x: f32| Some(Types::String(if x > 0.5 { "rare" } else { "common" }.to_string()));
+    let validation: Vec<Input> = [-2.0, -1.0, 0.0, 0.4, 0.6, 0.8, 1.0, 2.0].iter().map(|x| Input::new(vec![*x], label(*x))).collect();
+    assert_eq!(net.predict(&[0.8]).category, Types::String("common".to_string()));
+
+    assert!(matches!(net.optimize_threshold(&validation, &Types::String("other".to_string()), &ThresholdObjective::F1), Err(DarjeelingError::UnknownCategory(_))));
+    assert!(matches!(net.optimize_threshold(&[Input::new(vec![0.0], None)], &rare, &ThresholdObjective::F1), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(net.optimize_threshold(&validation, &rare, &ThresholdObjective::Precision(1.5)), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.optimize_threshold(&validation, &rare, &ThresholdObjective::Cost(CostMatrix::new(vec![vec![0.0]]).unwrap())), Err(DarjeelingError::InvalidInput(_))));
+    assert!(net.threshold().is_none());
+
+    let report = net.optimize_threshold(&validation, &rare, &ThresholdObjective::F1).unwrap();
+    assert_eq!((report.precision, report.recall, report.f1), (1.0, 1.0, 1.0));
+    assert_eq!(net.threshold().unwrap().category, rare);
+    assert!(validation.iter().all(|input| net.predict(&input.inputs).category == *input.answer.as_ref().unwrap()));
+    assert!(validation.iter().all(|input| net.predict_category(&input.inputs) == *input.answer.as_ref().unwrap()));
+
+    let read: CatNetwork = CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).unwrap();
+    assert_eq!(read.threshold(), net.threshold());
+    assert_eq!(read.predict(&[0.8]).category, rare);
+
+    let report = net.optimize_threshold(&validation, &rare, &ThresholdObjective::Cost(CostMatrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]).unwrap())).unwrap();
+    assert_eq!(report.cost, Some(0.0));
+
+    // The likeliest input isn't rare, so every threshold that answers rare has a false alarm
+    let mut noisy: Vec<Input> = validation.clone();
+    noisy.push(Input::new(vec![3.0], Some(Types::String("common".to_string()))));
+    assert!(matches!(net.optimize_threshold(&noisy, &rare, &ThresholdObjective::Precision(1.0)), Err(DarjeelingError::InvalidInput(_))));
+    let report = net.optimize_threshold(&noisy, &rare, &ThresholdObjective::Precision(0.8)).unwrap();
+    assert!(report.precision >= 0.8 && report.recall == 1.0);
+
+    assert!(matches!(net.set_threshold(Some(DecisionThreshold::new(Types::Integer(1), 0.5))), Err(DarjeelingError::UnknownCategory(_))));
+    net.set_threshold(None).unwrap();
+    assert!(!net.to_darj_string().unwrap().contains("threshold "));
+    assert_eq!(net.predict(&[0.8]).category, Types::String("common".to_string()));
+}
+
+#[test]
+fn cost_sensitive_decisions() {
+    use crate::cost::CostMatrix;
+
+    assert!(matches!(CostMatrix::new(vec![]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(CostMatrix::new(vec![vec![0.0, 1.0], vec![1.0]]), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(CostMatrix::new(vec![vec![0.0, -1.0], vec![1.0, 0.0]]), Err(DarjeelingError::InvalidInput(_))));
+
+    let costs = CostMatrix::new(vec![vec![0.0, 1.0], vec![20.0, 0.0]]).unwrap();
+    assert_eq!(costs.expected_costs(&[0.8, 0.2]), vec![4.0, 0.8]);
+    assert_eq!(costs.decide(&[0.99, 0.01]), 0);
+    assert_eq!(costs.decide(&[0.9, 0.1]), 1);
+    assert_eq!(costs.average_cost(&[0, 0, 1], &[0, 1, 0]), 7.0);
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["negative", "positive"]));
+    let data: Vec<Input> = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::String("positive".to_string()))),
+        Input::new(vec![1.0, 0.0], Some(Types::String("negative".to_string())))
+    ];
+    assert!(matches!(net.average_cost(&data), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.set_cost_matrix(Some(CostMatrix::new(vec![vec![0.0]]).unwrap())), Err(DarjeelingError::InvalidInput(_))));
+
+    // Answering positive is free, so every prediction is positive whatever the outputs
+    net.set_cost_matrix(Some(CostMatrix::new(vec![vec![0.0, 0.0], vec![1.0, 0.0]]).unwrap())).unwrap();
+    assert!(data.iter().all(|input| net.predict(&input.inputs).category == Types::String("positive".to_string())));
+    assert!(data.iter().all(|input| net.predict_category(&input.inputs) == Types::String("positive".to_string())));
+    assert_eq!(net.average_cost(&data).unwrap(), 0.0);
+
+    let read: CatNetwork = CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).unwrap();
+    assert_eq!(read.cost_matrix(), net.cost_matrix());
+    net.set_cost_matrix(None).unwrap();
+    assert!(!net.to_darj_string().unwrap().contains("costs "));
+}
+
+#[test]
+fn reinforce_from_rewards() {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut policy = CatNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    policy.randomize_weights(&mut rng);
+    policy.set_categories(categories_str_format(vec!["left", "stay", "right"]));
+    let state = [0.3, 0.8];
+
+    assert!(matches!(policy.reinforce(&[0.3], 0, 1.0, 0.1), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(policy.reinforce(&state, 3, 1.0, 0.1), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(policy.reinforce(&state, 0, f32::NAN, 0.1), Err(DarjeelingError::InvalidInput(_))));
+
+    let before: Vec<f32> = policy.raw_outputs(&state);
+    policy.reinforce(&state, 2, 0.0, 0.1).unwrap();
+    assert_eq!(policy.raw_outputs(&state), before);
+
+    policy.reinforce(&state, 2, 1.0, 0.1).unwrap();
+    let rewarded: Vec<f32> = policy.raw_outputs(&state);
+    assert!(rewarded[2] > before[2]);
+    assert!(rewarded[0] < before[0]);
+
+    policy.reinforce(&state, 2, -2.0, 0.1).unwrap();
+    assert!(policy.raw_outputs(&state)[2] < rewarded[2]);
+
+    // Rewarding one action over and over makes it the policy's choice
+    (0..200).for_each(|_| policy.reinforce(&state, 1, 1.0, 0.5).unwrap());
+    assert_eq!(policy.predict(&state).category, Types::String("stay".to_string()));
+}
+
+#[test]
+fn q_learning_helpers() {
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::qlearning::{ReplayBuffer, Transition, EpsilonGreedy, q_learning_step};
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let transition = |state: f32, action: usize, reward: f32| Transition { state: vec![state], action, reward, next_state: vec![state], done: true };
+
+    let mut buffer = ReplayBuffer::new(3);
+    assert!(buffer.sample(4, &mut rng).is_empty());
+    (0..5).for_each(|i| buffer.push(transition(i as f32, 0, 0.0)));
+    assert_eq!((buffer.len(), buffer.capacity()), (3, 3));
+    let sample: Vec<&Transition> = buffer.sample(20, &mut rng);
+    assert_eq!(sample.len(), 20);
+    assert!(sample.iter().all(|transition| transition.state[0] >= 2.0));
+
+    let mut online = CatNetwork::new(1, 4, 2, 1, ActivationFunction::Sigmoid);
+    online.randomize_weights(&mut rng);
+    let mut target = CatNetwork::new(1, 4, 2, 1, ActivationFunction::Sigmoid);
+    target.clone_weights_from(&online).unwrap();
+    assert_eq!(target.raw_outputs(&[0.5]), online.raw_outputs(&[0.5]));
+    assert!(matches!(target.clone_weights_from(&CatNetwork::new(1, 3, 2, 1, ActivationFunction::Sigmoid)), Err(DarjeelingError::ArchitectureMismatch(_))));
+    assert!(matches!(q_learning_step(&mut online, &target, &[&transition(0.5, 2, 1.0)], 0.9, 0.5), Err(DarjeelingError::InvalidInput(_))));
+
+    // Action 1 always pays off and action 0 never does, so action 1 ends up with the larger Q value
+    let mut buffer = ReplayBuffer::new(100);
+    (0..10).for_each(|i| {
+        buffer.push(transition(i as f32 / 10.0, 0, 0.0));
+        buffer.push(transition(i as f32 / 10.0, 1, 1.0));
+    });
+    let first_loss: f32 = q_learning_step(&mut online, &target, &buffer.sample(32, &mut rng), 0.9, 0.5).unwrap();
+    let mut loss: f32 = first_loss;
+    for _ in 0..300 {
+        loss = q_learning_step(&mut online, &target, &buffer.sample(32, &mut rng), 0.9, 0.5).unwrap();
+    }
+    assert!(loss < first_loss);
+    let q_values: Vec<f32> = online.raw_outputs(&[0.5]);
+    assert!(q_values[1] > q_values[0]);
+
+    let greedy = EpsilonGreedy::new(0.0, 0.0, 1.0);
+    assert_eq!(greedy.select(&online, &[0.5], &mut rng), 1);
+    let mut exploring = EpsilonGreedy::new(1.0, 0.1, 0.5);
+    assert!((0..50).map(|_| exploring.select(&online, &[0.5], &mut rng)).any(|action| action == 0));
+    (0..10).for_each(|_| exploring.decay());
+    assert_eq!(exploring.epsilon, 0.1);
+}
+
+#[test]
+fn copying_generation_weights() {
+    let trained = GenNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    let clone: GenNetwork = trained.clone();
+    assert_eq!(clone.to_darj_string(), trained.to_darj_string());
+
+    let mut copy = GenNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    assert_ne!(copy.to_darj_string(), trained.to_darj_string());
+    copy.copy_weights_from(&trained).unwrap();
+    assert_eq!(copy.to_darj_string(), trained.to_darj_string());
+    assert_eq!(copy.interpolate(&[0.0, 0.0], &[1.0, 1.0], 3).unwrap(), trained.interpolate(&[0.0, 0.0], &[1.0, 1.0], 3).unwrap());
+
+    for other in [GenNetwork::new(3, 4, 3, 1, ActivationFunction::Sigmoid), GenNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid), GenNetwork::new(2, 4, 3, 2, ActivationFunction::Sigmoid)] {
+        assert!(matches!(copy.copy_weights_from(&other), Err(DarjeelingError::ArchitectureMismatch(_))));
+    }
+}
+
+#[test]
+fn warm_starting_generation() {
+    let categorizer = CatNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    let mut generator = GenNetwork::new(2, 4, 3, 1, ActivationFunction::Sigmoid);
+    assert_eq!(generator.warm_start_from(&categorizer).unwrap(), vec![1, 2]);
+    let samples = generator.interpolate(&[0.25, 0.75], &[0.25, 0.75], 2).unwrap();
+    assert_eq!(samples[0].output, categorizer.raw_outputs(&[0.25, 0.75]));
+
+    // Only the answer layers line up, reading 4 hidden values and making 2
+    let categorizer = CatNetwork::new(3, 4, 2, 1, ActivationFunction::Sigmoid);
+    let mut generator = GenNetwork::new(2, 4, 2, 2, ActivationFunction::Sigmoid);
+    assert_eq!(generator.warm_start_from(&categorizer).unwrap(), vec![3]);
+
+    let mut generator = GenNetwork::new(2, 5, 2, 1, ActivationFunction::Sigmoid);
+    assert!(matches!(generator.warm_start_from(&categorizer), Err(DarjeelingError::ArchitectureMismatch(_))));
+    let mut generator = GenNetwork::new(3, 4, 2, 1, ActivationFunction::Tanh);
+    assert!(matches!(generator.warm_start_from(&categorizer), Err(DarjeelingError::ArchitectureMismatch(_))));
+}
+
+#[test]
+fn crossover_and_mutation() {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(21);
+    let mut a = CatNetwork::new(2, 6, 2, 1, ActivationFunction::Sigmoid);
+    let mut b = CatNetwork::new(2, 6, 2, 1, ActivationFunction::Sigmoid);
+    a.randomize_weights(&mut rng);
+    b.randomize_weights(&mut rng);
+    assert!(matches!(CatNetwork::crossover(&a, &CatNetwork::new(2, 5, 2, 1, ActivationFunction::Sigmoid), &mut rng), Err(DarjeelingError::ArchitectureMismatch(_))));
+
+    // Every node comes whole from one parent, and both parents are used
+    let child: CatNetwork = CatNetwork::crossover(&a, &b, &mut rng).unwrap();
+    let lines = |net: &CatNetwork| net.to_darj_string().unwrap().lines().map(str::to_string).collect::<Vec<String>>();
+    let (from_a, from_b) = (lines(&a), lines(&b));
+    // Only nodes after the sensors have links, so only their lines have commas
+    let inherited: Vec<bool> = lines(&child).iter().enumerate().filter(|(_, line)| line.contains(',')).map(|(i, line)| {
+        assert!(*line == from_a[i] || *line == from_b[i]);
+        *line == from_a[i]
+    }).collect();
+    assert!(inherited.contains(&true) && inherited.contains(&false));
+
+    let mut mutated: CatNetwork = child.clone();
+    assert!(matches!(mutated.mutate(1.5, 0.1, &mut rng), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(mutated.mutate(0.5, -0.1, &mut rng), Err(DarjeelingError::InvalidInput(_))));
+    mutated.mutate(0.0, 1.0, &mut rng).unwrap();
+    assert!(mutated.diff(&child, &[]).unwrap().layers.iter().all(|layer| layer.max_delta == 0.0));
+    mutated.mutate(1.0, 0.1, &mut rng).unwrap();
+    let diff = mutated.diff(&child, &[]).unwrap();
+    assert!(diff.layers.iter().all(|layer| layer.max_delta > 0.0 && layer.max_delta <= 0.1 + 1e-6));
+}
+
+#[test]
+fn unsupervised_pretraining() {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(8);
+    // Four inputs that only vary along two directions, so two hidden nodes can encode them
+    let unlabeled: Vec<Input> = (0..20).map(|i| {
+        let (a, b) = ((i % 5) as f32 / 4.0, (i / 5) as f32 / 3.0);
+        Input::new(vec![a, 1.0 - a, b, 1.0 - b], None)
+    }).collect();
+    let mut net = CatNetwork::new(4, 3, 2, 2, ActivationFunction::Sigmoid);
+    net.randomize_weights(&mut rng);
+    let untrained: CatNetwork = net.clone();
+
+    assert!(matches!(net.pretrain_with_rng(&[], 0.5, 1, &mut rng), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.pretrain_with_rng(&[Input::new(vec![0.0], None)], 0.5, 1, &mut rng), Err(DarjeelingError::InvalidInput(_))));
+
+    let first: Vec<f32> = net.clone().pretrain_with_rng(&unlabeled, 0.5, 1, &mut rng).unwrap();
+    let losses: Vec<f32> = net.pretrain_with_rng(&unlabeled, 0.5, 300, &mut rng).unwrap();
+    assert_eq!(losses.len(), 2);
+    assert!(losses[0] < first[0]);
+
+    // The hidden layers moved, but the answer layer is left for supervised training
+    let diff = net.diff(&untrained, &[]).unwrap();
+    assert!(diff.layers[0].max_delta > 0.0 && diff.layers[1].max_delta > 0.0);
+    assert_eq!(diff.layers[2].max_delta, 0.0);
+
+    // The same generator pre-trains the same network the same way
+    let (mut a, mut b) = (untrained.clone(), untrained);
+    let losses_a: Vec<f32> = a.pretrain_with_rng(&unlabeled, 0.5, 5, &mut StdRng::seed_from_u64(3)).unwrap();
+    assert_eq!(b.pretrain_with_rng(&unlabeled, 0.5, 5, &mut StdRng::seed_from_u64(3)).unwrap(), losses_a);
+    assert_eq!(a.to_darj_string().unwrap(), b.to_darj_string().unwrap());
+}
+
+#[test]
+fn semi_supervised_pseudo_labeling() {
+    use crate::semisupervised::PseudoLabeling;
+
+    let point = |x: f32, answer: Option<&str>| Input::new(vec![x, 1.0 - x], answer.map(|answer| Types::String(answer.to_string())));
+    let mut data: Vec<Input> = vec![point(0.0, Some("low")), point(1.0, Some("high"))];
+    data.extend((1..9).map(|i| point(i as f32 / 10.0, None)));
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+
+    let unlabeled: Vec<Input> = data.iter().filter(|input| input.answer.is_none()).cloned().collect();
+    assert!(matches!(net.learn_semi_supervised(&unlabeled, vec![], 0.5, 10, &PseudoLabeling::new(0.9, 2, 2, 1)), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(net.learn_semi_supervised(&data, vec![], 0.5, 10, &PseudoLabeling::new(1.5, 2, 2, 1)), Err(DarjeelingError::InvalidTrainingData(_))));
+
+    // Everything is confident enough at 0, and nothing at 1
+    let report = net.learn_semi_supervised(&data, vec![], 0.5, 20, &PseudoLabeling::new(0.0, 5, 5, 1)).unwrap();
+    assert_eq!((report.epochs, report.labeled_size, report.unlabeled_size), (20, 2, 8));
+    assert_eq!(report.pseudo_labeled, vec![8, 8, 8]);
+    assert_eq!(net.history().len(), 20);
+    assert_eq!(net.categories(), Some(categories_str_format(vec!["high", "low"])));
+
+    let report = net.learn_semi_supervised(&data, vec![], 0.5, 10, &PseudoLabeling::new(1.0, 0, 4, 1)).unwrap();
+    assert_eq!(report.pseudo_labeled, vec![0, 0, 0]);
+}
+
+#[test]
+fn distilling_a_teacher() {
+    use crate::distillation::Distillation;
+
+    let point = |x: f32, answer: &str| Input::new(vec![x, 1.0 - x], Some(Types::String(answer.to_string())));
+    let mut data: Vec<Input> = (0..10).map(|i| point(i as f32 / 10.0, if i < 5 { "low" } else { "high" })).collect();
+    let mut teacher = CatNetwork::new(2, 6, 2, 2, ActivationFunction::Sigmoid);
+    let mut student = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    let distillation = Distillation::new(2.0, 0.5, 20, 1);
+    assert!(matches!(student.distill(&teacher, &data, 0.5, &distillation), Err(DarjeelingError::InvalidInput(_))));
+
+    teacher.start_training(categories_str_format(vec!["low", "high"]));
+    (0..20).for_each(|_| { teacher.step_epoch(&mut data, 0.5).unwrap(); });
+    assert!(matches!(student.distill(&teacher, &data, 0.5, &Distillation::new(0.0, 0.5, 20, 1)), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(student.distill(&teacher, &data, 0.5, &Distillation::new(2.0, 1.5, 20, 1)), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(student.distill(&teacher, &[], 0.5, &distillation), Err(DarjeelingError::InvalidTrainingData(_))));
+    assert!(matches!(CatNetwork::new(3, 2, 2, 1, ActivationFunction::Sigmoid).distill(&teacher, &data, 0.5, &distillation), Err(DarjeelingError::ArchitectureMismatch(_))));
+
+    // Unanswered inputs learn from the teacher alone
+    data.iter_mut().step_by(2).for_each(|input| input.answer = None);
+    let report = student.distill(&teacher, &data, 0.5, &distillation).unwrap();
+    assert_eq!(report.epochs, 20);
+    assert_eq!((report.teacher_parameters, report.student_parameters), (2 * 6 + 6 + 6 * 6 + 6 + 6 * 2 + 2, 2 * 2 + 2 + 2 * 2 + 2));
+    assert!((0.0..=1.0).contains(&report.agreement));
+    assert_eq!(student.history().len(), 20);
+    assert_eq!(student.categories(), teacher.categories());
+
+    // The same seed trains the same student
+    let mut again = CatNetwork::from_darj_str(&student.to_darj_string().unwrap()).unwrap();
+    let mut twin = CatNetwork::from_darj_str(&student.to_darj_string().unwrap()).unwrap();
+    again.distill(&teacher, &data, 0.5, &distillation).unwrap();
+    twin.distill(&teacher, &data, 0.5, &distillation).unwrap();
+    assert_eq!(again.forward(&[0.3, 0.7]), twin.forward(&[0.3, 0.7]));
+}
+
+#[test]
+fn active_learning_queries() {
+    use crate::prediction::Uncertainty;
+
+    assert_eq!(Uncertainty::Entropy.score(&[1.0, 0.0]), 0.0);
+    assert!((Uncertainty::Entropy.score(&[0.5, 0.5]) - 2f32.ln()).abs() < 1e-6);
+    assert!((Uncertainty::Margin.score(&[0.6, 0.3, 0.1]) - 0.7).abs() < 1e-6);
+    assert!((Uncertainty::LeastConfident.score(&[0.6, 0.3, 0.1]) - 0.4).abs() < 1e-6);
+    // Outputs are normalized, so scaling them doesn't change anything
+    assert_eq!(Uncertainty::Margin.score(&[0.2, 0.2]), Uncertainty::Margin.score(&[0.9, 0.9]));
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let pool: Vec<Input> = (0..6).map(|i| Input::new(vec![i as f32 / 5.0, 1.0 - i as f32 / 5.0], None)).collect();
+    let picked: Vec<usize> = net.most_uncertain(&pool, 3);
+    assert_eq!(picked.len(), 3);
+    let entropy = |i: usize| Uncertainty::Entropy.score(&net.raw_outputs(&pool[i].inputs));
+    assert!(picked.windows(2).all(|pair| entropy(pair[0]) >= entropy(pair[1])));
+    assert!((0..pool.len()).filter(|i| !picked.contains(i)).all(|i| entropy(i) <= entropy(picked[2])));
+    assert_eq!(net.most_uncertain(&pool, 10).len(), 6);
+    assert!(net.most_uncertain(&[], 3).is_empty());
+
+    // Calibrated probabilities are used when the calibrator has them
+    net.set_calibrator(Some(crate::calibration::Calibrator::Temperature { temperature: 1.0 }));
+    assert_eq!(net.most_uncertain_by(&pool, 6, Uncertainty::Margin).len(), 6);
+}
+
+#[test]
+fn drift_detection() {
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["low", "high"]));
+    let data: Vec<Input> = (0..100).map(|i| Input::new(vec![i as f32 / 100.0, (i % 10) as f32], None)).collect();
+    assert!(net.detect_drift(&data).is_err());
+
+    net.set_data_profile(Some(net.profile(&data)));
+    let profile = net.data_profile().unwrap();
+    assert_eq!(profile.rows, 100);
+    assert_eq!(profile.features.len(), 2);
+    assert!((profile.features[0].bins.iter().sum::<f32>() - 1.0).abs() < 1e-4);
+    assert!((profile.predictions.iter().map(|(_, share)| share).sum::<f32>() - 1.0).abs() < 1e-4);
+
+    let same = net.detect_drift(&data).unwrap();
+    assert_eq!(same.rows, 100);
+    assert!(same.features.iter().all(|feature| feature.psi.abs() < 1e-4 && feature.out_of_range == 0.0));
+    assert!(same.prediction_psi.abs() < 1e-4);
+    assert!(same.drifted(0.1).is_empty());
+
+    // Only the first feature moves, and half of it out of the training range
+    let shifted: Vec<Input> = (0..100).map(|i| Input::new(vec![0.5 + i as f32 / 100.0, (i % 10) as f32], None)).collect();
+    let report = net.detect_drift(&shifted).unwrap();
+    assert!(report.features[0].psi > 0.25);
+    assert!(report.features[0].mean_shift > 1.0);
+    assert!((report.features[0].out_of_range - 0.5).abs() < 0.02);
+    assert!(report.features[1].psi.abs() < 1e-4);
+    let drifted = report.drifted(0.25);
+    assert_eq!(drifted.len(), 1);
+    assert_eq!(drifted[0].feature, 0);
+    assert!(report.to_string().contains("feature 0"));
+
+    assert!(net.detect_drift(&[]).is_err());
+    assert!(net.detect_drift(&[Input::new(vec![0.5], None)]).is_err());
+
+    // The profile is saved with the model
+    let read = CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).unwrap();
+    assert_eq!(read.data_profile(), net.data_profile());
+    let mut wider = CatNetwork::new(3, 3, 2, 1, ActivationFunction::Sigmoid);
+    wider.set_data_profile(net.data_profile().cloned());
+    assert!(CatNetwork::from_darj_str(&wider.to_darj_string().unwrap()).is_err());
+}
+
+#[test]
+fn incremental_update_with_anchor() {
+    use crate::consolidation::Anchor;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.randomize_weights(&mut StdRng::seed_from_u64(7));
+    let categories: Vec<Types> = categories_str_format(vec!["low", "high"]);
+    net.set_categories(categories.clone());
+    let labeled = |values: &[(f32, &str)]| -> Vec<Input> {
+        values.iter().map(|(x, answer)| Input::new(vec![*x, 1.0 - x], Some(Types::String(answer.to_string())))).collect()
+    };
+    let old: Vec<Input> = labeled(&[(0.1, "low"), (0.2, "low"), (0.8, "high"), (0.9, "high")]);
+    let mut new: Vec<Input> = labeled(&[(0.4, "high"), (0.6, "low")]);
+
+    let l2 = Anchor::l2(&net, 1.0);
+    assert_eq!(l2.len(), net.flat_weights().len());
+    assert!(l2.importance().iter().all(|importance| *importance == 1.0));
+
+    let ewc = Anchor::ewc(&net, &old, 1.0).unwrap();
+    assert_eq!(ewc.len(), l2.len());
+    assert!((ewc.importance().iter().sum::<f32>() / ewc.len() as f32 - 1.0).abs() < 1e-4);
+    assert!(ewc.importance().iter().all(|importance| *importance >= 0.0));
+    assert!(Anchor::ewc(&net, &[], 1.0).is_err());
+    assert!(Anchor::ewc(&net, &[Input::new(vec![0.1, 0.9], None)], 1.0).is_err());
+    assert!(Anchor::ewc(&net, &[Input::new(vec![0.1], Some(Types::String("low".to_string())))], 1.0).is_err());
+
+    // A pull as strong as the learning rate puts every weight straight back after each step
+    let before: Vec<f32> = net.flat_weights();
+    let records = net.update(&mut new, Anchor::l2(&net, 2.0), 0.5, 3).unwrap();
+    assert_eq!(records.len(), 3);
+    assert!(net.flat_weights().iter().zip(&before).all(|(after, before)| (after - before).abs() < 1e-5));
+    assert_eq!(net.categories(), Some(categories));
+
+    // Without a pull the new data moves the weights, and the anchor doesn't outlive the update
+    net.update(&mut new, Anchor::l2(&net, 0.0), 0.5, 1).unwrap();
+    assert!(net.flat_weights() != before);
+    let moved: Vec<f32> = net.flat_weights();
+    net.step_epoch(&mut new, 0.5).unwrap();
+    assert!(net.flat_weights() != moved);
+
+    let other = CatNetwork::new(2, 4, 2, 1, ActivationFunction::Sigmoid);
+    assert!(net.update(&mut new, Anchor::l2(&other, 1.0), 0.5, 1).is_err());
+    assert!(net.update(&mut [], Anchor::l2(&net, 1.0), 0.5, 1).is_err());
+    let mut uncategorized = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let anchor = Anchor::l2(&uncategorized, 1.0);
+    assert!(uncategorized.update(&mut new, anchor, 0.5, 1).is_err());
+}
+
+#[test]
+fn multi_seed_training() {
+    let data: Vec<Input> = (0..8).map(|i| Input::new(vec![i as f32 / 8.0, 1.0 - i as f32 / 8.0], Some(Types::String(if i < 4 { "low" } else { "high" }.to_string())))).collect();
+    let config = TrainingConfig::new(3, 1, ActivationFunction::Sigmoid, 0.5, 100.0);
+    let seeds: [u64; 4] = [1, 2, 3, 4];
+
+    let sequential = tuning::learn_multi_seed(&config, &seeds, &data, &data, 5, false).unwrap();
+    assert_eq!(sequential.runs.iter().map(|run| run.seed).collect::<Vec<u64>>(), seeds.to_vec());
+    assert!(sequential.runs.iter().all(|run| run.epochs >= 1 && run.epochs <= 5));
+    let best: f32 = sequential.runs.iter().map(|run| run.score).fold(f32::MIN, f32::max);
+    assert_eq!(sequential.score, best);
+    assert_eq!(sequential.runs.iter().find(|run| run.score == best).unwrap().seed, sequential.best_seed);
+    let (mean, std) = validation::mean_std(&sequential.runs.iter().map(|run| run.score).collect::<Vec<f32>>());
+    assert_eq!((sequential.mean_score, sequential.std_score), (mean, std));
+
+    // Seeds decide everything, so training them in parallel gives the same runs
+    let parallel = tuning::learn_multi_seed(&config, &seeds, &data, &data, 5, true).unwrap();
+    assert_eq!(parallel.runs, sequential.runs);
+    assert_eq!(parallel.model.to_darj_string().unwrap(), sequential.model.to_darj_string().unwrap());
+
+    assert!(tuning::learn_multi_seed(&config, &[], &data, &data, 5, false).is_err());
+    assert!(tuning::learn_multi_seed(&config, &seeds, &[], &data, 5, false).is_err());
+}
+
+#[test]
+fn network_visualization() {
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["yes", "say \"no\""]));
+
+    let dot: String = net.to_dot();
+    assert!(dot.starts_with("digraph network {") && dot.ends_with("}\n"));
+    assert_eq!(dot.matches("->").count(), 2 * 3 + 3 * 2);
+    assert!(dot.contains("n2_1 [label=\"say \\\"no\\\"\"]"));
+    assert!(dot.contains("cluster_1") && dot.contains("label=\"hidden 1\""));
+
+    let diagram: String = net.to_diagram(false);
+    let lines: Vec<&str> = diagram.lines().collect();
+    assert!(lines[0].starts_with("2 -> 3 -> 2 (sigmoid)"));
+    // A heading and a row for every node of each layer after the inputs
+    assert_eq!(lines.len(), 1 + (1 + 3) + (1 + 2));
+    assert!(lines.iter().any(|line| line.trim_start().starts_with("yes")));
+    // The largest weight in the network gets the darkest shade
+    assert!(diagram.contains('█'));
+    assert!(!diagram.contains('\x1b'));
+    assert!(net.to_diagram(true).contains("\x1b["));
+
+    let gen = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Tanh);
+    assert!(gen.to_dot().contains("n2_0 [label=\"y0\"]"));
+    assert!(gen.to_diagram(false).starts_with("2 -> 3 -> 2 (tanh)"));
+}
+
+#[cfg(feature = "plot")]
+#[test]
+fn plotting_history() {
+    use crate::{plot, history::{TrainingHistory, EpochRecord}};
+
+    let mut history = TrainingHistory::new();
+    let empty: String = plot::history_svg(&history);
+    assert!(empty.starts_with("<svg") && !empty.contains("stroke-width=\"2\""));
+
+    history.record(EpochRecord { epoch: 1, loss: 0.5, accuracy: 0.25, learning_rate: 0.1, grad_norm: 0.0, max_grad_norm: 0.0, layers: vec![], holdout_loss: None, holdout_accuracy: None, order: vec![], timings: vec![] });
+    assert!(plot::history_svg(&history).contains("<circle"));
+    for epoch in 2..=10 {
+        history.record(EpochRecord { epoch, loss: 0.5 / epoch as f32, accuracy: 0.25 + epoch as f32 / 20.0, learning_rate: 0.1, grad_norm: 0.0, max_grad_norm: 0.0, layers: vec![], holdout_loss: None, holdout_accuracy: None, order: vec![], timings: vec![] });
+    }
+    let svg: String = plot::history_svg(&history);
+    assert!(svg.trim_end().ends_with("</svg>"));
+    // One curve for the loss and one for the accuracy, each through every epoch
+    let curves: Vec<&str> = svg.lines().filter(|line| line.contains("stroke-width=\"2\"")).collect();
+    assert_eq!(curves.len(), 2);
+    assert!(curves.iter().all(|curve| curve.split("points=\"").nth(1).unwrap().split('"').next().unwrap().split(' ').count() == 10));
+
+    let path = std::env::temp_dir().join(format!("darjeeling_history_{}.svg", rand::random::<u32>()));
+    history.to_svg(path.to_str().unwrap()).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), svg);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn renaming_categories() {
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    assert_eq!(net.category(0), None);
+    net.set_categories(vec![Types::Boolean(false), Types::Boolean(true)]);
+    let data: Vec<Input> = (0..4).map(|i| Input::new(vec![i as f32 / 4.0, 0.5], None)).collect();
+    net.set_data_profile(Some(net.profile(&data)));
+    let before: Types = net.predict_category(&[0.2, 0.5]);
+
+    let approved = Types::String("approved".to_string());
+    let denied = Types::String("denied".to_string());
+    net.rename_category(1, approved.clone()).unwrap();
+    assert_eq!(net.category(1), Some(&approved));
+    assert_eq!(net.category(2), None);
+    net.relabel(&[(Types::Boolean(false), denied.clone())]).unwrap();
+    assert_eq!(net.categories(), Some(vec![denied.clone(), approved.clone()]));
+    // Predictions answer with the new names
+    let renamed: Types = net.predict_category(&[0.2, 0.5]);
+    assert_eq!(renamed, if before == Types::Boolean(true) { approved.clone() } else { denied.clone() });
+    assert!(net.predict(&[0.2, 0.5]).scores.iter().all(|(category, _)| *category == approved || *category == denied));
+    assert!(net.data_profile().unwrap().predictions.iter().all(|(category, _)| *category == approved || *category == denied));
+
+    // Names can be swapped in one go, but not doubled up
+    net.relabel(&[(approved.clone(), denied.clone()), (denied.clone(), approved.clone())]).unwrap();
+    assert_eq!(net.categories(), Some(vec![approved.clone(), denied.clone()]));
+    assert!(matches!(net.rename_category(0, denied.clone()), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.rename_category(2, Types::Integer(2)), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(net.relabel(&[(Types::Boolean(true), Types::Integer(1))]), Err(DarjeelingError::UnknownCategory(_))));
+    assert!(matches!(net.relabel(&[(approved.clone(), denied.clone())]), Err(DarjeelingError::InvalidInput(_))));
+    assert_eq!(net.categories(), Some(vec![approved.clone(), denied.clone()]));
+
+    let read = CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).unwrap();
+    assert_eq!(read.categories(), Some(vec![approved, denied]));
+}
+
+#[test]
+fn feature_schema() {
+    use crate::schema::Schema;
+
+    let schema = Schema::from_csv_header("age,approved,income\n34,yes,52000\n", Some(1)).unwrap();
+    assert_eq!(schema.features(), ["age".to_string(), "income".to_string()]);
+    assert_eq!(schema.index("income"), Some(1));
+    assert_eq!(schema.index("approved"), None);
+    assert_eq!(schema.name(5), "feature 5");
+    assert!(Schema::new(vec!["age".to_string(), "age".to_string()]).is_err());
+    assert!(Schema::new(vec!["age".to_string(), " ".to_string()]).is_err());
+    assert!(Schema::from_csv_header("", None).is_err());
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["no", "yes"]));
+    assert_eq!(net.feature_name(1), "feature 1");
+    assert!(matches!(net.set_schema(Some(Schema::new(vec!["age".to_string()]).unwrap())), Err(DarjeelingError::ArchitectureMismatch(_))));
+    net.set_schema(Some(schema.clone())).unwrap();
+    assert_eq!(net.feature_name(1), "income");
+
+    // Errors, drift reports and pictures of the network all use the names
+    match net.check_compatibility(&[Input::new(vec![30.0, f32::NAN], None)]) {
+        Err(DarjeelingError::InvalidInput(message)) => assert!(message.starts_with("income of input 0")),
+        other => panic!("Expected an invalid input, got {:?}", other)
+    }
+    let data: Vec<Input> = (0..10).map(|i| Input::new(vec![20.0 + i as f32, 1000.0 * i as f32], None)).collect();
+    net.set_data_profile(Some(net.profile(&data)));
+    let report = net.detect_drift(&data).unwrap();
+    assert_eq!(report.features[1].name, "income");
+    assert!(report.to_string().contains("\nage: PSI"));
+    assert!(net.to_dot().contains("n0_0 [label=\"age\"]"));
+    assert!(net.to_diagram(false).lines().nth(1) == Some("inputs: age, income"));
+
+    let read = CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).unwrap();
+    assert_eq!(read.schema(), Some(&schema));
+    let mislabeled: String = net.to_darj_string().unwrap().replace(r#"["age","income"]"#, r#"["age","income","height"]"#);
+    assert!(matches!(CatNetwork::from_darj_str(&mislabeled), Err(DarjeelingError::InvalidMetadataRead(_))));
+}
+
+#[test]
+fn prediction_audit_hook() {
+    use std::sync::{Arc, Mutex};
+    use crate::audit::{self, AuditHook, PredictionAudit};
+
+    let records: Arc<Mutex<Vec<PredictionAudit>>> = Arc::new(Mutex::new(vec![]));
+    let log = records.clone();
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["no", "yes"]));
+    net.predict(&[0.1, 0.2]);
+    net.set_audit_hook(Some(AuditHook::new(move |audit| log.lock().unwrap().push(audit.clone()))));
+
+    let prediction = net.predict(&[0.5, 0.25]);
+    let category: Types = net.predict_category(&[0.75, 0.5]);
+    net.try_predict(&[0.5, 0.25]).unwrap();
+    // Clones share the hook, but it isn't saved with the model
+    net.clone().predict(&[0.0, 0.0]);
+    CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).unwrap().predict(&[0.0, 0.0]);
+
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0].scores, prediction.scores);
+    assert_eq!(records[0].category, prediction.category);
+    assert_eq!(records[1].category, category);
+    assert_eq!(records[0].input_hash, records[2].input_hash);
+    assert_ne!(records[0].input_hash, records[1].input_hash);
+    assert_eq!(records[0].input_hash, audit::input_hash(&[0.5, 0.25]));
+    assert_eq!(records[0].input_hash.len(), 64);
+    assert!(records[0].timestamp <= records[1].timestamp);
+}
+
+#[test]
+fn scoring_csv_files() {
+    use crate::schema::Schema;
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    assert!(matches!(csv::score_csv(&net, "0.5,0.25\n", false), Err(DarjeelingError::InvalidInput(_))));
+    net.set_categories(vec![Types::String("no".to_string()), Types::String("yes, please".to_string())]);
+    let (scored, rows) = csv::score_csv(&net, "0.5,0.25\n\n1,0\n", false).unwrap();
+    assert_eq!(rows, 2);
+    let lines: Vec<&str> = scored.lines().collect();
+    assert_eq!(lines.len(), 2);
+    let prediction = net.predict(&[0.5, 0.25]);
+    let category: String = if prediction.category == Types::String("no".to_string()) { "no".to_string() } else { "\"yes, please\"".to_string() };
+    assert_eq!(lines[0], format!("0.5,0.25,{},{}", category, prediction.confidence));
+    assert!(csv::score_csv(&net, "0.5,0.25,7\n", false).is_err());
+    assert!(csv::score_csv(&net, "0.5,high\n", false).is_err());
+
+    // With a schema, the features are found by name and the other columns pass through
+    net.set_schema(Some(Schema::new(vec!["age".to_string(), "income".to_string()]).unwrap())).unwrap();
+    let (scored, rows) = csv::score_csv(&net, "id,income,age\n17,0.25,0.5\n", true).unwrap();
+    assert_eq!(rows, 1);
+    assert!(scored.starts_with("id,income,age,prediction,confidence\n17,0.25,0.5,"));
+    assert!(scored.ends_with(&format!(",{}\n", prediction.confidence)));
+    assert!(matches!(csv::score_csv(&net, "id,age\n17,0.5\n", true), Err(DarjeelingError::ReadDataFailed(_))));
+
+    let dir = std::env::temp_dir().join(format!("darjeeling_scoring_{}", rand::random::<u32>()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("in.csv"), "id,income,age\n17,0.25,0.5\n18,0.75,0.5\n").unwrap();
+    let (input, output) = (dir.join("in.csv").to_string_lossy().into_owned(), dir.join("out.csv").to_string_lossy().into_owned());
+    assert_eq!(csv::score_file(&net, &input, &output, true).unwrap(), 2);
+    assert_eq!(fs::read_to_string(&output).unwrap().lines().count(), 3);
+    assert!(csv::score_file(&net, &dir.join("missing.csv").to_string_lossy(), &output, true).is_err());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn float_categories_match_within_epsilon() {
+    assert!(Types::Float(0.1 + 0.2).approx_eq(&Types::Float(0.3), types::FLOAT_EPSILON));
+    assert!(!Types::Float(0.1).approx_eq(&Types::Float(0.2), types::FLOAT_EPSILON));
+    assert!(Types::Vector(vec![0.1 + 0.2, 1.0]).approx_eq(&Types::Vector(vec![0.3, 1.0]), 1e-5));
+    assert!(!Types::Vector(vec![0.3]).approx_eq(&Types::Vector(vec![0.3, 1.0]), 1e-5));
+    assert!(!Types::Float(f32::NAN).approx_eq(&Types::Float(f32::NAN), 1.0));
+
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(vec![Types::Float(0.3), Types::Float(0.7)]);
+    let data = vec![
+        Input::new(vec![0.0, 1.0], Some(Types::Float(0.1 + 0.2))),
+        Input::new(vec![1.0, 0.0], Some(Types::Float(0.7 + 1e-7)))
+    ];
+    assert!(net.check_compatibility(&data).is_ok());
+    assert!(net.check_compatibility(&[Input::new(vec![0.0, 1.0], Some(Types::Float(0.5)))]).is_err());
+
+    let report = net.evaluate(&data);
+    assert_eq!(report.count, 2);
+    let support: Vec<(Types, usize)> = report.metrics.classes.iter().map(|class| (class.category.clone(), class.support)).collect();
+    assert!(support.contains(&(Types::Float(0.3), 1)));
+    assert!(support.contains(&(Types::Float(0.7), 1)));
+
+    // Answers within the tolerance of each other are one category when they're found from the data
+    let mut answers = vec![Types::Float(0.1 + 0.2), Types::Float(0.3), Types::Float(0.7)].into_iter().map(|answer| Input::new(vec![0.5, 0.5], Some(answer))).collect::<Vec<Input>>();
+    assert_eq!(Dataset::new(answers.clone()).categories().len(), 2);
+    let mut discovering = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    discovering.learn(&mut answers, vec![], 0.5, "float_categories", 0.1, false).unwrap();
+    assert_eq!(discovering.categories().unwrap().len(), 2);
+
+    // The tolerance can be widened
+    net.set_float_tolerance(0.25);
+    assert!(net.check_compatibility(&[Input::new(vec![0.0, 1.0], Some(Types::Float(0.5)))]).is_ok());
+    let mut coarse = Dataset::new(vec![Input::new(vec![0.0], Some(Types::Float(0.5))), Input::new(vec![0.0], Some(Types::Float(0.6)))]);
+    assert_eq!(coarse.audit().conflicts.len(), 1);
+    coarse.set_float_tolerance(0.25);
+    assert_eq!(coarse.categories().len(), 1);
+    assert_eq!(coarse.audit().conflicts.len(), 0);
+}
+
+#[cfg(all(feature = "mmap", unix, target_endian = "little"))]
+#[test]
+fn mapped_dataset() {
+    use crate::{mapped::{self, MappedDataset}, dataset::DataSource};
+
+    let path = std::env::temp_dir().join(format!("darjeeling_mapped_{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+    let data: Vec<Input> = (0..50).map(|i| Input::new(vec![i as f32, -(i as f32) / 2.0, 0.25], Some(Types::Integer(i % 3)))).collect();
+    mapped::write_mapped(path, &data).unwrap();
+
+    let mut mapped: MappedDataset = unsafe { MappedDataset::open(path) }.unwrap();
+    assert_eq!((mapped.len(), mapped.columns(), mapped.labeled()), (50, 3, true));
+    assert_eq!(mapped.row(7), &[7.0, -3.5, 0.25]);
+    assert_eq!(mapped.iter().collect::<Vec<Input>>(), data);
+    assert_eq!(mapped.pass().count(), 50);
+    drop(mapped);
+
+    assert!(mapped::write_mapped(path, &[Input::new(vec![0.0], Some(Types::String("a".to_string())))]).is_err());
+    assert!(mapped::write_mapped(path, &[Input::new(vec![0.0], None), Input::new(vec![0.0], Some(Types::Integer(1)))]).is_err());
+    mapped::write_mapped(path, &[Input::new(vec![1.0, 2.0], None)]).unwrap();
+    let unlabeled: MappedDataset = unsafe { MappedDataset::open(path) }.unwrap();
+    assert_eq!(unlabeled.input(0), Input::new(vec![1.0, 2.0], None));
+    drop(unlabeled);
+
+    let bytes: Vec<u8> = fs::read(path).unwrap();
+    fs::write(path, &bytes[..bytes.len() - 2]).unwrap();
+    assert!(unsafe { MappedDataset::open(path) }.is_err());
+    fs::write(path, b"not a dataset, just some text").unwrap();
+    assert!(unsafe { MappedDataset::open(path) }.is_err());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn dataset_cache() {
+    use crate::cache::DatasetCache;
+
+    let dir = std::env::temp_dir().join(format!("darjeeling_cache_{}", std::process::id()));
+    let cache = DatasetCache::new(&dir);
+    let csv_path = dir.with_extension("csv");
+    let csv_path = csv_path.to_str().unwrap();
+    fs::write(csv_path, "a,b,label\n0,1,1\n1,1,0\n").unwrap();
+
+    let data: Vec<Input> = cache.read_csv(csv_path, true, Some(2)).unwrap();
+    assert_eq!(data, csv::read_csv(csv_path, true, Some(2)).unwrap());
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    assert_eq!(cache.read_csv(csv_path, true, Some(2)).unwrap(), data);
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    // Different preprocessing or different data is a different entry
+    assert_ne!(cache.read_csv(csv_path, true, None).unwrap(), data);
+    fs::write(csv_path, "a,b,label\n0,1,1\n1,1,0\n0,0,0\n").unwrap();
+    assert_eq!(cache.read_csv(csv_path, true, Some(2)).unwrap().len(), 3);
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+
+    let key: String = DatasetCache::key(b"raw", "encoded");
+    let typed: Vec<Input> = vec![Input::new(vec![0.5, -1.0], Some(Types::Vector(vec![1.0, 0.0]))), Input::new(vec![], None), Input::new(vec![2.0], Some(Types::category(1, "b")))];
+    cache.put(&key, &typed).unwrap();
+    assert_eq!(cache.get_or_insert_with(&key, || panic!("Cached data isn't made again")).unwrap(), typed);
+    assert_eq!(cache.get(&DatasetCache::key(b"raw", "encoded differently")).unwrap(), None);
+
+    fs::write(dir.join(format!("{}.cache", key)), b"DARJCACH truncated").unwrap();
+    assert!(cache.get(&key).is_err());
+    cache.clear().unwrap();
+    assert!(!dir.exists());
+    cache.clear().unwrap();
+    fs::remove_file(csv_path).unwrap();
+}
+
+#[test]
+fn csv_rows_keep_their_order() {
+    let mut text: String = "x,y,label\n".to_string();
+    (0..40_000).for_each(|i| text.push_str(&format!("{},{},{}\n", i, i % 7, i % 3)));
+    let data: Vec<Input> = csv::parse_csv(&text, true, Some(2)).unwrap();
+    assert_eq!(data.len(), 40_000);
+    assert!(data.iter().enumerate().all(|(i, input)| input.inputs[0] == i as f32 && input.answer == Some(Types::String((i % 3).to_string()))));
+
+    // The first bad line is the one reported, even with bad lines in later chunks
+    text.push_str("oops,1,2\n");
+    let mut broken: Vec<&str> = text.lines().collect();
+    broken[30_001] = "1,two,0";
+    let error = csv::parse_csv(&broken.join("\n"), true, Some(2)).unwrap_err();
+    assert!(error.to_string().contains("Line 30002"), "{}", error);
+}
+
+#[test]
+fn weight_histograms_and_dead_neurons() {
+    use crate::inspect::WeightHistogram;
+
+    let net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    // Zeroing the first hidden node's weights and bias leaves it outputting 0.5 whatever the input
+    let darj: String = net.to_darj_string().unwrap();
+    let mut layers: Vec<String> = darj.split("lb\n").map(|layer| layer.to_string()).collect();
+    layers[1] = layers[1].splitn(2, '\n').enumerate().map(|(i, line)| if i == 0 { "0,0;0" } else { line }).collect::<Vec<&str>>().join("\n");
+    let net: CatNetwork = CatNetwork::from_darj_str(&layers.join("lb\n")).unwrap();
+
+    let histogram = net.layer_weight_histogram(1, 4).unwrap();
+    assert_eq!((histogram.layer, histogram.counts.len(), histogram.total()), (1, 4, 9));
+    assert!(histogram.min <= 0.0 && histogram.max >= 0.0);
+    assert_eq!(histogram.bin_range(0).0, histogram.min);
+    assert!((histogram.bin_range(3).1 - histogram.max).abs() < 1e-5);
+    assert_eq!(net.layer_weight_histogram(2, 1).unwrap().counts, vec![8]);
+    assert!(net.layer_weight_histogram(0, 4).is_err());
+    assert!(net.layer_weight_histogram(3, 4).is_err());
+    assert!(net.layer_weight_histogram(1, 0).is_err());
+    assert_eq!(WeightHistogram::of(1, &[0.5, 0.5, 0.5], 3).counts, vec![3, 0, 0]);
+    assert_eq!(histogram.to_string().lines().count(), 5);
+
+    let probe: Vec<Input> = (0..20).map(|i| Input::new(vec![i as f32 / 4.0, (i % 5) as f32 - 2.0], None)).collect();
+    let report = net.dead_neurons(&probe, 1e-6).unwrap();
+    assert_eq!((report.probes, report.layer_sizes.clone()), (20, vec![3, 2]));
+    let dead = report.in_layer(1);
+    assert!(dead.iter().any(|neuron| neuron.node == 0 && (neuron.mean - 0.5).abs() < 1e-6 && neuron.std == 0.0));
+    assert!(report.fraction(1) >= 1.0 / 3.0);
+    assert_eq!(report.fraction(0), 0.0);
+    // Every node is constant across a single probe
+    assert_eq!(net.dead_neurons(&probe[..1], 0.0).unwrap().neurons.len(), 5);
+    assert!(net.dead_neurons(&[], 1e-3).is_err());
+    assert!(net.dead_neurons(&[Input::new(vec![1.0], None)], 1e-3).is_err());
+}
+
+#[test]
+fn dead_neuron_policy() {
+    use crate::history::{DeadNeuronPolicy, NeuronAction};
+
+    // A node with no link weights and a huge bias always outputs 1 and never learns, so it stays dead
+    let saturated = || {
+        let darj: String = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid).to_darj_string().unwrap();
+        let mut layers: Vec<String> = darj.split("lb\n").map(|layer| layer.to_string()).collect();
+        layers[1] = layers[1].splitn(2, '\n').enumerate().map(|(i, line)| if i == 0 { "0,0;1000" } else { line }).collect::<Vec<&str>>().join("\n");
+        CatNetwork::from_darj_str(&layers.join("lb\n")).unwrap()
+    };
+    let mut data: Vec<Input> = (0..8).map(|i| Input::new(vec![(i % 2) as f32, (i / 2 % 2) as f32 * 3.0 - 1.5], Some(Types::Boolean(i % 3 == 0)))).collect();
+    let categories: Vec<Types> = vec![Types::Boolean(false), Types::Boolean(true)];
+
+    let mut net: CatNetwork = saturated();
+    net.set_dead_neuron_policy(Some(DeadNeuronPolicy::remove(2, 1e-6)));
+    net.start_training(categories.clone());
+    net.step_epoch(&mut data, 0.5).unwrap();
+    assert!(net.history().events().is_empty());
+    net.step_epoch(&mut data, 0.5).unwrap();
+    let events = net.history().events();
+    assert!(events.iter().any(|event| event.epoch == 2 && event.layer == 1 && event.node == 0 && event.action == NeuronAction::Remove));
+    // The removed node's links from the answer layer went with it
+    let hidden: usize = net.layer_weight_histogram(1, 1).unwrap().total() / 3;
+    assert_eq!(hidden, 3 - events.len());
+    assert_eq!(net.layer_weight_histogram(2, 1).unwrap().total(), 2 * (hidden + 1));
+    assert_eq!(net.predict(&[1.0, 1.5]).scores.len(), 2);
+    net.step_epoch(&mut data, 0.5).unwrap();
+
+    let mut net: CatNetwork = saturated();
+    net.set_dead_neuron_policy(Some(DeadNeuronPolicy::reinitialize(1, 1e-6)));
+    net.start_training(categories);
+    net.step_epoch(&mut data, 0.5).unwrap();
+    assert!(net.history().events().iter().any(|event| event.epoch == 1 && event.node == 0 && event.action == NeuronAction::Reinitialize));
+    assert!(!net.to_darj_string().unwrap().split("lb\n").nth(1).unwrap().starts_with("0,0;1000"));
+    assert_eq!(net.layer_weight_histogram(1, 1).unwrap().total(), 9);
+}
+
+#[test]
+fn growing_trained_networks() {
+    let probe: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32 / 10.0, 1.0 - i as f32 / 7.0]).collect();
+    let outputs = |net: &CatNetwork| probe.iter().map(|inputs| net.forward(inputs)).collect::<Vec<Vec<f32>>>();
+    let close = |a: &[Vec<f32>], b: &[Vec<f32>], tolerance: f32| a.iter().flatten().zip(b.iter().flatten()).all(|(a, b)| (a - b).abs() < tolerance);
+
+    for activation in ActivationFunction::all() {
+        let mut net = CatNetwork::new(2, 3, 2, 1, activation);
+        let before = outputs(&net);
+
+        net.grow_hidden(1, 4).unwrap();
+        assert_eq!(outputs(&net), before);
+        assert_eq!(net.layer_weight_histogram(1, 1).unwrap().total(), 7 * 3);
+        assert_eq!(net.layer_weight_histogram(2, 1).unwrap().total(), 2 * 8);
+
+        net.insert_layer(2, 9).unwrap();
+        net.insert_layer(1, 2).unwrap();
+        assert_eq!(net.layer_weight_histogram(4, 1).unwrap().total(), 2 * 10);
+        let tolerance: f32 = if activation == ActivationFunction::Linear { 1e-4 } else { 1e-2 };
+        assert!(close(&outputs(&net), &before, tolerance), "{:?} changed its answers", activation);
+
+        assert!(net.grow_hidden(0, 1).is_err());
+        assert!(net.grow_hidden(4, 1).is_err());
+        assert!(net.insert_layer(0, 5).is_err());
+        assert!(net.insert_layer(5, 5).is_err());
+        assert!(net.insert_layer(3, 6).is_err());
+    }
+
+    // A grown network carries on training and round trips
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.start_training(vec![Types::Boolean(false), Types::Boolean(true)]);
+    let mut data: Vec<Input> = probe.iter().map(|inputs| Input::new(inputs.clone(), Some(Types::Boolean(inputs[0] > 0.5)))).collect();
+    net.step_epoch(&mut data, 0.5).unwrap();
+    net.grow_hidden(1, 2).unwrap();
+    net.insert_layer(2, 4).unwrap();
+    net.step_epoch(&mut data, 0.5).unwrap();
+    let read = CatNetwork::from_darj_str(&net.to_darj_string().unwrap()).unwrap();
+    assert_eq!(outputs(&read), outputs(&net));
+
+    // The next layer's weights are scaled up by 400 for sigmoid, and refused if that would overflow them
+    let mut net = CatNetwork::from_darj_str(";0\n;0\nlb\n1,-2;0\nlb\nsigmoid").unwrap();
+    net.insert_layer(1, 2).unwrap();
+    assert!(net.to_darj_string().unwrap().contains("400,-800;"));
+    let mut net = CatNetwork::from_darj_str(";0\n;0\nlb\n1e37,0;0\nlb\nsigmoid").unwrap();
+    let before = net.to_darj_string().unwrap();
+    assert!(net.insert_layer(1, 2).is_err());
+    assert_eq!(net.to_darj_string().unwrap(), before);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_thread_count() {
+    use crate::parallel;
+
+    parallel::set_threads(Some(3)).unwrap();
+    assert_eq!(parallel::configured_threads(), Some(3));
+    assert_eq!(parallel::threads(), 3);
+    // Parallel work inside the pool sees the pool's threads, not the global pool's
+    assert_eq!(parallel::install(rayon::current_num_threads), 3);
+    assert!(matches!(parallel::set_threads(Some(0)), Err(DarjeelingError::InvalidInput(_))));
+    assert_eq!(parallel::configured_threads(), Some(3));
+
+    let data: Vec<Input> = (0..6).map(|i| Input::new(vec![i as f32, 1.0], Some(Types::String(if i < 3 { "low" } else { "high" }.to_string())))).collect();
+    let config = TrainingConfig::new(2, 1, ActivationFunction::Sigmoid, 0.5, 100.0);
+    let result = crate::tuning::learn_multi_seed(&config, &[1, 2, 3, 4], &data, &data, 5, true).unwrap();
+    assert_eq!(result.runs.len(), 4);
+
+    parallel::set_threads(None).unwrap();
+    assert_eq!(parallel::configured_threads(), None);
+}
+
+#[test]
+fn prelude_surface() {
+    // Downstream crates rely on every one of these names staying in the prelude
+    #[allow(unused_imports)]
+    use crate::prelude::{
+        ActivationFunction, CatNetwork, DarjeelingError, DataSource, Dataset, Ensemble, GanConfig, GenNetwork, HierarchicalClassifier,
+        Input, LearningRateSchedule, MultiTaskNetwork, OneVsRest, OrdinalNetwork, Prediction, Regularization, Scaler, TrainingConfig, Types
+    };
+
+    let config: TrainingConfig = TrainingConfig::new(2, 1, ActivationFunction::Sigmoid, 0.5, 90.0);
+    let net: CatNetwork = CatNetwork::from_config(2, 2, &config);
+    let data: Dataset = Dataset::new(vec![Input::new(vec![0.0, 1.0], Some(Types::Integer(1)))]);
+    assert_eq!(net.input_size(), data.inputs()[0].inputs.len());
+}
+
+#[test]
+fn in_memory_model_round_trip() {
+    let mut net = CatNetwork::new(2, 3, 2, 1, ActivationFunction::Tanh);
+    net.set_categories(categories_str_format(vec!["yes", "no"]));
+    let serialized: String = net.to_darj_string().unwrap();
+    let read: CatNetwork = CatNetwork::from_darj_str(&serialized).unwrap();
+    assert_eq!(read.to_darj_string().unwrap(), serialized);
+    assert_eq!(read.predict_category(&[0.5, -1.0]), net.predict_category(&[0.5, -1.0]));
+    assert!(CatNetwork::from_darj_str("not a model").is_err());
+
+    let gen = GenNetwork::new(2, 3, 2, 1, ActivationFunction::Sigmoid);
+    let serialized: String = gen.to_darj_string();
+    assert_eq!(GenNetwork::from_darj_str(&serialized).unwrap().to_darj_string(), serialized);
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn serve_predict_json() {
+    use crate::serve::{self, PredictResponse};
+
+    let mut net = CatNetwork::new(2, 2, 2, 1, ActivationFunction::Sigmoid);
+    net.set_categories(categories_str_format(vec!["yes", "no"]));
+    let json: String = serve::predict_json(&net, r#"{"inputs": [[0.0, 1.0], [1.0, 0.0]]}"#).unwrap();
+    let response: PredictResponse = serde_json::from_str(&json).unwrap();
+    assert_eq!(response.predictions.len(), 2);
+    assert_eq!(response.predictions[0].category, net.predict_category(&[0.0, 1.0]).to_string());
+
+    assert!(matches!(serve::predict_json(&net, r#"{"inputs": [[0.0]]}"#), Err(DarjeelingError::InvalidInput(_))));
+    assert!(matches!(serve::predict_json(&net, "not json"), Err(DarjeelingError::InvalidInput(_))));
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi_predict() {
+    use crate::ffi;
+
+    let net = ffi::darj_create(3, 4, 2, 1, ffi::DARJ_TANH);
+    assert!(!net.is_null());
+    assert!(ffi::darj_create(3, 4, 2, 1, 7).is_null());
+    unsafe {
+        assert_eq!(ffi::darj_input_size(net), 3);
+        assert_eq!(ffi::darj_output_size(net), 2);
+        let inputs: [f32; 3] = [0.1, 0.5, 0.9];
+        let mut outputs: [f32; 2] = [0.0; 2];
+        let brightest = ffi::darj_predict(net, inputs.as_ptr(), 3, outputs.as_mut_ptr(), 2);
+        assert_eq!(outputs.to_vec(), (*net).forward(&inputs));
+        assert!(brightest == 0 || brightest == 1);
+        assert_eq!(ffi::darj_predict(net, inputs.as_ptr(), 2, outputs.as_mut_ptr(), 2), ffi::DARJ_WRONG_SIZE);
+        assert_eq!(ffi::darj_predict(net, std::ptr::null(), 3, outputs.as_mut_ptr(), 2), ffi::DARJ_NULL_POINTER);
+        let missing = std::ffi::CString::new("does_not_exist.darj").unwrap();
+        assert!(ffi::darj_load(missing.as_ptr()).is_null());
+        ffi::darj_free(net);
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn read_parquet_columns() {
+    use std::sync::Arc;
+    use arrow_array::{ArrayRef, Float64Array, Int32Array, RecordBatch, StringArray};
+    use parquet::arrow::ArrowWriter;
+    use crate::arrow;
+
+    let batch = RecordBatch::try_from_iter(vec![
+        ("x", Arc::new(Float64Array::from(vec![0.5, 1.5])) as ArrayRef),
+        ("y", Arc::new(Int32Array::from(vec![Some(2), None])) as ArrayRef),
+        ("z", Arc::new(Int32Array::from(vec![3, 4])) as ArrayRef),
+        ("label", Arc::new(StringArray::from(vec!["on", "off"])) as ArrayRef)
+    ]).unwrap();
+
+    let data: Vec<Input> = arrow::read_record_batch(&batch, &["z", "x"], Some("label")).unwrap();
+    assert_eq!(data[0].inputs, vec![3.0, 0.5]);
+    assert_eq!(data[1].answer, Some(Types::String("off".to_string())));
+    assert!(matches!(arrow::read_record_batch(&batch, &["y"], None), Err(DarjeelingError::ReadDataFailed(_))));
+    assert!(matches!(arrow::read_record_batch(&batch, &["label"], None), Err(DarjeelingError::ReadDataFailed(_))));
+    assert!(matches!(arrow::read_record_batch(&batch, &["w"], None), Err(DarjeelingError::ReadDataFailed(_))));
+
+    let path = std::env::temp_dir().join("darjeeling_parquet_test.parquet");
+    let mut writer = ArrowWriter::try_new(fs::File::create(&path).unwrap(), batch.schema(), None).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+    let read = arrow::read_parquet(path.to_str().unwrap(), &["z", "x"], Some("label"));
+    let _ = fs::remove_file(&path);
+    assert_eq!(read.unwrap(), data);
+}
+
 /// Formats cateories from a vector of string slices to a vector of strings
 /// # Params
 /// - Categories Strings: A list of string literals, one for each answer option(category)
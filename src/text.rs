@@ -0,0 +1,181 @@
+use std::{collections::HashMap, fs};
+use serde::{Serialize, Deserialize};
+use crate::{
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// Splits text into lowercase words, treating anything that isn't a letter or number as a separator
+pub fn tokenize(text: &str) -> Vec<String> {
+    text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// The words a vectorizer knows about, each mapped to the sensor it's counted in
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Vocabulary {
+    indexes: HashMap<String, usize>
+}
+
+impl Vocabulary {
+
+    /// Builds a vocabulary from a list of documents.
+    /// Words are ordered by how often they appear, then alphabetically, so the same documents always give the same vocabulary.
+    ///
+    /// ## Params
+    /// - Documents: The text to learn words from
+    /// - Min Count: Words that appear fewer times than this are left out
+    /// - Max Size: The most words the vocabulary will hold, None for no limit
+    pub fn build(documents: &[&str], min_count: usize, max_size: Option<usize>) -> Vocabulary {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        documents.iter().for_each(|document| {
+            tokenize(document).into_iter().for_each(|word| *counts.entry(word).or_insert(0) += 1)
+        });
+
+        let mut words: Vec<(String, usize)> = counts.into_iter().filter(|(_, count)| *count >= min_count).collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        if let Some(max_size) = max_size {
+            words.truncate(max_size);
+        }
+
+        Vocabulary { indexes: words.into_iter().enumerate().map(|(i, (word, _))| (word, i)).collect() }
+    }
+
+    /// The sensor a word is counted in, None if the word isn't in the vocabulary
+    pub fn index(&self, word: &str) -> Option<usize> {
+        self.indexes.get(word).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.indexes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+
+    /// The words in the vocabulary, in sensor order
+    pub fn words(&self) -> Vec<&str> {
+        let mut words: Vec<(&str, usize)> = self.indexes.iter().map(|(word, i)| (word.as_str(), *i)).collect();
+        words.sort_by_key(|(_, i)| *i);
+        words.into_iter().map(|(word, _)| word).collect()
+    }
+}
+
+/// Turns text into one sensor value per word in its vocabulary.
+/// Words that aren't in the vocabulary are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Vectorizer {
+    vocabulary: Vocabulary,
+    /// The inverse document frequency of every word, only used for TF-IDF
+    idf: Option<Vec<f32>>
+}
+
+impl Vectorizer {
+
+    /// A bag-of-words vectorizer, where each sensor is the number of times its word appears
+    pub fn bag_of_words(vocabulary: Vocabulary) -> Vectorizer {
+        Vectorizer { vocabulary, idf: None }
+    }
+
+    /// Fits a TF-IDF vectorizer, where each sensor is how often its word appears in the text,
+    /// weighted down the more documents the word appears in
+    ///
+    /// ## Params
+    /// - Documents: The text to learn words and document frequencies from
+    /// - Min Count: Words that appear fewer times than this are left out
+    /// - Max Size: The most words the vocabulary will hold, None for no limit
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::text::Vectorizer;
+    ///
+    /// let documents = vec!["the bee is yellow", "the bee buzzes", "the sky is blue"];
+    /// let vectorizer = Vectorizer::fit_tf_idf(&documents, 1, None);
+    /// let values: Vec<f32> = vectorizer.vectorize("a yellow bee");
+    /// assert_eq!(values.len(), vectorizer.vocabulary().len());
+    /// ```
+    pub fn fit_tf_idf(documents: &[&str], min_count: usize, max_size: Option<usize>) -> Vectorizer {
+        let vocabulary: Vocabulary = Vocabulary::build(documents, min_count, max_size);
+        let mut document_frequency: Vec<f32> = vec![0.0; vocabulary.len()];
+        documents.iter().for_each(|document| {
+            let mut seen: Vec<bool> = vec![false; vocabulary.len()];
+            tokenize(document).iter().for_each(|word| {
+                if let Some(i) = vocabulary.index(word) {
+                    seen[i] = true;
+                }
+            });
+            seen.iter().enumerate().for_each(|(i, seen)| if *seen { document_frequency[i] += 1.0 });
+        });
+
+        let total: f32 = documents.len() as f32;
+        let idf: Vec<f32> = document_frequency
+            .into_iter()
+            .map(|frequency| ((1.0 + total) / (1.0 + frequency)).ln() + 1.0)
+            .collect();
+
+        Vectorizer { vocabulary, idf: Some(idf) }
+    }
+
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    /// Turns text into sensor values
+    pub fn vectorize(&self, text: &str) -> Vec<f32> {
+        let tokens: Vec<String> = tokenize(text);
+        let mut values: Vec<f32> = vec![0.0; self.vocabulary.len()];
+        tokens.iter().for_each(|word| {
+            if let Some(i) = self.vocabulary.index(word) {
+                values[i] += 1.0;
+            }
+        });
+
+        if let Some(idf) = &self.idf {
+            let length: f32 = tokens.len().max(1) as f32;
+            values.iter_mut().zip(idf).for_each(|(value, idf)| *value = *value / length * idf);
+        }
+        values
+    }
+
+    /// Turns text into an Input
+    pub fn to_input(&self, text: &str, answer: Option<Types>) -> Input {
+        Input::new(self.vectorize(text), answer)
+    }
+
+    /// Saves the vectorizer as JSON, so text can be vectorized the same way when the model is used later
+    ///
+    /// ## Err
+    /// - ### WriteDataFailed
+    /// The file couldn't be written
+    pub fn write(&self, path: &str) -> Result<(), DarjeelingError> {
+        let json: String = match serde_json::to_string(self) {
+            Ok(json) => json,
+            Err(error) => return Err(DarjeelingError::UnknownError(error.to_string()))
+        };
+        match fs::write(path, json) {
+            Ok(()) => Ok(()),
+            Err(error) => Err(DarjeelingError::WriteDataFailed(format!("{}: {}", path, error)))
+        }
+    }
+
+    /// Reads a vectorizer saved with [`write`](fn@write)
+    ///
+    /// ## Err
+    /// - ### ReadDataFailed
+    /// The file couldn't be read, or didn't contain a vectorizer
+    pub fn read(path: &str) -> Result<Vectorizer, DarjeelingError> {
+        let json: String = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(error) => return Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+        };
+        match serde_json::from_str(&json) {
+            Ok(vectorizer) => Ok(vectorizer),
+            Err(error) => Err(DarjeelingError::ReadDataFailed(format!("{}: {}", path, error)))
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use core::fmt;
+use serde::{Serialize, Deserialize};
+use crate::{cost::CostMatrix, error::DarjeelingError, types::Types};
+
+/// Makes a network answer with one category whenever its probability reaches the threshold, instead of only when its answer node is the brightest.
+/// Below the threshold, the network picks from the other categories as usual. Saved with the model.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecisionThreshold {
+    pub category: Types,
+    /// The smallest probability of the category that makes the network answer with it,
+    /// from the [calibrator](fn@crate::categorize::CatNetwork::set_calibrator) if it spreads probability over every category, and the raw output of the category's answer node if not
+    pub threshold: f32
+}
+
+impl DecisionThreshold {
+
+    pub fn new(category: Types, threshold: f32) -> DecisionThreshold {
+        DecisionThreshold { category, threshold }
+    }
+}
+
+/// What [`optimize_threshold`](fn@crate::categorize::CatNetwork::optimize_threshold) picks the threshold for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ThresholdObjective {
+    /// The best F1 score of the category
+    F1,
+    /// The most recall of the category while its precision stays at least this high
+    Precision(f32),
+    /// The least average cost of every prediction, by answer node
+    Cost(CostMatrix)
+}
+
+/// How the network does on the validation data with the threshold [`optimize_threshold`](fn@crate::categorize::CatNetwork::optimize_threshold) chose
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ThresholdReport {
+    pub threshold: f32,
+    /// The precision, recall and F1 of the category the threshold is for
+    pub precision: f32,
+    pub recall: f32,
+    pub f1: f32,
+    /// The average cost of every prediction, for the cost objective
+    pub cost: Option<f32>
+}
+
+impl fmt::Display for ThresholdReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Threshold {:.4}: precision {:.4}, recall {:.4}, F1 {:.4}", self.threshold, self.precision, self.recall, self.f1)?;
+        if let Some(cost) = self.cost {
+            write!(f, ", average cost {:.4}", cost)?;
+        }
+        Ok(())
+    }
+}
+
+/// Tries every threshold that changes a decision, from answering with the category for nothing to answering with it for everything
+///
+/// ## Params
+/// - Probabilities: The probability of the category for every input
+/// - Others: The answer node every input gets if it isn't the category
+/// - Truth: The correct answer node of every input
+/// - Node: The category's answer node
+///
+/// ## Err
+/// - ### InvalidInput
+/// No threshold reaches the precision, the precision isn't above 0 and at most 1, or the cost matrix doesn't have a row for every answer node
+pub(crate) fn sweep(probabilities: &[f32], others: &[usize], truth: &[usize], node: usize, objective: &ThresholdObjective) -> Result<ThresholdReport, DarjeelingError> {
+    match objective {
+        ThresholdObjective::Precision(precision) if precision.is_nan() || *precision <= 0.0 || *precision > 1.0 => {
+            return Err(DarjeelingError::InvalidInput(format!("The precision has to be above 0 and at most 1, not {}", precision)));
+        },
+        ThresholdObjective::Cost(costs) if truth.iter().chain(others).chain([&node]).any(|i| *i >= costs.size()) => {
+            return Err(DarjeelingError::InvalidInput(format!("The cost matrix is for {} answer nodes, which isn't every one", costs.size())));
+        },
+        _ => {}
+    }
+
+    let mut order: Vec<usize> = (0..probabilities.len()).collect();
+    order.sort_by(|a, b| probabilities[*b].total_cmp(&probabilities[*a]));
+    let actual: usize = truth.iter().filter(|answer| **answer == node).count();
+    let cost_of = |i: usize, chosen: usize| match objective {
+        ThresholdObjective::Cost(costs) => costs.cost(truth[i], chosen),
+        _ => 0.0
+    };
+
+    // Nothing is answered with the category above the largest probability
+    let largest: f32 = probabilities.iter().copied().fold(0.0, f32::max);
+    let (mut true_positives, mut predicted) = (0, 0);
+    let mut total_cost: f32 = (0..probabilities.len()).map(|i| cost_of(i, others[i])).sum();
+    let report = |threshold: f32, true_positives: usize, predicted: usize, total_cost: f32| {
+        let precision: f32 = if predicted == 0 { 0.0 } else { true_positives as f32 / predicted as f32 };
+        let recall: f32 = if actual == 0 { 0.0 } else { true_positives as f32 / actual as f32 };
+        ThresholdReport {
+            threshold,
+            precision,
+            recall,
+            f1: if precision + recall == 0.0 { 0.0 } else { 2.0 * precision * recall / (precision + recall) },
+            cost: match objective {
+                ThresholdObjective::Cost(_) => Some(total_cost / probabilities.len().max(1) as f32),
+                _ => None
+            }
+        }
+    };
+    let better = |candidate: &ThresholdReport, best: &Option<ThresholdReport>| match (objective, best) {
+        (ThresholdObjective::Precision(precision), _) if candidate.precision < *precision => false,
+        (_, None) => true,
+        (ThresholdObjective::F1, Some(best)) => candidate.f1 > best.f1,
+        (ThresholdObjective::Precision(_), Some(best)) => candidate.recall > best.recall,
+        (ThresholdObjective::Cost(_), Some(best)) => candidate.cost < best.cost
+    };
+
+    let mut best: Option<ThresholdReport> = None;
+    let candidate: ThresholdReport = report(largest + 1.0, 0, 0, total_cost);
+    if better(&candidate, &best) {
+        best = Some(candidate);
+    }
+    let mut start: usize = 0;
+    while start < order.len() {
+        // Inputs with the same probability all change together
+        let threshold: f32 = probabilities[order[start]];
+        let end: usize = start + order[start..].iter().take_while(|i| probabilities[**i] == threshold).count();
+        order[start..end].iter().for_each(|i| {
+            predicted += 1;
+            if truth[*i] == node {
+                true_positives += 1;
+            }
+            total_cost += cost_of(*i, node) - cost_of(*i, others[*i]);
+        });
+        let candidate: ThresholdReport = report(threshold, true_positives, predicted, total_cost);
+        if better(&candidate, &best) {
+            best = Some(candidate);
+        }
+        start = end;
+    }
+
+    best.ok_or_else(|| DarjeelingError::InvalidInput("No threshold reaches the precision".to_string()))
+}
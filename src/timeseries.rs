@@ -0,0 +1,58 @@
+use crate::{
+    input::Input,
+    types::Types
+};
+
+/// Turns a time series into overlapping windows, each labeled with the value that comes `horizon` steps after it
+///
+/// ## Params
+/// - Series: The values, in time order
+/// - Window Size: How many values each Input holds
+/// - Horizon: How many steps after the end of the window the label is taken from. 1 is the very next value.
+/// - Step: How far each window moves from the last one
+///
+/// ## Returns
+/// One Input per window, answered with Types::Float
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{timeseries, types::Types};
+///
+/// let windows = timeseries::sliding_windows(&[1.0, 2.0, 3.0, 4.0, 5.0], 2, 1, 1);
+/// assert_eq!(windows.len(), 3);
+/// assert_eq!(windows[0].inputs, vec![1.0, 2.0]);
+/// assert_eq!(windows[0].answer, Some(Types::Float(3.0)));
+/// ```
+pub fn sliding_windows(series: &[f32], window_size: usize, horizon: usize, step: usize) -> Vec<Input> {
+    sliding_windows_with(series, window_size, horizon, step, |_window, target| Types::Float(target))
+}
+
+/// The same as [`sliding_windows`](fn@sliding_windows), but the label is made by a function of the window and the target value,
+/// for example to categorize whether the series goes up or down
+///
+/// ## Examples
+/// ```
+/// use darjeeling::{timeseries, types::Types};
+///
+/// let windows = timeseries::sliding_windows_with(&[1.0, 2.0, 1.5, 3.0], 2, 1, 1, |window, target| {
+///     Types::Boolean(target > window[window.len() - 1])
+/// });
+/// assert_eq!(windows[0].answer, Some(Types::Boolean(false)));
+/// assert_eq!(windows[1].answer, Some(Types::Boolean(true)));
+/// ```
+pub fn sliding_windows_with<F>(series: &[f32], window_size: usize, horizon: usize, step: usize, label: F) -> Vec<Input>
+where
+    F: Fn(&[f32], f32) -> Types {
+    if window_size == 0 || horizon == 0 || step == 0 || series.len() < window_size + horizon {
+        return vec![];
+    }
+
+    (0..=series.len() - window_size - horizon)
+        .step_by(step)
+        .map(|start| {
+            let window: &[f32] = &series[start..start + window_size];
+            let target: f32 = series[start + window_size + horizon - 1];
+            Input::new(window.to_vec(), Some(label(window, target)))
+        })
+        .collect()
+}
@@ -0,0 +1,332 @@
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use crate::{
+    parallel,
+    activation::ActivationFunction,
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    dataset::Dataset,
+    error::DarjeelingError,
+    input::Input,
+    types::Types
+};
+
+/// The hyperparameters a search is allowed to try
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchSpace {
+    /// Grid search tries each of these, random search picks log-uniformly between the smallest and largest
+    pub learning_rates: Vec<f32>,
+    pub hidden_nums: Vec<i32>,
+    pub hidden_layers: Vec<i32>,
+    pub activation_functions: Vec<ActivationFunction>,
+    /// The error percent every trial trains to
+    pub target_err_percent: f32,
+    /// Stops a trial after this many epochs, even if it hasn't reached the target error percent
+    pub max_epochs: usize
+}
+
+impl SearchSpace {
+
+    /// Every combination of the hyperparameters
+    pub fn grid(&self) -> Vec<TrainingConfig> {
+        let mut configs: Vec<TrainingConfig> = vec![];
+        for learning_rate in &self.learning_rates {
+            for hidden_num in &self.hidden_nums {
+                for hidden_layers in &self.hidden_layers {
+                    for activation_function in &self.activation_functions {
+                        configs.push(TrainingConfig::new(*hidden_num, *hidden_layers, *activation_function, *learning_rate, self.target_err_percent));
+                    }
+                }
+            }
+        }
+        configs
+    }
+
+    /// A random combination of the hyperparameters
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> TrainingConfig {
+        let smallest: f32 = self.learning_rates.iter().cloned().fold(f32::MAX, f32::min);
+        let largest: f32 = self.learning_rates.iter().cloned().fold(f32::MIN, f32::max);
+        let learning_rate: f32 = if smallest > 0.0 && largest > smallest {
+            rng.gen_range(smallest.ln()..largest.ln()).exp()
+        } else {
+            smallest
+        };
+
+        TrainingConfig::new(
+            self.hidden_nums[rng.gen_range(0..self.hidden_nums.len())],
+            self.hidden_layers[rng.gen_range(0..self.hidden_layers.len())],
+            self.activation_functions[rng.gen_range(0..self.activation_functions.len())],
+            learning_rate,
+            self.target_err_percent
+        )
+    }
+
+    fn is_empty(&self) -> bool {
+        self.learning_rates.is_empty() || self.hidden_nums.is_empty() || self.hidden_layers.is_empty() || self.activation_functions.is_empty()
+    }
+}
+
+/// A configuration that was tried, and its accuracy on the validation data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trial {
+    pub config: TrainingConfig,
+    pub score: f32
+}
+
+/// The best configuration found, with the model it trained
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub best: TrainingConfig,
+    pub score: f32,
+    pub model: CatNetwork,
+    /// Every trial, in the order they were generated
+    pub trials: Vec<Trial>
+}
+
+/// Trains a model with every combination of hyperparameters, keeping the one with the best validation accuracy
+///
+/// ## Params
+/// - Space: The hyperparameters to try
+/// - Train: The data each model is trained on
+/// - Validation: The data each model is scored on
+/// - Parallel: Whether to train the trials at the same time, which needs the `parallel` feature
+///
+/// ## Err
+/// - ### InvalidTrainingData
+/// The search space or the data is empty
+/// - Any error from [`step_epoch`](fn@crate::categorize::CatNetwork::step_epoch)
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::{tuning::{self, SearchSpace}, activation::ActivationFunction};
+///
+/// let space = SearchSpace {
+///     learning_rates: vec![0.1, 0.5, 1.0],
+///     hidden_nums: vec![2, 4],
+///     hidden_layers: vec![1],
+///     activation_functions: vec![ActivationFunction::Sigmoid],
+///     target_err_percent: 95.0,
+///     max_epochs: 500
+/// };
+/// let result = tuning::grid_search(&space, &train, &validation, true).unwrap();
+/// println!("Best: {:?} with accuracy {}", result.best, result.score);
+/// ```
+pub fn grid_search(space: &SearchSpace, train: &[Input], validation: &[Input], parallel: bool) -> Result<SearchResult, DarjeelingError> {
+    if space.is_empty() {
+        return Err(DarjeelingError::InvalidTrainingData("The search space is empty".to_string()));
+    }
+    run_trials(space.grid(), train, validation, space.max_epochs, parallel)
+}
+
+/// Trains a model with a number of random hyperparameter combinations, keeping the one with the best validation accuracy
+///
+/// ## Params
+/// - Space: The hyperparameters to pick from
+/// - Train: The data each model is trained on
+/// - Validation: The data each model is scored on
+/// - Trials: How many combinations to try
+/// - Seed: Decides which combinations are tried
+/// - Parallel: Whether to train the trials at the same time, which needs the `parallel` feature
+///
+/// ## Err
+/// The same as [`grid_search`](fn@grid_search)
+pub fn random_search(space: &SearchSpace, train: &[Input], validation: &[Input], trials: usize, seed: u64, parallel: bool) -> Result<SearchResult, DarjeelingError> {
+    if space.is_empty() {
+        return Err(DarjeelingError::InvalidTrainingData("The search space is empty".to_string()));
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    run_trials((0..trials).map(|_| space.sample(&mut rng)).collect(), train, validation, space.max_epochs, parallel)
+}
+
+/// The losses recorded while sweeping the learning rate, and the one suggested to start training with
+#[derive(Debug, Clone, PartialEq)]
+pub struct LrFinderResult {
+    /// The learning rate of every step, growing exponentially
+    pub learning_rates: Vec<f32>,
+    /// The smoothed loss after every step, in the same order
+    pub losses: Vec<f32>,
+    /// A tenth of the learning rate with the lowest loss, where the loss is still falling quickly
+    pub suggested: f32
+}
+
+/// Finds a good starting learning rate, instead of guessing.
+/// A copy of the network is trained on one input at a time while the learning rate grows exponentially from the smallest to the largest,
+/// and the loss is recorded at each step. The sweep stops early once the loss blows up.
+/// The network passed in isn't changed.
+///
+/// ## Params
+/// - Net: The untrained network to probe
+/// - Data: The training data. Inputs without answers are skipped.
+/// - Categories: The categories the network is trained on
+/// - Min Learning Rate: Where the sweep starts, like 0.0001
+/// - Max Learning Rate: Where the sweep ends, like 10.0
+/// - Steps: How many inputs the sweep trains on, a few hundred is usually enough
+/// - Seed: Decides the order inputs are trained on
+///
+/// ## Err
+/// - ### InvalidTrainingData
+/// There's no data with answers, or the learning rates or steps can't make a sweep
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::tuning;
+///
+/// let found = tuning::lr_find(&net, &data, categories.clone(), 0.0001, 10.0, 300, 42).unwrap();
+/// net.learn(&mut data, categories, found.suggested, "found", 99.0, true).unwrap();
+/// ```
+pub fn lr_find(net: &CatNetwork, data: &[Input], categories: Vec<Types>, min_learning_rate: f32, max_learning_rate: f32, steps: usize, seed: u64) -> Result<LrFinderResult, DarjeelingError> {
+    let data: Vec<&Input> = data.iter().filter(|input| input.answer.is_some()).collect();
+    if data.is_empty() || steps < 2 || min_learning_rate <= 0.0 || max_learning_rate <= min_learning_rate {
+        return Err(DarjeelingError::InvalidTrainingData(format!(
+            "Can't sweep from {} to {} over {} steps of {} inputs", min_learning_rate, max_learning_rate, steps, data.len()
+        )));
+    }
+
+    let mut net: CatNetwork = net.clone();
+    net.start_training(categories);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut order: Vec<usize> = vec![];
+    let growth: f32 = (max_learning_rate / min_learning_rate).powf(1.0 / (steps - 1) as f32);
+
+    let mut learning_rates: Vec<f32> = vec![];
+    let mut losses: Vec<f32> = vec![];
+    let mut average: f32 = 0.0;
+    let mut best: f32 = f32::INFINITY;
+    for step in 0..steps {
+        if order.is_empty() {
+            order = (0..data.len()).collect();
+            order.shuffle(&mut rng);
+        }
+        let learning_rate: f32 = min_learning_rate * growth.powi(step as i32);
+        let loss: f32 = net.train_step(data[order.pop().unwrap_or_default()], learning_rate);
+
+        // Smooths the loss, correcting for the average starting at 0
+        average = 0.98 * average + 0.02 * loss;
+        let smoothed: f32 = average / (1.0 - 0.98f32.powi(step as i32 + 1));
+        if !smoothed.is_finite() || (step > 0 && smoothed > 4.0 * best) {
+            break;
+        }
+        best = best.min(smoothed);
+        learning_rates.push(learning_rate);
+        losses.push(smoothed);
+    }
+
+    let lowest: usize = losses.iter().enumerate().min_by(|(_, a), (_, b)| a.total_cmp(b)).map(|(i, _)| i).unwrap_or_default();
+    Ok(LrFinderResult { suggested: learning_rates.get(lowest).copied().unwrap_or(min_learning_rate) / 10.0, learning_rates, losses })
+}
+
+/// How a model trained from one seed did on the validation data
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeedRun {
+    pub seed: u64,
+    pub score: f32,
+    pub epochs: usize
+}
+
+/// The best of several models trained the same way from different seeds, and how much the seed mattered
+#[derive(Debug, Clone)]
+pub struct MultiSeedResult {
+    pub best_seed: u64,
+    pub score: f32,
+    pub model: CatNetwork,
+    /// Every run, in the order of the seeds
+    pub runs: Vec<SeedRun>,
+    pub mean_score: f32,
+    /// The standard deviation of the scores, which is how noisy a single run is
+    pub std_score: f32
+}
+
+/// Trains a model from each seed with the same config, keeping the one with the best validation accuracy.
+/// The seed decides both the starting weights and the order the data is trained in, so the same seed always trains the same model.
+/// Small networks can score very differently from one seed to the next, so the spread of the scores shows how much one run can be trusted.
+///
+/// ## Params
+/// - Config: How every network is built and trained
+/// - Seeds: One model is trained from each
+/// - Train: The data each model is trained on
+/// - Validation: The data each model is scored on
+/// - Max Epochs: Stops a run after this many epochs, even if it hasn't reached the config's target error percent
+/// - Parallel: Whether to train the runs at the same time, which needs the `parallel` feature
+///
+/// ## Err
+/// - ### InvalidTrainingData
+/// There are no seeds, or the training or validation data is empty
+/// - The rest are the same as [`step_epoch`](fn@crate::categorize::CatNetwork::step_epoch)
+///
+/// ## Examples
+/// ```ignore
+/// let result = tuning::learn_multi_seed(&config, &[1, 2, 3, 4, 5], &train, &validation, 500, true).unwrap();
+/// println!("Seed {} scored {}, with {} +/- {} across seeds", result.best_seed, result.score, result.mean_score, result.std_score);
+/// ```
+pub fn learn_multi_seed(config: &TrainingConfig, seeds: &[u64], train: &[Input], validation: &[Input], max_epochs: usize, parallel: bool) -> Result<MultiSeedResult, DarjeelingError> {
+    if train.is_empty() || validation.is_empty() || seeds.is_empty() {
+        return Err(DarjeelingError::InvalidTrainingData("Training from several seeds needs training data, validation data and at least one seed".to_string()));
+    }
+    let categories: Vec<Types> = Dataset::new(train.to_vec()).categories();
+
+    let results: Vec<Result<(SeedRun, CatNetwork), DarjeelingError>> = parallel::map(seeds, parallel, |seed| run_seed(config, *seed, train, validation, &categories, max_epochs));
+
+    let mut runs: Vec<SeedRun> = vec![];
+    let mut best: Option<(SeedRun, CatNetwork)> = None;
+    for result in results {
+        let (run, model) = result?;
+        runs.push(run.clone());
+        if best.as_ref().map_or(true, |(best_run, _)| run.score > best_run.score) {
+            best = Some((run, model));
+        }
+    }
+
+    let scores: Vec<f32> = runs.iter().map(|run| run.score).collect();
+    let (mean_score, std_score) = crate::validation::mean_std(&scores);
+    let (best, model) = best.expect("There's at least one seed");
+    Ok(MultiSeedResult { best_seed: best.seed, score: best.score, model, runs, mean_score, std_score })
+}
+
+fn run_seed(config: &TrainingConfig, seed: u64, train: &[Input], validation: &[Input], categories: &[Types], max_epochs: usize) -> Result<(SeedRun, CatNetwork), DarjeelingError> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut net: CatNetwork = CatNetwork::from_config(train[0].inputs.len() as i32, categories.len() as i32, config);
+    net.randomize_weights(&mut rng);
+    net.start_training(categories.to_vec());
+    let mut data: Vec<Input> = train.to_vec();
+    let mut epochs: usize = 0;
+    while epochs < max_epochs {
+        epochs += 1;
+        if net.step_epoch_with_rng(&mut data, config.learning_rate, &mut rng)?.accuracy * 100.0 >= config.target_err_percent {
+            break;
+        }
+    }
+
+    let score: f32 = net.evaluate(validation).metrics.accuracy;
+    Ok((SeedRun { seed, score, epochs }, net))
+}
+
+fn run_trials(configs: Vec<TrainingConfig>, train: &[Input], validation: &[Input], max_epochs: usize, parallel: bool) -> Result<SearchResult, DarjeelingError> {
+    if train.is_empty() || validation.is_empty() || configs.is_empty() {
+        return Err(DarjeelingError::InvalidTrainingData("Searching needs training data, validation data and at least one trial".to_string()));
+    }
+    let categories: Vec<Types> = Dataset::new(train.to_vec()).categories();
+
+    let results: Vec<Result<(Trial, CatNetwork), DarjeelingError>> = parallel::map(&configs, parallel, |config| run_trial(config.clone(), train, validation, &categories, max_epochs));
+
+    let mut trials: Vec<Trial> = vec![];
+    let mut best: Option<(Trial, CatNetwork)> = None;
+    for result in results {
+        let (trial, model) = result?;
+        trials.push(trial.clone());
+        if best.as_ref().map_or(true, |(best_trial, _)| trial.score > best_trial.score) {
+            best = Some((trial, model));
+        }
+    }
+
+    match best {
+        Some((trial, model)) => Ok(SearchResult { best: trial.config, score: trial.score, model, trials }),
+        None => Err(DarjeelingError::InvalidTrainingData("No trials were run".to_string()))
+    }
+}
+
+fn run_trial(config: TrainingConfig, train: &[Input], validation: &[Input], categories: &[Types], max_epochs: usize) -> Result<(Trial, CatNetwork), DarjeelingError> {
+    let mut net: CatNetwork = CatNetwork::from_config(train[0].inputs.len() as i32, categories.len() as i32, &config);
+    net.learn_epochs(&mut train.to_vec(), categories.to_vec(), &config, max_epochs)?;
+
+    let score: f32 = net.evaluate(validation).metrics.accuracy;
+    Ok((Trial { config, score }, net))
+}
@@ -1,11 +1,27 @@
+use core::{cmp::Ordering, fmt};
 use serde::{Serialize, Deserialize};
 
+/// How far apart a float answer and a float category can be for the answer to still belong to the category
+pub const FLOAT_EPSILON: f32 = 1e-5;
+
+/// In JSON, a value is an object with the variant's name as its only key.
+/// Model files and other services rely on this, so variants are never renamed.
+/// - `{"String": "setosa"}`
+/// - `{"Integer": 3}`
+/// - `{"Float": 0.5}`
+/// - `{"Boolean": true}`
+/// - `{"Vector": [0.0, 1.0]}`
+/// - `{"Category": {"index": 0, "name": "setosa"}}`
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Types {
     String(String),
     Integer(i32),
     Float(f32),
     Boolean(bool),
+    /// A target value for every answer node, for networks with more than one output
+    Vector(Vec<f32>),
+    /// A named category, along with the index of the answer node it belongs to
+    Category { index: usize, name: String },
 }
 
 impl Types {
@@ -16,9 +32,92 @@ impl Types {
             Types::Integer(int) => print!("{}", int),
             Types::Float(float) => print!("{}", float),
             Types::Boolean(boolean) => print!("{}", boolean),
+            Types::Vector(vector) => print!("{:?}", vector),
+            Types::Category { name, .. } => print!("{}", name),
         };
 
     }
+
+    /// The value as a number, if it can be fed to a sensor directly.
+    /// Booleans are 1.0 for true and 0.0 for false, categories are their index, strings and vectors have no single numeric value.
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            Types::String(_) => None,
+            Types::Vector(_) => None,
+            Types::Category { index, .. } => Some(*index as f32),
+            Types::Integer(int) => Some(*int as f32),
+            Types::Float(float) => Some(*float),
+            Types::Boolean(boolean) => Some(if *boolean { 1.0 } else { 0.0 }),
+        }
+    }
+
+    /// Whether two values are the same, allowing floats to be up to epsilon apart, along with every element of two vectors.
+    /// Other values have to be equal. Networks match float answers to their categories this way with [`FLOAT_EPSILON`],
+    /// so an answer of `0.1 + 0.2` still belongs to the category `0.3`.
+    ///
+    /// ## Examples
+    /// ```
+    /// use darjeeling::types::Types;
+    ///
+    /// assert!(Types::Float(0.1 + 0.2).approx_eq(&Types::Float(0.3), 1e-5));
+    /// assert!(!Types::Float(0.5).approx_eq(&Types::Float(0.6), 1e-5));
+    /// assert!(!Types::Float(1.0).approx_eq(&Types::Integer(1), 1e-5));
+    /// ```
+    pub fn approx_eq(&self, other: &Types, epsilon: f32) -> bool {
+        match (self, other) {
+            (Types::Float(a), Types::Float(b)) => (a - b).abs() <= epsilon,
+            (Types::Vector(a), Types::Vector(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() <= epsilon),
+            _ => self == other
+        }
+    }
+
+    /// Whether an answer belongs to this category, with floats compared to within [`FLOAT_EPSILON`]
+    pub(crate) fn matches(&self, answer: &Types) -> bool {
+        self.approx_eq(answer, FLOAT_EPSILON)
+    }
+
+    /// Creates a named category for the answer node at the index
+    pub fn category(index: usize, name: &str) -> Types {
+        Types::Category { index, name: name.to_string() }
+    }
+
+    /// A total order over every value, for sorting answers the same way no matter what order they came in.
+    /// Values of different variants are ordered by variant, in the order they're declared, and floats use [`f32::total_cmp`].
+    pub fn total_cmp(&self, other: &Types) -> Ordering {
+        match (self, other) {
+            (Types::String(a), Types::String(b)) => a.cmp(b),
+            (Types::Integer(a), Types::Integer(b)) => a.cmp(b),
+            (Types::Float(a), Types::Float(b)) => a.total_cmp(b),
+            (Types::Boolean(a), Types::Boolean(b)) => a.cmp(b),
+            (Types::Vector(a), Types::Vector(b)) => a.iter().zip(b).map(|(a, b)| a.total_cmp(b)).find(|ordering| ordering.is_ne()).unwrap_or(a.len().cmp(&b.len())),
+            (Types::Category { index: a, name: a_name }, Types::Category { index: b, name: b_name }) => a.cmp(b).then_with(|| a_name.cmp(b_name)),
+            _ => self.rank().cmp(&other.rank())
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Types::String(_) => 0,
+            Types::Integer(_) => 1,
+            Types::Float(_) => 2,
+            Types::Boolean(_) => 3,
+            Types::Vector(_) => 4,
+            Types::Category { .. } => 5,
+        }
+    }
+}
+
+impl fmt::Display for Types {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Types::String(string) => write!(f, "{}", string),
+            Types::Integer(int) => write!(f, "{}", int),
+            Types::Float(float) => write!(f, "{}", float),
+            Types::Boolean(boolean) => write!(f, "{}", boolean),
+            Types::Vector(vector) => write!(f, "{:?}", vector),
+            Types::Category { name, .. } => write!(f, "{}", name),
+        }
+    }
 }
 
 impl Default for Types {
@@ -127,4 +226,9 @@ pub fn fmt_bool_type_vec(list: Vec<bool>) -> Vec<Types> {
     }
 
     fmted_list
-}
\ No newline at end of file
+}
+
+/// Formats a list of category names as categories, indexed in the order they're given
+pub fn fmt_category_type_vec(list: Vec<&str>) -> Vec<Types> {
+    list.iter().enumerate().map(|(index, name)| Types::category(index, name)).collect()
+}
@@ -0,0 +1,229 @@
+use std::ops::Range;
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Serialize, Deserialize};
+use crate::{
+    categorize::CatNetwork,
+    config::TrainingConfig,
+    dataset::Dataset,
+    error::DarjeelingError,
+    input::Input,
+    metrics::ClassificationMetrics,
+    types::Types
+};
+
+/// How a model trained on one fold did on the data that was held out from it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FoldReport {
+    pub fold: usize,
+    pub train_size: usize,
+    /// The validation inputs that had an answer to score against
+    pub validation_size: usize,
+    /// The training accuracy percentage of the last epoch
+    pub training_err_percent: f32,
+    pub metrics: ClassificationMetrics
+}
+
+/// The results of every fold, with the mean and standard deviation of their scores
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrossValidationReport {
+    pub folds: Vec<FoldReport>,
+    pub mean_accuracy: f32,
+    pub std_accuracy: f32,
+    pub mean_macro_f1: f32,
+    pub std_macro_f1: f32
+}
+
+impl CrossValidationReport {
+
+    fn of(folds: Vec<FoldReport>) -> CrossValidationReport {
+        let accuracies: Vec<f32> = folds.iter().map(|fold| fold.metrics.accuracy).collect();
+        let f1s: Vec<f32> = folds.iter().map(|fold| fold.metrics.macro_f1).collect();
+        let (mean_accuracy, std_accuracy) = mean_std(&accuracies);
+        let (mean_macro_f1, std_macro_f1) = mean_std(&f1s);
+        CrossValidationReport { folds, mean_accuracy, std_accuracy, mean_macro_f1, std_macro_f1 }
+    }
+}
+
+/// Trains k categorization models, each one validated on a different 1/k of the data and trained on the rest
+///
+/// ## Params
+/// - Config: How each network is built and trained
+/// - Data: Every input. Inputs without an answer are left out.
+/// - K: The number of folds
+/// - Seed: Decides which inputs go in which fold, so the same seed always gives the same folds
+/// - Max Epochs: Stops training a fold after this many epochs, even if it hasn't reached the config's target error percent
+///
+/// ## Returns
+/// A report for every fold, and the mean and standard deviation of their accuracy and macro F1
+///
+/// ## Err
+/// - ### InvalidTrainingData
+/// There are fewer inputs with answers than folds, or fewer than 2 folds
+/// - Any error from [`step_epoch`](fn@crate::categorize::CatNetwork::step_epoch)
+///
+/// ## Examples
+/// ```ignore
+/// use darjeeling::{validation, config::TrainingConfig, activation::ActivationFunction};
+///
+/// let config = TrainingConfig::new(4, 1, ActivationFunction::Sigmoid, 0.5, 90.0);
+/// let report = validation::cross_validate(&config, &data, 5, 42, 500).unwrap();
+/// println!("Accuracy: {} +/- {}", report.mean_accuracy, report.std_accuracy);
+/// ```
+pub fn cross_validate(config: &TrainingConfig, data: &[Input], k: usize, seed: u64, max_epochs: usize) -> Result<CrossValidationReport, DarjeelingError> {
+    let data: Vec<Input> = data.iter().filter(|input| input.answer.is_some()).cloned().collect();
+    if k < 2 || data.len() < k {
+        return Err(DarjeelingError::InvalidTrainingData(format!("Can't split {} inputs into {} folds", data.len(), k)));
+    }
+
+    let categories: Vec<Types> = Dataset::new(data.clone()).categories();
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    order.shuffle(&mut StdRng::seed_from_u64(seed));
+
+    let mut folds: Vec<FoldReport> = vec![];
+    for fold in 0..k {
+        let mut train: Vec<Input> = vec![];
+        let mut validation: Vec<Input> = vec![];
+        order.iter().enumerate().for_each(|(position, i)| {
+            if position % k == fold {
+                validation.push(data[*i].clone());
+            } else {
+                train.push(data[*i].clone());
+            }
+        });
+
+        folds.push(train_fold(config, &categories, fold, train, &validation, max_epochs)?);
+    }
+
+    Ok(CrossValidationReport::of(folds))
+}
+
+/// The train and validation ranges of every rolling origin fold, for data in time order.
+/// The first fold trains on the first `initial` inputs and validates on the `horizon` after them,
+/// then the origin moves forward by `step` and the next fold trains on everything before it, until the horizon runs past the end.
+/// Validation always comes after training, so no fold learns from the future it's validated on.
+///
+/// ## Examples
+/// ```
+/// use darjeeling::validation;
+///
+/// let splits = validation::rolling_origin_splits(10, 4, 2, 2);
+/// assert_eq!(splits, vec![(0..4, 4..6), (0..6, 6..8), (0..8, 8..10)]);
+/// ```
+pub fn rolling_origin_splits(len: usize, initial: usize, horizon: usize, step: usize) -> Vec<(Range<usize>, Range<usize>)> {
+    if initial == 0 || horizon == 0 || step == 0 {
+        return vec![];
+    }
+    (initial..).step_by(step)
+        .take_while(|origin| origin + horizon <= len)
+        .map(|origin| (0..origin, origin..origin + horizon))
+        .collect()
+}
+
+/// [`cross_validate`], except with [rolling origin](fn@rolling_origin_splits) folds instead of random ones,
+/// for data in time order where a random split would let models train on the future
+///
+/// ## Params
+/// - Config: How each network is built and trained
+/// - Data: Every input in time order, all of which need an answer
+/// - Initial: How many inputs the first fold trains on
+/// - Horizon: How many inputs after the origin each fold is validated on
+/// - Step: How far the origin moves forward between folds
+/// - Max Epochs: Stops training a fold after this many epochs, even if it hasn't reached the config's target error percent
+///
+/// ## Returns
+/// A report for every fold, in time order, and the mean and standard deviation of their accuracy and macro F1
+///
+/// ## Err
+/// - ### InvalidTrainingData
+/// Initial, horizon or step is 0, there aren't enough inputs for one fold, or an input doesn't have an answer
+/// - Any error from [`step_epoch`](fn@crate::categorize::CatNetwork::step_epoch)
+///
+/// ## Examples
+/// ```ignore
+/// // Train on the first 100 days, forecast the next 7, then move forward a week at a time
+/// let report = validation::rolling_origin_validate(&config, &days, 100, 7, 7, 500).unwrap();
+/// ```
+pub fn rolling_origin_validate(config: &TrainingConfig, data: &[Input], initial: usize, horizon: usize, step: usize, max_epochs: usize) -> Result<CrossValidationReport, DarjeelingError> {
+    // Leaving unanswered inputs out would move the folds' boundaries in time, so they aren't allowed
+    if let Some(position) = data.iter().position(|input| input.answer.is_none()) {
+        return Err(DarjeelingError::InvalidTrainingData(format!("Input {} doesn't have an answer", position)));
+    }
+    let splits: Vec<(Range<usize>, Range<usize>)> = rolling_origin_splits(data.len(), initial, horizon, step);
+    if splits.is_empty() {
+        return Err(DarjeelingError::InvalidTrainingData(format!(
+            "Can't split {} inputs into rolling origin folds starting with {}, validated on {} and moving {} at a time", data.len(), initial, horizon, step
+        )));
+    }
+
+    let categories: Vec<Types> = Dataset::new(data.to_vec()).categories();
+    let mut folds: Vec<FoldReport> = vec![];
+    for (fold, (train, validation)) in splits.into_iter().enumerate() {
+        folds.push(train_fold(config, &categories, fold, data[train].to_vec(), &data[validation], max_epochs)?);
+    }
+
+    Ok(CrossValidationReport::of(folds))
+}
+
+/// Trains a network on one fold's training data and scores it on the validation data
+fn train_fold(config: &TrainingConfig, categories: &[Types], fold: usize, mut train: Vec<Input>, validation: &[Input], max_epochs: usize) -> Result<FoldReport, DarjeelingError> {
+    let mut net: CatNetwork = CatNetwork::from_config(train[0].inputs.len() as i32, categories.len() as i32, config);
+    net.learn_epochs(&mut train, categories.to_vec(), config, max_epochs)?;
+
+    // Inputs without an answer can't be scored, so evaluating leaves them out
+    let report = net.evaluate(validation);
+    Ok(FoldReport {
+        fold,
+        train_size: train.len(),
+        validation_size: report.count,
+        training_err_percent: net.history().last().map(|record| record.accuracy * 100.0).unwrap_or(0.0),
+        metrics: report.metrics
+    })
+}
+
+/// Holds some of the training data back, and stops training once the loss on it stops improving.
+/// The held back data is [split off](fn@Dataset::stratified_split) so every category keeps its share.
+///
+/// ## Examples
+/// ```ignore
+/// let early_stopping = EarlyStopping::new(0.2, 10, 42);
+/// let report = net.learn_with_early_stopping(&dataset, vec![], 0.5, 1000, &early_stopping).unwrap();
+/// println!("Best validation loss {} at epoch {}", report.best_loss, report.best_epoch);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct EarlyStopping {
+    /// How much of each category is held back for validation
+    pub validation_fraction: f32,
+    /// How many epochs in a row the validation loss can go without improving before training stops
+    pub patience: usize,
+    /// Decides which inputs are held back
+    pub seed: u64
+}
+
+impl EarlyStopping {
+
+    pub fn new(validation_fraction: f32, patience: usize, seed: u64) -> EarlyStopping {
+        EarlyStopping { validation_fraction, patience, seed }
+    }
+}
+
+/// How training with [early stopping](EarlyStopping) went
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EarlyStoppingReport {
+    /// The epoch with the lowest validation loss, which the network's weights are restored to
+    pub best_epoch: usize,
+    pub best_loss: f32,
+    /// How many epochs were trained, including the ones after the best
+    pub epochs: usize,
+    pub train_size: usize,
+    pub validation_size: usize
+}
+
+/// The mean and population standard deviation of a list of values
+pub(crate) fn mean_std(values: &[f32]) -> (f32, f32) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let mean: f32 = values.iter().sum::<f32>() / values.len() as f32;
+    let variance: f32 = values.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / values.len() as f32;
+    (mean, variance.sqrt())
+}
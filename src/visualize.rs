@@ -0,0 +1,110 @@
+//! Pictures of a network's architecture and weights, for looking over small networks by eye or putting them in reports.
+//! Positive weights are red and negative ones blue, and the larger a weight is next to the largest in the network, the bolder it's drawn.
+
+use crate::{activation::ActivationFunction, node::Node};
+
+const POSITIVE: &str = "#d62728";
+const NEGATIVE: &str = "#1f77b4";
+
+/// From no weight to the largest in the network
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+const ANSI_POSITIVE: &str = "\x1b[31m";
+const ANSI_NEGATIVE: &str = "\x1b[34m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// A Graphviz digraph of every node and link
+///
+/// ## Params
+/// - Layers: Every layer, starting with the inputs
+/// - Inputs: The name of every input, empty to number them
+/// - Outputs: The name of every answer node, empty to number them
+pub(crate) fn dot(layers: &[Vec<Node>], inputs: &[String], outputs: &[String]) -> String {
+    let largest: f32 = largest_weight(layers);
+    let mut dot: String = "digraph network {\n    rankdir=LR;\n    splines=line;\n    node [shape=circle, fontsize=10];\n".to_string();
+    for (layer_i, layer) in layers.iter().enumerate() {
+        dot.push_str(&format!("    subgraph cluster_{} {{\n        label=\"{}\";\n        color=lightgrey;\n", layer_i, layer_name(layer_i, layers.len())));
+        for node_i in 0..layer.len() {
+            dot.push_str(&format!("        n{}_{} [label=\"{}\"];\n", layer_i, node_i, escape(&node_label(layer_i, node_i, layers.len(), inputs, outputs))));
+        }
+        dot.push_str("    }\n");
+    }
+    for (layer_i, layer) in layers.iter().enumerate().skip(1) {
+        for (node_i, node) in layer.iter().enumerate() {
+            for (link, weight) in node.link_weights.iter().enumerate() {
+                let strength: f32 = if largest > 0.0 { weight.abs() / largest } else { 0.0 };
+                dot.push_str(&format!(
+                    "    n{}_{} -> n{}_{} [color=\"{}{:02x}\", penwidth={:.2}, tooltip=\"{:.4}\"];\n",
+                    layer_i - 1, link, layer_i, node_i,
+                    if *weight < 0.0 { NEGATIVE } else { POSITIVE }, (40.0 + 215.0 * strength) as u8, 0.5 + 3.5 * strength, weight
+                ));
+            }
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// A compact text picture of every layer, with a row for each node shading the size of each incoming weight and then the bias
+///
+/// ## Params
+/// - Layers: Every layer, starting with the inputs
+/// - Inputs: The name of every input, listed under the sizes if there are any
+/// - Outputs: The name of every answer node, empty to number them
+/// - Activation Function: The network's activation function
+/// - Color: Whether to color the shades by sign with ANSI escape codes, for terminals
+pub(crate) fn diagram(layers: &[Vec<Node>], inputs: &[String], outputs: &[String], activation_function: &ActivationFunction, color: bool) -> String {
+    let largest: f32 = largest_weight(layers);
+    let sizes: Vec<String> = layers.iter().map(|layer| layer.len().to_string()).collect();
+    let mut diagram: String = format!("{} ({}), largest weight {:.4}\n", sizes.join(" -> "), activation_function, largest);
+    if !inputs.is_empty() {
+        diagram.push_str(&format!("inputs: {}\n", inputs.join(", ")));
+    }
+    let width: usize = layers.iter().enumerate().skip(1)
+        .flat_map(|(layer_i, layer)| (0..layer.len()).map(move |node_i| node_label(layer_i, node_i, layers.len(), inputs, outputs).chars().count()))
+        .max()
+        .unwrap_or(0);
+    for (layer_i, layer) in layers.iter().enumerate().skip(1) {
+        diagram.push_str(&format!("{}, {} nodes\n", layer_name(layer_i, layers.len()), layer.len()));
+        for (node_i, node) in layer.iter().enumerate() {
+            let weights: String = node.link_weights.iter().map(|weight| shade(*weight, largest, color)).collect();
+            let bias: String = node.b_weight.map(|bias| shade(bias, largest, color)).unwrap_or_default();
+            diagram.push_str(&format!("  {:<width$} |{}| {}\n", node_label(layer_i, node_i, layers.len(), inputs, outputs), weights, bias, width = width));
+        }
+    }
+    diagram
+}
+
+fn largest_weight(layers: &[Vec<Node>]) -> f32 {
+    layers.iter().skip(1).flatten().flat_map(|node| node.link_weights.iter().chain(node.b_weight.iter())).fold(0.0, |largest, weight| largest.max(weight.abs()))
+}
+
+fn layer_name(layer_i: usize, layers: usize) -> String {
+    match layer_i {
+        0 => "inputs".to_string(),
+        _ if layer_i == layers - 1 => "answers".to_string(),
+        _ => format!("hidden {}", layer_i)
+    }
+}
+
+fn node_label(layer_i: usize, node_i: usize, layers: usize, inputs: &[String], outputs: &[String]) -> String {
+    match layer_i {
+        0 => inputs.get(node_i).cloned().unwrap_or_else(|| format!("x{}", node_i)),
+        _ if layer_i == layers - 1 => outputs.get(node_i).cloned().unwrap_or_else(|| format!("y{}", node_i)),
+        _ => format!("h{}.{}", layer_i, node_i)
+    }
+}
+
+fn shade(weight: f32, largest: f32, color: bool) -> String {
+    let strength: f32 = if largest > 0.0 { weight.abs() / largest } else { 0.0 };
+    let shade: char = SHADES[((strength * (SHADES.len() - 1) as f32).round() as usize).min(SHADES.len() - 1)];
+    match color {
+        true => format!("{}{}{}", if weight < 0.0 { ANSI_NEGATIVE } else { ANSI_POSITIVE }, shade, ANSI_RESET),
+        false => shade.to_string()
+    }
+}
+
+/// Makes a label safe to put between quotes in DOT
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}